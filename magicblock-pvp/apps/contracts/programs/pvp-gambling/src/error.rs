@@ -61,4 +61,34 @@ pub enum PvpGamblingError {
     
     #[msg("Priority fee calculation failed")]
     PriorityFeeCalculationFailed,
-}
\ No newline at end of file
+    
+    #[msg("Relayer is not allowed to draw from this sponsor vault")]
+    RelayerNotAllowed,
+    
+    #[msg("Sponsor vault has insufficient funds")]
+    InsufficientSponsorFunds,
+    
+    #[msg("Too many sponsor relayers")]
+    TooManyRelayers,
+    
+    #[msg("Escrow is locked by an in-flight settlement")]
+    ReentrancyDetected,
+    
+    #[msg("Match account is not the one linked to this escrow")]
+    LinkedMatchMismatch,
+    
+    #[msg("Linked match has not completed with a winner")]
+    LinkedMatchNotCompleted,
+    
+    #[msg("Linked match has already completed")]
+    LinkedMatchAlreadyCompleted,
+    
+    #[msg("Linked match is not played by exactly the escrow's two players")]
+    LinkedMatchPlayersMismatch,
+    
+    #[msg("Only the program's upgrade authority can update its info")]
+    InvalidUpgradeAuthority,
+    
+    #[msg("Program info describes a newer deployment than this one")]
+    StaleProgramInfo,
+}
@@ -75,6 +75,10 @@ pub fn handler(ctx: Context<AbortGame>) -> Result<()> {
     let player2_state = &ctx.accounts.player2_state;
     let clock = Clock::get()?;
     
+    // Flush the lock to account data so a nested call sees it
+    game_escrow.lock()?;
+    game_escrow.exit(&crate::ID)?;
+    
     // Validate player accounts
     require!(
         ctx.accounts.player1_account.key() == game_escrow.player1,
@@ -184,6 +188,7 @@ pub fn handler(ctx: Context<AbortGame>) -> Result<()> {
     // Update remaining balance
     let total_refunded = Utils::safe_add(final_player1_refund, final_player2_refund)?;
     game_escrow.total_amount = Utils::safe_sub(game_escrow.total_amount, total_refunded)?;
+    game_escrow.unlock();
     
     msg!(
         "Game {} aborted! Player1 refund: {}, Player2 refund: {}", 
@@ -16,7 +16,8 @@ pub struct DepositCap<'info> {
     #[account(
         mut,
         constraint = game_escrow.game_state == GameState::WaitingForDeposits @ PvpGamblingError::InvalidStateTransition,
-        constraint = game_escrow.player1 == player.key() || game_escrow.player2 == player.key() @ PvpGamblingError::InvalidPlayer
+        constraint = game_escrow.player1 == player.key() || game_escrow.player2 == player.key() @ PvpGamblingError::InvalidPlayer,
+        constraint = !game_escrow.locked @ PvpGamblingError::ReentrancyDetected
     )]
     pub game_escrow: Account<'info, GameEscrow>,
     
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+
+use crate::error::PvpGamblingError;
+use crate::state::{FeeSponsorVault, GameEscrow, GameState, SponsoredGame};
+use crate::utils::Utils;
+
+/// Relayer draws fees it paid for a gasless game from the sponsor vault
+#[derive(Accounts)]
+pub struct DrawSponsoredFees<'info> {
+    /// Relayer that paid the game's transaction fees
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    
+    /// Fee sponsor vault paying the relayer
+    #[account(
+        mut,
+        seeds = [
+            b"fee_sponsor",
+            fee_sponsor.authority.as_ref(),
+        ],
+        bump = fee_sponsor.bump,
+        constraint = fee_sponsor.is_relayer(&relayer.key()) @ PvpGamblingError::RelayerNotAllowed
+    )]
+    pub fee_sponsor: Account<'info, FeeSponsorVault>,
+    
+    /// Game the fees were paid for
+    #[account(
+        mut,
+        constraint = game_escrow.gasless_mode @ PvpGamblingError::GaslessModeDisabled,
+        constraint = game_escrow.game_state != GameState::Settled @ PvpGamblingError::GameAlreadySettled,
+        constraint = game_escrow.game_state != GameState::Aborted @ PvpGamblingError::GameAborted,
+    )]
+    pub game_escrow: Account<'info, GameEscrow>,
+    
+    /// Per-game sponsorship record (PDA)
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = SponsoredGame::LEN,
+        seeds = [
+            b"sponsored_game",
+            game_escrow.key().as_ref(),
+        ],
+        bump
+    )]
+    pub sponsored_game: Account<'info, SponsoredGame>,
+    
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<DrawSponsoredFees>, amount: u64) -> Result<()> {
+    let fee_sponsor = &mut ctx.accounts.fee_sponsor;
+    let sponsored_game = &mut ctx.accounts.sponsored_game;
+    let game_escrow = &mut ctx.accounts.game_escrow;
+    
+    // A game is tied to the first vault that sponsors it
+    if sponsored_game.vault == Pubkey::default() {
+        sponsored_game.vault = fee_sponsor.key();
+        sponsored_game.game_escrow = game_escrow.key();
+        sponsored_game.bump = ctx.bumps.sponsored_game;
+        fee_sponsor.games_sponsored = Utils::safe_add(fee_sponsor.games_sponsored, 1)?;
+        game_escrow.sponsored = true;
+    }
+    require!(
+        sponsored_game.vault == fee_sponsor.key(),
+        PvpGamblingError::Unauthorized
+    );
+    
+    let drawn = Utils::safe_add(sponsored_game.drawn, amount)?;
+    require!(
+        drawn <= fee_sponsor.game_cap(game_escrow),
+        PvpGamblingError::CostCapExceeded
+    );
+    
+    let vault_info = fee_sponsor.to_account_info();
+    let rent_floor = Rent::get()?.minimum_balance(vault_info.data_len());
+    require!(
+        vault_info.lamports().saturating_sub(rent_floor) >= amount,
+        PvpGamblingError::InsufficientSponsorFunds
+    );
+    
+    // The vault is owned by this program, so lamports move directly
+    **vault_info.try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.relayer.to_account_info().try_borrow_mut_lamports()? += amount;
+    
+    sponsored_game.drawn = drawn;
+    fee_sponsor.total_drawn = Utils::safe_add(fee_sponsor.total_drawn, amount)?;
+    
+    msg!(
+        "Relayer {} drew {} lamports for game {} ({} of {} cap)",
+        ctx.accounts.relayer.key(),
+        amount,
+        game_escrow.game_id,
+        drawn,
+        fee_sponsor.game_cap(game_escrow)
+    );
+    
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::state::FeeSponsorVault;
+
+/// Top up a fee sponsor vault. Anyone may fund it.
+#[derive(Accounts)]
+pub struct FundFeeSponsor<'info> {
+    /// Account providing the lamports
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    
+    /// Fee sponsor vault receiving the funds
+    #[account(
+        mut,
+        seeds = [
+            b"fee_sponsor",
+            fee_sponsor.authority.as_ref(),
+        ],
+        bump = fee_sponsor.bump
+    )]
+    pub fee_sponsor: Account<'info, FeeSponsorVault>,
+    
+    /// System program for transferring SOL
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<FundFeeSponsor>, amount: u64) -> Result<()> {
+    let transfer_instruction = system_program::Transfer {
+        from: ctx.accounts.funder.to_account_info(),
+        to: ctx.accounts.fee_sponsor.to_account_info(),
+    };
+    
+    let cpi_context = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        transfer_instruction,
+    );
+    
+    system_program::transfer(cpi_context, amount)?;
+    
+    msg!(
+        "Fee sponsor {} funded with {} lamports",
+        ctx.accounts.fee_sponsor.key(),
+        amount
+    );
+    
+    Ok(())
+}
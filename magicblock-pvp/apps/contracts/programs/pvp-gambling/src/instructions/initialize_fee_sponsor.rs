@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+use crate::error::PvpGamblingError;
+use crate::state::{FeeSponsorVault, MAX_SPONSOR_RELAYERS};
+use crate::utils::GameConstants;
+
+/// Create a fee sponsor vault that relayers draw from for gasless games
+#[derive(Accounts)]
+pub struct InitializeFeeSponsor<'info> {
+    /// Sponsor funding the vault
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// Fee sponsor vault (PDA)
+    #[account(
+        init,
+        payer = authority,
+        space = FeeSponsorVault::LEN,
+        seeds = [
+            b"fee_sponsor",
+            authority.key().as_ref(),
+        ],
+        bump
+    )]
+    pub fee_sponsor: Account<'info, FeeSponsorVault>,
+    
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<InitializeFeeSponsor>,
+    per_game_cap: u64,
+    relayers: Vec<Pubkey>,
+    reimburse_from_loser: bool,
+) -> Result<()> {
+    let fee_sponsor = &mut ctx.accounts.fee_sponsor;
+    
+    require!(
+        per_game_cap > 0 && per_game_cap <= GameConstants::MAX_GASLESS_COST_CAP,
+        PvpGamblingError::InvalidCostCap
+    );
+    require!(
+        relayers.len() <= MAX_SPONSOR_RELAYERS,
+        PvpGamblingError::TooManyRelayers
+    );
+    
+    fee_sponsor.authority = ctx.accounts.authority.key();
+    fee_sponsor.relayers = relayers;
+    fee_sponsor.per_game_cap = per_game_cap;
+    fee_sponsor.reimburse_from_loser = reimburse_from_loser;
+    fee_sponsor.total_drawn = 0;
+    fee_sponsor.total_reimbursed = 0;
+    fee_sponsor.games_sponsored = 0;
+    fee_sponsor.bump = ctx.bumps.fee_sponsor;
+    fee_sponsor.reserved = [0; 32];
+    
+    msg!(
+        "Fee sponsor initialized: Authority={}, PerGameCap={}, Relayers={}, ReimburseFromLoser={}",
+        fee_sponsor.authority,
+        per_game_cap,
+        fee_sponsor.relayers.len(),
+        reimburse_from_loser
+    );
+    
+    Ok(())
+}
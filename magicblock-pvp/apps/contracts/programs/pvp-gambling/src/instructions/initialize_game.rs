@@ -66,6 +66,10 @@ pub struct InitializeGame<'info> {
     )]
     pub player2_state: Account<'info, PlayerState>,
     
+    /// game-program match named by `linked_match`; required when one is linked
+    /// CHECK: Owner, layout, state and participants are checked in the handler
+    pub linked_match_account: Option<UncheckedAccount<'info>>,
+    
     /// System program for account creation
     pub system_program: Program<'info, System>,
     
@@ -81,6 +85,7 @@ pub fn handler(
     player2: Pubkey,
     gasless_mode: bool,
     max_cost_cap: Option<u64>,
+    linked_match: Option<Pubkey>,
 ) -> Result<()> {
     let game_escrow = &mut ctx.accounts.game_escrow;
     let player1_state = &mut ctx.accounts.player1_state;
@@ -123,7 +128,18 @@ pub fn handler(
     game_escrow.max_cost_cap = max_cost_cap;
     game_escrow.accumulated_costs = initialization_cost;
     game_escrow.signature_count = 1;
-    game_escrow.reserved = [0; 64];
+    game_escrow.locked = false;
+    game_escrow.linked_match = linked_match;
+    game_escrow.sponsored = false;
+    game_escrow.reserved = [0; 29];
+    
+    // A linked match must still be undecided and seat exactly these players
+    if linked_match.is_some() {
+        let match_account = ctx.accounts.linked_match_account
+            .as_ref()
+            .ok_or(PvpGamblingError::LinkedMatchMismatch)?;
+        game_escrow.check_linked_match(match_account)?;
+    }
     
     // Initialize player 1 state
     player1_state.player = player1;
@@ -152,12 +168,13 @@ pub fn handler(
     }
     
     msg!(
-        "Game initialized: ID={}, Player1={}, Player2={}, Bet={}, GaslessMode={}", 
+        "Game initialized: ID={}, Player1={}, Player2={}, Bet={}, GaslessMode={}, LinkedMatch={:?}", 
         game_id, 
         player1, 
         player2, 
         bet_amount,
-        gasless_mode
+        gasless_mode,
+        linked_match
     );
     
     Ok(())
@@ -2,8 +2,18 @@ pub mod initialize_game;
 pub mod deposit_cap;
 pub mod settle_game;
 pub mod abort_game;
+pub mod initialize_fee_sponsor;
+pub mod fund_fee_sponsor;
+pub mod update_fee_sponsor;
+pub mod draw_sponsored_fees;
+pub mod update_program_info;
 
 pub use initialize_game::*;
 pub use deposit_cap::*;
 pub use settle_game::*;
-pub use abort_game::*;
\ No newline at end of file
+pub use abort_game::*;
+pub use initialize_fee_sponsor::*;
+pub use fund_fee_sponsor::*;
+pub use update_fee_sponsor::*;
+pub use draw_sponsored_fees::*;
+pub use update_program_info::*;
\ No newline at end of file
@@ -2,11 +2,12 @@ use anchor_lang::prelude::*;
 use anchor_lang::system_program;
 
 use crate::error::PvpGamblingError;
-use crate::state::{GameEscrow, GameState, PlayerState, CostModel};
+use crate::state::{GameEscrow, GameState, PlayerState, CostModel, FeeSponsorVault, SponsoredGame};
 use crate::vrf::EcVrf;
 use crate::utils::Utils;
 
-/// Settle the game using VRF-based winner selection
+/// Settle the game using VRF-based winner selection, or from the recorded
+/// winner of a linked game-program match for skill-game wagers
 #[derive(Accounts)]
 pub struct SettleGame<'info> {
     /// Authority that can settle the game (could be either player or designated settler)
@@ -68,6 +69,25 @@ pub struct SettleGame<'info> {
     )]
     pub vrf_authority: UncheckedAccount<'info>,
     
+    /// Fee sponsor vault, required once a relayer has drawn fees for this game
+    #[account(mut)]
+    pub fee_sponsor: Option<Account<'info, FeeSponsorVault>>,
+    
+    /// Sponsorship record for this game, required alongside the vault
+    #[account(
+        mut,
+        seeds = [
+            b"sponsored_game",
+            game_escrow.key().as_ref(),
+        ],
+        bump = sponsored_game.bump
+    )]
+    pub sponsored_game: Option<Account<'info, SponsoredGame>>,
+    
+    /// game-program match, required when the escrow is linked to one
+    /// CHECK: Key, owner and layout are verified against the escrow
+    pub linked_match: Option<UncheckedAccount<'info>>,
+    
     /// System program for SOL transfers
     pub system_program: Program<'info, System>,
 }
@@ -82,53 +102,66 @@ pub fn handler(
     let player2_state = &mut ctx.accounts.player2_state;
     let clock = Clock::get()?;
     
+    // Flush the lock to account data so a nested call sees it
+    game_escrow.lock()?;
+    game_escrow.exit(&crate::ID)?;
+    
     // Validate that both players have deposited
     require!(
         game_escrow.both_players_ready(),
         PvpGamblingError::PlayersNotReady
     );
     
-    // Validate alpha string matches game parameters
-    let expected_alpha = EcVrf::generate_alpha_string(
-        game_escrow.game_id,
-        &game_escrow.player1,
-        &game_escrow.player2,
-        game_escrow.bet_amount,
-        game_escrow.created_at,
-    );
-    
-    require!(
-        alpha_string == expected_alpha,
-        PvpGamblingError::InvalidVrfProof
-    );
-    
-    // Get VRF public key from authority account
-    // In a real implementation, this would be stored in the VRF authority account
-    // For now, we'll use a placeholder - in production, derive from authority PDA data
-    let vrf_public_key = [0u8; 32]; // Placeholder - should be loaded from vrf_authority account data
-    
-    // Verify VRF proof and extract randomness
-    let (vrf_output, is_valid) = EcVrf::verify_and_extract(
-        &vrf_public_key,
-        &vrf_proof,
-        &alpha_string,
-    )?;
-    
-    require!(is_valid, PvpGamblingError::VrfVerificationFailed);
-    
-    // Determine winner using proportional selection
-    // For equal bets, this is essentially a coin flip
-    // For unequal bets, this would be proportional (though both players bet the same in this implementation)
-    let player1_wins = EcVrf::select_winner(
-        &vrf_output,
-        player1_state.deposited_amount,
-        player2_state.deposited_amount,
-    )?;
-    
-    let winner_pubkey = if player1_wins {
-        game_escrow.player1
+    // A linked match decides the outcome by play; otherwise the VRF draws it
+    let (winner_pubkey, vrf_output) = if game_escrow.linked_match.is_some() {
+        let linked_match = ctx.accounts.linked_match
+            .as_ref()
+            .ok_or(PvpGamblingError::LinkedMatchMismatch)?;
+        (game_escrow.linked_match_winner(linked_match)?, None)
     } else {
-        game_escrow.player2
+        // Validate alpha string matches game parameters
+        let expected_alpha = EcVrf::generate_alpha_string(
+            game_escrow.game_id,
+            &game_escrow.player1,
+            &game_escrow.player2,
+            game_escrow.bet_amount,
+            game_escrow.created_at,
+        );
+        
+        require!(
+            alpha_string == expected_alpha,
+            PvpGamblingError::InvalidVrfProof
+        );
+        
+        // Get VRF public key from authority account
+        // In a real implementation, this would be stored in the VRF authority account
+        // For now, we'll use a placeholder - in production, derive from authority PDA data
+        let vrf_public_key = [0u8; 32]; // Placeholder - should be loaded from vrf_authority account data
+        
+        // Verify VRF proof and extract randomness
+        let (vrf_output, is_valid) = EcVrf::verify_and_extract(
+            &vrf_public_key,
+            &vrf_proof,
+            &alpha_string,
+        )?;
+        
+        require!(is_valid, PvpGamblingError::VrfVerificationFailed);
+        
+        // Determine winner using proportional selection
+        // For equal bets, this is essentially a coin flip
+        // For unequal bets, this would be proportional (though both players bet the same in this implementation)
+        let player1_wins = EcVrf::select_winner(
+            &vrf_output,
+            player1_state.deposited_amount,
+            player2_state.deposited_amount,
+        )?;
+        
+        let winner_pubkey = if player1_wins {
+            game_escrow.player1
+        } else {
+            game_escrow.player2
+        };
+        (winner_pubkey, Some(vrf_output))
     };
     
     // Validate winner account matches the selected winner
@@ -151,12 +184,47 @@ pub fn handler(
         }
     }
     
-    // Calculate payout (deduct costs if gasless mode)
-    let payout_amount = Utils::calculate_payout(
-        game_escrow.total_amount,
-        Utils::safe_add(game_escrow.accumulated_costs, settlement_cost)?,
-        game_escrow.gasless_mode,
-    )?;
+    // Sponsored fees are capped per game and, if the sponsor opted in, recovered
+    // from the pot up to the loser's stake before the winner is paid
+    let mut sponsor_reimbursement = 0;
+    let sponsored = match (&mut ctx.accounts.fee_sponsor, &mut ctx.accounts.sponsored_game) {
+        (Some(fee_sponsor), Some(sponsored_game)) => {
+            require!(
+                sponsored_game.vault == fee_sponsor.key(),
+                PvpGamblingError::Unauthorized
+            );
+            require!(
+                sponsored_game.drawn <= fee_sponsor.game_cap(game_escrow),
+                PvpGamblingError::CostCapExceeded
+            );
+            
+            if fee_sponsor.reimburse_from_loser {
+                let outstanding = Utils::safe_sub(sponsored_game.drawn, sponsored_game.reimbursed)?;
+                sponsor_reimbursement = outstanding.min(game_escrow.bet_amount);
+                
+                // The escrow is owned by this program, so lamports move directly
+                **game_escrow.to_account_info().try_borrow_mut_lamports()? -= sponsor_reimbursement;
+                **fee_sponsor.to_account_info().try_borrow_mut_lamports()? += sponsor_reimbursement;
+                
+                sponsored_game.reimbursed = Utils::safe_add(sponsored_game.reimbursed, sponsor_reimbursement)?;
+                fee_sponsor.total_reimbursed = Utils::safe_add(fee_sponsor.total_reimbursed, sponsor_reimbursement)?;
+            }
+            true
+        },
+        (None, None) if !game_escrow.sponsored => false,
+        _ => return Err(PvpGamblingError::InvalidAccountData.into()),
+    };
+    
+    // Calculate payout (deduct costs if gasless mode; a sponsor already paid them)
+    let payout_amount = if sponsored {
+        Utils::safe_sub(game_escrow.total_amount, sponsor_reimbursement)?
+    } else {
+        Utils::calculate_payout(
+            game_escrow.total_amount,
+            Utils::safe_add(game_escrow.accumulated_costs, settlement_cost)?,
+            game_escrow.gasless_mode,
+        )?
+    };
     
     // Transfer winnings to winner using game authority PDA
     let authority_seeds = &[
@@ -187,7 +255,11 @@ pub fn handler(
     game_escrow.increment_signatures(1)?;
     
     // Update remaining balance (should be close to 0 or equal to costs in gasless mode)
-    game_escrow.total_amount = Utils::safe_sub(game_escrow.total_amount, payout_amount)?;
+    game_escrow.total_amount = Utils::safe_sub(
+        game_escrow.total_amount,
+        Utils::safe_add(payout_amount, sponsor_reimbursement)?,
+    )?;
+    game_escrow.unlock();
     
     // Update player activity
     player1_state.update_activity();
@@ -202,13 +274,22 @@ pub fn handler(
         }
     }
     
-    msg!(
-        "Game {} settled! Winner: {}, Payout: {} lamports, VRF Output: {:?}",
-        game_escrow.game_id,
-        winner_pubkey,
-        payout_amount,
-        &vrf_output[..8] // Log first 8 bytes of VRF output for verification
-    );
+    match vrf_output {
+        Some(vrf_output) => msg!(
+            "Game {} settled! Winner: {}, Payout: {} lamports, VRF Output: {:?}",
+            game_escrow.game_id,
+            winner_pubkey,
+            payout_amount,
+            &vrf_output[..8] // Log first 8 bytes of VRF output for verification
+        ),
+        None => msg!(
+            "Game {} settled by match {:?}! Winner: {}, Payout: {} lamports",
+            game_escrow.game_id,
+            game_escrow.linked_match,
+            winner_pubkey,
+            payout_amount
+        ),
+    }
     
     Ok(())
 }
\ No newline at end of file
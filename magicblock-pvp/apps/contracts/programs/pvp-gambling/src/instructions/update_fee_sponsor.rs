@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::error::PvpGamblingError;
+use crate::state::{FeeSponsorVault, MAX_SPONSOR_RELAYERS};
+use crate::utils::GameConstants;
+
+/// Change a fee sponsor vault's cap, relayers or reimbursement setting
+#[derive(Accounts)]
+pub struct UpdateFeeSponsor<'info> {
+    /// Sponsor that owns the vault
+    pub authority: Signer<'info>,
+    
+    /// Fee sponsor vault to update
+    #[account(
+        mut,
+        seeds = [
+            b"fee_sponsor",
+            authority.key().as_ref(),
+        ],
+        bump = fee_sponsor.bump,
+        constraint = fee_sponsor.authority == authority.key() @ PvpGamblingError::Unauthorized
+    )]
+    pub fee_sponsor: Account<'info, FeeSponsorVault>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateFeeSponsor>,
+    per_game_cap: u64,
+    relayers: Vec<Pubkey>,
+    reimburse_from_loser: bool,
+) -> Result<()> {
+    let fee_sponsor = &mut ctx.accounts.fee_sponsor;
+    
+    require!(
+        per_game_cap > 0 && per_game_cap <= GameConstants::MAX_GASLESS_COST_CAP,
+        PvpGamblingError::InvalidCostCap
+    );
+    require!(
+        relayers.len() <= MAX_SPONSOR_RELAYERS,
+        PvpGamblingError::TooManyRelayers
+    );
+    
+    fee_sponsor.per_game_cap = per_game_cap;
+    fee_sponsor.relayers = relayers;
+    fee_sponsor.reimburse_from_loser = reimburse_from_loser;
+    
+    msg!(
+        "Fee sponsor {} updated: PerGameCap={}, Relayers={}, ReimburseFromLoser={}",
+        fee_sponsor.key(),
+        per_game_cap,
+        fee_sponsor.relayers.len(),
+        reimburse_from_loser
+    );
+    
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+use crate::error::PvpGamblingError;
+use crate::state::ProgramInfo;
+
+/// Record the deployed version, commit and IDL hash after an upgrade
+#[derive(Accounts)]
+pub struct UpdateProgramInfo<'info> {
+    /// Program upgrade authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// This program, to find its program data account
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::PvpGambling>,
+    
+    /// Program data holding the upgrade authority and deployment slot
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(authority.key()) @ PvpGamblingError::InvalidUpgradeAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    
+    /// Build record, created on first use
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = ProgramInfo::LEN,
+        seeds = [b"program_info"],
+        bump
+    )]
+    pub program_info: Account<'info, ProgramInfo>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateProgramInfo>,
+    version: [u16; 3],
+    git_commit: [u8; 20],
+    idl_hash: [u8; 32],
+) -> Result<()> {
+    let deployed_slot = ctx.accounts.program_data.slot;
+    let info = &mut ctx.accounts.program_info;
+    
+    // A stale upgrade must not overwrite the record of a newer one
+    require!(
+        deployed_slot >= info.deployed_slot,
+        PvpGamblingError::StaleProgramInfo
+    );
+    
+    info.program_id = crate::ID;
+    info.version = version;
+    info.git_commit = git_commit;
+    info.idl_hash = idl_hash;
+    info.deployed_slot = deployed_slot;
+    info.updated_by = ctx.accounts.authority.key();
+    info.updated_at = Clock::get()?.unix_timestamp;
+    info.bump = ctx.bumps.program_info;
+    
+    msg!(
+        "Program info updated: v{}.{}.{}, DeployedSlot={}",
+        version[0],
+        version[1],
+        version[2],
+        deployed_slot
+    );
+    
+    Ok(())
+}
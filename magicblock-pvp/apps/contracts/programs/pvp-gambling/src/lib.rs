@@ -27,6 +27,7 @@ pub mod pvp_gambling {
         player2: Pubkey,
         gasless_mode: bool,
         max_cost_cap: Option<u64>,
+        linked_match: Option<Pubkey>,
     ) -> Result<()> {
         instructions::initialize_game::handler(
             ctx,
@@ -36,6 +37,7 @@ pub mod pvp_gambling {
             player2,
             gasless_mode,
             max_cost_cap,
+            linked_match,
         )
     }
 
@@ -47,7 +49,7 @@ pub mod pvp_gambling {
         instructions::deposit_cap::handler(ctx, amount)
     }
 
-    /// Settle game using VRF-based winner selection
+    /// Settle game using VRF-based winner selection, or the linked match's winner
     pub fn settle_game(
         ctx: Context<SettleGame>,
         vrf_proof: [u8; 80],
@@ -56,10 +58,56 @@ pub mod pvp_gambling {
         instructions::settle_game::handler(ctx, vrf_proof, alpha_string)
     }
 
+    /// Create a fee sponsor vault for gasless games
+    pub fn initialize_fee_sponsor(
+        ctx: Context<InitializeFeeSponsor>,
+        per_game_cap: u64,
+        relayers: Vec<Pubkey>,
+        reimburse_from_loser: bool,
+    ) -> Result<()> {
+        instructions::initialize_fee_sponsor::handler(ctx, per_game_cap, relayers, reimburse_from_loser)
+    }
+
+    /// Add funds to a fee sponsor vault
+    pub fn fund_fee_sponsor(
+        ctx: Context<FundFeeSponsor>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::fund_fee_sponsor::handler(ctx, amount)
+    }
+
+    /// Update a fee sponsor vault's cap, relayers and reimbursement setting
+    pub fn update_fee_sponsor(
+        ctx: Context<UpdateFeeSponsor>,
+        per_game_cap: u64,
+        relayers: Vec<Pubkey>,
+        reimburse_from_loser: bool,
+    ) -> Result<()> {
+        instructions::update_fee_sponsor::handler(ctx, per_game_cap, relayers, reimburse_from_loser)
+    }
+
+    /// Reimburse a relayer for fees paid on a gasless game, within the per-game cap
+    pub fn draw_sponsored_fees(
+        ctx: Context<DrawSponsoredFees>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::draw_sponsored_fees::handler(ctx, amount)
+    }
+
     /// Abort game and return funds if conditions are met
     pub fn abort_game(ctx: Context<AbortGame>) -> Result<()> {
         instructions::abort_game::handler(ctx)
     }
+
+    /// Record the deployed version, commit and IDL hash - upgrade authority only
+    pub fn update_program_info(
+        ctx: Context<UpdateProgramInfo>,
+        version: [u16; 3],
+        git_commit: [u8; 20],
+        idl_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::update_program_info::handler(ctx, version, git_commit, idl_hash)
+    }
 }
 
 /// Security advisory
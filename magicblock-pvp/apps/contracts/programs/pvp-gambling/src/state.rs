@@ -10,6 +10,41 @@ pub enum GameState {
     Aborted,
 }
 
+/// game-program `Match` layout, mirrored from its published schema
+pub mod linked_match {
+    use anchor_lang::prelude::*;
+    
+    pub const GAME_PROGRAM_ID: Pubkey = pubkey!("GAMExxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx");
+    pub const STATE_OFFSET: usize = 40;
+    pub const WINNER_OFFSET: usize = 41;
+    /// `GameState::Completed` discriminant
+    pub const STATE_COMPLETED: u8 = 2;
+    /// Borsh size of one `MatchPlayer`; its key leads the entry
+    pub const MATCH_PLAYER_LEN: usize = 93;
+    
+    /// Keys seated in the match. Walks past the variable-length winner and
+    /// config fields; None if the data ends early.
+    pub fn participants(data: &[u8]) -> Option<Vec<Pubkey>> {
+        let read_len = |offset: usize| -> Option<usize> {
+            Some(u32::from_le_bytes(data.get(offset..offset + 4)?.try_into().ok()?) as usize)
+        };
+        
+        let mut offset = WINNER_OFFSET + if *data.get(WINNER_OFFSET)? == 1 { 33 } else { 1 };
+        offset += 8; // match_id
+        offset += 1 + 8 + 8 + 8; // max_players, entry_fee, turn_timeout, match_duration
+        offset += 4 + read_len(offset)? + 1; // reward_distribution, queue_type
+        let count = read_len(offset)?;
+        offset += 4;
+        
+        (0..count)
+            .map(|index| {
+                let start = offset + index * MATCH_PLAYER_LEN;
+                Pubkey::try_from(data.get(start..start + 32)?).ok()
+            })
+            .collect()
+    }
+}
+
 /// Game escrow account storing all game state and funds
 #[account]
 pub struct GameEscrow {
@@ -43,8 +78,14 @@ pub struct GameEscrow {
     pub accumulated_costs: u64,
     /// Number of signatures used
     pub signature_count: u32,
+    /// Set while a settlement or abort is moving escrowed funds
+    pub locked: bool,
+    /// game-program match whose recorded winner settles this escrow instead of VRF
+    pub linked_match: Option<Pubkey>,
+    /// Set once a fee sponsor has paid for this game; settlement then requires the sponsor accounts
+    pub sponsored: bool,
     /// Reserved space for future upgrades
-    pub reserved: [u8; 64],
+    pub reserved: [u8; 29],
 }
 
 impl GameEscrow {
@@ -69,7 +110,10 @@ impl GameEscrow {
         1 + 8 + // max_cost_cap (Option<u64>)
         8 +     // accumulated_costs
         4 +     // signature_count
-        64      // reserved
+        1 +     // locked
+        1 + 32 +// linked_match (Option<Pubkey>)
+        1 +     // sponsored
+        29      // reserved
     }
     
     /// Expected rent-exempt amount: 1,447,680 lamports
@@ -87,6 +131,94 @@ impl GameEscrow {
         }
     }
     
+    /// Take the payout lock, rejecting nested or concurrent settlement
+    pub fn lock(&mut self) -> Result<()> {
+        require!(!self.locked, crate::error::PvpGamblingError::ReentrancyDetected);
+        self.locked = true;
+        Ok(())
+    }
+    
+    /// Release the payout lock once funds have moved
+    pub fn unlock(&mut self) {
+        self.locked = false;
+    }
+    
+    /// Borrow the linked game-program match after checking it is the linked
+    /// account, owned by game-program and laid out as a `Match`. The layout pins
+    /// state and winner at fixed offsets (see the game-program schema registry),
+    /// so the account is read directly rather than deserialized.
+    fn linked_match_data<'a, 'info>(
+        &self,
+        match_account: &'a AccountInfo<'info>,
+    ) -> Result<std::cell::Ref<'a, &'info mut [u8]>> {
+        use crate::error::PvpGamblingError;
+        use linked_match::*;
+        
+        require!(
+            self.linked_match == Some(match_account.key()),
+            PvpGamblingError::LinkedMatchMismatch
+        );
+        require!(
+            *match_account.owner == GAME_PROGRAM_ID,
+            PvpGamblingError::LinkedMatchMismatch
+        );
+        
+        let data = match_account.try_borrow_data()?;
+        require!(
+            data.len() >= WINNER_OFFSET + 33
+                && data[..8] == anchor_lang::solana_program::hash::hash(b"account:Match").to_bytes()[..8],
+            PvpGamblingError::InvalidAccountData
+        );
+        Ok(data)
+    }
+    
+    /// The linked match must seat exactly this escrow's two players
+    fn require_linked_participants(&self, data: &[u8]) -> Result<()> {
+        let participants = linked_match::participants(data)
+            .ok_or(crate::error::PvpGamblingError::InvalidAccountData)?;
+        require!(
+            participants.len() == 2
+                && participants.contains(&self.player1)
+                && participants.contains(&self.player2),
+            crate::error::PvpGamblingError::LinkedMatchPlayersMismatch
+        );
+        Ok(())
+    }
+    
+    /// Check a match before escrowing against it: still undecided, and played
+    /// by exactly this escrow's players, so nobody can link a result they
+    /// already know
+    pub fn check_linked_match(&self, match_account: &AccountInfo) -> Result<()> {
+        let data = self.linked_match_data(match_account)?;
+        require!(
+            data[linked_match::STATE_OFFSET] < linked_match::STATE_COMPLETED,
+            crate::error::PvpGamblingError::LinkedMatchAlreadyCompleted
+        );
+        self.require_linked_participants(&data)
+    }
+    
+    /// Read the winner a linked game-program match recorded
+    pub fn linked_match_winner(&self, match_account: &AccountInfo) -> Result<Pubkey> {
+        use crate::error::PvpGamblingError;
+        use linked_match::*;
+        
+        let data = self.linked_match_data(match_account)?;
+        self.require_linked_participants(&data)?;
+        require!(
+            data[STATE_OFFSET] == STATE_COMPLETED,
+            PvpGamblingError::LinkedMatchNotCompleted
+        );
+        require!(data[WINNER_OFFSET] == 1, PvpGamblingError::LinkedMatchNotCompleted);
+        
+        let winner = Pubkey::try_from(&data[WINNER_OFFSET + 1..WINNER_OFFSET + 33])
+            .map_err(|_| PvpGamblingError::InvalidAccountData)?;
+        require!(
+            winner == self.player1 || winner == self.player2,
+            PvpGamblingError::InvalidPlayer
+        );
+        Ok(winner)
+    }
+    
     /// Check if both players have deposited
     pub fn both_players_ready(&self) -> bool {
         self.total_amount >= self.bet_amount.checked_mul(2).unwrap_or(0)
@@ -171,6 +303,122 @@ impl PlayerState {
     }
 }
 
+/// Maximum relayers allowed to draw from one fee sponsor vault
+pub const MAX_SPONSOR_RELAYERS: usize = 8;
+
+/// Vault that pays transaction fees for gasless games on the players' behalf
+#[account]
+pub struct FeeSponsorVault {
+    /// Sponsor that funds and configures the vault
+    pub authority: Pubkey,
+    /// Relayers allowed to draw fees
+    pub relayers: Vec<Pubkey>,
+    /// Maximum fees sponsored for a single game
+    pub per_game_cap: u64,
+    /// Recover sponsored fees from the loser's stake at settlement
+    pub reimburse_from_loser: bool,
+    /// Lifetime fees drawn by relayers
+    pub total_drawn: u64,
+    /// Lifetime fees recovered at settlement
+    pub total_reimbursed: u64,
+    /// Number of games that drew from the vault
+    pub games_sponsored: u64,
+    /// PDA bump seed
+    pub bump: u8,
+    /// Reserved space for future upgrades
+    pub reserved: [u8; 32],
+}
+
+impl FeeSponsorVault {
+    /// Space calculation for rent exemption
+    /// 8 (discriminator) + fee_sponsor_vault_size()
+    pub const LEN: usize = 8 + Self::fee_sponsor_vault_size();
+
+    /// Calculate exact size needed for FeeSponsorVault
+    const fn fee_sponsor_vault_size() -> usize {
+        32 +    // authority
+        4 + 32 * MAX_SPONSOR_RELAYERS + // relayers
+        8 +     // per_game_cap
+        1 +     // reimburse_from_loser
+        8 +     // total_drawn
+        8 +     // total_reimbursed
+        8 +     // games_sponsored
+        1 +     // bump
+        32      // reserved
+    }
+
+    /// Check whether a relayer may draw fees
+    pub fn is_relayer(&self, relayer: &Pubkey) -> bool {
+        self.relayers.contains(relayer)
+    }
+
+    /// Cap for a game: the vault's per-game cap, tightened by the game's own cap
+    pub fn game_cap(&self, game_escrow: &GameEscrow) -> u64 {
+        game_escrow.max_cost_cap.map_or(self.per_game_cap, |cap| cap.min(self.per_game_cap))
+    }
+}
+
+/// Fees a sponsor vault has paid for one game
+#[account]
+pub struct SponsoredGame {
+    /// Vault the fees were drawn from
+    pub vault: Pubkey,
+    /// Game the fees were paid for
+    pub game_escrow: Pubkey,
+    /// Fees drawn so far
+    pub drawn: u64,
+    /// Fees recovered at settlement
+    pub reimbursed: u64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl SponsoredGame {
+    /// Space calculation for rent exemption
+    pub const LEN: usize = 8 + // discriminator
+        32 +    // vault
+        32 +    // game_escrow
+        8 +     // drawn
+        8 +     // reimbursed
+        1;      // bump
+}
+
+/// Self-reported build of the deployed program, laid out like the game
+/// programs' record so one client check covers every deployment
+#[account]
+#[derive(Default)]
+pub struct ProgramInfo {
+    /// Program this record describes
+    pub program_id: Pubkey,
+    /// Semantic version: major, minor, patch
+    pub version: [u16; 3],
+    /// Commit the deployment was built from
+    pub git_commit: [u8; 20],
+    /// Hash of the published IDL
+    pub idl_hash: [u8; 32],
+    /// Slot of the upgrade this record describes
+    pub deployed_slot: u64,
+    /// Upgrade authority that wrote the record
+    pub updated_by: Pubkey,
+    /// Unix timestamp of the last update
+    pub updated_at: i64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl ProgramInfo {
+    /// Space calculation for rent exemption
+    pub const LEN: usize = 8 + // discriminator
+        32 +    // program_id
+        2 * 3 + // version
+        20 +    // git_commit
+        32 +    // idl_hash
+        8 +     // deployed_slot
+        32 +    // updated_by
+        8 +     // updated_at
+        1;      // bump
+}
+
 /// Cost tracking constants based on Solana fee structure
 pub struct CostModel;
 
@@ -209,4 +457,28 @@ impl CostModel {
     pub fn validate_cost_range(cost: u64) -> bool {
         cost >= Self::TARGET_MIN_COST && cost <= Self::TARGET_MAX_COST
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_linked_match_participants() {
+        let (one, two) = (Pubkey::new_unique(), Pubkey::new_unique());
+        
+        // Discriminator, creator, state, no winner, match_id, fixed config fields
+        let mut data = vec![0u8; 8 + 32 + 1 + 1 + 8 + 25];
+        data.extend_from_slice(&3u32.to_le_bytes());
+        data.extend_from_slice(&[50, 30, 20, 0]); // reward_distribution, queue_type
+        data.extend_from_slice(&2u32.to_le_bytes());
+        for player in [one, two] {
+            let mut entry = [0u8; linked_match::MATCH_PLAYER_LEN];
+            entry[..32].copy_from_slice(player.as_ref());
+            data.extend_from_slice(&entry);
+        }
+        
+        assert_eq!(linked_match::participants(&data), Some(vec![one, two]));
+        // Data ending inside the second player's key
+        assert_eq!(linked_match::participants(&data[..data.len() - 70]), None);
+    }
+}
@@ -93,7 +93,8 @@ describe("PvP Gambling Tests", () => {
           player1.publicKey,
           player2.publicKey,
           false, // gasless_mode
-          null   // max_cost_cap
+          null,  // max_cost_cap
+          null   // linked_match
         )
         .accounts({
           initializer: initializer.publicKey,
@@ -138,7 +139,8 @@ describe("PvP Gambling Tests", () => {
           player1.publicKey,
           player2.publicKey,
           true,        // gasless_mode
-          maxCostCap   // max_cost_cap
+          maxCostCap,  // max_cost_cap
+          null         // linked_match
         )
         .accounts({
           initializer: initializer.publicKey,
@@ -165,6 +167,7 @@ describe("PvP Gambling Tests", () => {
             player1.publicKey,
             player1.publicKey, // Same player
             false,
+            null,
             null
           )
           .accounts({
@@ -195,6 +198,7 @@ describe("PvP Gambling Tests", () => {
             player1.publicKey,
             player2.publicKey,
             false,
+            null,
             null
           )
           .accounts({
@@ -225,6 +229,7 @@ describe("PvP Gambling Tests", () => {
           player1.publicKey,
           player2.publicKey,
           false,
+          null,
           null
         )
         .accounts({
@@ -368,6 +373,7 @@ describe("PvP Gambling Tests", () => {
           player1.publicKey,
           player2.publicKey,
           false,
+          null,
           null
         )
         .accounts({
@@ -495,6 +501,7 @@ describe("PvP Gambling Tests", () => {
           player1.publicKey,
           player2.publicKey,
           false,
+          null,
           null
         )
         .accounts({
@@ -562,6 +569,7 @@ describe("PvP Gambling Tests", () => {
           player1.publicKey,
           player2.publicKey,
           false,
+          null,
           null
         )
         .accounts({
@@ -691,7 +699,8 @@ describe("PvP Gambling Tests", () => {
           player1.publicKey,
           player2.publicKey,
           true,        // gasless_mode
-          maxCostCap   // max_cost_cap
+          maxCostCap,  // max_cost_cap
+          null         // linked_match
         )
         .accounts({
           initializer: initializer.publicKey,
@@ -6,6 +6,7 @@ pub mod position;
 pub mod combat;
 pub mod match_state;
 pub mod session;
+pub mod map_config;
 
 // Re-export components for easier importing
 pub use player::Player;
@@ -14,6 +15,7 @@ pub use position::Position;
 pub use combat::Combat;
 pub use match_state::{MatchState, PlayerInMatch};
 pub use session::SessionKey;
+pub use map_config::{MapConfig, SpawnPoint, HazardZone, BuffPickup, BUFF_HEAL, BUFF_SPEED, BUFF_SHIELD};
 
 use bolt_lang::*;
 use anchor_lang::prelude::*;
@@ -0,0 +1,170 @@
+use bolt_lang::*;
+use crate::components::ArenaBounds;
+
+pub const MAX_SPAWN_POINTS: usize = 8;
+pub const MAX_HAZARD_ZONES: usize = 8;
+pub const MAX_BUFF_PICKUPS: usize = 8;
+
+pub const BUFF_HEAL: u8 = 0;
+pub const BUFF_SPEED: u8 = 1;
+pub const BUFF_SHIELD: u8 = 2;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SpawnPoint {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+/// Circular area that damages anything standing in it once per interval
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HazardZone {
+    pub x: i32,
+    pub y: i32,
+    pub radius: u32,
+    pub damage_per_tick: u32,
+    pub tick_interval: i64, // Seconds between damage ticks
+}
+
+impl HazardZone {
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        within_radius(self.x, self.y, self.radius, x, y)
+    }
+}
+
+/// Pickup granting a buff when walked over, available again after `respawn_seconds`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BuffPickup {
+    pub x: i32,
+    pub y: i32,
+    pub radius: u32,
+    pub buff_type: u8, // BUFF_HEAL, BUFF_SPEED or BUFF_SHIELD
+    pub amount: u32,
+    pub respawn_seconds: i64,
+}
+
+impl BuffPickup {
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        within_radius(self.x, self.y, self.radius, x, y)
+    }
+}
+
+fn within_radius(center_x: i32, center_y: i32, radius: u32, x: i32, y: i32) -> bool {
+    let dx = (x as i64 - center_x as i64).abs();
+    let dy = (y as i64 - center_y as i64).abs();
+    dx * dx + dy * dy <= radius as i64 * radius as i64
+}
+
+/// Map layout shared by every match created on it. New maps are data, not code:
+/// create_match copies the bounds into MatchState, and the movement system reads
+/// hazards and pickups from here.
+#[component]
+#[derive(Clone, Copy)]
+pub struct MapConfig {
+    pub authority: Pubkey,
+    pub map_id: u32,
+    pub arena: ArenaBounds,
+    pub spawn_points: [SpawnPoint; MAX_SPAWN_POINTS],
+    pub spawn_count: u8,
+    pub hazards: [HazardZone; MAX_HAZARD_ZONES],
+    pub hazard_count: u8,
+    pub pickups: [BuffPickup; MAX_BUFF_PICKUPS],
+    pub pickup_count: u8,
+    pub is_active: bool,
+    pub updated_at: i64,
+}
+
+impl Default for MapConfig {
+    fn default() -> Self {
+        Self {
+            authority: Pubkey::default(),
+            map_id: 0,
+            arena: ArenaBounds::default(),
+            spawn_points: [SpawnPoint::default(); MAX_SPAWN_POINTS],
+            spawn_count: 0,
+            hazards: [HazardZone::default(); MAX_HAZARD_ZONES],
+            hazard_count: 0,
+            pickups: [BuffPickup::default(); MAX_BUFF_PICKUPS],
+            pickup_count: 0,
+            is_active: true,
+            updated_at: 0,
+        }
+    }
+}
+
+impl MapConfig {
+    /// Replace the layout, rejecting spawns or pickups that sit outside the arena or on an obstacle
+    pub fn set_layout(
+        &mut self,
+        arena: ArenaBounds,
+        spawn_points: &[SpawnPoint],
+        hazards: &[HazardZone],
+        pickups: &[BuffPickup],
+    ) -> Result<()> {
+        if arena.min_x > arena.max_x || arena.min_y > arena.max_y || arena.min_z > arena.max_z {
+            return Err(crate::GameError::InvalidMapConfig.into());
+        }
+        if spawn_points.is_empty()
+            || spawn_points.len() > MAX_SPAWN_POINTS
+            || hazards.len() > MAX_HAZARD_ZONES
+            || pickups.len() > MAX_BUFF_PICKUPS
+        {
+            return Err(crate::GameError::InvalidMapConfig.into());
+        }
+        if spawn_points.iter().any(|spawn| !arena.is_passable(spawn.x, spawn.y, spawn.z))
+            || pickups.iter().any(|pickup| pickup.buff_type > BUFF_SHIELD || arena.is_blocked(pickup.x, pickup.y))
+            || hazards.iter().any(|hazard| hazard.tick_interval <= 0)
+        {
+            return Err(crate::GameError::InvalidMapConfig.into());
+        }
+
+        self.arena = arena;
+        self.spawn_points = [SpawnPoint::default(); MAX_SPAWN_POINTS];
+        self.spawn_points[..spawn_points.len()].copy_from_slice(spawn_points);
+        self.spawn_count = spawn_points.len() as u8;
+        self.hazards = [HazardZone::default(); MAX_HAZARD_ZONES];
+        self.hazards[..hazards.len()].copy_from_slice(hazards);
+        self.hazard_count = hazards.len() as u8;
+        self.pickups = [BuffPickup::default(); MAX_BUFF_PICKUPS];
+        self.pickups[..pickups.len()].copy_from_slice(pickups);
+        self.pickup_count = pickups.len() as u8;
+
+        Ok(())
+    }
+
+    /// Spawn point for a participant slot, wrapping if there are fewer spawns than players
+    pub fn spawn_for_slot(&self, slot: u8) -> SpawnPoint {
+        if self.spawn_count == 0 {
+            return SpawnPoint::default();
+        }
+        self.spawn_points[(slot % self.spawn_count) as usize]
+    }
+
+    pub fn active_hazards(&self) -> &[HazardZone] {
+        &self.hazards[..self.hazard_count as usize]
+    }
+
+    pub fn active_pickups(&self) -> &[BuffPickup] {
+        &self.pickups[..self.pickup_count as usize]
+    }
+
+    /// Total hazard damage accrued at a point over `elapsed` seconds
+    pub fn hazard_damage_at(&self, x: i32, y: i32, elapsed: i64) -> u32 {
+        if elapsed <= 0 {
+            return 0;
+        }
+        self.active_hazards()
+            .iter()
+            .filter(|hazard| hazard.contains(x, y))
+            .map(|hazard| {
+                let ticks = (elapsed / hazard.tick_interval.max(1)) as u32;
+                hazard.damage_per_tick.saturating_mul(ticks)
+            })
+            .fold(0u32, |total, damage| total.saturating_add(damage))
+    }
+
+    /// Index of the first pickup covering a point
+    pub fn pickup_at(&self, x: i32, y: i32) -> Option<usize> {
+        self.active_pickups().iter().position(|pickup| pickup.contains(x, y))
+    }
+}
@@ -1,4 +1,5 @@
 use bolt_lang::*;
+use crate::components::MAX_BUFF_PICKUPS;
 
 /// Match configuration and state component
 #[component]
@@ -19,6 +20,22 @@ pub struct MatchState {
     pub ended_at: Option<i64>,
     pub turn_timeout: i64,
     pub match_duration: i64,
+    // Optional pick/ban phase
+    pub draft_phase: DraftPhase,
+    pub bans_per_player: u8,
+    pub draft_step: u8,
+    pub draft_turn_timeout: i64,
+    pub draft_deadline: i64,
+    pub banned_classes: u8,   // Bitmask over player_class
+    pub banned_abilities: u8, // Bitmask over combat action_type
+    pub picks: [u8; 8],       // Picked class per participant slot, NO_PICK if none
+    // Frozen while the state is exported for migration
+    pub migration_locked: bool,
+    // Playable area and obstacles checked by the movement system
+    pub arena: ArenaBounds,
+    // Map the match was created on, and when each of its pickups is next available
+    pub map_config: Pubkey,
+    pub pickup_ready_at: [i64; MAX_BUFF_PICKUPS],
 }
 
 impl Default for MatchState {
@@ -39,10 +56,222 @@ impl Default for MatchState {
             ended_at: None,
             turn_timeout: 60,    // 60 seconds per turn
             match_duration: 1800, // 30 minutes max
+            draft_phase: DraftPhase::Disabled,
+            bans_per_player: 0,
+            draft_step: 0,
+            draft_turn_timeout: 30,
+            draft_deadline: 0,
+            banned_classes: 0,
+            banned_abilities: 0,
+            picks: [NO_PICK; 8],
+            migration_locked: false,
+            arena: ArenaBounds::default(),
+            map_config: Pubkey::default(),
+            pickup_ready_at: [0; MAX_BUFF_PICKUPS],
         }
     }
 }
 
+/// Commitment to an exported match state, read back by import
+#[component]
+#[derive(Clone, Copy, Default)]
+pub struct MatchStateExport {
+    pub match_state: Pubkey,
+    pub match_id: u64,
+    pub state_hash: [u8; 32],
+    pub exported_at: i64,
+    pub slot: u64,
+}
+
+/// Marker for a participant slot without a draft pick
+pub const NO_PICK: u8 = u8::MAX;
+pub const DRAFT_BAN_CLASS: u8 = 0;
+pub const DRAFT_BAN_ABILITY: u8 = 1;
+pub const MAX_PLAYER_CLASS: u8 = 3;
+pub const MAX_ABILITY_TYPE: u8 = 4;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum DraftPhase {
+    Disabled = 0,
+    Pending = 1,  // Enabled, waiting for the lobby to fill
+    Banning = 2,
+    Picking = 3,
+    Complete = 4,
+}
+
+impl Default for DraftPhase {
+    fn default() -> Self {
+        DraftPhase::Disabled
+    }
+}
+
+impl MatchState {
+    pub fn is_drafting(&self) -> bool {
+        matches!(self.draft_phase, DraftPhase::Banning | DraftPhase::Picking)
+    }
+
+    /// Total number of ban turns across all players
+    pub fn total_ban_turns(&self) -> u8 {
+        self.bans_per_player.saturating_mul(self.current_players)
+    }
+
+    /// Turn order slot whose draft turn it is; bans and picks alternate through the turn order
+    pub fn draft_turn_slot(&self) -> u8 {
+        if self.current_players == 0 {
+            return 0;
+        }
+        let step = if self.draft_phase == DraftPhase::Picking {
+            self.draft_step - self.total_ban_turns()
+        } else {
+            self.draft_step
+        };
+        step % self.current_players
+    }
+
+    pub fn start_draft(&mut self, current_time: i64) {
+        self.draft_step = 0;
+        self.draft_phase = if self.bans_per_player > 0 {
+            DraftPhase::Banning
+        } else {
+            DraftPhase::Picking
+        };
+        self.draft_deadline = current_time + self.draft_turn_timeout;
+    }
+
+    /// Move to the next draft turn, switching phase when bans or picks run out
+    pub fn advance_draft(&mut self, current_time: i64) {
+        self.draft_step += 1;
+        if self.draft_phase == DraftPhase::Banning && self.draft_step >= self.total_ban_turns() {
+            self.draft_phase = DraftPhase::Picking;
+        }
+        if self.draft_phase == DraftPhase::Picking
+            && self.draft_step >= self.total_ban_turns() + self.current_players
+        {
+            self.draft_phase = DraftPhase::Complete;
+        }
+        self.draft_deadline = current_time + self.draft_turn_timeout;
+    }
+
+    /// Forfeit any draft turns whose deadline has passed. Returns the number skipped.
+    pub fn skip_expired_draft_turns(&mut self, current_time: i64) -> u8 {
+        let mut skipped = 0;
+        while self.is_drafting() && current_time > self.draft_deadline {
+            let missed_deadline = self.draft_deadline;
+            self.advance_draft(missed_deadline);
+            skipped += 1;
+        }
+        skipped
+    }
+
+    pub fn is_class_banned(&self, player_class: u8) -> bool {
+        player_class <= MAX_PLAYER_CLASS && self.banned_classes & (1 << player_class) != 0
+    }
+
+    pub fn is_ability_banned(&self, ability_type: u8) -> bool {
+        ability_type <= MAX_ABILITY_TYPE && self.banned_abilities & (1 << ability_type) != 0
+    }
+
+    /// Whether a participant may act with the given class under the draft result
+    pub fn is_class_allowed(&self, slot: u8, player_class: u8) -> bool {
+        if self.is_class_banned(player_class) {
+            return false;
+        }
+        match self.picks.get(slot as usize) {
+            Some(&pick) if pick != NO_PICK => pick == player_class,
+            _ => true,
+        }
+    }
+
+    /// Finish the draft and start the match
+    pub fn complete_draft(&mut self, current_time: i64) {
+        self.draft_phase = DraftPhase::Complete;
+        self.state = GameState::InProgress;
+        self.started_at = Some(current_time);
+        self.turn_deadline = current_time + self.turn_timeout;
+    }
+}
+
+/// Side length of the obstacle grid; `obstacle_mask` holds one bit per cell
+pub const ARENA_GRID_SIZE: i32 = 8;
+
+/// Arena boundaries plus a coarse collision grid laid over the x/y plane
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct ArenaBounds {
+    pub min_x: i32,
+    pub max_x: i32,
+    pub min_y: i32,
+    pub max_y: i32,
+    pub min_z: i32,
+    pub max_z: i32,
+    pub obstacle_mask: u64, // Bit (row * ARENA_GRID_SIZE + col) set = cell blocked
+}
+
+impl Default for ArenaBounds {
+    fn default() -> Self {
+        Self {
+            min_x: -10000,
+            max_x: 10000,
+            min_y: -10000,
+            max_y: 10000,
+            min_z: 0,
+            max_z: 1000,
+            obstacle_mask: 0,
+        }
+    }
+}
+
+impl ArenaBounds {
+    pub fn contains(&self, x: i32, y: i32, z: i32) -> bool {
+        x >= self.min_x && x <= self.max_x &&
+        y >= self.min_y && y <= self.max_y &&
+        z >= self.min_z && z <= self.max_z
+    }
+
+    /// Grid cell (col, row) containing the point, or None outside the arena
+    pub fn cell_of(&self, x: i32, y: i32) -> Option<(i32, i32)> {
+        if x < self.min_x || x > self.max_x || y < self.min_y || y > self.max_y {
+            return None;
+        }
+        let width = (self.max_x as i64 - self.min_x as i64 + 1).max(1);
+        let height = (self.max_y as i64 - self.min_y as i64 + 1).max(1);
+        let col = ((x as i64 - self.min_x as i64) * ARENA_GRID_SIZE as i64 / width) as i32;
+        let row = ((y as i64 - self.min_y as i64) * ARENA_GRID_SIZE as i64 / height) as i32;
+        Some((col, row))
+    }
+
+    pub fn is_blocked(&self, x: i32, y: i32) -> bool {
+        match self.cell_of(x, y) {
+            Some((col, row)) => self.obstacle_mask & (1u64 << (row * ARENA_GRID_SIZE + col)) != 0,
+            None => true,
+        }
+    }
+
+    /// Inside the arena and not on an obstacle
+    pub fn is_passable(&self, x: i32, y: i32, z: i32) -> bool {
+        self.contains(x, y, z) && !self.is_blocked(x, y)
+    }
+
+    /// Whether the straight segment between two points crosses any blocked cell.
+    /// Samples at a quarter-cell stride so a move cannot step over an obstacle.
+    pub fn is_path_clear(&self, from_x: i32, from_y: i32, to_x: i32, to_y: i32) -> bool {
+        if self.obstacle_mask == 0 {
+            return self.cell_of(to_x, to_y).is_some();
+        }
+        let dx = to_x as i64 - from_x as i64;
+        let dy = to_y as i64 - from_y as i64;
+        let cell_width = ((self.max_x as i64 - self.min_x as i64 + 1) / ARENA_GRID_SIZE as i64).max(1);
+        let cell_height = ((self.max_y as i64 - self.min_y as i64 + 1) / ARENA_GRID_SIZE as i64).max(1);
+        let stride = (cell_width.min(cell_height) / 4).max(1);
+        let steps = (dx.abs().max(dy.abs()) / stride).max(1);
+
+        (0..=steps).all(|step| {
+            let x = from_x as i64 + dx * step / steps;
+            let y = from_y as i64 + dy * step / steps;
+            !self.is_blocked(x as i32, y as i32)
+        })
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum GameState {
     WaitingForPlayers = 0,
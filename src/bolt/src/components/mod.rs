@@ -2,8 +2,12 @@ pub mod player;
 pub mod combat;
 pub mod match_state;
 pub mod session;
+pub mod optimistic;
+pub mod map_config;
 
 pub use player::*;
 pub use combat::*;
 pub use match_state::*;
-pub use session::*;
\ No newline at end of file
+pub use session::*;
+pub use optimistic::*;
+pub use map_config::*;
\ No newline at end of file
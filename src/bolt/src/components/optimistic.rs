@@ -0,0 +1,126 @@
+use bolt_lang::*;
+
+/// Seconds an optimistic update may stay pending before it can be evicted
+pub const DEFAULT_OPTIMISTIC_EXPIRY: i64 = 30;
+
+/// Maximum pending updates held per entity
+pub const OPTIMISTIC_QUEUE_CAPACITY: usize = 16;
+
+/// A client-applied update awaiting confirmation from the Ephemeral Rollup
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PendingUpdate {
+    pub id: u64,
+    pub session_key: Pubkey,
+    pub action_type: u8,
+    pub payload_hash: [u8; 32],
+    pub created_at: i64,
+    pub expires_at: i64,
+    pub confirmed: bool,
+}
+
+/// Per-entity ring buffer of pending optimistic updates.
+/// Entries are ordered by creation and share one expiry, so the head is always
+/// the oldest and the first to expire; eviction only ever pops from the head.
+#[component]
+#[derive(Clone, Copy)]
+pub struct OptimisticQueue {
+    pub entity: Pubkey,
+    pub expiry: i64,
+    pub head: u8,
+    pub len: u8,
+    pub next_id: u64,
+    pub entries: [PendingUpdate; OPTIMISTIC_QUEUE_CAPACITY],
+    pub total_enqueued: u64,
+    pub total_evicted: u64,
+    pub last_gc_at: i64,
+}
+
+impl Default for OptimisticQueue {
+    fn default() -> Self {
+        Self {
+            entity: Pubkey::default(),
+            expiry: DEFAULT_OPTIMISTIC_EXPIRY,
+            head: 0,
+            len: 0,
+            next_id: 1,
+            entries: [PendingUpdate::default(); OPTIMISTIC_QUEUE_CAPACITY],
+            total_enqueued: 0,
+            total_evicted: 0,
+            last_gc_at: 0,
+        }
+    }
+}
+
+impl OptimisticQueue {
+    fn slot(&self, offset: u8) -> usize {
+        (self.head as usize + offset as usize) % OPTIMISTIC_QUEUE_CAPACITY
+    }
+
+    fn pop_front(&mut self) {
+        self.entries[self.head as usize] = PendingUpdate::default();
+        self.head = ((self.head as usize + 1) % OPTIMISTIC_QUEUE_CAPACITY) as u8;
+        self.len -= 1;
+        self.total_evicted += 1;
+    }
+
+    /// Pop confirmed and expired entries from the head. Returns how many were removed.
+    pub fn evict_expired(&mut self, current_time: i64) -> u32 {
+        let mut evicted = 0;
+        while self.len > 0 {
+            let front = self.entries[self.head as usize];
+            if !front.confirmed && current_time < front.expires_at {
+                break;
+            }
+            self.pop_front();
+            evicted += 1;
+        }
+        evicted
+    }
+
+    /// Append an update, evicting expired entries first and the oldest pending
+    /// entry if the queue is still full. Returns the new update's id.
+    pub fn push(
+        &mut self,
+        session_key: Pubkey,
+        action_type: u8,
+        payload_hash: [u8; 32],
+        current_time: i64,
+    ) -> u64 {
+        self.evict_expired(current_time);
+        if self.len as usize == OPTIMISTIC_QUEUE_CAPACITY {
+            self.pop_front();
+        }
+
+        let id = self.next_id;
+        let index = self.slot(self.len);
+        self.entries[index] = PendingUpdate {
+            id,
+            session_key,
+            action_type,
+            payload_hash,
+            created_at: current_time,
+            expires_at: current_time + self.expiry,
+            confirmed: false,
+        };
+        self.len += 1;
+        self.next_id += 1;
+        self.total_enqueued += 1;
+        id
+    }
+
+    /// Mark a pending update as confirmed; it is dropped once it reaches the head
+    pub fn confirm(&mut self, id: u64) -> bool {
+        for offset in 0..self.len {
+            let index = self.slot(offset);
+            if self.entries[index].id == id {
+                self.entries[index].confirmed = true;
+                return true;
+            }
+        }
+        false
+    }
+
+    pub fn pending_count(&self) -> u8 {
+        self.len
+    }
+}
@@ -39,11 +39,98 @@ pub mod solduel_bolt {
         systems::match_system::create_match::handler(ctx, max_players, entry_fee, turn_timeout)
     }
 
+    /// Publish a map layout that matches can be created on
+    pub fn create_map_config(
+        ctx: Context<CreateMapConfig>,
+        map_id: u32,
+        arena: ArenaBounds,
+        spawn_points: Vec<SpawnPoint>,
+        hazards: Vec<HazardZone>,
+        pickups: Vec<BuffPickup>,
+    ) -> Result<()> {
+        systems::map_system::create_map_config::handler(ctx, map_id, arena, spawn_points, hazards, pickups)
+    }
+
+    /// Replace a map's layout or retire it; running matches keep their copied bounds
+    pub fn update_map_config(
+        ctx: Context<UpdateMapConfig>,
+        arena: ArenaBounds,
+        spawn_points: Vec<SpawnPoint>,
+        hazards: Vec<HazardZone>,
+        pickups: Vec<BuffPickup>,
+        is_active: bool,
+    ) -> Result<()> {
+        systems::map_system::update_map_config::handler(ctx, arena, spawn_points, hazards, pickups, is_active)
+    }
+
     /// Join an existing match
     pub fn join_match(ctx: Context<JoinMatch>) -> Result<()> {
         systems::match_system::join_match::handler(ctx)
     }
 
+    /// Enable the pick/ban phase for a match that is still filling
+    pub fn enable_draft(
+        ctx: Context<EnableDraft>,
+        bans_per_player: u8,
+        draft_turn_timeout: i64,
+    ) -> Result<()> {
+        systems::draft_system::enable_draft::handler(ctx, bans_per_player, draft_turn_timeout)
+    }
+
+    /// Ban a class or ability during the draft phase
+    pub fn submit_ban(ctx: Context<SubmitDraft>, ban_type: u8, value: u8) -> Result<()> {
+        systems::draft_system::submit_ban::handler(ctx, ban_type, value)
+    }
+
+    /// Pick a class during the draft phase
+    pub fn submit_pick(ctx: Context<SubmitDraft>, player_class: u8) -> Result<()> {
+        systems::draft_system::submit_pick::handler(ctx, player_class)
+    }
+
+    /// Skip expired draft turns; starts the match once the draft has run out
+    pub fn advance_draft(ctx: Context<AdvanceDraft>) -> Result<()> {
+        systems::draft_system::advance_draft::handler(ctx)
+    }
+
+    /// Freeze a match and publish the hash of its state ahead of a migration
+    pub fn export_state_hash(ctx: Context<ExportMatchState>) -> Result<()> {
+        systems::migration_system::export_state_hash::handler(ctx)
+    }
+
+    /// Recreate an exported match after an upgrade or redeploy
+    pub fn import_state(
+        ctx: Context<ImportMatchState>,
+        source_program: Pubkey,
+        source_match: Pubkey,
+        state_bytes: Vec<u8>,
+    ) -> Result<()> {
+        systems::migration_system::import_state::handler(ctx, source_program, source_match, state_bytes)
+    }
+
+    /// Create the pending optimistic update queue for an entity
+    pub fn init_optimistic_queue(ctx: Context<InitOptimisticQueue>, expiry: i64) -> Result<()> {
+        systems::optimistic_queue_system::init_optimistic_queue::handler(ctx, expiry)
+    }
+
+    /// Record a client-applied update awaiting rollup confirmation
+    pub fn enqueue_optimistic_update(
+        ctx: Context<EnqueueOptimisticUpdate>,
+        action_type: u8,
+        payload_hash: [u8; 32],
+    ) -> Result<()> {
+        systems::optimistic_queue_system::enqueue_optimistic_update::handler(ctx, action_type, payload_hash)
+    }
+
+    /// Confirm a pending optimistic update
+    pub fn confirm_optimistic_update(ctx: Context<ConfirmOptimisticUpdate>, update_id: u64) -> Result<()> {
+        systems::optimistic_queue_system::confirm_optimistic_update::handler(ctx, update_id)
+    }
+
+    /// Crank that evicts confirmed and expired optimistic updates
+    pub fn gc_optimistic_updates(ctx: Context<GcOptimisticUpdates>) -> Result<()> {
+        systems::optimistic_queue_system::gc_optimistic_updates::handler(ctx)
+    }
+
     /// Execute combat action in match
     pub fn execute_combat_action(
         ctx: Context<ExecuteCombatAction>,
@@ -54,6 +141,21 @@ pub mod solduel_bolt {
         systems::combat_system::execute_action::handler(ctx, action_type, target_entity, power)
     }
 
+    /// Walk to a point in the match arena, within the player's speed
+    pub fn move_player(ctx: Context<MovePlayer>, target_x: i32, target_y: i32) -> Result<()> {
+        systems::movement_system::move_player::handler(ctx, target_x, target_y)
+    }
+
+    /// Teleport with the special or ultimate ability, putting it on cooldown
+    pub fn teleport_player(
+        ctx: Context<MovePlayer>,
+        action_type: u8,
+        target_x: i32,
+        target_y: i32,
+    ) -> Result<()> {
+        systems::movement_system::teleport_player::handler(ctx, action_type, target_x, target_y)
+    }
+
     /// Process turn and update game state
     pub fn process_turn(ctx: Context<ProcessTurn>) -> Result<()> {
         systems::turn_system::process_turn::handler(ctx)
@@ -64,6 +166,30 @@ pub mod solduel_bolt {
         systems::match_system::end_match::handler(ctx)
     }
 
+    /// Create the slot-driven tick schedule; upgrade authority only
+    pub fn initialize_tick_schedule(
+        ctx: Context<InitializeTickSchedule>,
+        authority: Pubkey,
+        bounty_per_system: u64,
+    ) -> Result<()> {
+        systems::tick_schedule::initialize_tick_schedule::handler(ctx, authority, bounty_per_system)
+    }
+
+    /// Update a scheduled system's interval and enabled flag
+    pub fn configure_tick_schedule(
+        ctx: Context<ConfigureTickSchedule>,
+        system_id: u8,
+        interval_slots: u64,
+        enabled: bool,
+    ) -> Result<()> {
+        systems::tick_schedule::configure_tick_schedule::handler(ctx, system_id, interval_slots, enabled)
+    }
+
+    /// Run every system whose tick interval has elapsed, paying the caller a bounty
+    pub fn run_due_systems<'info>(ctx: Context<'_, '_, 'info, 'info, RunDueSystems<'info>>) -> Result<()> {
+        systems::tick_schedule::run_due_systems::handler(ctx)
+    }
+
     /// Session key delegation for gasless transactions
     pub fn delegate_session_key(
         ctx: Context<DelegateSessionKey>,
@@ -106,4 +232,38 @@ pub enum GameError {
     InvalidSessionKey,
     #[msg("Arithmetic overflow")]
     ArithmeticOverflow,
+    #[msg("Draft phase is not active")]
+    DraftNotActive,
+    #[msg("Invalid draft selection")]
+    InvalidDraftSelection,
+    #[msg("Class or ability is banned for this match")]
+    BannedSelection,
+    #[msg("Match is frozen for state migration")]
+    MigrationLocked,
+    #[msg("State export record or payload is invalid")]
+    InvalidStateExport,
+    #[msg("Map configuration is invalid")]
+    InvalidMapConfig,
+    #[msg("Map is not active")]
+    MapInactive,
+    #[msg("Unknown system")]
+    UnknownSystem,
+    #[msg("No systems are due this slot")]
+    NoSystemsDue,
+    #[msg("Movement exceeds player speed")]
+    MovementTooFast,
+    #[msg("Position is outside the arena")]
+    OutOfArenaBounds,
+    #[msg("Path is blocked by an obstacle")]
+    PathBlocked,
+    #[msg("Teleport requires the special or ultimate ability")]
+    TeleportNotAllowed,
+    #[msg("Ability is on cooldown")]
+    AbilityOnCooldown,
+    #[msg("Target is out of range")]
+    TargetOutOfRange,
+    #[msg("Player cannot move while frozen")]
+    PlayerImmobilized,
+    #[msg("No draft turn has expired yet")]
+    DraftTurnNotExpired,
 }
\ No newline at end of file
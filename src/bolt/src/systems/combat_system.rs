@@ -18,6 +18,20 @@ pub mod execute_action {
             return Err(crate::GameError::PlayerAlreadyDead.into());
         }
 
+        // Enforce the draft result
+        let match_state = &ctx.accounts.match_state;
+        if match_state.migration_locked {
+            return Err(crate::GameError::MigrationLocked.into());
+        }
+        let attacker_slot = ctx.accounts.match_participants
+            .get_player_index(&ctx.accounts.attacker.key())
+            .ok_or(crate::GameError::PlayerNotFound)?;
+        if match_state.is_ability_banned(action_type)
+            || !match_state.is_class_allowed(attacker_slot, ctx.accounts.attacker_profile.player_class)
+        {
+            return Err(crate::GameError::BannedSelection.into());
+        }
+
         // Check ability cooldowns
         let cooldowns = &mut ctx.accounts.attacker_cooldowns;
         let ability_type = match action_type {
@@ -473,6 +487,18 @@ pub struct ExecuteCombatAction<'info> {
     #[account(mut)]
     pub match_analytics: Account<'info, MatchAnalytics>,
     
+    #[account(
+        seeds = [b"match_state", match_state.creator.as_ref(), &match_state.created_at.to_le_bytes()],
+        bump
+    )]
+    pub match_state: Account<'info, MatchState>,
+    
+    #[account(
+        seeds = [b"match_participants", match_state.key().as_ref()],
+        bump
+    )]
+    pub match_participants: Account<'info, MatchParticipants>,
+    
     pub system_program: Program<'info, System>,
 }
 
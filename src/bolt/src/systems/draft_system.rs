@@ -0,0 +1,253 @@
+use bolt_lang::*;
+use crate::components::*;
+
+pub mod enable_draft {
+    use super::*;
+
+    pub fn handler(
+        ctx: Context<EnableDraft>,
+        bans_per_player: u8,
+        draft_turn_timeout: i64,
+    ) -> Result<()> {
+        let match_state = &mut ctx.accounts.match_state;
+
+        // Only the creator can configure the draft, and only before the lobby fills
+        if ctx.accounts.creator.key() != match_state.creator {
+            return Err(crate::GameError::UnauthorizedAction.into());
+        }
+        if match_state.state != GameState::WaitingForPlayers
+            || match_state.current_players >= match_state.max_players
+        {
+            return Err(crate::GameError::InvalidGameState.into());
+        }
+        if draft_turn_timeout <= 0 || bans_per_player > 2 {
+            return Err(crate::GameError::InvalidDraftSelection.into());
+        }
+
+        match_state.draft_phase = DraftPhase::Pending;
+        match_state.bans_per_player = bans_per_player;
+        match_state.draft_turn_timeout = draft_turn_timeout;
+
+        msg!(
+            "Draft enabled for match {}: {} bans per player, {}s per turn",
+            match_state.match_id,
+            bans_per_player,
+            draft_turn_timeout
+        );
+
+        Ok(())
+    }
+}
+
+pub mod submit_ban {
+    use super::*;
+
+    pub fn handler(ctx: Context<SubmitDraft>, ban_type: u8, value: u8) -> Result<()> {
+        let match_state = &mut ctx.accounts.match_state;
+        let participants = &ctx.accounts.match_participants;
+        let clock = Clock::get()?;
+
+        // Expired turns may have finished the draft; erroring now would undo that
+        if resolve_draft_timeouts(match_state, clock.unix_timestamp) {
+            return Ok(());
+        }
+        if match_state.draft_phase != DraftPhase::Banning {
+            return Err(crate::GameError::DraftNotActive.into());
+        }
+        require_draft_turn(match_state, participants, &ctx.accounts.player.key())?;
+
+        match ban_type {
+            DRAFT_BAN_CLASS => {
+                if value > MAX_PLAYER_CLASS || match_state.is_class_banned(value) {
+                    return Err(crate::GameError::InvalidDraftSelection.into());
+                }
+                match_state.banned_classes |= 1 << value;
+            }
+            DRAFT_BAN_ABILITY => {
+                // Basic attack stays available so every class can act
+                if value == 0 || value > MAX_ABILITY_TYPE || match_state.is_ability_banned(value) {
+                    return Err(crate::GameError::InvalidDraftSelection.into());
+                }
+                match_state.banned_abilities |= 1 << value;
+            }
+            _ => return Err(crate::GameError::InvalidDraftSelection.into()),
+        }
+
+        match_state.advance_draft(clock.unix_timestamp);
+
+        msg!(
+            "Player {} banned {} {} in match {}",
+            ctx.accounts.player.key(),
+            if ban_type == DRAFT_BAN_CLASS { "class" } else { "ability" },
+            value,
+            match_state.match_id
+        );
+
+        Ok(())
+    }
+}
+
+pub mod submit_pick {
+    use super::*;
+
+    pub fn handler(ctx: Context<SubmitDraft>, player_class: u8) -> Result<()> {
+        let match_state = &mut ctx.accounts.match_state;
+        let participants = &ctx.accounts.match_participants;
+        let clock = Clock::get()?;
+
+        if resolve_draft_timeouts(match_state, clock.unix_timestamp) {
+            return Ok(());
+        }
+        if match_state.draft_phase != DraftPhase::Picking {
+            return Err(crate::GameError::DraftNotActive.into());
+        }
+        let slot = require_draft_turn(match_state, participants, &ctx.accounts.player.key())?;
+
+        if player_class > MAX_PLAYER_CLASS || match_state.is_class_banned(player_class) {
+            return Err(crate::GameError::BannedSelection.into());
+        }
+
+        match_state.picks[slot as usize] = player_class;
+        match_state.advance_draft(clock.unix_timestamp);
+
+        if match_state.draft_phase == DraftPhase::Complete {
+            match_state.complete_draft(clock.unix_timestamp);
+
+            msg!("Draft complete, match {} started", match_state.match_id);
+        }
+
+        Ok(())
+    }
+}
+
+pub mod advance_draft {
+    use super::*;
+
+    /// Skip expired draft turns, starting the match if none are left. Anyone may
+    /// crank it, so a draft nobody acts on still finishes.
+    pub fn handler(ctx: Context<AdvanceDraft>) -> Result<()> {
+        let match_state = &mut ctx.accounts.match_state;
+        let clock = Clock::get()?;
+
+        if !match_state.is_drafting() {
+            return Err(crate::GameError::DraftNotActive.into());
+        }
+        if clock.unix_timestamp <= match_state.draft_deadline {
+            return Err(crate::GameError::DraftTurnNotExpired.into());
+        }
+        resolve_draft_timeouts(match_state, clock.unix_timestamp);
+
+        Ok(())
+    }
+}
+
+/// Skip turns of players who let the draft timer run out. A missed ban is forfeited
+/// and a missed pick leaves the player on their profile class. Returns true if
+/// this started the match.
+fn resolve_draft_timeouts(match_state: &mut MatchState, current_time: i64) -> bool {
+    let skipped = match_state.skip_expired_draft_turns(current_time);
+    if skipped > 0 {
+        msg!("Skipped {} expired draft turns in match {}", skipped, match_state.match_id);
+    }
+    if match_state.draft_phase == DraftPhase::Complete && match_state.state == GameState::WaitingForPlayers {
+        match_state.complete_draft(current_time);
+        msg!("Draft timed out, match {} started", match_state.match_id);
+        return true;
+    }
+    false
+}
+
+/// Ensure the signer owns the current draft turn and return their participant slot
+fn require_draft_turn(
+    match_state: &MatchState,
+    participants: &MatchParticipants,
+    player: &Pubkey,
+) -> Result<u8> {
+    let turn_slot = match_state.draft_turn_slot();
+    let player_slot = participants
+        .get_player_index(player)
+        .ok_or(crate::GameError::PlayerNotFound)?;
+
+    if participants.turn_order[turn_slot as usize] != player_slot {
+        return Err(crate::GameError::NotPlayerTurn.into());
+    }
+
+    Ok(player_slot)
+}
+
+// Context definitions
+#[derive(Accounts)]
+pub struct EnableDraft<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"match_state", match_state.creator.as_ref(), &match_state.created_at.to_le_bytes()],
+        bump
+    )]
+    pub match_state: Account<'info, MatchState>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitDraft<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"match_state", match_state.creator.as_ref(), &match_state.created_at.to_le_bytes()],
+        bump
+    )]
+    pub match_state: Account<'info, MatchState>,
+
+    #[account(
+        seeds = [b"match_participants", match_state.key().as_ref()],
+        bump
+    )]
+    pub match_participants: Account<'info, MatchParticipants>,
+}
+
+#[derive(Accounts)]
+pub struct AdvanceDraft<'info> {
+    pub cranker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"match_state", match_state.creator.as_ref(), &match_state.created_at.to_le_bytes()],
+        bump
+    )]
+    pub match_state: Account<'info, MatchState>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fully_timed_out_draft_starts_match() {
+        let mut match_state = MatchState {
+            current_players: 2,
+            bans_per_player: 1,
+            draft_turn_timeout: 30,
+            ..MatchState::default()
+        };
+        match_state.start_draft(1_000);
+
+        // Nothing has expired yet
+        assert!(!resolve_draft_timeouts(&mut match_state, 1_030));
+        assert!(match_state.draft_phase == DraftPhase::Banning);
+
+        // Two bans and two picks all lapse; the match starts with no bans or picks
+        assert!(resolve_draft_timeouts(&mut match_state, 2_000));
+        assert!(match_state.draft_phase == DraftPhase::Complete);
+        assert!(match_state.state == GameState::InProgress);
+        assert_eq!(match_state.started_at, Some(2_000));
+        assert_eq!(match_state.banned_classes, 0);
+        assert_eq!(match_state.picks, [NO_PICK; 8]);
+
+        // A late submission finds the match already started rather than undoing it
+        assert!(!resolve_draft_timeouts(&mut match_state, 2_100));
+        assert!(match_state.state == GameState::InProgress);
+    }
+}
@@ -0,0 +1,88 @@
+use bolt_lang::*;
+use crate::components::*;
+
+pub mod create_map_config {
+    use super::*;
+
+    pub fn handler(
+        ctx: Context<CreateMapConfig>,
+        map_id: u32,
+        arena: ArenaBounds,
+        spawn_points: Vec<SpawnPoint>,
+        hazards: Vec<HazardZone>,
+        pickups: Vec<BuffPickup>,
+    ) -> Result<()> {
+        let mut map_config = MapConfig {
+            authority: ctx.accounts.authority.key(),
+            map_id,
+            updated_at: Clock::get()?.unix_timestamp,
+            ..MapConfig::default()
+        };
+        map_config.set_layout(arena, &spawn_points, &hazards, &pickups)?;
+
+        ctx.accounts.map_config.set_inner(map_config);
+
+        msg!(
+            "Map {} created with {} spawns, {} hazards, {} pickups",
+            map_id,
+            spawn_points.len(),
+            hazards.len(),
+            pickups.len()
+        );
+
+        Ok(())
+    }
+}
+
+pub mod update_map_config {
+    use super::*;
+
+    pub fn handler(
+        ctx: Context<UpdateMapConfig>,
+        arena: ArenaBounds,
+        spawn_points: Vec<SpawnPoint>,
+        hazards: Vec<HazardZone>,
+        pickups: Vec<BuffPickup>,
+        is_active: bool,
+    ) -> Result<()> {
+        let map_config = &mut ctx.accounts.map_config;
+        map_config.set_layout(arena, &spawn_points, &hazards, &pickups)?;
+        map_config.is_active = is_active;
+        map_config.updated_at = Clock::get()?.unix_timestamp;
+
+        msg!("Map {} updated (active: {})", map_config.map_id, is_active);
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(map_id: u32)]
+pub struct CreateMapConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<MapConfig>(),
+        seeds = [b"map_config", authority.key().as_ref(), &map_id.to_le_bytes()],
+        bump
+    )]
+    pub map_config: Account<'info, MapConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateMapConfig<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"map_config", map_config.authority.as_ref(), &map_config.map_id.to_le_bytes()],
+        bump,
+        constraint = map_config.authority == authority.key() @ crate::GameError::UnauthorizedAction
+    )]
+    pub map_config: Account<'info, MapConfig>,
+}
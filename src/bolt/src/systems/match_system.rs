@@ -21,6 +21,11 @@ pub mod create_match {
             return Err(crate::GameError::InvalidGameState.into());
         }
 
+        let map_config = &ctx.accounts.map_config;
+        if !map_config.is_active {
+            return Err(crate::GameError::MapInactive.into());
+        }
+
         // Initialize match state
         let match_state = MatchState {
             match_id: clock.unix_timestamp as u64,
@@ -38,6 +43,9 @@ pub mod create_match {
             ended_at: None,
             turn_timeout,
             match_duration: 1800, // 30 minutes default
+            arena: map_config.arena,
+            map_config: map_config.key(),
+            ..MatchState::default()
         };
 
         ctx.accounts.match_state.set_inner(match_state);
@@ -105,8 +113,18 @@ pub mod join_match {
         match_state.current_players += 1;
         match_state.reward_pool += match_state.entry_fee;
 
-        // Start match if full
-        if match_state.current_players == match_state.max_players {
+        // Full lobby with a draft configured moves into the pick/ban phase first
+        if match_state.current_players == match_state.max_players
+            && match_state.draft_phase == DraftPhase::Pending
+        {
+            match_state.start_draft(clock.unix_timestamp);
+
+            msg!(
+                "Match {} entering draft phase with {} players",
+                match_state.match_id,
+                match_state.current_players
+            );
+        } else if match_state.current_players == match_state.max_players {
             match_state.state = GameState::InProgress;
             match_state.started_at = Some(clock.unix_timestamp);
             match_state.turn_deadline = clock.unix_timestamp + match_state.turn_timeout;
@@ -309,6 +327,8 @@ pub struct CreateMatch<'info> {
         init,
         payer = creator,
         space = 8 + std::mem::size_of::<MatchState>(),
+        seeds = [b"match_state", creator.key().as_ref(), &Clock::get()?.unix_timestamp.to_le_bytes()],
+        bump
     )]
     pub match_state: Account<'info, MatchState>,
     
@@ -316,6 +336,8 @@ pub struct CreateMatch<'info> {
         init,
         payer = creator,
         space = 8 + std::mem::size_of::<MatchParticipants>(),
+        seeds = [b"match_participants", match_state.key().as_ref()],
+        bump
     )]
     pub match_participants: Account<'info, MatchParticipants>,
     
@@ -337,12 +359,20 @@ pub struct CreateMatch<'info> {
         init,
         payer = creator,
         space = 8 + std::mem::size_of::<EphemeralState>(),
+        seeds = [b"ephemeral_state", match_state.key().as_ref()],
+        bump
     )]
     pub ephemeral_state: Account<'info, EphemeralState>,
     
     /// CHECK: Ephemeral Rollup PDA
     pub ephemeral_rollup: UncheckedAccount<'info>,
     
+    #[account(
+        seeds = [b"map_config", map_config.authority.as_ref(), &map_config.map_id.to_le_bytes()],
+        bump
+    )]
+    pub map_config: Account<'info, MapConfig>,
+    
     pub system_program: Program<'info, System>,
 }
 
@@ -0,0 +1,147 @@
+use bolt_lang::*;
+use anchor_lang::solana_program::hash::hashv;
+use crate::components::*;
+
+pub mod export_state_hash {
+    use super::*;
+
+    pub fn handler(ctx: Context<ExportMatchState>) -> Result<()> {
+        let match_state = &mut ctx.accounts.match_state;
+        let export = &mut ctx.accounts.state_export;
+        let clock = Clock::get()?;
+
+        if match_state.migration_locked {
+            return Err(crate::GameError::MigrationLocked.into());
+        }
+
+        // Freeze before hashing so the committed bytes are exactly what gets imported
+        match_state.migration_locked = true;
+        let state_bytes = match_state.try_to_vec()?;
+
+        export.match_state = match_state.key();
+        export.match_id = match_state.match_id;
+        export.state_hash = match_state_hash(&state_bytes);
+        export.exported_at = clock.unix_timestamp;
+        export.slot = clock.slot;
+
+        msg!(
+            "Exported match {} state for migration at slot {}",
+            match_state.match_id,
+            clock.slot
+        );
+
+        Ok(())
+    }
+}
+
+pub mod import_state {
+    use super::*;
+
+    /// Recreate an exported match after an upgrade or redeploy. A layout-breaking
+    /// upgrade must decode the previous MatchState layout here.
+    pub fn handler(
+        ctx: Context<ImportMatchState>,
+        source_program: Pubkey,
+        source_match: Pubkey,
+        state_bytes: Vec<u8>,
+    ) -> Result<()> {
+        let source_export = &ctx.accounts.source_export;
+
+        let (expected_export, _) = Pubkey::find_program_address(
+            &[b"state_export", source_match.as_ref()],
+            &source_program,
+        );
+        if source_export.key() != expected_export || *source_export.owner != source_program {
+            return Err(crate::GameError::InvalidStateExport.into());
+        }
+
+        let export = {
+            let data = source_export.try_borrow_data()?;
+            if data.len() <= 8 {
+                return Err(crate::GameError::InvalidStateExport.into());
+            }
+            MatchStateExport::deserialize(&mut &data[8..])?
+        };
+        if export.match_state != source_match || match_state_hash(&state_bytes) != export.state_hash {
+            return Err(crate::GameError::InvalidStateExport.into());
+        }
+
+        let mut imported = MatchState::deserialize(&mut &state_bytes[..])?;
+        if imported.match_id != export.match_id {
+            return Err(crate::GameError::InvalidStateExport.into());
+        }
+
+        // Resume with a fresh turn clock
+        let clock = Clock::get()?;
+        imported.migration_locked = false;
+        imported.turn_deadline = clock.unix_timestamp + imported.turn_timeout;
+
+        let match_state = &mut ctx.accounts.match_state;
+        **match_state = imported;
+
+        msg!(
+            "Imported match {} from {} into {}",
+            export.match_id,
+            source_match,
+            match_state.key()
+        );
+
+        Ok(())
+    }
+}
+
+/// Domain-separated hash over the serialized match state
+pub fn match_state_hash(state_bytes: &[u8]) -> [u8; 32] {
+    hashv(&[b"solduel_match_state_v1", state_bytes]).to_bytes()
+}
+
+// Context definitions
+#[derive(Accounts)]
+pub struct ExportMatchState<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::SolduelBolt>,
+
+    #[account(constraint = program_data.upgrade_authority_address == Some(authority.key()) @ crate::GameError::UnauthorizedAction)]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(mut)]
+    pub match_state: Account<'info, MatchState>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + std::mem::size_of::<MatchStateExport>(),
+        seeds = [b"state_export", match_state.key().as_ref()],
+        bump
+    )]
+    pub state_export: Account<'info, MatchStateExport>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ImportMatchState<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::SolduelBolt>,
+
+    #[account(constraint = program_data.upgrade_authority_address == Some(authority.key()) @ crate::GameError::UnauthorizedAction)]
+    pub program_data: Account<'info, ProgramData>,
+
+    /// CHECK: Export record in the source program, validated against its PDA and owner
+    pub source_export: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<MatchState>(),
+    )]
+    pub match_state: Account<'info, MatchState>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -3,9 +3,21 @@ pub mod combat_system;
 pub mod match_system;
 pub mod turn_system;
 pub mod session_system;
+pub mod draft_system;
+pub mod migration_system;
+pub mod optimistic_queue_system;
+pub mod map_system;
+pub mod tick_schedule;
+pub mod movement_system;
 
 pub use player_system::*;
 pub use combat_system::*;
 pub use match_system::*;
 pub use turn_system::*;
-pub use session_system::*;
\ No newline at end of file
+pub use session_system::*;
+pub use draft_system::*;
+pub use migration_system::*;
+pub use optimistic_queue_system::*;
+pub use map_system::*;
+pub use tick_schedule::*;
+pub use movement_system::*;
\ No newline at end of file
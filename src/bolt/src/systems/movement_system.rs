@@ -0,0 +1,309 @@
+use bolt_lang::*;
+use crate::components::*;
+
+/// Absolute movement speed cap, in units per second, regardless of buffs
+pub const MAX_MOVEMENT_SPEED: u32 = 500;
+
+/// Idle time, in seconds, that counts toward a single move's distance budget
+pub const MAX_MOVEMENT_WINDOW: i64 = 5;
+
+/// Furthest a teleport ability can reach
+pub const MAX_TELEPORT_RANGE: u32 = 1000;
+
+/// How long speed and shield pickups last, in seconds
+pub const PICKUP_BUFF_DURATION: i64 = 10;
+
+/// Walk to a point in the match arena. Moves outside the arena, through
+/// obstacles or faster than the player's speed allows are rejected.
+pub mod move_player {
+    use super::*;
+
+    pub fn handler(ctx: Context<MovePlayer>, target_x: i32, target_y: i32) -> Result<()> {
+        let clock = Clock::get()?;
+        let slot = validate_mover(&ctx.accounts.match_state, &ctx.accounts.match_participants, &ctx.accounts.player.key())?;
+        require!(ctx.accounts.player_health.is_alive, crate::GameError::PlayerAlreadyDead);
+        require!(
+            !ctx.accounts.player_effects.has_effect(EffectType::Freeze),
+            crate::GameError::PlayerImmobilized
+        );
+
+        let match_state = &mut ctx.accounts.match_state;
+        let position = &mut ctx.accounts.player_position;
+        let map = &ctx.accounts.map_config;
+        enter_arena(position, match_state, map, slot);
+
+        // Time spent standing in a hazard is charged before the player leaves it
+        if apply_hazards(position, &mut ctx.accounts.player_health, &ctx.accounts.player_effects, map, clock.unix_timestamp) {
+            return Ok(());
+        }
+
+        let cooldowns = &mut ctx.accounts.player_cooldowns;
+        require!(
+            cooldowns.can_use_ability(AbilityType::Movement, clock.unix_timestamp),
+            crate::GameError::AbilityOnCooldown
+        );
+
+        // Validate target position and the path to it
+        let arena = &match_state.arena;
+        let (from_x, from_y) = (position.x.round() as i32, position.y.round() as i32);
+        require!(arena.contains(target_x, target_y, arena.min_z), crate::GameError::OutOfArenaBounds);
+        require!(
+            !arena.is_blocked(target_x, target_y) && arena.is_path_clear(from_x, from_y, target_x, target_y),
+            crate::GameError::PathBlocked
+        );
+
+        // Distance budget is the player's speed over the time since their last move
+        let distance = distance_between(from_x, from_y, target_x, target_y);
+        let elapsed = (clock.unix_timestamp - position.last_moved).clamp(0, MAX_MOVEMENT_WINDOW);
+        let effects = &ctx.accounts.player_effects;
+        let mut multiplier = position.movement_speed as f64;
+        for effect_type in [EffectType::SpeedBoost, EffectType::SpeedDebuff] {
+            if effects.has_effect(effect_type) {
+                multiplier *= effects.get_effect_strength(effect_type) as f64;
+            }
+        }
+        let speed = (ctx.accounts.player_stats.speed as f64 * multiplier).min(MAX_MOVEMENT_SPEED as f64);
+        require!(distance <= speed * elapsed as f64, crate::GameError::MovementTooFast);
+
+        place(position, target_x, target_y, clock.unix_timestamp);
+        cooldowns.use_ability(AbilityType::Movement, clock.unix_timestamp);
+        collect_pickup(
+            position,
+            &mut ctx.accounts.player_health,
+            &mut ctx.accounts.player_effects,
+            match_state,
+            map,
+            ctx.accounts.player.key(),
+            clock.unix_timestamp,
+        );
+
+        emit!(PlayerMovedEvent {
+            match_state: match_state.key(),
+            player: ctx.accounts.player.key(),
+            x: target_x,
+            y: target_y,
+            teleported: false,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+/// Teleport to a point in the match arena. Only the special and ultimate
+/// abilities can teleport, and doing so puts that ability on cooldown.
+pub mod teleport_player {
+    use super::*;
+
+    pub fn handler(ctx: Context<MovePlayer>, action_type: u8, target_x: i32, target_y: i32) -> Result<()> {
+        let clock = Clock::get()?;
+        let slot = validate_mover(&ctx.accounts.match_state, &ctx.accounts.match_participants, &ctx.accounts.player.key())?;
+        require!(ctx.accounts.player_health.is_alive, crate::GameError::PlayerAlreadyDead);
+        require!(
+            !ctx.accounts.player_effects.has_effect(EffectType::Freeze),
+            crate::GameError::PlayerImmobilized
+        );
+
+        let match_state = &mut ctx.accounts.match_state;
+        let ability_type = match action_type {
+            1 => AbilityType::SpecialAbility,
+            4 => AbilityType::Ultimate,
+            _ => return Err(crate::GameError::TeleportNotAllowed.into()),
+        };
+        if match_state.is_ability_banned(action_type) {
+            return Err(crate::GameError::BannedSelection.into());
+        }
+
+        let position = &mut ctx.accounts.player_position;
+        let map = &ctx.accounts.map_config;
+        enter_arena(position, match_state, map, slot);
+
+        if apply_hazards(position, &mut ctx.accounts.player_health, &ctx.accounts.player_effects, map, clock.unix_timestamp) {
+            return Ok(());
+        }
+
+        // Teleports skip the path check but must land on open ground within range
+        let arena = &match_state.arena;
+        let (from_x, from_y) = (position.x.round() as i32, position.y.round() as i32);
+        require!(
+            distance_between(from_x, from_y, target_x, target_y) <= MAX_TELEPORT_RANGE as f64,
+            crate::GameError::TargetOutOfRange
+        );
+        require!(arena.contains(target_x, target_y, arena.min_z), crate::GameError::OutOfArenaBounds);
+        require!(!arena.is_blocked(target_x, target_y), crate::GameError::PathBlocked);
+
+        // Spends the ability; fails while it is cooling down
+        require!(
+            ctx.accounts.player_cooldowns.use_ability(ability_type, clock.unix_timestamp),
+            crate::GameError::AbilityOnCooldown
+        );
+
+        place(position, target_x, target_y, clock.unix_timestamp);
+        collect_pickup(
+            position,
+            &mut ctx.accounts.player_health,
+            &mut ctx.accounts.player_effects,
+            match_state,
+            map,
+            ctx.accounts.player.key(),
+            clock.unix_timestamp,
+        );
+
+        emit!(PlayerMovedEvent {
+            match_state: match_state.key(),
+            player: ctx.accounts.player.key(),
+            x: target_x,
+            y: target_y,
+            teleported: true,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+/// The match must be running and the mover seated in it; returns their slot
+fn validate_mover(match_state: &MatchState, participants: &MatchParticipants, player: &Pubkey) -> Result<u8> {
+    if match_state.state != GameState::InProgress {
+        return Err(crate::GameError::InvalidGameState.into());
+    }
+    if match_state.migration_locked {
+        return Err(crate::GameError::MigrationLocked.into());
+    }
+    participants
+        .get_player_index(player)
+        .ok_or(crate::GameError::PlayerNotFound.into())
+}
+
+/// A player's first move in a match starts from their map spawn point
+fn enter_arena(position: &mut PlayerPosition, match_state: &MatchState, map: &MapConfig, slot: u8) {
+    let started_at = match_state.started_at.unwrap_or(0);
+    if position.last_moved < started_at {
+        let spawn = map.spawn_for_slot(slot);
+        position.x = spawn.x as f32;
+        position.y = spawn.y as f32;
+        position.last_moved = started_at;
+    }
+}
+
+/// Charge hazard damage for the time spent at the current spot, less any
+/// shield. Returns true if it killed the player.
+fn apply_hazards(
+    position: &PlayerPosition,
+    health: &mut PlayerHealth,
+    effects: &ActiveEffects,
+    map: &MapConfig,
+    current_time: i64,
+) -> bool {
+    let dwell = current_time - position.last_moved;
+    let damage = map
+        .hazard_damage_at(position.x.round() as i32, position.y.round() as i32, dwell)
+        .saturating_sub(effects.get_effect_strength(EffectType::Shield) as u32);
+    damage > 0 && health.take_damage(damage, current_time)
+}
+
+/// Apply the buff from a pickup at the player's position if it has respawned
+fn collect_pickup(
+    position: &PlayerPosition,
+    health: &mut PlayerHealth,
+    effects: &mut ActiveEffects,
+    match_state: &mut MatchState,
+    map: &MapConfig,
+    player: Pubkey,
+    current_time: i64,
+) {
+    let index = match map.pickup_at(position.x.round() as i32, position.y.round() as i32) {
+        Some(index) => index,
+        None => return,
+    };
+    if current_time < match_state.pickup_ready_at[index] {
+        return;
+    }
+
+    let pickup = map.pickups[index];
+    let buff = |effect_type, strength| StatusEffect {
+        effect_type,
+        strength,
+        duration: PICKUP_BUFF_DURATION,
+        expires_at: current_time + PICKUP_BUFF_DURATION,
+        caster: player,
+    };
+    let applied = match pickup.buff_type {
+        BUFF_HEAL => {
+            health.heal(pickup.amount, current_time);
+            true
+        }
+        BUFF_SPEED => effects.add_effect(buff(EffectType::SpeedBoost, 1.0 + pickup.amount as f32 / 100.0)),
+        BUFF_SHIELD => effects.add_effect(buff(EffectType::Shield, pickup.amount as f32)),
+        _ => false,
+    };
+
+    // A pickup that could not be applied stays on the map
+    if applied {
+        match_state.pickup_ready_at[index] = current_time + pickup.respawn_seconds;
+    }
+}
+
+fn place(position: &mut PlayerPosition, x: i32, y: i32, timestamp: i64) {
+    let (dx, dy) = (x as f32 - position.x, y as f32 - position.y);
+    if dx != 0.0 || dy != 0.0 {
+        position.facing_direction = dy.atan2(dx);
+    }
+    position.x = x as f32;
+    position.y = y as f32;
+    position.last_moved = timestamp;
+}
+
+fn distance_between(from_x: i32, from_y: i32, to_x: i32, to_y: i32) -> f64 {
+    let dx = to_x as f64 - from_x as f64;
+    let dy = to_y as f64 - from_y as f64;
+    (dx * dx + dy * dy).sqrt()
+}
+
+#[derive(Accounts)]
+pub struct MovePlayer<'info> {
+    pub player: Signer<'info>,
+
+    #[account(constraint = player_profile.owner == player.key() @ crate::GameError::UnauthorizedAction)]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    pub player_stats: Account<'info, PlayerStats>,
+
+    #[account(mut)]
+    pub player_health: Account<'info, PlayerHealth>,
+
+    #[account(mut)]
+    pub player_position: Account<'info, PlayerPosition>,
+
+    #[account(mut)]
+    pub player_cooldowns: Account<'info, AbilityCooldowns>,
+
+    #[account(mut)]
+    pub player_effects: Account<'info, ActiveEffects>,
+
+    #[account(
+        mut,
+        seeds = [b"match_state", match_state.creator.as_ref(), &match_state.created_at.to_le_bytes()],
+        bump
+    )]
+    pub match_state: Account<'info, MatchState>,
+
+    #[account(
+        seeds = [b"match_participants", match_state.key().as_ref()],
+        bump
+    )]
+    pub match_participants: Account<'info, MatchParticipants>,
+
+    #[account(constraint = map_config.key() == match_state.map_config @ crate::GameError::InvalidMapConfig)]
+    pub map_config: Account<'info, MapConfig>,
+}
+
+#[event]
+pub struct PlayerMovedEvent {
+    pub match_state: Pubkey,
+    pub player: Pubkey,
+    pub x: i32,
+    pub y: i32,
+    pub teleported: bool,
+    pub timestamp: i64,
+}
@@ -0,0 +1,177 @@
+use bolt_lang::*;
+use crate::components::*;
+
+pub mod init_optimistic_queue {
+    use super::*;
+
+    pub fn handler(ctx: Context<InitOptimisticQueue>, expiry: i64) -> Result<()> {
+        if expiry <= 0 {
+            return Err(crate::GameError::InvalidGameState.into());
+        }
+
+        let queue = &mut ctx.accounts.optimistic_queue;
+        queue.set_inner(OptimisticQueue {
+            entity: ctx.accounts.entity.key(),
+            expiry,
+            ..OptimisticQueue::default()
+        });
+
+        msg!("Optimistic queue created for {} with {}s expiry", ctx.accounts.entity.key(), expiry);
+
+        Ok(())
+    }
+}
+
+pub mod enqueue_optimistic_update {
+    use super::*;
+
+    pub fn handler(
+        ctx: Context<EnqueueOptimisticUpdate>,
+        action_type: u8,
+        payload_hash: [u8; 32],
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let session_delegation = &ctx.accounts.session_delegation;
+
+        if session_delegation.session_key != ctx.accounts.session_signer.key()
+            || !session_delegation.is_valid(clock.unix_timestamp)
+        {
+            return Err(crate::GameError::InvalidSessionKey.into());
+        }
+
+        let queue = &mut ctx.accounts.optimistic_queue;
+        let evicted_before = queue.total_evicted;
+        let id = queue.push(
+            ctx.accounts.session_signer.key(),
+            action_type,
+            payload_hash,
+            clock.unix_timestamp,
+        );
+
+        msg!(
+            "Optimistic update {} queued for {} ({} pending, {} evicted)",
+            id,
+            queue.entity,
+            queue.pending_count(),
+            queue.total_evicted - evicted_before
+        );
+
+        Ok(())
+    }
+}
+
+pub mod confirm_optimistic_update {
+    use super::*;
+
+    pub fn handler(ctx: Context<ConfirmOptimisticUpdate>, update_id: u64) -> Result<()> {
+        let queue = &mut ctx.accounts.optimistic_queue;
+
+        if !queue.confirm(update_id) {
+            return Err(crate::GameError::InvalidGameState.into());
+        }
+        queue.evict_expired(Clock::get()?.unix_timestamp);
+
+        msg!("Optimistic update {} confirmed for {}", update_id, queue.entity);
+
+        Ok(())
+    }
+}
+
+pub mod gc_optimistic_updates {
+    use super::*;
+
+    /// Permissionless crank that drops confirmed and expired entries
+    pub fn handler(ctx: Context<GcOptimisticUpdates>) -> Result<()> {
+        let clock = Clock::get()?;
+        let queue = &mut ctx.accounts.optimistic_queue;
+
+        let evicted = queue.evict_expired(clock.unix_timestamp);
+        queue.last_gc_at = clock.unix_timestamp;
+
+        msg!(
+            "Collected {} optimistic updates for {}, {} still pending",
+            evicted,
+            queue.entity,
+            queue.pending_count()
+        );
+
+        Ok(())
+    }
+}
+
+// Context definitions
+#[derive(Accounts)]
+pub struct InitOptimisticQueue<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Match state whose pending updates the queue tracks
+    pub entity: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<OptimisticQueue>(),
+        seeds = [b"optimistic_queue", entity.key().as_ref()],
+        bump
+    )]
+    pub optimistic_queue: Account<'info, OptimisticQueue>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct EnqueueOptimisticUpdate<'info> {
+    pub session_signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"session", session_delegation.authority.as_ref(), session_signer.key().as_ref()],
+        bump
+    )]
+    pub session_delegation: Account<'info, SessionDelegation>,
+
+    #[account(
+        mut,
+        seeds = [b"optimistic_queue", optimistic_queue.entity.as_ref()],
+        bump
+    )]
+    pub optimistic_queue: Account<'info, OptimisticQueue>,
+
+    #[account(
+        seeds = [b"match_participants", optimistic_queue.entity.as_ref()],
+        bump,
+        constraint = match_participants.is_player_in_match(&session_delegation.authority) @ crate::GameError::UnauthorizedAction
+    )]
+    pub match_participants: Account<'info, MatchParticipants>,
+}
+
+#[derive(Accounts)]
+pub struct ConfirmOptimisticUpdate<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"optimistic_queue", optimistic_queue.entity.as_ref()],
+        bump
+    )]
+    pub optimistic_queue: Account<'info, OptimisticQueue>,
+
+    #[account(
+        seeds = [b"ephemeral_state", optimistic_queue.entity.as_ref()],
+        bump,
+        constraint = ephemeral_state.commit_authority == authority.key() @ crate::GameError::UnauthorizedAction
+    )]
+    pub ephemeral_state: Account<'info, EphemeralState>,
+}
+
+#[derive(Accounts)]
+pub struct GcOptimisticUpdates<'info> {
+    pub cranker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"optimistic_queue", optimistic_queue.entity.as_ref()],
+        bump
+    )]
+    pub optimistic_queue: Account<'info, OptimisticQueue>,
+}
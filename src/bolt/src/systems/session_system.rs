@@ -280,6 +280,7 @@ pub mod update_session_permissions {
 
 // Context definitions
 #[derive(Accounts)]
+#[instruction(session_key: Pubkey)]
 pub struct DelegateSessionKey<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
@@ -288,6 +289,8 @@ pub struct DelegateSessionKey<'info> {
         init,
         payer = authority,
         space = 8 + std::mem::size_of::<SessionDelegation>(),
+        seeds = [b"session", authority.key().as_ref(), session_key.as_ref()],
+        bump
     )]
     pub session_delegation: Account<'info, SessionDelegation>,
     
@@ -330,6 +333,8 @@ pub struct CreateEphemeralSession<'info> {
         init,
         payer = authority,
         space = 8 + std::mem::size_of::<EphemeralState>(),
+        seeds = [b"ephemeral_state", authority.key().as_ref()],
+        bump
     )]
     pub ephemeral_state: Account<'info, EphemeralState>,
     
@@ -0,0 +1,302 @@
+use bolt_lang::*;
+use crate::components::*;
+
+/// Maximum number of systems a TickSchedule can drive
+pub const MAX_SCHEDULED_SYSTEMS: usize = 4;
+
+/// Default bounty paid to the caller per system executed, in lamports
+pub const DEFAULT_TICK_BOUNTY: u64 = 5_000;
+
+/// Mana restored per ManaRegen run
+pub const MANA_REGEN_PER_TICK: u32 = 5;
+
+/// Systems that can be driven by the TickSchedule. Each one processes the
+/// component accounts of its type passed to `run_due_systems`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ScheduledSystemId {
+    EffectExpiry = 0, // ActiveEffects: drop expired buffs/debuffs
+    ManaRegen = 1,    // PlayerHealth: restore mana for living players
+}
+
+impl ScheduledSystemId {
+    pub const ALL: [ScheduledSystemId; 2] = [Self::EffectExpiry, Self::ManaRegen];
+
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Self::EffectExpiry),
+            1 => Some(Self::ManaRegen),
+            _ => None,
+        }
+    }
+
+    /// Default run interval in slots (~400ms each)
+    pub fn default_interval_slots(&self) -> u64 {
+        match self {
+            Self::EffectExpiry => 3, // ~1.2 seconds
+            Self::ManaRegen => 25,   // ~10 seconds
+        }
+    }
+}
+
+/// Per-system timing entry in a TickSchedule
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct ScheduledSystem {
+    pub system_id: u8,       // ScheduledSystemId
+    pub interval_slots: u64, // Minimum slots between runs (0 = every slot)
+    pub last_executed_slot: u64,
+    pub enabled: bool,
+}
+
+impl ScheduledSystem {
+    pub fn is_due(&self, slot: u64) -> bool {
+        self.enabled && slot >= self.last_executed_slot.saturating_add(self.interval_slots.max(1))
+    }
+}
+
+/// On-chain tick schedule keyed off slot numbers, so every validator runs the
+/// same systems at the same point. Anyone may crank `run_due_systems`; the
+/// schedule pays them a bounty out of its balance.
+#[account]
+#[derive(Default)]
+pub struct TickSchedule {
+    pub authority: Pubkey,
+    pub bounty_per_system: u64,
+    pub systems: [ScheduledSystem; MAX_SCHEDULED_SYSTEMS],
+    pub system_count: u8,
+    pub total_runs: u64,
+    pub last_run_slot: u64,
+    pub last_run_at: i64,
+    pub total_bounties_paid: u64,
+    pub bump: u8,
+}
+
+impl TickSchedule {
+    pub fn active_entries(&self) -> &[ScheduledSystem] {
+        &self.systems[..self.system_count as usize]
+    }
+
+    pub fn entry_mut(&mut self, id: ScheduledSystemId) -> Option<&mut ScheduledSystem> {
+        let count = self.system_count as usize;
+        self.systems[..count].iter_mut().find(|entry| entry.system_id == id as u8)
+    }
+
+    pub fn register_defaults(&mut self) {
+        for id in ScheduledSystemId::ALL {
+            self.systems[self.system_count as usize] = ScheduledSystem {
+                system_id: id as u8,
+                interval_slots: id.default_interval_slots(),
+                last_executed_slot: 0,
+                enabled: true,
+            };
+            self.system_count += 1;
+        }
+    }
+
+    /// Systems due at `slot`, in registration order
+    pub fn due_systems(&self, slot: u64) -> Vec<ScheduledSystemId> {
+        self.active_entries()
+            .iter()
+            .filter(|entry| entry.is_due(slot))
+            .filter_map(|entry| ScheduledSystemId::from_u8(entry.system_id))
+            .collect()
+    }
+}
+
+/// Create the tick schedule with the default system intervals; upgrade authority only
+pub mod initialize_tick_schedule {
+    use super::*;
+
+    pub fn handler(
+        ctx: Context<InitializeTickSchedule>,
+        authority: Pubkey,
+        bounty_per_system: u64,
+    ) -> Result<()> {
+        let mut schedule = TickSchedule {
+            authority,
+            bounty_per_system,
+            last_run_slot: Clock::get()?.slot,
+            bump: ctx.bumps.tick_schedule,
+            ..TickSchedule::default()
+        };
+        schedule.register_defaults();
+
+        ctx.accounts.tick_schedule.set_inner(schedule);
+
+        Ok(())
+    }
+}
+
+/// Change a system's interval or enable/disable it
+pub mod configure_tick_schedule {
+    use super::*;
+
+    pub fn handler(
+        ctx: Context<ConfigureTickSchedule>,
+        system_id: u8,
+        interval_slots: u64,
+        enabled: bool,
+    ) -> Result<()> {
+        let id = ScheduledSystemId::from_u8(system_id).ok_or(crate::GameError::UnknownSystem)?;
+        let entry = ctx.accounts.tick_schedule
+            .entry_mut(id)
+            .ok_or(crate::GameError::UnknownSystem)?;
+
+        entry.interval_slots = interval_slots;
+        entry.enabled = enabled;
+
+        Ok(())
+    }
+}
+
+/// Permissionless crank: run every due system over the component accounts
+/// passed as remaining accounts and pay the caller a bounty per system that
+/// processed at least one entity
+pub mod run_due_systems {
+    use super::*;
+
+    pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, RunDueSystems<'info>>) -> Result<()> {
+        let clock = Clock::get()?;
+        let due = ctx.accounts.tick_schedule.due_systems(clock.slot);
+        require!(!due.is_empty(), crate::GameError::NoSystemsDue);
+
+        let mut executed: u64 = 0;
+        let mut entities_processed: u32 = 0;
+        for id in due {
+            let processed = run_system(id, ctx.remaining_accounts, clock.unix_timestamp)?;
+            if processed == 0 {
+                continue;
+            }
+            entities_processed += processed;
+            executed += 1;
+            if let Some(entry) = ctx.accounts.tick_schedule.entry_mut(id) {
+                entry.last_executed_slot = clock.slot;
+            }
+        }
+
+        // Nothing processed means nothing to pay for
+        require!(executed > 0, crate::GameError::NoSystemsDue);
+
+        let schedule = &mut ctx.accounts.tick_schedule;
+        schedule.total_runs += 1;
+        schedule.last_run_slot = clock.slot;
+        schedule.last_run_at = clock.unix_timestamp;
+
+        // Bounty comes from the schedule's balance above rent; an unfunded schedule still runs
+        let schedule_info = schedule.to_account_info();
+        let rent_floor = Rent::get()?.minimum_balance(schedule_info.data_len());
+        let bounty = schedule.bounty_per_system
+            .saturating_mul(executed)
+            .min(schedule_info.lamports().saturating_sub(rent_floor));
+
+        if bounty > 0 {
+            **schedule_info.try_borrow_mut_lamports()? -= bounty;
+            **ctx.accounts.caller.to_account_info().try_borrow_mut_lamports()? += bounty;
+            schedule.total_bounties_paid += bounty;
+        }
+
+        emit!(SystemsTickedEvent {
+            slot: clock.slot,
+            tick_number: schedule.total_runs,
+            systems_executed: executed as u8,
+            entities_processed,
+            caller: ctx.accounts.caller.key(),
+            bounty,
+        });
+
+        Ok(())
+    }
+
+    /// Apply one system to every remaining account holding its component;
+    /// accounts of other types and repeated accounts are skipped
+    fn run_system<'info>(
+        id: ScheduledSystemId,
+        accounts: &'info [AccountInfo<'info>],
+        current_time: i64,
+    ) -> Result<u32> {
+        let mut processed = 0;
+        for (index, info) in accounts.iter().enumerate() {
+            if !info.is_writable || accounts[..index].iter().any(|other| other.key == info.key) {
+                continue;
+            }
+            match id {
+                ScheduledSystemId::EffectExpiry => {
+                    let Ok(mut effects) = Account::<ActiveEffects>::try_from(info) else { continue };
+                    effects.update_effects(current_time);
+                    effects.exit(&crate::ID)?;
+                }
+                ScheduledSystemId::ManaRegen => {
+                    let Ok(mut health) = Account::<PlayerHealth>::try_from(info) else { continue };
+                    if !health.is_alive {
+                        continue;
+                    }
+                    health.restore_mana(MANA_REGEN_PER_TICK);
+                    health.exit(&crate::ID)?;
+                }
+            }
+            processed += 1;
+        }
+        Ok(processed)
+    }
+}
+
+// Context definitions
+
+#[derive(Accounts)]
+pub struct InitializeTickSchedule<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::SolduelBolt>,
+
+    #[account(constraint = program_data.upgrade_authority_address == Some(payer.key()) @ crate::GameError::UnauthorizedAction)]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<TickSchedule>(),
+        seeds = [b"tick_schedule"],
+        bump
+    )]
+    pub tick_schedule: Account<'info, TickSchedule>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureTickSchedule<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"tick_schedule"],
+        bump = tick_schedule.bump,
+        has_one = authority @ crate::GameError::UnauthorizedAction
+    )]
+    pub tick_schedule: Account<'info, TickSchedule>,
+}
+
+#[derive(Accounts)]
+pub struct RunDueSystems<'info> {
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"tick_schedule"],
+        bump = tick_schedule.bump
+    )]
+    pub tick_schedule: Account<'info, TickSchedule>,
+}
+
+#[event]
+pub struct SystemsTickedEvent {
+    pub slot: u64,
+    pub tick_number: u64,
+    pub systems_executed: u8,
+    pub entities_processed: u32,
+    pub caller: Pubkey,
+    pub bounty: u64,
+}
@@ -13,6 +13,9 @@ pub mod process_turn {
         if match_state.state != GameState::InProgress {
             return Err(crate::GameError::InvalidGameState.into());
         }
+        if match_state.migration_locked {
+            return Err(crate::GameError::MigrationLocked.into());
+        }
 
         // Check if current turn has timed out
         if clock.unix_timestamp > match_state.turn_deadline {
@@ -1,7 +1,10 @@
 use anchor_lang::prelude::*;
-use crate::{Component, ComponentData, ComponentTypeId, Entity};
+use crate::{notify_subscribers, ChangeKind, Component, ComponentData, ComponentTypeId, Entity};
 
-pub fn handler(ctx: Context<AddComponent>, component_data: ComponentData) -> Result<()> {
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AddComponent<'info>>,
+    component_data: ComponentData,
+) -> Result<()> {
     let component = &mut ctx.accounts.component;
     let entity = &mut ctx.accounts.entity;
     let clock = Clock::get()?;
@@ -32,6 +35,16 @@ pub fn handler(ctx: Context<AddComponent>, component_data: ComponentData) -> Res
     entity.add_component_mask(component_type);
     entity.touch()?;
 
+    // Subscriptions to notify are passed as remaining accounts
+    notify_subscribers(
+        ctx.remaining_accounts,
+        entity,
+        component_type,
+        ChangeKind::Added,
+        component.version,
+        clock.unix_timestamp,
+    )?;
+
     emit!(ComponentAdded {
         entity_id: entity.id,
         component_type,
@@ -5,10 +5,12 @@ use bytemuck::{Pod, Zeroable};
 pub mod add_component;
 pub mod update_component;
 pub mod remove_component;
+pub mod subscription;
 
 pub use add_component::*;
 pub use update_component::*;
 pub use remove_component::*;
+pub use subscription::*;
 
 /// Component type identifiers for bitmask operations
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
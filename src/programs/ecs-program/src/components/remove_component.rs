@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
-use crate::{Component, Entity};
+use crate::{notify_subscribers, ChangeKind, Component, Entity};
 
-pub fn handler(ctx: Context<RemoveComponent>) -> Result<()> {
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, RemoveComponent<'info>>) -> Result<()> {
     let component = &ctx.accounts.component;
     let entity = &mut ctx.accounts.entity;
     let clock = Clock::get()?;
@@ -15,6 +15,16 @@ pub fn handler(ctx: Context<RemoveComponent>) -> Result<()> {
     entity.remove_component_mask(component.component_type);
     entity.touch()?;
 
+    // Subscriptions to notify are passed as remaining accounts
+    notify_subscribers(
+        ctx.remaining_accounts,
+        entity,
+        component.component_type,
+        ChangeKind::Removed,
+        component.version,
+        clock.unix_timestamp,
+    )?;
+
     emit!(ComponentRemoved {
         entity_id: entity.id,
         component_type: component.component_type,
@@ -0,0 +1,179 @@
+use anchor_lang::prelude::*;
+use crate::{ComponentTypeId, Entity, Subscribe, Unsubscribe};
+
+/// Notifications held per subscription before the oldest are overwritten
+pub const SUBSCRIPTION_BUFFER_SIZE: usize = 32;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ChangeKind {
+    #[default]
+    Added,
+    Updated,
+    Removed,
+}
+
+/// Compact record of a single component change
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct ChangeNotification {
+    pub sequence: u64,
+    pub component_type: u8,
+    pub kind: ChangeKind,
+    pub version: u32,
+    pub timestamp: i64,
+}
+
+impl ChangeNotification {
+    pub const SIZE: usize = 8 + 1 + 1 + 4 + 8;
+}
+
+/// Client interest in a set of component types on one entity.
+/// Mutating instructions append to `notifications` as a ring buffer; clients
+/// poll this account and only refetch the components that changed. Writers
+/// may leave a subscription out, so `ComponentChanged` events fill any gaps.
+#[account]
+#[derive(Debug)]
+pub struct Subscription {
+    pub subscriber: Pubkey,
+    pub entity_id: u64,
+    pub component_mask: u64,
+    pub next_sequence: u64, // Sequence the next notification will carry
+    pub head: u8,           // Slot the next notification is written to
+    pub notifications: [ChangeNotification; SUBSCRIPTION_BUFFER_SIZE],
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl Subscription {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // subscriber
+        8 + // entity_id
+        8 + // component_mask
+        8 + // next_sequence
+        1 + // head
+        (ChangeNotification::SIZE * SUBSCRIPTION_BUFFER_SIZE) + // notifications
+        8 + // created_at
+        1; // bump
+
+    pub fn mask_for(component_types: &[ComponentTypeId]) -> u64 {
+        component_types
+            .iter()
+            .fold(0u64, |mask, component_type| mask | (1 << (*component_type as u64)))
+    }
+
+    pub fn is_watching(&self, component_type: ComponentTypeId) -> bool {
+        (self.component_mask & (1 << (component_type as u64))) != 0
+    }
+
+    /// Append a notification, overwriting the oldest once the buffer is full.
+    /// Clients detect dropped notifications by a gap in `sequence`.
+    pub fn push(&mut self, component_type: ComponentTypeId, kind: ChangeKind, version: u32, timestamp: i64) {
+        self.notifications[self.head as usize] = ChangeNotification {
+            sequence: self.next_sequence,
+            component_type: component_type as u8,
+            kind,
+            version,
+            timestamp,
+        };
+        self.next_sequence += 1;
+        self.head = ((self.head as usize + 1) % SUBSCRIPTION_BUFFER_SIZE) as u8;
+    }
+}
+
+/// Register interest in component changes on an entity
+pub fn subscribe(ctx: Context<Subscribe>, component_types: Vec<ComponentTypeId>) -> Result<()> {
+    if component_types.is_empty() {
+        return Err(SubscriptionError::EmptySubscription.into());
+    }
+
+    let subscription = &mut ctx.accounts.subscription;
+    let clock = Clock::get()?;
+
+    subscription.subscriber = ctx.accounts.subscriber.key();
+    subscription.entity_id = ctx.accounts.entity.id;
+    subscription.component_mask = Subscription::mask_for(&component_types);
+    subscription.next_sequence = 0;
+    subscription.head = 0;
+    subscription.notifications = [ChangeNotification::default(); SUBSCRIPTION_BUFFER_SIZE];
+    subscription.created_at = clock.unix_timestamp;
+    subscription.bump = ctx.bumps.subscription;
+
+    emit!(SubscriptionCreated {
+        subscriber: subscription.subscriber,
+        entity_id: subscription.entity_id,
+        component_mask: subscription.component_mask,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Drop a subscription and reclaim its rent
+pub fn unsubscribe(_ctx: Context<Unsubscribe>) -> Result<()> {
+    Ok(())
+}
+
+/// Emit the change, then append it to every subscription passed in
+/// `subscriptions`. Called by the instructions that add, update or remove
+/// components. The caller picks which buffers are written, so the event is
+/// the complete record; buffers are a polling convenience.
+pub fn notify_subscribers<'info>(
+    subscriptions: &'info [AccountInfo<'info>],
+    entity: &Entity,
+    component_type: ComponentTypeId,
+    kind: ChangeKind,
+    version: u32,
+    timestamp: i64,
+) -> Result<()> {
+    emit!(ComponentChanged {
+        entity_id: entity.id,
+        component_type: component_type as u8,
+        kind,
+        version,
+        timestamp,
+    });
+
+    for account_info in subscriptions.iter() {
+        if !account_info.is_writable {
+            return Err(SubscriptionError::SubscriptionNotWritable.into());
+        }
+
+        let mut subscription: Account<Subscription> = Account::try_from(account_info)?;
+        if subscription.entity_id != entity.id {
+            return Err(SubscriptionError::SubscriptionEntityMismatch.into());
+        }
+
+        if subscription.is_watching(component_type) {
+            subscription.push(component_type, kind, version, timestamp);
+            subscription.exit(&crate::ID)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[event]
+pub struct ComponentChanged {
+    pub entity_id: u64,
+    pub component_type: u8,
+    pub kind: ChangeKind,
+    pub version: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SubscriptionCreated {
+    pub subscriber: Pubkey,
+    pub entity_id: u64,
+    pub component_mask: u64,
+    pub timestamp: i64,
+}
+
+#[error_code]
+pub enum SubscriptionError {
+    #[msg("Subscription must watch at least one component type")]
+    EmptySubscription,
+    #[msg("Subscription account must be writable")]
+    SubscriptionNotWritable,
+    #[msg("Subscription does not belong to this entity")]
+    SubscriptionEntityMismatch,
+}
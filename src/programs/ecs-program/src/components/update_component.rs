@@ -1,7 +1,10 @@
 use anchor_lang::prelude::*;
-use crate::{Component, ComponentData, Entity};
+use crate::{notify_subscribers, ChangeKind, Component, ComponentData, Entity};
 
-pub fn handler(ctx: Context<UpdateComponent>, component_data: ComponentData) -> Result<()> {
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, UpdateComponent<'info>>,
+    component_data: ComponentData,
+) -> Result<()> {
     let component = &mut ctx.accounts.component;
     let entity = &mut ctx.accounts.entity;
     let clock = Clock::get()?;
@@ -30,6 +33,16 @@ pub fn handler(ctx: Context<UpdateComponent>, component_data: ComponentData) ->
     // Update entity timestamp
     entity.touch()?;
 
+    // Subscriptions to notify are passed as remaining accounts
+    notify_subscribers(
+        ctx.remaining_accounts,
+        entity,
+        component.component_type,
+        ChangeKind::Updated,
+        component.version,
+        clock.unix_timestamp,
+    )?;
+
     emit!(ComponentUpdated {
         entity_id: entity.id,
         component_type: component.component_type,
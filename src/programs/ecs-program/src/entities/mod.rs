@@ -163,6 +163,36 @@ pub struct EntityQueryResult {
     pub count: u32,
 }
 
+/// Largest page a single `query_entities` call will write
+pub const MAX_QUERY_PAGE_SIZE: u16 = 64;
+
+/// Per-requester page of query results, rewritten on every `query_entities` call.
+/// Clients walk large worlds by feeding `next_cursor` back in until `is_complete`.
+#[account]
+#[derive(Default, Debug)]
+pub struct QueryResultCache {
+    pub requester: Pubkey,
+    pub cursor: u64,       // First entity ID scanned for this page
+    pub next_cursor: u64,  // Entity ID to resume from
+    pub entities: Vec<u64>, // Matching entity IDs in this page
+    pub scanned: u32,
+    pub is_complete: bool, // No entities remain past `next_cursor`
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl QueryResultCache {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // requester
+        8 + // cursor
+        8 + // next_cursor
+        4 + (MAX_QUERY_PAGE_SIZE as usize * 8) + // entities vec
+        4 + // scanned
+        1 + // is_complete
+        8 + // updated_at
+        1; // bump
+}
+
 /// Entity factory for creating common entity patterns
 pub struct EntityFactory;
 
@@ -224,4 +254,8 @@ pub enum EntityError {
     ArchetypeFull,
     #[msg("Invalid component mask")]
     InvalidComponentMask,
+    #[msg("Query cursor is past the end of the world")]
+    InvalidQueryCursor,
+    #[msg("Query page size is out of range")]
+    InvalidPageSize,
 }
\ No newline at end of file
@@ -29,20 +29,30 @@ pub mod ecs_program {
     }
 
     /// Add component to entity
-    pub fn add_component(ctx: Context<AddComponent>, component_data: ComponentData) -> Result<()> {
+    pub fn add_component<'info>(ctx: Context<'_, '_, 'info, 'info, AddComponent<'info>>, component_data: ComponentData) -> Result<()> {
         components::add_component::handler(ctx, component_data)
     }
 
     /// Update component data
-    pub fn update_component(ctx: Context<UpdateComponent>, component_data: ComponentData) -> Result<()> {
+    pub fn update_component<'info>(ctx: Context<'_, '_, 'info, 'info, UpdateComponent<'info>>, component_data: ComponentData) -> Result<()> {
         components::update_component::handler(ctx, component_data)
     }
 
     /// Remove component from entity
-    pub fn remove_component(ctx: Context<RemoveComponent>) -> Result<()> {
+    pub fn remove_component<'info>(ctx: Context<'_, '_, 'info, 'info, RemoveComponent<'info>>) -> Result<()> {
         components::remove_component::handler(ctx)
     }
 
+    /// Subscribe to change notifications for components on an entity
+    pub fn subscribe(ctx: Context<Subscribe>, component_types: Vec<ComponentTypeId>) -> Result<()> {
+        components::subscription::subscribe(ctx, component_types)
+    }
+
+    /// Close a subscription
+    pub fn unsubscribe(ctx: Context<Unsubscribe>) -> Result<()> {
+        components::subscription::unsubscribe(ctx)
+    }
+
     /// Execute movement system
     pub fn execute_movement_system(ctx: Context<ExecuteMovementSystem>) -> Result<()> {
         systems::movement_system::handler(ctx)
@@ -73,9 +83,14 @@ pub mod ecs_program {
         systems::commit_system::handler(ctx)
     }
 
-    /// Query entities with specific components
-    pub fn query_entities(ctx: Context<QueryEntities>, query: ComponentQuery) -> Result<()> {
-        world::query_system::handler(ctx, query)
+    /// Query entities with specific components, one page at a time from `cursor`
+    pub fn query_entities<'info>(
+        ctx: Context<'_, '_, 'info, 'info, QueryEntities<'info>>,
+        query: ComponentQuery,
+        cursor: u64,
+        page_size: u16,
+    ) -> Result<()> {
+        world::query_system::handler(ctx, query, cursor, page_size)
     }
 }
 
@@ -156,6 +171,36 @@ pub struct UpdateComponent<'info> {
     pub authority: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct Subscribe<'info> {
+    #[account(
+        init,
+        payer = subscriber,
+        space = Subscription::SIZE,
+        seeds = [b"subscription", subscriber.key().as_ref(), &entity.id.to_le_bytes()],
+        bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+    pub entity: Account<'info, Entity>,
+    #[account(mut)]
+    pub subscriber: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Unsubscribe<'info> {
+    #[account(
+        mut,
+        close = subscriber,
+        has_one = subscriber,
+        seeds = [b"subscription", subscriber.key().as_ref(), &subscription.entity_id.to_le_bytes()],
+        bump = subscription.bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+    #[account(mut)]
+    pub subscriber: Signer<'info>,
+}
+
 #[derive(Accounts)]
 pub struct RemoveComponent<'info> {
     #[account(
@@ -250,5 +295,30 @@ pub struct QueryEntities<'info> {
         bump
     )]
     pub world: Account<'info, World>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = QueryResultCache::SIZE,
+        seeds = [b"query_cache", authority.key().as_ref()],
+        bump
+    )]
+    pub query_cache: Account<'info, QueryResultCache>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = shared::RateLimit::LEN,
+        seeds = [b"rate_limit", authority.key().as_ref(), &[shared::RATE_LIMIT_QUERY_ENTITIES]],
+        bump
+    )]
+    pub rate_limit: Account<'info, shared::RateLimit>,
+    /// CHECK: Deployment rate limits owned by strategic-duel; required once initialized
+    #[account(
+        seeds = [shared::RATE_LIMIT_CONFIG_SEED],
+        seeds::program = shared::STRATEGIC_DUEL_PROGRAM_ID,
+        bump
+    )]
+    pub rate_limit_config: UncheckedAccount<'info>,
+    #[account(mut)]
     pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
 }
\ No newline at end of file
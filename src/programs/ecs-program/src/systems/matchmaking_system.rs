@@ -104,6 +104,7 @@ pub struct MatchmakingPlayer {
     pub player_class: crate::shared::PlayerClass,
     pub level: u32,
     pub win_rate: f32,
+    pub lifetime_winnings: u64,
 }
 
 impl MatchmakingPlayer {
@@ -113,6 +114,7 @@ impl MatchmakingPlayer {
         skill_rating: u32,
         player_class: crate::shared::PlayerClass,
         level: u32,
+        lifetime_winnings: u64,
     ) -> Self {
         let current_time = Clock::get().unwrap().unix_timestamp;
         
@@ -126,9 +128,15 @@ impl MatchmakingPlayer {
             player_class,
             level,
             win_rate: 0.5, // Default 50% win rate
+            lifetime_winnings,
         }
     }
 
+    /// Skill rating lifted to the floor implied by lifetime winnings
+    pub fn matchmaking_rating(&self) -> u32 {
+        shared::matchmaking_rating(self.skill_rating, self.lifetime_winnings)
+    }
+
     pub fn get_wait_time(&self, current_time: i64) -> i64 {
         current_time - self.queue_time
     }
@@ -202,8 +210,8 @@ impl MatchmakingQueue {
     fn skill_based_matching(&mut self, match_size: u32) -> Vec<Vec<MatchmakingPlayer>> {
         let mut matches = Vec::new();
         
-        // Sort players by skill rating
-        self.players.sort_by_key(|p| p.skill_rating);
+        // Sort players by skill rating, floored by winnings so sandbaggers can't reach low tables
+        self.players.sort_by_key(|p| p.matchmaking_rating());
         
         // Group players with similar skill levels
         let mut current_match = Vec::new();
@@ -211,14 +219,10 @@ impl MatchmakingQueue {
         
         for player in self.players.drain(..) {
             if current_match.is_empty() {
-                base_skill = player.skill_rating;
+                base_skill = player.matchmaking_rating();
                 current_match.push(player);
             } else if current_match.len() < match_size as usize {
-                let skill_diff = if player.skill_rating > base_skill {
-                    player.skill_rating - base_skill
-                } else {
-                    base_skill - player.skill_rating
-                };
+                let skill_diff = player.matchmaking_rating().abs_diff(base_skill);
                 
                 if skill_diff <= self.skill_tolerance {
                     current_match.push(player);
@@ -231,14 +235,14 @@ impl MatchmakingQueue {
                         self.players.extend(current_match.drain(..));
                     }
                     current_match.clear();
-                    base_skill = player.skill_rating;
+                    base_skill = player.matchmaking_rating();
                     current_match.push(player);
                 }
             } else {
                 // Current match is full
                 matches.push(current_match.clone());
                 current_match.clear();
-                base_skill = player.skill_rating;
+                base_skill = player.matchmaking_rating();
                 current_match.push(player);
             }
         }
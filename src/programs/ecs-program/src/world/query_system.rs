@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use crate::{ComponentQuery, Entity, EntityError, QueryEntities, MAX_QUERY_PAGE_SIZE};
+
+/// Scan one page of the world starting at `cursor` and write the matching
+/// entity IDs into the requester's QueryResultCache.
+///
+/// Entity accounts are passed as remaining accounts in ascending ID order,
+/// beginning with the entity whose ID equals `cursor`. Scanning stops once
+/// `page_size` matches are found or the accounts run out, and `next_cursor`
+/// records where the following call should resume.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, QueryEntities<'info>>,
+    query: ComponentQuery,
+    cursor: u64,
+    page_size: u16,
+) -> Result<()> {
+    if page_size == 0 || page_size > MAX_QUERY_PAGE_SIZE {
+        return Err(EntityError::InvalidPageSize.into());
+    }
+
+    // Paging through the world is cheap to request and costly to serve
+    let now = Clock::get()?.unix_timestamp;
+    let rate_limit = &mut ctx.accounts.rate_limit;
+    if rate_limit.player == Pubkey::default() {
+        rate_limit.player = ctx.accounts.authority.key();
+        rate_limit.action = shared::RATE_LIMIT_QUERY_ENTITIES;
+        rate_limit.window_start = now;
+    }
+    let window = shared::RateLimitConfig::window_for(&ctx.accounts.rate_limit_config, shared::RATE_LIMIT_QUERY_ENTITIES)?;
+    rate_limit.consume(window, now)?;
+
+    let world = &ctx.accounts.world;
+    if cursor > world.entity_count {
+        return Err(EntityError::InvalidQueryCursor.into());
+    }
+
+    let page_limit = (page_size as u32).min(query.max_results.max(1)) as usize;
+    let mut entities = Vec::with_capacity(page_limit);
+    let mut next_cursor = cursor;
+
+    for account_info in ctx.remaining_accounts.iter() {
+        if entities.len() >= page_limit || next_cursor >= world.entity_count {
+            break;
+        }
+
+        // Owner and discriminator are checked on load; the ID check keeps the
+        // walk contiguous so no entity can be skipped between pages
+        let entity: Account<Entity> = Account::try_from(account_info)?;
+        if entity.id != next_cursor {
+            return Err(EntityError::InvalidQueryCursor.into());
+        }
+
+        if query.matches_entity(&entity) {
+            entities.push(entity.id);
+        }
+        next_cursor += 1;
+    }
+
+    let clock = Clock::get()?;
+    let cache = &mut ctx.accounts.query_cache;
+    cache.requester = ctx.accounts.authority.key();
+    cache.cursor = cursor;
+    cache.next_cursor = next_cursor;
+    cache.scanned = (next_cursor - cursor) as u32;
+    cache.is_complete = next_cursor >= world.entity_count;
+    cache.entities = entities;
+    cache.updated_at = clock.unix_timestamp;
+    cache.bump = ctx.bumps.query_cache;
+
+    emit!(QueryPageWritten {
+        requester: cache.requester,
+        cursor,
+        next_cursor,
+        result_count: cache.entities.len() as u32,
+        is_complete: cache.is_complete,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct QueryPageWritten {
+    pub requester: Pubkey,
+    pub cursor: u64,
+    pub next_cursor: u64,
+    pub result_count: u32,
+    pub is_complete: bool,
+    pub timestamp: i64,
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use shared::magicblock::delegation::{DelegatedAccountType, PERMISSION_WRITE};
+
+/// `diff_hash` is `FinalDiff::hash` of the diff, which settlement later replays
+pub fn handler(
+    ctx: Context<super::CommitCheckpoint>,
+    seq: u64,
+    diff_hash: [u8; 32],
+) -> Result<()> {
+    let checkpoint = &mut ctx.accounts.checkpoint;
+    let match_er = &mut ctx.accounts.match_er;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    ctx.accounts.delegation_state.require_permission(DelegatedAccountType::Match, PERMISSION_WRITE)?;
+    ctx.accounts.account_map.validate(&ctx.accounts.delegation_state.key(), &ctx.accounts.delegation_state)?;
+
+    if checkpoint.match_er == Pubkey::default() {
+        checkpoint.match_er = match_er.key();
+        checkpoint.bump = ctx.bumps.checkpoint;
+    }
+
+    checkpoint.advance(seq, diff_hash, current_time)?;
+    match_er.mainnet_sync_status = MainnetSyncStatus::Pending;
+    match_er.last_component_update = current_time;
+
+    emit!(CheckpointCommitted {
+        match_id: match_er.match_id,
+        seq,
+        diff_hash,
+        state_hash: checkpoint.state_hash,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct CheckpointCommitted {
+    pub match_id: u64,
+    pub seq: u64,
+    pub diff_hash: [u8; 32],
+    pub state_hash: [u8; 32],
+    pub timestamp: i64,
+}
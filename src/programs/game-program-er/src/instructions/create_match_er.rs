@@ -61,6 +61,7 @@ pub fn handler(
     match_er.force_ended = false;
     match_er.force_ended_by = None;
     match_er.cancel_reason = None;
+    match_er.cancel_memo_hash = None;
     match_er.rollback_requested = false;
     match_er.rollback_reason = None;
     match_er.bump = ctx.bumps.match_er;
@@ -38,6 +38,7 @@ pub fn handler(
     delegation_state.expiry_timestamp = Some(current_time + game_state_er.delegation_expiry_default);
     delegation_state.is_active = true;
     delegation_state.pending_commits = Vec::new();
+    delegation_state.permissions = DelegationState::default_permissions();
     delegation_state.bump = ctx.bumps.delegation_state;
 
     // Initialize router config
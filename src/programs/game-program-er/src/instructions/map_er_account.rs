@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+/// Record (or refresh for a new delegation epoch) the ER address of a delegated mainnet PDA
+pub fn handler(ctx: Context<super::MapErAccount>, er_account: Pubkey) -> Result<()> {
+    let account_map = &mut ctx.accounts.account_map;
+    let delegation_state = &ctx.accounts.delegation_state;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    if delegation_state
+        .account_type_of(&ctx.accounts.mainnet_account.key())
+        .is_none()
+    {
+        return Err(shared::GameError::StaleAccountMapping.into());
+    }
+
+    // An existing mapping can only be refreshed through the delegation that created it
+    if account_map.delegation_state != Pubkey::default()
+        && account_map.delegation_state != delegation_state.key()
+    {
+        return Err(shared::GameError::UnauthorizedPlayer.into());
+    }
+
+    account_map.mainnet_account = ctx.accounts.mainnet_account.key();
+    account_map.er_account = er_account;
+    account_map.delegation_state = delegation_state.key();
+    account_map.delegation_epoch = delegation_state.delegation_timestamp;
+    account_map.mapped_at = current_time;
+    account_map.bump = ctx.bumps.account_map;
+
+    emit!(ErAccountMapped {
+        mainnet_account: account_map.mainnet_account,
+        er_account,
+        delegation_epoch: account_map.delegation_epoch,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ErAccountMapped {
+    pub mainnet_account: Pubkey,
+    pub er_account: Pubkey,
+    pub delegation_epoch: i64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use shared::magicblock::delegation::{DelegationState, LegacyDelegationState};
+use shared::GameError;
+
+/// Grow a delegation written before the permission matrix existed. The
+/// session carries over unchanged with the default matrix. Permissionless;
+/// the payer covers the extra rent.
+pub fn handler(ctx: Context<super::MigrateDelegationState>, delegator: Pubkey) -> Result<()> {
+    let state_info = ctx.accounts.delegation_state.to_account_info();
+    if state_info.data_len() >= DelegationState::LEN {
+        return Err(GameError::InvalidGameState.into());
+    }
+
+    let legacy = {
+        let data = state_info.try_borrow_data()?;
+        if data.len() < 8 || data[..8] != *DelegationState::DISCRIMINATOR {
+            return Err(ErrorCode::AccountDiscriminatorMismatch.into());
+        }
+        LegacyDelegationState::deserialize(&mut &data[8..])?
+    };
+    let current = legacy.into_current();
+
+    let required = Rent::get()?.minimum_balance(DelegationState::LEN);
+    let top_up = required.saturating_sub(state_info.lamports());
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer { from: ctx.accounts.payer.to_account_info(), to: state_info.clone() },
+            ),
+            top_up,
+        )?;
+    }
+    state_info.realloc(DelegationState::LEN, true)?;
+    current.try_serialize(&mut &mut state_info.try_borrow_mut_data()?[..])?;
+
+    emit!(DelegationStateMigrated {
+        delegator,
+        permissions: current.permissions,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct DelegationStateMigrated {
+    pub delegator: Pubkey,
+    pub permissions: [u8; shared::magicblock::delegation::DELEGATED_ACCOUNT_TYPE_COUNT],
+    pub timestamp: i64,
+}
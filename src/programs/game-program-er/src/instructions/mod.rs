@@ -7,11 +7,20 @@ pub mod execute_action_er;
 pub mod end_turn_er;
 pub mod finish_match_er;
 pub mod delegate_game_state;
+pub mod narrow_delegation;
+pub mod map_er_account;
 pub mod commit_er_results;
+pub mod commit_checkpoint;
+pub mod settle_from_checkpoint;
 pub mod rollback_er_state;
+pub mod snapshot_components;
+pub mod record_er_fault;
+pub mod restore_from_snapshot;
 pub mod initialize_player_components;
 pub mod update_player_stats_ecs;
 pub mod emergency_stop_match_er;
+pub mod migrate_delegation_state;
+pub mod program_info;
 
 pub use initialize_game_er::*;
 pub use register_player_er::*;
@@ -22,8 +31,17 @@ pub use execute_action_er::*;
 pub use end_turn_er::*;
 pub use finish_match_er::*;
 pub use delegate_game_state::*;
+pub use narrow_delegation::*;
+pub use map_er_account::*;
 pub use commit_er_results::*;
+pub use commit_checkpoint::*;
+pub use settle_from_checkpoint::*;
 pub use rollback_er_state::*;
+pub use snapshot_components::*;
+pub use record_er_fault::*;
+pub use restore_from_snapshot::*;
 pub use initialize_player_components::*;
 pub use update_player_stats_ecs::*;
-pub use emergency_stop_match_er::*;
\ No newline at end of file
+pub use emergency_stop_match_er::*;
+pub use migrate_delegation_state::*;
+pub use program_info::*;
\ No newline at end of file
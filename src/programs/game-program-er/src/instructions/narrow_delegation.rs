@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use shared::magicblock::delegation::DelegatedAccountType;
+
+/// Reduce the permissions an active delegation grants for one account type.
+/// Permissions can only shrink mid-session; widening needs a new delegation.
+pub fn handler(
+    ctx: Context<super::NarrowDelegation>,
+    account_type: DelegatedAccountType,
+    permissions: u8,
+) -> Result<()> {
+    let delegation_state = &mut ctx.accounts.delegation_state;
+    let previous = delegation_state.permissions[account_type.index()];
+
+    delegation_state.narrow(account_type, permissions)?;
+
+    emit!(DelegationNarrowed {
+        delegator: delegation_state.delegator,
+        account_type,
+        previous,
+        permissions,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct DelegationNarrowed {
+    pub delegator: Pubkey,
+    pub account_type: DelegatedAccountType,
+    pub previous: u8,
+    pub permissions: u8,
+    pub timestamp: i64,
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use shared::GameError;
+
+/// Record the deployed version, commit and IDL hash - upgrade authority only
+pub fn handler(
+    ctx: Context<super::UpdateProgramInfo>,
+    version: [u16; 3],
+    git_commit: [u8; 20],
+    idl_hash: [u8; 32],
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let deployed_slot = ctx.accounts.program_data.slot;
+    let info = &mut ctx.accounts.program_info;
+    
+    // A stale upgrade must not overwrite the record of a newer one
+    if deployed_slot < info.deployed_slot {
+        return Err(GameError::StaleProgramInfo.into());
+    }
+    
+    info.program_id = crate::ID;
+    info.version = version;
+    info.git_commit = git_commit;
+    info.idl_hash = idl_hash;
+    info.deployed_slot = deployed_slot;
+    info.updated_by = ctx.accounts.authority.key();
+    info.updated_at = clock.unix_timestamp;
+    info.bump = ctx.bumps.program_info;
+    
+    emit!(ProgramInfoUpdated {
+        version,
+        git_commit,
+        idl_hash,
+        deployed_slot,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct ProgramInfoUpdated {
+    pub version: [u16; 3],
+    pub git_commit: [u8; 20],
+    pub idl_hash: [u8; 32],
+    pub deployed_slot: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use shared::ReasonCode;
+
+/// Record a proven fault in a player's ER session, opening the restore path
+/// for snapshots taken before it
+pub fn handler(ctx: Context<super::RecordErFault>, reason: ReasonCode, evidence_hash: [u8; 32]) -> Result<()> {
+    let fault = &mut ctx.accounts.er_fault;
+    let delegation_state = &ctx.accounts.delegation_state;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    fault.delegation_state = delegation_state.key();
+    fault.delegation_epoch = delegation_state.delegation_timestamp;
+    fault.reason = reason;
+    fault.evidence_hash = evidence_hash;
+    fault.proven_by = ctx.accounts.authority.key();
+    fault.proven_at = current_time;
+    fault.bump = ctx.bumps.er_fault;
+
+    emit!(ErFaultRecorded {
+        delegation_state: fault.delegation_state,
+        delegation_epoch: fault.delegation_epoch,
+        reason,
+        evidence_hash,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ErFaultRecorded {
+    pub delegation_state: Pubkey,
+    pub delegation_epoch: i64,
+    pub reason: ReasonCode,
+    pub evidence_hash: [u8; 32],
+    pub timestamp: i64,
+}
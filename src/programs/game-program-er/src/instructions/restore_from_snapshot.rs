@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+/// Write a verified snapshot back over the player's components, undoing
+/// whatever the ER did to them. Authority-only, and only for a snapshot taken
+/// before the session's recorded fault.
+pub fn handler(ctx: Context<super::RestoreFromSnapshot>) -> Result<()> {
+    let snapshot = &mut ctx.accounts.snapshot;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    snapshot.verify(
+        &ctx.accounts.delegation_state.key(),
+        &ctx.accounts.delegation_state,
+        &ctx.accounts.er_fault,
+    )?;
+
+    if snapshot.component_mask & SNAPSHOT_POSITION != 0 {
+        let position = &mut ctx.accounts.position;
+        position.x = snapshot.position[0];
+        position.y = snapshot.position[1];
+        position.z = snapshot.position[2];
+    }
+    if snapshot.component_mask & SNAPSHOT_HEALTH != 0 {
+        let health = &mut ctx.accounts.health;
+        health.current = snapshot.health[0];
+        health.max = snapshot.health[1];
+        health.regeneration_rate = snapshot.health[2];
+    }
+    if snapshot.component_mask & SNAPSHOT_MANA != 0 {
+        let mana = &mut ctx.accounts.mana;
+        mana.current = snapshot.mana[0];
+        mana.max = snapshot.mana[1];
+        mana.regeneration_rate = snapshot.mana[2];
+    }
+    snapshot.restored = true;
+
+    emit!(ComponentsRestored {
+        player: snapshot.player,
+        restored_by: ctx.accounts.authority.key(),
+        delegation_epoch: snapshot.delegation_epoch,
+        component_mask: snapshot.component_mask,
+        snapshot_hash: snapshot.snapshot_hash,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ComponentsRestored {
+    pub player: Pubkey,
+    pub restored_by: Pubkey,
+    pub delegation_epoch: i64,
+    pub component_mask: u8,
+    pub snapshot_hash: [u8; 32],
+    pub timestamp: i64,
+}
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use shared::GameState as SharedGameState;
+use shared::magicblock::delegation::{DelegatedAccountType, PERMISSION_WRITE};
+
+pub fn handler(
+    ctx: Context<super::SettleFromCheckpoint>,
+    checkpoint_diffs: Vec<FinalDiff>,
+    final_diff: FinalDiff,
+) -> Result<()> {
+    let checkpoint = &mut ctx.accounts.checkpoint;
+    let match_er = &mut ctx.accounts.match_er;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    if !matches!(match_er.state, SharedGameState::WaitingForPlayers | SharedGameState::InProgress) {
+        return Err(shared::GameError::InvalidGameState.into());
+    }
+
+    let delegation_state = &ctx.accounts.delegation_state;
+    ctx.accounts.account_map.validate(&ctx.accounts.delegation_state.key(), &ctx.accounts.delegation_state)?;
+    delegation_state.require_permission(DelegatedAccountType::Match, PERMISSION_WRITE)?;
+    if checkpoint_diffs.iter().chain(std::iter::once(&final_diff)).any(|diff| !diff.changed_players.is_empty()) {
+        delegation_state.require_permission(DelegatedAccountType::PlayerStats, PERMISSION_WRITE)?;
+    }
+
+    // Every committed diff must hash back to the checkpoint chain before any is applied;
+    // the final diff is then the last link
+    checkpoint.verify_diffs(&checkpoint_diffs)?;
+    checkpoint.advance(final_diff.seq, final_diff.hash()?, current_time)?;
+    checkpoint.settled = true;
+
+    for update in checkpoint_diffs.iter().chain(std::iter::once(&final_diff)).flat_map(|diff| &diff.changed_players) {
+        let player = match_er.get_player_mut(&update.player)
+            .ok_or(shared::GameError::PlayerNotFound)?;
+        player.current_health = update.current_health;
+        player.damage_dealt = update.damage_dealt;
+        player.damage_taken = update.damage_taken;
+        player.is_alive = update.is_alive;
+    }
+
+    if let Some(winner) = final_diff.winner {
+        if !match_er.players.iter().any(|p| p.player == winner) {
+            return Err(shared::GameError::PlayerNotFound.into());
+        }
+    }
+
+    match_er.winner = final_diff.winner;
+    match_er.state = SharedGameState::Completed;
+    match_er.ended_at = Some(final_diff.ended_at);
+    match_er.mainnet_sync_status = MainnetSyncStatus::Committed;
+    match_er.last_component_update = current_time;
+
+    ctx.accounts.winner_attestation.set_inner(shared::WinnerAttestation::new(
+        crate::ID,
+        match_er.key(),
+        match_er.match_id,
+        final_diff.winner,
+        ctx.accounts.delegator.key(),
+        current_time,
+        ctx.bumps.winner_attestation,
+    ));
+
+    emit!(CheckpointSettled {
+        match_id: match_er.match_id,
+        final_seq: final_diff.seq,
+        state_hash: checkpoint.state_hash,
+        winner: final_diff.winner,
+        timestamp: current_time,
+    });
+
+    msg!("Match ER {} settled from checkpoint {}", match_er.match_id, final_diff.seq);
+
+    Ok(())
+}
+
+#[event]
+pub struct CheckpointSettled {
+    pub match_id: u64,
+    pub final_seq: u64,
+    pub state_hash: [u8; 32],
+    pub winner: Option<Pubkey>,
+    pub timestamp: i64,
+}
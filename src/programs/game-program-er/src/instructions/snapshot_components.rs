@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+/// Capture the selected components before they are delegated to the ER.
+/// Re-snapshotting chains onto the previous snapshot's hash.
+pub fn handler(ctx: Context<super::SnapshotComponents>, component_mask: u8) -> Result<()> {
+    if component_mask == 0 || component_mask & !SNAPSHOT_ALL != 0 {
+        return Err(shared::GameError::InvalidSnapshot.into());
+    }
+
+    let snapshot = &mut ctx.accounts.snapshot;
+    let delegation_state = &ctx.accounts.delegation_state;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    if snapshot.player == Pubkey::default() {
+        snapshot.player = ctx.accounts.player.key();
+        snapshot.bump = ctx.bumps.snapshot;
+    } else {
+        snapshot.prev_hash = snapshot.snapshot_hash;
+        snapshot.sequence += 1;
+    }
+
+    let position = &ctx.accounts.position;
+    let health = &ctx.accounts.health;
+    let mana = &ctx.accounts.mana;
+
+    snapshot.delegation_state = delegation_state.key();
+    snapshot.delegation_epoch = delegation_state.delegation_timestamp;
+    snapshot.component_mask = component_mask;
+    snapshot.position = [position.x, position.y, position.z];
+    snapshot.health = [health.current, health.max, health.regeneration_rate];
+    snapshot.mana = [mana.current, mana.max, mana.regeneration_rate];
+    snapshot.taken_at = current_time;
+    snapshot.restored = false;
+    snapshot.snapshot_hash = snapshot.compute_hash();
+
+    emit!(ComponentsSnapshotted {
+        player: snapshot.player,
+        delegation_epoch: snapshot.delegation_epoch,
+        component_mask,
+        sequence: snapshot.sequence,
+        snapshot_hash: snapshot.snapshot_hash,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ComponentsSnapshotted {
+    pub player: Pubkey,
+    pub delegation_epoch: i64,
+    pub component_mask: u8,
+    pub sequence: u32,
+    pub snapshot_hash: [u8; 32],
+    pub timestamp: i64,
+}
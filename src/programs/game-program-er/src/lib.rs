@@ -15,7 +15,7 @@ use shared::magicblock::{
     router::*,
     state_management::*,
 };
-use shared::{GameError, GameState as SharedGameState, PlayerClass, PlayerStats, CombatAction, MatchConfig, MAX_PLAYERS_PER_MATCH};
+use shared::{GameError, GameState as SharedGameState, PlayerClass, PlayerStats, CombatAction, MatchConfig, MAX_PLAYERS_PER_MATCH, ReasonCode};
 
 declare_id!("ERGMxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx");
 
@@ -90,6 +90,20 @@ pub mod sol_duel_game_er {
         instructions::delegate_game_state::handler(ctx, accounts_to_delegate, expiry_timestamp)
     }
 
+    /// Drop delegated permissions for an account type mid-session
+    pub fn narrow_delegation(
+        ctx: Context<NarrowDelegation>,
+        account_type: DelegatedAccountType,
+        permissions: u8,
+    ) -> Result<()> {
+        instructions::narrow_delegation::handler(ctx, account_type, permissions)
+    }
+
+    /// Record the ER address of a delegated mainnet account for the current delegation
+    pub fn map_er_account(ctx: Context<MapErAccount>, er_account: Pubkey) -> Result<()> {
+        instructions::map_er_account::handler(ctx, er_account)
+    }
+
     /// Commit ER results back to mainnet
     pub fn commit_er_results(
         ctx: Context<CommitErResults>,
@@ -98,11 +112,48 @@ pub mod sol_duel_game_er {
         instructions::commit_er_results::handler(ctx, match_results)
     }
 
+    /// Post an incremental state-diff hash for a delegated match
+    pub fn commit_checkpoint(
+        ctx: Context<CommitCheckpoint>,
+        seq: u64,
+        diff_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::commit_checkpoint::handler(ctx, seq, diff_hash)
+    }
+
+    /// Settle a delegated match by replaying every committed diff plus the final one
+    pub fn settle_from_checkpoint(
+        ctx: Context<SettleFromCheckpoint>,
+        checkpoint_diffs: Vec<FinalDiff>,
+        final_diff: FinalDiff,
+    ) -> Result<()> {
+        instructions::settle_from_checkpoint::handler(ctx, checkpoint_diffs, final_diff)
+    }
+
     /// Rollback ER state if needed
     pub fn rollback_er_state(ctx: Context<RollbackErState>) -> Result<()> {
         instructions::rollback_er_state::handler(ctx)
     }
 
+    /// Snapshot a player's components at delegation time for later rollback
+    pub fn snapshot_components(ctx: Context<SnapshotComponents>, component_mask: u8) -> Result<()> {
+        instructions::snapshot_components::handler(ctx, component_mask)
+    }
+
+    /// Record a proven fault in a player's ER session (upgrade authority only)
+    pub fn record_er_fault(
+        ctx: Context<RecordErFault>,
+        reason: ReasonCode,
+        evidence_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::record_er_fault::handler(ctx, reason, evidence_hash)
+    }
+
+    /// Verify and restore a player's components from a snapshot taken before a recorded fault
+    pub fn restore_from_snapshot(ctx: Context<RestoreFromSnapshot>) -> Result<()> {
+        instructions::restore_from_snapshot::handler(ctx)
+    }
+
     /// Initialize BOLT ECS components for a player
     pub fn initialize_player_components(
         ctx: Context<InitializePlayerComponents>,
@@ -119,10 +170,25 @@ pub mod sol_duel_game_er {
         instructions::update_player_stats_ecs::handler(ctx, experience_gained)
     }
 
+    /// Grow a delegation written before the permission matrix to the current layout
+    pub fn migrate_delegation_state(ctx: Context<MigrateDelegationState>, delegator: Pubkey) -> Result<()> {
+        instructions::migrate_delegation_state::handler(ctx, delegator)
+    }
+
     /// Emergency stop with cross-chain coordination
     pub fn emergency_stop_match_er(ctx: Context<EmergencyStopMatchEr>) -> Result<()> {
         instructions::emergency_stop_match_er::handler(ctx)
     }
+
+    /// Self-report version, commit and IDL hash after an upgrade - upgrade authority only
+    pub fn update_program_info(
+        ctx: Context<UpdateProgramInfo>,
+        version: [u16; 3],
+        git_commit: [u8; 20],
+        idl_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::program_info::handler(ctx, version, git_commit, idl_hash)
+    }
 }
 
 #[derive(Accounts)]
@@ -298,7 +364,19 @@ pub struct ExecuteActionEr<'info> {
     #[account(
         mut,
         seeds = [b"match_er", match_er.creator.as_ref(), &match_er.created_at.to_le_bytes()],
-        bump = match_er.bump
+        bump = match_er.bump,
+        constraint = DelegationState::permits(
+            match_er.delegation_state,
+            delegation_state.as_ref(),
+            DelegatedAccountType::Match,
+            PERMISSION_WRITE,
+        ) @ GameError::DelegationPermissionDenied,
+        constraint = DelegationState::permits(
+            match_er.delegation_state,
+            delegation_state.as_ref(),
+            DelegatedAccountType::PlayerStats,
+            PERMISSION_WRITE,
+        ) @ GameError::DelegationPermissionDenied
     )]
     pub match_er: Account<'info, MatchEr>,
 
@@ -342,8 +420,16 @@ pub struct ExecuteActionEr<'info> {
 
     // Target components (when applicable)
     /// CHECK: Target player for combat
+    #[account(constraint = shared::is_wallet(&target_player) @ GameError::InvalidAccountOwner)]
     pub target_player: UncheckedAccount<'info>,
 
+    /// Required while the match is delegated
+    #[account(
+        seeds = [b"delegation", match_er.creator.as_ref()],
+        bump = delegation_state.bump
+    )]
+    pub delegation_state: Option<Account<'info, DelegationState>>,
+
     #[account(mut)]
     pub player: Signer<'info>,
     
@@ -376,7 +462,13 @@ pub struct EndTurnEr<'info> {
     #[account(
         mut,
         seeds = [b"match_er", match_er.creator.as_ref(), &match_er.created_at.to_le_bytes()],
-        bump = match_er.bump
+        bump = match_er.bump,
+        constraint = DelegationState::permits(
+            match_er.delegation_state,
+            delegation_state.as_ref(),
+            DelegatedAccountType::Match,
+            PERMISSION_WRITE,
+        ) @ GameError::DelegationPermissionDenied
     )]
     pub match_er: Account<'info, MatchEr>,
 
@@ -387,6 +479,13 @@ pub struct EndTurnEr<'info> {
     )]
     pub match_state: Account<'info, MatchState>,
 
+    /// Required while the match is delegated
+    #[account(
+        seeds = [b"delegation", match_er.creator.as_ref()],
+        bump = delegation_state.bump
+    )]
+    pub delegation_state: Option<Account<'info, DelegationState>>,
+
     #[account(mut)]
     pub player: Signer<'info>,
 }
@@ -410,7 +509,14 @@ pub struct FinishMatchEr<'info> {
     #[account(
         mut,
         seeds = [b"delegation", match_er.creator.as_ref()],
-        bump
+        bump = delegation_state.bump,
+        // Finishing writes the result and closes the match out of the rollup
+        constraint = DelegationState::permits(
+            match_er.delegation_state,
+            Some(&delegation_state),
+            DelegatedAccountType::Match,
+            PERMISSION_WRITE | PERMISSION_CLOSE,
+        ) @ GameError::DelegationPermissionDenied
     )]
     pub delegation_state: Account<'info, DelegationState>,
 
@@ -430,6 +536,10 @@ pub struct DelegateGameState<'info> {
     pub delegation_state: Account<'info, DelegationState>,
 
     /// CHECK: Ephemeral rollup program
+    #[account(
+        constraint = shared::is_program(&ephemeral_rollup, &delegation_state.ephemeral_rollup)
+            @ GameError::InvalidProgramAccount
+    )]
     pub ephemeral_rollup: UncheckedAccount<'info>,
 
     #[account(mut)]
@@ -437,15 +547,73 @@ pub struct DelegateGameState<'info> {
 }
 
 #[derive(Accounts)]
+pub struct NarrowDelegation<'info> {
+    #[account(
+        mut,
+        seeds = [b"delegation", delegator.key().as_ref()],
+        bump = delegation_state.bump,
+        constraint = delegation_state.is_active
+    )]
+    pub delegation_state: Account<'info, DelegationState>,
+
+    pub delegator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MapErAccount<'info> {
+    #[account(
+        init_if_needed,
+        payer = delegator,
+        space = ErAccountMap::LEN,
+        seeds = [b"er_account_map", mainnet_account.key().as_ref()],
+        bump
+    )]
+    pub account_map: Account<'info, ErAccountMap>,
+
+    /// CHECK: Delegated mainnet PDA; must be listed in the delegation
+    #[account(constraint = !mainnet_account.data_is_empty() @ GameError::InvalidAccountOwner)]
+    pub mainnet_account: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"delegation", delegator.key().as_ref()],
+        bump = delegation_state.bump,
+        constraint = delegation_state.is_active
+    )]
+    pub delegation_state: Account<'info, DelegationState>,
+
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(match_results: MatchResults)]
 pub struct CommitErResults<'info> {
     #[account(
         mut,
         seeds = [b"delegation", delegator.key().as_ref()],
-        bump = delegation_state.bump
+        bump = delegation_state.bump,
+        constraint = delegation_state.has_permission(DelegatedAccountType::Match, PERMISSION_WRITE)
+            && delegation_state.has_permission(DelegatedAccountType::PlayerStats, PERMISSION_WRITE)
+            @ GameError::DelegationPermissionDenied,
+        constraint = match_results.token_rewards.is_empty()
+            || delegation_state.has_permission(DelegatedAccountType::TokenAccount, PERMISSION_TRANSFER_VALUE)
+            @ GameError::DelegationPermissionDenied
     )]
     pub delegation_state: Account<'info, DelegationState>,
 
+    #[account(
+        seeds = [b"game_state_er"],
+        bump
+    )]
+    pub game_state_er: Account<'info, GameStateEr>,
+
     /// CHECK: Mainnet game program
+    #[account(
+        constraint = shared::is_program(&mainnet_game_program, &game_state_er.mainnet_program_id)
+            @ GameError::InvalidProgramAccount
+    )]
     pub mainnet_game_program: UncheckedAccount<'info>,
 
     #[account(mut)]
@@ -454,12 +622,97 @@ pub struct CommitErResults<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct CommitCheckpoint<'info> {
+    #[account(
+        mut,
+        seeds = [b"match_er", match_er.creator.as_ref(), &match_er.created_at.to_le_bytes()],
+        bump = match_er.bump,
+        constraint = match_er.delegation_state == Some(delegation_state.key()) @ GameError::AccessDenied
+    )]
+    pub match_er: Account<'info, MatchEr>,
+
+    #[account(
+        init_if_needed,
+        payer = delegator,
+        space = ErCheckpoint::LEN,
+        seeds = [b"er_checkpoint", match_er.key().as_ref()],
+        bump
+    )]
+    pub checkpoint: Account<'info, ErCheckpoint>,
+
+    #[account(
+        seeds = [b"er_account_map", match_er.key().as_ref()],
+        bump = account_map.bump
+    )]
+    pub account_map: Account<'info, ErAccountMap>,
+
+    #[account(
+        seeds = [b"delegation", delegator.key().as_ref()],
+        bump = delegation_state.bump,
+        constraint = delegation_state.is_active
+    )]
+    pub delegation_state: Account<'info, DelegationState>,
+
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SettleFromCheckpoint<'info> {
+    #[account(
+        mut,
+        seeds = [b"match_er", match_er.creator.as_ref(), &match_er.created_at.to_le_bytes()],
+        bump = match_er.bump,
+        constraint = match_er.delegation_state == Some(delegation_state.key()) @ GameError::AccessDenied
+    )]
+    pub match_er: Account<'info, MatchEr>,
+
+    #[account(
+        mut,
+        seeds = [b"er_checkpoint", match_er.key().as_ref()],
+        bump = checkpoint.bump
+    )]
+    pub checkpoint: Account<'info, ErCheckpoint>,
+
+    #[account(
+        seeds = [b"er_account_map", match_er.key().as_ref()],
+        bump = account_map.bump
+    )]
+    pub account_map: Account<'info, ErAccountMap>,
+
+    #[account(
+        seeds = [b"delegation", delegator.key().as_ref()],
+        bump = delegation_state.bump,
+        constraint = delegation_state.is_active
+    )]
+    pub delegation_state: Account<'info, DelegationState>,
+
+    /// Standard result record read by escrows, quests and tournaments
+    #[account(
+        init,
+        payer = delegator,
+        space = shared::WinnerAttestation::LEN,
+        seeds = [shared::WINNER_ATTESTATION_SEED, match_er.key().as_ref()],
+        bump
+    )]
+    pub winner_attestation: Account<'info, shared::WinnerAttestation>,
+
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 pub struct RollbackErState<'info> {
     #[account(
         mut,
         seeds = [b"delegation", delegator.key().as_ref()],
-        bump = delegation_state.bump
+        bump = delegation_state.bump,
+        constraint = delegation_state.can_roll_back() @ GameError::DelegationPermissionDenied
     )]
     pub delegation_state: Account<'info, DelegationState>,
 
@@ -467,6 +720,140 @@ pub struct RollbackErState<'info> {
     pub delegator: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct SnapshotComponents<'info> {
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = ComponentSnapshot::LEN,
+        seeds = [b"component_snapshot", player.key().as_ref()],
+        bump
+    )]
+    pub snapshot: Account<'info, ComponentSnapshot>,
+
+    #[account(
+        seeds = [b"position", player.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(
+        seeds = [b"health", player.key().as_ref()],
+        bump
+    )]
+    pub health: Account<'info, Health>,
+
+    #[account(
+        seeds = [b"mana", player.key().as_ref()],
+        bump
+    )]
+    pub mana: Account<'info, Mana>,
+
+    #[account(
+        seeds = [b"delegation", player.key().as_ref()],
+        bump = delegation_state.bump,
+        constraint = delegation_state.is_active
+    )]
+    pub delegation_state: Account<'info, DelegationState>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RestoreFromSnapshot<'info> {
+    #[account(
+        mut,
+        seeds = [b"component_snapshot", player.key().as_ref()],
+        bump = snapshot.bump
+    )]
+    pub snapshot: Account<'info, ComponentSnapshot>,
+
+    #[account(
+        mut,
+        seeds = [b"position", player.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(
+        mut,
+        seeds = [b"health", player.key().as_ref()],
+        bump
+    )]
+    pub health: Account<'info, Health>,
+
+    #[account(
+        mut,
+        seeds = [b"mana", player.key().as_ref()],
+        bump
+    )]
+    pub mana: Account<'info, Mana>,
+
+    #[account(
+        seeds = [b"delegation", player.key().as_ref()],
+        bump = delegation_state.bump
+    )]
+    pub delegation_state: Account<'info, DelegationState>,
+
+    #[account(
+        seeds = [b"er_fault", delegation_state.key().as_ref()],
+        bump = er_fault.bump
+    )]
+    pub er_fault: Account<'info, ErFault>,
+
+    #[account(
+        seeds = [b"game_state_er"],
+        bump
+    )]
+    pub game_state_er: Account<'info, GameStateEr>,
+
+    /// CHECK: Player whose components are restored
+    #[account(constraint = shared::is_wallet(&player) @ GameError::InvalidAccountOwner)]
+    pub player: UncheckedAccount<'info>,
+
+    /// Restores go through the upgrade authority; a player cannot roll back their own state
+    #[account(constraint = authority.key() == game_state_er.upgrade_authority @ GameError::AccessDenied)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RecordErFault<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = ErFault::LEN,
+        seeds = [b"er_fault", delegation_state.key().as_ref()],
+        bump
+    )]
+    pub er_fault: Account<'info, ErFault>,
+
+    #[account(
+        seeds = [b"delegation", player.key().as_ref()],
+        bump = delegation_state.bump
+    )]
+    pub delegation_state: Account<'info, DelegationState>,
+
+    /// CHECK: Player whose session faulted; only used to derive the delegation
+    pub player: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"game_state_er"],
+        bump
+    )]
+    pub game_state_er: Account<'info, GameStateEr>,
+
+    #[account(
+        mut,
+        constraint = authority.key() == game_state_er.upgrade_authority @ GameError::AccessDenied
+    )]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 pub struct InitializePlayerComponents<'info> {
     #[account(
@@ -532,14 +919,41 @@ pub struct UpdatePlayerStatsEcs<'info> {
     #[account(
         mut,
         seeds = [b"player_er", player.key().as_ref()],
-        bump
+        bump,
+        constraint = DelegationState::permits(
+            player_profile.current_delegation_state,
+            delegation_state.as_ref(),
+            DelegatedAccountType::PlayerStats,
+            PERMISSION_WRITE,
+        ) @ GameError::DelegationPermissionDenied
     )]
     pub player_profile: Account<'info, PlayerProfileEr>,
 
+    /// The profile's current delegation, required while it has one
+    pub delegation_state: Option<Account<'info, DelegationState>>,
+
     #[account(mut)]
     pub player: Signer<'info>,
 }
 
+#[derive(Accounts)]
+#[instruction(delegator: Pubkey)]
+pub struct MigrateDelegationState<'info> {
+    /// CHECK: Read with the pre-permission layout, so it cannot be deserialized up front
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"delegation", delegator.as_ref()],
+        bump
+    )]
+    pub delegation_state: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 pub struct EmergencyStopMatchEr<'info> {
     #[account(
@@ -596,4 +1010,112 @@ pub struct FinalPlayerResult {
     pub damage_taken: u32,
     pub actions_taken: u32,
     pub placement: u8,
-}
\ No newline at end of file
+}
+
+#[derive(Accounts)]
+pub struct UpdateProgramInfo<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = shared::ProgramInfo::LEN,
+        seeds = [b"program_info"],
+        bump
+    )]
+    pub program_info: Account<'info, shared::ProgramInfo>,
+    
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::SolDuelGameEr>,
+    
+    #[account(constraint = program_data.upgrade_authority_address == Some(authority.key()) @ GameError::InvalidUpgradeAuthority)]
+    pub program_data: Account<'info, ProgramData>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(delegation: Pubkey, epoch: i64, taken_at: i64) -> ComponentSnapshot {
+        ComponentSnapshot {
+            player: Pubkey::new_unique(),
+            delegation_state: delegation,
+            delegation_epoch: epoch,
+            component_mask: SNAPSHOT_ALL,
+            position: [0; 3],
+            health: [100, 100, 1],
+            mana: [50, 50, 1],
+            sequence: 0,
+            prev_hash: [0; 32],
+            snapshot_hash: [0; 32],
+            taken_at,
+            restored: false,
+            bump: 255,
+        }
+    }
+
+    fn fault(delegation: Pubkey, epoch: i64, proven_at: i64) -> ErFault {
+        ErFault {
+            delegation_state: delegation,
+            delegation_epoch: epoch,
+            reason: ReasonCode::RollupOutage,
+            evidence_hash: [0; 32],
+            proven_by: Pubkey::new_unique(),
+            proven_at,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_player_cannot_roll_back_mid_match() {
+        let delegation = Pubkey::new_unique();
+        // Snapshot re-taken mid-match, after the only recorded fault
+        let late = snapshot(delegation, 1_000, 1_500);
+        assert!(!fault(delegation, 1_000, 1_200).covers(&delegation, &late));
+
+        // A fault from an earlier session or another delegation opens nothing
+        let early = snapshot(delegation, 1_000, 1_100);
+        assert!(!fault(delegation, 900, 1_200).covers(&delegation, &early));
+        assert!(!fault(Pubkey::new_unique(), 1_000, 1_200).covers(&delegation, &early));
+
+        // Only a snapshot from before a proven fault in the same session is restorable
+        assert!(fault(delegation, 1_000, 1_200).covers(&delegation, &early));
+    }
+
+    #[test]
+    fn test_rollback_needs_write_on_every_modified_account() {
+        let delegated = |account_type, is_modified| DelegatedAccount {
+            account_pubkey: Pubkey::new_unique(),
+            account_type,
+            original_data_hash: [0; 32],
+            current_data_hash: [1; 32],
+            is_modified,
+        };
+        let mut state = DelegationState {
+            delegator: Pubkey::new_unique(),
+            ephemeral_rollup: Pubkey::new_unique(),
+            original_owner: Pubkey::new_unique(),
+            delegated_accounts: vec![
+                delegated(DelegatedAccountType::Match, true),
+                delegated(DelegatedAccountType::TokenAccount, false),
+            ],
+            delegation_timestamp: 0,
+            expiry_timestamp: None,
+            is_active: true,
+            pending_commits: Vec::new(),
+            permissions: DelegationState::default_permissions(),
+            bump: 255,
+        };
+        assert!(state.can_roll_back());
+
+        // Untouched accounts do not need write access
+        state.narrow(DelegatedAccountType::TokenAccount, PERMISSION_READ).unwrap();
+        assert!(state.can_roll_back());
+
+        state.narrow(DelegatedAccountType::Match, PERMISSION_READ).unwrap();
+        assert!(!state.can_roll_back());
+    }
+}
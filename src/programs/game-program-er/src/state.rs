@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
-use shared::{GameState as SharedGameState, PlayerClass, PlayerStats, MatchConfig, MAX_PLAYERS_PER_MATCH, MAX_USERNAME_LENGTH, AdminConfig};
-use shared::magicblock::delegation::{DelegatedAccountType, PendingCommit, CommitType};
+use shared::{GameState as SharedGameState, PlayerClass, PlayerStats, MatchConfig, MAX_PLAYERS_PER_MATCH, MAX_USERNAME_LENGTH, AdminConfig, ReasonCode};
+use shared::magicblock::delegation::{DelegatedAccountType, DelegationState, PendingCommit, CommitType};
 
 /// Enhanced game state for Ephemeral Rollup integration
 #[account]
@@ -147,9 +147,10 @@ pub struct MatchEr {
     // Admin control fields
     pub force_ended: bool,
     pub force_ended_by: Option<Pubkey>,
-    pub cancel_reason: Option<String>,
+    pub cancel_reason: Option<ReasonCode>,
+    pub cancel_memo_hash: Option<[u8; 32]>,
     pub rollback_requested: bool,
-    pub rollback_reason: Option<String>,
+    pub rollback_reason: Option<ReasonCode>,
     
     pub bump: u8,
 }
@@ -176,9 +177,10 @@ impl MatchEr {
         8 + // last_component_update
         1 + // force_ended
         1 + 32 + // force_ended_by
-        4 + 256 + // cancel_reason
+        1 + 1 + // cancel_reason
+        1 + 32 + // cancel_memo_hash
         1 + // rollback_requested
-        4 + 256 + // rollback_reason
+        1 + 1 + // rollback_reason
         1; // bump
 
     pub fn is_player_turn(&self, player: &Pubkey) -> bool {
@@ -444,6 +446,235 @@ impl CombatResultEr {
         8; // timestamp
 }
 
+/// Rolling commitment to a delegated match's state, advanced by periodic diffs
+/// from the ER so settlement only has to carry the changes since the last one
+#[account]
+pub struct ErCheckpoint {
+    pub match_er: Pubkey,
+    pub last_seq: u64,
+    pub state_hash: [u8; 32],
+    pub last_diff_hash: [u8; 32],
+    pub last_checkpoint_at: i64,
+    pub settled: bool,
+    pub bump: u8,
+}
+
+impl ErCheckpoint {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // match_er
+        8 + // last_seq
+        32 + // state_hash
+        32 + // last_diff_hash
+        8 + // last_checkpoint_at
+        1 + // settled
+        1; // bump
+
+    /// Chain the next diff onto the commitment. Sequence numbers must be contiguous
+    /// so a skipped or replayed diff is rejected.
+    pub fn advance(&mut self, seq: u64, diff_hash: [u8; 32], timestamp: i64) -> Result<()> {
+        if self.settled || seq != self.last_seq + 1 {
+            return Err(shared::GameError::InvalidCheckpointSequence.into());
+        }
+        self.state_hash = anchor_lang::solana_program::hash::hashv(&[
+            &self.state_hash,
+            &seq.to_le_bytes(),
+            &diff_hash,
+        ]).to_bytes();
+        self.last_seq = seq;
+        self.last_diff_hash = diff_hash;
+        self.last_checkpoint_at = timestamp;
+        Ok(())
+    }
+
+    /// Rebuild the commitment from every committed diff, in order from the first,
+    /// so settlement can replay them; each must hash to what was committed for its seq
+    pub fn verify_diffs(&self, diffs: &[FinalDiff]) -> Result<()> {
+        let mut state_hash = [0u8; 32];
+        for (index, diff) in diffs.iter().enumerate() {
+            // Only the final diff may name a winner
+            if diff.seq != index as u64 + 1 || diff.winner.is_some() {
+                return Err(shared::GameError::InvalidCheckpointSequence.into());
+            }
+            state_hash = anchor_lang::solana_program::hash::hashv(&[
+                &state_hash,
+                &diff.seq.to_le_bytes(),
+                &diff.hash()?,
+            ]).to_bytes();
+        }
+        if diffs.len() as u64 != self.last_seq || state_hash != self.state_hash {
+            return Err(shared::GameError::CheckpointHashMismatch.into());
+        }
+        Ok(())
+    }
+}
+
+/// Registry entry mapping a delegated mainnet PDA to its ER address for one delegation epoch.
+/// The epoch is the delegation's `delegation_timestamp`, so any re-delegation makes it stale.
+#[account]
+pub struct ErAccountMap {
+    pub mainnet_account: Pubkey,
+    pub er_account: Pubkey,
+    pub delegation_state: Pubkey,
+    pub delegation_epoch: i64,
+    pub mapped_at: i64,
+    pub bump: u8,
+}
+
+impl ErAccountMap {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // mainnet_account
+        32 + // er_account
+        32 + // delegation_state
+        8 + // delegation_epoch
+        8 + // mapped_at
+        1; // bump
+
+    /// Reject mappings recorded under another delegation or an earlier epoch
+    pub fn validate(&self, delegation_key: &Pubkey, delegation: &DelegationState) -> Result<()> {
+        if self.delegation_state != *delegation_key
+            || self.delegation_epoch != delegation.delegation_timestamp
+            || !delegation.is_active
+        {
+            return Err(shared::GameError::StaleAccountMapping.into());
+        }
+        Ok(())
+    }
+}
+
+/// Component selection bits for `ComponentSnapshot`
+pub const SNAPSHOT_POSITION: u8 = 1 << 0;
+pub const SNAPSHOT_HEALTH: u8 = 1 << 1;
+pub const SNAPSHOT_MANA: u8 = 1 << 2;
+pub const SNAPSHOT_ALL: u8 = SNAPSHOT_POSITION | SNAPSHOT_HEALTH | SNAPSHOT_MANA;
+
+/// Pre-delegation copy of a player's components, restored if the ER session is
+/// rolled back or abandoned. Each snapshot hashes over the previous one, so a
+/// restore can only ever apply the latest snapshot for the current delegation.
+#[account]
+pub struct ComponentSnapshot {
+    pub player: Pubkey,
+    pub delegation_state: Pubkey,
+    pub delegation_epoch: i64,
+    pub component_mask: u8,
+    pub position: [i32; 3],   // x, y, z
+    pub health: [u32; 3],     // current, max, regeneration_rate
+    pub mana: [u32; 3],       // current, max, regeneration_rate
+    pub sequence: u32,
+    pub prev_hash: [u8; 32],
+    pub snapshot_hash: [u8; 32],
+    pub taken_at: i64,
+    pub restored: bool,
+    pub bump: u8,
+}
+
+impl ComponentSnapshot {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // player
+        32 + // delegation_state
+        8 + // delegation_epoch
+        1 + // component_mask
+        12 + // position
+        12 + // health
+        12 + // mana
+        4 + // sequence
+        32 + // prev_hash
+        32 + // snapshot_hash
+        8 + // taken_at
+        1 + // restored
+        1; // bump
+
+    pub fn compute_hash(&self) -> [u8; 32] {
+        let mut payload = Vec::with_capacity(36);
+        for value in self.position {
+            payload.extend_from_slice(&value.to_le_bytes());
+        }
+        for value in self.health.iter().chain(self.mana.iter()) {
+            payload.extend_from_slice(&value.to_le_bytes());
+        }
+        anchor_lang::solana_program::hash::hashv(&[
+            &self.prev_hash,
+            self.player.as_ref(),
+            &self.delegation_epoch.to_le_bytes(),
+            &self.sequence.to_le_bytes(),
+            &[self.component_mask],
+            &payload,
+        ]).to_bytes()
+    }
+
+    /// Only an unrestored snapshot taken under the live delegation epoch, before a
+    /// proven fault in that epoch, may be applied
+    pub fn verify(&self, delegation_key: &Pubkey, delegation: &DelegationState, fault: &ErFault) -> Result<()> {
+        if self.restored {
+            return Err(shared::GameError::SnapshotAlreadyRestored.into());
+        }
+        if self.delegation_state != *delegation_key
+            || self.delegation_epoch != delegation.delegation_timestamp
+            || self.compute_hash() != self.snapshot_hash
+            || !fault.covers(delegation_key, self)
+        {
+            return Err(shared::GameError::InvalidSnapshot.into());
+        }
+        Ok(())
+    }
+}
+
+/// A fault in an ER session, attested by the upgrade authority. Snapshots can
+/// only be restored once one exists, and only if taken before it, so a player
+/// cannot undo state from a healthy session.
+#[account]
+pub struct ErFault {
+    pub delegation_state: Pubkey,
+    pub delegation_epoch: i64,
+    pub reason: ReasonCode,
+    pub evidence_hash: [u8; 32],
+    pub proven_by: Pubkey,
+    pub proven_at: i64,
+    pub bump: u8,
+}
+
+impl ErFault {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // delegation_state
+        8 + // delegation_epoch
+        1 + // reason
+        32 + // evidence_hash
+        32 + // proven_by
+        8 + // proven_at
+        1; // bump
+
+    pub fn covers(&self, delegation_key: &Pubkey, snapshot: &ComponentSnapshot) -> bool {
+        self.delegation_state == *delegation_key
+            && self.delegation_epoch == snapshot.delegation_epoch
+            && snapshot.taken_at <= self.proven_at
+    }
+}
+
+/// Changes since the previous checkpoint; every committed diff is replayed at settlement
+/// in place of full MatchResults
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct FinalDiff {
+    pub seq: u64,
+    pub winner: Option<Pubkey>,
+    pub changed_players: Vec<FinalPlayerDiff>,
+    pub ended_at: i64,
+}
+
+impl FinalDiff {
+    /// Hash committed for this diff by `commit_checkpoint`
+    pub fn hash(&self) -> Result<[u8; 32]> {
+        Ok(anchor_lang::solana_program::hash::hash(&self.try_to_vec()?).to_bytes())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct FinalPlayerDiff {
+    pub player: Pubkey,
+    pub current_health: u32,
+    pub damage_dealt: u32,
+    pub damage_taken: u32,
+    pub is_alive: bool,
+}
+
 /// Session metadata for ER tracking
 #[account]
 pub struct ErSession {
@@ -525,7 +756,8 @@ impl LegacyMatchAdapter {
             last_component_update: Clock::get().unwrap().unix_timestamp,
             force_ended: legacy_match.force_ended,
             force_ended_by: legacy_match.force_ended_by,
-            cancel_reason: legacy_match.cancel_reason.clone(),
+            cancel_reason: legacy_match.cancel_reason,
+            cancel_memo_hash: legacy_match.cancel_memo_hash,
             rollback_requested: false,
             rollback_reason: None,
             bump: 0, // Will be set properly when initializing
@@ -558,7 +790,8 @@ impl LegacyMatchAdapter {
             ended_at: er_match.ended_at,
             force_ended: er_match.force_ended,
             force_ended_by: er_match.force_ended_by,
-            cancel_reason: er_match.cancel_reason.clone(),
+            cancel_reason: er_match.cancel_reason,
+            cancel_memo_hash: er_match.cancel_memo_hash,
             bump: er_match.bump,
         }
     }
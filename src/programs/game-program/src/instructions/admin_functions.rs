@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
-use crate::state::{Match, PlayerProfile};
-use crate::shared::{GameError, GameState, AdminConfig, AdminRole};
+use anchor_lang::solana_program::sysvar::instructions as ix_sysvar;
+use crate::state::{Match, PlayerProfile, PenaltyComponent, PenaltyLevel, ComplianceConfig, EconomyStats, GemSource};
+use crate::shared::{GameError, GameState, AdminConfig, AdminRole, ADMIN_CONFIG_SEED, MAX_ADMINS, MAX_CPI_ALLOWED_CALLERS, ComplianceMode, FeatureFlags, MAX_FEATURE_FLAGS, SchemaRegistry, SCHEMA_LAYOUT_VERSION, EventSchedule, ScheduledEvent, NO_MODIFIER_BPS, ReasonCode, MetricsBuffer, METRICS_BUFFER_SEED, METRICS_SHARDS, ProgramInfo};
 
 // Access control macro for admin functions
 macro_rules! require_admin {
@@ -16,11 +17,15 @@ macro_rules! require_admin {
 pub fn emergency_stop_match(
     ctx: Context<EmergencyStopMatch>,
     match_id: u64,
-    reason: String
+    reason: ReasonCode,
+    memo_hash: Option<[u8; 32]>,
 ) -> Result<()> {
     let admin_config = &ctx.accounts.admin_config;
     let admin = &ctx.accounts.admin.key();
     
+    // SECURITY: Optionally reject calls wrapped by unknown programs
+    admin_config.check_cpi_origin(&ctx.accounts.instructions, &crate::ID)?;
+    
     // SECURITY: Verify admin privileges
     require_admin!(admin_config, admin, AdminRole::GameAdmin);
     
@@ -34,12 +39,14 @@ pub fn emergency_stop_match(
     // Emergency stop the match
     match_account.state = GameState::Cancelled;
     match_account.ended_at = Some(Clock::get()?.unix_timestamp);
-    match_account.cancel_reason = Some(reason.clone());
+    match_account.cancel_reason = Some(reason);
+    match_account.cancel_memo_hash = memo_hash;
     
     emit!(MatchEmergencyStopped {
         match_id,
         admin: *admin,
         reason,
+        memo_hash,
         timestamp: Clock::get()?.unix_timestamp,
     });
     
@@ -79,6 +86,11 @@ pub fn update_admin_config(
         admin_config.role_assignments.push((admin_key, role));
     }
     
+    // The account is sized for MAX_ADMINS of each
+    if admin_config.admin_whitelist.len() > MAX_ADMINS || admin_config.role_assignments.len() > MAX_ADMINS {
+        return Err(GameError::TooManyAdmins.into());
+    }
+    
     emit!(AdminConfigUpdated {
         super_admin: *admin,
         new_admins,
@@ -98,6 +110,9 @@ pub fn force_end_match(
     let admin_config = &ctx.accounts.admin_config;
     let admin = &ctx.accounts.admin.key();
     
+    // SECURITY: Optionally reject calls wrapped by unknown programs
+    admin_config.check_cpi_origin(&ctx.accounts.instructions, &crate::ID)?;
+    
     // SECURITY: Verify admin privileges
     require_admin!(admin_config, admin, AdminRole::GameAdmin);
     
@@ -169,6 +184,363 @@ pub fn reset_player_stats(
     Ok(())
 }
 
+/// Clear an AFK penalty after a successful appeal - SupportAdmin only
+pub fn clear_penalty(
+    ctx: Context<ClearPenalty>,
+    player: Pubkey,
+    reset_strikes: bool
+) -> Result<()> {
+    let admin_config = &ctx.accounts.admin_config;
+    let admin = &ctx.accounts.admin.key();
+    
+    // SECURITY: Verify admin privileges
+    require_admin!(admin_config, admin, AdminRole::SupportAdmin);
+    
+    let penalty = &mut ctx.accounts.penalty;
+    let previous_level = penalty.level;
+    
+    penalty.level = PenaltyLevel::None;
+    penalty.banned_until = 0;
+    penalty.cleared_by = Some(*admin);
+    
+    if reset_strikes {
+        ctx.accounts.player_profile.afk_strikes = 0;
+    }
+    
+    emit!(PenaltyCleared {
+        player,
+        admin: *admin,
+        previous_level,
+        strikes_reset: reset_strikes,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("AFK penalty cleared for {} by support admin {}", player, admin);
+    
+    Ok(())
+}
+
+/// Configure the compliance gate for regulated deployments - SecurityAdmin only
+pub fn configure_compliance(
+    ctx: Context<ConfigureCompliance>,
+    mode: ComplianceMode,
+    allowlist_root: [u8; 32],
+    attestation_issuer: Pubkey
+) -> Result<()> {
+    let admin_config = &ctx.accounts.admin_config;
+    let admin = &ctx.accounts.admin.key();
+    
+    // SECURITY: Verify admin privileges
+    require_admin!(admin_config, admin, AdminRole::SecurityAdmin);
+    
+    if mode == ComplianceMode::Attestation && attestation_issuer == Pubkey::default() {
+        return Err(GameError::InvalidComplianceCredential.into());
+    }
+    
+    let clock = Clock::get()?;
+    let compliance = &mut ctx.accounts.compliance_config;
+    compliance.mode = mode;
+    compliance.allowlist_root = allowlist_root;
+    compliance.attestation_issuer = attestation_issuer;
+    compliance.updated_by = *admin;
+    compliance.updated_at = clock.unix_timestamp;
+    compliance.bump = ctx.bumps.compliance_config;
+    
+    emit!(ComplianceConfigured {
+        mode,
+        allowlist_root,
+        attestation_issuer,
+        admin: *admin,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Compliance mode set to {:?} by security admin {}", mode, admin);
+    
+    Ok(())
+}
+
+/// Enable, disable or stage rollout of a feature flag - GameAdmin only
+pub fn set_feature_flag(
+    ctx: Context<SetFeatureFlag>,
+    flag: u8,
+    enabled: bool,
+    rollout_bps: u16
+) -> Result<()> {
+    let admin_config = &ctx.accounts.admin_config;
+    let admin = &ctx.accounts.admin.key();
+    
+    // SECURITY: Verify admin privileges
+    require_admin!(admin_config, admin, AdminRole::GameAdmin);
+    
+    if flag as usize >= MAX_FEATURE_FLAGS {
+        return Err(GameError::InvalidMatchConfig.into());
+    }
+    
+    let clock = Clock::get()?;
+    let flags = &mut ctx.accounts.feature_flags;
+    if flags.authority == Pubkey::default() {
+        flags.authority = *admin;
+        flags.bump = ctx.bumps.feature_flags;
+    }
+    flags.set(flag, enabled, rollout_bps);
+    flags.updated_at = clock.unix_timestamp;
+    
+    emit!(FeatureFlagUpdated {
+        flag,
+        enabled,
+        rollout_bps: flags.rollout_bps[flag as usize],
+        admin: *admin,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Feature flag {} set to {} ({} bps) by {}", flag, enabled, rollout_bps, admin);
+    
+    Ok(())
+}
+
+/// Write the current filterable field offsets to the schema registry - GameAdmin only
+pub fn publish_schema_registry(ctx: Context<PublishSchemaRegistry>) -> Result<()> {
+    let admin_config = &ctx.accounts.admin_config;
+    let admin = &ctx.accounts.admin.key();
+    
+    // SECURITY: Verify admin privileges
+    require_admin!(admin_config, admin, AdminRole::GameAdmin);
+    
+    let clock = Clock::get()?;
+    let registry = &mut ctx.accounts.schema_registry;
+    if registry.authority == Pubkey::default() {
+        registry.authority = *admin;
+        registry.bump = ctx.bumps.schema_registry;
+    }
+    registry.layout_version = SCHEMA_LAYOUT_VERSION;
+    registry.fields = SchemaRegistry::current_fields();
+    registry.updated_at = clock.unix_timestamp;
+    
+    emit!(SchemaRegistryPublished {
+        layout_version: registry.layout_version,
+        field_count: registry.fields.len() as u8,
+        admin: *admin,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Create one shard of the metrics rings the keeper samples for dashboards - GameAdmin only
+pub fn initialize_metrics_buffer(ctx: Context<InitializeMetricsBuffer>, shard: u8) -> Result<()> {
+    let admin_config = &ctx.accounts.admin_config;
+    let admin = &ctx.accounts.admin.key();
+    
+    // SECURITY: Verify admin privileges
+    require_admin!(admin_config, admin, AdminRole::GameAdmin);
+    
+    if shard >= METRICS_SHARDS {
+        return Err(GameError::InvalidGameState.into());
+    }
+    
+    let buffer = &mut ctx.accounts.metrics_buffer;
+    buffer.authority = *admin;
+    buffer.shard = shard;
+    buffer.bump = ctx.bumps.metrics_buffer;
+    
+    Ok(())
+}
+
+/// Record the deployed version, commit and IDL hash - upgrade authority only
+pub fn update_program_info(
+    ctx: Context<UpdateProgramInfo>,
+    version: [u16; 3],
+    git_commit: [u8; 20],
+    idl_hash: [u8; 32],
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let deployed_slot = ctx.accounts.program_data.slot;
+    let info = &mut ctx.accounts.program_info;
+    
+    // A stale upgrade must not overwrite the record of a newer one
+    if deployed_slot < info.deployed_slot {
+        return Err(GameError::StaleProgramInfo.into());
+    }
+    
+    info.program_id = crate::ID;
+    info.version = version;
+    info.git_commit = git_commit;
+    info.idl_hash = idl_hash;
+    info.deployed_slot = deployed_slot;
+    info.updated_by = ctx.accounts.authority.key();
+    info.updated_at = clock.unix_timestamp;
+    info.bump = ctx.bumps.program_info;
+    
+    emit!(ProgramInfoUpdated {
+        version,
+        git_commit,
+        idl_hash,
+        deployed_slot,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Highest multiplier an event may apply, in basis points (5x)
+pub const MAX_EVENT_MULTIPLIER_BPS: u16 = 50_000;
+
+/// Schedule a one-off or recurring limited-time event - GameAdmin only
+pub fn add_scheduled_event(
+    ctx: Context<ManageEventSchedule>,
+    starts_at: i64,
+    duration: i64,
+    period: i64,
+    xp_multiplier_bps: u16,
+    max_bet_multiplier_bps: u16,
+) -> Result<()> {
+    let admin_config = &ctx.accounts.admin_config;
+    let admin = &ctx.accounts.admin.key();
+    
+    // SECURITY: Verify admin privileges
+    require_admin!(admin_config, admin, AdminRole::GameAdmin);
+    
+    let multiplier_range = NO_MODIFIER_BPS..=MAX_EVENT_MULTIPLIER_BPS;
+    if duration <= 0
+        || period < 0
+        || (period > 0 && period < duration)
+        || !multiplier_range.contains(&xp_multiplier_bps)
+        || !multiplier_range.contains(&max_bet_multiplier_bps)
+    {
+        return Err(GameError::InvalidEventSchedule.into());
+    }
+    
+    let clock = Clock::get()?;
+    let schedule = &mut ctx.accounts.event_schedule;
+    if schedule.authority == Pubkey::default() {
+        schedule.authority = *admin;
+        schedule.bump = ctx.bumps.event_schedule;
+    }
+    let event_id = schedule
+        .add(ScheduledEvent {
+            starts_at,
+            duration,
+            period,
+            xp_multiplier_bps,
+            max_bet_multiplier_bps,
+            ..Default::default()
+        })
+        .ok_or(GameError::InvalidEventSchedule)?;
+    schedule.updated_at = clock.unix_timestamp;
+    
+    emit!(ScheduledEventChanged {
+        event_id,
+        active: true,
+        starts_at,
+        period,
+        admin: *admin,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Cancel a scheduled event - GameAdmin only
+pub fn cancel_scheduled_event(ctx: Context<ManageEventSchedule>, event_id: u32) -> Result<()> {
+    let admin_config = &ctx.accounts.admin_config;
+    let admin = &ctx.accounts.admin.key();
+    
+    // SECURITY: Verify admin privileges
+    require_admin!(admin_config, admin, AdminRole::GameAdmin);
+    
+    let clock = Clock::get()?;
+    let schedule = &mut ctx.accounts.event_schedule;
+    if !schedule.cancel(event_id) {
+        return Err(GameError::InvalidEventSchedule.into());
+    }
+    schedule.updated_at = clock.unix_timestamp;
+    
+    emit!(ScheduledEventChanged {
+        event_id,
+        active: false,
+        starts_at: 0,
+        period: 0,
+        admin: *admin,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Tune gem earn rates and sink prices - GameAdmin only
+pub fn set_gem_rates(
+    ctx: Context<SetGemRates>,
+    gems_per_win: u64,
+    gems_per_quest: u64,
+    rematch_fee_gems: u64,
+    crate_price_gems: u64,
+    cosmetic_price_gems: u64,
+) -> Result<()> {
+    let admin_config = &ctx.accounts.admin_config;
+    let admin = &ctx.accounts.admin.key();
+    
+    // SECURITY: Verify admin privileges
+    require_admin!(admin_config, admin, AdminRole::GameAdmin);
+    
+    let clock = Clock::get()?;
+    let economy = &mut ctx.accounts.economy_stats;
+    economy.gems_per_win = gems_per_win;
+    economy.gems_per_quest = gems_per_quest;
+    economy.rematch_fee_gems = rematch_fee_gems;
+    economy.crate_price_gems = crate_price_gems;
+    economy.cosmetic_price_gems = cosmetic_price_gems;
+    economy.updated_at = clock.unix_timestamp;
+    economy.bump = ctx.bumps.economy_stats;
+    
+    emit!(GemRatesUpdated {
+        gems_per_win,
+        gems_per_quest,
+        rematch_fee_gems,
+        crate_price_gems,
+        cosmetic_price_gems,
+        gems_minted: economy.gems_minted,
+        gems_burned: economy.gems_burned,
+        admin: *admin,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Credit gems for match wins or completed quests at the configured rate - GameAdmin only
+pub fn grant_gems(
+    ctx: Context<GrantGems>,
+    player: Pubkey,
+    source: GemSource,
+    count: u32,
+) -> Result<()> {
+    let admin_config = &ctx.accounts.admin_config;
+    let admin = &ctx.accounts.admin.key();
+    
+    // SECURITY: Verify admin privileges
+    require_admin!(admin_config, admin, AdminRole::GameAdmin);
+    
+    let economy = &mut ctx.accounts.economy_stats;
+    let amount = economy.earn_rate(source)
+        .checked_mul(count as u64)
+        .ok_or(GameError::ArithmeticOverflow)?;
+    economy.record_mint(source, amount)?;
+    
+    let player_profile = &mut ctx.accounts.player_profile;
+    player_profile.gems = player_profile.gems.checked_add(amount).ok_or(GameError::ArithmeticOverflow)?;
+    
+    emit!(GemsGranted {
+        player,
+        source,
+        amount,
+        balance: player_profile.gems,
+        circulating: economy.circulating(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
 /// Toggle emergency stop mode - SuperAdmin only
 #[access_control(super_admin_only)]
 pub fn toggle_emergency_stop(
@@ -178,6 +550,9 @@ pub fn toggle_emergency_stop(
     let admin_config = &mut ctx.accounts.admin_config;
     let admin = &ctx.accounts.admin.key();
     
+    // SECURITY: Optionally reject calls wrapped by unknown programs
+    admin_config.check_cpi_origin(&ctx.accounts.instructions, &crate::ID)?;
+    
     // SECURITY: Only super admin can toggle emergency stop
     if admin_config.super_admin != *admin {
         return Err(GameError::AccessDenied.into());
@@ -196,6 +571,36 @@ pub fn toggle_emergency_stop(
     Ok(())
 }
 
+/// Configure the CPI guard on sensitive entrypoints - Only SuperAdmin
+#[access_control(super_admin_only)]
+pub fn set_cpi_guard(
+    ctx: Context<SetCpiGuard>,
+    enabled: bool,
+    allowed_callers: Vec<Pubkey>
+) -> Result<()> {
+    let admin_config = &mut ctx.accounts.admin_config;
+    let admin = &ctx.accounts.admin.key();
+    
+    if admin_config.super_admin != *admin {
+        return Err(GameError::AccessDenied.into());
+    }
+    if allowed_callers.len() > MAX_CPI_ALLOWED_CALLERS || allowed_callers.contains(&crate::ID) {
+        return Err(GameError::InvalidMatchConfig.into());
+    }
+    
+    admin_config.cpi_guard_enabled = enabled;
+    admin_config.cpi_allowed_callers = allowed_callers.clone();
+    
+    emit!(CpiGuardUpdated {
+        admin: *admin,
+        enabled,
+        allowed_callers,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
 /// Verify admin access for specific role
 fn verify_admin_access(
     admin_config: &AdminConfig,
@@ -227,16 +632,29 @@ pub struct EmergencyStopMatch<'info> {
     #[account(mut)]
     pub match_account: Account<'info, Match>,
     
-    #[account(constraint = admin_config.admin_whitelist.contains(&admin.key()) || admin_config.super_admin == admin.key())]
+    #[account(
+        seeds = [ADMIN_CONFIG_SEED],
+        bump = admin_config.bump,
+        constraint = admin_config.admin_whitelist.contains(&admin.key()) || admin_config.super_admin == admin.key()
+    )]
     pub admin_config: Account<'info, AdminConfig>,
     
     #[account(signer)]
     pub admin: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar, read by the CPI guard
+    #[account(address = ix_sysvar::ID)]
+    pub instructions: AccountInfo<'info>,
 }
 
 #[derive(Accounts)]
 pub struct UpdateAdminConfig<'info> {
-    #[account(mut, constraint = admin_config.super_admin == admin.key())]
+    #[account(
+        mut,
+        seeds = [ADMIN_CONFIG_SEED],
+        bump = admin_config.bump,
+        constraint = admin_config.super_admin == admin.key()
+    )]
     pub admin_config: Account<'info, AdminConfig>,
     
     #[account(signer)]
@@ -249,11 +667,19 @@ pub struct ForceEndMatch<'info> {
     #[account(mut)]
     pub match_account: Account<'info, Match>,
     
-    #[account(constraint = admin_config.admin_whitelist.contains(&admin.key()) || admin_config.super_admin == admin.key())]
+    #[account(
+        seeds = [ADMIN_CONFIG_SEED],
+        bump = admin_config.bump,
+        constraint = admin_config.admin_whitelist.contains(&admin.key()) || admin_config.super_admin == admin.key()
+    )]
     pub admin_config: Account<'info, AdminConfig>,
     
     #[account(signer)]
     pub admin: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar, read by the CPI guard
+    #[account(address = ix_sysvar::ID)]
+    pub instructions: AccountInfo<'info>,
 }
 
 #[derive(Accounts)]
@@ -262,7 +688,235 @@ pub struct ResetPlayerStats<'info> {
     #[account(mut)]
     pub player_profile: Account<'info, PlayerProfile>,
     
-    #[account(constraint = admin_config.admin_whitelist.contains(&admin.key()) || admin_config.super_admin == admin.key())]
+    #[account(
+        seeds = [ADMIN_CONFIG_SEED],
+        bump = admin_config.bump,
+        constraint = admin_config.admin_whitelist.contains(&admin.key()) || admin_config.super_admin == admin.key()
+    )]
+    pub admin_config: Account<'info, AdminConfig>,
+    
+    #[account(signer)]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(player: Pubkey)]
+pub struct ClearPenalty<'info> {
+    #[account(
+        mut,
+        seeds = [b"penalty", player.as_ref()],
+        bump = penalty.bump
+    )]
+    pub penalty: Account<'info, PenaltyComponent>,
+    
+    #[account(
+        mut,
+        seeds = [b"player", player.as_ref()],
+        bump = player_profile.bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+    
+    #[account(
+        seeds = [ADMIN_CONFIG_SEED],
+        bump = admin_config.bump,
+        constraint = admin_config.admin_whitelist.contains(&admin.key()) || admin_config.super_admin == admin.key()
+    )]
+    pub admin_config: Account<'info, AdminConfig>,
+    
+    #[account(signer)]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureCompliance<'info> {
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = ComplianceConfig::LEN,
+        seeds = [b"compliance_config"],
+        bump
+    )]
+    pub compliance_config: Account<'info, ComplianceConfig>,
+    
+    #[account(
+        seeds = [ADMIN_CONFIG_SEED],
+        bump = admin_config.bump,
+        constraint = admin_config.admin_whitelist.contains(&admin.key()) || admin_config.super_admin == admin.key()
+    )]
+    pub admin_config: Account<'info, AdminConfig>,
+    
+    #[account(mut, signer)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetFeatureFlag<'info> {
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = FeatureFlags::LEN,
+        seeds = [b"feature_flags"],
+        bump
+    )]
+    pub feature_flags: Account<'info, FeatureFlags>,
+    
+    #[account(
+        seeds = [ADMIN_CONFIG_SEED],
+        bump = admin_config.bump,
+        constraint = admin_config.admin_whitelist.contains(&admin.key()) || admin_config.super_admin == admin.key()
+    )]
+    pub admin_config: Account<'info, AdminConfig>,
+    
+    #[account(mut, signer)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PublishSchemaRegistry<'info> {
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = SchemaRegistry::LEN,
+        seeds = [b"schema_registry"],
+        bump
+    )]
+    pub schema_registry: Account<'info, SchemaRegistry>,
+    
+    #[account(
+        seeds = [ADMIN_CONFIG_SEED],
+        bump = admin_config.bump,
+        constraint = admin_config.admin_whitelist.contains(&admin.key()) || admin_config.super_admin == admin.key()
+    )]
+    pub admin_config: Account<'info, AdminConfig>,
+    
+    #[account(mut, signer)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(shard: u8)]
+pub struct InitializeMetricsBuffer<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = MetricsBuffer::LEN,
+        seeds = [METRICS_BUFFER_SEED, &[shard]],
+        bump
+    )]
+    pub metrics_buffer: Account<'info, MetricsBuffer>,
+    
+    #[account(
+        seeds = [ADMIN_CONFIG_SEED],
+        bump = admin_config.bump,
+        constraint = admin_config.admin_whitelist.contains(&admin.key()) || admin_config.super_admin == admin.key()
+    )]
+    pub admin_config: Account<'info, AdminConfig>,
+    
+    #[account(mut, signer)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateProgramInfo<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = ProgramInfo::LEN,
+        seeds = [b"program_info"],
+        bump
+    )]
+    pub program_info: Account<'info, ProgramInfo>,
+    
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::SolDuelGame>,
+    
+    #[account(constraint = program_data.upgrade_authority_address == Some(authority.key()) @ GameError::InvalidUpgradeAuthority)]
+    pub program_data: Account<'info, ProgramData>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ManageEventSchedule<'info> {
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = EventSchedule::LEN,
+        seeds = [b"event_schedule"],
+        bump
+    )]
+    pub event_schedule: Account<'info, EventSchedule>,
+    
+    #[account(
+        seeds = [ADMIN_CONFIG_SEED],
+        bump = admin_config.bump,
+        constraint = admin_config.admin_whitelist.contains(&admin.key()) || admin_config.super_admin == admin.key()
+    )]
+    pub admin_config: Account<'info, AdminConfig>,
+    
+    #[account(mut, signer)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetGemRates<'info> {
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = EconomyStats::LEN,
+        seeds = [b"economy_stats"],
+        bump
+    )]
+    pub economy_stats: Account<'info, EconomyStats>,
+    
+    #[account(
+        seeds = [ADMIN_CONFIG_SEED],
+        bump = admin_config.bump,
+        constraint = admin_config.admin_whitelist.contains(&admin.key()) || admin_config.super_admin == admin.key()
+    )]
+    pub admin_config: Account<'info, AdminConfig>,
+    
+    #[account(mut, signer)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(player: Pubkey)]
+pub struct GrantGems<'info> {
+    #[account(
+        mut,
+        seeds = [b"economy_stats"],
+        bump = economy_stats.bump
+    )]
+    pub economy_stats: Account<'info, EconomyStats>,
+    
+    #[account(
+        mut,
+        seeds = [b"player", player.as_ref()],
+        bump = player_profile.bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+    
+    #[account(
+        seeds = [ADMIN_CONFIG_SEED],
+        bump = admin_config.bump,
+        constraint = admin_config.admin_whitelist.contains(&admin.key()) || admin_config.super_admin == admin.key()
+    )]
     pub admin_config: Account<'info, AdminConfig>,
     
     #[account(signer)]
@@ -271,7 +925,30 @@ pub struct ResetPlayerStats<'info> {
 
 #[derive(Accounts)]
 pub struct ToggleEmergencyStop<'info> {
-    #[account(mut, constraint = admin_config.super_admin == admin.key())]
+    #[account(
+        mut,
+        seeds = [ADMIN_CONFIG_SEED],
+        bump = admin_config.bump,
+        constraint = admin_config.super_admin == admin.key()
+    )]
+    pub admin_config: Account<'info, AdminConfig>,
+    
+    #[account(signer)]
+    pub admin: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar, read by the CPI guard
+    #[account(address = ix_sysvar::ID)]
+    pub instructions: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetCpiGuard<'info> {
+    #[account(
+        mut,
+        seeds = [ADMIN_CONFIG_SEED],
+        bump = admin_config.bump,
+        constraint = admin_config.super_admin == admin.key()
+    )]
     pub admin_config: Account<'info, AdminConfig>,
     
     #[account(signer)]
@@ -291,7 +968,8 @@ pub enum StatResetType {
 pub struct MatchEmergencyStopped {
     pub match_id: u64,
     pub admin: Pubkey,
-    pub reason: String,
+    pub reason: ReasonCode,
+    pub memo_hash: Option<[u8; 32]>,
     pub timestamp: i64,
 }
 
@@ -318,9 +996,93 @@ pub struct PlayerStatsReset {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct PenaltyCleared {
+    pub player: Pubkey,
+    pub admin: Pubkey,
+    pub previous_level: PenaltyLevel,
+    pub strikes_reset: bool,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct EmergencyStopToggled {
     pub admin: Pubkey,
     pub enabled: bool,
     pub timestamp: i64,
-}
\ No newline at end of file
+}
+#[event]
+pub struct ComplianceConfigured {
+    pub mode: ComplianceMode,
+    pub allowlist_root: [u8; 32],
+    pub attestation_issuer: Pubkey,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeatureFlagUpdated {
+    pub flag: u8,
+    pub enabled: bool,
+    pub rollout_bps: u16,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProgramInfoUpdated {
+    pub version: [u16; 3],
+    pub git_commit: [u8; 20],
+    pub idl_hash: [u8; 32],
+    pub deployed_slot: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SchemaRegistryPublished {
+    pub layout_version: u16,
+    pub field_count: u8,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ScheduledEventChanged {
+    pub event_id: u32,
+    pub active: bool,
+    pub starts_at: i64,
+    pub period: i64,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GemRatesUpdated {
+    pub gems_per_win: u64,
+    pub gems_per_quest: u64,
+    pub rematch_fee_gems: u64,
+    pub crate_price_gems: u64,
+    pub cosmetic_price_gems: u64,
+    pub gems_minted: u64,
+    pub gems_burned: u64,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GemsGranted {
+    pub player: Pubkey,
+    pub source: GemSource,
+    pub amount: u64,
+    pub balance: u64,
+    pub circulating: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CpiGuardUpdated {
+    pub admin: Pubkey,
+    pub enabled: bool,
+    pub allowed_callers: Vec<Pubkey>,
+    pub timestamp: i64,
+}
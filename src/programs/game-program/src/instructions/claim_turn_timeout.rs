@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use crate::state::{Match, PlayerProfile, PenaltyComponent, PenaltyLevel};
+use crate::shared::{GameState, GameError};
+
+/// Apply an AFK penalty to the player whose turn has expired and move the match on.
+/// Penalties escalate with repeated timeouts: warning, round forfeit, match forfeit,
+/// and finally a temporary matchmaking ban once enough match forfeits accumulate.
+pub fn handler(ctx: Context<crate::ClaimTurnTimeout>) -> Result<()> {
+    let match_account = &mut ctx.accounts.match_account;
+    let profile = &mut ctx.accounts.timed_out_profile;
+    let penalty = &mut ctx.accounts.penalty;
+    let clock = Clock::get()?;
+    let afk_player = ctx.accounts.timed_out_player.key();
+
+    // Validate match state
+    if match_account.state != GameState::InProgress {
+        return Err(GameError::InvalidGameState.into());
+    }
+
+    // Only the player holding the expired turn can be penalized
+    if !match_account.is_player_turn(&afk_player) {
+        return Err(GameError::NotPlayerTurn.into());
+    }
+
+    if clock.unix_timestamp <= match_account.turn_deadline {
+        return Err(GameError::TurnNotTimedOut.into());
+    }
+
+    if penalty.player == Pubkey::default() {
+        penalty.player = afk_player;
+        penalty.bump = ctx.bumps.penalty;
+    }
+
+    // SECURITY: Update timeout counters with checked arithmetic
+    let match_player = match_account.get_player_mut(&afk_player)
+        .ok_or(GameError::PlayerNotFound)?;
+    match_player.timeouts = match_player.timeouts
+        .checked_add(1)
+        .ok_or(GameError::ArithmeticOverflow)?;
+    let match_timeouts = match_player.timeouts;
+
+    profile.timeout_count = profile.timeout_count
+        .checked_add(1)
+        .ok_or(GameError::ArithmeticOverflow)?;
+    profile.last_timeout_at = clock.unix_timestamp;
+
+    let level = PenaltyComponent::level_for_match_timeouts(match_timeouts);
+    match level {
+        PenaltyLevel::RoundForfeit => {
+            // Lose the next turn as well as this one
+            match_player.skip_turns = match_player.skip_turns.saturating_add(1);
+        }
+        PenaltyLevel::MatchForfeit => {
+            match_player.current_health = 0;
+            match_player.is_alive = false;
+            profile.afk_strikes = profile.afk_strikes.saturating_add(1);
+        }
+        _ => {}
+    }
+    penalty.record(level, clock.unix_timestamp);
+
+    // Repeated match forfeits escalate to a temporary matchmaking ban
+    let ban_duration = PenaltyComponent::ban_duration_for_strikes(profile.afk_strikes);
+    if level == PenaltyLevel::MatchForfeit && ban_duration > 0 {
+        penalty.banned_until = clock.unix_timestamp + ban_duration;
+        penalty.record(PenaltyLevel::MatchmakingBan, clock.unix_timestamp);
+    }
+
+    emit!(AfkPenaltyApplied {
+        match_id: match_account.match_id,
+        player: afk_player,
+        level: penalty.level,
+        match_timeouts,
+        total_timeouts: profile.timeout_count,
+        banned_until: penalty.banned_until,
+        timestamp: clock.unix_timestamp,
+    });
+
+    // End the match if the forfeit left a single player standing
+    if match_account.is_match_over() {
+        match_account.state = GameState::Completed;
+        match_account.ended_at = Some(clock.unix_timestamp);
+        match_account.winner = match_account.get_alive_players().first().map(|p| p.player);
+
+        msg!("Match {} ended by AFK forfeit of {}", match_account.match_id, afk_player);
+        return Ok(());
+    }
+
+    match_account.next_turn()?;
+
+    msg!(
+        "Turn timeout for {} in match {} ({} this match, penalty {:?})",
+        afk_player,
+        match_account.match_id,
+        match_timeouts,
+        penalty.level
+    );
+
+    Ok(())
+}
+
+#[event]
+pub struct AfkPenaltyApplied {
+    pub match_id: u64,
+    pub player: Pubkey,
+    pub level: PenaltyLevel,
+    pub match_timeouts: u8,
+    pub total_timeouts: u32,
+    pub banned_until: i64,
+    pub timestamp: i64,
+}
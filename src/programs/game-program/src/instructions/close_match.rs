@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::shared::GameError;
+use crate::state::{Match, MATCH_CLOSE_GRACE_PERIOD};
+
+/// Close a finished match; rent returns to the creator who paid for it
+pub fn close_match(ctx: Context<crate::CloseMatch>) -> Result<()> {
+    let match_account = &ctx.accounts.match_account;
+    if !match_account.is_closable() {
+        return Err(GameError::InvalidGameState.into());
+    }
+    
+    emit!(MatchClosed {
+        match_id: match_account.match_id,
+        creator: match_account.creator,
+        closed_by: ctx.accounts.creator.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Sweep finished matches in bulk. Remaining accounts are (match, creator)
+/// pairs; rent always goes back to the creator, so anyone can crank this.
+/// Matches that are not closable yet are skipped rather than failing the batch.
+pub fn close_finished_matches<'info>(ctx: Context<'_, '_, '_, 'info, crate::CloseFinishedMatches<'info>>) -> Result<()> {
+    let remaining = ctx.remaining_accounts;
+    if remaining.is_empty() || remaining.len() % 2 != 0 {
+        return Err(GameError::InvalidMatchConfig.into());
+    }
+    
+    let now = Clock::get()?.unix_timestamp;
+    let mut closed = 0u32;
+    for pair in remaining.chunks(2) {
+        let (match_info, creator_info) = (&pair[0], &pair[1]);
+        // Owner and discriminator checks guarantee this is a match created here
+        let match_account = Account::<Match>::try_from(match_info)?;
+        if creator_info.key() != match_account.creator {
+            return Err(GameError::AccessDenied.into());
+        }
+        
+        let past_grace = match_account.ended_at
+            .map_or(false, |ended_at| now >= ended_at.saturating_add(MATCH_CLOSE_GRACE_PERIOD));
+        if !match_account.is_closable() || !past_grace {
+            continue;
+        }
+        
+        emit!(MatchClosed {
+            match_id: match_account.match_id,
+            creator: match_account.creator,
+            closed_by: ctx.accounts.keeper.key(),
+            timestamp: now,
+        });
+        match_account.close(creator_info.clone())?;
+        closed += 1;
+    }
+    
+    msg!("Closed {} of {} matches", closed, remaining.len() / 2);
+    
+    Ok(())
+}
+
+#[event]
+pub struct MatchClosed {
+    pub match_id: u64,
+    pub creator: Pubkey,
+    pub closed_by: Pubkey,
+    pub timestamp: i64,
+}
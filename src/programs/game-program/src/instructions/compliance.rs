@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::state::ComplianceCredential;
+use crate::shared::GameError;
+
+/// Issue or refresh a player's compliance credential. Only the attestation issuer
+/// named in the compliance config may sign.
+pub fn issue_credential(ctx: Context<crate::IssueComplianceCredential>, player: Pubkey, expires_at: i64) -> Result<()> {
+    let config = &ctx.accounts.compliance_config;
+    let credential = &mut ctx.accounts.credential;
+    let clock = Clock::get()?;
+
+    if ctx.accounts.issuer.key() != config.attestation_issuer {
+        return Err(GameError::AccessDenied.into());
+    }
+    if expires_at != 0 && expires_at <= clock.unix_timestamp {
+        return Err(GameError::InvalidComplianceCredential.into());
+    }
+
+    credential.player = player;
+    credential.issuer = config.attestation_issuer;
+    credential.issued_at = clock.unix_timestamp;
+    credential.expires_at = expires_at;
+    credential.revoked = false;
+    credential.bump = ctx.bumps.credential;
+
+    emit!(ComplianceCredentialIssued {
+        player,
+        issuer: credential.issuer,
+        expires_at,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Revoke a previously issued credential
+pub fn revoke_credential(ctx: Context<crate::RevokeComplianceCredential>) -> Result<()> {
+    let credential = &mut ctx.accounts.credential;
+
+    if ctx.accounts.issuer.key() != credential.issuer {
+        return Err(GameError::AccessDenied.into());
+    }
+
+    credential.revoked = true;
+
+    emit!(ComplianceCredentialRevoked {
+        player: credential.player,
+        issuer: credential.issuer,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ComplianceCredentialIssued {
+    pub player: Pubkey,
+    pub issuer: Pubkey,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ComplianceCredentialRevoked {
+    pub player: Pubkey,
+    pub issuer: Pubkey,
+    pub timestamp: i64,
+}
@@ -1,12 +1,36 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Transfer};
-use crate::state::{Match, PlayerProfile};
-use crate::shared::{MatchConfig, GameState, GameError, validate_entry_fee};
+use crate::state::{Match, PlayerProfile, TournamentBlindLevel, TournamentState};
+use crate::shared::{MatchConfig, GameState, GameError, GemSink, QueueType, PlayerIdentity, resolve_player, validate_entry_fee, event_modifiers};
+use super::gems::GemsSpent;
 
 pub fn handler(ctx: Context<crate::CreateMatch>, match_config: MatchConfig) -> Result<()> {
     let clock = Clock::get()?;
     let match_account = &mut ctx.accounts.match_account;
     let creator_profile = &mut ctx.accounts.creator_profile;
+    let player_key = resolve_player(ctx.accounts.player_identity.as_deref(), PlayerIdentity::exists(&ctx.accounts.signer_identity), &ctx.accounts.creator.key(), clock.unix_timestamp)?;
+    
+    // Players serving an AFK ban cannot enter matchmaking
+    if ctx.accounts.creator_penalty.is_banned(clock.unix_timestamp) {
+        return Err(GameError::MatchmakingBanned.into());
+    }
+    
+    // A rematch is paid for in gems by the player calling it
+    if let Some(previous) = &ctx.accounts.rematch_of {
+        if previous.state != GameState::Completed || !previous.players.iter().any(|p| p.player == player_key) {
+            return Err(GameError::InvalidGameState.into());
+        }
+        let economy = ctx.accounts.economy_stats.as_mut().ok_or(GameError::InvalidGameState)?;
+        let amount = economy.charge(creator_profile, GemSink::RematchFee)?;
+        emit!(GemsSpent {
+            player: player_key,
+            sink: GemSink::RematchFee,
+            amount,
+            balance: creator_profile.gems,
+            circulating: economy.circulating(),
+            timestamp: clock.unix_timestamp,
+        });
+    }
     
     // Validate match configuration
     if match_config.max_players == 0 || match_config.max_players > 8 {
@@ -17,6 +41,30 @@ pub fn handler(ctx: Context<crate::CreateMatch>, match_config: MatchConfig) -> R
         return Err(GameError::InvalidMatchConfig.into());
     }
     
+    // The tournament queue is reserved for bracket matches, and bracket matches always use it
+    if (match_config.queue_type == QueueType::Tournament) != ctx.accounts.tournament.is_some() {
+        return Err(GameError::InvalidMatchConfig.into());
+    }
+    
+    // Tournament matches are played by a pairing of the current round, at that round's blinds
+    let mut tournament_blinds = None;
+    if let Some(tournament) = &ctx.accounts.tournament {
+        let bracket = ctx.accounts.bracket.as_ref().ok_or(GameError::BracketPairingMismatch)?;
+        if bracket.tournament != tournament.key() {
+            return Err(GameError::BracketPairingMismatch.into());
+        }
+        if tournament.state != TournamentState::InProgress {
+            return Err(GameError::TournamentNotInProgress.into());
+        }
+        let seated = bracket.pairings.iter().any(|pairing| {
+            pairing.round == bracket.current_round && pairing.winner.is_none() && pairing.seat_of(&player_key).is_some()
+        });
+        if !seated {
+            return Err(GameError::BracketPairingMismatch.into());
+        }
+        tournament_blinds = tournament.blinds_for_round(bracket.current_round);
+    }
+    
     // Validate entry fee payment
     validate_entry_fee(
         ctx.accounts.creator_token_account.amount,
@@ -49,11 +97,14 @@ pub fn handler(ctx: Context<crate::CreateMatch>, match_config: MatchConfig) -> R
     match_account.created_at = clock.unix_timestamp;
     match_account.started_at = None;
     match_account.ended_at = None;
+    match_account.event_modifiers = event_modifiers(ctx.accounts.event_schedule.as_deref(), clock.unix_timestamp);
+    match_account.tournament = ctx.accounts.tournament.as_ref().map(|t| t.key());
+    match_account.blinds = tournament_blinds;
     match_account.bump = ctx.bumps.match_account;
     
     // Add creator as first player
     let creator_stats = creator_profile.get_current_stats();
-    match_account.add_player(ctx.accounts.creator.key(), creator_stats)?;
+    match_account.add_player(player_key, creator_stats, creator_profile.prestige_level)?;
     
     // Update creator's last match timestamp
     creator_profile.last_match_at = clock.unix_timestamp;
@@ -62,6 +113,8 @@ pub fn handler(ctx: Context<crate::CreateMatch>, match_config: MatchConfig) -> R
         match_id: match_account.match_id,
         creator: ctx.accounts.creator.key(),
         config: match_config,
+        tournament: match_account.tournament,
+        blinds: tournament_blinds,
         timestamp: clock.unix_timestamp,
     });
     
@@ -80,5 +133,7 @@ pub struct MatchCreated {
     pub match_id: u64,
     pub creator: Pubkey,
     pub config: MatchConfig,
+    pub tournament: Option<Pubkey>,
+    pub blinds: Option<TournamentBlindLevel>,
     pub timestamp: i64,
 }
\ No newline at end of file
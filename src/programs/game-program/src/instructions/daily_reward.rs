@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, Transfer};
+use crate::state::DailyStreak;
+use crate::shared::{GameError, PlayerIdentity, resolve_player};
+
+/// Set daily reward amounts and the streak-freeze item - upgrade authority only
+pub fn configure_daily_rewards(
+    ctx: Context<crate::ConfigureDailyRewards>,
+    base_tokens: u64,
+    tokens_per_streak_day: u64,
+    base_xp: u32,
+    xp_per_streak_day: u32,
+    max_bonus_days: u32,
+) -> Result<()> {
+    let config = &mut ctx.accounts.daily_reward_config;
+    config.reward_mint = ctx.accounts.reward_mint.key();
+    config.streak_freeze_mint = ctx.accounts.streak_freeze_mint.key();
+    config.base_tokens = base_tokens;
+    config.tokens_per_streak_day = tokens_per_streak_day;
+    config.base_xp = base_xp;
+    config.xp_per_streak_day = xp_per_streak_day;
+    config.max_bonus_days = max_bonus_days;
+    config.bump = ctx.bumps.daily_reward_config;
+    
+    Ok(())
+}
+
+/// Claim today's login reward. Days run on UTC boundaries. Missed days break the
+/// streak unless the player holds enough streak-freeze items, one burned per day missed.
+pub fn claim_daily_reward(ctx: Context<crate::ClaimDailyReward>) -> Result<()> {
+    let clock = Clock::get()?;
+    let player_key = resolve_player(ctx.accounts.player_identity.as_deref(), PlayerIdentity::exists(&ctx.accounts.signer_identity), &ctx.accounts.player.key(), clock.unix_timestamp)?;
+    let today = DailyStreak::utc_day(clock.unix_timestamp);
+    
+    let streak = &mut ctx.accounts.daily_streak;
+    if streak.total_claims > 0 && streak.last_claim_day >= today {
+        return Err(GameError::DailyRewardAlreadyClaimed.into());
+    }
+    
+    let missed = streak.missed_days(today);
+    let mut freezes_burned = 0;
+    if missed > 0 {
+        let freezes_held = ctx.accounts.streak_freeze_account.as_ref().map_or(0, |account| account.amount);
+        if freezes_held >= missed {
+            let freeze_account = ctx.accounts.streak_freeze_account.as_ref().unwrap();
+            token::burn(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Burn {
+                        mint: ctx.accounts.streak_freeze_mint.to_account_info(),
+                        from: freeze_account.to_account_info(),
+                        authority: ctx.accounts.player.to_account_info(),
+                    },
+                ),
+                missed,
+            )?;
+            freezes_burned = missed;
+        } else {
+            emit!(DailyStreakBroken {
+                player: player_key,
+                streak: streak.current_streak,
+                missed_days: missed,
+                timestamp: clock.unix_timestamp,
+            });
+            streak.current_streak = 0;
+        }
+    }
+    
+    if streak.total_claims == 0 {
+        streak.player = player_key;
+        streak.bump = ctx.bumps.daily_streak;
+    }
+    streak.current_streak = streak.current_streak.saturating_add(1);
+    streak.longest_streak = streak.longest_streak.max(streak.current_streak);
+    streak.total_claims = streak.total_claims.saturating_add(1);
+    streak.freezes_used = streak.freezes_used.saturating_add(freezes_burned as u32);
+    streak.last_claim_day = today;
+    
+    let (tokens, xp) = ctx.accounts.daily_reward_config.reward_for(streak.current_streak);
+    
+    let player_profile = &mut ctx.accounts.player_profile;
+    player_profile.experience = player_profile.experience.saturating_add(xp);
+    
+    if tokens > 0 {
+        // Reward vault is owned by the game state PDA
+        let game_state_seeds: &[&[u8]] = &[b"game_state", &[ctx.accounts.game_state.bump]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.reward_vault.to_account_info(),
+                    to: ctx.accounts.player_token_account.to_account_info(),
+                    authority: ctx.accounts.game_state.to_account_info(),
+                },
+                &[game_state_seeds],
+            ),
+            tokens,
+        )?;
+    }
+    
+    emit!(DailyRewardClaimed {
+        player: player_key,
+        day: today,
+        streak: streak.current_streak,
+        tokens,
+        experience: xp,
+        freezes_burned,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct DailyRewardClaimed {
+    pub player: Pubkey,
+    pub day: i64,
+    pub streak: u32,
+    pub tokens: u64,
+    pub experience: u64,
+    pub freezes_burned: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DailyStreakBroken {
+    pub player: Pubkey,
+    pub streak: u32,
+    pub missed_days: u64,
+    pub timestamp: i64,
+}
@@ -1,9 +1,13 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, Transfer};
 use crate::state::{Match, GameState as ProgramGameState};
-use crate::shared::{GameState, GameError};
+use crate::shared::{GameState, GameError, ReasonCode};
 
-pub fn handler(ctx: Context<crate::EmergencyStopMatch>) -> Result<()> {
+pub fn handler(
+    ctx: Context<crate::EmergencyStopMatch>,
+    reason: ReasonCode,
+    memo_hash: Option<[u8; 32]>,
+) -> Result<()> {
     let match_account = &mut ctx.accounts.match_account;
     let game_state = &ctx.accounts.game_state;
     let clock = Clock::get()?;
@@ -13,6 +17,9 @@ pub fn handler(ctx: Context<crate::EmergencyStopMatch>) -> Result<()> {
         return Err(GameError::InvalidUpgradeAuthority.into());
     }
     
+    // SECURITY: Optionally reject calls wrapped by unknown programs
+    ctx.accounts.admin_config.check_cpi_origin(&ctx.accounts.instructions, &crate::ID)?;
+    
     // Only allow emergency stop for active matches
     if match_account.state == GameState::Completed || match_account.state == GameState::Cancelled {
         return Err(GameError::InvalidGameState.into());
@@ -22,6 +29,8 @@ pub fn handler(ctx: Context<crate::EmergencyStopMatch>) -> Result<()> {
     match_account.state = GameState::Cancelled;
     match_account.ended_at = Some(clock.unix_timestamp);
     match_account.winner = None;
+    match_account.cancel_reason = Some(reason);
+    match_account.cancel_memo_hash = memo_hash;
     
     // Refund entry fees to all players
     // Note: This is a simplified version - in practice, you'd need proper vault management
@@ -45,6 +54,8 @@ pub fn handler(ctx: Context<crate::EmergencyStopMatch>) -> Result<()> {
         authority: ctx.accounts.authority.key(),
         total_refunded: match_account.reward_pool,
         players_count: match_account.players.len() as u8,
+        reason,
+        memo_hash,
         timestamp: clock.unix_timestamp,
     });
     
@@ -67,6 +78,8 @@ pub struct EmergencyMatchStopped {
     pub authority: Pubkey,
     pub total_refunded: u64,
     pub players_count: u8,
+    pub reason: ReasonCode,
+    pub memo_hash: Option<[u8; 32]>,
     pub timestamp: i64,
 }
 
@@ -1,10 +1,11 @@
 use anchor_lang::prelude::*;
 use crate::state::Match;
-use crate::shared::{GameState, GameError};
+use crate::shared::{GameState, GameError, PlayerIdentity, resolve_player};
 
 pub fn handler(ctx: Context<crate::EndTurn>) -> Result<()> {
     let match_account = &mut ctx.accounts.match_account;
     let clock = Clock::get()?;
+    let player_key = resolve_player(ctx.accounts.player_identity.as_deref(), PlayerIdentity::exists(&ctx.accounts.signer_identity), &ctx.accounts.player.key(), clock.unix_timestamp)?;
     
     // Validate match state
     if match_account.state != GameState::InProgress {
@@ -12,12 +13,12 @@ pub fn handler(ctx: Context<crate::EndTurn>) -> Result<()> {
     }
     
     // Check if it's player's turn
-    if !match_account.is_player_turn(&ctx.accounts.player.key()) {
+    if !match_account.is_player_turn(&player_key) {
         return Err(GameError::NotPlayerTurn.into());
     }
     
     // Restore some mana at end of turn
-    if let Some(current_player) = match_account.get_player_mut(&ctx.accounts.player.key()) {
+    if let Some(current_player) = match_account.get_player_mut(&player_key) {
         current_player.restore_mana(10); // Restore 10 mana each turn
     }
     
@@ -26,14 +27,14 @@ pub fn handler(ctx: Context<crate::EndTurn>) -> Result<()> {
     
     emit!(TurnEnded {
         match_id: match_account.match_id,
-        player: ctx.accounts.player.key(),
+        player: player_key,
         next_player: match_account.players[match_account.current_turn as usize].player,
         timestamp: clock.unix_timestamp,
     });
     
     msg!(
         "Turn ended for player {}, next player: {}",
-        ctx.accounts.player.key(),
+        player_key,
         match_account.players[match_account.current_turn as usize].player
     );
     
@@ -2,13 +2,19 @@ use anchor_lang::prelude::*;
 use crate::state::{Match, PlayerProfile, CombatResult};
 use crate::shared::{
     CombatAction, ActionType, GameState, GameError,
-    calculate_damage, calculate_critical_chance, calculate_experience_gain
+    calculate_damage, calculate_critical_chance, calculate_experience_gain, PlayerIdentity, resolve_player,
+    CuMeter, record_metric
 };
 
+/// Metrics id for this instruction; the branch is the action type
+pub const METRIC_EXECUTE_ACTION: u8 = 0;
+
 pub fn handler(ctx: Context<crate::ExecuteAction>, action: CombatAction) -> Result<()> {
+    let meter = CuMeter::start();
     let match_account = &mut ctx.accounts.match_account;
     let player_profile = &mut ctx.accounts.player_profile;
     let clock = Clock::get()?;
+    let player_key = resolve_player(ctx.accounts.player_identity.as_deref(), PlayerIdentity::exists(&ctx.accounts.signer_identity), &ctx.accounts.player.key(), clock.unix_timestamp)?;
     
     // Validate match state
     if match_account.state != GameState::InProgress {
@@ -16,7 +22,7 @@ pub fn handler(ctx: Context<crate::ExecuteAction>, action: CombatAction) -> Resu
     }
     
     // Check if it's player's turn
-    if !match_account.is_player_turn(&ctx.accounts.player.key()) {
+    if !match_account.is_player_turn(&player_key) {
         return Err(GameError::NotPlayerTurn.into());
     }
     
@@ -26,7 +32,7 @@ pub fn handler(ctx: Context<crate::ExecuteAction>, action: CombatAction) -> Resu
     }
     
     // Get current player
-    let current_player = match_account.get_player_mut(&ctx.accounts.player.key())
+    let current_player = match_account.get_player_mut(&player_key)
         .ok_or(GameError::PlayerNotFound)?;
     
     if !current_player.can_act() {
@@ -41,21 +47,21 @@ pub fn handler(ctx: Context<crate::ExecuteAction>, action: CombatAction) -> Resu
     // Execute action based on type
     let combat_result = match action.action_type {
         ActionType::BasicAttack => {
-            execute_basic_attack(match_account, &ctx.accounts.player.key(), &action)?
+            execute_basic_attack(match_account, &player_key, &action)?
         }
         ActionType::SpecialAbility => {
-            execute_special_ability(match_account, &ctx.accounts.player.key(), &action)?
+            execute_special_ability(match_account, &player_key, &action)?
         }
         ActionType::DefensiveStance => {
-            execute_defensive_stance(match_account, &ctx.accounts.player.key(), &action)?
+            execute_defensive_stance(match_account, &player_key, &action)?
         }
         ActionType::Heal => {
-            execute_heal(match_account, &ctx.accounts.player.key(), &action)?
+            execute_heal(match_account, &player_key, &action)?
         }
     };
     
     // SECURITY: Update player stats with checked arithmetic to prevent overflow
-    let acting_player = match_account.get_player_mut(&ctx.accounts.player.key()).unwrap();
+    let acting_player = match_account.get_player_mut(&player_key).unwrap();
     acting_player.use_mana(action.mana_cost);
     acting_player.actions_taken = acting_player.actions_taken
         .checked_add(1)
@@ -66,16 +72,15 @@ pub fn handler(ctx: Context<crate::ExecuteAction>, action: CombatAction) -> Resu
     
     // SECURITY: Update player profile experience with checked arithmetic
     player_profile.experience = player_profile.experience
-        .checked_add(combat_result.experience_gained as u64)
+        .checked_add(match_account.event_modifiers.apply_xp(combat_result.experience_gained as u64))
         .ok_or(GameError::ArithmeticOverflow)?;
-    player_profile.level = player_profile.calculate_level();
     player_profile.total_damage_dealt = player_profile.total_damage_dealt
         .checked_add(combat_result.damage_dealt as u64)
         .ok_or(GameError::ArithmeticOverflow)?;
     
     emit!(ActionExecuted {
         match_id: match_account.match_id,
-        player: ctx.accounts.player.key(),
+        player: player_key,
         action: action.clone(),
         result: combat_result.clone(),
         timestamp: clock.unix_timestamp,
@@ -99,6 +104,16 @@ pub fn handler(ctx: Context<crate::ExecuteAction>, action: CombatAction) -> Resu
         });
     }
     
+    let players_touched = match_account.players.len() as u16;
+    record_metric(
+        &mut ctx.accounts.metrics_buffer,
+        &meter,
+        METRIC_EXECUTE_ACTION,
+        action.action_type as u8,
+        players_touched,
+        clock.slot,
+    );
+    
     Ok(())
 }
 
@@ -1,9 +1,16 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
-use crate::state::Match;
-use crate::shared::{GameState, GameError, calculate_reward_share};
+use crate::state::{Match, PlayerProfile, PrestigeBonusFund};
+use crate::shared::{GameState, GameError, QueueType, WinnerAttestation, calculate_reward_share};
 
-pub fn handler(ctx: Context<crate::FinishMatch>) -> Result<()> {
+/// Distribute rewards and credit every player's result to their profile.
+///
+/// Remaining accounts start with one writable PlayerProfile per match player,
+/// in seat order, followed by any reward token accounts.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, crate::FinishMatch<'info>>) -> Result<()> {
+    // SECURITY: Optionally reject reward distribution wrapped by unknown programs
+    ctx.accounts.admin_config.check_cpi_origin(&ctx.accounts.instructions, &crate::ID)?;
+    
     let match_account = &mut ctx.accounts.match_account;
     let clock = Clock::get()?;
     
@@ -17,8 +24,16 @@ pub fn handler(ctx: Context<crate::FinishMatch>) -> Result<()> {
         return Err(GameError::InvalidGameState.into());
     }
     
+    if ctx.remaining_accounts.len() < match_account.players.len() {
+        return Err(GameError::PlayerNotFound.into());
+    }
+    let (profile_accounts, token_accounts) = ctx.remaining_accounts.split_at(match_account.players.len());
+    
     // Calculate and distribute rewards
-    distribute_rewards(match_account, &ctx.remaining_accounts, &ctx.accounts.token_program)?;
+    distribute_rewards(match_account, token_accounts, ctx.accounts.prestige_fund.as_deref_mut(), &ctx.accounts.token_program)?;
+    
+    // Every result counts, so no player can skip a loss or a low reward
+    credit_results(match_account, profile_accounts, clock.unix_timestamp)?;
     
     // Update player statistics
     for player in &match_account.players {
@@ -36,6 +51,16 @@ pub fn handler(ctx: Context<crate::FinishMatch>) -> Result<()> {
         });
     }
     
+    ctx.accounts.winner_attestation.set_inner(WinnerAttestation::new(
+        crate::ID,
+        match_account.key(),
+        match_account.match_id,
+        match_account.winner,
+        ctx.accounts.authority.key(),
+        clock.unix_timestamp,
+        ctx.bumps.winner_attestation,
+    ));
+    
     emit!(MatchFinalized {
         match_id: match_account.match_id,
         winner: match_account.winner,
@@ -57,6 +82,7 @@ pub fn handler(ctx: Context<crate::FinishMatch>) -> Result<()> {
 fn distribute_rewards(
     match_account: &mut Match,
     remaining_accounts: &[AccountInfo],
+    mut prestige_fund: Option<&mut PrestigeBonusFund>,
     token_program: &Program<Token>,
 ) -> Result<()> {
     if match_account.reward_pool == 0 {
@@ -79,8 +105,9 @@ fn distribute_rewards(
     // Distribute rewards based on ranking and configuration
     let total_pool = match_account.reward_pool;
     let mut distributed = 0u64;
+    let mut earned = Vec::new();
     
-    for (rank, &(_, player)) in player_rankings.iter().enumerate() {
+    for (rank, &(index, player)) in player_rankings.iter().enumerate() {
         if rank >= match_account.config.reward_distribution.len() {
             break;
         }
@@ -97,6 +124,14 @@ fn distribute_rewards(
                 distributed = distributed.checked_add(reward_amount)
                     .ok_or(GameError::ArithmeticOverflow)?;
                 
+                // The prestige bonus is paid from its own fund, never the pool
+                let bonus = match prestige_fund.as_deref_mut() {
+                    Some(fund) => fund.draw(PrestigeBonusFund::bonus_for(reward_amount, player.prestige_level)),
+                    None => 0,
+                };
+                let reward_amount = reward_amount + bonus;
+                earned.push((index, reward_amount));
+                
                 emit!(RewardDistributed {
                     match_id: match_account.match_id,
                     player: player.player,
@@ -108,10 +143,45 @@ fn distribute_rewards(
         }
     }
     
+    for (index, amount) in earned {
+        match_account.players[index].reward_earned = amount;
+    }
     match_account.reward_pool = match_account.reward_pool.saturating_sub(distributed);
     Ok(())
 }
 
+/// Count each player's result and reward in the match's queue, which sets that queue's rating floor
+fn credit_results(match_account: &mut Match, profile_accounts: &[AccountInfo], now: i64) -> Result<()> {
+    let match_id = match_account.match_id;
+    let queue_type = match_account.config.queue_type;
+    let winner = match_account.winner;
+    
+    for (player, profile_info) in match_account.players.iter_mut().zip(profile_accounts) {
+        let (expected, _) = Pubkey::find_program_address(&[b"player", player.player.as_ref()], &crate::ID);
+        if profile_info.key() != expected {
+            return Err(GameError::AccessDenied.into());
+        }
+        let mut profile = Account::<PlayerProfile>::try_from(profile_info)?;
+        let queue_stats = profile.record_queue_result(queue_type, winner == Some(player.player), player.reward_earned);
+        player.result_credited = true;
+        profile.exit(&crate::ID)?;
+        
+        emit!(WinningsRecorded {
+            player: player.player,
+            match_id,
+            amount: player.reward_earned,
+            lifetime_winnings: profile.lifetime_winnings,
+            rating_floor: profile.rating_floor(queue_type),
+            queue_type,
+            queue_matches: queue_stats.matches,
+            queue_wins: queue_stats.wins,
+            queue_winnings: queue_stats.winnings,
+            timestamp: now,
+        });
+    }
+    Ok(())
+}
+
 fn find_player_token_account<'info>(
     remaining_accounts: &'info [AccountInfo<'info>],
     player: &Pubkey,
@@ -138,6 +208,20 @@ pub struct PlayerMatchCompleted {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct WinningsRecorded {
+    pub player: Pubkey,
+    pub match_id: u64,
+    pub amount: u64,
+    pub lifetime_winnings: u64,
+    pub rating_floor: u32,
+    pub queue_type: QueueType,
+    pub queue_matches: u32,
+    pub queue_wins: u32,
+    pub queue_winnings: u64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct MatchFinalized {
     pub match_id: u64,
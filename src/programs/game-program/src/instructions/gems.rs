@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::shared::{GameError, GemSink, PlayerIdentity, resolve_player};
+
+/// Spend gems on a crate or cosmetic at the configured price. The burn is
+/// recorded in a receipt the NFT program redeems to deliver the purchase;
+/// rematch fees are charged by create_match instead.
+pub fn spend_gems(ctx: Context<crate::SpendGems>, sink: GemSink, target: Pubkey) -> Result<()> {
+    let clock = Clock::get()?;
+    let player_key = resolve_player(ctx.accounts.player_identity.as_deref(), PlayerIdentity::exists(&ctx.accounts.signer_identity), &ctx.accounts.player.key(), clock.unix_timestamp)?;
+    if sink == GemSink::RematchFee {
+        return Err(GameError::InvalidGameState.into());
+    }
+    
+    let economy = &mut ctx.accounts.economy_stats;
+    let player_profile = &mut ctx.accounts.player_profile;
+    let amount = economy.charge(player_profile, sink)?;
+    
+    let receipt = &mut ctx.accounts.gem_receipt;
+    receipt.receipt_id = economy.receipts_issued;
+    receipt.player = player_key;
+    receipt.sink = sink;
+    receipt.amount = amount;
+    receipt.target = target;
+    receipt.issued_at = clock.unix_timestamp;
+    receipt.bump = ctx.bumps.gem_receipt;
+    economy.receipts_issued = economy.receipts_issued
+        .checked_add(1)
+        .ok_or(GameError::ArithmeticOverflow)?;
+    
+    emit!(GemsSpent {
+        player: player_key,
+        sink,
+        amount,
+        balance: player_profile.gems,
+        circulating: economy.circulating(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct GemsSpent {
+    pub player: Pubkey,
+    pub sink: GemSink,
+    pub amount: u64,
+    pub balance: u64,
+    pub circulating: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,149 @@
+use anchor_lang::prelude::*;
+use crate::shared::{GameError, LinkedWallet, WalletRole, MAX_LINKED_WALLETS};
+
+/// Create an identity for the signing wallet. The signer becomes the first hot
+/// wallet and its key stays the identity's player key, so existing profiles keep
+/// their addresses; `cold_wallet` takes over withdrawals and identity changes.
+pub fn create_identity(ctx: Context<crate::CreateIdentity>, cold_wallet: Pubkey) -> Result<()> {
+    let identity = &mut ctx.accounts.identity;
+    let clock = Clock::get()?;
+    let player = ctx.accounts.player.key();
+
+    identity.player_key = player;
+    identity.cold_wallet = cold_wallet;
+    identity.wallets = vec![LinkedWallet {
+        wallet: player,
+        role: WalletRole::Hot,
+        expires_at: 0,
+    }];
+    identity.created_at = clock.unix_timestamp;
+    identity.updated_at = clock.unix_timestamp;
+    identity.bump = ctx.bumps.identity;
+
+    emit!(IdentityCreated {
+        identity: identity.key(),
+        player_key: player,
+        cold_wallet,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Link a hot or session wallet - cold wallet only
+pub fn link_wallet(
+    ctx: Context<crate::UpdateIdentity>,
+    wallet: Pubkey,
+    role: WalletRole,
+    expires_at: i64,
+) -> Result<()> {
+    let identity = &mut ctx.accounts.identity;
+    let clock = Clock::get()?;
+
+    // The cold role is only ever held by `cold_wallet`
+    if role == WalletRole::Cold || wallet == identity.cold_wallet {
+        return Err(GameError::InvalidMove.into());
+    }
+    if role == WalletRole::Session && expires_at <= clock.unix_timestamp {
+        return Err(GameError::InvalidMove.into());
+    }
+
+    let expires_at = if role == WalletRole::Session { expires_at } else { 0 };
+    match identity.wallets.iter_mut().find(|linked| linked.wallet == wallet) {
+        Some(linked) => {
+            linked.role = role;
+            linked.expires_at = expires_at;
+        }
+        None => {
+            if identity.wallets.len() >= MAX_LINKED_WALLETS {
+                return Err(GameError::TooManyLinkedWallets.into());
+            }
+            identity.wallets.push(LinkedWallet { wallet, role, expires_at });
+        }
+    }
+    identity.updated_at = clock.unix_timestamp;
+
+    emit!(WalletLinked {
+        identity: identity.key(),
+        wallet,
+        role,
+        expires_at,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Remove a linked wallet - cold wallet only
+pub fn unlink_wallet(ctx: Context<crate::UpdateIdentity>, wallet: Pubkey) -> Result<()> {
+    let identity = &mut ctx.accounts.identity;
+    let clock = Clock::get()?;
+
+    let before = identity.wallets.len();
+    identity.wallets.retain(|linked| linked.wallet != wallet);
+    if identity.wallets.len() == before {
+        return Err(GameError::WalletNotLinked.into());
+    }
+    identity.updated_at = clock.unix_timestamp;
+
+    emit!(WalletUnlinked {
+        identity: identity.key(),
+        wallet,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Hand the cold role to a new wallet - current cold wallet only
+pub fn set_cold_wallet(ctx: Context<crate::UpdateIdentity>, new_cold_wallet: Pubkey) -> Result<()> {
+    let identity = &mut ctx.accounts.identity;
+    let clock = Clock::get()?;
+    let previous = identity.cold_wallet;
+
+    // A wallet holds one role; promoting a hot wallet drops its hot link
+    identity.wallets.retain(|linked| linked.wallet != new_cold_wallet);
+    identity.cold_wallet = new_cold_wallet;
+    identity.updated_at = clock.unix_timestamp;
+
+    emit!(ColdWalletChanged {
+        identity: identity.key(),
+        previous,
+        cold_wallet: new_cold_wallet,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct IdentityCreated {
+    pub identity: Pubkey,
+    pub player_key: Pubkey,
+    pub cold_wallet: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WalletLinked {
+    pub identity: Pubkey,
+    pub wallet: Pubkey,
+    pub role: WalletRole,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WalletUnlinked {
+    pub identity: Pubkey,
+    pub wallet: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ColdWalletChanged {
+    pub identity: Pubkey,
+    pub previous: Pubkey,
+    pub cold_wallet: Pubkey,
+    pub timestamp: i64,
+}
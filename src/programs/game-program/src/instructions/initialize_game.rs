@@ -11,6 +11,9 @@ pub fn handler(ctx: Context<crate::InitializeGame>, upgrade_authority: Pubkey) -
     game_state.paused = false;
     game_state.bump = ctx.bumps.game_state;
     
+    // Settlement and admin paths read the config, so it exists from the start
+    ctx.accounts.admin_config.init(upgrade_authority, ctx.bumps.admin_config);
+    
     emit!(GameInitialized {
         upgrade_authority,
         timestamp: Clock::get()?.unix_timestamp,
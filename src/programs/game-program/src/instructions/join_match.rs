@@ -1,25 +1,72 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Allocate, Assign};
 use anchor_spl::token::{self, Transfer};
 use crate::state::{Match, PlayerProfile};
-use crate::shared::{GameState, GameError, validate_entry_fee};
+use crate::shared::{GameState, GameError, PairingWindow, PAIRING_WINDOW_SEED, PlayerIdentity, resolve_player, validate_entry_fee};
 
-pub fn handler(ctx: Context<crate::JoinMatch>) -> Result<()> {
+/// Pairing windows (one per player already seated, in seat order) are passed
+/// in `remaining_accounts` and created on first use
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, crate::JoinMatch<'info>>,
+    allowlist_proof: Vec<[u8; 32]>,
+) -> Result<()> {
     let match_account = &mut ctx.accounts.match_account;
     let player_profile = &mut ctx.accounts.player_profile;
     let clock = Clock::get()?;
+    let player_key = resolve_player(ctx.accounts.player_identity.as_deref(), PlayerIdentity::exists(&ctx.accounts.signer_identity), &ctx.accounts.player.key(), clock.unix_timestamp)?;
     
     // Validate match state
     if match_account.state != GameState::WaitingForPlayers {
         return Err(GameError::InvalidGameState.into());
     }
     
+    // Players serving an AFK ban cannot enter matchmaking
+    if ctx.accounts.player_penalty.is_banned(clock.unix_timestamp) {
+        return Err(GameError::MatchmakingBanned.into());
+    }
+    
+    // Regulated deployments require an allowlist proof or issuer credential
+    ctx.accounts.compliance_config.verify(
+        &player_key,
+        &allowlist_proof,
+        ctx.accounts.compliance_credential.as_deref(),
+        clock.unix_timestamp,
+    )?;
+    
     // Check if player is already in the match
     for existing_player in &match_account.players {
-        if existing_player.player == ctx.accounts.player.key() {
+        if existing_player.player == player_key {
             return Err(GameError::PlayerAlreadyRegistered.into());
         }
     }
     
+    // Repeat pairings with the same opponent are capped per window to blunt
+    // win-trading; the counter is shared by both players, so it does not
+    // matter who creates and who joins. Repeats still allowed are flagged.
+    let opponents: Vec<Pubkey> = match_account.players.iter().map(|p| p.player).collect();
+    if ctx.remaining_accounts.len() < opponents.len() {
+        return Err(ErrorCode::AccountNotEnoughKeys.into());
+    }
+    for (opponent, window_info) in opponents.iter().zip(ctx.remaining_accounts) {
+        let pairings = record_pairing(
+            window_info,
+            &ctx.accounts.player,
+            &ctx.accounts.system_program,
+            player_key,
+            *opponent,
+            clock.unix_timestamp,
+        )?;
+        if pairings > 1 {
+            emit!(RepeatOpponentFlagged {
+                match_id: match_account.match_id,
+                player: player_key,
+                opponent: *opponent,
+                pairings,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+    }
+    
     // Check if match is full
     if match_account.players.len() >= match_account.config.max_players as usize {
         return Err(GameError::MatchFull.into());
@@ -46,7 +93,7 @@ pub fn handler(ctx: Context<crate::JoinMatch>) -> Result<()> {
     
     // Add player to match
     let player_stats = player_profile.get_current_stats();
-    match_account.add_player(ctx.accounts.player.key(), player_stats)?;
+    match_account.add_player(player_key, player_stats, player_profile.prestige_level)?;
     match_account.reward_pool = match_account.reward_pool
         .checked_add(match_account.config.entry_fee)
         .ok_or(GameError::ArithmeticOverflow)?;
@@ -56,7 +103,7 @@ pub fn handler(ctx: Context<crate::JoinMatch>) -> Result<()> {
     
     emit!(PlayerJoinedMatch {
         match_id: match_account.match_id,
-        player: ctx.accounts.player.key(),
+        player: player_key,
         players_count: match_account.players.len() as u8,
         timestamp: clock.unix_timestamp,
     });
@@ -78,7 +125,7 @@ pub fn handler(ctx: Context<crate::JoinMatch>) -> Result<()> {
     
     msg!(
         "Player {} joined match {} ({}/{} players)",
-        ctx.accounts.player.key(),
+        player_key,
         match_account.match_id,
         match_account.players.len(),
         match_account.config.max_players
@@ -95,6 +142,73 @@ pub struct PlayerJoinedMatch {
     pub timestamp: i64,
 }
 
+/// Count a pairing on the pair's PairingWindow, creating the PDA (paid by the
+/// joiner) the first time the two players meet
+fn record_pairing<'info>(
+    window_info: &'info AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    player: Pubkey,
+    opponent: Pubkey,
+    now: i64,
+) -> Result<u16> {
+    let (first, second) = PairingWindow::ordered(player, opponent);
+    let (expected, bump) = Pubkey::find_program_address(
+        &[PAIRING_WINDOW_SEED, first.as_ref(), second.as_ref()],
+        &crate::ID,
+    );
+    if window_info.key() != expected {
+        return Err(ErrorCode::ConstraintSeeds.into());
+    }
+    
+    if window_info.data_is_empty() {
+        // Top up rather than create, so lamports sent to the address ahead of time cannot block it
+        let required = Rent::get()?.minimum_balance(PairingWindow::LEN);
+        let top_up = required.saturating_sub(window_info.lamports());
+        if top_up > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    system_program.to_account_info(),
+                    system_program::Transfer { from: payer.to_account_info(), to: window_info.clone() },
+                ),
+                top_up,
+            )?;
+        }
+        let seeds: &[&[u8]] = &[PAIRING_WINDOW_SEED, first.as_ref(), second.as_ref(), &[bump]];
+        system_program::allocate(
+            CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                Allocate { account_to_allocate: window_info.clone() },
+                &[seeds],
+            ),
+            PairingWindow::LEN as u64,
+        )?;
+        system_program::assign(
+            CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                Assign { account_to_assign: window_info.clone() },
+                &[seeds],
+            ),
+            &crate::ID,
+        )?;
+        PairingWindow::default().try_serialize(&mut &mut window_info.try_borrow_mut_data()?[..])?;
+    }
+    
+    let mut window: Account<PairingWindow> = Account::try_from(window_info)?;
+    let pairings = window.record(player, opponent, bump, now)?;
+    window.exit(&crate::ID)?;
+    Ok(pairings)
+}
+
+#[event]
+pub struct RepeatOpponentFlagged {
+    pub match_id: u64,
+    pub player: Pubkey,
+    pub opponent: Pubkey,
+    pub pairings: u16,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct MatchStarted {
     pub match_id: u64,
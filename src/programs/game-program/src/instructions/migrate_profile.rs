@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::state::{PlayerProfile, QueueStats};
+use crate::shared::{GameError, MAX_USERNAME_LENGTH, QUEUE_TYPES};
+
+/// Grow a profile written before per-queue stats existed to the current
+/// layout. Permissionless; the payer covers the extra rent. Existing queue
+/// results start empty.
+pub fn handler(ctx: Context<crate::MigratePlayerProfile>, player: Pubkey) -> Result<()> {
+    let profile_info = ctx.accounts.player_profile.to_account_info();
+    if profile_info.data_len() >= PlayerProfile::LEN {
+        return Err(GameError::InvalidGameState.into());
+    }
+
+    let username_len = {
+        let data = profile_info.try_borrow_data()?;
+        if data.len() < 44 || data[..8] != *PlayerProfile::DISCRIMINATOR {
+            return Err(ErrorCode::AccountDiscriminatorMismatch.into());
+        }
+        u32::from_le_bytes(data[40..44].try_into().unwrap()) as usize
+    };
+    if username_len > MAX_USERNAME_LENGTH {
+        return Err(GameError::InvalidGameState.into());
+    }
+
+    let required = Rent::get()?.minimum_balance(PlayerProfile::LEN);
+    let top_up = required.saturating_sub(profile_info.lamports());
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer { from: ctx.accounts.payer.to_account_info(), to: profile_info.clone() },
+            ),
+            top_up,
+        )?;
+    }
+    profile_info.realloc(PlayerProfile::LEN, true)?;
+
+    // The old bump byte moves behind the new, zeroed queue stats
+    let mut data = profile_info.try_borrow_mut_data()?;
+    let offset = PlayerProfile::legacy_queue_stats_offset(username_len);
+    let stats_end = offset + QUEUE_TYPES * QueueStats::LEN;
+    let bump = data[offset];
+    data[offset..stats_end].fill(0);
+    data[stats_end] = bump;
+    drop(data);
+
+    // Fails if the bytes were not a legacy profile after all
+    Account::<PlayerProfile>::try_from(&profile_info)?;
+
+    emit!(PlayerProfileMigrated {
+        player,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct PlayerProfileMigrated {
+    pub player: Pubkey,
+    pub timestamp: i64,
+}
@@ -8,6 +8,18 @@ pub mod end_turn;
 pub mod finish_match;
 pub mod update_player_stats;
 pub mod emergency_stop_match;
+pub mod claim_turn_timeout;
+pub mod compliance;
+pub mod identity;
+pub mod recovery;
+pub mod sponsorship;
+pub mod progression;
+pub mod prestige;
+pub mod daily_reward;
+pub mod gems;
+pub mod close_match;
+pub mod tournament;
+pub mod migrate_profile;
 // SECURITY: Admin functions with access control
 pub mod admin_functions;
 
@@ -21,5 +33,17 @@ pub use end_turn::*;
 pub use finish_match::*;
 pub use update_player_stats::*;
 pub use emergency_stop_match::*;
+pub use claim_turn_timeout::*;
+pub use compliance::*;
+pub use identity::*;
+pub use recovery::*;
+pub use sponsorship::*;
+pub use progression::*;
+pub use prestige::*;
+pub use daily_reward::*;
+pub use gems::*;
+pub use close_match::*;
+pub use tournament::*;
+pub use migrate_profile::*;
 // SECURITY: Admin functions exports
 pub use admin_functions::*;
\ No newline at end of file
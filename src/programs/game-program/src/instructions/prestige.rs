@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, FreezeAccount, MintTo, Transfer};
+use crate::shared::{PlayerIdentity, resolve_player, GameError};
+
+/// Reset a max-level player into the next prestige tier and mint the tier's
+/// badge. The badge account is frozen straight after minting, so it cannot be
+/// transferred.
+pub fn handler(ctx: Context<crate::PrestigePlayer>) -> Result<()> {
+    let clock = Clock::get()?;
+    let player_key = resolve_player(ctx.accounts.player_identity.as_deref(), PlayerIdentity::exists(&ctx.accounts.signer_identity), &ctx.accounts.player.key(), clock.unix_timestamp)?;
+    
+    let tier = ctx.accounts.player_profile.prestige(&ctx.accounts.class_config, clock.unix_timestamp)?;
+    
+    // Game state PDA is the badge mint and freeze authority
+    let game_state_seeds: &[&[u8]] = &[b"game_state", &[ctx.accounts.game_state.bump]];
+    let signer = &[game_state_seeds];
+    
+    token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.badge_mint.to_account_info(),
+                to: ctx.accounts.badge_token_account.to_account_info(),
+                authority: ctx.accounts.game_state.to_account_info(),
+            },
+            signer,
+        ),
+        1,
+    )?;
+    
+    token::freeze_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        FreezeAccount {
+            account: ctx.accounts.badge_token_account.to_account_info(),
+            mint: ctx.accounts.badge_mint.to_account_info(),
+            authority: ctx.accounts.game_state.to_account_info(),
+        },
+        signer,
+    ))?;
+    
+    emit!(PlayerPrestiged {
+        player: player_key,
+        prestige_level: tier,
+        multiplier_bps: ctx.accounts.player_profile.prestige_multiplier_bps(),
+        badge_mint: ctx.accounts.badge_mint.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Player {} reached prestige tier {}", player_key, tier);
+    Ok(())
+}
+
+/// Add tokens to the prestige bonus fund. Anyone may fund it.
+pub fn fund_prestige_bonus(ctx: Context<crate::FundPrestigeBonus>, amount: u64) -> Result<()> {
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.funder_token_account.to_account_info(),
+                to: ctx.accounts.fund_vault.to_account_info(),
+                authority: ctx.accounts.funder.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+    
+    let fund = &mut ctx.accounts.prestige_fund;
+    if fund.mint == Pubkey::default() {
+        fund.mint = ctx.accounts.mint.key();
+        fund.bump = ctx.bumps.prestige_fund;
+    }
+    fund.available = fund.available.checked_add(amount).ok_or(GameError::ArithmeticOverflow)?;
+    fund.total_funded = fund.total_funded.saturating_add(amount);
+    
+    emit!(PrestigeBonusFunded {
+        funder: ctx.accounts.funder.key(),
+        amount,
+        available: fund.available,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct PrestigeBonusFunded {
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub available: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PlayerPrestiged {
+    pub player: Pubkey,
+    pub prestige_level: u8,
+    pub multiplier_bps: u64,
+    pub badge_mint: Pubkey,
+    pub timestamp: i64,
+}
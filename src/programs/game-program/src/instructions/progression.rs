@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use crate::state::{ClassConfig, MAX_CLASS_LEVEL};
+use crate::shared::{GameError, PlayerClass, PlayerStats, PlayerIdentity, resolve_player};
+
+/// Set a class's level curve, stat points per level and allocation caps
+pub fn configure_class(
+    ctx: Context<crate::ConfigureClass>,
+    player_class: PlayerClass,
+    level_thresholds: [u64; MAX_CLASS_LEVEL],
+    max_level: u32,
+    stat_points_per_level: u8,
+    gain_per_point: PlayerStats,
+    allocation_caps: PlayerStats,
+) -> Result<()> {
+    if !ClassConfig::validate_curve(&level_thresholds, max_level) {
+        return Err(GameError::InvalidLevelCurve.into());
+    }
+    
+    let class_config = &mut ctx.accounts.class_config;
+    let clock = Clock::get()?;
+    
+    class_config.player_class = player_class;
+    class_config.level_thresholds = level_thresholds;
+    class_config.max_level = max_level;
+    class_config.stat_points_per_level = stat_points_per_level;
+    class_config.gain_per_point = gain_per_point;
+    class_config.allocation_caps = allocation_caps;
+    class_config.updated_at = clock.unix_timestamp;
+    class_config.bump = ctx.bumps.class_config;
+    
+    emit!(ClassConfigured {
+        player_class,
+        max_level,
+        stat_points_per_level,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Spend unspent stat points; `points` is the number of points put into each stat
+pub fn allocate_stat_points(ctx: Context<crate::AllocateStatPoints>, points: PlayerStats) -> Result<()> {
+    let player_profile = &mut ctx.accounts.player_profile;
+    let class_config = &ctx.accounts.class_config;
+    let clock = Clock::get()?;
+    let player_key = resolve_player(ctx.accounts.player_identity.as_deref(), PlayerIdentity::exists(&ctx.accounts.signer_identity), &ctx.accounts.player.key(), clock.unix_timestamp)?;
+    
+    let spent = [points.health, points.attack, points.defense, points.speed, points.mana]
+        .iter()
+        .try_fold(0u32, |total, value| total.checked_add(*value))
+        .ok_or(GameError::ArithmeticOverflow)?;
+    if spent == 0 || spent > player_profile.unspent_stat_points {
+        return Err(GameError::InsufficientStatPoints.into());
+    }
+    
+    let bonus = class_config.bonus_for(&points).ok_or(GameError::ArithmeticOverflow)?;
+    let current = player_profile.allocated_stats;
+    let allocated = PlayerStats {
+        health: current.health.checked_add(bonus.health).ok_or(GameError::ArithmeticOverflow)?,
+        attack: current.attack.checked_add(bonus.attack).ok_or(GameError::ArithmeticOverflow)?,
+        defense: current.defense.checked_add(bonus.defense).ok_or(GameError::ArithmeticOverflow)?,
+        speed: current.speed.checked_add(bonus.speed).ok_or(GameError::ArithmeticOverflow)?,
+        mana: current.mana.checked_add(bonus.mana).ok_or(GameError::ArithmeticOverflow)?,
+    };
+    if !class_config.within_caps(&allocated) {
+        return Err(GameError::StatCapExceeded.into());
+    }
+    
+    player_profile.allocated_stats = allocated;
+    player_profile.unspent_stat_points -= spent;
+    
+    emit!(StatPointsAllocated {
+        player: player_key,
+        points,
+        remaining_points: player_profile.unspent_stat_points,
+        current_stats: player_profile.get_current_stats(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct ClassConfigured {
+    pub player_class: PlayerClass,
+    pub max_level: u32,
+    pub stat_points_per_level: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StatPointsAllocated {
+    pub player: Pubkey,
+    pub points: PlayerStats,
+    pub remaining_points: u32,
+    pub current_stats: PlayerStats,
+    pub timestamp: i64,
+}
@@ -0,0 +1,210 @@
+use anchor_lang::prelude::*;
+use crate::state::{MAX_GUARDIANS, RECOVERY_TIMELOCK};
+use crate::shared::GameError;
+
+/// Register the guardian set and approval threshold - cold wallet only.
+/// Replacing the set cancels any recovery in progress.
+pub fn configure_guardians(
+    ctx: Context<crate::ConfigureGuardians>,
+    guardians: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    let identity = &ctx.accounts.identity;
+    let recovery = &mut ctx.accounts.recovery;
+
+    if guardians.is_empty() || guardians.len() > MAX_GUARDIANS {
+        return Err(GameError::InvalidGuardianConfig.into());
+    }
+    if threshold == 0 || threshold as usize > guardians.len() {
+        return Err(GameError::InvalidGuardianConfig.into());
+    }
+    for (i, guardian) in guardians.iter().enumerate() {
+        // Guardians must be distinct and independent of the identity's own wallets
+        if guardians[..i].contains(guardian) || identity.role_of(guardian, 0).is_some() {
+            return Err(GameError::InvalidGuardianConfig.into());
+        }
+    }
+
+    recovery.identity = identity.key();
+    recovery.guardians = guardians.clone();
+    recovery.threshold = threshold;
+    recovery.clear_pending();
+    recovery.bump = ctx.bumps.recovery;
+
+    emit!(GuardiansConfigured {
+        identity: identity.key(),
+        guardians,
+        threshold,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Start re-pointing the identity to `new_wallet`. The initiating guardian's
+/// approval counts, and the timelock starts now. A pending recovery can only be
+/// replaced once the cold wallet or a threshold of guardians cancels it.
+pub fn initiate_recovery(ctx: Context<crate::GuardianRecovery>, new_wallet: Pubkey) -> Result<()> {
+    let recovery = &mut ctx.accounts.recovery;
+    let clock = Clock::get()?;
+
+    let index = recovery.guardian_index(&ctx.accounts.guardian.key())
+        .ok_or(GameError::NotGuardian)?;
+    if recovery.pending_wallet.is_some() {
+        return Err(GameError::RecoveryAlreadyPending.into());
+    }
+
+    recovery.pending_wallet = Some(new_wallet);
+    recovery.initiated_at = clock.unix_timestamp;
+    recovery.approvals = 1 << index;
+    recovery.cancel_votes = 0;
+
+    emit!(RecoveryInitiated {
+        identity: recovery.identity,
+        guardian: ctx.accounts.guardian.key(),
+        new_wallet,
+        executable_at: clock.unix_timestamp + RECOVERY_TIMELOCK,
+    });
+
+    Ok(())
+}
+
+/// Add a guardian's approval to the pending recovery. The guardian names the
+/// wallet they approve, so an approval never carries over to a different one.
+pub fn approve_recovery(ctx: Context<crate::GuardianRecovery>, new_wallet: Pubkey) -> Result<()> {
+    let recovery = &mut ctx.accounts.recovery;
+
+    let index = recovery.guardian_index(&ctx.accounts.guardian.key())
+        .ok_or(GameError::NotGuardian)?;
+    match recovery.pending_wallet {
+        None => return Err(GameError::NoPendingRecovery.into()),
+        Some(pending) if pending != new_wallet => return Err(GameError::RecoveryWalletMismatch.into()),
+        Some(_) => {}
+    }
+
+    recovery.approvals |= 1 << index;
+
+    emit!(RecoveryApproved {
+        identity: recovery.identity,
+        guardian: ctx.accounts.guardian.key(),
+        approvals: recovery.approval_count(),
+        threshold: recovery.threshold,
+    });
+
+    Ok(())
+}
+
+/// Re-point the identity once enough guardians approved and the timelock passed.
+/// Every previously linked wallet is dropped since any of them may be compromised;
+/// the player key is untouched, so profiles, NFTs and stakes stay reachable. The
+/// old player-key wallet cannot skip the identity to reach them (resolve_player
+/// requires it once `[b"identity", wallet]` exists), so it stays locked out
+/// until the new cold wallet links it again.
+pub fn execute_recovery(ctx: Context<crate::ExecuteRecovery>) -> Result<()> {
+    let identity = &mut ctx.accounts.identity;
+    let recovery = &mut ctx.accounts.recovery;
+    let clock = Clock::get()?;
+
+    if !recovery.can_execute(clock.unix_timestamp) {
+        return Err(GameError::RecoveryNotReady.into());
+    }
+    let new_wallet = recovery.pending_wallet.ok_or(GameError::NoPendingRecovery)?;
+    let previous = identity.cold_wallet;
+
+    identity.cold_wallet = new_wallet;
+    identity.wallets.clear();
+    identity.updated_at = clock.unix_timestamp;
+
+    recovery.clear_pending();
+
+    emit!(IdentityRecovered {
+        identity: identity.key(),
+        previous,
+        cold_wallet: new_wallet,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Abort a pending recovery - cold wallet only, e.g. against colluding guardians
+pub fn cancel_recovery(ctx: Context<crate::ConfigureGuardians>) -> Result<()> {
+    let recovery = &mut ctx.accounts.recovery;
+
+    if recovery.pending_wallet.is_none() {
+        return Err(GameError::NoPendingRecovery.into());
+    }
+
+    recovery.clear_pending();
+
+    emit!(RecoveryCancelled {
+        identity: recovery.identity,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Vote to abort the pending recovery - guardian only. It is cancelled once
+/// the same threshold of guardians that could execute it votes against it.
+pub fn vote_cancel_recovery(ctx: Context<crate::GuardianRecovery>) -> Result<()> {
+    let recovery = &mut ctx.accounts.recovery;
+
+    let index = recovery.guardian_index(&ctx.accounts.guardian.key())
+        .ok_or(GameError::NotGuardian)?;
+    if recovery.pending_wallet.is_none() {
+        return Err(GameError::NoPendingRecovery.into());
+    }
+
+    recovery.cancel_votes |= 1 << index;
+    if recovery.cancel_vote_count() < recovery.threshold {
+        return Ok(());
+    }
+
+    recovery.clear_pending();
+
+    emit!(RecoveryCancelled {
+        identity: recovery.identity,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct GuardiansConfigured {
+    pub identity: Pubkey,
+    pub guardians: Vec<Pubkey>,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecoveryInitiated {
+    pub identity: Pubkey,
+    pub guardian: Pubkey,
+    pub new_wallet: Pubkey,
+    pub executable_at: i64,
+}
+
+#[event]
+pub struct RecoveryApproved {
+    pub identity: Pubkey,
+    pub guardian: Pubkey,
+    pub approvals: u8,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct IdentityRecovered {
+    pub identity: Pubkey,
+    pub previous: Pubkey,
+    pub cold_wallet: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecoveryCancelled {
+    pub identity: Pubkey,
+    pub timestamp: i64,
+}
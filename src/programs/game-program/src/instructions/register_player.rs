@@ -1,11 +1,44 @@
 use anchor_lang::prelude::*;
-use crate::state::PlayerProfile;
-use crate::shared::{PlayerClass, PlayerStats, GameError, MAX_USERNAME_LENGTH};
+use crate::state::{PlayerProfile, QueueStats};
+use crate::shared::{PlayerClass, PlayerStats, GameError, PlayerIdentity, resolve_player, MAX_USERNAME_LENGTH, QUEUE_TYPES};
 
 pub fn handler(
     ctx: Context<crate::RegisterPlayer>,
     username: String,
     player_class: PlayerClass,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let player_key = resolve_player(ctx.accounts.player_identity.as_deref(), PlayerIdentity::exists(&ctx.accounts.signer_identity), &ctx.accounts.player.key(), clock.unix_timestamp)?;
+    
+    initialize_profile(
+        &mut ctx.accounts.player_profile,
+        player_key,
+        username.clone(),
+        player_class,
+        clock.unix_timestamp,
+        ctx.bumps.player_profile,
+    )?;
+    
+    emit!(PlayerRegistered {
+        player: player_key,
+        username: username.clone(),
+        player_class,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Player {} registered with class {:?}", username, player_class);
+    Ok(())
+}
+
+/// Validate the username and set up a fresh profile. Shared by self-registration
+/// and sponsored registration.
+pub(crate) fn initialize_profile(
+    player_profile: &mut PlayerProfile,
+    owner: Pubkey,
+    username: String,
+    player_class: PlayerClass,
+    now: i64,
+    bump: u8,
 ) -> Result<()> {
     // Validate username length
     if username.len() > MAX_USERNAME_LENGTH {
@@ -16,9 +49,6 @@ pub fn handler(
         return Err(GameError::InvalidMatchConfig.into());
     }
     
-    let player_profile = &mut ctx.accounts.player_profile;
-    let clock = Clock::get()?;
-    
     // Initialize base stats based on player class
     let base_stats = match player_class {
         PlayerClass::Warrior => PlayerStats::new_warrior(),
@@ -27,8 +57,8 @@ pub fn handler(
         PlayerClass::Rogue => PlayerStats::new_rogue(),
     };
     
-    player_profile.owner = ctx.accounts.player.key();
-    player_profile.username = username.clone();
+    player_profile.owner = owner;
+    player_profile.username = username;
     player_profile.player_class = player_class;
     player_profile.base_stats = base_stats;
     player_profile.level = 1;
@@ -38,19 +68,21 @@ pub fn handler(
     player_profile.losses = 0;
     player_profile.total_damage_dealt = 0;
     player_profile.total_damage_taken = 0;
-    player_profile.created_at = clock.unix_timestamp;
+    player_profile.created_at = now;
     player_profile.last_match_at = 0;
     player_profile.is_active = true;
-    player_profile.bump = ctx.bumps.player_profile;
+    player_profile.timeout_count = 0;
+    player_profile.afk_strikes = 0;
+    player_profile.last_timeout_at = 0;
+    player_profile.unspent_stat_points = 0;
+    player_profile.allocated_stats = PlayerStats { health: 0, attack: 0, defense: 0, speed: 0, mana: 0 };
+    player_profile.prestige_level = 0;
+    player_profile.last_prestige_at = 0;
+    player_profile.gems = 0;
+    player_profile.lifetime_winnings = 0;
+    player_profile.queue_stats = [QueueStats::default(); QUEUE_TYPES];
+    player_profile.bump = bump;
     
-    emit!(PlayerRegistered {
-        player: ctx.accounts.player.key(),
-        username: username.clone(),
-        player_class,
-        timestamp: clock.unix_timestamp,
-    });
-    
-    msg!("Player {} registered with class {:?}", username, player_class);
     Ok(())
 }
 
@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+use crate::state::{PlayerProfile, PenaltyComponent, Sponsorship};
+use crate::shared::{PlayerClass, GameError};
+use super::register_player::{initialize_profile, PlayerRegistered};
+
+/// Create a sponsor vault. It is funded by plain lamport transfers afterwards.
+pub fn create_sponsor_vault(ctx: Context<crate::CreateSponsorVault>, daily_cap: u32) -> Result<()> {
+    let vault = &mut ctx.accounts.sponsor_vault;
+
+    vault.authority = ctx.accounts.authority.key();
+    vault.daily_cap = daily_cap;
+    vault.window_start = Clock::get()?.unix_timestamp;
+    vault.bump = ctx.bumps.sponsor_vault;
+
+    Ok(())
+}
+
+/// Change the vault's daily registration cap - vault authority only
+pub fn set_sponsor_cap(ctx: Context<crate::SetSponsorCap>, daily_cap: u32) -> Result<()> {
+    ctx.accounts.sponsor_vault.daily_cap = daily_cap;
+    Ok(())
+}
+
+/// Register a new player with the sponsor paying rent for the profile and
+/// penalty component. The relayer funds the accounts up front and is
+/// reimbursed from the vault, so the player needs no SOL. A penalty record
+/// left by an earlier profile is kept as it is.
+pub fn sponsor_registration(
+    ctx: Context<crate::SponsorRegistration>,
+    username: String,
+    player_class: PlayerClass,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let player = ctx.accounts.player.key();
+
+    initialize_profile(
+        &mut ctx.accounts.player_profile,
+        player,
+        username.clone(),
+        player_class,
+        clock.unix_timestamp,
+        ctx.bumps.player_profile,
+    )?;
+
+    let rent = Rent::get()?;
+    let mut lamports = rent.minimum_balance(PlayerProfile::LEN) + rent.minimum_balance(Sponsorship::LEN);
+
+    let penalty = &mut ctx.accounts.player_penalty;
+    if penalty.player == Pubkey::default() {
+        penalty.player = player;
+        penalty.bump = ctx.bumps.player_penalty;
+        lamports += rent.minimum_balance(PenaltyComponent::LEN);
+    }
+
+    {
+        let vault_info = ctx.accounts.sponsor_vault.to_account_info();
+        let rent_floor = rent.minimum_balance(vault_info.data_len());
+        if vault_info.lamports().saturating_sub(rent_floor) < lamports {
+            return Err(GameError::InsufficientSponsorFunds.into());
+        }
+
+        // The vault is owned by this program, so lamports can be moved directly
+        **vault_info.try_borrow_mut_lamports()? -= lamports;
+        **ctx.accounts.relayer.to_account_info().try_borrow_mut_lamports()? += lamports;
+    }
+
+    let vault = &mut ctx.accounts.sponsor_vault;
+    vault.consume_daily_slot(clock.unix_timestamp)?;
+    vault.total_sponsored += 1;
+    vault.active_sponsorships += 1;
+    vault.lamports_advanced += lamports;
+
+    let sponsorship = &mut ctx.accounts.sponsorship;
+    sponsorship.sponsor_vault = vault.key();
+    sponsorship.player = player;
+    sponsorship.lamports = lamports;
+    sponsorship.created_at = clock.unix_timestamp;
+    sponsorship.bump = ctx.bumps.sponsorship;
+
+    emit!(PlayerRegistered {
+        player,
+        username,
+        player_class,
+        timestamp: clock.unix_timestamp,
+    });
+
+    emit!(RegistrationSponsored {
+        sponsor_vault: vault.key(),
+        player,
+        lamports,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Close a sponsored player's profile. Its rent goes back to the sponsor vault;
+/// the penalty record stays open so closing cannot clear a ban.
+pub fn close_sponsored_profile(ctx: Context<crate::CloseSponsoredProfile>) -> Result<()> {
+    let reclaimed = ctx.accounts.player_profile.to_account_info().lamports()
+        + ctx.accounts.sponsorship.to_account_info().lamports();
+
+    let vault = &mut ctx.accounts.sponsor_vault;
+    vault.active_sponsorships = vault.active_sponsorships.saturating_sub(1);
+    vault.lamports_reclaimed += reclaimed;
+
+    emit!(SponsorshipReclaimed {
+        sponsor_vault: vault.key(),
+        player: ctx.accounts.player.key(),
+        lamports: reclaimed,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct RegistrationSponsored {
+    pub sponsor_vault: Pubkey,
+    pub player: Pubkey,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SponsorshipReclaimed {
+    pub sponsor_vault: Pubkey,
+    pub player: Pubkey,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
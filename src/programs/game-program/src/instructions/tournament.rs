@@ -0,0 +1,496 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, TokenAccount, Transfer};
+use crate::state::{
+    valid_blind_schedule, EntryMethod, PayoutTable, Standing, TournamentBlindLevel, TournamentConfig, TournamentFormat,
+    TournamentState, MAX_PLATFORM_FEE_BPS, TOURNAMENT_START_DEADLINE,
+};
+use crate::shared::{split_by_weights, GameError, TournamentTicket, NFT_PROGRAM_ID};
+
+/// Set the protocol's cut of tournament prize pools - upgrade authority only
+pub fn configure_tournament_fees(
+    ctx: Context<crate::ConfigureTournamentFees>,
+    treasury: Pubkey,
+    platform_fee_bps: u16,
+) -> Result<()> {
+    if platform_fee_bps > MAX_PLATFORM_FEE_BPS {
+        return Err(GameError::InvalidTournamentConfig.into());
+    }
+
+    let fee_config = &mut ctx.accounts.fee_config;
+    fee_config.treasury = treasury;
+    fee_config.platform_fee_bps = platform_fee_bps;
+    fee_config.bump = ctx.bumps.fee_config;
+
+    Ok(())
+}
+
+/// Open a tournament for registration. Anyone may organize one; the creator
+/// sets the rules, takes `organizer_fee_bps` of the pool and pays the
+/// protocol's platform fee out of it at finalize.
+pub fn create_tournament(
+    ctx: Context<crate::CreateTournament>,
+    tournament_id: u64,
+    config: TournamentConfig,
+    blind_levels: Vec<TournamentBlindLevel>,
+) -> Result<()> {
+    if !config.validate(config.max_entrants as usize) || !valid_blind_schedule(&blind_levels) {
+        return Err(GameError::InvalidTournamentConfig.into());
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    let tournament = &mut ctx.accounts.tournament;
+    tournament.authority = ctx.accounts.authority.key();
+    tournament.tournament_id = tournament_id;
+    tournament.state = TournamentState::Registration;
+    tournament.config = config;
+    tournament.entry_mint = ctx.accounts.entry_mint.key();
+    tournament.prize_vault = ctx.accounts.prize_vault.key();
+    tournament.prize_pool = 0;
+    tournament.platform_fee_bps = ctx.accounts.fee_config.platform_fee_bps;
+    tournament.blind_levels = blind_levels;
+    tournament.entrants = Vec::new();
+    tournament.created_at = now;
+    tournament.bump = ctx.bumps.tournament;
+
+    let bracket = &mut ctx.accounts.bracket;
+    bracket.tournament = tournament.key();
+    bracket.pairings = Vec::new();
+    bracket.standings = Vec::new();
+    bracket.bump = ctx.bumps.bracket;
+    bracket.touch(now);
+
+    emit!(TournamentCreated {
+        tournament: tournament.key(),
+        authority: tournament.authority,
+        format: config.format,
+        max_entrants: config.max_entrants,
+        best_of: config.best_of,
+        entry_fee: config.entry_fee,
+        organizer_fee_bps: config.organizer_fee_bps,
+        platform_fee_bps: tournament.platform_fee_bps,
+    });
+
+    Ok(())
+}
+
+/// Take a seat in a tournament, paying the entry fee or, when a ticket is
+/// passed, burning a ticket NFT instead. Swiss events with a late window also
+/// admit new players after the start at the field's average points, and
+/// eliminated players back in at zero.
+pub fn register_for_tournament(ctx: Context<crate::RegisterForTournament>) -> Result<()> {
+    let tournament = &mut ctx.accounts.tournament;
+    let bracket = &mut ctx.accounts.bracket;
+    let entry = &mut ctx.accounts.entry;
+    let player = ctx.accounts.player.key();
+    let now = Clock::get()?.unix_timestamp;
+
+    let late = match tournament.state {
+        TournamentState::Registration if !tournament.start_deadline_passed(now) => false,
+        TournamentState::InProgress if tournament.config.late_registration_open(bracket.current_round) => true,
+        _ => return Err(GameError::TournamentNotOpen.into()),
+    };
+    if !tournament.config.requirements.met_by(&ctx.accounts.player_profile) {
+        return Err(GameError::EntryRequirementsNotMet.into());
+    }
+
+    // An existing entry means this is a re-entry
+    let reentry = entry.player != Pubkey::default();
+    if reentry {
+        if !late {
+            return Err(GameError::PlayerAlreadyRegistered.into());
+        }
+        if entry.reentries >= tournament.config.max_reentries || !bracket.is_eliminated(&player) {
+            return Err(GameError::ReentryNotAllowed.into());
+        }
+    } else {
+        let entrants = tournament.entrants.len();
+        if entrants >= tournament.config.max_entrants as usize || (late && !tournament.config.validate(entrants + 1)) {
+            return Err(GameError::TournamentFull.into());
+        }
+        entry.tournament = tournament.key();
+        entry.player = player;
+        entry.registered_at = now;
+        entry.bump = ctx.bumps.entry;
+    }
+
+    if let Some(ticket_info) = &ctx.accounts.ticket {
+        // Tickets cover a first entry only; re-entries pay the escalated fee
+        if !tournament.config.accepts_tickets || reentry {
+            return Err(GameError::TicketsNotAccepted.into());
+        }
+        let ticket = read_ticket(ticket_info)?;
+        let (ticket_mint, ticket_account) = match (&ctx.accounts.ticket_mint, &ctx.accounts.ticket_token_account) {
+            (Some(mint), Some(account)) => (mint, account),
+            _ => return Err(GameError::InvalidTicket.into()),
+        };
+        let held = ticket_account.mint == ticket.mint && ticket_account.amount > 0;
+        if ticket.mint != ticket_mint.key() || !ticket.admits(&tournament.key()) || !held {
+            return Err(GameError::InvalidTicket.into());
+        }
+
+        token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ticket_mint.to_account_info(),
+                    from: ticket_account.to_account_info(),
+                    authority: ctx.accounts.player.to_account_info(),
+                },
+            ),
+            1,
+        )?;
+
+        entry.method = EntryMethod::Ticket;
+        entry.ticket_mint = Some(ticket.mint);
+        entry.ticket_source = Some(ticket.source);
+        entry.ticket_issued_at = ticket.issued_at;
+    } else {
+        let reentry_number = if reentry { entry.reentries + 1 } else { 0 };
+        let fee = tournament.config.entry_fee_for(reentry_number).ok_or(GameError::ArithmeticOverflow)?;
+        if fee > 0 {
+            let player_token_account = ctx.accounts.player_token_account.as_ref().ok_or(GameError::InsufficientFunds)?;
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: player_token_account.to_account_info(),
+                        to: ctx.accounts.prize_vault.to_account_info(),
+                        authority: ctx.accounts.player.to_account_info(),
+                    },
+                ),
+                fee,
+            )?;
+            tournament.prize_pool = tournament.prize_pool.checked_add(fee).ok_or(GameError::ArithmeticOverflow)?;
+        }
+        if !reentry {
+            entry.method = EntryMethod::Fee;
+        }
+        entry.fee_paid = entry.fee_paid.checked_add(fee).ok_or(GameError::ArithmeticOverflow)?;
+    }
+
+    // Late entrants start from the field's average score and re-entries from
+    // zero, so buying back in is never a score boost; both are paired from the next round
+    if late {
+        let points = if reentry { 0 } else { bracket.average_points() };
+        match bracket.standing_mut(&player) {
+            Some(standing) => standing.points = points,
+            None => bracket.standings.push(Standing { player, points, ..Standing::default() }),
+        }
+        bracket.touch(now);
+    }
+    if reentry {
+        entry.reentries += 1;
+    } else {
+        tournament.entrants.push(player);
+    }
+    tournament.total_entries = tournament.total_entries.saturating_add(1);
+
+    emit!(TournamentRegistration {
+        tournament: tournament.key(),
+        player,
+        entrants: tournament.entrants.len() as u8,
+        method: entry.method,
+        ticket_mint: entry.ticket_mint,
+        late,
+        reentry: entry.reentries,
+        prize_pool: tournament.prize_pool,
+    });
+
+    Ok(())
+}
+
+/// Load a ticket record written by nft-program
+fn read_ticket(info: &AccountInfo) -> Result<TournamentTicket> {
+    if *info.owner != NFT_PROGRAM_ID {
+        return Err(GameError::InvalidTicket.into());
+    }
+    let data = info.try_borrow_data()?;
+    TournamentTicket::try_deserialize(&mut &data[..]).map_err(|_| GameError::InvalidTicket.into())
+}
+
+/// Close registration and seed the first round - tournament authority only
+pub fn start_tournament(ctx: Context<crate::StartTournament>) -> Result<()> {
+    let tournament = &mut ctx.accounts.tournament;
+    let bracket = &mut ctx.accounts.bracket;
+    let now = Clock::get()?.unix_timestamp;
+
+    if tournament.state != TournamentState::Registration || tournament.start_deadline_passed(now) {
+        return Err(GameError::TournamentNotOpen.into());
+    }
+    let entrants = tournament.entrants.len();
+    if !tournament.config.validate(entrants) {
+        return Err(GameError::InvalidTournamentConfig.into());
+    }
+
+    tournament.state = TournamentState::InProgress;
+    tournament.started_at = now;
+    bracket.total_rounds = tournament.total_rounds(entrants);
+    bracket.standings = tournament.entrants
+        .iter()
+        .map(|player| Standing { player: *player, ..Standing::default() })
+        .collect();
+    match tournament.config.format {
+        TournamentFormat::SingleElimination => bracket.seed_round(1, &tournament.entrants),
+        TournamentFormat::Swiss => bracket.seed_swiss_round(1),
+        TournamentFormat::RoundRobin => bracket.seed_round_robin_round(1, &tournament.entrants),
+    }
+    bracket.touch(now);
+
+    emit!(BracketUpdated {
+        tournament: tournament.key(),
+        round: bracket.current_round,
+        sequence: bracket.sequence,
+        champion: None,
+    });
+
+    Ok(())
+}
+
+/// Count a finished match towards its bracket pairing. Permissionless: the
+/// result comes from the match's winner attestation, so any keeper can post it.
+pub fn record_tournament_result(ctx: Context<crate::RecordTournamentResult>, pairing_index: u8) -> Result<()> {
+    let tournament = &mut ctx.accounts.tournament;
+    let bracket = &mut ctx.accounts.bracket;
+    let match_account = &ctx.accounts.match_account;
+    let attestation = &ctx.accounts.winner_attestation;
+    let now = Clock::get()?.unix_timestamp;
+
+    if tournament.state != TournamentState::InProgress {
+        return Err(GameError::TournamentNotInProgress.into());
+    }
+
+    let wins_needed = tournament.wins_needed();
+    let current_round = bracket.current_round;
+    let pairing = bracket.pairings
+        .get_mut(pairing_index as usize)
+        .ok_or(GameError::BracketPairingMismatch)?;
+    if pairing.round != current_round || pairing.winner.is_some() {
+        return Err(GameError::BracketPairingMismatch.into());
+    }
+
+    // The match must have been played by exactly this pairing
+    let seated = match_account.players.len() == 2
+        && match_account.players.iter().all(|p| pairing.seat_of(&p.player).is_some());
+    if !seated {
+        return Err(GameError::BracketPairingMismatch.into());
+    }
+
+    // Draws and cancellations do not count; each match counts once
+    let winner = attestation.winner.ok_or(GameError::BracketPairingMismatch)?;
+    let seat = pairing.seat_of(&winner).ok_or(GameError::BracketPairingMismatch)?;
+    // Matches the pair played before the tournament started are not bracket games
+    if attestation.finalized_at < tournament.started_at {
+        return Err(GameError::BracketPairingMismatch.into());
+    }
+    if attestation.finalized_at <= pairing.last_result_at {
+        return Err(GameError::TournamentResultAlreadyRecorded.into());
+    }
+
+    pairing.last_result_at = attestation.finalized_at;
+    pairing.scores[seat as usize] += 1;
+    if pairing.scores[seat as usize] >= wins_needed {
+        pairing.winner = Some(seat);
+        if let Some(standing) = bracket.standing_mut(&winner) {
+            standing.points += 1;
+        }
+    }
+
+    // Seed the next round, or settle the final standings, once every pairing is decided
+    if let Some(winners) = bracket.round_winners(current_round) {
+        match tournament.config.format {
+            TournamentFormat::SingleElimination if winners.len() == 1 => {
+                tournament.state = TournamentState::Completed;
+                tournament.champion = Some(winners[0]);
+            },
+            TournamentFormat::SingleElimination => bracket.seed_round(current_round + 1, &winners),
+            _ if current_round >= bracket.total_rounds => {
+                bracket.rank_standings();
+                tournament.state = TournamentState::Completed;
+                tournament.champion = bracket.standings.first().map(|s| s.player);
+            },
+            TournamentFormat::Swiss => {
+                bracket.refresh_buchholz();
+                bracket.seed_swiss_round(current_round + 1);
+            },
+            TournamentFormat::RoundRobin => {
+                bracket.refresh_buchholz();
+                bracket.seed_round_robin_round(current_round + 1, &tournament.entrants);
+            },
+        }
+    }
+    bracket.touch(now);
+
+    emit!(BracketUpdated {
+        tournament: tournament.key(),
+        round: bracket.current_round,
+        sequence: bracket.sequence,
+        champion: tournament.champion,
+    });
+
+    Ok(())
+}
+
+/// Pay out a completed tournament: platform fee to the treasury, organizer
+/// fee to the organizer, and the rest by the tournament's payout table.
+/// Remaining accounts are the paid places' token accounts, first place first.
+/// Permissionless.
+pub fn finalize_tournament<'info>(ctx: Context<'_, '_, '_, 'info, crate::FinalizeTournament<'info>>) -> Result<()> {
+    let tournament = &ctx.accounts.tournament;
+    if tournament.state != TournamentState::Completed {
+        return Err(GameError::TournamentNotInProgress.into());
+    }
+    let (platform_fee, organizer_fee, prize_total) = tournament.fee_split().ok_or(GameError::ArithmeticOverflow)?;
+
+    let order = ctx.accounts.bracket.finishing_order(tournament.config.format);
+    let weights = tournament.config.payout_table.weights(order.len());
+    let prizes = split_by_weights(prize_total, &weights)?;
+    if ctx.remaining_accounts.len() != prizes.len() {
+        return Err(GameError::InvalidRewardDistribution.into());
+    }
+
+    let mut payouts = vec![
+        (ctx.accounts.treasury_token_account.to_account_info(), platform_fee),
+        (ctx.accounts.organizer_token_account.to_account_info(), organizer_fee),
+    ];
+    for ((info, player), amount) in ctx.remaining_accounts.iter().zip(&order).zip(&prizes) {
+        let token_account = Account::<TokenAccount>::try_from(info)?;
+        if token_account.mint != tournament.entry_mint || token_account.owner != *player {
+            return Err(GameError::AccessDenied.into());
+        }
+        payouts.push((info.clone(), *amount));
+    }
+
+    let authority = tournament.authority;
+    let id_bytes = tournament.tournament_id.to_le_bytes();
+    let seeds = &[b"tournament".as_ref(), authority.as_ref(), id_bytes.as_ref(), &[tournament.bump]];
+    let signer = &[&seeds[..]];
+    for (to, amount) in payouts {
+        if amount == 0 {
+            continue;
+        }
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.prize_vault.to_account_info(),
+                    to,
+                    authority: ctx.accounts.tournament.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+    }
+
+    let tournament = &mut ctx.accounts.tournament;
+    tournament.state = TournamentState::Finalized;
+
+    emit!(TournamentFinalized {
+        tournament: tournament.key(),
+        payout_table: tournament.config.payout_table,
+        prize_pool: tournament.prize_pool,
+        platform_fee,
+        organizer_fee,
+        winners: order.into_iter().take(prizes.len()).collect(),
+        prizes,
+    });
+
+    Ok(())
+}
+
+/// Reclaim an entry fee from a tournament its organizer never started. The
+/// first refund after the start deadline cancels the event; ticket entries
+/// only get their entry account's rent back, since the ticket was burned.
+pub fn refund_tournament_entry(ctx: Context<crate::RefundTournamentEntry>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let refund = ctx.accounts.entry.fee_paid;
+
+    if !ctx.accounts.tournament.start_deadline_passed(now) {
+        return Err(GameError::TournamentStartDeadlineNotReached.into());
+    }
+
+    if refund > 0 {
+        let tournament = &ctx.accounts.tournament;
+        let authority = tournament.authority;
+        let id_bytes = tournament.tournament_id.to_le_bytes();
+        let seeds = &[b"tournament".as_ref(), authority.as_ref(), id_bytes.as_ref(), &[tournament.bump]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.prize_vault.to_account_info(),
+                    to: ctx.accounts.player_token_account.to_account_info(),
+                    authority: ctx.accounts.tournament.to_account_info(),
+                },
+                &[&seeds[..]],
+            ),
+            refund,
+        )?;
+    }
+
+    let tournament = &mut ctx.accounts.tournament;
+    let player = ctx.accounts.player.key();
+    tournament.state = TournamentState::Cancelled;
+    tournament.prize_pool = tournament.prize_pool.saturating_sub(refund);
+    tournament.entrants.retain(|entrant| *entrant != player);
+
+    emit!(TournamentEntryRefunded {
+        tournament: tournament.key(),
+        player,
+        amount: refund,
+        deadline: tournament.created_at + TOURNAMENT_START_DEADLINE,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct TournamentCreated {
+    pub tournament: Pubkey,
+    pub authority: Pubkey,
+    pub format: TournamentFormat,
+    pub max_entrants: u8,
+    pub best_of: u8,
+    pub entry_fee: u64,
+    pub organizer_fee_bps: u16,
+    pub platform_fee_bps: u16,
+}
+
+#[event]
+pub struct TournamentRegistration {
+    pub tournament: Pubkey,
+    pub player: Pubkey,
+    pub entrants: u8,
+    pub method: EntryMethod,
+    pub ticket_mint: Option<Pubkey>,
+    pub late: bool,
+    pub reentry: u8, // Re-entries so far; 0 for a first entry
+    pub prize_pool: u64,
+}
+
+#[event]
+pub struct BracketUpdated {
+    pub tournament: Pubkey,
+    pub round: u8,
+    pub sequence: u32,
+    pub champion: Option<Pubkey>,
+}
+
+#[event]
+pub struct TournamentFinalized {
+    pub tournament: Pubkey,
+    pub payout_table: PayoutTable,
+    pub prize_pool: u64,
+    pub platform_fee: u64,
+    pub organizer_fee: u64,
+    pub winners: Vec<Pubkey>,
+    pub prizes: Vec<u64>,
+}
+
+#[event]
+pub struct TournamentEntryRefunded {
+    pub tournament: Pubkey,
+    pub player: Pubkey,
+    pub amount: u64,
+    pub deadline: i64,
+}
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 use crate::state::PlayerProfile;
-use crate::shared::GameError;
+use crate::shared::{GameError, PlayerIdentity, resolve_player};
 
 pub fn handler(
     ctx: Context<crate::UpdatePlayerStats>,
@@ -8,12 +8,12 @@ pub fn handler(
 ) -> Result<()> {
     let player_profile = &mut ctx.accounts.player_profile;
     let clock = Clock::get()?;
+    let player_key = resolve_player(ctx.accounts.player_identity.as_deref(), PlayerIdentity::exists(&ctx.accounts.signer_identity), &ctx.accounts.player.key(), clock.unix_timestamp)?;
     
-    // Update experience and level
-    let old_level = player_profile.level;
+    // Update experience, then level up along the class curve
     player_profile.experience = player_profile.experience
         .saturating_add(experience_gained as u64);
-    player_profile.level = player_profile.calculate_level();
+    let old_level = player_profile.apply_level_ups(&ctx.accounts.class_config);
     
     // Update match count
     player_profile.total_matches = player_profile.total_matches.saturating_add(1);
@@ -22,23 +22,24 @@ pub fn handler(
     // Check for level up
     if player_profile.level > old_level {
         emit!(PlayerLevelUp {
-            player: ctx.accounts.player.key(),
+            player: player_key,
             old_level,
             new_level: player_profile.level,
             total_experience: player_profile.experience,
+            unspent_stat_points: player_profile.unspent_stat_points,
             timestamp: clock.unix_timestamp,
         });
         
         msg!(
             "Player {} leveled up from {} to {}!",
-            ctx.accounts.player.key(),
+            player_key,
             old_level,
             player_profile.level
         );
     }
     
     emit!(PlayerStatsUpdated {
-        player: ctx.accounts.player.key(),
+        player: player_key,
         experience_gained,
         total_experience: player_profile.experience,
         level: player_profile.level,
@@ -63,5 +64,6 @@ pub struct PlayerLevelUp {
     pub old_level: u32,
     pub new_level: u32,
     pub total_experience: u64,
+    pub unspent_stat_points: u32,
     pub timestamp: i64,
 }
\ No newline at end of file
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::associated_token::AssociatedToken;
 
 pub mod instructions;
 pub mod state;
@@ -8,7 +9,7 @@ pub use instructions::*;
 pub use state::*;
 
 // Import shared modules
-use crate::shared::{GameError, GameState, PlayerClass, PlayerStats, CombatAction, MatchConfig, MAX_PLAYERS_PER_MATCH};
+use crate::shared::{GameError, GameState, AdminConfig, ADMIN_CONFIG_SEED, EventSchedule, WinnerAttestation, WINNER_ATTESTATION_SEED, PlayerClass, PlayerStats, CombatAction, MatchConfig, ComplianceMode, PlayerIdentity, WalletRole, resolve_player, ReasonCode, MetricsBuffer, METRICS_BUFFER_SEED, metrics_shard, GemSink, GemReceipt, GEM_RECEIPT_SEED, MAX_PLAYERS_PER_MATCH};
 
 declare_id!("GAMExxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx");
 
@@ -30,6 +31,30 @@ pub mod sol_duel_game {
         instructions::register_player::handler(ctx, username, player_class)
     }
 
+    /// Create a vault that sponsors rent for new players
+    pub fn create_sponsor_vault(ctx: Context<CreateSponsorVault>, daily_cap: u32) -> Result<()> {
+        instructions::sponsorship::create_sponsor_vault(ctx, daily_cap)
+    }
+
+    /// Update a sponsor vault's daily cap - vault authority only
+    pub fn set_sponsor_cap(ctx: Context<SetSponsorCap>, daily_cap: u32) -> Result<()> {
+        instructions::sponsorship::set_sponsor_cap(ctx, daily_cap)
+    }
+
+    /// Register a new player with rent paid by a sponsor vault
+    pub fn sponsor_registration(
+        ctx: Context<SponsorRegistration>,
+        username: String,
+        player_class: PlayerClass,
+    ) -> Result<()> {
+        instructions::sponsorship::sponsor_registration(ctx, username, player_class)
+    }
+
+    /// Close a sponsored player's accounts, returning rent to the sponsor
+    pub fn close_sponsored_profile(ctx: Context<CloseSponsoredProfile>) -> Result<()> {
+        instructions::sponsorship::close_sponsored_profile(ctx)
+    }
+
     /// Create a new match
     pub fn create_match(
         ctx: Context<CreateMatch>,
@@ -39,8 +64,11 @@ pub mod sol_duel_game {
     }
 
     /// Join an existing match
-    pub fn join_match(ctx: Context<JoinMatch>) -> Result<()> {
-        instructions::join_match::handler(ctx)
+    pub fn join_match<'info>(
+        ctx: Context<'_, '_, 'info, 'info, JoinMatch<'info>>,
+        allowlist_proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::join_match::handler(ctx, allowlist_proof)
     }
 
     /// Start a match when enough players have joined
@@ -61,8 +89,198 @@ pub mod sol_duel_game {
         instructions::end_turn::handler(ctx)
     }
 
-    /// Finish match and distribute rewards
-    pub fn finish_match(ctx: Context<FinishMatch>) -> Result<()> {
+    /// Penalize the player whose turn expired and advance the match
+    pub fn claim_turn_timeout(ctx: Context<ClaimTurnTimeout>) -> Result<()> {
+        instructions::claim_turn_timeout::handler(ctx)
+    }
+
+    /// Clear an AFK penalty or matchmaking ban - SupportAdmin only
+    pub fn clear_penalty(ctx: Context<ClearPenalty>, player: Pubkey, reset_strikes: bool) -> Result<()> {
+        instructions::admin_functions::clear_penalty(ctx, player, reset_strikes)
+    }
+
+    /// Configure the KYC/allowlist gate - SecurityAdmin only
+    pub fn configure_compliance(
+        ctx: Context<ConfigureCompliance>,
+        mode: ComplianceMode,
+        allowlist_root: [u8; 32],
+        attestation_issuer: Pubkey,
+    ) -> Result<()> {
+        instructions::admin_functions::configure_compliance(ctx, mode, allowlist_root, attestation_issuer)
+    }
+
+    /// Enable, disable or stage rollout of a feature flag - GameAdmin only
+    pub fn set_feature_flag(
+        ctx: Context<SetFeatureFlag>,
+        flag: u8,
+        enabled: bool,
+        rollout_bps: u16,
+    ) -> Result<()> {
+        instructions::admin_functions::set_feature_flag(ctx, flag, enabled, rollout_bps)
+    }
+
+    /// Publish memcmp-filterable field offsets for indexers - GameAdmin only
+    pub fn publish_schema_registry(ctx: Context<PublishSchemaRegistry>) -> Result<()> {
+        instructions::admin_functions::publish_schema_registry(ctx)
+    }
+
+    /// Create one shard of the metrics buffers sampled by the keeper - GameAdmin only
+    pub fn initialize_metrics_buffer(ctx: Context<InitializeMetricsBuffer>, shard: u8) -> Result<()> {
+        instructions::admin_functions::initialize_metrics_buffer(ctx, shard)
+    }
+
+    /// Self-report version, commit and IDL hash after an upgrade - upgrade authority only
+    pub fn update_program_info(
+        ctx: Context<UpdateProgramInfo>,
+        version: [u16; 3],
+        git_commit: [u8; 20],
+        idl_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::admin_functions::update_program_info(ctx, version, git_commit, idl_hash)
+    }
+
+    /// Schedule a limited-time event (double XP, high-roller hours) - GameAdmin only
+    pub fn add_scheduled_event(
+        ctx: Context<ManageEventSchedule>,
+        starts_at: i64,
+        duration: i64,
+        period: i64,
+        xp_multiplier_bps: u16,
+        max_bet_multiplier_bps: u16,
+    ) -> Result<()> {
+        instructions::admin_functions::add_scheduled_event(
+            ctx,
+            starts_at,
+            duration,
+            period,
+            xp_multiplier_bps,
+            max_bet_multiplier_bps,
+        )
+    }
+
+    /// Cancel a scheduled event - GameAdmin only
+    pub fn cancel_scheduled_event(ctx: Context<ManageEventSchedule>, event_id: u32) -> Result<()> {
+        instructions::admin_functions::cancel_scheduled_event(ctx, event_id)
+    }
+
+    /// Tune gem earn rates and sink prices - GameAdmin only
+    pub fn set_gem_rates(
+        ctx: Context<SetGemRates>,
+        gems_per_win: u64,
+        gems_per_quest: u64,
+        rematch_fee_gems: u64,
+        crate_price_gems: u64,
+        cosmetic_price_gems: u64,
+    ) -> Result<()> {
+        instructions::admin_functions::set_gem_rates(
+            ctx,
+            gems_per_win,
+            gems_per_quest,
+            rematch_fee_gems,
+            crate_price_gems,
+            cosmetic_price_gems,
+        )
+    }
+
+    /// Credit gems for wins or quests - GameAdmin only
+    pub fn grant_gems(
+        ctx: Context<GrantGems>,
+        player: Pubkey,
+        source: GemSource,
+        count: u32,
+    ) -> Result<()> {
+        instructions::admin_functions::grant_gems(ctx, player, source, count)
+    }
+
+    /// Reject sensitive entrypoints reached via CPI from unlisted programs - SuperAdmin only
+    pub fn set_cpi_guard(
+        ctx: Context<SetCpiGuard>,
+        enabled: bool,
+        allowed_callers: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::admin_functions::set_cpi_guard(ctx, enabled, allowed_callers)
+    }
+
+    /// Spend gems on a crate or cosmetic, issuing a receipt the NFT program redeems
+    pub fn spend_gems(ctx: Context<SpendGems>, sink: GemSink, target: Pubkey) -> Result<()> {
+        instructions::gems::spend_gems(ctx, sink, target)
+    }
+
+    /// Issue a compliance credential - attestation issuer only
+    pub fn issue_compliance_credential(
+        ctx: Context<IssueComplianceCredential>,
+        player: Pubkey,
+        expires_at: i64,
+    ) -> Result<()> {
+        instructions::compliance::issue_credential(ctx, player, expires_at)
+    }
+
+    /// Revoke a compliance credential - attestation issuer only
+    pub fn revoke_compliance_credential(ctx: Context<RevokeComplianceCredential>) -> Result<()> {
+        instructions::compliance::revoke_credential(ctx)
+    }
+
+    /// Create a player identity that several wallets can act through
+    pub fn create_identity(ctx: Context<CreateIdentity>, cold_wallet: Pubkey) -> Result<()> {
+        instructions::identity::create_identity(ctx, cold_wallet)
+    }
+
+    /// Link a hot or session wallet to an identity - cold wallet only
+    pub fn link_wallet(
+        ctx: Context<UpdateIdentity>,
+        wallet: Pubkey,
+        role: WalletRole,
+        expires_at: i64,
+    ) -> Result<()> {
+        instructions::identity::link_wallet(ctx, wallet, role, expires_at)
+    }
+
+    /// Unlink a wallet from an identity - cold wallet only
+    pub fn unlink_wallet(ctx: Context<UpdateIdentity>, wallet: Pubkey) -> Result<()> {
+        instructions::identity::unlink_wallet(ctx, wallet)
+    }
+
+    /// Move the cold role to a new wallet - cold wallet only
+    pub fn set_cold_wallet(ctx: Context<UpdateIdentity>, new_cold_wallet: Pubkey) -> Result<()> {
+        instructions::identity::set_cold_wallet(ctx, new_cold_wallet)
+    }
+
+    /// Register recovery guardians and threshold - cold wallet only
+    pub fn configure_guardians(
+        ctx: Context<ConfigureGuardians>,
+        guardians: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::recovery::configure_guardians(ctx, guardians, threshold)
+    }
+
+    /// Start a timelocked recovery to a new cold wallet - guardian only
+    pub fn initiate_recovery(ctx: Context<GuardianRecovery>, new_wallet: Pubkey) -> Result<()> {
+        instructions::recovery::initiate_recovery(ctx, new_wallet)
+    }
+
+    /// Approve the pending recovery to `new_wallet` - guardian only
+    pub fn approve_recovery(ctx: Context<GuardianRecovery>, new_wallet: Pubkey) -> Result<()> {
+        instructions::recovery::approve_recovery(ctx, new_wallet)
+    }
+
+    /// Complete an approved recovery once its timelock has passed
+    pub fn execute_recovery(ctx: Context<ExecuteRecovery>) -> Result<()> {
+        instructions::recovery::execute_recovery(ctx)
+    }
+
+    /// Cancel the pending recovery - cold wallet only
+    pub fn cancel_recovery(ctx: Context<ConfigureGuardians>) -> Result<()> {
+        instructions::recovery::cancel_recovery(ctx)
+    }
+
+    /// Vote to cancel the pending recovery; takes effect at the guardian threshold
+    pub fn vote_cancel_recovery(ctx: Context<GuardianRecovery>) -> Result<()> {
+        instructions::recovery::vote_cancel_recovery(ctx)
+    }
+
+    /// Finish match, distribute rewards and credit every player's result
+    pub fn finish_match<'info>(ctx: Context<'_, '_, 'info, 'info, FinishMatch<'info>>) -> Result<()> {
         instructions::finish_match::handler(ctx)
     }
 
@@ -71,9 +289,132 @@ pub mod sol_duel_game {
         instructions::update_player_stats::handler(ctx, experience_gained)
     }
 
+    /// Set a class's level curve and stat allocation rules - upgrade authority only
+    pub fn configure_class(
+        ctx: Context<ConfigureClass>,
+        player_class: PlayerClass,
+        level_thresholds: [u64; MAX_CLASS_LEVEL],
+        max_level: u32,
+        stat_points_per_level: u8,
+        gain_per_point: PlayerStats,
+        allocation_caps: PlayerStats,
+    ) -> Result<()> {
+        instructions::progression::configure_class(
+            ctx,
+            player_class,
+            level_thresholds,
+            max_level,
+            stat_points_per_level,
+            gain_per_point,
+            allocation_caps,
+        )
+    }
+
+    /// Spend stat points earned from leveling
+    pub fn allocate_stat_points(ctx: Context<AllocateStatPoints>, points: PlayerStats) -> Result<()> {
+        instructions::progression::allocate_stat_points(ctx, points)
+    }
+
+    /// Grow a profile written before per-queue stats to the current layout
+    pub fn migrate_player_profile(ctx: Context<MigratePlayerProfile>, player: Pubkey) -> Result<()> {
+        instructions::migrate_profile::handler(ctx, player)
+    }
+
+    /// Reset a max-level player into the next prestige tier for a permanent reward bonus
+    pub fn prestige_player(ctx: Context<PrestigePlayer>) -> Result<()> {
+        instructions::prestige::handler(ctx)
+    }
+
+    /// Add tokens to the fund that pays prestige reward bonuses
+    pub fn fund_prestige_bonus(ctx: Context<FundPrestigeBonus>, amount: u64) -> Result<()> {
+        instructions::prestige::fund_prestige_bonus(ctx, amount)
+    }
+
+    /// Configure daily login rewards - upgrade authority only
+    pub fn configure_daily_rewards(
+        ctx: Context<ConfigureDailyRewards>,
+        base_tokens: u64,
+        tokens_per_streak_day: u64,
+        base_xp: u32,
+        xp_per_streak_day: u32,
+        max_bonus_days: u32,
+    ) -> Result<()> {
+        instructions::daily_reward::configure_daily_rewards(
+            ctx,
+            base_tokens,
+            tokens_per_streak_day,
+            base_xp,
+            xp_per_streak_day,
+            max_bonus_days,
+        )
+    }
+
+    /// Claim the daily login reward and extend the streak
+    pub fn claim_daily_reward(ctx: Context<ClaimDailyReward>) -> Result<()> {
+        instructions::daily_reward::claim_daily_reward(ctx)
+    }
+
+    /// Close a finished match, refunding rent to its creator
+    pub fn close_match(ctx: Context<CloseMatch>) -> Result<()> {
+        instructions::close_match::close_match(ctx)
+    }
+
+    /// Keeper crank closing finished matches past the grace period
+    pub fn close_finished_matches<'info>(ctx: Context<'_, '_, '_, 'info, CloseFinishedMatches<'info>>) -> Result<()> {
+        instructions::close_match::close_finished_matches(ctx)
+    }
+
+    /// Set the platform fee taken from tournament prize pools
+    pub fn configure_tournament_fees(
+        ctx: Context<ConfigureTournamentFees>,
+        treasury: Pubkey,
+        platform_fee_bps: u16,
+    ) -> Result<()> {
+        instructions::tournament::configure_tournament_fees(ctx, treasury, platform_fee_bps)
+    }
+
+    /// Open a single-elimination, Swiss or round-robin tournament for registration
+    pub fn create_tournament(
+        ctx: Context<CreateTournament>,
+        tournament_id: u64,
+        config: TournamentConfig,
+        blind_levels: Vec<TournamentBlindLevel>,
+    ) -> Result<()> {
+        instructions::tournament::create_tournament(ctx, tournament_id, config, blind_levels)
+    }
+
+    /// Register, late-register or re-enter a tournament, by entry fee or ticket NFT
+    pub fn register_for_tournament(ctx: Context<RegisterForTournament>) -> Result<()> {
+        instructions::tournament::register_for_tournament(ctx)
+    }
+
+    /// Close registration and seed the first bracket round
+    pub fn start_tournament(ctx: Context<StartTournament>) -> Result<()> {
+        instructions::tournament::start_tournament(ctx)
+    }
+
+    /// Count a finished match towards its bracket pairing
+    pub fn record_tournament_result(ctx: Context<RecordTournamentResult>, pairing_index: u8) -> Result<()> {
+        instructions::tournament::record_tournament_result(ctx, pairing_index)
+    }
+
+    /// Refund an entry fee once a tournament has missed its start deadline
+    pub fn refund_tournament_entry(ctx: Context<RefundTournamentEntry>) -> Result<()> {
+        instructions::tournament::refund_tournament_entry(ctx)
+    }
+
+    /// Pay out a completed tournament's prize pool by its payout table
+    pub fn finalize_tournament<'info>(ctx: Context<'_, '_, '_, 'info, FinalizeTournament<'info>>) -> Result<()> {
+        instructions::tournament::finalize_tournament(ctx)
+    }
+
     /// Emergency functions for admin
-    pub fn emergency_stop_match(ctx: Context<EmergencyStopMatch>) -> Result<()> {
-        instructions::emergency_stop_match::handler(ctx)
+    pub fn emergency_stop_match(
+        ctx: Context<EmergencyStopMatch>,
+        reason: ReasonCode,
+        memo_hash: Option<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::emergency_stop_match::handler(ctx, reason, memo_hash)
     }
 }
 
@@ -88,6 +429,15 @@ pub struct InitializeGame<'info> {
     )]
     pub game_state: Account<'info, GameState>,
     
+    #[account(
+        init,
+        payer = authority,
+        space = AdminConfig::LEN,
+        seeds = [ADMIN_CONFIG_SEED],
+        bump
+    )]
+    pub admin_config: Account<'info, AdminConfig>,
+    
     #[account(mut)]
     pub authority: Signer<'info>,
     
@@ -101,7 +451,7 @@ pub struct RegisterPlayer<'info> {
         init,
         payer = player,
         space = PlayerProfile::LEN,
-        seeds = [b"player", player.key().as_ref()],
+        seeds = [b"player", resolve_player(player_identity.as_deref(), PlayerIdentity::exists(&signer_identity), &player.key(), Clock::get()?.unix_timestamp)?.as_ref()],
         bump
     )]
     pub player_profile: Account<'info, PlayerProfile>,
@@ -109,9 +459,128 @@ pub struct RegisterPlayer<'info> {
     #[account(mut)]
     pub player: Signer<'info>,
     
+    #[account(
+        seeds = [b"identity", player_identity.player_key.as_ref()],
+        bump = player_identity.bump
+    )]
+    pub player_identity: Option<Account<'info, PlayerIdentity>>,
+    
+    /// CHECK: The signer's own identity PDA, empty unless it is an identity's player key
+    #[account(
+        seeds = [b"identity", player.key().as_ref()],
+        bump
+    )]
+    pub signer_identity: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateSponsorVault<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = SponsorVault::LEN,
+        seeds = [b"sponsor_vault", authority.key().as_ref()],
+        bump
+    )]
+    pub sponsor_vault: Account<'info, SponsorVault>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetSponsorCap<'info> {
+    #[account(
+        mut,
+        seeds = [b"sponsor_vault", authority.key().as_ref()],
+        bump = sponsor_vault.bump,
+        has_one = authority
+    )]
+    pub sponsor_vault: Account<'info, SponsorVault>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(username: String)]
+pub struct SponsorRegistration<'info> {
+    #[account(
+        mut,
+        seeds = [b"sponsor_vault", sponsor_vault.authority.as_ref()],
+        bump = sponsor_vault.bump
+    )]
+    pub sponsor_vault: Account<'info, SponsorVault>,
+    
+    #[account(
+        init,
+        payer = relayer,
+        space = PlayerProfile::LEN,
+        seeds = [b"player", player.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+    
+    /// Outlives the profile, so a player re-registering keeps their record
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = PenaltyComponent::LEN,
+        seeds = [b"penalty", player.key().as_ref()],
+        bump
+    )]
+    pub player_penalty: Account<'info, PenaltyComponent>,
+    
+    #[account(
+        init,
+        payer = relayer,
+        space = Sponsorship::LEN,
+        seeds = [b"sponsorship", player.key().as_ref()],
+        bump
+    )]
+    pub sponsorship: Account<'info, Sponsorship>,
+    
+    pub player: Signer<'info>,
+    
+    /// Any fee payer; reimbursed for rent from the vault within its daily cap
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct CloseSponsoredProfile<'info> {
+    #[account(
+        mut,
+        seeds = [b"sponsor_vault", sponsor_vault.authority.as_ref()],
+        bump = sponsor_vault.bump
+    )]
+    pub sponsor_vault: Account<'info, SponsorVault>,
+    
+    #[account(
+        mut,
+        close = sponsor_vault,
+        seeds = [b"player", player.key().as_ref()],
+        bump = player_profile.bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+    
+    #[account(
+        mut,
+        close = sponsor_vault,
+        seeds = [b"sponsorship", player.key().as_ref()],
+        bump = sponsorship.bump,
+        has_one = sponsor_vault
+    )]
+    pub sponsorship: Account<'info, Sponsorship>,
+    
+    pub player: Signer<'info>,
+}
+
 #[derive(Accounts)]
 pub struct CreateMatch<'info> {
     #[account(
@@ -125,14 +594,36 @@ pub struct CreateMatch<'info> {
     
     #[account(
         mut,
-        seeds = [b"player", creator.key().as_ref()],
+        seeds = [b"player", resolve_player(player_identity.as_deref(), PlayerIdentity::exists(&signer_identity), &creator.key(), Clock::get()?.unix_timestamp)?.as_ref()],
         bump
     )]
     pub creator_profile: Account<'info, PlayerProfile>,
     
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = PenaltyComponent::LEN,
+        seeds = [b"penalty", resolve_player(player_identity.as_deref(), PlayerIdentity::exists(&signer_identity), &creator.key(), Clock::get()?.unix_timestamp)?.as_ref()],
+        bump
+    )]
+    pub creator_penalty: Account<'info, PenaltyComponent>,
+    
     #[account(mut)]
     pub creator: Signer<'info>,
     
+    #[account(
+        seeds = [b"identity", player_identity.player_key.as_ref()],
+        bump = player_identity.bump
+    )]
+    pub player_identity: Option<Account<'info, PlayerIdentity>>,
+    
+    /// CHECK: The signer's own identity PDA, empty unless it is an identity's player key
+    #[account(
+        seeds = [b"identity", creator.key().as_ref()],
+        bump
+    )]
+    pub signer_identity: UncheckedAccount<'info>,
+    
     #[account(
         mut,
         associated_token::mint = sol_mint,
@@ -143,6 +634,35 @@ pub struct CreateMatch<'info> {
     /// CHECK: SOL mint account
     pub sol_mint: UncheckedAccount<'info>,
     
+    #[account(
+        seeds = [b"event_schedule"],
+        bump
+    )]
+    pub event_schedule: Option<Account<'info, EventSchedule>>,
+    
+    /// Tournament the match is played for; its bracket supplies the round's blinds
+    #[account(
+        seeds = [b"tournament", tournament.authority.as_ref(), &tournament.tournament_id.to_le_bytes()],
+        bump = tournament.bump
+    )]
+    pub tournament: Option<Account<'info, Tournament>>,
+    
+    pub bracket: Option<Account<'info, BracketState>>,
+    
+    /// Finished match the creator is calling a rematch of; charged the gem rematch fee
+    #[account(
+        seeds = [b"match", rematch_of.creator.as_ref(), &rematch_of.created_at.to_le_bytes()],
+        bump = rematch_of.bump
+    )]
+    pub rematch_of: Option<Account<'info, Match>>,
+    
+    #[account(
+        mut,
+        seeds = [b"economy_stats"],
+        bump = economy_stats.bump
+    )]
+    pub economy_stats: Option<Account<'info, EconomyStats>>,
+    
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
@@ -158,14 +678,48 @@ pub struct JoinMatch<'info> {
     
     #[account(
         mut,
-        seeds = [b"player", player.key().as_ref()],
+        seeds = [b"player", resolve_player(player_identity.as_deref(), PlayerIdentity::exists(&signer_identity), &player.key(), Clock::get()?.unix_timestamp)?.as_ref()],
         bump
     )]
     pub player_profile: Account<'info, PlayerProfile>,
     
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PenaltyComponent::LEN,
+        seeds = [b"penalty", resolve_player(player_identity.as_deref(), PlayerIdentity::exists(&signer_identity), &player.key(), Clock::get()?.unix_timestamp)?.as_ref()],
+        bump
+    )]
+    pub player_penalty: Account<'info, PenaltyComponent>,
+    
+    #[account(
+        seeds = [b"compliance_config"],
+        bump = compliance_config.bump
+    )]
+    pub compliance_config: Account<'info, ComplianceConfig>,
+    
+    #[account(
+        seeds = [b"compliance_credential", resolve_player(player_identity.as_deref(), PlayerIdentity::exists(&signer_identity), &player.key(), Clock::get()?.unix_timestamp)?.as_ref()],
+        bump = compliance_credential.bump
+    )]
+    pub compliance_credential: Option<Account<'info, ComplianceCredential>>,
+    
     #[account(mut)]
     pub player: Signer<'info>,
     
+    #[account(
+        seeds = [b"identity", player_identity.player_key.as_ref()],
+        bump = player_identity.bump
+    )]
+    pub player_identity: Option<Account<'info, PlayerIdentity>>,
+    
+    /// CHECK: The signer's own identity PDA, empty unless it is an identity's player key
+    #[account(
+        seeds = [b"identity", player.key().as_ref()],
+        bump
+    )]
+    pub signer_identity: UncheckedAccount<'info>,
+    
     #[account(
         mut,
         associated_token::mint = sol_mint,
@@ -177,6 +731,7 @@ pub struct JoinMatch<'info> {
     pub sol_mint: UncheckedAccount<'info>,
     
     pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
@@ -203,13 +758,34 @@ pub struct ExecuteAction<'info> {
     
     #[account(
         mut,
-        seeds = [b"player", player.key().as_ref()],
+        seeds = [b"player", resolve_player(player_identity.as_deref(), PlayerIdentity::exists(&signer_identity), &player.key(), Clock::get()?.unix_timestamp)?.as_ref()],
         bump
     )]
     pub player_profile: Account<'info, PlayerProfile>,
     
     #[account(mut)]
     pub player: Signer<'info>,
+    
+    #[account(
+        seeds = [b"identity", player_identity.player_key.as_ref()],
+        bump = player_identity.bump
+    )]
+    pub player_identity: Option<Account<'info, PlayerIdentity>>,
+    
+    /// CHECK: The signer's own identity PDA, empty unless it is an identity's player key
+    #[account(
+        seeds = [b"identity", player.key().as_ref()],
+        bump
+    )]
+    pub signer_identity: UncheckedAccount<'info>,
+    
+    /// Every action is measured, into the shard its match maps to
+    #[account(
+        mut,
+        seeds = [METRICS_BUFFER_SEED, &[metrics_shard(&match_account.key())]],
+        bump = metrics_buffer.bump
+    )]
+    pub metrics_buffer: Account<'info, MetricsBuffer>,
 }
 
 #[derive(Accounts)]
@@ -223,19 +799,495 @@ pub struct EndTurn<'info> {
     
     #[account(mut)]
     pub player: Signer<'info>,
-}
-
-#[derive(Accounts)]
-pub struct FinishMatch<'info> {
+    
+    #[account(
+        seeds = [b"identity", player_identity.player_key.as_ref()],
+        bump = player_identity.bump
+    )]
+    pub player_identity: Option<Account<'info, PlayerIdentity>>,
+    
+    /// CHECK: The signer's own identity PDA, empty unless it is an identity's player key
+    #[account(
+        seeds = [b"identity", player.key().as_ref()],
+        bump
+    )]
+    pub signer_identity: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimTurnTimeout<'info> {
+    #[account(
+        mut,
+        seeds = [b"match", match_account.creator.as_ref(), &match_account.created_at.to_le_bytes()],
+        bump = match_account.bump
+    )]
+    pub match_account: Account<'info, Match>,
+    
+    /// CHECK: Player holding the expired turn, validated against the match turn order
+    pub timed_out_player: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"player", timed_out_player.key().as_ref()],
+        bump = timed_out_profile.bump
+    )]
+    pub timed_out_profile: Account<'info, PlayerProfile>,
+    
+    #[account(
+        init_if_needed,
+        payer = caller,
+        space = PenaltyComponent::LEN,
+        seeds = [b"penalty", timed_out_player.key().as_ref()],
+        bump
+    )]
+    pub penalty: Account<'info, PenaltyComponent>,
+    
+    #[account(mut)]
+    pub caller: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(player: Pubkey)]
+pub struct IssueComplianceCredential<'info> {
+    #[account(
+        seeds = [b"compliance_config"],
+        bump = compliance_config.bump
+    )]
+    pub compliance_config: Account<'info, ComplianceConfig>,
+    
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        space = ComplianceCredential::LEN,
+        seeds = [b"compliance_credential", player.as_ref()],
+        bump
+    )]
+    pub credential: Account<'info, ComplianceCredential>,
+    
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeComplianceCredential<'info> {
+    #[account(
+        mut,
+        seeds = [b"compliance_credential", credential.player.as_ref()],
+        bump = credential.bump
+    )]
+    pub credential: Account<'info, ComplianceCredential>,
+    
+    pub issuer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CreateIdentity<'info> {
+    #[account(
+        init,
+        payer = player,
+        space = PlayerIdentity::LEN,
+        seeds = [b"identity", player.key().as_ref()],
+        bump
+    )]
+    pub identity: Account<'info, PlayerIdentity>,
+    
+    #[account(mut)]
+    pub player: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateIdentity<'info> {
+    #[account(
+        mut,
+        seeds = [b"identity", identity.player_key.as_ref()],
+        bump = identity.bump,
+        constraint = identity.is_cold(&cold_wallet.key()) @ GameError::ColdWalletRequired
+    )]
+    pub identity: Account<'info, PlayerIdentity>,
+    
+    pub cold_wallet: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureGuardians<'info> {
+    #[account(
+        seeds = [b"identity", identity.player_key.as_ref()],
+        bump = identity.bump,
+        constraint = identity.is_cold(&cold_wallet.key()) @ GameError::ColdWalletRequired
+    )]
+    pub identity: Account<'info, PlayerIdentity>,
+    
+    #[account(
+        init_if_needed,
+        payer = cold_wallet,
+        space = IdentityRecovery::LEN,
+        seeds = [b"recovery", identity.key().as_ref()],
+        bump
+    )]
+    pub recovery: Account<'info, IdentityRecovery>,
+    
+    #[account(mut)]
+    pub cold_wallet: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GuardianRecovery<'info> {
+    #[account(
+        mut,
+        seeds = [b"recovery", recovery.identity.as_ref()],
+        bump = recovery.bump
+    )]
+    pub recovery: Account<'info, IdentityRecovery>,
+    
+    pub guardian: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteRecovery<'info> {
+    #[account(
+        mut,
+        seeds = [b"identity", identity.player_key.as_ref()],
+        bump = identity.bump
+    )]
+    pub identity: Account<'info, PlayerIdentity>,
+    
+    #[account(
+        mut,
+        seeds = [b"recovery", identity.key().as_ref()],
+        bump = recovery.bump
+    )]
+    pub recovery: Account<'info, IdentityRecovery>,
+}
+
+#[derive(Accounts)]
+pub struct FinishMatch<'info> {
+    #[account(
+        mut,
+        seeds = [b"match", match_account.creator.as_ref(), &match_account.created_at.to_le_bytes()],
+        bump = match_account.bump
+    )]
+    pub match_account: Account<'info, Match>,
+    
+    /// Standard result record; init also keeps a match from being finalized twice
+    #[account(
+        init,
+        payer = authority,
+        space = WinnerAttestation::LEN,
+        seeds = [WINNER_ATTESTATION_SEED, match_account.key().as_ref()],
+        bump
+    )]
+    pub winner_attestation: Account<'info, WinnerAttestation>,
+    
+    #[account(
+        seeds = [ADMIN_CONFIG_SEED],
+        bump = admin_config.bump
+    )]
+    pub admin_config: Account<'info, AdminConfig>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    
+    /// CHECK: Instructions sysvar, read by the CPI guard
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+    
+    /// Pays prestige bonuses on top of the pool's shares; without it none are paid
+    #[account(
+        mut,
+        seeds = [b"prestige_fund"],
+        bump = prestige_fund.bump
+    )]
+    pub prestige_fund: Option<Account<'info, PrestigeBonusFund>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseMatch<'info> {
+    #[account(
+        mut,
+        close = creator,
+        has_one = creator @ GameError::AccessDenied,
+        seeds = [b"match", match_account.creator.as_ref(), &match_account.created_at.to_le_bytes()],
+        bump = match_account.bump
+    )]
+    pub match_account: Account<'info, Match>,
+    
+    #[account(mut)]
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseFinishedMatches<'info> {
+    pub keeper: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureTournamentFees<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = TournamentFeeConfig::LEN,
+        seeds = [b"tournament_fee_config"],
+        bump
+    )]
+    pub fee_config: Account<'info, TournamentFeeConfig>,
+    
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        constraint = game_state.upgrade_authority == authority.key() @ GameError::AccessDenied
+    )]
+    pub game_state: Account<'info, state::GameState>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(tournament_id: u64)]
+pub struct CreateTournament<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Tournament::LEN,
+        seeds = [b"tournament", authority.key().as_ref(), &tournament_id.to_le_bytes()],
+        bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = BracketState::LEN,
+        seeds = [b"bracket", tournament.key().as_ref()],
+        bump
+    )]
+    pub bracket: Account<'info, BracketState>,
+    
+    #[account(
+        seeds = [b"tournament_fee_config"],
+        bump = fee_config.bump
+    )]
+    pub fee_config: Account<'info, TournamentFeeConfig>,
+    
+    pub entry_mint: Account<'info, Mint>,
+    
+    // Entry fees pool here until payout
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"tournament_vault", tournament.key().as_ref()],
+        bump,
+        token::mint = entry_mint,
+        token::authority = tournament
+    )]
+    pub prize_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterForTournament<'info> {
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.authority.as_ref(), &tournament.tournament_id.to_le_bytes()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+    
+    /// Only registered players can enter
+    #[account(
+        seeds = [b"player", player.key().as_ref()],
+        bump = player_profile.bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+    
+    // Late entrants are added to the standings
+    #[account(
+        mut,
+        seeds = [b"bracket", tournament.key().as_ref()],
+        bump = bracket.bump
+    )]
+    pub bracket: Account<'info, BracketState>,
+    
+    // Existing on re-entry
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = TournamentEntry::LEN,
+        seeds = [b"tournament_entry", tournament.key().as_ref(), player.key().as_ref()],
+        bump
+    )]
+    pub entry: Account<'info, TournamentEntry>,
+    
+    #[account(mut, address = tournament.prize_vault)]
+    pub prize_vault: Account<'info, TokenAccount>,
+    
+    // Pays the entry fee; omit when entering with a ticket
+    #[account(
+        mut,
+        token::mint = tournament.entry_mint,
+        token::authority = player
+    )]
+    pub player_token_account: Option<Account<'info, TokenAccount>>,
+    
+    /// CHECK: TournamentTicket record owned by nft-program, verified in the handler
+    pub ticket: Option<UncheckedAccount<'info>>,
+    
+    #[account(mut)]
+    pub ticket_mint: Option<Account<'info, Mint>>,
+    
+    #[account(mut, token::authority = player)]
+    pub ticket_token_account: Option<Account<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub player: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StartTournament<'info> {
+    #[account(
+        mut,
+        seeds = [b"tournament", authority.key().as_ref(), &tournament.tournament_id.to_le_bytes()],
+        bump = tournament.bump,
+        has_one = authority @ GameError::AccessDenied
+    )]
+    pub tournament: Account<'info, Tournament>,
+    
+    #[account(
+        mut,
+        seeds = [b"bracket", tournament.key().as_ref()],
+        bump = bracket.bump
+    )]
+    pub bracket: Account<'info, BracketState>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RecordTournamentResult<'info> {
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.authority.as_ref(), &tournament.tournament_id.to_le_bytes()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+    
+    #[account(
+        mut,
+        seeds = [b"bracket", tournament.key().as_ref()],
+        bump = bracket.bump
+    )]
+    pub bracket: Account<'info, BracketState>,
+    
+    #[account(
+        seeds = [b"match", match_account.creator.as_ref(), &match_account.created_at.to_le_bytes()],
+        bump = match_account.bump
+    )]
+    pub match_account: Account<'info, Match>,
+    
+    #[account(
+        seeds = [WINNER_ATTESTATION_SEED, match_account.key().as_ref()],
+        bump = winner_attestation.bump
+    )]
+    pub winner_attestation: Account<'info, WinnerAttestation>,
+    
+    pub keeper: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RefundTournamentEntry<'info> {
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.authority.as_ref(), &tournament.tournament_id.to_le_bytes()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+    
+    #[account(
+        mut,
+        close = player,
+        seeds = [b"tournament_entry", tournament.key().as_ref(), player.key().as_ref()],
+        bump = entry.bump
+    )]
+    pub entry: Account<'info, TournamentEntry>,
+    
+    #[account(mut, address = tournament.prize_vault)]
+    pub prize_vault: Account<'info, TokenAccount>,
+    
     #[account(
         mut,
-        seeds = [b"match", match_account.creator.as_ref(), &match_account.created_at.to_le_bytes()],
-        bump = match_account.bump
+        token::mint = tournament.entry_mint,
+        token::authority = player
     )]
-    pub match_account: Account<'info, Match>,
+    pub player_token_account: Account<'info, TokenAccount>,
     
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub player: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeTournament<'info> {
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.authority.as_ref(), &tournament.tournament_id.to_le_bytes()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+    
+    #[account(
+        seeds = [b"bracket", tournament.key().as_ref()],
+        bump = bracket.bump
+    )]
+    pub bracket: Account<'info, BracketState>,
+    
+    #[account(mut, address = tournament.prize_vault)]
+    pub prize_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [b"tournament_fee_config"],
+        bump = fee_config.bump
+    )]
+    pub fee_config: Account<'info, TournamentFeeConfig>,
+    
+    #[account(
+        mut,
+        token::mint = tournament.entry_mint,
+        token::authority = fee_config.treasury
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = tournament.entry_mint,
+        token::authority = tournament.authority
+    )]
+    pub organizer_token_account: Account<'info, TokenAccount>,
+    
+    pub keeper: Signer<'info>,
     
     pub token_program: Program<'info, Token>,
 }
@@ -244,13 +1296,355 @@ pub struct FinishMatch<'info> {
 pub struct UpdatePlayerStats<'info> {
     #[account(
         mut,
-        seeds = [b"player", player.key().as_ref()],
+        seeds = [b"player", resolve_player(player_identity.as_deref(), PlayerIdentity::exists(&signer_identity), &player.key(), Clock::get()?.unix_timestamp)?.as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+    
+    #[account(
+        seeds = [b"class_config", &[player_profile.player_class as u8]],
+        bump = class_config.bump
+    )]
+    pub class_config: Account<'info, ClassConfig>,
+    
+    #[account(mut)]
+    pub player: Signer<'info>,
+    
+    #[account(
+        seeds = [b"identity", player_identity.player_key.as_ref()],
+        bump = player_identity.bump
+    )]
+    pub player_identity: Option<Account<'info, PlayerIdentity>>,
+    
+    /// CHECK: The signer's own identity PDA, empty unless it is an identity's player key
+    #[account(
+        seeds = [b"identity", player.key().as_ref()],
+        bump
+    )]
+    pub signer_identity: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(player_class: PlayerClass)]
+pub struct ConfigureClass<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = ClassConfig::LEN,
+        seeds = [b"class_config", &[player_class as u8]],
+        bump
+    )]
+    pub class_config: Account<'info, ClassConfig>,
+    
+    #[account(
+        seeds = [b"game_state"],
+        bump,
+        constraint = game_state.upgrade_authority == authority.key() @ GameError::AccessDenied
+    )]
+    pub game_state: Account<'info, state::GameState>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AllocateStatPoints<'info> {
+    #[account(
+        mut,
+        seeds = [b"player", resolve_player(player_identity.as_deref(), PlayerIdentity::exists(&signer_identity), &player.key(), Clock::get()?.unix_timestamp)?.as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+    
+    #[account(
+        seeds = [b"class_config", &[player_profile.player_class as u8]],
+        bump = class_config.bump
+    )]
+    pub class_config: Account<'info, ClassConfig>,
+    
+    pub player: Signer<'info>,
+    
+    #[account(
+        seeds = [b"identity", player_identity.player_key.as_ref()],
+        bump = player_identity.bump
+    )]
+    pub player_identity: Option<Account<'info, PlayerIdentity>>,
+    
+    /// CHECK: The signer's own identity PDA, empty unless it is an identity's player key
+    #[account(
+        seeds = [b"identity", player.key().as_ref()],
+        bump
+    )]
+    pub signer_identity: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(player: Pubkey)]
+pub struct MigratePlayerProfile<'info> {
+    /// CHECK: Read with the pre-queue-stats layout, so it cannot be deserialized up front
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"player", player.as_ref()],
+        bump
+    )]
+    pub player_profile: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PrestigePlayer<'info> {
+    #[account(
+        mut,
+        seeds = [b"player", resolve_player(player_identity.as_deref(), PlayerIdentity::exists(&signer_identity), &player.key(), Clock::get()?.unix_timestamp)?.as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+    
+    #[account(
+        seeds = [b"class_config", &[player_profile.player_class as u8]],
+        bump = class_config.bump
+    )]
+    pub class_config: Account<'info, ClassConfig>,
+    
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, state::GameState>,
+    
+    // One badge mint per player per tier
+    #[account(
+        init,
+        payer = player,
+        seeds = [b"prestige_badge", player_profile.owner.as_ref(), &[player_profile.prestige_level + 1]],
+        bump,
+        mint::decimals = 0,
+        mint::authority = game_state,
+        mint::freeze_authority = game_state
+    )]
+    pub badge_mint: Account<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = player,
+        associated_token::mint = badge_mint,
+        associated_token::authority = player
+    )]
+    pub badge_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub player: Signer<'info>,
+    
+    #[account(
+        seeds = [b"identity", player_identity.player_key.as_ref()],
+        bump = player_identity.bump
+    )]
+    pub player_identity: Option<Account<'info, PlayerIdentity>>,
+    
+    /// CHECK: The signer's own identity PDA, empty unless it is an identity's player key
+    #[account(
+        seeds = [b"identity", player.key().as_ref()],
+        bump
+    )]
+    pub signer_identity: UncheckedAccount<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FundPrestigeBonus<'info> {
+    #[account(
+        init_if_needed,
+        payer = funder,
+        space = PrestigeBonusFund::LEN,
+        seeds = [b"prestige_fund"],
+        bump,
+        constraint = prestige_fund.mint == Pubkey::default() || prestige_fund.mint == mint.key() @ GameError::TokenAccountMismatch
+    )]
+    pub prestige_fund: Account<'info, PrestigeBonusFund>,
+    
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        init_if_needed,
+        payer = funder,
+        seeds = [b"prestige_fund_vault"],
+        bump,
+        token::mint = mint,
+        token::authority = prestige_fund
+    )]
+    pub fund_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = funder
+    )]
+    pub funder_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureDailyRewards<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = DailyRewardConfig::LEN,
+        seeds = [b"daily_reward_config"],
+        bump
+    )]
+    pub daily_reward_config: Account<'info, DailyRewardConfig>,
+    
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        constraint = game_state.upgrade_authority == authority.key() @ GameError::AccessDenied
+    )]
+    pub game_state: Account<'info, state::GameState>,
+    
+    pub reward_mint: Account<'info, Mint>,
+    
+    pub streak_freeze_mint: Account<'info, Mint>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimDailyReward<'info> {
+    #[account(
+        seeds = [b"daily_reward_config"],
+        bump = daily_reward_config.bump
+    )]
+    pub daily_reward_config: Account<'info, DailyRewardConfig>,
+    
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = DailyStreak::LEN,
+        seeds = [b"daily_streak", resolve_player(player_identity.as_deref(), PlayerIdentity::exists(&signer_identity), &player.key(), Clock::get()?.unix_timestamp)?.as_ref()],
+        bump
+    )]
+    pub daily_streak: Account<'info, DailyStreak>,
+    
+    #[account(
+        mut,
+        seeds = [b"player", resolve_player(player_identity.as_deref(), PlayerIdentity::exists(&signer_identity), &player.key(), Clock::get()?.unix_timestamp)?.as_ref()],
         bump
     )]
     pub player_profile: Account<'info, PlayerProfile>,
     
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, state::GameState>,
+    
+    #[account(
+        mut,
+        seeds = [b"daily_reward_vault"],
+        bump,
+        token::mint = daily_reward_config.reward_mint,
+        token::authority = game_state
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = daily_reward_config.reward_mint,
+        token::authority = player
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut, address = daily_reward_config.streak_freeze_mint)]
+    pub streak_freeze_mint: Account<'info, Mint>,
+    
+    // Player's streak-freeze items; omit to claim without protecting the streak
+    #[account(
+        mut,
+        token::mint = streak_freeze_mint,
+        token::authority = player
+    )]
+    pub streak_freeze_account: Option<Account<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub player: Signer<'info>,
+    
+    #[account(
+        seeds = [b"identity", player_identity.player_key.as_ref()],
+        bump = player_identity.bump
+    )]
+    pub player_identity: Option<Account<'info, PlayerIdentity>>,
+    
+    /// CHECK: The signer's own identity PDA, empty unless it is an identity's player key
+    #[account(
+        seeds = [b"identity", player.key().as_ref()],
+        bump
+    )]
+    pub signer_identity: UncheckedAccount<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SpendGems<'info> {
+    #[account(
+        mut,
+        seeds = [b"economy_stats"],
+        bump = economy_stats.bump
+    )]
+    pub economy_stats: Account<'info, EconomyStats>,
+    
+    #[account(
+        mut,
+        seeds = [b"player", resolve_player(player_identity.as_deref(), PlayerIdentity::exists(&signer_identity), &player.key(), Clock::get()?.unix_timestamp)?.as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+    
+    #[account(
+        init,
+        payer = player,
+        space = GemReceipt::LEN,
+        seeds = [GEM_RECEIPT_SEED, &economy_stats.receipts_issued.to_le_bytes()],
+        bump
+    )]
+    pub gem_receipt: Account<'info, GemReceipt>,
+    
     #[account(mut)]
     pub player: Signer<'info>,
+    
+    #[account(
+        seeds = [b"identity", player_identity.player_key.as_ref()],
+        bump = player_identity.bump
+    )]
+    pub player_identity: Option<Account<'info, PlayerIdentity>>,
+    
+    /// CHECK: The signer's own identity PDA, empty unless it is an identity's player key
+    #[account(
+        seeds = [b"identity", player.key().as_ref()],
+        bump
+    )]
+    pub signer_identity: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
@@ -269,8 +1663,18 @@ pub struct EmergencyStopMatch<'info> {
     )]
     pub game_state: Account<'info, state::GameState>,
     
+    #[account(
+        seeds = [ADMIN_CONFIG_SEED],
+        bump = admin_config.bump
+    )]
+    pub admin_config: Account<'info, AdminConfig>,
+    
     #[account(mut)]
     pub authority: Signer<'info>,
     
     pub token_program: Program<'info, Token>,
+    
+    /// CHECK: Instructions sysvar, read by the CPI guard
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
 }
\ No newline at end of file
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::shared::{GameState as SharedGameState, PlayerClass, PlayerStats, MatchConfig, MAX_PLAYERS_PER_MATCH, MAX_USERNAME_LENGTH, AdminConfig};
+use crate::shared::{GameState as SharedGameState, PlayerClass, PlayerStats, MatchConfig, QueueType, QUEUE_TYPES, MAX_PLAYERS_PER_MATCH, MAX_USERNAME_LENGTH, AdminConfig, ComplianceMode, EventModifiers, GameError, TicketSource, ReasonCode, GemSink, allowlist_leaf, rating_floor, verify_merkle_proof};
 
 #[account]
 pub struct GameState {
@@ -21,6 +21,19 @@ impl GameState {
         1; // bump
 }
 
+/// Results a profile has recorded in one queue
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct QueueStats {
+    pub matches: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub winnings: u64, // Sets this queue's matchmaking rating floor
+}
+
+impl QueueStats {
+    pub const LEN: usize = 4 + 4 + 4 + 8;
+}
+
 #[account]
 pub struct PlayerProfile {
     pub owner: Pubkey,
@@ -37,6 +50,18 @@ pub struct PlayerProfile {
     pub created_at: i64,
     pub last_match_at: i64,
     pub is_active: bool,
+    // AFK tracking
+    pub timeout_count: u32,
+    pub afk_strikes: u8,
+    pub last_timeout_at: i64,
+    // Progression
+    pub unspent_stat_points: u32,
+    pub allocated_stats: PlayerStats, // Stat bonus bought with stat points
+    pub prestige_level: u8,
+    pub last_prestige_at: i64,
+    pub gems: u64, // Non-transferable soft currency
+    pub lifetime_winnings: u64, // Across every queue; floors come from queue_stats
+    pub queue_stats: [QueueStats; QUEUE_TYPES], // Indexed by QueueType
     pub bump: u8,
 }
 
@@ -56,23 +81,101 @@ impl PlayerProfile {
         8 + // created_at
         8 + // last_match_at
         1 + // is_active
+        4 + // timeout_count
+        1 + // afk_strikes
+        8 + // last_timeout_at
+        4 + // unspent_stat_points
+        20 + // allocated_stats (5 * u32)
+        1 + // prestige_level
+        8 + // last_prestige_at
+        8 + // gems
+        8 + // lifetime_winnings
+        QUEUE_TYPES * QueueStats::LEN + // queue_stats
         1; // bump
 
+    /// Count a finished match and its reward in its queue's stats
+    pub fn record_queue_result(&mut self, queue: QueueType, won: bool, reward: u64) -> QueueStats {
+        self.lifetime_winnings = self.lifetime_winnings.saturating_add(reward);
+        let stats = &mut self.queue_stats[queue.index()];
+        stats.matches = stats.matches.saturating_add(1);
+        stats.winnings = stats.winnings.saturating_add(reward);
+        if won {
+            stats.wins = stats.wins.saturating_add(1);
+        } else {
+            stats.losses = stats.losses.saturating_add(1);
+        }
+        *stats
+    }
+
+    /// Matchmaking rating floor in `queue`, from that queue's winnings only
+    pub fn rating_floor(&self, queue: QueueType) -> u32 {
+        rating_floor(self.queue_stats[queue.index()].winnings)
+    }
+
+    /// Offset of `queue_stats` in a profile written before it existed; the
+    /// bump byte sat there. Only the username before it varies in length.
+    pub fn legacy_queue_stats_offset(username_len: usize) -> usize {
+        Self::LEN - QUEUE_TYPES * QueueStats::LEN - 1 - (MAX_USERNAME_LENGTH - username_len)
+    }
+
     pub fn calculate_level(&self) -> u32 {
         // Level formula: sqrt(experience / 1000)
         ((self.experience / 1000) as f64).sqrt() as u32 + 1
     }
 
+    /// Combat stats derived from base stats, allocated points and level.
+    /// Integer-only so every validator computes the same values.
     pub fn get_current_stats(&self) -> PlayerStats {
-        let level_multiplier = self.level as f64 * 0.1 + 1.0;
+        // +10% per level, as (10 + level) / 10
+        let scale = |base: u32, allocated: u32| -> u32 {
+            ((base as u64 + allocated as u64) * (10 + self.level as u64) / 10).min(u32::MAX as u64) as u32
+        };
         
         PlayerStats {
-            health: (self.base_stats.health as f64 * level_multiplier) as u32,
-            attack: (self.base_stats.attack as f64 * level_multiplier) as u32,
-            defense: (self.base_stats.defense as f64 * level_multiplier) as u32,
-            speed: (self.base_stats.speed as f64 * level_multiplier) as u32,
-            mana: (self.base_stats.mana as f64 * level_multiplier) as u32,
+            health: scale(self.base_stats.health, self.allocated_stats.health),
+            attack: scale(self.base_stats.attack, self.allocated_stats.attack),
+            defense: scale(self.base_stats.defense, self.allocated_stats.defense),
+            speed: scale(self.base_stats.speed, self.allocated_stats.speed),
+            mana: scale(self.base_stats.mana, self.allocated_stats.mana),
+        }
+    }
+
+    /// Reward multiplier from prestige, in basis points
+    pub fn prestige_multiplier_bps(&self) -> u64 {
+        prestige_multiplier_bps(self.prestige_level)
+    }
+
+    /// Trade a max-level character for the next prestige tier. Level, experience
+    /// and allocated points start over; base stats and match history are kept.
+    pub fn prestige(&mut self, class_config: &ClassConfig, now: i64) -> Result<u8> {
+        if self.level < class_config.max_level {
+            return Err(GameError::PrestigeLevelNotReached.into());
+        }
+        if self.prestige_level as usize >= MAX_PRESTIGE_TIER {
+            return Err(GameError::MaxPrestigeReached.into());
+        }
+
+        self.prestige_level += 1;
+        self.last_prestige_at = now;
+        self.level = 1;
+        self.experience = 0;
+        self.unspent_stat_points = 0;
+        self.allocated_stats = PlayerStats { health: 0, attack: 0, defense: 0, speed: 0, mana: 0 };
+
+        Ok(self.prestige_level)
+    }
+
+    /// Bring `level` in line with experience on the class curve, granting stat
+    /// points for every level gained. Returns the previous level.
+    pub fn apply_level_ups(&mut self, class_config: &ClassConfig) -> u32 {
+        let old_level = self.level;
+        let new_level = class_config.level_for(self.experience);
+        if new_level > old_level {
+            let gained = (new_level - old_level).saturating_mul(class_config.stat_points_per_level as u32);
+            self.unspent_stat_points = self.unspent_stat_points.saturating_add(gained);
+            self.level = new_level;
         }
+        old_level
     }
 
     pub fn win_rate(&self) -> f64 {
@@ -83,44 +186,318 @@ impl PlayerProfile {
     }
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GemSource {
+    MatchWin,
+    Quest,
+}
+
+/// Global gem mint/sink accounting plus the tunable earn rates
+#[account]
+pub struct EconomyStats {
+    pub gems_per_win: u64,
+    pub gems_per_quest: u64,
+    pub rematch_fee_gems: u64,
+    pub crate_price_gems: u64,
+    pub cosmetic_price_gems: u64,
+    pub gems_minted: u64,
+    pub gems_burned: u64,
+    pub minted_by_source: [u64; 2], // Indexed by GemSource
+    pub burned_by_sink: [u64; 3], // Indexed by GemSink
+    pub receipts_issued: u64,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl EconomyStats {
+    pub const LEN: usize = 8 + // discriminator
+        8 + // gems_per_win
+        8 + // gems_per_quest
+        8 + // rematch_fee_gems
+        8 + // crate_price_gems
+        8 + // cosmetic_price_gems
+        8 + // gems_minted
+        8 + // gems_burned
+        8 * 2 + // minted_by_source
+        8 * 3 + // burned_by_sink
+        8 + // receipts_issued
+        8 + // updated_at
+        1; // bump
+
+    pub fn earn_rate(&self, source: GemSource) -> u64 {
+        match source {
+            GemSource::MatchWin => self.gems_per_win,
+            GemSource::Quest => self.gems_per_quest,
+        }
+    }
+
+    /// Gems currently held across all players
+    pub fn circulating(&self) -> u64 {
+        self.gems_minted.saturating_sub(self.gems_burned)
+    }
+
+    pub fn record_mint(&mut self, source: GemSource, amount: u64) -> Result<()> {
+        self.gems_minted = self.gems_minted.checked_add(amount).ok_or(GameError::ArithmeticOverflow)?;
+        self.minted_by_source[source as usize] = self.minted_by_source[source as usize].saturating_add(amount);
+        Ok(())
+    }
+
+    pub fn price_of(&self, sink: GemSink) -> u64 {
+        match sink {
+            GemSink::Crate => self.crate_price_gems,
+            GemSink::Cosmetic => self.cosmetic_price_gems,
+            GemSink::RematchFee => self.rematch_fee_gems,
+        }
+    }
+
+    pub fn record_burn(&mut self, sink: GemSink, amount: u64) {
+        self.gems_burned = self.gems_burned.saturating_add(amount);
+        self.burned_by_sink[sink as usize] = self.burned_by_sink[sink as usize].saturating_add(amount);
+    }
+
+    /// Burn a sink's configured price from the player's balance; returns the amount
+    pub fn charge(&mut self, profile: &mut PlayerProfile, sink: GemSink) -> Result<u64> {
+        let amount = self.price_of(sink);
+        if amount == 0 {
+            return Err(GameError::InvalidGameState.into());
+        }
+        profile.gems = profile.gems.checked_sub(amount).ok_or(GameError::InsufficientFunds)?;
+        self.record_burn(sink, amount);
+        Ok(amount)
+    }
+}
+
+pub const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Keepers may only sweep matches that ended at least this long ago, leaving
+/// clients time to read results
+pub const MATCH_CLOSE_GRACE_PERIOD: i64 = 7 * SECONDS_PER_DAY;
+
+/// Daily login reward settings. Rewards grow with the streak up to `max_bonus_days`.
+#[account]
+pub struct DailyRewardConfig {
+    pub reward_mint: Pubkey,
+    pub streak_freeze_mint: Pubkey, // Item burned to cover a missed day
+    pub base_tokens: u64,
+    pub tokens_per_streak_day: u64,
+    pub base_xp: u32,
+    pub xp_per_streak_day: u32,
+    pub max_bonus_days: u32,
+    pub bump: u8,
+}
+
+impl DailyRewardConfig {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // reward_mint
+        32 + // streak_freeze_mint
+        8 + // base_tokens
+        8 + // tokens_per_streak_day
+        4 + // base_xp
+        4 + // xp_per_streak_day
+        4 + // max_bonus_days
+        1; // bump
+
+    /// Token and XP reward for a claim on day `streak` of a streak
+    pub fn reward_for(&self, streak: u32) -> (u64, u64) {
+        let bonus_days = streak.saturating_sub(1).min(self.max_bonus_days);
+        let tokens = self.base_tokens.saturating_add(self.tokens_per_streak_day.saturating_mul(bonus_days as u64));
+        let xp = self.base_xp as u64 + self.xp_per_streak_day as u64 * bonus_days as u64;
+        (tokens, xp)
+    }
+}
+
+#[account]
+pub struct DailyStreak {
+    pub player: Pubkey,
+    pub last_claim_day: i64, // UTC day number (unix days)
+    pub current_streak: u32,
+    pub longest_streak: u32,
+    pub total_claims: u32,
+    pub freezes_used: u32,
+    pub bump: u8,
+}
+
+impl DailyStreak {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // player
+        8 + // last_claim_day
+        4 + // current_streak
+        4 + // longest_streak
+        4 + // total_claims
+        4 + // freezes_used
+        1; // bump
+
+    pub fn utc_day(unix_timestamp: i64) -> i64 {
+        unix_timestamp.div_euclid(SECONDS_PER_DAY)
+    }
+
+    /// Days skipped between the last claim and `today`
+    pub fn missed_days(&self, today: i64) -> u64 {
+        if self.total_claims == 0 {
+            return 0;
+        }
+        (today - self.last_claim_day - 1).max(0) as u64
+    }
+}
+
+/// Highest prestige tier a player can reach
+pub const MAX_PRESTIGE_TIER: usize = 10;
+
+/// Permanent reward bonus per prestige tier, in basis points
+pub const PRESTIGE_BONUS_BPS_PER_TIER: u64 = 200;
+
+pub fn prestige_multiplier_bps(prestige_level: u8) -> u64 {
+    10_000 + prestige_level as u64 * PRESTIGE_BONUS_BPS_PER_TIER
+}
+
+/// Tokens set aside for prestige bonuses, so a bonus never comes out of the
+/// share the match pool owes other ranks
+#[account]
+#[derive(Default)]
+pub struct PrestigeBonusFund {
+    pub mint: Pubkey,
+    pub available: u64,
+    pub total_funded: u64,
+    pub total_paid: u64,
+    pub bump: u8,
+}
+
+impl PrestigeBonusFund {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // mint
+        8 + // available
+        8 + // total_funded
+        8 + // total_paid
+        1; // bump
+
+    /// Bonus on top of a base reward for a prestige tier
+    pub fn bonus_for(base_amount: u64, prestige_level: u8) -> u64 {
+        (base_amount as u128 * (prestige_multiplier_bps(prestige_level) - 10_000) as u128 / 10_000) as u64
+    }
+
+    /// Pay as much of `amount` as the fund still holds
+    pub fn draw(&mut self, amount: u64) -> u64 {
+        let paid = amount.min(self.available);
+        self.available -= paid;
+        self.total_paid += paid;
+        paid
+    }
+}
+
+/// Highest level a class curve can define
+pub const MAX_CLASS_LEVEL: usize = 50;
+
+/// Per-class progression: the experience curve, stat points per level, and
+/// how far allocation can push each stat.
+#[account]
+pub struct ClassConfig {
+    pub player_class: PlayerClass,
+    // Cumulative experience to reach level i + 2; levels beyond `max_level` are unreachable
+    pub level_thresholds: [u64; MAX_CLASS_LEVEL],
+    pub max_level: u32,
+    pub stat_points_per_level: u8,
+    pub gain_per_point: PlayerStats,  // Stat bonus bought by one point
+    pub allocation_caps: PlayerStats, // Maximum allocated bonus per stat
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl ClassConfig {
+    pub const LEN: usize = 8 + // discriminator
+        1 + // player_class
+        8 * MAX_CLASS_LEVEL + // level_thresholds
+        4 + // max_level
+        1 + // stat_points_per_level
+        20 + // gain_per_point (5 * u32)
+        20 + // allocation_caps (5 * u32)
+        8 + // updated_at
+        1; // bump
+
+    /// Thresholds must be strictly increasing up to `max_level`
+    pub fn validate_curve(thresholds: &[u64; MAX_CLASS_LEVEL], max_level: u32) -> bool {
+        if max_level == 0 || max_level as usize > MAX_CLASS_LEVEL {
+            return false;
+        }
+        let used = &thresholds[..(max_level - 1) as usize];
+        used.iter().all(|threshold| *threshold > 0)
+            && used.windows(2).all(|pair| pair[0] < pair[1])
+    }
+
+    pub fn level_for(&self, experience: u64) -> u32 {
+        let reachable = &self.level_thresholds[..self.max_level.saturating_sub(1) as usize];
+        1 + reachable.iter().take_while(|threshold| experience >= **threshold).count() as u32
+    }
+
+    /// Stat bonus bought by spending `points`, or None if it overflows
+    pub fn bonus_for(&self, points: &PlayerStats) -> Option<PlayerStats> {
+        Some(PlayerStats {
+            health: points.health.checked_mul(self.gain_per_point.health)?,
+            attack: points.attack.checked_mul(self.gain_per_point.attack)?,
+            defense: points.defense.checked_mul(self.gain_per_point.defense)?,
+            speed: points.speed.checked_mul(self.gain_per_point.speed)?,
+            mana: points.mana.checked_mul(self.gain_per_point.mana)?,
+        })
+    }
+
+    pub fn within_caps(&self, allocated: &PlayerStats) -> bool {
+        allocated.health <= self.allocation_caps.health
+            && allocated.attack <= self.allocation_caps.attack
+            && allocated.defense <= self.allocation_caps.defense
+            && allocated.speed <= self.allocation_caps.speed
+            && allocated.mana <= self.allocation_caps.mana
+    }
+}
+
 #[account]
 pub struct Match {
+    // creator, state and winner lead the layout so memcmp filters and
+    // escrow programs see fixed offsets; see shared::MATCH_CREATOR_OFFSET
     pub creator: Pubkey,
+    pub state: SharedGameState,
+    pub winner: Option<Pubkey>,
     pub match_id: u64,
     pub config: MatchConfig,
-    pub state: SharedGameState,
     pub players: Vec<MatchPlayer>,
     pub current_turn: u8,
     pub turn_deadline: i64,
     pub reward_pool: u64,
-    pub winner: Option<Pubkey>,
     pub created_at: i64,
     pub started_at: Option<i64>,
     pub ended_at: Option<i64>,
     // SECURITY: Admin control fields
     pub force_ended: bool,
     pub force_ended_by: Option<Pubkey>,
-    pub cancel_reason: Option<String>,
+    pub cancel_reason: Option<ReasonCode>,
+    pub cancel_memo_hash: Option<[u8; 32]>,
+    // Event bonuses locked in at creation
+    pub event_modifiers: EventModifiers,
+    // Tournament this match was created for, and the blind level of its round
+    pub tournament: Option<Pubkey>,
+    pub blinds: Option<TournamentBlindLevel>,
     pub bump: u8,
 }
 
 impl Match {
     pub const LEN: usize = 8 + // discriminator
         32 + // creator
-        8 + // match_id
-        64 + // config (MatchConfig size)
         1 + // state
+        1 + 32 + // winner (Option<Pubkey>)
+        8 + // match_id
+        64 + 1 + // config (MatchConfig size, queue_type)
         4 + (MAX_PLAYERS_PER_MATCH * MatchPlayer::LEN) + // players vec
         1 + // current_turn
         8 + // turn_deadline
         8 + // reward_pool
-        1 + 32 + // winner (Option<Pubkey>)
         8 + // created_at
         1 + 8 + // started_at (Option<i64>)
         1 + 8 + // ended_at (Option<i64>)
         1 + // force_ended
         1 + 32 + // force_ended_by (Option<Pubkey>)
-        4 + 256 + // cancel_reason (Option<String>, max 256 chars)
+        1 + 1 + // cancel_reason (Option<ReasonCode>)
+        1 + 32 + // cancel_memo_hash
+        2 + 2 + // event_modifiers
+        1 + 32 + // tournament
+        1 + TournamentBlindLevel::LEN + // blinds
         1; // bump
 
     pub fn is_player_turn(&self, player: &Pubkey) -> bool {
@@ -135,11 +512,21 @@ impl Match {
         self.players.iter().filter(|p| p.is_alive).collect()
     }
 
+    /// Cancelled, or completed with every player's result credited by
+    /// finish_match, so nothing still reads the account
+    pub fn is_closable(&self) -> bool {
+        match self.state {
+            SharedGameState::Cancelled => true,
+            SharedGameState::Completed => self.players.iter().all(|p| p.result_credited),
+            _ => false,
+        }
+    }
+
     pub fn is_match_over(&self) -> bool {
         self.get_alive_players().len() <= 1
     }
 
-    pub fn add_player(&mut self, player: Pubkey, stats: PlayerStats) -> Result<()> {
+    pub fn add_player(&mut self, player: Pubkey, stats: PlayerStats, prestige_level: u8) -> Result<()> {
         if self.players.len() >= self.config.max_players as usize {
             return Err(crate::shared::GameError::MatchFull.into());
         }
@@ -154,6 +541,11 @@ impl Match {
             damage_dealt: 0,
             damage_taken: 0,
             joined_at: Clock::get()?.unix_timestamp,
+            timeouts: 0,
+            skip_turns: 0,
+            prestige_level,
+            reward_earned: 0,
+            result_credited: false,
         };
 
         self.players.push(match_player);
@@ -170,15 +562,31 @@ impl Match {
             return Err(crate::shared::GameError::InvalidGameState.into());
         }
 
-        // Find next alive player
+        // Find next alive player, consuming any forfeited turns along the way
         let mut next_turn = (self.current_turn + 1) % self.players.len() as u8;
         let mut attempts = 0;
         
-        while attempts < self.players.len() && !self.players[next_turn as usize].is_alive {
+        while attempts < self.players.len() {
+            let candidate = &mut self.players[next_turn as usize];
+            if candidate.is_alive && candidate.skip_turns == 0 {
+                break;
+            }
+            if candidate.is_alive {
+                candidate.skip_turns -= 1;
+            }
             next_turn = (next_turn + 1) % self.players.len() as u8;
             attempts += 1;
         }
 
+        // Everyone alive had a forfeited turn queued; fall back to the next alive player
+        if attempts >= self.players.len() {
+            attempts = 0;
+            while attempts < self.players.len() && !self.players[next_turn as usize].is_alive {
+                next_turn = (next_turn + 1) % self.players.len() as u8;
+                attempts += 1;
+            }
+        }
+
         if attempts >= self.players.len() {
             return Err(crate::shared::GameError::InvalidGameState.into());
         }
@@ -200,6 +608,11 @@ pub struct MatchPlayer {
     pub damage_dealt: u32,
     pub damage_taken: u32,
     pub joined_at: i64,
+    pub timeouts: u8,
+    pub skip_turns: u8,
+    pub prestige_level: u8, // Captured at join for settlement multipliers
+    pub reward_earned: u64,
+    pub result_credited: bool, // Counted in the profile by finish_match
 }
 
 impl MatchPlayer {
@@ -211,7 +624,12 @@ impl MatchPlayer {
         4 + // actions_taken
         4 + // damage_dealt
         4 + // damage_taken
-        8; // joined_at
+        8 + // joined_at
+        1 + // timeouts
+        1 + // skip_turns
+        1 + // prestige_level
+        8 + // reward_earned
+        1; // result_credited
 
     pub fn take_damage(&mut self, damage: u32) {
         self.current_health = self.current_health.saturating_sub(damage);
@@ -268,4 +686,997 @@ impl CombatResult {
         1 + // critical_hit
         1 + // target_defeated
         4; // experience_gained
-}
\ No newline at end of file
+}
+
+/// Timeouts in a single match before each escalation step
+pub const AFK_ROUND_FORFEIT_TIMEOUTS: u8 = 2;
+pub const AFK_MATCH_FORFEIT_TIMEOUTS: u8 = 3;
+/// Match forfeits (strikes) before a matchmaking ban is applied
+pub const AFK_BAN_STRIKES: u8 = 3;
+pub const AFK_BAN_BASE_DURATION: i64 = 30 * 60; // 30 minutes, doubled per extra strike
+pub const AFK_MAX_BAN_DURATION: i64 = 7 * 24 * 60 * 60; // 7 days
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PenaltyLevel {
+    None,
+    Warning,
+    RoundForfeit,
+    MatchForfeit,
+    MatchmakingBan,
+}
+
+impl Default for PenaltyLevel {
+    fn default() -> Self {
+        PenaltyLevel::None
+    }
+}
+
+/// Progressive AFK penalty record for a player
+#[account]
+#[derive(Default)]
+pub struct PenaltyComponent {
+    pub player: Pubkey,
+    pub level: PenaltyLevel,
+    pub warnings: u32,
+    pub round_forfeits: u32,
+    pub match_forfeits: u32,
+    pub bans: u32,
+    pub banned_until: i64,
+    pub last_penalty_at: i64,
+    pub cleared_by: Option<Pubkey>,
+    pub bump: u8,
+}
+
+impl PenaltyComponent {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // player
+        1 + // level
+        4 + // warnings
+        4 + // round_forfeits
+        4 + // match_forfeits
+        4 + // bans
+        8 + // banned_until
+        8 + // last_penalty_at
+        1 + 32 + // cleared_by (Option<Pubkey>)
+        1; // bump
+
+    pub fn is_banned(&self, current_time: i64) -> bool {
+        self.banned_until > current_time
+    }
+
+    /// Penalty for the given number of timeouts within a single match
+    pub fn level_for_match_timeouts(timeouts: u8) -> PenaltyLevel {
+        if timeouts >= AFK_MATCH_FORFEIT_TIMEOUTS {
+            PenaltyLevel::MatchForfeit
+        } else if timeouts >= AFK_ROUND_FORFEIT_TIMEOUTS {
+            PenaltyLevel::RoundForfeit
+        } else {
+            PenaltyLevel::Warning
+        }
+    }
+
+    /// Ban length for a strike count; zero below the threshold
+    pub fn ban_duration_for_strikes(strikes: u8) -> i64 {
+        if strikes < AFK_BAN_STRIKES {
+            return 0;
+        }
+        let doublings = (strikes - AFK_BAN_STRIKES).min(16) as u32;
+        AFK_BAN_BASE_DURATION
+            .saturating_mul(1i64 << doublings)
+            .min(AFK_MAX_BAN_DURATION)
+    }
+
+    pub fn record(&mut self, level: PenaltyLevel, current_time: i64) {
+        match level {
+            PenaltyLevel::Warning => self.warnings = self.warnings.saturating_add(1),
+            PenaltyLevel::RoundForfeit => self.round_forfeits = self.round_forfeits.saturating_add(1),
+            PenaltyLevel::MatchForfeit => self.match_forfeits = self.match_forfeits.saturating_add(1),
+            PenaltyLevel::MatchmakingBan => self.bans = self.bans.saturating_add(1),
+            PenaltyLevel::None => {}
+        }
+        self.level = level;
+        self.last_penalty_at = current_time;
+        self.cleared_by = None;
+    }
+}
+
+/// Compliance gate for regulated deployments. Permissionless unless configured.
+#[account]
+#[derive(Default)]
+pub struct ComplianceConfig {
+    pub mode: ComplianceMode,
+    pub allowlist_root: [u8; 32],
+    pub attestation_issuer: Pubkey,
+    pub updated_by: Pubkey,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl ComplianceConfig {
+    pub const LEN: usize = 8 + // discriminator
+        1 + // mode
+        32 + // allowlist_root
+        32 + // attestation_issuer
+        32 + // updated_by
+        8 + // updated_at
+        1; // bump
+
+    /// Check a player against the configured gate
+    pub fn verify(
+        &self,
+        player: &Pubkey,
+        allowlist_proof: &[[u8; 32]],
+        credential: Option<&ComplianceCredential>,
+        current_time: i64,
+    ) -> Result<()> {
+        match self.mode {
+            ComplianceMode::Permissionless => Ok(()),
+            ComplianceMode::MerkleAllowlist => {
+                if !verify_merkle_proof(allowlist_leaf(player), allowlist_proof, self.allowlist_root) {
+                    return Err(GameError::NotAllowlisted.into());
+                }
+                Ok(())
+            }
+            ComplianceMode::Attestation => {
+                let valid = credential.map_or(false, |c| {
+                    c.player == *player
+                        && c.issuer == self.attestation_issuer
+                        && !c.revoked
+                        && (c.expires_at == 0 || c.expires_at > current_time)
+                });
+                if !valid {
+                    return Err(GameError::InvalidComplianceCredential.into());
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Credential issued to a player by the configured attestation issuer
+#[account]
+#[derive(Default)]
+pub struct ComplianceCredential {
+    pub player: Pubkey,
+    pub issuer: Pubkey,
+    pub issued_at: i64,
+    pub expires_at: i64, // 0 = no expiry
+    pub revoked: bool,
+    pub bump: u8,
+}
+
+impl ComplianceCredential {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // player
+        32 + // issuer
+        8 + // issued_at
+        8 + // expires_at
+        1 + // revoked
+        1; // bump
+}
+
+// Social recovery for player identities
+pub const MAX_GUARDIANS: usize = 7;
+pub const RECOVERY_TIMELOCK: i64 = 48 * 60 * 60; // 48 hours
+
+/// Guardians able to re-point an identity's cold wallet after losing access
+#[account]
+#[derive(Default)]
+pub struct IdentityRecovery {
+    pub identity: Pubkey,
+    pub guardians: Vec<Pubkey>,
+    pub threshold: u8,
+    pub pending_wallet: Option<Pubkey>,
+    pub initiated_at: i64,
+    pub approvals: u8, // bitset indexed by guardian position
+    pub cancel_votes: u8, // guardians voting to abort the pending recovery, same indexing
+    pub bump: u8,
+}
+
+impl IdentityRecovery {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // identity
+        4 + MAX_GUARDIANS * 32 + // guardians
+        1 + // threshold
+        1 + 32 + // pending_wallet
+        8 + // initiated_at
+        1 + // approvals
+        1 + // cancel_votes
+        1; // bump
+
+    pub fn guardian_index(&self, guardian: &Pubkey) -> Option<usize> {
+        self.guardians.iter().position(|g| g == guardian)
+    }
+
+    pub fn approval_count(&self) -> u8 {
+        self.approvals.count_ones() as u8
+    }
+
+    pub fn cancel_vote_count(&self) -> u8 {
+        self.cancel_votes.count_ones() as u8
+    }
+
+    pub fn clear_pending(&mut self) {
+        self.pending_wallet = None;
+        self.initiated_at = 0;
+        self.approvals = 0;
+        self.cancel_votes = 0;
+    }
+
+    pub fn can_execute(&self, now: i64) -> bool {
+        self.pending_wallet.is_some()
+            && self.threshold > 0
+            && self.approval_count() >= self.threshold
+            && now >= self.initiated_at + RECOVERY_TIMELOCK
+    }
+}
+
+// Sponsored onboarding
+pub const SPONSOR_WINDOW: i64 = 24 * 60 * 60; // daily cap window
+
+/// Program-owned vault whose lamports cover rent for sponsored registrations
+#[account]
+#[derive(Default)]
+pub struct SponsorVault {
+    pub authority: Pubkey,
+    pub daily_cap: u32, // registrations per window
+    pub window_start: i64,
+    pub sponsored_in_window: u32,
+    pub total_sponsored: u64,
+    pub active_sponsorships: u64,
+    pub lamports_advanced: u64,
+    pub lamports_reclaimed: u64,
+    pub bump: u8,
+}
+
+impl SponsorVault {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // authority
+        4 + // daily_cap
+        8 + // window_start
+        4 + // sponsored_in_window
+        8 + // total_sponsored
+        8 + // active_sponsorships
+        8 + // lamports_advanced
+        8 + // lamports_reclaimed
+        1; // bump
+
+    /// Count a registration against the daily cap, rolling the window when it expires
+    pub fn consume_daily_slot(&mut self, now: i64) -> Result<()> {
+        if now >= self.window_start + SPONSOR_WINDOW {
+            self.window_start = now;
+            self.sponsored_in_window = 0;
+        }
+        if self.sponsored_in_window >= self.daily_cap {
+            return Err(GameError::SponsorCapReached.into());
+        }
+        self.sponsored_in_window += 1;
+        Ok(())
+    }
+}
+
+/// Record of rent a sponsor advanced for a player, reclaimed when the accounts close
+#[account]
+#[derive(Default)]
+pub struct Sponsorship {
+    pub sponsor_vault: Pubkey,
+    pub player: Pubkey,
+    pub lamports: u64,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl Sponsorship {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // sponsor_vault
+        32 + // player
+        8 + // lamports
+        8 + // created_at
+        1; // bump
+}
+
+/// Most entrants a tournament bracket holds
+pub const MAX_TOURNAMENT_ENTRANTS: usize = 32;
+
+/// Pairings the bracket account holds across all rounds: enough for a full
+/// single-elimination bracket, five Swiss rounds of 32, or a 12-player round robin
+pub const MAX_BRACKET_PAIRINGS: usize = 80;
+
+/// Round robin plays every pairing, so its field is kept small
+pub const MAX_ROUND_ROBIN_ENTRANTS: usize = 12;
+
+/// Longest blind schedule an organizer may attach to a tournament
+pub const MAX_TOURNAMENT_BLIND_LEVELS: usize = 8;
+// A tournament not started this long after creation is cancelled and entry fees refunded
+pub const TOURNAMENT_START_DEADLINE: i64 = 7 * 24 * 60 * 60;
+/// Ceiling on the protocol's cut of every prize pool
+pub const MAX_PLATFORM_FEE_BPS: u16 = 500;
+/// Ceiling on what an organizer may take from their tournament's prize pool
+pub const MAX_ORGANIZER_FEE_BPS: u16 = 2000;
+
+/// Protocol fee on tournament prize pools - set by the upgrade authority
+#[account]
+#[derive(Default)]
+pub struct TournamentFeeConfig {
+    pub treasury: Pubkey, // Owner of the token accounts platform fees are paid to
+    pub platform_fee_bps: u16,
+    pub bump: u8,
+}
+
+impl TournamentFeeConfig {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // treasury
+        2 + // platform_fee_bps
+        1; // bump
+}
+
+/// Profile thresholds a player must meet to register
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct EntryRequirements {
+    pub min_level: u32,
+    pub min_matches: u32,
+    pub min_prestige: u8,
+}
+
+impl EntryRequirements {
+    pub const LEN: usize = 4 + 4 + 1;
+
+    pub fn met_by(&self, profile: &PlayerProfile) -> bool {
+        profile.level >= self.min_level
+            && profile.total_matches >= self.min_matches
+            && profile.prestige_level >= self.min_prestige
+    }
+}
+
+/// One level of an organizer's blind schedule, held for `rounds` tournament rounds
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct TournamentBlindLevel {
+    pub small_blind: u64,
+    pub big_blind: u64,
+    pub ante: u64,
+    pub rounds: u8,
+}
+
+impl TournamentBlindLevel {
+    pub const LEN: usize = 8 + 8 + 8 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TournamentState {
+    Registration,
+    InProgress,
+    Completed,
+    Finalized, // Prize pool paid out
+    Cancelled, // Never started; entrants reclaim their fees
+}
+
+impl Default for TournamentState {
+    fn default() -> Self {
+        TournamentState::Registration
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TournamentFormat {
+    SingleElimination,
+    Swiss,      // Fixed rounds, paired within score groups, rematches avoided
+    RoundRobin, // Everyone plays everyone once
+}
+
+impl Default for TournamentFormat {
+    fn default() -> Self {
+        TournamentFormat::SingleElimination
+    }
+}
+
+/// How the prize pool is shared among the top finishers
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PayoutTable {
+    WinnerTakeAll,
+    TopThree,      // 50/30/20
+    TopTenPercent, // Top tenth of the field, rounded up, on a linear scale
+}
+
+impl Default for PayoutTable {
+    fn default() -> Self {
+        PayoutTable::WinnerTakeAll
+    }
+}
+
+impl PayoutTable {
+    /// Relative share of each paid place, first place first
+    pub fn weights(&self, entrants: usize) -> Vec<u64> {
+        match self {
+            PayoutTable::WinnerTakeAll => vec![1],
+            PayoutTable::TopThree => [50, 30, 20].into_iter().take(entrants).collect(),
+            PayoutTable::TopTenPercent => {
+                let places = entrants.div_ceil(10).max(1) as u64;
+                (1..=places).rev().collect()
+            },
+        }
+    }
+}
+
+/// Tournament rules fixed at creation
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct TournamentConfig {
+    pub format: TournamentFormat,
+    pub swiss_rounds: u8,
+    pub max_entrants: u8,
+    pub best_of: u8, // Matches per pairing; first to a majority advances
+    pub entry_fee: u64, // In the tournament's entry mint; 0 = free
+    pub accepts_tickets: bool, // A ticket NFT burn may stand in for the fee
+    pub late_registration_rounds: u8, // Swiss only: joining stays open through this round; 0 = closed at start
+    pub max_reentries: u8, // Re-entries per player after a lost pairing, inside the late window
+    pub reentry_fee_step_bps: u16, // Each re-entry costs this much more of entry_fee than the last
+    pub organizer_fee_bps: u16, // Organizer's cut of the prize pool, paid at finalize
+    pub requirements: EntryRequirements,
+    pub payout_table: PayoutTable,
+}
+
+impl TournamentConfig {
+    pub const LEN: usize = 1 + // format
+        1 + // swiss_rounds
+        1 + // max_entrants
+        1 + // best_of
+        8 + // entry_fee
+        1 + // accepts_tickets
+        1 + // late_registration_rounds
+        1 + // max_reentries
+        2 + // reentry_fee_step_bps
+        2 + // organizer_fee_bps
+        EntryRequirements::LEN + // requirements
+        1; // payout_table
+
+    /// Check the rules are coherent and fit the bracket account for `entrants` players
+    pub fn validate(&self, entrants: usize) -> bool {
+        if entrants < 2 || entrants > MAX_TOURNAMENT_ENTRANTS || self.best_of == 0 || self.best_of % 2 == 0 {
+            return false;
+        }
+        if self.organizer_fee_bps > MAX_ORGANIZER_FEE_BPS {
+            return false;
+        }
+        // Late entrants and re-entries need a field that can absorb them mid-event
+        let late = self.late_registration_rounds > 0;
+        if (late || self.max_reentries > 0)
+            && (self.format != TournamentFormat::Swiss || !late || self.late_registration_rounds >= self.swiss_rounds)
+        {
+            return false;
+        }
+        match self.format {
+            TournamentFormat::SingleElimination => true,
+            TournamentFormat::Swiss => {
+                self.swiss_rounds > 0
+                    && (self.swiss_rounds as usize) < entrants
+                    && self.swiss_rounds as usize * entrants.div_ceil(2) <= MAX_BRACKET_PAIRINGS
+            },
+            TournamentFormat::RoundRobin => entrants <= MAX_ROUND_ROBIN_ENTRANTS,
+        }
+    }
+
+    /// Fee for a player's `reentry`-th re-entry (0 = first entry), escalating
+    /// by `reentry_fee_step_bps` of the base fee each time
+    pub fn entry_fee_for(&self, reentry: u8) -> Option<u64> {
+        let bps = 10_000u128 + self.reentry_fee_step_bps as u128 * reentry as u128;
+        u64::try_from(self.entry_fee as u128 * bps / 10_000).ok()
+    }
+
+    /// Whether players may still join, or re-enter, with `current_round` in play
+    pub fn late_registration_open(&self, current_round: u8) -> bool {
+        current_round <= self.late_registration_rounds
+    }
+}
+
+/// Tournament over game-program matches. Entry fees collect in `prize_vault`,
+/// a token account owned by the tournament PDA.
+#[account]
+#[derive(Default)]
+pub struct Tournament {
+    pub authority: Pubkey,
+    pub tournament_id: u64,
+    pub state: TournamentState,
+    pub config: TournamentConfig,
+    pub entry_mint: Pubkey,
+    pub prize_vault: Pubkey,
+    pub prize_pool: u64,
+    pub platform_fee_bps: u16, // Snapshot of the protocol fee at creation
+    pub blind_levels: Vec<TournamentBlindLevel>,
+    pub entrants: Vec<Pubkey>,
+    pub total_entries: u16, // Registrations plus re-entries
+    pub champion: Option<Pubkey>,
+    pub created_at: i64,
+    pub started_at: i64,
+    pub bump: u8,
+}
+
+impl Tournament {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // authority
+        8 + // tournament_id
+        1 + // state
+        TournamentConfig::LEN + // config
+        32 + // entry_mint
+        32 + // prize_vault
+        8 + // prize_pool
+        2 + // platform_fee_bps
+        4 + TournamentBlindLevel::LEN * MAX_TOURNAMENT_BLIND_LEVELS + // blind_levels
+        4 + 32 * MAX_TOURNAMENT_ENTRANTS + // entrants
+        2 + // total_entries
+        1 + 32 + // champion
+        8 + // created_at
+        8 + // started_at
+        1; // bump
+
+    /// Whether registration has outlived the start deadline, so the event can no
+    /// longer start and entrants may reclaim their fees
+    pub fn start_deadline_passed(&self, now: i64) -> bool {
+        match self.state {
+            TournamentState::Registration => now >= self.created_at + TOURNAMENT_START_DEADLINE,
+            TournamentState::Cancelled => true,
+            _ => false,
+        }
+    }
+
+    /// Blinds in force during `round`; the last level holds once the schedule runs out
+    pub fn blinds_for_round(&self, round: u8) -> Option<TournamentBlindLevel> {
+        let mut remaining = round.saturating_sub(1);
+        for level in &self.blind_levels {
+            if remaining < level.rounds {
+                return Some(*level);
+            }
+            remaining -= level.rounds;
+        }
+        self.blind_levels.last().copied()
+    }
+
+    /// Split of the prize pool into (platform fee, organizer fee, prizes)
+    pub fn fee_split(&self) -> Option<(u64, u64, u64)> {
+        let pool = self.prize_pool as u128;
+        let platform = pool * self.platform_fee_bps as u128 / 10_000;
+        let organizer = pool * self.config.organizer_fee_bps as u128 / 10_000;
+        let prizes = pool.checked_sub(platform + organizer)?;
+        Some((platform as u64, organizer as u64, prizes as u64))
+    }
+
+    pub fn wins_needed(&self) -> u8 {
+        self.config.best_of / 2 + 1
+    }
+
+    /// Rounds needed to finish with `entrants` players
+    pub fn total_rounds(&self, entrants: usize) -> u8 {
+        match self.config.format {
+            TournamentFormat::SingleElimination => elimination_rounds(entrants),
+            TournamentFormat::Swiss => self.config.swiss_rounds,
+            // An odd field adds a bye seat, so everyone sits out once
+            TournamentFormat::RoundRobin => (entrants + entrants % 2 - 1) as u8,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EntryMethod {
+    Fee,
+    Ticket,
+}
+
+impl Default for EntryMethod {
+    fn default() -> Self {
+        EntryMethod::Fee
+    }
+}
+
+/// A player's registration, with how they paid. Ticket entries keep the
+/// ticket's provenance from nft-program.
+#[account]
+#[derive(Default)]
+pub struct TournamentEntry {
+    pub tournament: Pubkey,
+    pub player: Pubkey,
+    pub method: EntryMethod,
+    pub fee_paid: u64, // Cumulative across re-entries
+    pub reentries: u8,
+    pub ticket_mint: Option<Pubkey>,
+    pub ticket_source: Option<TicketSource>,
+    pub ticket_issued_at: i64,
+    pub registered_at: i64,
+    pub bump: u8,
+}
+
+impl TournamentEntry {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // tournament
+        32 + // player
+        1 + // method
+        8 + // fee_paid
+        1 + // reentries
+        1 + 32 + // ticket_mint
+        1 + 1 + // ticket_source
+        8 + // ticket_issued_at
+        8 + // registered_at
+        1; // bump
+}
+
+/// A player's running score. Buchholz (the sum of opponents' points) is
+/// refreshed as each round closes and breaks ties in the final standings.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct Standing {
+    pub player: Pubkey,
+    pub points: u8,
+    pub buchholz: u16,
+    pub byes: u8,
+}
+
+impl Standing {
+    pub const LEN: usize = 32 + 1 + 2 + 1;
+}
+
+/// One pairing in the bracket. A default key in the second seat is a bye.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct BracketPairing {
+    pub round: u8,
+    pub players: [Pubkey; 2],
+    pub scores: [u8; 2],
+    pub winner: Option<u8>, // Seat that advanced
+    pub last_result_at: i64, // Finalization time of the last counted match
+}
+
+impl BracketPairing {
+    pub const LEN: usize = 1 + 64 + 2 + 2 + 8;
+
+    pub fn seat_of(&self, player: &Pubkey) -> Option<u8> {
+        self.players.iter().position(|p| p == player).map(|seat| seat as u8)
+    }
+}
+
+/// Live bracket feed: every round's pairings and scores in one account, so
+/// tournament UIs and stream overlays render the bracket from a single fetch
+#[account]
+#[derive(Default)]
+pub struct BracketState {
+    pub tournament: Pubkey,
+    pub current_round: u8,
+    pub total_rounds: u8,
+    pub sequence: u32, // Bumped on every update; clients skip fetches that did not change it
+    pub pairings: Vec<BracketPairing>,
+    pub standings: Vec<Standing>, // Registration order until the final ranking
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl BracketState {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // tournament
+        1 + // current_round
+        1 + // total_rounds
+        4 + // sequence
+        4 + BracketPairing::LEN * MAX_BRACKET_PAIRINGS + // pairings
+        4 + Standing::LEN * MAX_TOURNAMENT_ENTRANTS + // standings
+        8 + // updated_at
+        1; // bump
+
+    /// Pair `players` in order for `round`; an odd player out gets a bye
+    pub fn seed_round(&mut self, round: u8, players: &[Pubkey]) {
+        self.current_round = round;
+        for pair in players.chunks(2) {
+            let bye = pair.len() == 1;
+            self.pairings.push(BracketPairing {
+                round,
+                players: [pair[0], if bye { Pubkey::default() } else { pair[1] }],
+                winner: if bye { Some(0) } else { None },
+                ..BracketPairing::default()
+            });
+        }
+    }
+
+    /// Swiss pairing: order by points (registration order within a score
+    /// group), give the bye to the lowest-placed player without one, then pair
+    /// each player with the next one they have not met, falling back to a
+    /// rematch only when nobody else is left
+    pub fn seed_swiss_round(&mut self, round: u8) {
+        let mut order: Vec<Pubkey> = self.standings.iter().map(|s| s.player).collect();
+        order.sort_by_key(|player| std::cmp::Reverse(self.standing(player).map_or(0, |s| s.points)));
+
+        let mut bye = None;
+        if order.len() % 2 == 1 {
+            let bye_index = order
+                .iter()
+                .rposition(|player| self.standing(player).map_or(false, |s| s.byes == 0))
+                .unwrap_or(order.len() - 1);
+            let player = order.remove(bye_index);
+            self.award_bye(&player);
+            bye = Some(player);
+        }
+
+        let mut pairs = Vec::with_capacity(order.len() / 2);
+        while let Some(first) = order.first().copied() {
+            order.remove(0);
+            let partner = order.iter().position(|other| !self.have_met(&first, other)).unwrap_or(0);
+            pairs.push([first, order.remove(partner)]);
+        }
+
+        self.current_round = round;
+        for players in pairs {
+            self.pairings.push(BracketPairing { round, players, ..BracketPairing::default() });
+        }
+        if let Some(bye) = bye {
+            self.pairings.push(BracketPairing {
+                round,
+                players: [bye, Pubkey::default()],
+                winner: Some(0),
+                ..BracketPairing::default()
+            });
+        }
+    }
+
+    /// Round-robin pairing by the circle method: the first entrant stays put
+    /// while the rest rotate one place per round. An odd field gets a bye seat,
+    /// scored as a win as in Swiss.
+    pub fn seed_round_robin_round(&mut self, round: u8, entrants: &[Pubkey]) {
+        let mut seats = entrants.to_vec();
+        if seats.len() % 2 == 1 {
+            seats.push(Pubkey::default());
+        }
+        let count = seats.len();
+        let shift = (round as usize).saturating_sub(1);
+        let mut arrangement = vec![seats[0]];
+        arrangement.extend((0..count - 1).map(|i| seats[1 + (i + shift) % (count - 1)]));
+
+        self.current_round = round;
+        for i in 0..count / 2 {
+            let (a, b) = (arrangement[i], arrangement[count - 1 - i]);
+            let players = if a == Pubkey::default() { [b, a] } else { [a, b] };
+            let bye = players[1] == Pubkey::default();
+            if bye {
+                self.award_bye(&players[0]);
+            }
+            self.pairings.push(BracketPairing {
+                round,
+                players,
+                winner: if bye { Some(0) } else { None },
+                ..BracketPairing::default()
+            });
+        }
+    }
+
+    /// A bye scores a point, the same as a won pairing
+    fn award_bye(&mut self, player: &Pubkey) {
+        if let Some(standing) = self.standing_mut(player) {
+            standing.points += 1;
+            standing.byes += 1;
+        }
+    }
+
+    pub fn standing(&self, player: &Pubkey) -> Option<&Standing> {
+        self.standings.iter().find(|s| s.player == *player)
+    }
+
+    pub fn standing_mut(&mut self, player: &Pubkey) -> Option<&mut Standing> {
+        self.standings.iter_mut().find(|s| s.player == *player)
+    }
+
+    /// Mean points across the field, rounded down: the score a late entrant
+    /// starts from
+    pub fn average_points(&self) -> u8 {
+        if self.standings.is_empty() {
+            return 0;
+        }
+        let total: u32 = self.standings.iter().map(|s| s.points as u32).sum();
+        (total / self.standings.len() as u32) as u8
+    }
+
+    /// A player is out once they have lost their most recent pairing
+    pub fn is_eliminated(&self, player: &Pubkey) -> bool {
+        self.pairings
+            .iter()
+            .rev()
+            .find_map(|p| p.seat_of(player).map(|seat| p.winner.map_or(false, |w| w != seat)))
+            .unwrap_or(false)
+    }
+
+    pub fn have_met(&self, a: &Pubkey, b: &Pubkey) -> bool {
+        self.pairings.iter().any(|p| p.seat_of(a).is_some() && p.seat_of(b).is_some())
+    }
+
+    /// Winner of the decided pairing between `a` and `b`, if they have met
+    pub fn head_to_head(&self, a: &Pubkey, b: &Pubkey) -> Option<Pubkey> {
+        self.pairings
+            .iter()
+            .rev()
+            .filter(|p| p.seat_of(a).is_some() && p.seat_of(b).is_some())
+            .find_map(|p| p.winner.map(|seat| p.players[seat as usize]))
+    }
+
+    /// Recompute every Buchholz score from the decided pairings
+    pub fn refresh_buchholz(&mut self) {
+        let points: Vec<(Pubkey, u8)> = self.standings.iter().map(|s| (s.player, s.points)).collect();
+        let points_of = |player: &Pubkey| points.iter().find(|(p, _)| p == player).map_or(0, |(_, pts)| *pts as u16);
+        let mut buchholz = vec![0u16; self.standings.len()];
+        for pairing in self.pairings.iter().filter(|p| p.players[1] != Pubkey::default()) {
+            for (index, standing) in self.standings.iter().enumerate() {
+                if let Some(seat) = pairing.seat_of(&standing.player) {
+                    buchholz[index] += points_of(&pairing.players[1 - seat as usize]);
+                }
+            }
+        }
+        for (standing, score) in self.standings.iter_mut().zip(buchholz) {
+            standing.buchholz = score;
+        }
+    }
+
+    /// Final order: points, then Buchholz, then head-to-head between players
+    /// still level, then registration order
+    pub fn rank_standings(&mut self) {
+        self.refresh_buchholz();
+        self.standings.sort_by_key(|s| std::cmp::Reverse((s.points, s.buchholz)));
+        for i in 1..self.standings.len() {
+            let (upper, lower) = (self.standings[i - 1], self.standings[i]);
+            let level = upper.points == lower.points && upper.buchholz == lower.buchholz;
+            if level && self.head_to_head(&upper.player, &lower.player) == Some(lower.player) {
+                self.standings.swap(i - 1, i);
+            }
+        }
+    }
+
+    /// Players from first place down. Elimination brackets rank by the round
+    /// each player went out in; Swiss and round robin use the ranked standings.
+    pub fn finishing_order(&self, format: TournamentFormat) -> Vec<Pubkey> {
+        let mut order: Vec<Pubkey> = self.standings.iter().map(|s| s.player).collect();
+        if format == TournamentFormat::SingleElimination {
+            let reached = |player: &Pubkey| {
+                self.pairings
+                    .iter()
+                    .rev()
+                    .find_map(|p| p.seat_of(player).map(|seat| (p.round, p.winner == Some(seat))))
+                    .unwrap_or_default()
+            };
+            order.sort_by_key(|player| std::cmp::Reverse(reached(player)));
+        }
+        order
+    }
+
+    /// Winners of `round` in bracket order, or None while a pairing is undecided
+    pub fn round_winners(&self, round: u8) -> Option<Vec<Pubkey>> {
+        self.pairings
+            .iter()
+            .filter(|pairing| pairing.round == round)
+            .map(|pairing| pairing.winner.map(|seat| pairing.players[seat as usize]))
+            .collect()
+    }
+
+    pub fn touch(&mut self, now: i64) {
+        self.sequence = self.sequence.wrapping_add(1);
+        self.updated_at = now;
+    }
+}
+
+/// Rounds a single-elimination bracket of `entrants` needs
+pub fn elimination_rounds(entrants: usize) -> u8 {
+    let mut rounds = 0;
+    while (1usize << rounds) < entrants {
+        rounds += 1;
+    }
+    rounds
+}
+
+/// Blind schedules must escalate: every level lasts at least a round and never
+/// lowers the big blind, and the big blind covers the small
+pub fn valid_blind_schedule(levels: &[TournamentBlindLevel]) -> bool {
+    levels.len() <= MAX_TOURNAMENT_BLIND_LEVELS
+        && levels.iter().all(|l| l.rounds > 0 && l.big_blind >= l.small_blind)
+        && levels.windows(2).all(|w| w[1].big_blind >= w[0].big_blind)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seed_round() {
+        let players: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+        let mut bracket = BracketState::default();
+        bracket.seed_round(1, &players);
+
+        assert_eq!(bracket.current_round, 1);
+        assert_eq!(bracket.pairings.len(), 3);
+        assert_eq!(bracket.pairings[0].players, [players[0], players[1]]);
+        assert_eq!(bracket.pairings[1].players, [players[2], players[3]]);
+        assert_eq!(bracket.pairings[0].winner, None);
+        // The odd player out advances on a bye
+        assert_eq!(bracket.pairings[2].players, [players[4], Pubkey::default()]);
+        assert_eq!(bracket.pairings[2].winner, Some(0));
+    }
+
+    #[test]
+    fn test_round_winners() {
+        let players: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let mut bracket = BracketState::default();
+        bracket.seed_round(1, &players);
+        assert_eq!(bracket.round_winners(1), None);
+
+        bracket.pairings[0].winner = Some(1);
+        assert_eq!(bracket.round_winners(1), None);
+        bracket.pairings[1].winner = Some(0);
+        assert_eq!(bracket.round_winners(1), Some(vec![players[1], players[2]]));
+
+        // Later rounds only count their own pairings
+        bracket.seed_round(2, &[players[1], players[2]]);
+        assert_eq!(bracket.round_winners(2), None);
+        assert_eq!(bracket.round_winners(1), Some(vec![players[1], players[2]]));
+    }
+
+    fn bracket_with(players: &[Pubkey]) -> BracketState {
+        BracketState {
+            standings: players.iter().map(|&player| Standing { player, ..Standing::default() }).collect(),
+            ..BracketState::default()
+        }
+    }
+
+    #[test]
+    fn test_swiss_round_avoids_rematches_and_spreads_byes() {
+        let players: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+        let mut bracket = bracket_with(&players);
+        bracket.seed_swiss_round(1);
+
+        // Level on points, so the last registered takes the first bye
+        assert_eq!(bracket.pairings.len(), 3);
+        assert_eq!(bracket.pairings[0].players, [players[0], players[1]]);
+        assert_eq!(bracket.pairings[1].players, [players[2], players[3]]);
+        assert_eq!(bracket.pairings[2].players, [players[4], Pubkey::default()]);
+        assert_eq!(bracket.standing(&players[4]).map(|s| (s.points, s.byes)), Some((1, 1)));
+
+        bracket.pairings[0].winner = Some(0);
+        bracket.standing_mut(&players[0]).unwrap().points += 1;
+        bracket.pairings[1].winner = Some(0);
+        bracket.standing_mut(&players[2]).unwrap().points += 1;
+        bracket.seed_swiss_round(2);
+
+        let round_two: Vec<[Pubkey; 2]> = bracket.pairings[3..].iter().map(|p| p.players).collect();
+        // The bye goes to the lowest-placed player who has not had one
+        assert_eq!(round_two[2], [players[3], Pubkey::default()]);
+        // Nobody meets a round-one opponent again
+        assert_eq!(round_two[0], [players[0], players[2]]);
+        assert_eq!(round_two[1], [players[4], players[1]]);
+    }
+
+    #[test]
+    fn test_round_robin_meets_everyone_once() {
+        let players: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+        let mut bracket = bracket_with(&players);
+        let rounds = 5;
+        for round in 1..=rounds {
+            bracket.seed_round_robin_round(round, &players);
+        }
+
+        for (i, a) in players.iter().enumerate() {
+            for b in &players[i + 1..] {
+                let meetings = bracket.pairings.iter().filter(|p| p.seat_of(a).is_some() && p.seat_of(b).is_some()).count();
+                assert_eq!(meetings, 1);
+            }
+            // Each player sits out exactly once and is scored for it as in Swiss
+            let standing = bracket.standing(a).unwrap();
+            assert_eq!((standing.points, standing.byes), (1, 1));
+        }
+        assert!(bracket.pairings.iter().all(|p| p.players[0] != Pubkey::default()));
+    }
+
+    #[test]
+    fn test_rank_standings_breaks_ties() {
+        let players: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let mut bracket = bracket_with(&players);
+        let decided = |winner: Pubkey, loser: Pubkey| BracketPairing { round: 1, players: [winner, loser], winner: Some(0), ..BracketPairing::default() };
+        bracket.pairings = vec![decided(players[1], players[0]), decided(players[2], players[3])];
+        for (player, points) in players.iter().zip([1, 1, 2, 0]) {
+            bracket.standing_mut(player).unwrap().points = points;
+        }
+
+        // Buchholz sums the points of everyone a player has met
+        bracket.refresh_buchholz();
+        let buchholz: Vec<u16> = bracket.standings.iter().map(|s| s.buchholz).collect();
+        assert_eq!(buchholz, vec![1, 1, 0, 2]);
+
+        // Points first; the two level on points and Buchholz are split by their game
+        bracket.rank_standings();
+        let order: Vec<Pubkey> = bracket.standings.iter().map(|s| s.player).collect();
+        assert_eq!(order, vec![players[2], players[1], players[0], players[3]]);
+    }
+}
@@ -0,0 +1,199 @@
+use anchor_lang::prelude::*;
+use shared::GameError;
+use crate::state::{ClanWar, Guild, WarStatus};
+
+/// Move lamports between two accounts owned by this program
+fn move_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    **from.try_borrow_mut_lamports()? -= amount;
+    **to.try_borrow_mut_lamports()? += amount;
+    Ok(())
+}
+
+fn stake_from_treasury(guild: &mut Account<Guild>, war: &AccountInfo, stake: u64) -> Result<()> {
+    if stake > guild.treasury_balance {
+        return Err(GameError::InsufficientTreasury.into());
+    }
+    guild.treasury_balance -= stake;
+    move_lamports(&guild.to_account_info(), war, stake)
+}
+
+fn pay_to_treasury(war: &AccountInfo, guild: &mut Account<Guild>, amount: u64) -> Result<()> {
+    guild.treasury_balance = guild.treasury_balance.checked_add(amount).ok_or(GameError::ArithmeticOverflow)?;
+    guild.active_war = None;
+    move_lamports(war, &guild.to_account_info(), amount)
+}
+
+/// Challenge another guild, staking from the challenger's treasury
+pub fn declare_war(ctx: Context<crate::DeclareWar>, stake: u64, starts_at: i64, ends_at: i64) -> Result<()> {
+    let clock = Clock::get()?;
+    if !ctx.accounts.manager_member.role.can_manage() {
+        return Err(GameError::NotGuildManager.into());
+    }
+    if starts_at < clock.unix_timestamp || ends_at <= starts_at {
+        return Err(GameError::WarNotActive.into());
+    }
+    if ctx.accounts.challenger.key() == ctx.accounts.defender.key() {
+        return Err(GameError::InvalidGuildConfig.into());
+    }
+    if ctx.accounts.challenger.active_war.is_some() || ctx.accounts.defender.active_war.is_some() {
+        return Err(GameError::WarInProgress.into());
+    }
+
+    let config = &mut ctx.accounts.guild_config;
+    let war = &mut ctx.accounts.clan_war;
+    war.war_id = config.war_count;
+    war.challenger = ctx.accounts.challenger.key();
+    war.defender = ctx.accounts.defender.key();
+    war.stake = stake;
+    war.prize_pool = stake;
+    war.starts_at = starts_at;
+    war.ends_at = ends_at;
+    war.challenger_wins = 0;
+    war.defender_wins = 0;
+    war.status = WarStatus::Declared;
+    war.winner = None;
+    war.bump = ctx.bumps.clan_war;
+    config.war_count = config.war_count.checked_add(1).ok_or(GameError::ArithmeticOverflow)?;
+
+    let war_info = war.to_account_info();
+    let challenger = &mut ctx.accounts.challenger;
+    stake_from_treasury(challenger, &war_info, stake)?;
+    challenger.active_war = Some(war.key());
+
+    emit!(ClanWarDeclared {
+        war: war.key(),
+        challenger: war.challenger,
+        defender: war.defender,
+        stake,
+        starts_at,
+        ends_at,
+    });
+
+    Ok(())
+}
+
+/// Defender matches the stake; the war counts wins once `starts_at` passes
+pub fn accept_war(ctx: Context<crate::RespondToWar>) -> Result<()> {
+    if !ctx.accounts.manager_member.role.can_manage() {
+        return Err(GameError::NotGuildManager.into());
+    }
+    let war = &mut ctx.accounts.clan_war;
+    if war.status != WarStatus::Declared || Clock::get()?.unix_timestamp >= war.ends_at {
+        return Err(GameError::WarNotActive.into());
+    }
+    let defender = &mut ctx.accounts.defender;
+    if defender.active_war.is_some() {
+        return Err(GameError::WarInProgress.into());
+    }
+
+    stake_from_treasury(defender, &war.to_account_info(), war.stake)?;
+    defender.active_war = Some(war.key());
+    war.prize_pool = war.prize_pool.checked_add(war.stake).ok_or(GameError::ArithmeticOverflow)?;
+    war.status = WarStatus::Active;
+
+    Ok(())
+}
+
+/// Withdraw an unaccepted challenge, refunding the challenger's stake
+pub fn cancel_war(ctx: Context<crate::CancelWar>) -> Result<()> {
+    if !ctx.accounts.manager_member.role.can_manage() {
+        return Err(GameError::NotGuildManager.into());
+    }
+    let war = &mut ctx.accounts.clan_war;
+    if war.status != WarStatus::Declared {
+        return Err(GameError::WarNotActive.into());
+    }
+
+    let refund = war.prize_pool;
+    war.prize_pool = 0;
+    war.status = WarStatus::Cancelled;
+    pay_to_treasury(&war.to_account_info(), &mut ctx.accounts.challenger, refund)?;
+
+    Ok(())
+}
+
+/// Count a member's match win toward their guild's war score. Signed by the
+/// game authority when it settles a match. Members who joined after the war
+/// started do not count, so guilds cannot recruit mid-war.
+pub fn record_war_win(ctx: Context<crate::RecordWarWin>) -> Result<()> {
+    let war = &mut ctx.accounts.clan_war;
+    let member = &mut ctx.accounts.guild_member;
+    let now = Clock::get()?.unix_timestamp;
+
+    if war.status != WarStatus::Active || !war.is_in_window(now) || member.joined_at > war.starts_at {
+        return Err(GameError::WarNotActive.into());
+    }
+
+    match war.side_of(&member.guild) {
+        Some(true) => war.challenger_wins = war.challenger_wins.saturating_add(1),
+        Some(false) => war.defender_wins = war.defender_wins.saturating_add(1),
+        None => return Err(GameError::WarNotActive.into()),
+    }
+    member.war_wins = member.war_wins.saturating_add(1);
+
+    Ok(())
+}
+
+/// Pay out after the window closes: the guild with more member wins takes the
+/// pool, a draw refunds both stakes. Anyone can crank this.
+pub fn settle_war(ctx: Context<crate::SettleWar>) -> Result<()> {
+    let war = &mut ctx.accounts.clan_war;
+    if war.status != WarStatus::Active || Clock::get()?.unix_timestamp < war.ends_at {
+        return Err(GameError::WarNotActive.into());
+    }
+
+    let war_info = war.to_account_info();
+    let challenger = &mut ctx.accounts.challenger;
+    let defender = &mut ctx.accounts.defender;
+    let prize_pool = war.prize_pool;
+
+    if war.challenger_wins > war.defender_wins {
+        pay_to_treasury(&war_info, challenger, prize_pool)?;
+        defender.active_war = None;
+        challenger.wars_won += 1;
+        defender.wars_lost += 1;
+        war.winner = Some(challenger.key());
+    } else if war.defender_wins > war.challenger_wins {
+        pay_to_treasury(&war_info, defender, prize_pool)?;
+        challenger.active_war = None;
+        defender.wars_won += 1;
+        challenger.wars_lost += 1;
+        war.winner = Some(defender.key());
+    } else {
+        let half = prize_pool / 2;
+        pay_to_treasury(&war_info, challenger, prize_pool - half)?;
+        pay_to_treasury(&war_info, defender, half)?;
+    }
+
+    war.prize_pool = 0;
+    war.status = WarStatus::Settled;
+
+    emit!(ClanWarSettled {
+        war: war.key(),
+        winner: war.winner,
+        challenger_wins: war.challenger_wins,
+        defender_wins: war.defender_wins,
+        prize_pool,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ClanWarDeclared {
+    pub war: Pubkey,
+    pub challenger: Pubkey,
+    pub defender: Pubkey,
+    pub stake: u64,
+    pub starts_at: i64,
+    pub ends_at: i64,
+}
+
+#[event]
+pub struct ClanWarSettled {
+    pub war: Pubkey,
+    pub winner: Option<Pubkey>,
+    pub challenger_wins: u32,
+    pub defender_wins: u32,
+    pub prize_pool: u64,
+}
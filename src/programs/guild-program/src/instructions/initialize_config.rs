@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+use shared::GameError;
+use crate::state::GuildConfig;
+
+pub fn handler(ctx: Context<crate::InitializeGuildConfig>, game_authority: Pubkey, rake_share_bps: u16) -> Result<()> {
+    if !GuildConfig::is_valid_rake_share(rake_share_bps) {
+        return Err(GameError::InvalidGuildConfig.into());
+    }
+    
+    let config = &mut ctx.accounts.guild_config;
+    config.admin = ctx.accounts.admin.key();
+    config.game_authority = game_authority;
+    config.rake_share_bps = rake_share_bps;
+    config.guild_count = 0;
+    config.war_count = 0;
+    config.bump = ctx.bumps.guild_config;
+    
+    Ok(())
+}
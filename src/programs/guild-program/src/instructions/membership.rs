@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+use shared::GameError;
+use crate::state::{GuildRole, DEFAULT_MAX_MEMBERS, MAX_GUILD_NAME_LENGTH};
+
+/// Create a guild with the caller as its leader and first member
+pub fn create_guild(ctx: Context<crate::CreateGuild>, name: String) -> Result<()> {
+    if name.trim().is_empty() || name.len() > MAX_GUILD_NAME_LENGTH {
+        return Err(GameError::InvalidGuildConfig.into());
+    }
+
+    let config = &mut ctx.accounts.guild_config;
+    let guild = &mut ctx.accounts.guild;
+    let clock = Clock::get()?;
+
+    guild.guild_id = config.guild_count;
+    guild.name = name.clone();
+    guild.leader = ctx.accounts.leader.key();
+    guild.member_count = 1;
+    guild.max_members = DEFAULT_MAX_MEMBERS;
+    guild.treasury_balance = 0;
+    guild.total_contributions = 0;
+    guild.total_rake = 0;
+    guild.wars_won = 0;
+    guild.wars_lost = 0;
+    guild.active_war = None;
+    guild.created_at = clock.unix_timestamp;
+    guild.bump = ctx.bumps.guild;
+
+    let member = &mut ctx.accounts.guild_member;
+    member.guild = guild.key();
+    member.member = ctx.accounts.leader.key();
+    member.role = GuildRole::Leader;
+    member.joined_at = clock.unix_timestamp;
+    member.contributed = 0;
+    member.rake_contributed = 0;
+    member.war_wins = 0;
+    member.bump = ctx.bumps.guild_member;
+
+    config.guild_count = config.guild_count
+        .checked_add(1)
+        .ok_or(GameError::ArithmeticOverflow)?;
+
+    emit!(GuildCreated {
+        guild: guild.key(),
+        guild_id: guild.guild_id,
+        name,
+        leader: guild.leader,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn join_guild(ctx: Context<crate::JoinGuild>) -> Result<()> {
+    let guild = &mut ctx.accounts.guild;
+    if guild.is_full() {
+        return Err(GameError::GuildFull.into());
+    }
+
+    let clock = Clock::get()?;
+    let member = &mut ctx.accounts.guild_member;
+    member.guild = guild.key();
+    member.member = ctx.accounts.member.key();
+    member.role = GuildRole::Member;
+    member.joined_at = clock.unix_timestamp;
+    member.contributed = 0;
+    member.rake_contributed = 0;
+    member.war_wins = 0;
+    member.bump = ctx.bumps.guild_member;
+
+    guild.member_count += 1;
+
+    emit!(GuildMembershipChanged {
+        guild: guild.key(),
+        member: member.member,
+        role: Some(GuildRole::Member),
+        member_count: guild.member_count,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Leave the guild, closing the membership record. Contributions stay in the treasury.
+pub fn leave_guild(ctx: Context<crate::LeaveGuild>) -> Result<()> {
+    if ctx.accounts.guild_member.role == GuildRole::Leader {
+        return Err(GameError::LeaderCannotLeave.into());
+    }
+
+    let guild = &mut ctx.accounts.guild;
+    guild.member_count = guild.member_count.saturating_sub(1);
+
+    emit!(GuildMembershipChanged {
+        guild: guild.key(),
+        member: ctx.accounts.member.key(),
+        role: None,
+        member_count: guild.member_count,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Promote or demote a member - leader only. Granting Leader hands over
+/// leadership and demotes the current leader to Officer.
+pub fn set_member_role(ctx: Context<crate::SetMemberRole>, role: GuildRole) -> Result<()> {
+    let guild = &mut ctx.accounts.guild;
+    let target = &mut ctx.accounts.target_member;
+
+    if target.member == guild.leader {
+        return Err(GameError::LeaderCannotLeave.into());
+    }
+
+    target.role = role;
+    if role == GuildRole::Leader {
+        ctx.accounts.leader_member.role = GuildRole::Officer;
+        guild.leader = target.member;
+    }
+
+    emit!(GuildMembershipChanged {
+        guild: guild.key(),
+        member: target.member,
+        role: Some(role),
+        member_count: guild.member_count,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct GuildCreated {
+    pub guild: Pubkey,
+    pub guild_id: u64,
+    pub name: String,
+    pub leader: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GuildMembershipChanged {
+    pub guild: Pubkey,
+    pub member: Pubkey,
+    pub role: Option<GuildRole>, // None when the member left
+    pub member_count: u16,
+    pub timestamp: i64,
+}
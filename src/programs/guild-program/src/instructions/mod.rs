@@ -0,0 +1,9 @@
+pub mod initialize_config;
+pub mod membership;
+pub mod treasury;
+pub mod clan_war;
+
+pub use initialize_config::*;
+pub use membership::*;
+pub use treasury::*;
+pub use clan_war::*;
@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use shared::GameError;
+
+/// Member donation to the guild treasury
+pub fn contribute(ctx: Context<crate::Contribute>, amount: u64) -> Result<()> {
+    if amount == 0 {
+        return Err(GameError::InsufficientFunds.into());
+    }
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.member.to_account_info(),
+                to: ctx.accounts.guild.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    let guild = &mut ctx.accounts.guild;
+    let member = &mut ctx.accounts.guild_member;
+    guild.treasury_balance = guild.treasury_balance.checked_add(amount).ok_or(GameError::ArithmeticOverflow)?;
+    guild.total_contributions = guild.total_contributions.checked_add(amount).ok_or(GameError::ArithmeticOverflow)?;
+    member.contributed = member.contributed.checked_add(amount).ok_or(GameError::ArithmeticOverflow)?;
+
+    emit!(TreasuryDeposited {
+        guild: guild.key(),
+        member: member.member,
+        amount,
+        from_rake: false,
+        treasury_balance: guild.treasury_balance,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Route the guild's slice of a member's rake into the treasury. Called through
+/// CPI from strategic-duel settlement, which parks the slice on its rake PDA
+/// first; `rake` is the member's full rake and only the configured share is
+/// transferred.
+pub fn deposit_rake(ctx: Context<crate::DepositRake>, rake: u64) -> Result<()> {
+    let share = ctx.accounts.guild_config.rake_share(rake);
+    if share == 0 {
+        return Ok(());
+    }
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.rake_authority.to_account_info(),
+                to: ctx.accounts.guild.to_account_info(),
+            },
+        ),
+        share,
+    )?;
+
+    let guild = &mut ctx.accounts.guild;
+    let member = &mut ctx.accounts.guild_member;
+    guild.treasury_balance = guild.treasury_balance.checked_add(share).ok_or(GameError::ArithmeticOverflow)?;
+    guild.total_rake = guild.total_rake.checked_add(share).ok_or(GameError::ArithmeticOverflow)?;
+    member.rake_contributed = member.rake_contributed.checked_add(share).ok_or(GameError::ArithmeticOverflow)?;
+
+    emit!(TreasuryDeposited {
+        guild: guild.key(),
+        member: member.member,
+        amount: share,
+        from_rake: true,
+        treasury_balance: guild.treasury_balance,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Spend from the treasury - officers and leader only
+pub fn withdraw_treasury(ctx: Context<crate::WithdrawTreasury>, amount: u64) -> Result<()> {
+    if !ctx.accounts.manager_member.role.can_manage() {
+        return Err(GameError::NotGuildManager.into());
+    }
+
+    let guild = &mut ctx.accounts.guild;
+    if amount > guild.treasury_balance {
+        return Err(GameError::InsufficientTreasury.into());
+    }
+    guild.treasury_balance -= amount;
+
+    // Guild is program-owned, so lamports move directly
+    **guild.to_account_info().try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.recipient.try_borrow_mut_lamports()? += amount;
+
+    emit!(TreasuryWithdrawn {
+        guild: guild.key(),
+        manager: ctx.accounts.manager.key(),
+        recipient: ctx.accounts.recipient.key(),
+        amount,
+        treasury_balance: guild.treasury_balance,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct TreasuryDeposited {
+    pub guild: Pubkey,
+    pub member: Pubkey,
+    pub amount: u64,
+    pub from_rake: bool,
+    pub treasury_balance: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasuryWithdrawn {
+    pub guild: Pubkey,
+    pub manager: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub treasury_balance: u64,
+    pub timestamp: i64,
+}
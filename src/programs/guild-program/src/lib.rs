@@ -0,0 +1,449 @@
+use anchor_lang::prelude::*;
+
+pub mod instructions;
+pub mod state;
+
+pub use instructions::*;
+pub use state::*;
+
+use shared::GameError;
+
+declare_id!("GLDxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx");
+
+#[program]
+pub mod sol_duel_guild {
+    use super::*;
+
+    /// Initialize guild settings: the game authority and the rake share paid to guilds (upgrade authority only)
+    pub fn initialize_guild_config(
+        ctx: Context<InitializeGuildConfig>,
+        game_authority: Pubkey,
+        rake_share_bps: u16,
+    ) -> Result<()> {
+        instructions::initialize_config::handler(ctx, game_authority, rake_share_bps)
+    }
+
+    /// Create a guild led by the caller
+    pub fn create_guild(ctx: Context<CreateGuild>, name: String) -> Result<()> {
+        instructions::membership::create_guild(ctx, name)
+    }
+
+    /// Join a guild as a member
+    pub fn join_guild(ctx: Context<JoinGuild>) -> Result<()> {
+        instructions::membership::join_guild(ctx)
+    }
+
+    /// Leave the current guild
+    pub fn leave_guild(ctx: Context<LeaveGuild>) -> Result<()> {
+        instructions::membership::leave_guild(ctx)
+    }
+
+    /// Change a member's role - leader only
+    pub fn set_member_role(ctx: Context<SetMemberRole>, role: GuildRole) -> Result<()> {
+        instructions::membership::set_member_role(ctx, role)
+    }
+
+    /// Contribute lamports to the guild treasury
+    pub fn contribute(ctx: Context<Contribute>, amount: u64) -> Result<()> {
+        instructions::treasury::contribute(ctx, amount)
+    }
+
+    /// Deposit the guild's share of a member's rake - strategic-duel settlement only
+    pub fn deposit_rake(ctx: Context<DepositRake>, rake: u64) -> Result<()> {
+        instructions::treasury::deposit_rake(ctx, rake)
+    }
+
+    /// Withdraw from the guild treasury - officers and leader only
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+        instructions::treasury::withdraw_treasury(ctx, amount)
+    }
+
+    /// Challenge another guild to a clan war
+    pub fn declare_war(ctx: Context<DeclareWar>, stake: u64, starts_at: i64, ends_at: i64) -> Result<()> {
+        instructions::clan_war::declare_war(ctx, stake, starts_at, ends_at)
+    }
+
+    /// Accept a clan war challenge
+    pub fn accept_war(ctx: Context<RespondToWar>) -> Result<()> {
+        instructions::clan_war::accept_war(ctx)
+    }
+
+    /// Cancel an unaccepted clan war challenge
+    pub fn cancel_war(ctx: Context<CancelWar>) -> Result<()> {
+        instructions::clan_war::cancel_war(ctx)
+    }
+
+    /// Record a member's match win during a clan war - game authority only
+    pub fn record_war_win(ctx: Context<RecordWarWin>) -> Result<()> {
+        instructions::clan_war::record_war_win(ctx)
+    }
+
+    /// Settle a finished clan war
+    pub fn settle_war(ctx: Context<SettleWar>) -> Result<()> {
+        instructions::clan_war::settle_war(ctx)
+    }
+}
+
+/// Create the global guild config; the program's upgrade authority becomes its admin
+#[derive(Accounts)]
+pub struct InitializeGuildConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = GuildConfig::LEN,
+        seeds = [b"guild_config"],
+        bump
+    )]
+    pub guild_config: Account<'info, GuildConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::SolDuelGuild>,
+    
+    #[account(constraint = program_data.upgrade_authority_address == Some(admin.key()) @ GameError::AccessDenied)]
+    pub program_data: Account<'info, ProgramData>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateGuild<'info> {
+    #[account(
+        mut,
+        seeds = [b"guild_config"],
+        bump = guild_config.bump
+    )]
+    pub guild_config: Account<'info, GuildConfig>,
+    
+    #[account(
+        init,
+        payer = leader,
+        space = Guild::LEN,
+        seeds = [b"guild", &guild_config.guild_count.to_le_bytes()],
+        bump
+    )]
+    pub guild: Account<'info, Guild>,
+    
+    #[account(
+        init,
+        payer = leader,
+        space = GuildMember::LEN,
+        seeds = [b"guild_member", leader.key().as_ref()],
+        bump
+    )]
+    pub guild_member: Account<'info, GuildMember>,
+    
+    #[account(mut)]
+    pub leader: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct JoinGuild<'info> {
+    #[account(
+        mut,
+        seeds = [b"guild", &guild.guild_id.to_le_bytes()],
+        bump = guild.bump
+    )]
+    pub guild: Account<'info, Guild>,
+    
+    #[account(
+        init,
+        payer = member,
+        space = GuildMember::LEN,
+        seeds = [b"guild_member", member.key().as_ref()],
+        bump
+    )]
+    pub guild_member: Account<'info, GuildMember>,
+    
+    #[account(mut)]
+    pub member: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct LeaveGuild<'info> {
+    #[account(
+        mut,
+        seeds = [b"guild", &guild.guild_id.to_le_bytes()],
+        bump = guild.bump
+    )]
+    pub guild: Account<'info, Guild>,
+    
+    #[account(
+        mut,
+        close = member,
+        seeds = [b"guild_member", member.key().as_ref()],
+        bump = guild_member.bump,
+        constraint = guild_member.guild == guild.key() @ GameError::AccessDenied
+    )]
+    pub guild_member: Account<'info, GuildMember>,
+    
+    #[account(mut)]
+    pub member: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetMemberRole<'info> {
+    #[account(
+        mut,
+        seeds = [b"guild", &guild.guild_id.to_le_bytes()],
+        bump = guild.bump,
+        constraint = guild.leader == leader.key() @ GameError::NotGuildManager
+    )]
+    pub guild: Account<'info, Guild>,
+    
+    #[account(
+        mut,
+        seeds = [b"guild_member", leader.key().as_ref()],
+        bump = leader_member.bump
+    )]
+    pub leader_member: Account<'info, GuildMember>,
+    
+    #[account(
+        mut,
+        seeds = [b"guild_member", target_member.member.as_ref()],
+        bump = target_member.bump,
+        constraint = target_member.guild == guild.key() @ GameError::AccessDenied
+    )]
+    pub target_member: Account<'info, GuildMember>,
+    
+    pub leader: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Contribute<'info> {
+    #[account(
+        mut,
+        seeds = [b"guild", &guild.guild_id.to_le_bytes()],
+        bump = guild.bump
+    )]
+    pub guild: Account<'info, Guild>,
+    
+    #[account(
+        mut,
+        seeds = [b"guild_member", member.key().as_ref()],
+        bump = guild_member.bump,
+        constraint = guild_member.guild == guild.key() @ GameError::AccessDenied
+    )]
+    pub guild_member: Account<'info, GuildMember>,
+    
+    #[account(mut)]
+    pub member: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DepositRake<'info> {
+    #[account(
+        seeds = [b"guild_config"],
+        bump = guild_config.bump
+    )]
+    pub guild_config: Account<'info, GuildConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"guild", &guild.guild_id.to_le_bytes()],
+        bump = guild.bump
+    )]
+    pub guild: Account<'info, Guild>,
+    
+    #[account(
+        mut,
+        seeds = [b"guild_member", guild_member.member.as_ref()],
+        bump = guild_member.bump,
+        constraint = guild_member.guild == guild.key() @ GameError::AccessDenied
+    )]
+    pub guild_member: Account<'info, GuildMember>,
+    
+    /// strategic-duel's rake PDA, signing from settlement
+    #[account(
+        mut,
+        seeds = [shared::GUILD_RAKE_AUTHORITY_SEED],
+        bump,
+        seeds::program = shared::STRATEGIC_DUEL_PROGRAM_ID
+    )]
+    pub rake_authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    #[account(
+        mut,
+        seeds = [b"guild", &guild.guild_id.to_le_bytes()],
+        bump = guild.bump
+    )]
+    pub guild: Account<'info, Guild>,
+    
+    #[account(
+        seeds = [b"guild_member", manager.key().as_ref()],
+        bump = manager_member.bump,
+        constraint = manager_member.guild == guild.key() @ GameError::AccessDenied
+    )]
+    pub manager_member: Account<'info, GuildMember>,
+    
+    pub manager: Signer<'info>,
+    
+    /// CHECK: Any wallet the guild chooses to pay
+    #[account(mut)]
+    pub recipient: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DeclareWar<'info> {
+    #[account(
+        mut,
+        seeds = [b"guild_config"],
+        bump = guild_config.bump
+    )]
+    pub guild_config: Account<'info, GuildConfig>,
+    
+    #[account(
+        init,
+        payer = manager,
+        space = ClanWar::LEN,
+        seeds = [b"clan_war", &guild_config.war_count.to_le_bytes()],
+        bump
+    )]
+    pub clan_war: Account<'info, ClanWar>,
+    
+    #[account(
+        mut,
+        seeds = [b"guild", &challenger.guild_id.to_le_bytes()],
+        bump = challenger.bump
+    )]
+    pub challenger: Account<'info, Guild>,
+    
+    #[account(
+        seeds = [b"guild", &defender.guild_id.to_le_bytes()],
+        bump = defender.bump
+    )]
+    pub defender: Account<'info, Guild>,
+    
+    #[account(
+        seeds = [b"guild_member", manager.key().as_ref()],
+        bump = manager_member.bump,
+        constraint = manager_member.guild == challenger.key() @ GameError::AccessDenied
+    )]
+    pub manager_member: Account<'info, GuildMember>,
+    
+    #[account(mut)]
+    pub manager: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RespondToWar<'info> {
+    #[account(
+        mut,
+        seeds = [b"clan_war", &clan_war.war_id.to_le_bytes()],
+        bump = clan_war.bump,
+        has_one = defender @ GameError::AccessDenied
+    )]
+    pub clan_war: Account<'info, ClanWar>,
+    
+    #[account(
+        mut,
+        seeds = [b"guild", &defender.guild_id.to_le_bytes()],
+        bump = defender.bump
+    )]
+    pub defender: Account<'info, Guild>,
+    
+    #[account(
+        seeds = [b"guild_member", manager.key().as_ref()],
+        bump = manager_member.bump,
+        constraint = manager_member.guild == defender.key() @ GameError::AccessDenied
+    )]
+    pub manager_member: Account<'info, GuildMember>,
+    
+    pub manager: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelWar<'info> {
+    #[account(
+        mut,
+        seeds = [b"clan_war", &clan_war.war_id.to_le_bytes()],
+        bump = clan_war.bump,
+        has_one = challenger @ GameError::AccessDenied
+    )]
+    pub clan_war: Account<'info, ClanWar>,
+    
+    #[account(
+        mut,
+        seeds = [b"guild", &challenger.guild_id.to_le_bytes()],
+        bump = challenger.bump
+    )]
+    pub challenger: Account<'info, Guild>,
+    
+    #[account(
+        seeds = [b"guild_member", manager.key().as_ref()],
+        bump = manager_member.bump,
+        constraint = manager_member.guild == challenger.key() @ GameError::AccessDenied
+    )]
+    pub manager_member: Account<'info, GuildMember>,
+    
+    pub manager: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RecordWarWin<'info> {
+    #[account(
+        seeds = [b"guild_config"],
+        bump = guild_config.bump,
+        constraint = guild_config.game_authority == game_authority.key() @ GameError::AccessDenied
+    )]
+    pub guild_config: Account<'info, GuildConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"clan_war", &clan_war.war_id.to_le_bytes()],
+        bump = clan_war.bump
+    )]
+    pub clan_war: Account<'info, ClanWar>,
+    
+    #[account(
+        mut,
+        seeds = [b"guild_member", guild_member.member.as_ref()],
+        bump = guild_member.bump
+    )]
+    pub guild_member: Account<'info, GuildMember>,
+    
+    pub game_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SettleWar<'info> {
+    #[account(
+        mut,
+        seeds = [b"clan_war", &clan_war.war_id.to_le_bytes()],
+        bump = clan_war.bump,
+        has_one = challenger @ GameError::AccessDenied,
+        has_one = defender @ GameError::AccessDenied
+    )]
+    pub clan_war: Account<'info, ClanWar>,
+    
+    #[account(
+        mut,
+        seeds = [b"guild", &challenger.guild_id.to_le_bytes()],
+        bump = challenger.bump
+    )]
+    pub challenger: Account<'info, Guild>,
+    
+    #[account(
+        mut,
+        seeds = [b"guild", &defender.guild_id.to_le_bytes()],
+        bump = defender.bump
+    )]
+    pub defender: Account<'info, Guild>,
+    
+    pub cranker: Signer<'info>,
+}
@@ -0,0 +1,200 @@
+use anchor_lang::prelude::*;
+
+pub const MAX_GUILD_NAME_LENGTH: usize = 32;
+pub const DEFAULT_MAX_MEMBERS: u16 = 50;
+
+/// Upper bound on the share of member rake routed to guild treasuries
+pub const MAX_RAKE_SHARE_BPS: u16 = 5_000;
+
+#[account]
+pub struct GuildConfig {
+    pub admin: Pubkey,
+    // Signs war results; rake deposits come from strategic-duel settlement instead
+    pub game_authority: Pubkey,
+    pub rake_share_bps: u16,
+    pub guild_count: u64,
+    pub war_count: u64,
+    pub bump: u8,
+}
+
+impl GuildConfig {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // admin
+        32 + // game_authority
+        2 + // rake_share_bps
+        8 + // guild_count
+        8 + // war_count
+        1; // bump
+
+    pub fn is_valid_rake_share(rake_share_bps: u16) -> bool {
+        rake_share_bps <= MAX_RAKE_SHARE_BPS
+    }
+
+    pub fn rake_share(&self, rake: u64) -> u64 {
+        (rake as u128 * self.rake_share_bps as u128 / 10_000) as u64
+    }
+}
+
+/// Guild account. Treasury lamports are held on the account itself above rent.
+#[account]
+pub struct Guild {
+    pub guild_id: u64,
+    pub name: String,
+    pub leader: Pubkey,
+    pub member_count: u16,
+    pub max_members: u16,
+    pub treasury_balance: u64,
+    pub total_contributions: u64,
+    pub total_rake: u64,
+    pub wars_won: u32,
+    pub wars_lost: u32,
+    pub active_war: Option<Pubkey>,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl Guild {
+    pub const LEN: usize = 8 + // discriminator
+        8 + // guild_id
+        4 + MAX_GUILD_NAME_LENGTH + // name
+        32 + // leader
+        2 + // member_count
+        2 + // max_members
+        8 + // treasury_balance
+        8 + // total_contributions
+        8 + // total_rake
+        4 + // wars_won
+        4 + // wars_lost
+        1 + 32 + // active_war
+        8 + // created_at
+        1; // bump
+
+    pub fn is_full(&self) -> bool {
+        self.member_count >= self.max_members
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GuildRole {
+    Member,
+    Officer,
+    Leader,
+}
+
+impl GuildRole {
+    /// Officers and the leader can spend the treasury and run wars
+    pub fn can_manage(&self) -> bool {
+        matches!(self, GuildRole::Officer | GuildRole::Leader)
+    }
+}
+
+/// Membership record; one per player, so a player belongs to at most one guild
+#[account]
+pub struct GuildMember {
+    pub guild: Pubkey,
+    pub member: Pubkey,
+    pub role: GuildRole,
+    pub joined_at: i64,
+    pub contributed: u64,
+    pub rake_contributed: u64,
+    pub war_wins: u32,
+    pub bump: u8,
+}
+
+impl GuildMember {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // guild
+        32 + // member
+        1 + // role
+        8 + // joined_at
+        8 + // contributed
+        8 + // rake_contributed
+        4 + // war_wins
+        1; // bump
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WarStatus {
+    Declared,
+    Active,
+    Settled,
+    Cancelled,
+}
+
+/// Clan war between two guilds. Both sides stake from their treasuries; member
+/// wins recorded inside the window are summed and the side with more takes the pool.
+#[account]
+pub struct ClanWar {
+    pub war_id: u64,
+    pub challenger: Pubkey,
+    pub defender: Pubkey,
+    pub stake: u64, // Per side
+    pub prize_pool: u64,
+    pub starts_at: i64,
+    pub ends_at: i64,
+    pub challenger_wins: u32,
+    pub defender_wins: u32,
+    pub status: WarStatus,
+    pub winner: Option<Pubkey>,
+    pub bump: u8,
+}
+
+impl ClanWar {
+    pub const LEN: usize = 8 + // discriminator
+        8 + // war_id
+        32 + // challenger
+        32 + // defender
+        8 + // stake
+        8 + // prize_pool
+        8 + // starts_at
+        8 + // ends_at
+        4 + // challenger_wins
+        4 + // defender_wins
+        1 + // status
+        1 + 32 + // winner
+        1; // bump
+
+    pub fn is_in_window(&self, now: i64) -> bool {
+        now >= self.starts_at && now < self.ends_at
+    }
+
+    pub fn side_of(&self, guild: &Pubkey) -> Option<bool> {
+        if *guild == self.challenger {
+            Some(true)
+        } else if *guild == self.defender {
+            Some(false)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(rake_share_bps: u16) -> GuildConfig {
+        GuildConfig {
+            admin: Pubkey::new_unique(),
+            game_authority: Pubkey::new_unique(),
+            rake_share_bps,
+            guild_count: 0,
+            war_count: 0,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_rake_share_bounds() {
+        assert!(GuildConfig::is_valid_rake_share(MAX_RAKE_SHARE_BPS));
+        assert!(!GuildConfig::is_valid_rake_share(MAX_RAKE_SHARE_BPS + 1));
+    }
+
+    #[test]
+    fn test_rake_share() {
+        assert_eq!(config(1_000).rake_share(50_000), 5_000);
+        assert_eq!(config(0).rake_share(50_000), 0);
+        // Large rakes don't overflow
+        assert_eq!(config(MAX_RAKE_SHARE_BPS).rake_share(u64::MAX), u64::MAX / 2);
+    }
+}
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, MintTo};
+use crate::state::Rarity;
+use crate::shared::{CosmeticType, GameError};
+
+pub fn handler(
+    ctx: Context<crate::CreateCosmeticNft>,
+    name: String,
+    cosmetic_type: CosmeticType,
+    rarity: Rarity,
+) -> Result<()> {
+    if name.len() > 64 {
+        return Err(GameError::InvalidNftMetadata.into());
+    }
+    
+    let cosmetic_nft = &mut ctx.accounts.cosmetic_nft;
+    let clock = Clock::get()?;
+    
+    cosmetic_nft.mint = ctx.accounts.nft_mint.key();
+    cosmetic_nft.cosmetic_type = cosmetic_type;
+    cosmetic_nft.name = name.clone();
+    cosmetic_nft.rarity = rarity;
+    cosmetic_nft.created_at = clock.unix_timestamp;
+    cosmetic_nft.bump = ctx.bumps.cosmetic_nft;
+    
+    // Mint the cosmetic to the recipient
+    let nft_authority_bump = ctx.bumps.nft_authority;
+    let signer_seeds = &[
+        b"nft_authority".as_ref(),
+        &[nft_authority_bump],
+    ];
+    
+    token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.nft_mint.to_account_info(),
+                to: ctx.accounts.recipient_token_account.to_account_info(),
+                authority: ctx.accounts.nft_authority.to_account_info(),
+            },
+            &[signer_seeds],
+        ),
+        1,
+    )?;
+    
+    ctx.accounts.collection.items_minted += 1;
+    
+    // A gem purchase redeems its receipt so it pays for only this cosmetic
+    if let Some(gem_receipt) = &ctx.accounts.gem_receipt {
+        let redemption = ctx.accounts.gem_redemption.as_mut().ok_or(GameError::AccessDenied)?;
+        redemption.receipt = gem_receipt.key();
+        redemption.redeemed_at = clock.unix_timestamp;
+        redemption.bump = ctx.bumps.gem_redemption.ok_or(GameError::AccessDenied)?;
+    }
+    
+    emit!(CosmeticMinted {
+        mint: cosmetic_nft.mint,
+        recipient: ctx.accounts.recipient.key(),
+        cosmetic_type,
+        rarity,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Cosmetic '{}' ({:?}) minted: {}", name, cosmetic_type, cosmetic_nft.mint);
+    
+    Ok(())
+}
+
+#[event]
+pub struct CosmeticMinted {
+    pub mint: Pubkey,
+    pub recipient: Pubkey,
+    pub cosmetic_type: CosmeticType,
+    pub rarity: Rarity,
+    pub timestamp: i64,
+}
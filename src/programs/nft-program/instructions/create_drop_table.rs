@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::state::{DropEntry, DropTable};
+use crate::shared::GameError;
+
+pub fn handler(
+    ctx: Context<crate::CreateDropTable>,
+    table_id: u32,
+    price: u64,
+    vrf_authority: Pubkey,
+    entries: Vec<DropEntry>,
+) -> Result<()> {
+    if price == 0 || !DropTable::validate_entries(&entries) {
+        return Err(GameError::InvalidDropTable.into());
+    }
+    
+    let drop_table = &mut ctx.accounts.drop_table;
+    drop_table.authority = ctx.accounts.authority.key();
+    drop_table.table_id = table_id;
+    drop_table.price = price;
+    drop_table.payment_mint = ctx.accounts.payment_mint.key();
+    drop_table.treasury = ctx.accounts.treasury.key();
+    drop_table.vrf_authority = vrf_authority;
+    drop_table.entries = entries;
+    drop_table.crates_sold = 0;
+    drop_table.crates_opened = 0;
+    drop_table.is_active = true;
+    drop_table.bump = ctx.bumps.drop_table;
+    
+    emit!(DropTableCreated {
+        drop_table: drop_table.key(),
+        table_id,
+        price,
+        weights_bps: drop_table.entries.iter().map(|entry| entry.weight_bps).collect(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct DropTableCreated {
+    pub drop_table: Pubkey,
+    pub table_id: u32,
+    pub price: u64,
+    pub weights_bps: Vec<u16>,
+    pub timestamp: i64,
+}
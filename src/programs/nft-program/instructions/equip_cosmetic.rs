@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::shared::CosmeticType;
+
+pub fn handler(ctx: Context<crate::EquipCosmetic>) -> Result<()> {
+    let loadout = &mut ctx.accounts.cosmetic_loadout;
+    let cosmetic_nft = &ctx.accounts.cosmetic_nft;
+    let clock = Clock::get()?;
+    
+    if loadout.player == Pubkey::default() {
+        loadout.player = ctx.accounts.player.key();
+        loadout.bump = ctx.bumps.cosmetic_loadout;
+    }
+    
+    let replaced = loadout.equip(cosmetic_nft.cosmetic_type, cosmetic_nft.mint);
+    loadout.updated_at = clock.unix_timestamp;
+    
+    emit!(CosmeticEquipped {
+        player: loadout.player,
+        mint: cosmetic_nft.mint,
+        cosmetic_type: cosmetic_nft.cosmetic_type,
+        replaced,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct CosmeticEquipped {
+    pub player: Pubkey,
+    pub mint: Pubkey,
+    pub cosmetic_type: CosmeticType,
+    pub replaced: Option<Pubkey>,
+    pub timestamp: i64,
+}
@@ -7,6 +7,14 @@ pub mod burn_nft;
 pub mod create_item_nft;
 pub mod equip_item;
 pub mod unequip_item;
+pub mod create_cosmetic_nft;
+pub mod equip_cosmetic;
+pub mod unequip_cosmetic;
+pub mod create_drop_table;
+pub mod purchase_crate;
+pub mod open_crate;
+pub mod tournament_ticket;
+pub mod program_info;
 
 pub use initialize_collection::*;
 pub use create_player_nft::*;
@@ -16,4 +24,12 @@ pub use transfer_nft::*;
 pub use burn_nft::*;
 pub use create_item_nft::*;
 pub use equip_item::*;
-pub use unequip_item::*;
\ No newline at end of file
+pub use unequip_item::*;
+pub use create_cosmetic_nft::*;
+pub use equip_cosmetic::*;
+pub use unequip_cosmetic::*;
+pub use create_drop_table::*;
+pub use purchase_crate::*;
+pub use open_crate::*;
+pub use tournament_ticket::*;
+pub use program_info::*;
\ No newline at end of file
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_spl::token::{self, MintTo};
+use crate::state::{CrateStatus, DROP_TABLE_TOTAL_BPS};
+use crate::shared::GameError;
+
+/// Fulfil a crate with the oracle's VRF output. The proof is the table oracle's
+/// ed25519 signature over the seed fixed at purchase, verified by the preceding
+/// ed25519 instruction; ed25519 signing is deterministic, so each seed has one
+/// proof and one roll. The seed, proof and roll are emitted so anyone can
+/// recompute which drop table entry was selected.
+pub fn handler(
+    ctx: Context<crate::OpenCrate>,
+    vrf_proof: [u8; 64],
+) -> Result<()> {
+    let receipt = &mut ctx.accounts.crate_receipt;
+    if receipt.status != CrateStatus::Pending {
+        return Err(GameError::CrateAlreadyOpened.into());
+    }
+    
+    let signature = crate::shared::preceding_ed25519_signature(
+        &ctx.accounts.instructions,
+        &ctx.accounts.drop_table.vrf_authority,
+        &receipt.vrf_seed,
+    )?;
+    require!(signature == vrf_proof, GameError::InvalidSignature);
+    
+    let randomness = hashv(&[&receipt.vrf_seed, &vrf_proof]).to_bytes();
+    let roll = (u64::from_le_bytes(randomness[0..8].try_into().unwrap()) % DROP_TABLE_TOTAL_BPS as u64) as u16;
+    
+    let drop_table = &mut ctx.accounts.drop_table;
+    let entry = drop_table.pick(roll).ok_or(GameError::InvalidDropTable)?.clone();
+    let clock = Clock::get()?;
+    
+    let item_nft = &mut ctx.accounts.item_nft;
+    item_nft.owner = receipt.owner;
+    item_nft.mint = ctx.accounts.nft_mint.key();
+    item_nft.item_type = entry.item_type;
+    item_nft.name = entry.name.clone();
+    item_nft.description = String::new();
+    item_nft.stats = entry.stats;
+    item_nft.rarity = entry.rarity;
+    item_nft.is_equipped = false;
+    item_nft.equipped_slot = None;
+    item_nft.durability = 100;
+    item_nft.max_durability = 100;
+    item_nft.created_at = clock.unix_timestamp;
+    item_nft.item_index = ctx.accounts.item_counter.claim(receipt.owner, ctx.bumps.item_counter)?;
+    item_nft.bump = ctx.bumps.item_nft;
+    
+    let nft_authority_bump = ctx.bumps.nft_authority;
+    let signer_seeds = &[
+        b"nft_authority".as_ref(),
+        &[nft_authority_bump],
+    ];
+    
+    token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.nft_mint.to_account_info(),
+                to: ctx.accounts.owner_token_account.to_account_info(),
+                authority: ctx.accounts.nft_authority.to_account_info(),
+            },
+            &[signer_seeds],
+        ),
+        1,
+    )?;
+    
+    receipt.status = CrateStatus::Opened;
+    receipt.item_mint = Some(item_nft.mint);
+    drop_table.crates_opened += 1;
+    
+    emit!(CrateOpened {
+        crate_receipt: receipt.key(),
+        owner: receipt.owner,
+        vrf_seed: receipt.vrf_seed,
+        vrf_proof,
+        randomness,
+        roll,
+        item_mint: item_nft.mint,
+        rarity: entry.rarity,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct CrateOpened {
+    pub crate_receipt: Pubkey,
+    pub owner: Pubkey,
+    pub vrf_seed: [u8; 32],
+    pub vrf_proof: [u8; 64],
+    pub randomness: [u8; 32],
+    pub roll: u16,
+    pub item_mint: Pubkey,
+    pub rarity: crate::state::Rarity,
+    pub timestamp: i64,
+}
@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_spl::token::{self, Transfer};
+use crate::state::{CrateReceipt, CrateStatus, DropTable};
+use crate::shared::GameError;
+
+pub fn handler(ctx: Context<crate::PurchaseCrate>) -> Result<()> {
+    let drop_table = &mut ctx.accounts.drop_table;
+    if !drop_table.is_active {
+        return Err(GameError::InvalidDropTable.into());
+    }
+    
+    // Pay in game tokens
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.buyer_token_account.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+                authority: ctx.accounts.buyer.to_account_info(),
+            },
+        ),
+        drop_table.price,
+    )?;
+    
+    let price = drop_table.price;
+    issue_crate(
+        drop_table,
+        &mut ctx.accounts.crate_receipt,
+        ctx.accounts.buyer.key(),
+        ctx.bumps.crate_receipt,
+        price,
+    )
+}
+
+/// Buy a crate with gems already burned in game-program, redeeming the receipt
+pub fn with_gems(ctx: Context<crate::PurchaseCrateWithGems>) -> Result<()> {
+    let drop_table = &mut ctx.accounts.drop_table;
+    if !drop_table.is_active {
+        return Err(GameError::InvalidDropTable.into());
+    }
+    
+    let gem_receipt = &ctx.accounts.gem_receipt;
+    let redemption = &mut ctx.accounts.gem_redemption;
+    redemption.receipt = gem_receipt.key();
+    redemption.redeemed_at = Clock::get()?.unix_timestamp;
+    redemption.bump = ctx.bumps.gem_redemption;
+    
+    // Gem purchases report no token price
+    issue_crate(
+        drop_table,
+        &mut ctx.accounts.crate_receipt,
+        ctx.accounts.buyer.key(),
+        ctx.bumps.crate_receipt,
+        0,
+    )
+}
+
+fn issue_crate(
+    drop_table: &mut Account<DropTable>,
+    receipt: &mut Account<CrateReceipt>,
+    buyer: Pubkey,
+    bump: u8,
+    price: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let crate_id = drop_table.crates_sold;
+    
+    receipt.owner = buyer;
+    receipt.drop_table = drop_table.key();
+    receipt.crate_id = crate_id;
+    // Seed is fixed at purchase, before the oracle is asked for randomness
+    receipt.vrf_seed = hashv(&[
+        b"crate",
+        drop_table.key().as_ref(),
+        receipt.owner.as_ref(),
+        &crate_id.to_le_bytes(),
+        &clock.slot.to_le_bytes(),
+    ]).to_bytes();
+    receipt.status = CrateStatus::Pending;
+    receipt.item_mint = None;
+    receipt.purchased_at = clock.unix_timestamp;
+    receipt.bump = bump;
+    
+    drop_table.crates_sold = drop_table.crates_sold
+        .checked_add(1)
+        .ok_or(GameError::ArithmeticOverflow)?;
+    
+    emit!(CratePurchased {
+        crate_receipt: receipt.key(),
+        drop_table: receipt.drop_table,
+        buyer: receipt.owner,
+        crate_id,
+        price,
+        vrf_seed: receipt.vrf_seed,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct CratePurchased {
+    pub crate_receipt: Pubkey,
+    pub drop_table: Pubkey,
+    pub buyer: Pubkey,
+    pub crate_id: u64,
+    pub price: u64,
+    pub vrf_seed: [u8; 32],
+    pub timestamp: i64,
+}
@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, MintTo, Transfer};
+use crate::shared::{TicketSource, GameError};
+
+/// Set the ticket price and quest issuer - collection authority only
+pub fn configure_tickets(ctx: Context<crate::ConfigureTickets>, price: u64, quest_issuer: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.ticket_config;
+    config.authority = ctx.accounts.authority.key();
+    config.price = price;
+    config.payment_mint = ctx.accounts.payment_mint.key();
+    config.treasury = ctx.accounts.treasury.key();
+    config.quest_issuer = quest_issuer;
+    config.bump = ctx.bumps.ticket_config;
+    Ok(())
+}
+
+/// Buy a tournament ticket with game tokens
+pub fn purchase_ticket(ctx: Context<crate::PurchaseTicket>, tournament: Option<Pubkey>) -> Result<()> {
+    let config = &mut ctx.accounts.ticket_config;
+    if config.price == 0 {
+        return Err(GameError::InvalidTicketConfig.into());
+    }
+    
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.buyer_token_account.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+                authority: ctx.accounts.buyer.to_account_info(),
+            },
+        ),
+        config.price,
+    )?;
+    config.tickets_sold = config.tickets_sold.checked_add(1).ok_or(GameError::ArithmeticOverflow)?;
+    
+    let buyer = ctx.accounts.buyer.key();
+    let ticket = &mut ctx.accounts.ticket;
+    ticket.mint = ctx.accounts.ticket_mint.key();
+    ticket.tournament = tournament;
+    ticket.source = TicketSource::Purchase;
+    ticket.issued_to = buyer;
+    ticket.issued_at = Clock::get()?.unix_timestamp;
+    ticket.bump = ctx.bumps.ticket;
+    
+    mint_ticket(
+        &ctx.accounts.token_program,
+        &ctx.accounts.ticket_mint,
+        &ctx.accounts.recipient_token_account,
+        &ctx.accounts.nft_authority,
+        ctx.bumps.nft_authority,
+    )?;
+    
+    emit!(TicketIssued {
+        mint: ticket.mint,
+        recipient: buyer,
+        tournament,
+        source: TicketSource::Purchase,
+        timestamp: ticket.issued_at,
+    });
+    
+    Ok(())
+}
+
+/// Grant a ticket won from a quest - quest issuer only
+pub fn grant_quest_ticket(ctx: Context<crate::GrantQuestTicket>, tournament: Option<Pubkey>) -> Result<()> {
+    let config = &mut ctx.accounts.ticket_config;
+    config.tickets_granted = config.tickets_granted.checked_add(1).ok_or(GameError::ArithmeticOverflow)?;
+    
+    let recipient = ctx.accounts.recipient.key();
+    let ticket = &mut ctx.accounts.ticket;
+    ticket.mint = ctx.accounts.ticket_mint.key();
+    ticket.tournament = tournament;
+    ticket.source = TicketSource::Quest;
+    ticket.issued_to = recipient;
+    ticket.issued_at = Clock::get()?.unix_timestamp;
+    ticket.bump = ctx.bumps.ticket;
+    
+    mint_ticket(
+        &ctx.accounts.token_program,
+        &ctx.accounts.ticket_mint,
+        &ctx.accounts.recipient_token_account,
+        &ctx.accounts.nft_authority,
+        ctx.bumps.nft_authority,
+    )?;
+    
+    emit!(TicketIssued {
+        mint: ticket.mint,
+        recipient,
+        tournament,
+        source: TicketSource::Quest,
+        timestamp: ticket.issued_at,
+    });
+    
+    Ok(())
+}
+
+fn mint_ticket<'info>(
+    token_program: &Program<'info, anchor_spl::token::Token>,
+    mint: &Account<'info, anchor_spl::token::Mint>,
+    to: &Account<'info, anchor_spl::token::TokenAccount>,
+    nft_authority: &UncheckedAccount<'info>,
+    nft_authority_bump: u8,
+) -> Result<()> {
+    let signer_seeds = &[
+        b"nft_authority".as_ref(),
+        &[nft_authority_bump],
+    ];
+    
+    token::mint_to(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            MintTo {
+                mint: mint.to_account_info(),
+                to: to.to_account_info(),
+                authority: nft_authority.to_account_info(),
+            },
+            &[signer_seeds],
+        ),
+        1,
+    )
+}
+
+#[event]
+pub struct TicketIssued {
+    pub mint: Pubkey,
+    pub recipient: Pubkey,
+    pub tournament: Option<Pubkey>,
+    pub source: TicketSource,
+    pub timestamp: i64,
+}
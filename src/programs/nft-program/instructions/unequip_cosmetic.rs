@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use crate::shared::{CosmeticType, GameError};
+
+pub fn handler(
+    ctx: Context<crate::UnequipCosmetic>,
+    cosmetic_type: CosmeticType,
+) -> Result<()> {
+    let loadout = &mut ctx.accounts.cosmetic_loadout;
+    let mint = loadout.unequip(cosmetic_type).ok_or(GameError::InvalidMove)?;
+    loadout.updated_at = Clock::get()?.unix_timestamp;
+    
+    emit!(CosmeticUnequipped {
+        player: loadout.player,
+        mint,
+        cosmetic_type,
+        timestamp: loadout.updated_at,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct CosmeticUnequipped {
+    pub player: Pubkey,
+    pub mint: Pubkey,
+    pub cosmetic_type: CosmeticType,
+    pub timestamp: i64,
+}
@@ -20,7 +20,7 @@ pub use instructions::*;
 pub use state::*;
 
 // Import shared modules
-use crate::shared::GameError;
+use crate::shared::{GameError, TournamentTicket, TOURNAMENT_TICKET_SEED, GemReceipt, GemSink, GEM_RECEIPT_SEED, GAME_PROGRAM_ID, is_owned_account};
 
 declare_id!("NFTExxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx");
 
@@ -111,6 +111,80 @@ pub mod sol_duel_nft {
     ) -> Result<()> {
         instructions::unequip_item::handler(ctx, item_slot)
     }
+
+    /// Mint a cosmetic NFT (skin, card back, table theme) - collection authority only
+    pub fn create_cosmetic_nft(
+        ctx: Context<CreateCosmeticNft>,
+        name: String,
+        cosmetic_type: crate::shared::CosmeticType,
+        rarity: Rarity,
+    ) -> Result<()> {
+        instructions::create_cosmetic_nft::handler(ctx, name, cosmetic_type, rarity)
+    }
+
+    /// Equip a held cosmetic into its loadout slot
+    pub fn equip_cosmetic(ctx: Context<EquipCosmetic>) -> Result<()> {
+        instructions::equip_cosmetic::handler(ctx)
+    }
+
+    /// Clear a cosmetic loadout slot
+    pub fn unequip_cosmetic(
+        ctx: Context<UnequipCosmetic>,
+        cosmetic_type: crate::shared::CosmeticType,
+    ) -> Result<()> {
+        instructions::unequip_cosmetic::handler(ctx, cosmetic_type)
+    }
+
+    /// Publish a crate drop table - collection authority only
+    pub fn create_drop_table(
+        ctx: Context<CreateDropTable>,
+        table_id: u32,
+        price: u64,
+        vrf_authority: Pubkey,
+        entries: Vec<DropEntry>,
+    ) -> Result<()> {
+        instructions::create_drop_table::handler(ctx, table_id, price, vrf_authority, entries)
+    }
+
+    /// Buy a crate with game tokens
+    pub fn purchase_crate(ctx: Context<PurchaseCrate>) -> Result<()> {
+        instructions::purchase_crate::handler(ctx)
+    }
+
+    /// Buy a crate with gems spent in the game program
+    pub fn purchase_crate_with_gems(ctx: Context<PurchaseCrateWithGems>) -> Result<()> {
+        instructions::purchase_crate::with_gems(ctx)
+    }
+
+    /// Open a purchased crate with VRF randomness and mint the rolled item
+    pub fn open_crate(ctx: Context<OpenCrate>, vrf_proof: [u8; 64]) -> Result<()> {
+        instructions::open_crate::handler(ctx, vrf_proof)
+    }
+
+    /// Set the tournament ticket price and quest issuer - collection authority only
+    pub fn configure_tickets(ctx: Context<ConfigureTickets>, price: u64, quest_issuer: Pubkey) -> Result<()> {
+        instructions::tournament_ticket::configure_tickets(ctx, price, quest_issuer)
+    }
+
+    /// Buy a tournament ticket NFT, optionally bound to one tournament
+    pub fn purchase_ticket(ctx: Context<PurchaseTicket>, tournament: Option<Pubkey>) -> Result<()> {
+        instructions::tournament_ticket::purchase_ticket(ctx, tournament)
+    }
+
+    /// Grant a tournament ticket NFT won from a quest
+    pub fn grant_quest_ticket(ctx: Context<GrantQuestTicket>, tournament: Option<Pubkey>) -> Result<()> {
+        instructions::tournament_ticket::grant_quest_ticket(ctx, tournament)
+    }
+
+    /// Self-report version, commit and IDL hash after an upgrade - upgrade authority only
+    pub fn update_program_info(
+        ctx: Context<UpdateProgramInfo>,
+        version: [u16; 3],
+        git_commit: [u8; 20],
+        idl_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::program_info::handler(ctx, version, git_commit, idl_hash)
+    }
 }
 
 #[derive(Accounts)]
@@ -350,11 +424,20 @@ pub struct CreateAchievementNft<'info> {
 
 #[derive(Accounts)]
 pub struct CreateItemNft<'info> {
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = ItemCounter::LEN,
+        seeds = [b"item_counter", player.key().as_ref()],
+        bump
+    )]
+    pub item_counter: Account<'info, ItemCounter>,
+
     #[account(
         init,
         payer = player,
         space = ItemNft::LEN,
-        seeds = [b"item", player.key().as_ref(), &Clock::get()?.unix_timestamp.to_le_bytes()],
+        seeds = [b"item", player.key().as_ref(), &item_counter.next_index.to_le_bytes()],
         bump
     )]
     pub item_nft: Account<'info, ItemNft>,
@@ -492,7 +575,7 @@ pub struct EquipItem<'info> {
     pub player_nft: Account<'info, PlayerNft>,
     
     #[account(
-        seeds = [b"item", item_nft.owner.as_ref(), &item_nft.created_at.to_le_bytes()],
+        seeds = [b"item", item_nft.owner.as_ref(), &item_nft.item_index.to_le_bytes()],
         bump = item_nft.bump,
         constraint = item_nft.owner == player.key()
     )]
@@ -513,4 +596,475 @@ pub struct UnequipItem<'info> {
     
     #[account(mut)]
     pub player: Signer<'info>,
-}
\ No newline at end of file
+}
+
+#[derive(Accounts)]
+pub struct CreateCosmeticNft<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = CosmeticNft::LEN,
+        seeds = [b"cosmetic", nft_mint.key().as_ref()],
+        bump
+    )]
+    pub cosmetic_nft: Account<'info, CosmeticNft>,
+    
+    #[account(
+        init,
+        payer = authority,
+        mint::decimals = 0,
+        mint::authority = nft_authority,
+        mint::freeze_authority = nft_authority,
+    )]
+    pub nft_mint: Account<'info, Mint>,
+    
+    /// CHECK: This is the NFT mint authority PDA
+    #[account(
+        seeds = [b"nft_authority"],
+        bump
+    )]
+    pub nft_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = nft_mint,
+        associated_token::authority = recipient
+    )]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+    
+    /// CHECK: Cosmetic recipient
+    pub recipient: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"collection"],
+        bump = collection.bump,
+        constraint = collection.authority == authority.key() @ GameError::AccessDenied
+    )]
+    pub collection: Account<'info, NftCollection>,
+    
+    /// Gems the recipient burned for this cosmetic, when it was bought with gems
+    #[account(
+        seeds = [GEM_RECEIPT_SEED, &gem_receipt.receipt_id.to_le_bytes()],
+        seeds::program = GAME_PROGRAM_ID,
+        bump = gem_receipt.bump,
+        constraint = is_owned_account(&gem_receipt.to_account_info(), &GAME_PROGRAM_ID, GemReceipt::LEN)
+            @ GameError::InvalidAccountOwner,
+        constraint = gem_receipt.sink == GemSink::Cosmetic
+            && gem_receipt.player == recipient.key() @ GameError::AccessDenied
+    )]
+    pub gem_receipt: Option<Account<'info, GemReceipt>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = GemRedemption::LEN,
+        seeds = [b"gem_redemption", gem_receipt.as_ref().unwrap().key().as_ref()],
+        bump
+    )]
+    pub gem_redemption: Option<Account<'info, GemRedemption>>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct EquipCosmetic<'info> {
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = crate::shared::CosmeticLoadout::LEN,
+        seeds = [b"cosmetic_loadout", player.key().as_ref()],
+        bump
+    )]
+    pub cosmetic_loadout: Account<'info, crate::shared::CosmeticLoadout>,
+    
+    #[account(
+        seeds = [b"cosmetic", cosmetic_nft.mint.as_ref()],
+        bump = cosmetic_nft.bump
+    )]
+    pub cosmetic_nft: Account<'info, CosmeticNft>,
+    
+    // Player must hold the cosmetic to equip it
+    #[account(
+        token::mint = cosmetic_nft.mint,
+        token::authority = player,
+        constraint = player_token_account.amount == 1 @ GameError::AccessDenied
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub player: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnequipCosmetic<'info> {
+    #[account(
+        mut,
+        seeds = [b"cosmetic_loadout", player.key().as_ref()],
+        bump = cosmetic_loadout.bump
+    )]
+    pub cosmetic_loadout: Account<'info, crate::shared::CosmeticLoadout>,
+    
+    pub player: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(table_id: u32)]
+pub struct CreateDropTable<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = DropTable::LEN,
+        seeds = [b"drop_table", &table_id.to_le_bytes()],
+        bump
+    )]
+    pub drop_table: Account<'info, DropTable>,
+    
+    pub payment_mint: Account<'info, Mint>,
+    
+    #[account(token::mint = payment_mint)]
+    pub treasury: Account<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [b"collection"],
+        bump = collection.bump,
+        constraint = collection.authority == authority.key() @ GameError::AccessDenied
+    )]
+    pub collection: Account<'info, NftCollection>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PurchaseCrate<'info> {
+    #[account(
+        mut,
+        seeds = [b"drop_table", &drop_table.table_id.to_le_bytes()],
+        bump = drop_table.bump
+    )]
+    pub drop_table: Account<'info, DropTable>,
+    
+    #[account(
+        init,
+        payer = buyer,
+        space = CrateReceipt::LEN,
+        seeds = [b"crate", drop_table.key().as_ref(), &drop_table.crates_sold.to_le_bytes()],
+        bump
+    )]
+    pub crate_receipt: Account<'info, CrateReceipt>,
+    
+    #[account(
+        mut,
+        token::mint = drop_table.payment_mint,
+        token::authority = buyer
+    )]
+    pub buyer_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut, address = drop_table.treasury)]
+    pub treasury: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PurchaseCrateWithGems<'info> {
+    #[account(
+        mut,
+        seeds = [b"drop_table", &drop_table.table_id.to_le_bytes()],
+        bump = drop_table.bump
+    )]
+    pub drop_table: Account<'info, DropTable>,
+    
+    #[account(
+        init,
+        payer = buyer,
+        space = CrateReceipt::LEN,
+        seeds = [b"crate", drop_table.key().as_ref(), &drop_table.crates_sold.to_le_bytes()],
+        bump
+    )]
+    pub crate_receipt: Account<'info, CrateReceipt>,
+    
+    /// Gems the buyer burned for a crate from this table
+    #[account(
+        seeds = [GEM_RECEIPT_SEED, &gem_receipt.receipt_id.to_le_bytes()],
+        seeds::program = GAME_PROGRAM_ID,
+        bump = gem_receipt.bump,
+        constraint = is_owned_account(&gem_receipt.to_account_info(), &GAME_PROGRAM_ID, GemReceipt::LEN)
+            @ GameError::InvalidAccountOwner,
+        constraint = gem_receipt.sink == GemSink::Crate
+            && gem_receipt.player == buyer.key()
+            && gem_receipt.target == drop_table.key() @ GameError::AccessDenied
+    )]
+    pub gem_receipt: Account<'info, GemReceipt>,
+    
+    #[account(
+        init,
+        payer = buyer,
+        space = GemRedemption::LEN,
+        seeds = [b"gem_redemption", gem_receipt.key().as_ref()],
+        bump
+    )]
+    pub gem_redemption: Account<'info, GemRedemption>,
+    
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OpenCrate<'info> {
+    #[account(
+        mut,
+        seeds = [b"drop_table", &drop_table.table_id.to_le_bytes()],
+        bump = drop_table.bump,
+        constraint = drop_table.vrf_authority == vrf_authority.key() @ GameError::AccessDenied
+    )]
+    pub drop_table: Account<'info, DropTable>,
+    
+    #[account(
+        mut,
+        seeds = [b"crate", drop_table.key().as_ref(), &crate_receipt.crate_id.to_le_bytes()],
+        bump = crate_receipt.bump,
+        has_one = drop_table,
+        has_one = owner
+    )]
+    pub crate_receipt: Account<'info, CrateReceipt>,
+    
+    #[account(
+        init_if_needed,
+        payer = vrf_authority,
+        space = ItemCounter::LEN,
+        seeds = [b"item_counter", owner.key().as_ref()],
+        bump
+    )]
+    pub item_counter: Account<'info, ItemCounter>,
+    
+    #[account(
+        init,
+        payer = vrf_authority,
+        space = ItemNft::LEN,
+        seeds = [b"item", owner.key().as_ref(), &item_counter.next_index.to_le_bytes()],
+        bump
+    )]
+    pub item_nft: Account<'info, ItemNft>,
+    
+    #[account(
+        init,
+        payer = vrf_authority,
+        mint::decimals = 0,
+        mint::authority = nft_authority,
+        mint::freeze_authority = nft_authority,
+    )]
+    pub nft_mint: Account<'info, Mint>,
+    
+    /// CHECK: This is the NFT mint authority PDA
+    #[account(
+        seeds = [b"nft_authority"],
+        bump
+    )]
+    pub nft_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = vrf_authority,
+        associated_token::mint = nft_mint,
+        associated_token::authority = owner
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+    
+    /// CHECK: Crate owner, checked against the receipt
+    pub owner: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub vrf_authority: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar, read to find the oracle's signature over the seed
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureTickets<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = TicketConfig::LEN,
+        seeds = [b"ticket_config"],
+        bump
+    )]
+    pub ticket_config: Account<'info, TicketConfig>,
+    
+    pub payment_mint: Account<'info, Mint>,
+    
+    #[account(token::mint = payment_mint)]
+    pub treasury: Account<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [b"collection"],
+        bump = collection.bump,
+        constraint = collection.authority == authority.key() @ GameError::AccessDenied
+    )]
+    pub collection: Account<'info, NftCollection>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PurchaseTicket<'info> {
+    #[account(
+        mut,
+        seeds = [b"ticket_config"],
+        bump = ticket_config.bump
+    )]
+    pub ticket_config: Account<'info, TicketConfig>,
+    
+    #[account(
+        init,
+        payer = buyer,
+        space = TournamentTicket::LEN,
+        seeds = [TOURNAMENT_TICKET_SEED, ticket_mint.key().as_ref()],
+        bump
+    )]
+    pub ticket: Account<'info, TournamentTicket>,
+    
+    #[account(
+        init,
+        payer = buyer,
+        mint::decimals = 0,
+        mint::authority = nft_authority,
+        mint::freeze_authority = nft_authority,
+    )]
+    pub ticket_mint: Account<'info, Mint>,
+    
+    /// CHECK: This is the NFT mint authority PDA
+    #[account(
+        seeds = [b"nft_authority"],
+        bump
+    )]
+    pub nft_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = buyer,
+        associated_token::mint = ticket_mint,
+        associated_token::authority = buyer
+    )]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = ticket_config.payment_mint,
+        token::authority = buyer
+    )]
+    pub buyer_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut, address = ticket_config.treasury)]
+    pub treasury: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GrantQuestTicket<'info> {
+    #[account(
+        mut,
+        seeds = [b"ticket_config"],
+        bump = ticket_config.bump,
+        constraint = ticket_config.quest_issuer == issuer.key() @ GameError::AccessDenied
+    )]
+    pub ticket_config: Account<'info, TicketConfig>,
+    
+    #[account(
+        init,
+        payer = issuer,
+        space = TournamentTicket::LEN,
+        seeds = [TOURNAMENT_TICKET_SEED, ticket_mint.key().as_ref()],
+        bump
+    )]
+    pub ticket: Account<'info, TournamentTicket>,
+    
+    #[account(
+        init,
+        payer = issuer,
+        mint::decimals = 0,
+        mint::authority = nft_authority,
+        mint::freeze_authority = nft_authority,
+    )]
+    pub ticket_mint: Account<'info, Mint>,
+    
+    /// CHECK: This is the NFT mint authority PDA
+    #[account(
+        seeds = [b"nft_authority"],
+        bump
+    )]
+    pub nft_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = issuer,
+        associated_token::mint = ticket_mint,
+        associated_token::authority = recipient
+    )]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+    
+    /// CHECK: Ticket recipient
+    pub recipient: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateProgramInfo<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = crate::shared::ProgramInfo::LEN,
+        seeds = [b"program_info"],
+        bump
+    )]
+    pub program_info: Account<'info, crate::shared::ProgramInfo>,
+    
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::SolDuelNft>,
+    
+    #[account(constraint = program_data.upgrade_authority_address == Some(authority.key()) @ GameError::InvalidUpgradeAuthority)]
+    pub program_data: Account<'info, ProgramData>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
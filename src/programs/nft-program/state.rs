@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::shared::{PlayerClass, PlayerStats, MAX_USERNAME_LENGTH};
+use crate::shared::{CosmeticType, PlayerClass, PlayerStats, MAX_USERNAME_LENGTH};
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Rarity {
@@ -231,6 +231,7 @@ pub struct ItemNft {
     pub durability: u32,
     pub max_durability: u32,
     pub created_at: i64,
+    pub item_index: u64, // Owner's ItemCounter value at mint; seeds this PDA
     pub bump: u8,
 }
 
@@ -248,6 +249,7 @@ impl ItemNft {
         4 + // durability
         4 + // max_durability
         8 + // created_at
+        8 + // item_index
         1; // bump
 
     pub fn get_effective_stats(&self) -> ItemStats {
@@ -296,6 +298,194 @@ impl ItemNft {
     }
 }
 
+/// Cosmetic NFT (skins, card backs, table themes). Ownership follows the token,
+/// so the holder is checked against the token account rather than stored here.
+/// Items minted to one owner; the count seeds each new item's PDA so two
+/// mints in the same second never collide
+#[account]
+#[derive(Default)]
+pub struct ItemCounter {
+    pub owner: Pubkey,
+    pub next_index: u64,
+    pub bump: u8,
+}
+
+impl ItemCounter {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // owner
+        8 + // next_index
+        1; // bump
+
+    /// Claim the next index for `owner`
+    pub fn claim(&mut self, owner: Pubkey, bump: u8) -> Result<u64> {
+        self.owner = owner;
+        self.bump = bump;
+        let index = self.next_index;
+        self.next_index = index.checked_add(1).ok_or(crate::shared::GameError::ArithmeticOverflow)?;
+        Ok(index)
+    }
+}
+
+#[account]
+pub struct CosmeticNft {
+    pub mint: Pubkey,
+    pub cosmetic_type: CosmeticType,
+    pub name: String,
+    pub rarity: Rarity,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl CosmeticNft {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // mint
+        1 + // cosmetic_type
+        4 + 64 + // name (max 64 chars)
+        1 + // rarity
+        8 + // created_at
+        1; // bump
+}
+
+pub const MAX_DROP_ENTRIES: usize = 16;
+pub const MAX_DROP_NAME_LENGTH: usize = 32;
+pub const DROP_TABLE_TOTAL_BPS: u16 = 10_000;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct DropEntry {
+    pub name: String,
+    pub item_type: ItemType,
+    pub rarity: Rarity,
+    pub stats: ItemStats,
+    pub weight_bps: u16, // Drop probability; a table's weights sum to 10_000
+}
+
+impl DropEntry {
+    pub const LEN: usize = 4 + MAX_DROP_NAME_LENGTH + // name
+        1 + // item_type
+        1 + // rarity
+        24 + // stats
+        2; // weight_bps
+}
+
+/// Published drop rates for a crate. Kept on-chain so anyone can check the
+/// odds and replay a roll from the randomness emitted in CrateOpened.
+#[account]
+pub struct DropTable {
+    pub authority: Pubkey,
+    pub table_id: u32,
+    pub price: u64, // In game tokens
+    pub payment_mint: Pubkey,
+    pub treasury: Pubkey, // Token account receiving crate payments
+    pub vrf_authority: Pubkey, // Oracle that fulfils open_crate
+    pub entries: Vec<DropEntry>,
+    pub crates_sold: u64,
+    pub crates_opened: u64,
+    pub is_active: bool,
+    pub bump: u8,
+}
+
+impl DropTable {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // authority
+        4 + // table_id
+        8 + // price
+        32 + // payment_mint
+        32 + // treasury
+        32 + // vrf_authority
+        4 + MAX_DROP_ENTRIES * DropEntry::LEN + // entries
+        8 + // crates_sold
+        8 + // crates_opened
+        1 + // is_active
+        1; // bump
+
+    pub fn validate_entries(entries: &[DropEntry]) -> bool {
+        !entries.is_empty() &&
+        entries.len() <= MAX_DROP_ENTRIES &&
+        entries.iter().all(|entry| entry.name.len() <= MAX_DROP_NAME_LENGTH && entry.weight_bps > 0) &&
+        entries.iter().map(|entry| entry.weight_bps as u32).sum::<u32>() == DROP_TABLE_TOTAL_BPS as u32
+    }
+
+    /// Walk the cumulative weights with a roll in 0..10_000
+    pub fn pick(&self, roll: u16) -> Option<&DropEntry> {
+        let mut cumulative = 0u32;
+        self.entries.iter().find(|entry| {
+            cumulative += entry.weight_bps as u32;
+            (roll as u32) < cumulative
+        })
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CrateStatus {
+    Pending,
+    Opened,
+}
+
+/// A paid, unopened crate awaiting VRF fulfilment
+#[account]
+pub struct CrateReceipt {
+    pub owner: Pubkey,
+    pub drop_table: Pubkey,
+    pub crate_id: u64,
+    pub vrf_seed: [u8; 32],
+    pub status: CrateStatus,
+    pub item_mint: Option<Pubkey>,
+    pub purchased_at: i64,
+    pub bump: u8,
+}
+
+impl CrateReceipt {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // owner
+        32 + // drop_table
+        8 + // crate_id
+        32 + // vrf_seed
+        1 + // status
+        1 + 32 + // item_mint
+        8 + // purchased_at
+        1; // bump
+}
+
+/// Marks a game-program gem receipt as redeemed, so it buys one crate or cosmetic
+#[account]
+pub struct GemRedemption {
+    pub receipt: Pubkey,
+    pub redeemed_at: i64,
+    pub bump: u8,
+}
+
+impl GemRedemption {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // receipt
+        8 + // redeemed_at
+        1; // bump
+}
+
+/// Tournament ticket sales and quest grants
+#[account]
+pub struct TicketConfig {
+    pub authority: Pubkey,
+    pub price: u64, // In game tokens
+    pub payment_mint: Pubkey,
+    pub treasury: Pubkey, // Token account receiving ticket payments
+    pub quest_issuer: Pubkey, // Signer allowed to grant tickets won from quests
+    pub tickets_sold: u64,
+    pub tickets_granted: u64,
+    pub bump: u8,
+}
+
+impl TicketConfig {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // authority
+        8 + // price
+        32 + // payment_mint
+        32 + // treasury
+        32 + // quest_issuer
+        8 + // tickets_sold
+        8 + // tickets_granted
+        1; // bump
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 pub struct NftMetadata {
     pub name: String,
@@ -76,4 +76,193 @@ pub enum GameError {
     
     #[msg("Admin not in whitelist")]
     AdminNotWhitelisted,
-}
\ No newline at end of file
+    
+    #[msg("Player is temporarily banned from matchmaking")]
+    MatchmakingBanned,
+    
+    #[msg("Turn has not timed out")]
+    TurnNotTimedOut,
+    
+    #[msg("Player is not on the compliance allowlist")]
+    NotAllowlisted,
+    
+    #[msg("Compliance credential missing, expired or revoked")]
+    InvalidComplianceCredential,
+    
+    #[msg("Feature is disabled for this player")]
+    FeatureDisabled,
+    
+    #[msg("Wallet is not linked to this player identity")]
+    WalletNotLinked,
+    
+    #[msg("Only the identity's cold wallet can do this")]
+    ColdWalletRequired,
+    
+    #[msg("Too many wallets linked to this identity")]
+    TooManyLinkedWallets,
+    
+    #[msg("Signer is not a guardian of this identity")]
+    NotGuardian,
+    
+    #[msg("Invalid guardian set or threshold")]
+    InvalidGuardianConfig,
+    
+    #[msg("No recovery is pending")]
+    NoPendingRecovery,
+    
+    #[msg("Recovery lacks approvals or is still timelocked")]
+    RecoveryNotReady,
+    
+    #[msg("Sponsor has reached its daily registration cap")]
+    SponsorCapReached,
+    
+    #[msg("Sponsor vault cannot cover the rent")]
+    InsufficientSponsorFunds,
+    
+    #[msg("Checkpoint sequence is not contiguous")]
+    InvalidCheckpointSequence,
+    
+    #[msg("Delegation does not grant this permission")]
+    DelegationPermissionDenied,
+    
+    #[msg("ER account mapping is missing or stale")]
+    StaleAccountMapping,
+    
+    #[msg("ER session costs exceed the cost cap")]
+    CostCapExceeded,
+    
+    #[msg("Component snapshot is missing, stale or corrupted")]
+    InvalidSnapshot,
+    
+    #[msg("Component snapshot has already been restored")]
+    SnapshotAlreadyRestored,
+    
+    #[msg("Level curve is not strictly increasing")]
+    InvalidLevelCurve,
+    
+    #[msg("Not enough unspent stat points")]
+    InsufficientStatPoints,
+    
+    #[msg("Allocation exceeds the class stat cap")]
+    StatCapExceeded,
+    
+    #[msg("Player must reach the class max level to prestige")]
+    PrestigeLevelNotReached,
+    
+    #[msg("Player is already at the highest prestige tier")]
+    MaxPrestigeReached,
+    
+    #[msg("Guild configuration or name is invalid")]
+    InvalidGuildConfig,
+    
+    #[msg("Guild has reached its member limit")]
+    GuildFull,
+    
+    #[msg("Only guild officers or the leader can do this")]
+    NotGuildManager,
+    
+    #[msg("Guild leader must hand over leadership before leaving")]
+    LeaderCannotLeave,
+    
+    #[msg("Guild treasury balance is too low")]
+    InsufficientTreasury,
+    
+    #[msg("Clan war is not in the required state or window")]
+    WarNotActive,
+    
+    #[msg("Guild is already committed to a clan war")]
+    WarInProgress,
+    
+    #[msg("Scheduled event is invalid or not found")]
+    InvalidEventSchedule,
+    
+    #[msg("Daily reward already claimed today")]
+    DailyRewardAlreadyClaimed,
+    
+    #[msg("Invalid drop table")]
+    InvalidDropTable,
+    
+    #[msg("Crate has already been opened")]
+    CrateAlreadyOpened,
+    
+    #[msg("Instruction invoked via CPI from a program that is not allowlisted")]
+    CpiCallerNotAllowed,
+    
+    #[msg("Stake position does not allow ER collateral usage")]
+    ErCollateralNotAllowed,
+    
+    #[msg("ER collateral exceeds the stake's available share")]
+    ErCollateralExceeded,
+    
+    #[msg("Vault or vault authority does not match the registered address")]
+    VaultMismatch,
+    
+    #[msg("Already matched against this opponent too often; try again later")]
+    RepeatOpponentLimit,
+    
+    #[msg("Tournament configuration is invalid")]
+    InvalidTournamentConfig,
+    
+    #[msg("Tournament is not open for registration")]
+    TournamentNotOpen,
+    
+    #[msg("Tournament is full")]
+    TournamentFull,
+    
+    #[msg("Tournament is not in progress")]
+    TournamentNotInProgress,
+    
+    #[msg("Match does not belong to this bracket pairing")]
+    BracketPairingMismatch,
+    
+    #[msg("Match result already counted for this pairing")]
+    TournamentResultAlreadyRecorded,
+    
+    #[msg("Invalid ticket configuration")]
+    InvalidTicketConfig,
+    
+    #[msg("This tournament does not accept ticket entries")]
+    TicketsNotAccepted,
+    
+    #[msg("Ticket is not valid for this tournament")]
+    InvalidTicket,
+    
+    #[msg("Re-entry not allowed: player is still in, out of re-entries, or the late window has closed")]
+    ReentryNotAllowed,
+    
+    #[msg("Player does not meet this tournament's entry requirements")]
+    EntryRequirementsNotMet,
+    
+    #[msg("Program info describes a newer deployment than this one")]
+    StaleProgramInfo,
+    
+    #[msg("Too many calls to this instruction; try again shortly")]
+    RateLimitExceeded,
+    
+    #[msg("Account has the wrong owner, type or size")]
+    InvalidAccountOwner,
+    
+    #[msg("Account is not the expected program")]
+    InvalidProgramAccount,
+    
+    #[msg("Admin list is full")]
+    TooManyAdmins,
+    
+    #[msg("Missing or invalid ed25519 signature")]
+    InvalidSignature,
+    
+    #[msg("Checkpoint diffs do not match the committed hashes")]
+    CheckpointHashMismatch,
+    
+    #[msg("A recovery is already pending")]
+    RecoveryAlreadyPending,
+    
+    #[msg("Approval is for a different wallet than the pending recovery")]
+    RecoveryWalletMismatch,
+    
+    #[msg("Tournament start deadline has not passed")]
+    TournamentStartDeadlineNotReached,
+    
+    #[msg("This wallet created an identity and must play through it")]
+    IdentityRequired,
+}
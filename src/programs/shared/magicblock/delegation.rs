@@ -1,6 +1,15 @@
 use anchor_lang::prelude::*;
 use crate::shared::GameError;
 
+/// Delegation permission bits, granted per delegated account type
+pub const PERMISSION_READ: u8 = 1 << 0;
+pub const PERMISSION_WRITE: u8 = 1 << 1;
+pub const PERMISSION_CLOSE: u8 = 1 << 2;
+pub const PERMISSION_TRANSFER_VALUE: u8 = 1 << 3;
+
+/// Number of `DelegatedAccountType` variants, i.e. rows in the permission matrix
+pub const DELEGATED_ACCOUNT_TYPE_COUNT: usize = 6;
+
 /// Delegation state for MagicBlock Ephemeral Rollups
 #[account]
 pub struct DelegationState {
@@ -12,6 +21,7 @@ pub struct DelegationState {
     pub expiry_timestamp: Option<i64>,
     pub is_active: bool,
     pub pending_commits: Vec<PendingCommit>,
+    pub permissions: [u8; DELEGATED_ACCOUNT_TYPE_COUNT],
     pub bump: u8,
 }
 
@@ -25,6 +35,7 @@ impl DelegationState {
         1 + 8 + // expiry_timestamp (Option<i64>)
         1 + // is_active
         4 + (50 * PendingCommit::LEN) + // pending_commits (max 50)
+        DELEGATED_ACCOUNT_TYPE_COUNT + // permissions
         1; // bump
 
     pub fn add_delegated_account(&mut self, account: DelegatedAccount) -> Result<()> {
@@ -43,6 +54,68 @@ impl DelegationState {
         Ok(())
     }
 
+    /// Matrix granted to a fresh delegation: read/write everywhere, value moves
+    /// only on token and NFT accounts, and only the match itself may be closed
+    pub fn default_permissions() -> [u8; DELEGATED_ACCOUNT_TYPE_COUNT] {
+        let mut permissions = [PERMISSION_READ | PERMISSION_WRITE; DELEGATED_ACCOUNT_TYPE_COUNT];
+        permissions[DelegatedAccountType::Match.index()] |= PERMISSION_CLOSE;
+        permissions[DelegatedAccountType::TokenAccount.index()] |= PERMISSION_TRANSFER_VALUE;
+        permissions[DelegatedAccountType::NftAccount.index()] |= PERMISSION_TRANSFER_VALUE;
+        permissions
+    }
+
+    pub fn has_permission(&self, account_type: DelegatedAccountType, permission: u8) -> bool {
+        self.permissions[account_type.index()] & permission == permission
+    }
+
+    /// Every ER-originated write goes through here before touching delegated state
+    pub fn require_permission(&self, account_type: DelegatedAccountType, permission: u8) -> Result<()> {
+        if !self.has_permission(account_type, permission) {
+            return Err(GameError::DelegationPermissionDenied.into());
+        }
+        Ok(())
+    }
+
+    /// Whether the delegation a match or profile is bound to grants `permission`.
+    /// Nothing bound means the account never left mainnet and needs no grant.
+    pub fn permits(
+        bound: Option<Pubkey>,
+        delegation_state: Option<&Account<DelegationState>>,
+        account_type: DelegatedAccountType,
+        permission: u8,
+    ) -> bool {
+        match bound {
+            None => true,
+            Some(bound) => delegation_state
+                .map_or(false, |state| state.key() == bound && state.has_permission(account_type, permission)),
+        }
+    }
+
+    /// Rolling back rewrites every modified account, so each needs write access
+    pub fn can_roll_back(&self) -> bool {
+        self.delegated_accounts
+            .iter()
+            .filter(|delegated| delegated.is_modified)
+            .all(|delegated| self.has_permission(delegated.account_type, PERMISSION_WRITE))
+    }
+
+    /// Drop permissions for an account type. Bits can only be removed, never added.
+    pub fn narrow(&mut self, account_type: DelegatedAccountType, permissions: u8) -> Result<()> {
+        let current = self.permissions[account_type.index()];
+        if permissions & !current != 0 {
+            return Err(GameError::DelegationPermissionDenied.into());
+        }
+        self.permissions[account_type.index()] = permissions;
+        Ok(())
+    }
+
+    pub fn account_type_of(&self, account: &Pubkey) -> Option<DelegatedAccountType> {
+        self.delegated_accounts
+            .iter()
+            .find(|delegated| delegated.account_pubkey == *account)
+            .map(|delegated| delegated.account_type)
+    }
+
     pub fn is_expired(&self, current_time: i64) -> bool {
         if let Some(expiry) = self.expiry_timestamp {
             current_time > expiry
@@ -52,6 +125,41 @@ impl DelegationState {
     }
 }
 
+/// `DelegationState` as written before the permission matrix, which was
+/// inserted ahead of the bump
+#[derive(AnchorDeserialize)]
+pub struct LegacyDelegationState {
+    pub delegator: Pubkey,
+    pub ephemeral_rollup: Pubkey,
+    pub original_owner: Pubkey,
+    pub delegated_accounts: Vec<DelegatedAccount>,
+    pub delegation_timestamp: i64,
+    pub expiry_timestamp: Option<i64>,
+    pub is_active: bool,
+    pub pending_commits: Vec<PendingCommit>,
+    pub bump: u8,
+}
+
+impl LegacyDelegationState {
+    pub const LEN: usize = DelegationState::LEN - DELEGATED_ACCOUNT_TYPE_COUNT;
+
+    /// Carry the session over with the default matrix
+    pub fn into_current(self) -> DelegationState {
+        DelegationState {
+            delegator: self.delegator,
+            ephemeral_rollup: self.ephemeral_rollup,
+            original_owner: self.original_owner,
+            delegated_accounts: self.delegated_accounts,
+            delegation_timestamp: self.delegation_timestamp,
+            expiry_timestamp: self.expiry_timestamp,
+            is_active: self.is_active,
+            pending_commits: self.pending_commits,
+            permissions: DelegationState::default_permissions(),
+            bump: self.bump,
+        }
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 pub struct DelegatedAccount {
     pub account_pubkey: Pubkey,
@@ -79,6 +187,13 @@ pub enum DelegatedAccountType {
     NftAccount,
 }
 
+impl DelegatedAccountType {
+    /// Row of this type in `DelegationState::permissions`
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 pub struct PendingCommit {
     pub account_pubkey: Pubkey,
@@ -200,6 +315,7 @@ pub mod delegation_handlers {
         delegation_state.expiry_timestamp = expiry_timestamp;
         delegation_state.is_active = true;
         delegation_state.pending_commits = Vec::new();
+        delegation_state.permissions = DelegationState::default_permissions();
         delegation_state.bump = ctx.bumps.delegation_state;
 
         Ok(())
@@ -248,6 +364,13 @@ pub mod delegation_handlers {
 
         // Add all commits to pending list
         for commit in commits {
+            let account_type = delegation_state
+                .account_type_of(&commit.account_pubkey)
+                .ok_or(GameError::DelegationPermissionDenied)?;
+            delegation_state.require_permission(account_type, PERMISSION_WRITE)?;
+            if commit.commit_type == CommitType::TokenTransfer {
+                delegation_state.require_permission(account_type, PERMISSION_TRANSFER_VALUE)?;
+            }
             delegation_state.add_pending_commit(commit)?;
         }
 
@@ -266,6 +389,9 @@ pub mod delegation_handlers {
 
     pub fn rollback_changes(ctx: Context<RollbackChanges>) -> Result<()> {
         let delegation_state = &mut ctx.accounts.delegation_state;
+        if !delegation_state.can_roll_back() {
+            return Err(GameError::DelegationPermissionDenied.into());
+        }
 
         // Mark all delegated accounts as needing rollback
         for delegated_account in delegation_state.delegated_accounts.iter_mut() {
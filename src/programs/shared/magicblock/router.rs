@@ -150,6 +150,12 @@ pub enum RouteStatus {
     Rollback,
 }
 
+impl RouteStatus {
+    pub fn is_final(&self) -> bool {
+        matches!(self, RouteStatus::Completed | RouteStatus::Failed | RouteStatus::Rollback)
+    }
+}
+
 /// Router instruction contexts
 #[derive(Accounts)]
 pub struct InitializeRouter<'info> {
@@ -208,6 +214,35 @@ pub struct ExecuteRoute<'info> {
     pub executor: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct CloseTransactionRoute<'info> {
+    #[account(
+        seeds = [b"router_config"],
+        bump = router_config.bump
+    )]
+    pub router_config: Account<'info, RouterConfig>,
+
+    #[account(
+        mut,
+        close = requester,
+        seeds = [b"transaction_route", transaction_route.requester.as_ref(), &transaction_route.created_at.to_le_bytes()],
+        bump = transaction_route.bump,
+        constraint = transaction_route.status.is_final() @ GameError::InvalidGameState
+    )]
+    pub transaction_route: Account<'info, TransactionRoute>,
+
+    /// CHECK: Original payer of the route, receives the rent refund
+    #[account(mut, address = transaction_route.requester)]
+    pub requester: UncheckedAccount<'info>,
+
+    /// The requester or the router authority
+    #[account(
+        constraint = closer.key() == transaction_route.requester
+            || closer.key() == router_config.authority @ GameError::AccessDenied
+    )]
+    pub closer: Signer<'info>,
+}
+
 /// Router implementation
 pub mod router_handlers {
     use super::*;
@@ -286,6 +321,15 @@ pub mod router_handlers {
         Ok(())
     }
 
+    pub fn close_transaction_route(ctx: Context<CloseTransactionRoute>) -> Result<()> {
+        msg!(
+            "Closed route {} for {}",
+            ctx.accounts.transaction_route.transaction_id,
+            ctx.accounts.requester.key()
+        );
+        Ok(())
+    }
+
     fn create_default_routing_rules() -> Vec<RoutingRule> {
         vec![
             // Game operations go to ER
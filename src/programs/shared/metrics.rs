@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::compute_units::sol_remaining_compute_units;
+
+// Samples kept per shard; the keeper reads every shard's ring each pass
+pub const METRICS_BUFFER_SIZE: usize = 32;
+
+pub const METRICS_BUFFER_SEED: &[u8] = b"metrics_buffer";
+
+/// Buffers per program. Each match records into the shard picked by its
+/// account key, so concurrent matches lock different buffers instead of
+/// serializing on one.
+pub const METRICS_SHARDS: u8 = 8;
+
+pub fn metrics_shard(match_key: &Pubkey) -> u8 {
+    match_key.to_bytes()[0] % METRICS_SHARDS
+}
+
+/// One measured instruction call. `instruction` and `branch` are ids chosen by
+/// the recording program; dashboards map them back to names.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MetricSample {
+    pub instruction: u8,
+    pub branch: u8,
+    pub compute_units: u32,
+    pub entities: u16,
+    pub slot: u64,
+}
+
+impl MetricSample {
+    pub const LEN: usize = 1 + 1 + 4 + 2 + 8;
+}
+
+#[account]
+#[derive(Default)]
+pub struct MetricsBuffer {
+    pub authority: Pubkey,
+    pub shard: u8,
+    pub samples: [MetricSample; METRICS_BUFFER_SIZE],
+    pub head: u8, // Next slot to overwrite
+    pub total_samples: u64,
+    pub bump: u8,
+}
+
+impl MetricsBuffer {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // authority
+        1 + // shard
+        METRICS_BUFFER_SIZE * MetricSample::LEN + // samples
+        1 + // head
+        8 + // total_samples
+        1; // bump
+
+    pub fn record(&mut self, sample: MetricSample) {
+        self.samples[self.head as usize] = sample;
+        self.head = ((self.head as usize + 1) % METRICS_BUFFER_SIZE) as u8;
+        self.total_samples = self.total_samples.saturating_add(1);
+    }
+}
+
+/// Compute-unit meter built on `sol_remaining_compute_units` deltas. Off-chain
+/// the syscall reports zero, so samples taken in tests read as 0 CU.
+pub struct CuMeter {
+    start: u64,
+}
+
+impl CuMeter {
+    pub fn start() -> Self {
+        Self { start: sol_remaining_compute_units() }
+    }
+
+    pub fn consumed(&self) -> u32 {
+        self.start.saturating_sub(sol_remaining_compute_units()).min(u32::MAX as u64) as u32
+    }
+}
+
+/// Record a sample into the match's metrics shard
+pub fn record_metric(
+    buffer: &mut MetricsBuffer,
+    meter: &CuMeter,
+    instruction: u8,
+    branch: u8,
+    entities: u16,
+    slot: u64,
+) {
+    buffer.record(MetricSample {
+        instruction,
+        branch,
+        compute_units: meter.consumed(),
+        entities,
+        slot,
+    });
+}
@@ -2,7 +2,11 @@ pub mod error;
 pub mod state;
 pub mod utils;
 pub mod magicblock;
+pub mod metrics;
+pub mod validation;
 
 pub use error::*;
 pub use state::*;
-pub use utils::*;
\ No newline at end of file
+pub use utils::*;
+pub use metrics::*;
+pub use validation::*;
\ No newline at end of file
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::error::GameError;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
 pub enum GameState {
@@ -83,6 +84,29 @@ pub enum ActionType {
     Heal,
 }
 
+/// Queue a duel or match was played in; each keeps its own rating and stats
+/// so casual games never move a ranked ladder position
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum QueueType {
+    Ranked,
+    Casual,
+    Tournament,
+}
+
+pub const QUEUE_TYPES: usize = 3;
+
+impl QueueType {
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl Default for QueueType {
+    fn default() -> Self {
+        QueueType::Ranked
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 pub struct MatchConfig {
     pub max_players: u8,
@@ -90,6 +114,7 @@ pub struct MatchConfig {
     pub turn_timeout: i64,
     pub match_duration: i64,
     pub reward_distribution: Vec<u8>, // Percentages for 1st, 2nd, etc.
+    pub queue_type: QueueType,
 }
 
 impl Default for MatchConfig {
@@ -100,6 +125,7 @@ impl Default for MatchConfig {
             turn_timeout: 60, // 60 seconds
             match_duration: 1800, // 30 minutes
             reward_distribution: vec![50, 30, 20], // Winner gets 50%, 2nd gets 30%, 3rd gets 20%
+            queue_type: QueueType::Ranked,
         }
     }
 }
@@ -115,6 +141,130 @@ pub enum ReentrancyState {
     Entered = 1,
 }
 
+// Compliance gating for regulated deployments
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ComplianceMode {
+    Permissionless,
+    MerkleAllowlist,
+    Attestation,
+}
+
+impl Default for ComplianceMode {
+    fn default() -> Self {
+        ComplianceMode::Permissionless
+    }
+}
+
+// Wallet-agnostic player identity. Game-program profiles, token-program stakes
+// and strategic-duel loyalty stakes resolve through it (see resolve_player);
+// strategic-duel seats and psych profiles, NFT ownership and guild membership
+// still key on the signing wallet.
+pub const MAX_LINKED_WALLETS: usize = 4;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WalletRole {
+    Cold,    // withdrawals and identity changes
+    Hot,     // day-to-day play
+    Session, // short-lived play key, e.g. for ER sessions
+}
+
+impl Default for WalletRole {
+    fn default() -> Self {
+        WalletRole::Hot
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct LinkedWallet {
+    pub wallet: Pubkey,
+    pub role: WalletRole,
+    pub expires_at: i64, // 0 = never
+}
+
+#[account]
+#[derive(Default)]
+pub struct PlayerIdentity {
+    pub player_key: Pubkey, // wallet that created the identity; player PDAs derive from it
+    pub cold_wallet: Pubkey,
+    pub wallets: Vec<LinkedWallet>,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl PlayerIdentity {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // player_key
+        32 + // cold_wallet
+        4 + MAX_LINKED_WALLETS * (32 + 1 + 8) + // wallets
+        8 + // created_at
+        8 + // updated_at
+        1; // bump
+
+    pub fn role_of(&self, wallet: &Pubkey, now: i64) -> Option<WalletRole> {
+        if *wallet == self.cold_wallet {
+            return Some(WalletRole::Cold);
+        }
+        self.wallets
+            .iter()
+            .find(|linked| linked.wallet == *wallet && (linked.expires_at == 0 || now < linked.expires_at))
+            .map(|linked| linked.role)
+    }
+
+    /// Any linked wallet may play, including the cold wallet
+    pub fn can_play(&self, wallet: &Pubkey, now: i64) -> bool {
+        self.role_of(wallet, now).is_some()
+    }
+
+    /// Only the cold wallet may withdraw or change the identity
+    pub fn is_cold(&self, wallet: &Pubkey) -> bool {
+        *wallet == self.cold_wallet
+    }
+
+    /// Whether `identity`, the game-program PDA `[b"identity", wallet]` passed
+    /// unchecked, holds an identity, i.e. `wallet` is some identity's player key
+    pub fn exists(identity: &AccountInfo) -> bool {
+        !identity.data_is_empty() && *identity.owner == GAME_PROGRAM_ID
+    }
+
+    /// Whether `signer` may withdraw what `owner` holds. `identity` is the
+    /// game-program PDA `[b"identity", owner]`, passed unchecked so it cannot be
+    /// omitted: once it exists only its cold wallet may withdraw, until then
+    /// only `owner` itself.
+    pub fn may_withdraw(identity: &AccountInfo, owner: &Pubkey, signer: &Pubkey) -> bool {
+        if identity.data_is_empty() {
+            return signer == owner;
+        }
+        if *identity.owner != GAME_PROGRAM_ID {
+            return false;
+        }
+        let Ok(data) = identity.try_borrow_data() else { return false };
+        PlayerIdentity::try_deserialize(&mut &data[..]).map_or(false, |identity| identity.is_cold(signer))
+    }
+}
+
+/// Why an admin froze, stopped or force-ended something. Clients map codes to
+/// localized text off-chain; details go in a separate 32-byte memo hash.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReasonCode {
+    Unspecified,
+    SecurityIncident,
+    ExploitSuspected,
+    CheatingDetected,
+    OracleFailure,
+    RollupOutage,
+    BridgeOutage,
+    ComplianceHold,
+    DisputeResolution,
+    ScheduledMaintenance,
+}
+
+impl Default for ReasonCode {
+    fn default() -> Self {
+        ReasonCode::Unspecified
+    }
+}
+
 // Admin Role System
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum AdminRole {
@@ -122,12 +272,786 @@ pub enum AdminRole {
     GameAdmin,
     TokenAdmin,
     SecurityAdmin,
+    SupportAdmin,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+/// Each program keeps its own AdminConfig at `[ADMIN_CONFIG_SEED]`, created by its initializer
+#[account]
+#[derive(Default)]
 pub struct AdminConfig {
     pub super_admin: Pubkey,
     pub admin_whitelist: Vec<Pubkey>,
     pub role_assignments: Vec<(Pubkey, AdminRole)>,
     pub emergency_stop_enabled: bool,
-}
\ No newline at end of file
+    // Reject sensitive entrypoints reached via CPI unless the outer program is allowlisted
+    pub cpi_guard_enabled: bool,
+    pub cpi_allowed_callers: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+pub const ADMIN_CONFIG_SEED: &[u8] = b"admin_config";
+pub const MAX_CPI_ALLOWED_CALLERS: usize = 8;
+pub const MAX_ADMINS: usize = 16;
+
+impl AdminConfig {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // super_admin
+        4 + MAX_ADMINS * 32 + // admin_whitelist
+        4 + MAX_ADMINS * 33 + // role_assignments
+        1 + // emergency_stop_enabled
+        1 + // cpi_guard_enabled
+        4 + MAX_CPI_ALLOWED_CALLERS * 32 + // cpi_allowed_callers
+        1; // bump
+
+    pub fn init(&mut self, super_admin: Pubkey, bump: u8) {
+        self.super_admin = super_admin;
+        self.bump = bump;
+    }
+
+    pub fn check_cpi_origin(&self, instructions_sysvar: &AccountInfo, program_id: &Pubkey) -> Result<()> {
+        if !self.cpi_guard_enabled {
+            return Ok(());
+        }
+        crate::utils::check_cpi_origin(instructions_sysvar, program_id, &self.cpi_allowed_callers)
+    }
+}
+// Byte offsets, from the start of account data, of fields indexers filter on
+// with getProgramAccounts memcmp. Filtered fields lead their layouts so adding
+// fields elsewhere never moves them; bump SCHEMA_LAYOUT_VERSION if one must move.
+pub const SCHEMA_LAYOUT_VERSION: u16 = 1;
+pub const MATCH_CREATOR_OFFSET: u16 = 8;
+pub const MATCH_STATE_OFFSET: u16 = 40;
+// Option<Pubkey>: a 1-byte tag, then the key when set
+pub const MATCH_WINNER_OFFSET: u16 = 41;
+pub const DUEL_GAME_STATE_OFFSET: u16 = 8;
+pub const DUEL_ID_OFFSET: u16 = 9;
+pub const DUEL_PLAYER_ONE_OFFSET: u16 = 17;
+pub const DUEL_PLAYER_TWO_OFFSET: u16 = 49;
+pub const MAX_SCHEMA_FIELDS: usize = 16;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct SchemaField {
+    pub account: [u8; 16], // Account type name, zero padded
+    pub field: [u8; 16],
+    pub offset: u16,
+    pub size: u16,
+}
+
+impl SchemaField {
+    pub const LEN: usize = 16 + 16 + 2 + 2;
+
+    pub fn new(account: &str, field: &str, offset: u16, size: u16) -> Self {
+        let pad = |name: &str| {
+            let mut padded = [0u8; 16];
+            let len = name.len().min(16);
+            padded[..len].copy_from_slice(&name.as_bytes()[..len]);
+            padded
+        };
+        Self { account: pad(account), field: pad(field), offset, size }
+    }
+}
+
+/// On-chain copy of the filterable offsets, so indexers can read the layout
+/// rather than hard-code it
+#[account]
+#[derive(Default)]
+pub struct SchemaRegistry {
+    pub authority: Pubkey,
+    pub layout_version: u16,
+    pub fields: Vec<SchemaField>,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl SchemaRegistry {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // authority
+        2 + // layout_version
+        4 + MAX_SCHEMA_FIELDS * SchemaField::LEN + // fields
+        8 + // updated_at
+        1; // bump
+
+    pub fn current_fields() -> Vec<SchemaField> {
+        vec![
+            SchemaField::new("Match", "creator", MATCH_CREATOR_OFFSET, 32),
+            SchemaField::new("Match", "state", MATCH_STATE_OFFSET, 1),
+            SchemaField::new("Match", "winner", MATCH_WINNER_OFFSET, 33),
+            SchemaField::new("DuelComponent", "game_state", DUEL_GAME_STATE_OFFSET, 1),
+            SchemaField::new("DuelComponent", "duel_id", DUEL_ID_OFFSET, 8),
+            SchemaField::new("DuelComponent", "player_one", DUEL_PLAYER_ONE_OFFSET, 32),
+            SchemaField::new("DuelComponent", "player_two", DUEL_PLAYER_TWO_OFFSET, 32),
+        ]
+    }
+}
+
+/// Self-reported build of a deployed program, so clients and the SDK can
+/// refuse to talk to an incompatible deployment
+#[account]
+#[derive(Default)]
+pub struct ProgramInfo {
+    pub program_id: Pubkey,
+    pub version: [u16; 3], // Semantic version: major, minor, patch
+    pub git_commit: [u8; 20],
+    pub idl_hash: [u8; 32],
+    pub deployed_slot: u64, // Slot of the upgrade this record describes
+    pub updated_by: Pubkey,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl ProgramInfo {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // program_id
+        2 * 3 + // version
+        20 + // git_commit
+        32 + // idl_hash
+        8 + // deployed_slot
+        32 + // updated_by
+        8 + // updated_at
+        1; // bump
+
+    /// Same major version and at least the client's minor version
+    pub fn is_compatible_with(&self, major: u16, min_minor: u16) -> bool {
+        self.version[0] == major && self.version[1] >= min_minor
+    }
+}
+
+pub const WINNER_ATTESTATION_VERSION: u8 = 1;
+pub const WINNER_ATTESTATION_SEED: &[u8] = b"winner_attestation";
+
+/// Finished-match record in one shape for every game program. game-program,
+/// strategic-duel and game-program-er each write one when a match finalizes,
+/// at [WINNER_ATTESTATION_SEED, match account], so escrows, quests and
+/// tournaments read results without knowing the producer's own layout.
+/// Consumers must still check the account owner is the program they trust.
+#[account]
+#[derive(Default)]
+pub struct WinnerAttestation {
+    pub version: u8,
+    pub program: Pubkey, // program that ran the match
+    pub match_account: Pubkey,
+    pub match_id: u64,
+    pub winner: Option<Pubkey>, // None for draws and cancellations
+    pub finalized_at: i64,
+    pub authority: Pubkey, // signer that finalized the result
+    pub hash: [u8; 32],
+    pub bump: u8,
+}
+
+impl WinnerAttestation {
+    pub const LEN: usize = 8 + // discriminator
+        1 + // version
+        32 + // program
+        32 + // match_account
+        8 + // match_id
+        1 + 32 + // winner
+        8 + // finalized_at
+        32 + // authority
+        32 + // hash
+        1; // bump
+
+    pub fn result_hash(
+        program: &Pubkey,
+        match_account: &Pubkey,
+        match_id: u64,
+        winner: Option<Pubkey>,
+        finalized_at: i64,
+    ) -> [u8; 32] {
+        anchor_lang::solana_program::hash::hashv(&[
+            WINNER_ATTESTATION_SEED,
+            &[WINNER_ATTESTATION_VERSION],
+            program.as_ref(),
+            match_account.as_ref(),
+            &match_id.to_le_bytes(),
+            winner.unwrap_or_default().as_ref(),
+            &finalized_at.to_le_bytes(),
+        ])
+        .to_bytes()
+    }
+
+    pub fn new(
+        program: Pubkey,
+        match_account: Pubkey,
+        match_id: u64,
+        winner: Option<Pubkey>,
+        authority: Pubkey,
+        finalized_at: i64,
+        bump: u8,
+    ) -> Self {
+        Self {
+            version: WINNER_ATTESTATION_VERSION,
+            program,
+            match_account,
+            match_id,
+            winner,
+            finalized_at,
+            authority,
+            hash: Self::result_hash(&program, &match_account, match_id, winner, finalized_at),
+            bump,
+        }
+    }
+
+    /// True if the stored hash still commits to the stored result
+    pub fn is_consistent(&self) -> bool {
+        self.hash == Self::result_hash(&self.program, &self.match_account, self.match_id, self.winner, self.finalized_at)
+    }
+}
+
+pub const TOURNAMENT_TICKET_SEED: &[u8] = b"tournament_ticket";
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TicketSource {
+    Quest,
+    Purchase,
+}
+
+impl Default for TicketSource {
+    fn default() -> Self {
+        TicketSource::Purchase
+    }
+}
+
+/// Tournament entry ticket. nft-program writes one at [TOURNAMENT_TICKET_SEED,
+/// mint] for each ticket NFT it mints; game-program reads it to accept a
+/// ticket burn in place of the entry fee. Consumers must check the owner.
+#[account]
+#[derive(Default)]
+pub struct TournamentTicket {
+    pub mint: Pubkey,
+    pub tournament: Option<Pubkey>, // None = valid for any tournament that takes tickets
+    pub source: TicketSource,
+    pub issued_to: Pubkey,
+    pub issued_at: i64,
+    pub bump: u8,
+}
+
+impl TournamentTicket {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // mint
+        1 + 32 + // tournament
+        1 + // source
+        32 + // issued_to
+        8 + // issued_at
+        1; // bump
+
+    pub fn admits(&self, tournament: &Pubkey) -> bool {
+        self.tournament.map_or(true, |t| t == *tournament)
+    }
+}
+
+// Feature flags for gradual rollout and per-deployment (regional) disabling
+/// game-program owns the deployment's flags; other programs derive the PDA
+/// against it with `seeds::program`
+pub const GAME_PROGRAM_ID: Pubkey = pubkey!("GAMExxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx");
+/// nft-program owns tournament tickets and cosmetic loadouts
+pub const NFT_PROGRAM_ID: Pubkey = pubkey!("NFTExxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx");
+pub const MAX_FEATURE_FLAGS: usize = 16;
+pub const FEATURE_CREATE_DUEL: u8 = 0;
+pub const FEATURE_STAKE: u8 = 1;
+pub const FEATURE_BRIDGE: u8 = 2;
+pub const FULL_ROLLOUT_BPS: u16 = 10_000;
+
+#[account]
+#[derive(Default)]
+pub struct FeatureFlags {
+    pub authority: Pubkey,
+    pub enabled: u64, // bitset indexed by FEATURE_* constants
+    pub rollout_bps: [u16; MAX_FEATURE_FLAGS], // share of players who see an enabled flag
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl FeatureFlags {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // authority
+        8 + // enabled
+        2 * MAX_FEATURE_FLAGS + // rollout_bps
+        8 + // updated_at
+        1; // bump
+
+    /// Stable bucket in 0..10_000 for a player and flag, so rollouts widen
+    /// monotonically as the percentage grows
+    pub fn rollout_bucket(flag: u8, player: &Pubkey) -> u16 {
+        let hash = anchor_lang::solana_program::hash::hashv(&[b"feature", &[flag], player.as_ref()]).to_bytes();
+        u16::from_le_bytes([hash[0], hash[1]]) % FULL_ROLLOUT_BPS
+    }
+
+    pub fn is_enabled_for(&self, flag: u8, player: &Pubkey) -> bool {
+        if flag as usize >= MAX_FEATURE_FLAGS || self.enabled & (1u64 << flag) == 0 {
+            return false;
+        }
+        let rollout = self.rollout_bps[flag as usize];
+        rollout >= FULL_ROLLOUT_BPS || Self::rollout_bucket(flag, player) < rollout
+    }
+
+    pub fn set(&mut self, flag: u8, enabled: bool, rollout_bps: u16) {
+        if enabled {
+            self.enabled |= 1u64 << flag;
+        } else {
+            self.enabled &= !(1u64 << flag);
+        }
+        self.rollout_bps[flag as usize] = rollout_bps.min(FULL_ROLLOUT_BPS);
+    }
+}
+
+// Per-player rate limits on spam-prone entrypoints, indexed by RATE_LIMIT_* ids
+pub const RATE_LIMIT_CREATE_DUEL: u8 = 0;
+pub const RATE_LIMIT_QUERY_ENTITIES: u8 = 1;
+pub const RATE_LIMITED_ACTIONS: usize = 2;
+
+/// At most `max_calls` per `window_secs`; `max_calls == 0` disables the limit
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RateWindow {
+    pub window_secs: u32,
+    pub max_calls: u16,
+}
+
+impl RateWindow {
+    pub const LEN: usize = 4 + 2;
+
+    /// Limits used until governance configures its own
+    pub fn default_for(action: u8) -> Self {
+        match action {
+            RATE_LIMIT_CREATE_DUEL => RateWindow { window_secs: 60, max_calls: 5 },
+            RATE_LIMIT_QUERY_ENTITIES => RateWindow { window_secs: 60, max_calls: 30 },
+            _ => RateWindow::default(),
+        }
+    }
+}
+
+/// strategic-duel owns the deployment's rate limit config; other programs
+/// derive the PDA against it with `seeds::program`
+pub const STRATEGIC_DUEL_PROGRAM_ID: Pubkey = pubkey!("4afPz2WpaejNd2TrnneC4ybC7Us86WBqkJyQa7pnkkdr");
+pub const RATE_LIMIT_CONFIG_SEED: &[u8] = b"rate_limit_config";
+/// strategic-duel's system-owned PDA that signs the guild slice of settlement
+/// rake into guild treasuries; guild-program only takes rake deposits from it
+pub const GUILD_RAKE_AUTHORITY_SEED: &[u8] = b"guild_rake_authority";
+
+/// Governance-set windows, indexed by RATE_LIMIT_* ids
+#[account]
+#[derive(Default)]
+pub struct RateLimitConfig {
+    pub authority: Pubkey,
+    pub windows: [RateWindow; RATE_LIMITED_ACTIONS],
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl RateLimitConfig {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // authority
+        RATE_LIMITED_ACTIONS * RateWindow::LEN + // windows
+        8 + // updated_at
+        1; // bump
+
+    pub fn init(&mut self, authority: Pubkey, bump: u8, now: i64) {
+        self.authority = authority;
+        for (action, window) in self.windows.iter_mut().enumerate() {
+            *window = RateWindow::default_for(action as u8);
+        }
+        self.updated_at = now;
+        self.bump = bump;
+    }
+
+    /// Window in force for `action`: the config's once it exists, the defaults
+    /// until then. Callers pass the seeded PDA, so it cannot be swapped or omitted.
+    pub fn window_for(config: &AccountInfo, action: u8) -> Result<RateWindow> {
+        if config.data_is_empty() {
+            return Ok(RateWindow::default_for(action));
+        }
+        let config: Account<RateLimitConfig> = Account::try_from(config)?;
+        Ok(config.windows.get(action as usize).copied().unwrap_or_default())
+    }
+}
+
+/// Sliding-window counter for one player and entrypoint. The previous window's
+/// count is weighted by how much of it still overlaps the sliding window, so
+/// two fixed buckets approximate a true log without storing timestamps.
+#[account]
+#[derive(Default)]
+pub struct RateLimit {
+    pub player: Pubkey,
+    pub action: u8,
+    pub window_start: i64,
+    pub current_count: u16,
+    pub previous_count: u16,
+}
+
+impl RateLimit {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // player
+        1 + // action
+        8 + // window_start
+        2 + // current_count
+        2; // previous_count
+
+    fn roll(&mut self, window_secs: i64, now: i64) {
+        let elapsed = now.saturating_sub(self.window_start);
+        if elapsed < window_secs {
+            return;
+        }
+        self.previous_count = if elapsed < 2 * window_secs { self.current_count } else { 0 };
+        self.current_count = 0;
+        self.window_start = now - elapsed % window_secs;
+    }
+
+    /// Calls counted against the sliding window ending at `now`
+    fn estimated_calls(&self, window_secs: i64, now: i64) -> u64 {
+        let overlap = (window_secs - now.saturating_sub(self.window_start)).max(0) as u64;
+        self.previous_count as u64 * overlap / window_secs as u64 + self.current_count as u64
+    }
+
+    /// Count one call, or fail with RateLimitExceeded when the window is full
+    pub fn consume(&mut self, window: RateWindow, now: i64) -> Result<()> {
+        if window.max_calls == 0 || window.window_secs == 0 {
+            return Ok(());
+        }
+        let window_secs = window.window_secs as i64;
+        self.roll(window_secs, now);
+        require!(
+            self.estimated_calls(window_secs, now) < window.max_calls as u64,
+            GameError::RateLimitExceeded
+        );
+        self.current_count = self.current_count.saturating_add(1);
+        Ok(())
+    }
+}
+
+// Scheduled limited-time events (double-XP weekends, high-roller hours)
+pub const MAX_SCHEDULED_EVENTS: usize = 8;
+pub const NO_MODIFIER_BPS: u16 = 10_000;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ScheduledEvent {
+    pub event_id: u32,
+    pub starts_at: i64,
+    pub duration: i64,      // Seconds each occurrence lasts
+    pub period: i64,        // Seconds between occurrences, 0 for a one-off
+    pub xp_multiplier_bps: u16,
+    pub max_bet_multiplier_bps: u16,
+    pub active: bool,
+}
+
+impl ScheduledEvent {
+    pub fn is_live(&self, now: i64) -> bool {
+        if !self.active || now < self.starts_at {
+            return false;
+        }
+        let elapsed = now - self.starts_at;
+        if self.period > 0 {
+            elapsed % self.period < self.duration
+        } else {
+            elapsed < self.duration
+        }
+    }
+}
+
+/// Modifiers in effect at a moment; overlapping events take the larger bonus
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EventModifiers {
+    pub xp_multiplier_bps: u16,
+    pub max_bet_multiplier_bps: u16,
+}
+
+impl Default for EventModifiers {
+    fn default() -> Self {
+        Self {
+            xp_multiplier_bps: NO_MODIFIER_BPS,
+            max_bet_multiplier_bps: NO_MODIFIER_BPS,
+        }
+    }
+}
+
+impl EventModifiers {
+    pub fn apply_xp(&self, experience: u64) -> u64 {
+        (experience as u128 * self.xp_multiplier_bps as u128 / NO_MODIFIER_BPS as u128) as u64
+    }
+
+    pub fn apply_max_bet(&self, max_bet: u64) -> u64 {
+        (max_bet as u128 * self.max_bet_multiplier_bps as u128 / NO_MODIFIER_BPS as u128).min(u64::MAX as u128) as u64
+    }
+}
+
+#[account]
+#[derive(Default)]
+pub struct EventSchedule {
+    pub authority: Pubkey,
+    pub events: [ScheduledEvent; MAX_SCHEDULED_EVENTS],
+    pub next_event_id: u32,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl EventSchedule {
+    pub const EVENT_LEN: usize = 4 + 8 + 8 + 8 + 2 + 2 + 1;
+    pub const LEN: usize = 8 + // discriminator
+        32 + // authority
+        Self::EVENT_LEN * MAX_SCHEDULED_EVENTS + // events
+        4 + // next_event_id
+        8 + // updated_at
+        1; // bump
+
+    pub fn modifiers_at(&self, now: i64) -> EventModifiers {
+        self.events
+            .iter()
+            .filter(|event| event.is_live(now))
+            .fold(EventModifiers::default(), |modifiers, event| EventModifiers {
+                xp_multiplier_bps: modifiers.xp_multiplier_bps.max(event.xp_multiplier_bps),
+                max_bet_multiplier_bps: modifiers.max_bet_multiplier_bps.max(event.max_bet_multiplier_bps),
+            })
+    }
+
+    /// Store an event in the first free slot, returning its id
+    pub fn add(&mut self, mut event: ScheduledEvent) -> Option<u32> {
+        let slot = self.events.iter_mut().find(|existing| !existing.active)?;
+        event.event_id = self.next_event_id;
+        event.active = true;
+        *slot = event;
+        self.next_event_id += 1;
+        Some(event.event_id)
+    }
+
+    pub fn cancel(&mut self, event_id: u32) -> bool {
+        match self.events.iter_mut().find(|event| event.active && event.event_id == event_id) {
+            Some(event) => {
+                event.active = false;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Purely visual slots; cosmetics never touch combat stats
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CosmeticType {
+    Skin,
+    CardBack,
+    TableTheme,
+}
+
+pub const COSMETIC_SLOT_COUNT: usize = 3;
+
+/// Per-player cosmetic loadout, managed by the NFT program and read by the
+/// duel program so clients can render an opponent's cosmetics
+#[account]
+#[derive(Default)]
+pub struct CosmeticLoadout {
+    pub player: Pubkey,
+    pub equipped: [Option<Pubkey>; COSMETIC_SLOT_COUNT], // cosmetic mint per CosmeticType
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl CosmeticLoadout {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // player
+        COSMETIC_SLOT_COUNT * (1 + 32) + // equipped
+        8 + // updated_at
+        1; // bump
+
+    pub fn equipped_in(&self, slot: CosmeticType) -> Option<Pubkey> {
+        self.equipped[slot as usize]
+    }
+
+    /// Returns the mint previously in the slot, if any
+    pub fn equip(&mut self, slot: CosmeticType, mint: Pubkey) -> Option<Pubkey> {
+        self.equipped[slot as usize].replace(mint)
+    }
+
+    pub fn unequip(&mut self, slot: CosmeticType) -> Option<Pubkey> {
+        self.equipped[slot as usize].take()
+    }
+}
+
+pub const GEM_RECEIPT_SEED: &[u8] = b"gem_receipt";
+
+/// What gems are spent on
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GemSink {
+    Crate,
+    Cosmetic,
+    RematchFee,
+}
+
+/// Gems burned for a purchase another program delivers. game-program writes
+/// one at [GEM_RECEIPT_SEED, receipt_id]; the delivering program checks the
+/// owner and redeems it once by opening its own PDA keyed by the receipt.
+#[account]
+pub struct GemReceipt {
+    pub receipt_id: u64,
+    pub player: Pubkey,
+    pub sink: GemSink,
+    pub amount: u64,
+    pub target: Pubkey, // e.g. the drop table a crate is bought from
+    pub issued_at: i64,
+    pub bump: u8,
+}
+
+impl GemReceipt {
+    pub const LEN: usize = 8 + // discriminator
+        8 + // receipt_id
+        32 + // player
+        1 + // sink
+        8 + // amount
+        32 + // target
+        8 + // issued_at
+        1; // bump
+}
+
+/// Lifecycle of a strategic duel. The transition table lives here so every
+/// instruction that moves a duel checks against the same rules.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DuelState {
+    WaitingForPlayers,
+    InProgress,
+    AwaitingAction,
+    ResolutionPending,
+    Completed,
+    Cancelled,
+}
+
+impl Default for DuelState {
+    fn default() -> Self {
+        DuelState::WaitingForPlayers
+    }
+}
+
+/// Instruction-level events that move a duel between states
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DuelTrigger {
+    Join,          // Second seat filled
+    OpenRound,     // Round opened for actions
+    RoundComplete, // Both players acted
+    Fold,          // A player folded mid-round
+    Timeout,       // Action clock expired
+    FinalRound,    // Last round closed, awaiting VRF
+    Resolve,       // Winner determined
+    Cancel,        // Creator withdrew before anyone joined
+    Void,          // Voided by insurance or an admin
+    Recover,       // Rollup recovery back to a playable state
+    Forfeit,       // A player conceded; the opponent wins outright
+}
+
+pub const DUEL_TRIGGERS: [DuelTrigger; 11] = [
+    DuelTrigger::Join,
+    DuelTrigger::OpenRound,
+    DuelTrigger::RoundComplete,
+    DuelTrigger::Fold,
+    DuelTrigger::Timeout,
+    DuelTrigger::FinalRound,
+    DuelTrigger::Resolve,
+    DuelTrigger::Cancel,
+    DuelTrigger::Void,
+    DuelTrigger::Recover,
+    DuelTrigger::Forfeit,
+];
+
+impl DuelState {
+    pub const ALL: [DuelState; 6] = [
+        DuelState::WaitingForPlayers,
+        DuelState::InProgress,
+        DuelState::AwaitingAction,
+        DuelState::ResolutionPending,
+        DuelState::Completed,
+        DuelState::Cancelled,
+    ];
+
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, DuelState::Completed | DuelState::Cancelled)
+    }
+
+    /// The complete transition table; `None` means the trigger is not allowed here
+    pub fn next(self, trigger: DuelTrigger) -> Option<DuelState> {
+        use DuelState::*;
+        match (self, trigger) {
+            (WaitingForPlayers, DuelTrigger::Join) => Some(InProgress),
+            (InProgress, DuelTrigger::OpenRound) => Some(AwaitingAction),
+            (AwaitingAction, DuelTrigger::RoundComplete) => Some(InProgress),
+            (AwaitingAction, DuelTrigger::Fold) => Some(ResolutionPending),
+            (InProgress | AwaitingAction, DuelTrigger::Timeout) => Some(ResolutionPending),
+            (InProgress, DuelTrigger::FinalRound) => Some(ResolutionPending),
+            (ResolutionPending, DuelTrigger::Resolve) => Some(Completed),
+            (WaitingForPlayers, DuelTrigger::Cancel) => Some(Cancelled),
+            (state, DuelTrigger::Void) if !state.is_terminal() => Some(Cancelled),
+            (InProgress | AwaitingAction, DuelTrigger::Recover) => Some(InProgress),
+            (InProgress | AwaitingAction, DuelTrigger::Forfeit) => Some(Completed),
+            _ => None,
+        }
+    }
+
+    /// Whether any trigger moves this state to `to`
+    pub fn can_transition_to(self, to: DuelState) -> bool {
+        DUEL_TRIGGERS.iter().any(|&trigger| self.next(trigger) == Some(to))
+    }
+
+    /// Whether `to` is this state or a single transition away, for batched
+    /// updates such as rollup finalization. Resolution is left out: only the
+    /// VRF path may move a duel from ResolutionPending to Completed.
+    pub fn can_step_to(self, to: DuelState) -> bool {
+        self == to
+            || DUEL_TRIGGERS
+                .iter()
+                .any(|&trigger| trigger != DuelTrigger::Resolve && self.next(trigger) == Some(to))
+    }
+}
+
+pub const PAIRING_WINDOW_SEED: &[u8] = b"pairing_window";
+/// Window over which repeat pairings between two players are counted
+pub const REPEAT_OPPONENT_WINDOW: i64 = 86_400;
+/// Most matches between the same two players allowed inside the window
+pub const MAX_REPEAT_PAIRINGS: u16 = 3;
+
+/// Pairings between one unordered pair of players, keyed
+/// `[PAIRING_WINDOW_SEED, first, second]` with the keys in `ordered` order so
+/// whichever side creates, joins or is matched counts against the same record
+#[account]
+#[derive(Default)]
+pub struct PairingWindow {
+    pub first: Pubkey,
+    pub second: Pubkey,
+    pub window_start: i64,
+    pub count: u16,
+    pub total_pairings: u64,
+    pub bump: u8,
+}
+
+impl PairingWindow {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // first
+        32 + // second
+        8 + // window_start
+        2 + // count
+        8 + // total_pairings
+        1; // bump
+
+    pub fn ordered(a: Pubkey, b: Pubkey) -> (Pubkey, Pubkey) {
+        if a <= b { (a, b) } else { (b, a) }
+    }
+
+    /// Pairings inside the window still open at `now`
+    pub fn live_count(&self, now: i64) -> u16 {
+        if now < self.window_start.saturating_add(REPEAT_OPPONENT_WINDOW) { self.count } else { 0 }
+    }
+
+    /// Count a pairing between `a` and `b`, or fail with RepeatOpponentLimit once
+    /// the window is full. Returns the pairings in the window, this one included.
+    pub fn record(&mut self, a: Pubkey, b: Pubkey, bump: u8, now: i64) -> Result<u16> {
+        let (first, second) = Self::ordered(a, b);
+        if self.first == Pubkey::default() {
+            self.first = first;
+            self.second = second;
+            self.bump = bump;
+        }
+        require!(self.first == first && self.second == second, GameError::UnauthorizedPlayer);
+
+        let live = self.live_count(now);
+        require!(live < MAX_REPEAT_PAIRINGS, GameError::RepeatOpponentLimit);
+        if live == 0 {
+            self.window_start = now;
+        }
+        self.count = live + 1;
+        self.total_pairings += 1;
+        Ok(self.count)
+    }
+}
@@ -69,9 +69,411 @@ pub fn calculate_reward_share(total_pool: u64, percentage: u8) -> Result<u64> {
     Ok(share as u64)
 }
 
+/// Split `total` in proportion to `weights`. Each share rounds down and the
+/// leftover units go to the first share, so the split always sums to `total`.
+pub fn split_by_weights(total: u64, weights: &[u64]) -> Result<Vec<u64>> {
+    let weight_sum: u128 = weights.iter().map(|w| *w as u128).sum();
+    if weight_sum == 0 {
+        return Err(GameError::InvalidRewardDistribution.into());
+    }
+
+    let mut shares: Vec<u64> = weights
+        .iter()
+        .map(|w| (total as u128 * *w as u128 / weight_sum) as u64)
+        .collect();
+    let remainder = total - shares.iter().sum::<u64>();
+    shares[0] += remainder;
+
+    Ok(shares)
+}
+
+/// Leaf committed to the compliance allowlist for a player
+pub fn allowlist_leaf(player: &Pubkey) -> [u8; 32] {
+    anchor_lang::solana_program::hash::hashv(&[b"allowlist", player.as_ref()]).to_bytes()
+}
+
+/// Verify a merkle inclusion proof. Sibling pairs are hashed in sorted order so
+/// proofs don't need to carry left/right flags.
+pub fn verify_merkle_proof(leaf: [u8; 32], proof: &[[u8; 32]], root: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        let (left, right) = if node <= *sibling { (node, *sibling) } else { (*sibling, node) };
+        anchor_lang::solana_program::hash::hashv(&[&left, &right]).to_bytes()
+    });
+    computed == root
+}
+
+/// Guard an entrypoint behind a feature flag. The flags account is required,
+/// so a caller cannot skip the gate by leaving it out.
+pub fn require_feature(flags: &crate::state::FeatureFlags, flag: u8, player: &Pubkey) -> Result<()> {
+    require!(flags.is_enabled_for(flag, player), GameError::FeatureDisabled);
+    Ok(())
+}
+
+/// Event modifiers in effect now. Deployments without a schedule run unmodified.
+pub fn event_modifiers(schedule: Option<&crate::state::EventSchedule>, now: i64) -> crate::state::EventModifiers {
+    schedule.map(|schedule| schedule.modifiers_at(now)).unwrap_or_default()
+}
+
+/// Key that player-scoped PDAs derive from. Without an identity it is the signer;
+/// with one it is the identity's original wallet, so every linked wallet reaches
+/// the same profile. `signer_has_identity` says whether `[b"identity", signer]`
+/// exists (see `PlayerIdentity::exists`): that wallet must then come through its
+/// identity, so unlinking it or recovering the identity away from it locks it out
+/// of the profiles its key derives.
+pub fn resolve_player(
+    identity: Option<&crate::state::PlayerIdentity>,
+    signer_has_identity: bool,
+    signer: &Pubkey,
+    now: i64,
+) -> Result<Pubkey> {
+    match identity {
+        Some(identity) => {
+            require!(identity.can_play(signer, now), GameError::WalletNotLinked);
+            Ok(identity.player_key)
+        }
+        None => {
+            require!(!signer_has_identity, GameError::IdentityRequired);
+            Ok(*signer)
+        }
+    }
+}
+
+// Anti-sandbagging: lifetime winnings imply a rating floor for matchmaking
+pub const RATING_FLOOR_BASE: u32 = 1000;
+pub const RATING_FLOOR_STEP: u32 = 100; // Per doubling of winnings
+pub const RATING_FLOOR_WINNINGS_UNIT: u64 = 1_000_000_000; // 1 SOL
+pub const MAX_RATING_BELOW_FLOOR: u32 = 200;
+
+/// Rating a player with these lifetime winnings is expected to hold. Zero below one unit.
+pub fn rating_floor(lifetime_winnings: u64) -> u32 {
+    let units = lifetime_winnings / RATING_FLOOR_WINNINGS_UNIT;
+    if units == 0 {
+        return 0;
+    }
+    RATING_FLOOR_BASE.saturating_add(RATING_FLOOR_STEP.saturating_mul(units.ilog2()))
+}
+
+/// Rating used for pairing. Tanking ELO cannot drop a winning player more than
+/// MAX_RATING_BELOW_FLOOR under the floor their winnings imply.
+pub fn matchmaking_rating(rating: u32, lifetime_winnings: u64) -> u32 {
+    rating.max(rating_floor(lifetime_winnings).saturating_sub(MAX_RATING_BELOW_FLOOR))
+}
+
+// Glicko-2 in fixed point. Stored values carry GLICKO_FX (six decimals); the
+// update runs at a finer internal scale so the volatility iteration converges
+// the same way on every validator.
+pub const GLICKO_FX: i64 = 1_000_000;
+pub const GLICKO_INITIAL_RATING: i64 = 1500 * GLICKO_FX;
+pub const GLICKO_INITIAL_DEVIATION: i64 = 350 * GLICKO_FX;
+pub const GLICKO_INITIAL_VOLATILITY: i64 = 60_000; // 0.06
+/// Score for a win, draw and loss
+pub const GLICKO_WIN: i64 = GLICKO_FX;
+pub const GLICKO_DRAW: i64 = GLICKO_FX / 2;
+pub const GLICKO_LOSS: i64 = 0;
+/// Inactivity that counts as one empty rating period, growing deviation
+pub const GLICKO_RATING_PERIOD: i64 = 7 * 24 * 60 * 60;
+
+const FX: i128 = 1_000_000_000;
+const FX_PER_STORED: i128 = FX / GLICKO_FX as i128;
+const FX_LN2: i128 = 693_147_181;
+const FX_LN10: i128 = 2_302_585_093;
+const FX_PI_SQUARED: i128 = 9_869_604_401;
+const GLICKO2_SCALE: i128 = 173_717_800_000; // 173.7178
+const GLICKO2_TAU: i128 = 500_000_000; // System constant limiting volatility change
+const GLICKO2_EPSILON: i128 = 1_000;
+const GLICKO2_MAX_ITERATIONS: u32 = 100;
+
+/// A player's Glicko-2 state; all zero until the first rated result
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct Glicko2Rating {
+    pub rating: i64,
+    pub deviation: i64,
+    pub volatility: i64,
+}
+
+impl Glicko2Rating {
+    pub const INITIAL: Self = Self {
+        rating: GLICKO_INITIAL_RATING,
+        deviation: GLICKO_INITIAL_DEVIATION,
+        volatility: GLICKO_INITIAL_VOLATILITY,
+    };
+
+    /// This rating, or the initial one for a player who has never been rated
+    pub fn or_initial(self) -> Self {
+        if self.deviation == 0 { Self::INITIAL } else { self }
+    }
+
+    /// Whole rating points, for display and matchmaking
+    pub fn display_rating(&self) -> u32 {
+        (self.or_initial().rating / GLICKO_FX).max(0) as u32
+    }
+
+    /// This rating after `periods` empty rating periods: deviation grows by the
+    /// volatility each period, up to an unrated player's. Unrated stays unrated.
+    pub fn aged(self, periods: i64) -> Self {
+        if self.deviation == 0 || periods <= 0 {
+            return self;
+        }
+        let step = self.volatility as i128 * GLICKO2_SCALE / FX;
+        let variance = (self.deviation as i128).pow(2) + periods as i128 * step * step;
+        let deviation = (isqrt(variance as u128) as i128).min(GLICKO_INITIAL_DEVIATION as i128);
+        Self { deviation: deviation as i64, ..self }
+    }
+}
+
+fn fx_mul(a: i128, b: i128) -> i128 {
+    a * b / FX
+}
+
+fn fx_div(a: i128, b: i128) -> i128 {
+    a * FX / b
+}
+
+fn isqrt(n: u128) -> u128 {
+    let (mut x, mut y) = (n, (n + 1) / 2);
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    x
+}
+
+fn fx_sqrt(a: i128) -> i128 {
+    if a <= 0 {
+        return 0;
+    }
+    isqrt((a * FX) as u128) as i128
+}
+
+fn fx_exp(x: i128) -> i128 {
+    if x < -40 * FX {
+        return 0;
+    }
+    let x = x.min(40 * FX);
+    // e^x = 2^k * e^r with |r| <= ln2 / 2
+    let k = (x + if x >= 0 { FX_LN2 / 2 } else { -FX_LN2 / 2 }) / FX_LN2;
+    let r = x - k * FX_LN2;
+    let (mut sum, mut term, mut n) = (FX, FX, 1);
+    while term != 0 {
+        term = fx_mul(term, r) / n;
+        sum += term;
+        n += 1;
+    }
+    if k >= 0 { sum << k } else { sum >> -k }
+}
+
+fn fx_ln(x: i128) -> i128 {
+    // ln x = k ln2 + ln m with m in [1, 2), and ln m = 2 atanh((m - 1) / (m + 1))
+    let (mut m, mut k) = (x.max(1), 0i128);
+    while m >= 2 * FX {
+        m /= 2;
+        k += 1;
+    }
+    while m < FX {
+        m *= 2;
+        k -= 1;
+    }
+    let y = fx_div(m - FX, m + FX);
+    let y_squared = fx_mul(y, y);
+    let (mut sum, mut term, mut n) = (0, y, 1);
+    while term != 0 {
+        sum += term / n;
+        term = fx_mul(term, y_squared);
+        n += 2;
+    }
+    k * FX_LN2 + 2 * sum
+}
+
+fn glicko2_g(phi: i128) -> i128 {
+    fx_div(FX, fx_sqrt(FX + fx_div(3 * fx_mul(phi, phi), FX_PI_SQUARED)))
+}
+
+/// Glicko-2 update for one rating period holding a single game against
+/// `opponent`. `score` is GLICKO_WIN, GLICKO_DRAW or GLICKO_LOSS.
+pub fn glicko2_update(player: Glicko2Rating, opponent: Glicko2Rating, score: i64) -> Glicko2Rating {
+    let (player, opponent) = (player.or_initial(), opponent.or_initial());
+    let to_fx = |value: i64| value as i128 * FX_PER_STORED;
+    let mu = fx_div(to_fx(player.rating) - to_fx(GLICKO_INITIAL_RATING), GLICKO2_SCALE);
+    let phi = fx_div(to_fx(player.deviation), GLICKO2_SCALE);
+    let opponent_mu = fx_div(to_fx(opponent.rating) - to_fx(GLICKO_INITIAL_RATING), GLICKO2_SCALE);
+    let g = glicko2_g(fx_div(to_fx(opponent.deviation), GLICKO2_SCALE));
+    let score = to_fx(score);
+
+    let expected = fx_div(FX, FX + fx_exp(-fx_mul(g, mu - opponent_mu)));
+    let variance = fx_div(FX, fx_mul(fx_mul(g, g), fx_mul(expected, FX - expected)).max(1));
+    let delta = fx_mul(fx_mul(variance, g), score - expected);
+
+    // New volatility by the Illinois iteration from Glickman's paper
+    let phi_squared = fx_mul(phi, phi);
+    let delta_squared = fx_mul(delta, delta);
+    let a = fx_ln(fx_mul(to_fx(player.volatility), to_fx(player.volatility)));
+    let tau_squared = fx_mul(GLICKO2_TAU, GLICKO2_TAU);
+    let f = |x: i128| {
+        let ex = fx_exp(x);
+        let denominator = phi_squared + variance + ex;
+        fx_div(fx_mul(ex, delta_squared - phi_squared - variance - ex), 2 * fx_mul(denominator, denominator))
+            - fx_div(x - a, tau_squared)
+    };
+
+    let mut lower = a;
+    let mut upper = if delta_squared > phi_squared + variance {
+        fx_ln(delta_squared - phi_squared - variance)
+    } else {
+        let mut k = 1;
+        while f(a - k * GLICKO2_TAU) < 0 && k < GLICKO2_MAX_ITERATIONS as i128 {
+            k += 1;
+        }
+        a - k * GLICKO2_TAU
+    };
+    let (mut f_lower, mut f_upper) = (f(lower), f(upper));
+    let mut iterations = 0;
+    while (upper - lower).abs() > GLICKO2_EPSILON && iterations < GLICKO2_MAX_ITERATIONS && f_upper != f_lower {
+        let c = lower + fx_div(fx_mul(lower - upper, f_lower), f_upper - f_lower);
+        let f_c = f(c);
+        if f_c.signum() * f_upper.signum() <= 0 {
+            lower = upper;
+            f_lower = f_upper;
+        } else {
+            f_lower /= 2;
+        }
+        upper = c;
+        f_upper = f_c;
+        iterations += 1;
+    }
+    let volatility = fx_exp(lower / 2);
+
+    let phi_star_squared = phi_squared + fx_mul(volatility, volatility);
+    let new_phi = fx_div(FX, fx_sqrt(fx_div(FX, phi_star_squared) + fx_div(FX, variance)));
+    let new_mu = mu + fx_mul(fx_mul(fx_mul(new_phi, new_phi), g), score - expected);
+
+    Glicko2Rating {
+        rating: ((to_fx(GLICKO_INITIAL_RATING) + fx_mul(new_mu, GLICKO2_SCALE)) / FX_PER_STORED).max(0) as i64,
+        deviation: (fx_mul(new_phi, GLICKO2_SCALE) / FX_PER_STORED).min(GLICKO_INITIAL_DEVIATION as i128) as i64,
+        volatility: (volatility / FX_PER_STORED) as i64,
+    }
+}
+
+/// Share of the pot, in bps, at which a player with Elo expected score E against
+/// `opponent` breaks even: 1/(2E). Only a favorite takes less than the full pot.
+pub fn elo_break_even_share_bps(rating: u32, opponent: u32) -> u16 {
+    if rating <= opponent {
+        return 10_000;
+    }
+    // 1/(2E) = (1 + 10^(-d/400)) / 2 for a favorite d points ahead
+    let odds = fx_exp(-((rating - opponent) as i128) * FX_LN10 / 400);
+    ((10_000 * (FX + odds) + FX) / (2 * FX)) as u16
+}
+
+/// Reject calls that arrive through CPI unless the transaction's outer
+/// instruction belongs to an allowlisted program. A CPI back into
+/// `program_id` from its own outer instruction is always rejected, since
+/// that is a reentrant call.
+pub fn check_cpi_origin(
+    instructions_sysvar: &AccountInfo,
+    program_id: &Pubkey,
+    allowed_callers: &[Pubkey],
+) -> Result<()> {
+    use anchor_lang::solana_program::instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT};
+    use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+
+    if get_stack_height() <= TRANSACTION_LEVEL_STACK_HEIGHT {
+        return Ok(());
+    }
+
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    let outer = load_instruction_at_checked(current_index as usize, instructions_sysvar)?;
+    if outer.program_id == *program_id || !allowed_callers.contains(&outer.program_id) {
+        return Err(GameError::CpiCallerNotAllowed.into());
+    }
+    Ok(())
+}
+
+/// Signature carried by ed25519 program instruction data that verifies exactly
+/// one `message` signed by `signer`, with all offsets pointing into that same
+/// instruction
+pub fn ed25519_signature(data: &[u8], signer: &Pubkey, message: &[u8]) -> Option<[u8; 64]> {
+    // Header: count (u8), padding (u8), then one 14-byte offsets record
+    if data.len() < 16 || data[0] != 1 {
+        return None;
+    }
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let same_ix = |at: usize| read_u16(at) == u16::MAX;
+    if !(same_ix(4) && same_ix(8) && same_ix(14)) {
+        return None;
+    }
+
+    let signature_offset = read_u16(2) as usize;
+    let key_offset = read_u16(6) as usize;
+    let message_offset = read_u16(10) as usize;
+    let message_len = read_u16(12) as usize;
+    if data.get(key_offset..key_offset + 32) != Some(signer.as_ref())
+        || data.get(message_offset..message_offset + message_len) != Some(message)
+    {
+        return None;
+    }
+    data.get(signature_offset..signature_offset + 64)?.try_into().ok()
+}
+
+/// Signature from the ed25519 verification immediately before the current
+/// instruction, which must cover `message` signed by `signer`
+pub fn preceding_ed25519_signature(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<[u8; 64]> {
+    use anchor_lang::solana_program::ed25519_program;
+    use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    require!(current_index > 0, GameError::InvalidSignature);
+    let ix = load_instruction_at_checked((current_index - 1) as usize, instructions_sysvar)?;
+    require_keys_eq!(ix.program_id, ed25519_program::ID, GameError::InvalidSignature);
+    ed25519_signature(&ix.data, signer, message).ok_or_else(|| GameError::InvalidSignature.into())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::metrics::*;
+
+    #[test]
+    fn test_metrics_ring_wraps() {
+        let mut buffer = MetricsBuffer::default();
+        for i in 0..METRICS_BUFFER_SIZE + 3 {
+            buffer.record(MetricSample { slot: i as u64, ..MetricSample::default() });
+        }
+
+        // The oldest samples are overwritten in order and the head follows them
+        assert_eq!(buffer.total_samples, (METRICS_BUFFER_SIZE + 3) as u64);
+        assert_eq!(buffer.head, 3);
+        assert_eq!(buffer.samples[0].slot, METRICS_BUFFER_SIZE as u64);
+        assert_eq!(buffer.samples[2].slot, METRICS_BUFFER_SIZE as u64 + 2);
+        assert_eq!(buffer.samples[3].slot, 3);
+
+        assert!(metrics_shard(&Pubkey::new_unique()) < METRICS_SHARDS);
+    }
+
+    #[test]
+    fn test_ed25519_signature_offsets() {
+        let signer = Pubkey::new_unique();
+        let message = [7u8; 32];
+        let signature = [9u8; 64];
+        // Offsets record, then key, signature and message in the same instruction
+        let mut data = vec![1u8, 0];
+        for value in [48u16, u16::MAX, 16, u16::MAX, 112, 32, u16::MAX] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(signer.as_ref());
+        data.extend_from_slice(&signature);
+        data.extend_from_slice(&message);
+
+        assert_eq!(ed25519_signature(&data, &signer, &message), Some(signature));
+        assert_eq!(ed25519_signature(&data, &Pubkey::new_unique(), &message), None);
+        assert_eq!(ed25519_signature(&data, &signer, &[8u8; 32]), None);
+        // A key pulled from another instruction is not accepted
+        data[8] = 0;
+        assert_eq!(ed25519_signature(&data, &signer, &message), None);
+    }
     
     #[test]
     fn test_damage_calculation() {
@@ -89,6 +491,13 @@ mod tests {
         assert_eq!(share, 500);
     }
     
+    #[test]
+    fn test_split_by_weights() {
+        assert_eq!(split_by_weights(1001, &[50, 30, 20]).unwrap(), vec![501, 300, 200]);
+        assert_eq!(split_by_weights(10, &[1, 1, 1]).unwrap(), vec![4, 3, 3]);
+        assert!(split_by_weights(10, &[]).is_err());
+    }
+    
     #[test]
     fn test_turn_validation() {
         let next_turn = validate_turn_order(0, 4).unwrap();
@@ -97,4 +506,247 @@ mod tests {
         let wrap_turn = validate_turn_order(3, 4).unwrap();
         assert_eq!(wrap_turn, 0);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_merkle_allowlist_proof() {
+        let (a, b, c) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let hash_pair = |x: [u8; 32], y: [u8; 32]| {
+            let (l, r) = if x <= y { (x, y) } else { (y, x) };
+            anchor_lang::solana_program::hash::hashv(&[&l, &r]).to_bytes()
+        };
+        let ab = hash_pair(allowlist_leaf(&a), allowlist_leaf(&b));
+        let root = hash_pair(ab, allowlist_leaf(&c));
+        
+        assert!(verify_merkle_proof(allowlist_leaf(&a), &[allowlist_leaf(&b), allowlist_leaf(&c)], root));
+        assert!(verify_merkle_proof(allowlist_leaf(&c), &[ab], root));
+        assert!(!verify_merkle_proof(allowlist_leaf(&Pubkey::new_unique()), &[ab], root));
+    }
+    
+    #[test]
+    fn test_feature_flag_rollout() {
+        let mut flags = crate::state::FeatureFlags::default();
+        let player = Pubkey::new_unique();
+        
+        // A flag that was never enabled blocks everyone
+        assert!(require_feature(&flags, crate::state::FEATURE_STAKE, &player).is_err());
+        
+        flags.set(crate::state::FEATURE_STAKE, true, crate::state::FULL_ROLLOUT_BPS);
+        assert!(require_feature(&flags, crate::state::FEATURE_STAKE, &player).is_ok());
+        
+        // Partial rollout follows the player's stable bucket
+        let bucket = crate::state::FeatureFlags::rollout_bucket(crate::state::FEATURE_STAKE, &player);
+        flags.set(crate::state::FEATURE_STAKE, true, bucket);
+        assert!(!flags.is_enabled_for(crate::state::FEATURE_STAKE, &player));
+        flags.set(crate::state::FEATURE_STAKE, true, bucket + 1);
+        assert!(flags.is_enabled_for(crate::state::FEATURE_STAKE, &player));
+    }
+    
+    #[test]
+    fn test_identity_resolution() {
+        let (cold, hot, session) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let identity = crate::state::PlayerIdentity {
+            player_key: hot,
+            cold_wallet: cold,
+            wallets: vec![
+                crate::state::LinkedWallet { wallet: hot, role: crate::state::WalletRole::Hot, expires_at: 0 },
+                crate::state::LinkedWallet { wallet: session, role: crate::state::WalletRole::Session, expires_at: 100 },
+            ],
+            ..Default::default()
+        };
+        
+        assert_eq!(resolve_player(Some(&identity), false, &cold, 0).unwrap(), hot);
+        assert_eq!(resolve_player(Some(&identity), false, &session, 50).unwrap(), hot);
+        // Expired session keys and unlinked wallets are rejected
+        assert!(resolve_player(Some(&identity), false, &session, 100).is_err());
+        assert!(resolve_player(Some(&identity), false, &Pubkey::new_unique(), 0).is_err());
+        assert_eq!(resolve_player(None, false, &session, 0).unwrap(), session);
+        
+        // The player key cannot drop the identity to reach its profile directly,
+        // so once it is unlinked it is locked out entirely
+        assert!(resolve_player(None, true, &hot, 0).is_err());
+        let mut unlinked = identity.clone();
+        unlinked.wallets.retain(|linked| linked.wallet != hot);
+        assert!(resolve_player(Some(&unlinked), true, &hot, 0).is_err());
+    }
+    
+    #[test]
+    fn test_recovery_locks_out_previous_wallets() {
+        let (cold, hot, session, recovered) =
+            (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let mut identity = crate::state::PlayerIdentity {
+            player_key: hot,
+            cold_wallet: cold,
+            wallets: vec![
+                crate::state::LinkedWallet { wallet: hot, role: crate::state::WalletRole::Hot, expires_at: 0 },
+                crate::state::LinkedWallet { wallet: session, role: crate::state::WalletRole::Session, expires_at: 0 },
+            ],
+            ..Default::default()
+        };
+        
+        // What execute_recovery does to the identity
+        identity.cold_wallet = recovered;
+        identity.wallets.clear();
+        
+        // No previous wallet can act for the identity, with or without passing it
+        for wallet in [cold, hot, session] {
+            assert!(resolve_player(Some(&identity), wallet == hot, &wallet, 0).is_err());
+        }
+        assert!(resolve_player(None, true, &hot, 0).is_err());
+        // The recovered cold wallet reaches the same profiles
+        assert_eq!(resolve_player(Some(&identity), false, &recovered, 0).unwrap(), hot);
+    }
+    
+    #[test]
+    fn test_event_schedule_windows() {
+        let mut schedule = crate::state::EventSchedule::default();
+        assert_eq!(event_modifiers(Some(&schedule), 0), crate::state::EventModifiers::default());
+        
+        // Weekly two-day double XP starting at t=1000, plus a one-off high-roller hour
+        let weekend = schedule.add(crate::state::ScheduledEvent {
+            starts_at: 1_000,
+            duration: 2 * 86_400,
+            period: 7 * 86_400,
+            xp_multiplier_bps: 20_000,
+            max_bet_multiplier_bps: crate::state::NO_MODIFIER_BPS,
+            ..Default::default()
+        }).unwrap();
+        schedule.add(crate::state::ScheduledEvent {
+            starts_at: 5_000,
+            duration: 3_600,
+            xp_multiplier_bps: crate::state::NO_MODIFIER_BPS,
+            max_bet_multiplier_bps: 50_000,
+            ..Default::default()
+        }).unwrap();
+        
+        let live = schedule.modifiers_at(5_500);
+        assert_eq!(live.apply_xp(100), 200);
+        assert_eq!(live.apply_max_bet(1_000), 5_000);
+        
+        // Recurs next week, one-off does not
+        let next_week = schedule.modifiers_at(1_000 + 7 * 86_400 + 10);
+        assert_eq!(next_week.xp_multiplier_bps, 20_000);
+        assert_eq!(next_week.max_bet_multiplier_bps, crate::state::NO_MODIFIER_BPS);
+        assert_eq!(schedule.modifiers_at(1_000 + 3 * 86_400), crate::state::EventModifiers::default());
+        
+        assert!(schedule.cancel(weekend));
+        assert!(!schedule.cancel(weekend));
+        assert_eq!(schedule.modifiers_at(1_000 + 7 * 86_400 + 10).xp_multiplier_bps, crate::state::NO_MODIFIER_BPS);
+    }
+    
+    #[test]
+    fn test_rating_floor_from_winnings() {
+        assert_eq!(rating_floor(RATING_FLOOR_WINNINGS_UNIT - 1), 0);
+        assert_eq!(rating_floor(RATING_FLOOR_WINNINGS_UNIT), RATING_FLOOR_BASE);
+        assert_eq!(rating_floor(8 * RATING_FLOOR_WINNINGS_UNIT), RATING_FLOOR_BASE + 3 * RATING_FLOOR_STEP);
+        
+        // A tanked rating is lifted to just under the floor; honest ratings pass through
+        let winnings = 8 * RATING_FLOOR_WINNINGS_UNIT;
+        assert_eq!(matchmaking_rating(400, winnings), RATING_FLOOR_BASE + 3 * RATING_FLOOR_STEP - MAX_RATING_BELOW_FLOOR);
+        assert_eq!(matchmaking_rating(1800, winnings), 1800);
+        assert_eq!(matchmaking_rating(400, 0), 400);
+    }
+    
+    #[test]
+    fn test_glicko2_update() {
+        let rating = |rating: i64, deviation: i64| Glicko2Rating {
+            rating: rating * GLICKO_FX,
+            deviation: deviation * GLICKO_FX,
+            volatility: GLICKO_INITIAL_VOLATILITY,
+        };
+        let close = |actual: i64, expected: i64| (actual - expected).abs() <= 10;
+
+        // Glickman's worked example, first opponent only
+        let updated = glicko2_update(rating(1500, 200), rating(1400, 30), GLICKO_WIN);
+        assert!(close(updated.rating, 1_563_564_194), "{:?}", updated);
+        assert!(close(updated.deviation, 175_402_655), "{:?}", updated);
+        assert!(close(updated.volatility, 59_998), "{:?}", updated);
+
+        // Unrated players start from the defaults; a draw between equals only tightens deviation
+        let drawn = glicko2_update(Glicko2Rating::default(), Glicko2Rating::default(), GLICKO_DRAW);
+        assert_eq!(drawn.rating, GLICKO_INITIAL_RATING);
+        assert!(close(drawn.deviation, 290_318_962), "{:?}", drawn);
+
+        // An upset loss costs a confident favourite little
+        let upset = glicko2_update(rating(1800, 60), rating(1200, 300), GLICKO_LOSS);
+        assert!(close(upset.rating, 1_785_774_020), "{:?}", upset);
+        assert_eq!(upset.display_rating(), 1785);
+    }
+
+    #[test]
+    fn test_glicko2_aging() {
+        let settled = Glicko2Rating { rating: 1700 * GLICKO_FX, deviation: 50 * GLICKO_FX, volatility: GLICKO_INITIAL_VOLATILITY };
+
+        // No time away, or never rated, changes nothing
+        assert_eq!(settled.aged(0), settled);
+        assert_eq!(Glicko2Rating::default().aged(10), Glicko2Rating::default());
+
+        // Ten idle periods: sqrt(50^2 + 10 * (0.06 * 173.7178)^2) ~ 59.89
+        let idle = settled.aged(10);
+        assert_eq!(idle.rating, settled.rating);
+        assert!((idle.deviation - 59_886_588).abs() <= 10, "{:?}", idle);
+
+        // A long absence is no more certain than a new player
+        assert_eq!(settled.aged(10_000).deviation, GLICKO_INITIAL_DEVIATION);
+    }
+
+    #[test]
+    fn test_elo_break_even_share() {
+        assert_eq!(elo_break_even_share_bps(1500, 1500), 10_000);
+        assert_eq!(elo_break_even_share_bps(1200, 1600), 10_000);
+        // 10^(-400/400) = 0.1 and 10^(-800/400) = 0.01
+        assert_eq!(elo_break_even_share_bps(1600, 1200), 5_500);
+        assert_eq!(elo_break_even_share_bps(2000, 1200), 5_050);
+    }
+
+    #[test]
+    fn test_duel_transition_matrix() {
+        use crate::state::{DuelState, DuelTrigger, DUEL_TRIGGERS};
+        use DuelState::*;
+        
+        // Rows follow DuelState::ALL, columns follow DUEL_TRIGGERS:
+        // Join, OpenRound, RoundComplete, Fold, Timeout, FinalRound, Resolve, Cancel, Void, Recover, Forfeit
+        let expected: [[Option<DuelState>; 11]; 6] = [
+            [Some(InProgress), None, None, None, None, None, None, Some(Cancelled), Some(Cancelled), None, None],
+            [None, Some(AwaitingAction), None, None, Some(ResolutionPending), Some(ResolutionPending), None, None, Some(Cancelled), Some(InProgress), Some(Completed)],
+            [None, None, Some(InProgress), Some(ResolutionPending), Some(ResolutionPending), None, None, None, Some(Cancelled), Some(InProgress), Some(Completed)],
+            [None, None, None, None, None, None, Some(Completed), None, Some(Cancelled), None, None],
+            [None; 11],
+            [None; 11],
+        ];
+        
+        for (row, state) in DuelState::ALL.iter().enumerate() {
+            for (col, trigger) in DUEL_TRIGGERS.iter().enumerate() {
+                assert_eq!(state.next(*trigger), expected[row][col], "{:?} on {:?}", state, trigger);
+            }
+        }
+        
+        assert!(AwaitingAction.can_transition_to(ResolutionPending));
+        assert!(!Completed.can_transition_to(Cancelled));
+        assert!(!WaitingForPlayers.can_step_to(Completed));
+        assert!(!ResolutionPending.can_step_to(InProgress));
+        assert!(!ResolutionPending.can_step_to(Completed));
+        assert!(AwaitingAction.can_step_to(Completed));
+        assert!(InProgress.can_step_to(InProgress));
+        assert_eq!(AwaitingAction.next(DuelTrigger::Join), None);
+    }
+
+    #[test]
+    fn test_pairing_window_counts_both_sides() {
+        use crate::state::{PairingWindow, REPEAT_OPPONENT_WINDOW};
+        
+        let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut window = PairingWindow::default();
+        
+        // Alternating who creates and who joins still lands on one counter
+        assert_eq!(window.record(alice, bob, 255, 1_000).unwrap(), 1);
+        assert_eq!(window.record(bob, alice, 255, 2_000).unwrap(), 2);
+        assert_eq!(window.record(alice, bob, 255, 3_000).unwrap(), 3);
+        assert!(window.record(bob, alice, 255, 4_000).is_err());
+        assert!(window.record(alice, Pubkey::new_unique(), 255, 4_000).is_err());
+        
+        // The cap lifts once the window has passed
+        assert_eq!(window.live_count(1_000 + REPEAT_OPPONENT_WINDOW), 0);
+        assert_eq!(window.record(bob, alice, 255, 1_000 + REPEAT_OPPONENT_WINDOW).unwrap(), 1);
+        assert_eq!(window.total_pairings, 4);
+    }
+}
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+// Checks for `/// CHECK` accounts, written as bool predicates so contexts can
+// use them directly in `constraint = ... @ GameError::...` clauses
+
+/// A plain wallet: system-owned with no data. Anything the program pays out
+/// lamports to by key must be one, so payouts cannot be routed into another
+/// program's state. Unfunded addresses also qualify.
+pub fn is_wallet(info: &AccountInfo) -> bool {
+    info.owner == &anchor_lang::system_program::ID && info.data_is_empty()
+}
+
+/// The deployed program at `program_id`
+pub fn is_program(info: &AccountInfo, program_id: &Pubkey) -> bool {
+    info.key == program_id && info.executable
+}
+
+/// An initialized account owned by `owner` holding at least `min_len` bytes;
+/// for state read from another program
+pub fn is_owned_account(info: &AccountInfo, owner: &Pubkey, min_len: usize) -> bool {
+    info.owner == owner && info.data_len() >= min_len
+}
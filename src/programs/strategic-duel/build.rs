@@ -26,7 +26,9 @@ fn main() {
     verify_bolt_compatibility();
     
     // Set optimization flags for MagicBlock deployment
-    set_magicblock_optimizations();
+    if std::env::var_os("CARGO_FEATURE_MAGICBLOCK").is_some() {
+        set_magicblock_optimizations();
+    }
 }
 
 fn verify_bolt_compatibility() {
@@ -17,12 +17,13 @@ pub use betting::*;
 #[component]
 #[derive(Default)]
 pub struct DuelComponent {
+    // Leading fields sit at fixed offsets for memcmp filters; see shared::DUEL_GAME_STATE_OFFSET
+    pub game_state: GameState,
     pub duel_id: u64,
     pub player_one: Pubkey,
     pub player_two: Pubkey,
     pub current_round: u8,
     pub max_rounds: u8,
-    pub game_state: GameState,
     pub winner: Option<Pubkey>,
     pub start_time: i64,
     pub last_action_time: i64,
@@ -37,6 +38,73 @@ pub struct DuelComponent {
     pub rollup_id: Option<[u8; 32]>,
     pub weights_validated: bool,
     pub transcript_validated: bool,
+    pub is_practice: bool,
+    pub last_actor: Pubkey,
+    pub outcome_evaluator: OutcomeEvaluator,
+    pub randomness_provider: RandomnessProvider,
+    // Set once the state has been exported for migration; the duel is frozen
+    pub migration_locked: bool,
+    // ER sequencer allowed to attest network latency for decision timing
+    pub latency_authority: Pubkey,
+    // Minimum reputation a joiner needs
+    pub min_reputation: u16,
+    // Reconnect grace
+    pub reconnect_grace_period: i64,
+    pub grace_extension: i64,
+    // Replay log tracking
+    pub replay_state: ReplayState,
+    pub replay_chunk_count: u16,
+    pub replay_head_hash: [u8; 32],
+    // Holding requirement for both seats on gated tables
+    pub table_gate: TableGate,
+    // Player-contributed entropy mixed into the VRF output, indexed by seat
+    pub entropy_commitments: [[u8; 32]; 2],
+    pub entropy_reveals: [[u8; 32]; 2],
+    pub entropy_revealed: [bool; 2],
+    // Commitments left unopened past this forfeit; set when resolution is requested
+    pub entropy_reveal_deadline: i64,
+    // Running hash over every logged action; see extend_transcript
+    pub action_transcript: [u8; 32],
+    // Set at resolution; see compute_transcript_hash
+    pub transcript_hash: [u8; 32],
+    // Jackpot roll drawn from the resolved randomness, fixed before settlement
+    pub jackpot_roll: [u8; 32],
+    // Performance-weighted resolution; weights are committed on entering
+    // ResolutionPending, before any randomness exists
+    pub round_wins: [u8; 2],
+    pub round_start_bets: [u64; 2],
+    pub weights_bps: [u16; 2],
+    pub weights_hash: [u8; 32],
+    // Handicap terms, fixed from both ratings once the second seat joins
+    pub handicap: HandicapMode,
+    pub join_ratings: [u32; 2],
+    pub payout_share_bps: [u16; 2],
+    // Mutually agreed break, once per match; the timer is frozen until `paused_until`
+    pub pause_proposer: Option<u8>,
+    pub pause_proposed_duration: i64,
+    pub paused_until: i64,
+    pub pause_used: bool,
+    // Concession terms: share of unspent blinds returned on an early forfeit
+    pub early_forfeit_refund_bps: u16,
+    pub forfeited_by: Option<Pubkey>,
+    pub forfeit_refund: u64,
+    // Exact tie reported by the evaluator; settlement splits the pot
+    pub is_draw: bool,
+    pub rating_system: RatingSystem, // Filled from QueueRatingConfig at settlement
+    pub queue_type: QueueType,
+    // Left out of the lobby and delayed feeds; see SpectatorState for who may
+    // watch. The accounts and events themselves stay public
+    pub is_private: bool,
+    // Lamports each seat escrowed on the duel account; zero for chip-only duels
+    pub escrowed_stake: u64,
+    // Part of each seat's stake an insurance claim already paid out; the pool
+    // takes it from escrow, so a refund cannot return it a second time
+    pub escrow_claimed: [u64; 2],
+    // Covered failure the first insurance claim voided the duel for, so every
+    // other policy on the duel can still claim against it
+    pub protocol_failure: FailureReason,
+    // Canonical PDA bump, stored at init; see backfill_component_bumps
+    pub bump: u8,
 }
 
 /// PlayerComponent - Individual player statistics and state
@@ -51,10 +119,14 @@ pub struct PlayerComponent {
     pub is_active: bool,
     pub position: PlayerPosition,
     pub skill_rating: u32,
+    pub rating_deviation: u16, // Copied from the profile at join; sets this duel's K-factor
     pub games_played: u64,
     pub games_won: u64,
     pub total_winnings: u64,
     pub last_seen: i64,
+    pub reconnect_used: bool,
+    pub pending_sync_nonce: u64,
+    pub bump: u8,
 }
 
 /// ActionComponent - Player action tracking and validation
@@ -87,6 +159,31 @@ pub struct PsychProfileComponent {
     pub late_game_behavior: u16,
     pub sample_size: u32,
     pub last_updated: i64,
+    // Reputation inputs, tracked across every duel the player joins
+    pub duels_started: u32,
+    pub duels_completed: u32,
+    pub timeouts: u32,
+    pub disputes: u32,
+    pub collusion_flags: u32,
+    pub reputation: u16, // 0-1000, independent of skill rating
+    pub forfeits: u32,
+    // Ratings and results, kept apart per queue; indexed by QueueType
+    pub tracks: [RatingTrack; shared::QUEUE_TYPES],
+    pub bump: u8,
+}
+
+/// One queue's rating track on a psych profile
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct RatingTrack {
+    pub skill_rating: u32, // ELO carried between duels; 0 until the first rated result
+    // Rating confidence; 0 until the first rated result, then shrinks per game
+    pub rating_deviation: u16,
+    pub rated_games: u32,
+    pub wins: u32,
+    pub glicko: shared::Glicko2Rating,
+    // Inactivity decay; see RatingDecayConfig
+    pub last_rated_at: i64,
+    pub last_decayed_at: i64,
 }
 
 /// BettingComponent - Pot and betting state management
@@ -103,22 +200,133 @@ pub struct BettingComponent {
     pub side_pots: Vec<SidePot>,
     pub rake_amount: u64,
     pub is_settled: bool,
+    pub locked: bool, // Held for the duration of a settlement instruction
+    // Forced bets, posted automatically at round start
+    pub blind_levels: Vec<BlindLevel>,
+    pub blind_escalation: BlindEscalation,
+    pub blind_level: u8,
+    // Betting structure enforcement
+    pub betting_structure: BettingStructure,
+    pub raises_this_round: u8,
+    pub bump: u8,
+}
+
+/// Game state enumeration; the transition table is shared with other programs
+pub use shared::{DuelState as GameState, DuelTrigger};
+
+pub use shared::QueueType;
+
+/// Rule set used to turn the resolution randomness into a winner
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum OutcomeEvaluator {
+    HighCard,
+    BestOfRounds,
+    DamageRace,
+    Weighted, // Draw weighted by fold state, chips committed and rounds won
+}
+
+impl Default for OutcomeEvaluator {
+    fn default() -> Self {
+        OutcomeEvaluator::HighCard
+    }
 }
 
-/// Game state enumeration
+/// Rating model settlement updates; set per queue by QueueRatingConfig
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
-pub enum GameState {
-    WaitingForPlayers,
-    InProgress,
-    AwaitingAction,
-    ResolutionPending,
-    Completed,
-    Cancelled,
+pub enum RatingSystem {
+    Elo,
+    Glicko2, // Tracks deviation and volatility, so inactivity and upsets are handled
 }
 
-impl Default for GameState {
+impl Default for RatingSystem {
     fn default() -> Self {
-        GameState::WaitingForPlayers
+        RatingSystem::Elo
+    }
+}
+
+/// Source of the randomness that resolves a duel, chosen at creation
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum RandomnessProvider {
+    MagicBlockVrf, // TEE VRF on the ephemeral rollup
+    Switchboard,
+    CommitReveal,  // Both players' revealed entropy, no oracle
+}
+
+impl Default for RandomnessProvider {
+    fn default() -> Self {
+        RandomnessProvider::MagicBlockVrf
+    }
+}
+
+/// Protocol failure that makes a duel's insurance claimable
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum FailureReason {
+    None,
+    RollupEmergencyExit,
+    OracleStale,
+}
+
+impl Default for FailureReason {
+    fn default() -> Self {
+        FailureReason::None
+    }
+}
+
+/// How settlement splits the pot between mismatched players
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum HandicapMode {
+    Even,    // Winner takes the whole pot
+    EloOdds, // Favorite's winning share shrinks with the rating gap
+}
+
+impl Default for HandicapMode {
+    fn default() -> Self {
+        HandicapMode::Even
+    }
+}
+
+/// Table tier; anything above Standard is gated on a token holding
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum TableTier {
+    Standard,
+    HighStakes,
+    Vip,
+}
+
+impl Default for TableTier {
+    fn default() -> Self {
+        TableTier::Standard
+    }
+}
+
+/// Entry requirement for a gated table. `gate_mint` is either a specific NFT
+/// mint (min_balance 1) or the staking receipt mint with a minimum balance.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct TableGate {
+    pub tier: TableTier,
+    pub gate_mint: Pubkey,
+    pub min_balance: u64,
+}
+
+impl TableGate {
+    pub fn is_gated(&self) -> bool {
+        self.tier != TableTier::Standard
+    }
+
+    pub fn is_valid(&self) -> bool {
+        if self.is_gated() {
+            self.gate_mint != Pubkey::default() && self.min_balance > 0
+        } else {
+            self.min_balance == 0
+        }
+    }
+
+    /// `holding` is the mint and amount of the player's token account, if one was passed
+    pub fn is_satisfied_by(&self, holding: Option<(Pubkey, u64)>) -> bool {
+        if !self.is_gated() {
+            return true;
+        }
+        matches!(holding, Some((mint, amount)) if mint == self.gate_mint && amount >= self.min_balance)
     }
 }
 
@@ -145,6 +353,8 @@ pub enum ActionType {
     Fold,
     AllIn,
     Timeout,
+    PostBlind,
+    Forfeit,
 }
 
 impl Default for ActionType {
@@ -171,22 +381,264 @@ impl Default for SidePot {
     }
 }
 
+/// Longest blind schedule a duel may configure
+pub const MAX_BLIND_LEVELS: usize = 8;
+
+/// One step of the blind schedule
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct BlindLevel {
+    pub small_blind: u64,
+    pub big_blind: u64,
+    pub ante: u64,
+}
+
+/// When the blind schedule moves to its next level
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum BlindEscalation {
+    None,
+    EveryRounds(u8),
+    EverySeconds(i64),
+}
+
+impl Default for BlindEscalation {
+    fn default() -> Self {
+        BlindEscalation::None
+    }
+}
+
+/// Raises allowed per round in fixed-limit duels (one bet plus three raises)
+pub const FIXED_LIMIT_MAX_RAISES: u8 = 4;
+
+/// Limit structure governing raise sizes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum BettingStructure {
+    NoLimit,
+    PotLimit,
+    FixedLimit,
+}
+
+impl Default for BettingStructure {
+    fn default() -> Self {
+        BettingStructure::NoLimit
+    }
+}
+
+/// Upper bound on attested latency credited back to a player
+pub const MAX_ATTESTED_LATENCY_MS: u32 = 5_000;
+/// Samples required before statistical outlier clamping kicks in
+pub const MIN_SAMPLES_FOR_OUTLIER_CLAMP: u32 = 10;
+
+/// Network latency measured by the ER sequencer for a single action
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct LatencyAttestation {
+    pub sequencer: Pubkey,
+    pub latency_ms: u32,
+    pub slot: u64,
+}
+
+/// Reputation assigned to players with no finished duels
+pub const DEFAULT_REPUTATION: u16 = 500;
+pub const MAX_REPUTATION: u16 = 1000;
+/// Points lost per duel the player conceded
+pub const FORFEIT_REPUTATION_PENALTY: u16 = 10;
+/// Points lost per dispute raised against the player
+pub const DISPUTE_REPUTATION_PENALTY: u16 = 50;
+/// Points lost per chip-dumping flag involving the player
+pub const COLLUSION_REPUTATION_PENALTY: u16 = 150;
+
+/// Rating given to players before their first rated result
+pub const INITIAL_SKILL_RATING: u32 = 1200;
+
+/// Rating deviation of an unrated player, and the floor veterans settle at
+pub const INITIAL_RATING_DEVIATION: u16 = 350;
+pub const MIN_RATING_DEVIATION: u16 = 50;
+/// Deviation lost per rated game
+pub const RATING_DEVIATION_STEP: u16 = 30;
+/// Rated games played before events and clients show a rating. Withholding
+/// is presentation only: `skill_rating` stays readable on the accounts.
+pub const PLACEMENT_MATCHES: u32 = 10;
+/// ELO K-factor range, from veterans at the deviation floor to unrated players
+pub const MIN_K_FACTOR: f64 = 16.0;
+pub const MAX_K_FACTOR: f64 = 64.0;
+
+/// K-factor for a player with `rating_deviation`: new players move quickly,
+/// veterans slowly
+pub fn k_factor(rating_deviation: u16) -> f64 {
+    let deviation = rating_deviation.clamp(MIN_RATING_DEVIATION, INITIAL_RATING_DEVIATION);
+    let confidence_gap = (deviation - MIN_RATING_DEVIATION) as f64
+        / (INITIAL_RATING_DEVIATION - MIN_RATING_DEVIATION) as f64;
+    MIN_K_FACTOR + (MAX_K_FACTOR - MIN_K_FACTOR) * confidence_gap
+}
+
+/// Share of the pot each seat takes when it wins, in basis points. With equal
+/// stakes a favorite with expected score E breaks even taking 1/(2E) of the pot;
+/// the underdog keeps the full pot, and whatever the winner does not take goes
+/// back to the loser.
+pub fn elo_payout_shares(ratings: [u32; 2]) -> [u16; 2] {
+    [
+        shared::elo_break_even_share_bps(ratings[0], ratings[1]),
+        shared::elo_break_even_share_bps(ratings[1], ratings[0]),
+    ]
+}
+
+/// Single logged action in a replay chunk
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct ReplayEntry {
+    pub seat: u8, // 0 = player one, 1 = player two
+    pub action_type: ActionType,
+    pub bet_amount: u64,
+    pub round_number: u8,
+    pub timestamp: i64,
+}
+
+/// Minimal duel state needed to re-execute logged actions
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct ReplayState {
+    pub chip_counts: [u64; 2],
+    pub total_bets: [u64; 2],
+    pub is_active: [bool; 2],
+    pub total_pot: u64,
+    pub current_bet: u64,
+    pub round: u8,
+}
+
 /// Component validation traits
 impl DuelComponent {
     pub fn is_valid_state_transition(&self, new_state: GameState) -> bool {
-        match (self.game_state, new_state) {
-            (GameState::WaitingForPlayers, GameState::InProgress) => true,
-            (GameState::InProgress, GameState::AwaitingAction) => true,
-            (GameState::AwaitingAction, GameState::InProgress) => true,
-            (GameState::InProgress, GameState::ResolutionPending) => true,
-            (GameState::ResolutionPending, GameState::Completed) => true,
-            (_, GameState::Cancelled) => true,
-            _ => false,
+        self.game_state.can_transition_to(new_state)
+    }
+
+    /// Move the duel along the shared transition table; false leaves it untouched
+    pub fn apply_transition(&mut self, trigger: DuelTrigger) -> bool {
+        match self.game_state.next(trigger) {
+            Some(next) => {
+                self.game_state = next;
+                if next == GameState::ResolutionPending {
+                    self.commit_weights();
+                }
+                true
+            }
+            None => false,
         }
     }
 
+    /// Record the resolved outcome and complete the duel; no winner is an exact
+    /// tie. The betting is left for settlement to pay out, split or not.
+    pub fn record_resolution(&mut self, winner: Option<Pubkey>) -> bool {
+        if !self.apply_transition(DuelTrigger::Resolve) {
+            return false;
+        }
+        self.winner = winner;
+        self.is_draw = winner.is_none();
+        self.resolution_pending = false;
+        true
+    }
+
+    /// Hand the duel to VRF resolution, opening the entropy reveal window
+    pub fn request_resolution(&mut self, current_time: i64) {
+        self.resolution_pending = true;
+        self.entropy_reveal_deadline = current_time + crate::instructions::ENTROPY_REVEAL_WINDOW;
+    }
+
+    pub fn is_paused(&self, current_time: i64) -> bool {
+        current_time < self.paused_until
+    }
+
     pub fn is_timeout_exceeded(&self, current_time: i64) -> bool {
-        current_time > self.last_action_time + self.timeout_duration
+        current_time > self.last_action_time + self.timeout_duration + self.grace_extension
+    }
+
+    /// Record both seats' ratings and derive the payout shares settlement will use
+    pub fn fix_handicap(&mut self, ratings: [u32; 2]) {
+        self.join_ratings = ratings;
+        self.payout_share_bps = match self.handicap {
+            HandicapMode::Even => [10_000; 2],
+            HandicapMode::EloOdds => elo_payout_shares(ratings),
+        };
+    }
+
+    /// Split a drawn pot evenly. The seat in the small-blind position, first
+    /// to act, takes the odd chip. Returned in seat order.
+    pub fn draw_split(pot: u64, first_seat_position: PlayerPosition) -> [u64; 2] {
+        let half = pot / 2;
+        let odd = pot % 2;
+        if first_seat_position == PlayerPosition::Big {
+            [half, half + odd]
+        } else {
+            [half + odd, half]
+        }
+    }
+
+    /// Settlement split: the handicap split, then any early-forfeit refund
+    /// moved from the winner's take back to the player who conceded
+    pub fn settlement_split(&self, winner_seat: u8, payout: u64) -> (u64, u64) {
+        let (take, refund) = self.handicap_split(winner_seat, payout);
+        let forfeit_refund = self.forfeit_refund.min(take);
+        (take - forfeit_refund, refund + forfeit_refund)
+    }
+
+    /// Split a net payout into the winner's take and the loser's refund
+    pub fn handicap_split(&self, winner_seat: u8, payout: u64) -> (u64, u64) {
+        // Duels opened before handicaps existed carry no shares; treat them as even
+        let share_bps = match self.payout_share_bps[winner_seat as usize] {
+            0 => 10_000,
+            share => share.min(10_000),
+        };
+        let take = (payout as u128 * share_bps as u128 / 10_000) as u64;
+        (take, payout - take)
+    }
+
+    /// Split the escrowed lamports the way the chips settled: the rake takes its
+    /// share of the pot, and the rest follows each side's chip payout. Returns
+    /// the lamport rake and the lamports for each side, in the order given.
+    pub fn escrow_split(&self, total_pot: u64, rake: u64, payouts: [u64; 2]) -> (u64, [u64; 2]) {
+        let escrow = self.escrowed_stake.saturating_mul(2);
+        if escrow == 0 {
+            return (0, [0, 0]);
+        }
+        let escrow_rake = match total_pot {
+            0 => 0,
+            pot => (escrow as u128 * rake.min(pot) as u128 / pot as u128) as u64,
+        };
+        let net = escrow - escrow_rake;
+        let paid = payouts[0] as u128 + payouts[1] as u128;
+        let first = match paid {
+            0 => net / 2,
+            paid => (net as u128 * payouts[0] as u128 / paid) as u64,
+        };
+        (escrow_rake, [first, net - first])
+    }
+
+    /// Lamports of `seat`'s stake an escrow refund would still return
+    pub fn refundable_stake(&self, seat: usize) -> u64 {
+        self.escrowed_stake.saturating_sub(self.escrow_claimed[seat])
+    }
+
+    /// Hand up to `amount` of `seat`'s refundable stake to the insurance pool
+    /// that paid the seat out. Returns the lamports taken from escrow.
+    pub fn claim_stake(&mut self, seat: usize, amount: u64) -> u64 {
+        let claimed = amount.min(self.refundable_stake(seat));
+        self.escrow_claimed[seat] += claimed;
+        claimed
+    }
+
+    /// Whether the action timer is running against `player`: the seat that
+    /// did not act last, or the small blind before either seat has acted
+    pub fn is_on_clock(&self, player: &PlayerComponent) -> bool {
+        match self.seat_of(&self.last_actor) {
+            Some(_) => self.last_actor != player.player_id,
+            None => player.position == PlayerPosition::Small,
+        }
+    }
+
+    pub fn seat_of(&self, player: &Pubkey) -> Option<u8> {
+        if *player == self.player_one {
+            Some(0)
+        } else if *player == self.player_two {
+            Some(1)
+        } else {
+            None
+        }
     }
 }
 
@@ -224,6 +676,51 @@ impl PsychProfileComponent {
         self.sample_size += 1;
     }
 
+    /// Strip attested network latency from a raw decision time and clamp outliers to
+    /// three standard deviations of the running average once enough samples exist
+    pub fn normalize_decision_time(&self, raw_ms: u64, latency_ms: u32, timeout_ms: u64) -> u32 {
+        let latency = latency_ms.min(MAX_ATTESTED_LATENCY_MS) as u64;
+        let mut thinking_ms = raw_ms.saturating_sub(latency).min(timeout_ms) as f64;
+
+        if self.sample_size >= MIN_SAMPLES_FOR_OUTLIER_CLAMP {
+            let std_dev = (self.decision_variance as f64).sqrt();
+            let avg = self.avg_decision_time as f64;
+            let lower = (avg - 3.0 * std_dev).max(0.0);
+            let upper = avg + 3.0 * std_dev;
+            thinking_ms = thinking_ms.clamp(lower, upper);
+        }
+
+        thinking_ms as u32
+    }
+
+    /// Recompute the public reputation score: the completion rate scaled to 1000,
+    /// minus the timeout rate counted again, minus flat forfeit, dispute and
+    /// collusion penalties
+    pub fn refresh_reputation(&mut self) -> u16 {
+        self.reputation = if self.duels_started == 0 {
+            DEFAULT_REPUTATION
+        } else {
+            let started = self.duels_started as u64;
+            let completed = (self.duels_completed as u64).min(started);
+            let timeouts = (self.timeouts as u64).min(started);
+            let score = (completed * MAX_REPUTATION as u64 / started) as i64
+                - (timeouts * MAX_REPUTATION as u64 / started) as i64
+                - self.forfeits as i64 * FORFEIT_REPUTATION_PENALTY as i64
+                - self.disputes as i64 * DISPUTE_REPUTATION_PENALTY as i64
+                - self.collusion_flags as i64 * COLLUSION_REPUTATION_PENALTY as i64;
+            score.clamp(0, MAX_REPUTATION as i64) as u16
+        };
+        self.reputation
+    }
+
+    pub fn track(&self, queue: QueueType) -> &RatingTrack {
+        &self.tracks[queue.index()]
+    }
+
+    pub fn track_mut(&mut self, queue: QueueType) -> &mut RatingTrack {
+        &mut self.tracks[queue.index()]
+    }
+
     pub fn calculate_pressure_score(&self, pot_size: u64, time_pressure: bool) -> u16 {
         let base_score = if time_pressure { 100 } else { 0 };
         let pot_factor = (pot_size / 1000).min(100) as u16; // Scale pot influence
@@ -233,19 +730,256 @@ impl PsychProfileComponent {
     }
 }
 
+impl RatingTrack {
+    pub fn rating(&self) -> u32 {
+        match self.skill_rating {
+            0 => INITIAL_SKILL_RATING,
+            rating => rating,
+        }
+    }
+
+    pub fn deviation(&self) -> u16 {
+        match self.rating_deviation {
+            0 => INITIAL_RATING_DEVIATION,
+            deviation => deviation,
+        }
+    }
+
+    /// Rating for events and leaderboards; withheld until placement matches
+    /// are done. The raw rating is still on the account for anyone who reads it.
+    pub fn public_rating(&self) -> Option<u32> {
+        (self.rated_games >= PLACEMENT_MATCHES).then(|| self.rating())
+    }
+
+    /// Glicko-2 rating as of `now`, its deviation grown for every full rating
+    /// period since the last rated result
+    pub fn glicko_at(&self, now: i64) -> shared::Glicko2Rating {
+        let idle = (now - self.last_rated_at).max(0);
+        self.glicko.aged(idle / shared::GLICKO_RATING_PERIOD)
+    }
+
+    /// Store the rating from a rated result and tighten the deviation
+    pub fn record_rated_game(&mut self, rating: u32, now: i64) {
+        self.skill_rating = rating;
+        self.last_rated_at = now;
+        self.rated_games += 1;
+        self.rating_deviation = self.deviation().saturating_sub(RATING_DEVIATION_STEP).max(MIN_RATING_DEVIATION);
+    }
+
+    /// Apply a settled duel's rating change to the current rating, so decay
+    /// cranked while the duel was open is kept
+    pub fn record_rated_delta(&mut self, delta: i64, now: i64) {
+        let rating = (self.rating() as i64 + delta).clamp(0, u32::MAX as i64) as u32;
+        self.record_rated_game(rating, now);
+    }
+}
+
 impl BettingComponent {
     pub fn can_raise(&self, player_chips: u64, raise_amount: u64) -> bool {
-        !self.is_settled && 
+        !self.is_settled &&
+        !self.locked &&
         player_chips >= raise_amount && 
         raise_amount >= self.min_bet &&
         raise_amount <= self.max_bet
     }
 
+    /// Take the settlement lock; false if a settlement already holds it
+    pub fn lock(&mut self) -> bool {
+        if self.locked {
+            return false;
+        }
+        self.locked = true;
+        true
+    }
+
+    pub fn unlock(&mut self) {
+        self.locked = false;
+    }
+
     pub fn add_to_pot(&mut self, amount: u64) {
         self.total_pot += amount;
     }
 
-    pub fn calculate_rake(&self, rake_percentage: u8) -> u64 {
-        (self.total_pot * rake_percentage as u64) / 10000 // basis points
+    pub fn calculate_rake(&self, rake_bps: u16) -> u64 {
+        (self.total_pot * rake_bps as u64) / 10000 // basis points
+    }
+
+    /// Current big blind, or the table minimum when no blinds are configured
+    pub fn big_blind(&self) -> u64 {
+        self.blind_levels
+            .get(self.blind_level as usize)
+            .map(|level| level.big_blind)
+            .unwrap_or(0)
+            .max(self.min_bet)
+    }
+
+    /// Smallest legal raise: the larger of the last raise and the current big blind
+    pub fn min_raise(&self) -> u64 {
+        self.last_raise_amount.max(self.big_blind())
+    }
+
+    /// Check a raise against the duel's betting structure. `to_call` is what the
+    /// raiser owes before raising, which pot-limit counts towards the pot.
+    pub fn is_legal_raise(&self, raise_amount: u64, to_call: u64) -> bool {
+        match self.betting_structure {
+            BettingStructure::NoLimit => raise_amount >= self.min_raise(),
+            BettingStructure::PotLimit => {
+                raise_amount >= self.min_raise() && raise_amount <= self.total_pot + to_call
+            },
+            BettingStructure::FixedLimit => {
+                raise_amount == self.big_blind() && self.raises_this_round < FIXED_LIMIT_MAX_RAISES
+            },
+        }
+    }
+
+    /// Track a completed raise so later raises respect the minimum increment
+    pub fn register_raise(&mut self, raise_amount: u64) {
+        self.last_raise_amount = raise_amount;
+        self.raises_this_round += 1;
+    }
+
+    /// Apply an all-in that lifted the bet to `total_bet`. A shortfall smaller than a
+    /// full raise lifts the amount to call but does not reopen the betting.
+    pub fn register_all_in(&mut self, total_bet: u64) {
+        if total_bet <= self.current_bet {
+            return;
+        }
+        let increment = total_bet - self.current_bet;
+        self.current_bet = total_bet;
+        if increment >= self.min_raise() {
+            self.register_raise(increment);
+        }
+    }
+
+    /// Clear per-round raise tracking when a new round starts
+    pub fn reset_round_raises(&mut self) {
+        self.last_raise_amount = 0;
+        self.raises_this_round = 0;
+    }
+
+    /// Schedule level in effect for a round, capped at the last configured level
+    pub fn level_for(&self, round: u8, elapsed: i64) -> u8 {
+        if self.blind_levels.is_empty() {
+            return 0;
+        }
+        let level = match self.blind_escalation {
+            BlindEscalation::None => 0,
+            BlindEscalation::EveryRounds(rounds) => (round.saturating_sub(1) / rounds.max(1)) as usize,
+            BlindEscalation::EverySeconds(seconds) => (elapsed.max(0) / seconds.max(1)) as usize,
+        };
+        level.min(self.blind_levels.len() - 1) as u8
+    }
+
+    /// Blind plus ante owed by a seat at the current level
+    pub fn forced_bet(&self, position: PlayerPosition) -> u64 {
+        let level = match self.blind_levels.get(self.blind_level as usize) {
+            Some(level) => level,
+            None => return 0,
+        };
+        let blind = match position {
+            PlayerPosition::Small => level.small_blind,
+            PlayerPosition::Big => level.big_blind,
+            PlayerPosition::None => 0,
+        };
+        blind + level.ante
+    }
+}
+
+impl ReplayState {
+    pub fn capture(
+        duel: &DuelComponent,
+        player_one: &PlayerComponent,
+        player_two: &PlayerComponent,
+        betting: &BettingComponent,
+    ) -> Self {
+        Self {
+            chip_counts: [player_one.chip_count, player_two.chip_count],
+            total_bets: [player_one.total_bet, player_two.total_bet],
+            is_active: [player_one.is_active, player_two.is_active],
+            total_pot: betting.total_pot,
+            current_bet: betting.current_bet,
+            round: duel.current_round,
+        }
+    }
+
+    /// Mirror round progression, which resets the current bet
+    pub fn advance_to_round(&mut self, round: u8) {
+        if round != self.round {
+            self.round = round;
+            self.current_bet = 0;
+        }
+    }
+
+    /// Re-execute an action with the same rules as the action processing system.
+    /// Returns false if the action could not have been legal from this state.
+    pub fn apply(&mut self, entry: &ReplayEntry) -> bool {
+        let seat = entry.seat as usize;
+        if seat > 1 || !self.is_active[seat] {
+            return false;
+        }
+        self.advance_to_round(entry.round_number);
+
+        match entry.action_type {
+            ActionType::Check => self.current_bet == self.total_bets[seat],
+            ActionType::Call => {
+                let call_amount = self.current_bet.saturating_sub(self.total_bets[seat]);
+                if self.chip_counts[seat] < call_amount {
+                    return false;
+                }
+                self.chip_counts[seat] -= call_amount;
+                self.total_bets[seat] += call_amount;
+                self.total_pot += call_amount;
+                true
+            },
+            ActionType::Raise => {
+                let total_required = self.current_bet + entry.bet_amount;
+                let additional_bet = total_required.saturating_sub(self.total_bets[seat]);
+                if self.chip_counts[seat] < additional_bet {
+                    return false;
+                }
+                self.chip_counts[seat] -= additional_bet;
+                self.total_bets[seat] = total_required;
+                self.current_bet = total_required;
+                self.total_pot += additional_bet;
+                true
+            },
+            ActionType::AllIn => {
+                let all_in_amount = self.chip_counts[seat];
+                if all_in_amount == 0 {
+                    return false;
+                }
+                self.chip_counts[seat] = 0;
+                self.total_bets[seat] += all_in_amount;
+                self.current_bet = self.current_bet.max(self.total_bets[seat]);
+                self.total_pot += all_in_amount;
+                true
+            },
+            ActionType::Fold | ActionType::Timeout | ActionType::Forfeit => {
+                self.is_active[seat] = false;
+                true
+            },
+            ActionType::PostBlind => {
+                // Short stacks post what they have left
+                let posted = entry.bet_amount.min(self.chip_counts[seat]);
+                self.chip_counts[seat] -= posted;
+                self.total_bets[seat] += posted;
+                self.current_bet = self.current_bet.max(self.total_bets[seat]);
+                self.total_pot += posted;
+                true
+            },
+        }
     }
-}
\ No newline at end of file
+
+    pub fn hash(&self) -> [u8; 32] {
+        anchor_lang::solana_program::hash::hashv(&[
+            &self.chip_counts[0].to_le_bytes(),
+            &self.chip_counts[1].to_le_bytes(),
+            &self.total_bets[0].to_le_bytes(),
+            &self.total_bets[1].to_le_bytes(),
+            &[self.is_active[0] as u8, self.is_active[1] as u8, self.round],
+            &self.total_pot.to_le_bytes(),
+            &self.current_bet.to_le_bytes(),
+        ])
+        .to_bytes()
+    }
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+/// InitializeAdminConfig - Create the program's AdminConfig; upgrade authority only.
+/// Settlement reads it for the CPI guard, and global configs are created by its super admin.
+#[derive(Accounts)]
+pub struct InitializeAdminConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::StrategicDuel>,
+
+    #[account(constraint = program_data.upgrade_authority_address == Some(authority.key()) @ AdminConfigError::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = shared::AdminConfig::LEN,
+        seeds = [shared::ADMIN_CONFIG_SEED],
+        bump
+    )]
+    pub admin_config: Account<'info, shared::AdminConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeAdminConfig<'info> {
+    pub fn process(&mut self, super_admin: Pubkey, bump: u8) -> Result<()> {
+        self.admin_config.init(super_admin, bump);
+
+        emit!(AdminConfigInitializedEvent {
+            super_admin,
+            initialized_by: self.authority.key(),
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct AdminConfigInitializedEvent {
+    pub super_admin: Pubkey,
+    pub initialized_by: Pubkey,
+}
+
+#[error_code]
+pub enum AdminConfigError {
+    #[msg("Only the program upgrade authority can create the admin config")]
+    Unauthorized,
+    #[msg("Only the super admin can create or change this config")]
+    NotSuperAdmin,
+}
@@ -0,0 +1,323 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use bolt_lang::*;
+use crate::components::*;
+
+/// Window over which the daily withdrawal limit applies
+pub const WITHDRAWAL_WINDOW: i64 = 24 * 60 * 60; // 1 day
+
+/// Delay before a looser withdrawal policy takes effect
+pub const POLICY_TIMELOCK: i64 = 24 * 60 * 60; // 1 day
+
+/// InitializeBankroll - Create a player's winnings vault
+#[derive(Accounts)]
+pub struct InitializeBankroll<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + std::mem::size_of::<Bankroll>(),
+        seeds = [b"bankroll", owner.key().as_ref()],
+        bump
+    )]
+    pub bankroll: Account<'info, ComponentData<Bankroll>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// DepositBankroll - Move lamports into the vault
+#[derive(Accounts)]
+pub struct DepositBankroll<'info> {
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"bankroll", bankroll.load()?.owner.as_ref()],
+        bump
+    )]
+    pub bankroll: Account<'info, ComponentData<Bankroll>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// WithdrawBankroll - Withdraw under the vault's policy
+#[derive(Accounts)]
+pub struct WithdrawBankroll<'info> {
+    /// The vault owner, or its identity's cold wallet once one is linked
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"bankroll", bankroll.load()?.owner.as_ref()],
+        bump
+    )]
+    pub bankroll: Account<'info, ComponentData<Bankroll>>,
+
+    /// CHECK: The vault owner's game-program identity, empty if none
+    #[account(
+        seeds = [b"identity", bankroll.load()?.owner.as_ref()],
+        seeds::program = shared::GAME_PROGRAM_ID,
+        bump,
+        constraint = shared::PlayerIdentity::may_withdraw(&player_identity, &bankroll.load()?.owner, &owner.key())
+            @ shared::GameError::ColdWalletRequired
+    )]
+    pub player_identity: UncheckedAccount<'info>,
+
+    /// Required above the policy's co-sign threshold
+    pub cosigner: Option<Signer<'info>>,
+}
+
+/// SetWithdrawalPolicy - Change limits; needs the current co-signer if one is set, and looser limits wait out the timelock
+#[derive(Accounts)]
+pub struct SetWithdrawalPolicy<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"bankroll", owner.key().as_ref()],
+        bump
+    )]
+    pub bankroll: Account<'info, ComponentData<Bankroll>>,
+
+    pub cosigner: Option<Signer<'info>>,
+}
+
+/// Withdrawal limits protecting winnings from a compromised session key
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct WithdrawalPolicy {
+    pub daily_limit: u64,             // 0 = unlimited
+    pub large_win_threshold: u64,     // lamports, 0 = no cooldown
+    pub large_win_cooldown: i64,
+    pub cosigner: Pubkey,             // default = no co-signer
+    pub cosign_threshold: u64,
+}
+
+impl WithdrawalPolicy {
+    /// Whether moving to `next` relaxes any limit
+    pub fn is_loosened_by(&self, next: &WithdrawalPolicy) -> bool {
+        // 0 means unlimited for the daily limit and no cooldown for the threshold
+        let raised = |current: u64, new: u64| current != 0 && (new == 0 || new > current);
+        raised(self.daily_limit, next.daily_limit)
+            || raised(self.large_win_threshold, next.large_win_threshold)
+            || next.large_win_cooldown < self.large_win_cooldown
+            || (self.cosigner != Pubkey::default()
+                && (next.cosigner != self.cosigner || next.cosign_threshold > self.cosign_threshold))
+    }
+}
+
+/// Bankroll - A player's winnings vault
+#[component]
+#[derive(Default)]
+pub struct Bankroll {
+    pub owner: Pubkey,
+    pub policy: WithdrawalPolicy,
+    pub window_start: i64,
+    pub withdrawn_in_window: u64,
+    pub cooldown_until: i64,
+    pub total_deposited: u64,
+    pub total_withdrawn: u64,
+    pub pending_policy: Option<WithdrawalPolicy>,
+    pub pending_policy_at: i64,
+}
+
+impl Bankroll {
+    /// A settled lamport win at or above the threshold locks withdrawals for the cooldown
+    pub fn record_win(&mut self, amount: u64, now: i64) -> bool {
+        let large = self.policy.large_win_threshold > 0 && amount >= self.policy.large_win_threshold;
+        if large {
+            self.cooldown_until = self.cooldown_until.max(now + self.policy.large_win_cooldown);
+        }
+        large
+    }
+
+    /// Apply a looser policy once its timelock has passed
+    pub fn activate_pending_policy(&mut self, now: i64) {
+        if let Some(policy) = self.pending_policy {
+            if now >= self.pending_policy_at {
+                self.policy = policy;
+                self.pending_policy = None;
+            }
+        }
+    }
+
+    /// Tighter policies apply at once and cancel anything pending; looser ones are
+    /// staged behind the timelock. Returns whether the policy was staged.
+    pub fn set_policy(&mut self, policy: WithdrawalPolicy, now: i64) -> bool {
+        self.activate_pending_policy(now);
+        if self.policy.is_loosened_by(&policy) {
+            self.pending_policy = Some(policy);
+            self.pending_policy_at = now + POLICY_TIMELOCK;
+            true
+        } else {
+            self.policy = policy;
+            self.pending_policy = None;
+            false
+        }
+    }
+
+    /// Check a withdrawal against the policy and count it towards the daily limit
+    pub fn authorize_withdrawal(&mut self, amount: u64, cosigned: bool, now: i64) -> Result<()> {
+        self.activate_pending_policy(now);
+        require!(now >= self.cooldown_until, BankrollError::CooldownActive);
+
+        if now >= self.window_start + WITHDRAWAL_WINDOW {
+            self.window_start = now;
+            self.withdrawn_in_window = 0;
+        }
+        let withdrawn = self.withdrawn_in_window.saturating_add(amount);
+        require!(
+            self.policy.daily_limit == 0 || withdrawn <= self.policy.daily_limit,
+            BankrollError::DailyLimitExceeded
+        );
+        require!(
+            self.policy.cosigner == Pubkey::default() || amount <= self.policy.cosign_threshold || cosigned,
+            BankrollError::CosignerRequired
+        );
+
+        self.withdrawn_in_window = withdrawn;
+        Ok(())
+    }
+}
+
+fn cosigned_by(cosigner: &Option<Signer>, expected: &Pubkey) -> bool {
+    *expected != Pubkey::default() && cosigner.as_ref().map(|s| s.key()) == Some(*expected)
+}
+
+impl<'info> InitializeBankroll<'info> {
+    pub fn process(&mut self, policy: WithdrawalPolicy) -> Result<()> {
+        require!(policy.large_win_cooldown >= 0, BankrollError::InvalidPolicy);
+
+        let mut bankroll = self.bankroll.load_init()?;
+        bankroll.owner = self.owner.key();
+        bankroll.policy = policy;
+        bankroll.window_start = Clock::get()?.unix_timestamp;
+
+        Ok(())
+    }
+}
+
+impl<'info> DepositBankroll<'info> {
+    pub fn process(&mut self, amount: u64) -> Result<()> {
+        system_program::transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: self.depositor.to_account_info(),
+                    to: self.bankroll.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let mut bankroll = self.bankroll.load_mut()?;
+        bankroll.total_deposited += amount;
+
+        Ok(())
+    }
+}
+
+impl<'info> WithdrawBankroll<'info> {
+    pub fn process(&mut self, amount: u64) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+
+        {
+            let mut bankroll = self.bankroll.load_mut()?;
+            let cosigned = cosigned_by(&self.cosigner, &bankroll.policy.cosigner);
+            bankroll.authorize_withdrawal(amount, cosigned, current_time)?;
+            bankroll.total_withdrawn += amount;
+        }
+
+        let vault_info = self.bankroll.to_account_info();
+        let rent_floor = Rent::get()?.minimum_balance(vault_info.data_len());
+        require!(
+            vault_info.lamports().saturating_sub(rent_floor) >= amount,
+            BankrollError::InsufficientBalance
+        );
+
+        // The vault is owned by this program, so lamports can be moved directly
+        **vault_info.try_borrow_mut_lamports()? -= amount;
+        **self.owner.to_account_info().try_borrow_mut_lamports()? += amount;
+
+        emit!(BankrollWithdrawalEvent {
+            owner: self.owner.key(),
+            amount,
+            withdrawn_in_window: self.bankroll.load()?.withdrawn_in_window,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> SetWithdrawalPolicy<'info> {
+    pub fn process(&mut self, policy: WithdrawalPolicy) -> Result<()> {
+        require!(policy.large_win_cooldown >= 0, BankrollError::InvalidPolicy);
+
+        let current_time = Clock::get()?.unix_timestamp;
+        let mut bankroll = self.bankroll.load_mut()?;
+        // Once a co-signer is set, a stolen owner key alone cannot loosen the policy
+        if bankroll.policy.cosigner != Pubkey::default() {
+            require!(cosigned_by(&self.cosigner, &bankroll.policy.cosigner), BankrollError::CosignerRequired);
+        }
+
+        if bankroll.set_policy(policy, current_time) {
+            emit!(WithdrawalPolicyStagedEvent {
+                owner: bankroll.owner,
+                policy,
+                effective_at: bankroll.pending_policy_at,
+            });
+        } else {
+            emit!(WithdrawalPolicyUpdatedEvent {
+                owner: bankroll.owner,
+                policy,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct BankrollWithdrawalEvent {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub withdrawn_in_window: u64,
+}
+
+#[event]
+pub struct WithdrawalPolicyUpdatedEvent {
+    pub owner: Pubkey,
+    pub policy: WithdrawalPolicy,
+}
+
+#[event]
+pub struct WithdrawalPolicyStagedEvent {
+    pub owner: Pubkey,
+    pub policy: WithdrawalPolicy,
+    pub effective_at: i64,
+}
+
+#[event]
+pub struct LargeWinCooldownEvent {
+    pub owner: Pubkey,
+    pub duel_id: u64,
+    pub amount: u64,
+    pub cooldown_until: i64,
+}
+
+#[error_code]
+pub enum BankrollError {
+    #[msg("Withdrawals are locked after a large win")]
+    CooldownActive,
+    #[msg("Withdrawal exceeds the daily limit")]
+    DailyLimitExceeded,
+    #[msg("Withdrawal requires the co-signer")]
+    CosignerRequired,
+    #[msg("Bankroll has insufficient balance")]
+    InsufficientBalance,
+    #[msg("Invalid withdrawal policy")]
+    InvalidPolicy,
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use bolt_lang::*;
+use crate::components::*;
+
+/// CloseAction - Reclaim rent from a player's action record once it is spent
+#[derive(Accounts)]
+pub struct CloseAction<'info> {
+    #[account(mut)]
+    pub player_signer: Signer<'info>,
+
+    /// CHECK: Entity for the duel
+    pub entity: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"duel", entity.key().as_ref()],
+        bump = duel.load()?.bump
+    )]
+    pub duel: Account<'info, ComponentData<DuelComponent>>,
+
+    #[account(
+        seeds = [b"player", player_signer.key().as_ref(), entity.key().as_ref()],
+        bump = player.load()?.bump
+    )]
+    pub player: Account<'info, ComponentData<PlayerComponent>>,
+
+    #[account(
+        mut,
+        close = player_signer,
+        seeds = [b"action", player.key().as_ref(), entity.key().as_ref()],
+        bump
+    )]
+    pub action: Account<'info, ComponentData<ActionComponent>>,
+}
+
+impl<'info> CloseAction<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let duel = self.duel.load()?;
+        let action = self.action.load()?;
+
+        // Unprocessed actions still feed the action system until the duel ends
+        require!(
+            action.is_processed || duel.game_state.is_terminal(),
+            CloseActionError::ActionPending
+        );
+
+        emit!(ActionClosedEvent {
+            duel_id: duel.duel_id,
+            player: self.player_signer.key(),
+            sequence_number: action.sequence_number,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct ActionClosedEvent {
+    pub duel_id: u64,
+    pub player: Pubkey,
+    pub sequence_number: u16,
+}
+
+#[error_code]
+pub enum CloseActionError {
+    #[msg("Action has not been processed and the duel is still live")]
+    ActionPending,
+}
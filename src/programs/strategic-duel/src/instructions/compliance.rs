@@ -0,0 +1,195 @@
+use anchor_lang::prelude::*;
+use bolt_lang::*;
+use shared::{allowlist_leaf, verify_merkle_proof};
+pub use shared::ComplianceMode;
+use super::admin_config::AdminConfigError;
+
+/// ConfigureCompliance - Create or update the deployment's compliance gate;
+/// super admin only
+#[derive(Accounts)]
+pub struct ConfigureCompliance<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [shared::ADMIN_CONFIG_SEED],
+        bump = admin_config.bump,
+        constraint = admin_config.super_admin == authority.key() @ AdminConfigError::NotSuperAdmin
+    )]
+    pub admin_config: Account<'info, shared::AdminConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + std::mem::size_of::<ComplianceConfig>(),
+        seeds = [b"compliance_config"],
+        bump
+    )]
+    pub compliance_config: Account<'info, ComponentData<ComplianceConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// IssueComplianceCredential - Attestation issuer vouches for a player
+#[derive(Accounts)]
+#[instruction(player: Pubkey)]
+pub struct IssueComplianceCredential<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        seeds = [b"compliance_config"],
+        bump
+    )]
+    pub compliance_config: Account<'info, ComponentData<ComplianceConfig>>,
+
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        space = 8 + std::mem::size_of::<ComplianceCredential>(),
+        seeds = [b"compliance_credential", player.as_ref()],
+        bump
+    )]
+    pub credential: Account<'info, ComponentData<ComplianceCredential>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// ComplianceConfig - KYC/allowlist gate checked on every entry; permissionless by default
+#[component]
+#[derive(Default)]
+pub struct ComplianceConfig {
+    pub authority: Pubkey,
+    pub mode: ComplianceMode,
+    pub allowlist_root: [u8; 32],
+    pub attestation_issuer: Pubkey,
+    pub spectator_betting_paused: bool, // Kill switch for spectator side pools
+    pub updated_at: i64,
+}
+
+/// ComplianceCredential - Issuer attestation that a player passed checks
+#[component]
+#[derive(Default)]
+pub struct ComplianceCredential {
+    pub player: Pubkey,
+    pub issuer: Pubkey,
+    pub issued_at: i64,
+    pub expires_at: i64, // 0 = no expiry
+    pub revoked: bool,
+}
+
+impl ComplianceConfig {
+    /// Check a player against the configured gate
+    pub fn verify(
+        &self,
+        player: &Pubkey,
+        allowlist_proof: &[[u8; 32]],
+        credential: Option<&ComplianceCredential>,
+        current_time: i64,
+    ) -> Result<()> {
+        match self.mode {
+            ComplianceMode::Permissionless => Ok(()),
+            ComplianceMode::MerkleAllowlist => {
+                require!(
+                    verify_merkle_proof(allowlist_leaf(player), allowlist_proof, self.allowlist_root),
+                    ComplianceError::NotAllowlisted
+                );
+                Ok(())
+            },
+            ComplianceMode::Attestation => {
+                let credential = credential.ok_or(ComplianceError::InvalidCredential)?;
+                require!(
+                    credential.player == *player
+                        && credential.issuer == self.attestation_issuer
+                        && !credential.revoked
+                        && (credential.expires_at == 0 || credential.expires_at > current_time),
+                    ComplianceError::InvalidCredential
+                );
+                Ok(())
+            },
+        }
+    }
+}
+
+impl<'info> ConfigureCompliance<'info> {
+    pub fn process(
+        &mut self,
+        mode: ComplianceMode,
+        allowlist_root: [u8; 32],
+        attestation_issuer: Pubkey,
+        spectator_betting_paused: bool,
+    ) -> Result<()> {
+        let mut config = self.compliance_config.load_mut()?;
+        config.authority = self.authority.key();
+        require!(
+            mode != ComplianceMode::Attestation || attestation_issuer != Pubkey::default(),
+            ComplianceError::InvalidCredential
+        );
+
+        config.mode = mode;
+        config.allowlist_root = allowlist_root;
+        config.attestation_issuer = attestation_issuer;
+        config.spectator_betting_paused = spectator_betting_paused;
+        config.updated_at = Clock::get()?.unix_timestamp;
+
+        emit!(ComplianceConfiguredEvent {
+            mode,
+            allowlist_root,
+            attestation_issuer,
+            spectator_betting_paused,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> IssueComplianceCredential<'info> {
+    pub fn process(&mut self, player: Pubkey, expires_at: i64, revoked: bool) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let config = self.compliance_config.load()?;
+        require_keys_eq!(config.attestation_issuer, self.issuer.key(), ComplianceError::Unauthorized);
+        require!(expires_at == 0 || expires_at > current_time, ComplianceError::InvalidCredential);
+
+        let mut credential = self.credential.load_mut()?;
+        credential.player = player;
+        credential.issuer = self.issuer.key();
+        credential.issued_at = current_time;
+        credential.expires_at = expires_at;
+        credential.revoked = revoked;
+
+        emit!(ComplianceCredentialIssuedEvent {
+            player,
+            issuer: credential.issuer,
+            expires_at,
+            revoked,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct ComplianceConfiguredEvent {
+    pub mode: ComplianceMode,
+    pub allowlist_root: [u8; 32],
+    pub attestation_issuer: Pubkey,
+    pub spectator_betting_paused: bool,
+}
+
+#[event]
+pub struct ComplianceCredentialIssuedEvent {
+    pub player: Pubkey,
+    pub issuer: Pubkey,
+    pub expires_at: i64,
+    pub revoked: bool,
+}
+
+#[error_code]
+pub enum ComplianceError {
+    #[msg("Player is not on the compliance allowlist")]
+    NotAllowlisted,
+    #[msg("Compliance credential missing, expired or revoked")]
+    InvalidCredential,
+    #[msg("Signer cannot manage compliance settings")]
+    Unauthorized,
+}
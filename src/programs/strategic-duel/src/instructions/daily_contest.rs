@@ -0,0 +1,218 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use bolt_lang::*;
+use super::admin_config::AdminConfigError;
+
+pub const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// InitializeDailyContest - Create the high-hand contest and its prize; super admin only
+#[derive(Accounts)]
+pub struct InitializeDailyContest<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [shared::ADMIN_CONFIG_SEED],
+        bump = admin_config.bump,
+        constraint = admin_config.super_admin == authority.key() @ AdminConfigError::NotSuperAdmin
+    )]
+    pub admin_config: Account<'info, shared::AdminConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<DailyContest>(),
+        seeds = [b"daily_contest"],
+        bump
+    )]
+    pub daily_contest: Account<'info, ComponentData<DailyContest>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// FundDailyContest - Treasury tops up the prize pool
+#[derive(Accounts)]
+pub struct FundDailyContest<'info> {
+    #[account(mut)]
+    pub treasury: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"daily_contest"],
+        bump
+    )]
+    pub daily_contest: Account<'info, ComponentData<DailyContest>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// FinalizeContestDay - Pay the previous day's record holder and start a new
+/// day; any keeper may crank it once the UTC day is over
+#[derive(Accounts)]
+pub struct FinalizeContestDay<'info> {
+    pub keeper: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"daily_contest"],
+        bump
+    )]
+    pub daily_contest: Account<'info, ComponentData<DailyContest>>,
+
+    /// CHECK: Must match the recorded holder; only receives lamports
+    #[account(mut, constraint = shared::is_wallet(&record_holder) @ shared::GameError::InvalidAccountOwner)]
+    pub record_holder: Option<AccountInfo<'info>>,
+}
+
+/// DailyContest - Largest real-stake pot won per UTC day. Settlement records into the
+/// current day; a pot settled after midnight only counts once the previous
+/// day has been finalized, unless that day closed without a record.
+#[component]
+#[derive(Default)]
+pub struct DailyContest {
+    pub authority: Pubkey,
+    pub treasury: Pubkey,
+    pub prize_amount: u64,
+    pub day: u32, // Days since the Unix epoch, UTC
+    pub record_pot: u64,
+    pub record_holder: Option<Pubkey>,
+    pub record_duel: u64,
+    pub days_finalized: u32,
+    pub total_prizes_paid: u64,
+}
+
+pub fn utc_day(now: i64) -> u32 {
+    now.div_euclid(SECONDS_PER_DAY).max(0) as u32
+}
+
+impl DailyContest {
+    /// Count a won pot toward today's record. Returns true if it set a new record.
+    pub fn record(&mut self, now: i64, pot: u64, holder: Pubkey, duel_id: u64) -> bool {
+        let today = utc_day(now);
+        if today > self.day && self.record_holder.is_none() {
+            self.day = today;
+        }
+        if today != self.day || pot <= self.record_pot {
+            return false;
+        }
+        self.record_pot = pot;
+        self.record_holder = Some(holder);
+        self.record_duel = duel_id;
+        true
+    }
+
+    fn roll_to(&mut self, day: u32) {
+        self.day = day;
+        self.record_pot = 0;
+        self.record_holder = None;
+        self.record_duel = 0;
+    }
+}
+
+impl<'info> InitializeDailyContest<'info> {
+    pub fn process(&mut self, treasury: Pubkey, prize_amount: u64) -> Result<()> {
+        require!(prize_amount > 0, DailyContestError::InvalidPrize);
+
+        let mut contest = self.daily_contest.load_init()?;
+        contest.authority = self.authority.key();
+        contest.treasury = treasury;
+        contest.prize_amount = prize_amount;
+        contest.day = utc_day(Clock::get()?.unix_timestamp);
+
+        Ok(())
+    }
+}
+
+impl<'info> FundDailyContest<'info> {
+    pub fn process(&mut self, amount: u64) -> Result<()> {
+        require!(amount > 0, DailyContestError::InvalidPrize);
+        require_keys_eq!(
+            self.daily_contest.load()?.treasury,
+            self.treasury.key(),
+            DailyContestError::Unauthorized
+        );
+
+        system_program::transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: self.treasury.to_account_info(),
+                    to: self.daily_contest.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        Ok(())
+    }
+}
+
+impl<'info> FinalizeContestDay<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let today = utc_day(Clock::get()?.unix_timestamp);
+        let (day, holder, record_pot, record_duel, prize_amount) = {
+            let contest = self.daily_contest.load()?;
+            require!(today > contest.day, DailyContestError::DayNotOver);
+            (contest.day, contest.record_holder, contest.record_pot, contest.record_duel, contest.prize_amount)
+        };
+
+        // A short pool pays what it holds rather than stalling the contest
+        let mut prize = 0;
+        if let Some(holder) = holder {
+            let recipient = self.record_holder.as_ref().ok_or(DailyContestError::WrongRecordHolder)?;
+            require_keys_eq!(recipient.key(), holder, DailyContestError::WrongRecordHolder);
+
+            let pool_info = self.daily_contest.to_account_info();
+            let rent_floor = Rent::get()?.minimum_balance(pool_info.data_len());
+            prize = prize_amount.min(pool_info.lamports().saturating_sub(rent_floor));
+
+            // The contest account is owned by this program, so lamports can be moved directly
+            **pool_info.try_borrow_mut_lamports()? -= prize;
+            **recipient.try_borrow_mut_lamports()? += prize;
+        }
+
+        let mut contest = self.daily_contest.load_mut()?;
+        contest.total_prizes_paid += prize;
+        contest.days_finalized += 1;
+        contest.roll_to(today);
+
+        emit!(ContestDayFinalizedEvent {
+            day,
+            record_holder: holder,
+            record_pot,
+            record_duel,
+            prize,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct DailyRecordSetEvent {
+    pub day: u32,
+    pub holder: Pubkey,
+    pub duel_id: u64,
+    pub pot: u64,
+}
+
+#[event]
+pub struct ContestDayFinalizedEvent {
+    pub day: u32,
+    pub record_holder: Option<Pubkey>,
+    pub record_pot: u64,
+    pub record_duel: u64,
+    pub prize: u64,
+}
+
+#[error_code]
+pub enum DailyContestError {
+    #[msg("Prize and funding amounts must be positive")]
+    InvalidPrize,
+    #[msg("Only the configured treasury can fund the contest")]
+    Unauthorized,
+    #[msg("The contest day is not over yet")]
+    DayNotOver,
+    #[msg("Record holder account does not match the contest record")]
+    WrongRecordHolder,
+}
@@ -0,0 +1,341 @@
+use anchor_lang::prelude::*;
+use bolt_lang::*;
+use crate::components::*;
+use super::admin_config::AdminConfigError;
+use super::observer_access::{may_spectate, ObserverError, SpectatorState};
+use super::replay_log::{extend_transcript, ReplayChunk};
+
+/// Shortest broadcast delay a feed may use; anything shorter is close enough
+/// to live for an opponent watching the stream to act on
+pub const MIN_FEED_DELAY_SECONDS: i64 = 30;
+
+/// Longest broadcast delay a feed may use
+pub const MAX_FEED_DELAY_SECONDS: i64 = 900;
+
+/// Mirrored actions kept on the feed account; older ones drop off the front
+pub const MAX_FEED_ENTRIES: usize = 32;
+
+/// InitializeFeedDelay - Fix the broadcast delay every feed runs at; super admin only
+#[derive(Accounts)]
+pub struct InitializeFeedDelay<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [shared::ADMIN_CONFIG_SEED],
+        bump = admin_config.bump,
+        constraint = admin_config.super_admin == authority.key() @ AdminConfigError::NotSuperAdmin
+    )]
+    pub admin_config: Account<'info, shared::AdminConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<FeedDelayConfig>(),
+        seeds = [b"feed_delay_config"],
+        bump
+    )]
+    pub feed_delay_config: Account<'info, ComponentData<FeedDelayConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// UpdateFeedDelay - Change the delay for feeds opened from now on
+#[derive(Accounts)]
+pub struct UpdateFeedDelay<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"feed_delay_config"],
+        bump
+    )]
+    pub feed_delay_config: Account<'info, ComponentData<FeedDelayConfig>>,
+}
+
+/// OpenDelayedFeed - Start a delayed mirror of a duel for broadcasting
+#[derive(Accounts)]
+pub struct OpenDelayedFeed<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Entity for the duel
+    pub entity: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"duel", entity.key().as_ref()],
+        bump = duel.load()?.bump
+    )]
+    pub duel: Account<'info, ComponentData<DuelComponent>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<DelayedFeed>() + MAX_FEED_ENTRIES * std::mem::size_of::<FeedEntry>(),
+        seeds = [b"delayed_feed", entity.key().as_ref()],
+        bump
+    )]
+    pub delayed_feed: Account<'info, ComponentData<DelayedFeed>>,
+
+    #[account(
+        seeds = [b"feed_delay_config"],
+        bump
+    )]
+    pub feed_delay_config: Account<'info, ComponentData<FeedDelayConfig>>,
+
+    /// Observer list; required to open a feed on a private duel. It gates the
+    /// feed only, not the duel accounts the feed mirrors
+    #[account(
+        seeds = [b"spectator_state", entity.key().as_ref()],
+        bump
+    )]
+    pub spectator_state: Option<Account<'info, ComponentData<SpectatorState>>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// MirrorDelayedEvents - Copy logged actions that have aged past the feed's
+/// delay onto the feed; any keeper may crank it
+#[derive(Accounts)]
+pub struct MirrorDelayedEvents<'info> {
+    pub keeper: Signer<'info>,
+
+    /// CHECK: Entity for the duel
+    pub entity: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"duel", entity.key().as_ref()],
+        bump = duel.load()?.bump
+    )]
+    pub duel: Account<'info, ComponentData<DuelComponent>>,
+
+    #[account(
+        mut,
+        seeds = [b"delayed_feed", entity.key().as_ref()],
+        bump
+    )]
+    pub delayed_feed: Account<'info, ComponentData<DelayedFeed>>,
+
+    /// The replay chunk the feed's cursor points into
+    #[account(
+        seeds = [b"replay", entity.key().as_ref(), &delayed_feed.load()?.cursor_chunk.to_le_bytes()],
+        bump
+    )]
+    pub replay_chunk: Option<Account<'info, ComponentData<ReplayChunk>>>,
+}
+
+/// One mirrored action. Only the public fields of the replay log are carried
+/// over; decision timing and psych data never reach the feed.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct FeedEntry {
+    pub seat: u8,
+    pub action_type: ActionType,
+    pub bet_amount: u64,
+    pub round_number: u8,
+    pub acted_at: i64,
+}
+
+/// FeedDelayConfig - Governance-set broadcast delay, so whoever opens a feed
+/// first cannot pick a short one for everyone watching it
+#[component]
+#[derive(Default)]
+pub struct FeedDelayConfig {
+    pub authority: Pubkey,
+    pub delay_seconds: i64,
+    pub updated_at: i64,
+}
+
+impl FeedDelayConfig {
+    fn apply(&mut self, delay_seconds: i64) -> Result<()> {
+        require!(
+            (MIN_FEED_DELAY_SECONDS..=MAX_FEED_DELAY_SECONDS).contains(&delay_seconds),
+            DelayedFeedError::InvalidDelay
+        );
+        self.delay_seconds = delay_seconds;
+        self.updated_at = Clock::get()?.unix_timestamp;
+
+        emit!(FeedDelayUpdatedEvent {
+            authority: self.authority,
+            delay_seconds,
+        });
+
+        Ok(())
+    }
+}
+
+/// DelayedFeed - Broadcast-safe mirror of a duel's action log, running
+/// `delay_seconds` behind the table. Only the feed is delayed: the replay
+/// chunks it reads from, like the duel's other components, are written as
+/// each action lands, so the delay protects stream viewers, not the table.
+#[component]
+#[derive(Default)]
+pub struct DelayedFeed {
+    pub duel_id: u64,
+    pub delay_seconds: i64,
+    pub cursor_chunk: u16, // Next replay entry to mirror
+    pub cursor_entry: u16,
+    pub mirrored: u32,
+    pub entries: Vec<FeedEntry>,
+    // Running transcript over mirrored actions; equals the duel's once caught up
+    pub transcript: [u8; 32],
+    pub outcome_published: bool,
+    pub winner: Option<Pubkey>,
+    pub updated_at: i64,
+}
+
+impl DelayedFeed {
+    /// Whether something that happened at `at` may be shown at `now`
+    pub fn is_releasable(&self, at: i64, now: i64) -> bool {
+        at.saturating_add(self.delay_seconds) <= now
+    }
+
+    fn push(&mut self, entry: &ReplayEntry) {
+        if self.entries.len() >= MAX_FEED_ENTRIES {
+            self.entries.remove(0);
+        }
+        self.entries.push(FeedEntry {
+            seat: entry.seat,
+            action_type: entry.action_type,
+            bet_amount: entry.bet_amount,
+            round_number: entry.round_number,
+            acted_at: entry.timestamp,
+        });
+        self.transcript = extend_transcript(&self.transcript, self.duel_id, entry);
+        self.mirrored += 1;
+    }
+}
+
+impl<'info> InitializeFeedDelay<'info> {
+    pub fn process(&mut self, delay_seconds: i64) -> Result<()> {
+        let mut config = self.feed_delay_config.load_init()?;
+        config.authority = self.authority.key();
+        config.apply(delay_seconds)
+    }
+}
+
+impl<'info> UpdateFeedDelay<'info> {
+    pub fn process(&mut self, delay_seconds: i64) -> Result<()> {
+        let mut config = self.feed_delay_config.load_mut()?;
+        require_keys_eq!(config.authority, self.authority.key(), DelayedFeedError::Unauthorized);
+        config.apply(delay_seconds)
+    }
+}
+
+impl<'info> OpenDelayedFeed<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        // Feeds keep the delay they opened with
+        let delay_seconds = self.feed_delay_config.load()?.delay_seconds;
+        let duel = self.duel.load()?;
+        let state = self.spectator_state.as_ref().map(|account| account.load()).transpose()?;
+        require!(
+            may_spectate(&duel, state.as_deref(), &self.payer.key()),
+            ObserverError::NotAnObserver
+        );
+
+        let mut feed = self.delayed_feed.load_init()?;
+        feed.duel_id = duel.duel_id;
+        feed.delay_seconds = delay_seconds;
+        feed.updated_at = Clock::get()?.unix_timestamp;
+
+        emit!(DelayedFeedOpenedEvent {
+            duel_id: duel.duel_id,
+            delay_seconds,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> MirrorDelayedEvents<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let duel = self.duel.load()?;
+        let mut feed = self.delayed_feed.load_mut()?;
+        let mut released = 0u16;
+
+        if let Some(chunk_account) = &self.replay_chunk {
+            let chunk = chunk_account.load()?;
+            for entry in chunk.entries.iter().skip(feed.cursor_entry as usize) {
+                if !feed.is_releasable(entry.timestamp, now) {
+                    break;
+                }
+                feed.push(entry);
+                feed.cursor_entry += 1;
+                released += 1;
+
+                emit!(DelayedActionEvent {
+                    duel_id: feed.duel_id,
+                    seat: entry.seat,
+                    action_type: entry.action_type,
+                    bet_amount: entry.bet_amount,
+                    round_number: entry.round_number,
+                    acted_at: entry.timestamp,
+                });
+            }
+            // A sealed chunk never grows, so once it is drained move to the next
+            if chunk.is_sealed && feed.cursor_entry as usize >= chunk.entries.len() {
+                feed.cursor_chunk += 1;
+                feed.cursor_entry = 0;
+            }
+        }
+
+        // The result goes out last, once every action before it has been shown
+        let caught_up = feed.transcript == duel.action_transcript;
+        let finished = matches!(duel.game_state, GameState::Completed | GameState::Cancelled);
+        let publish = finished && caught_up && !feed.outcome_published && feed.is_releasable(duel.last_action_time, now);
+        if publish {
+            feed.outcome_published = true;
+            feed.winner = duel.winner;
+
+            emit!(DelayedOutcomeEvent {
+                duel_id: feed.duel_id,
+                winner: duel.winner,
+                cancelled: duel.game_state == GameState::Cancelled,
+            });
+        }
+
+        require!(released > 0 || publish, DelayedFeedError::NothingToRelease);
+        feed.updated_at = now;
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct FeedDelayUpdatedEvent {
+    pub authority: Pubkey,
+    pub delay_seconds: i64,
+}
+
+#[event]
+pub struct DelayedFeedOpenedEvent {
+    pub duel_id: u64,
+    pub delay_seconds: i64,
+}
+
+#[event]
+pub struct DelayedActionEvent {
+    pub duel_id: u64,
+    pub seat: u8,
+    pub action_type: ActionType,
+    pub bet_amount: u64,
+    pub round_number: u8,
+    pub acted_at: i64,
+}
+
+#[event]
+pub struct DelayedOutcomeEvent {
+    pub duel_id: u64,
+    pub winner: Option<Pubkey>,
+    pub cancelled: bool,
+}
+
+#[error_code]
+pub enum DelayedFeedError {
+    #[msg("Feed delay is outside the allowed range")]
+    InvalidDelay,
+    #[msg("Nothing on this feed has aged past its delay yet")]
+    NothingToRelease,
+    #[msg("Only the feed delay authority can update it")]
+    Unauthorized,
+}
@@ -0,0 +1,210 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use bolt_lang::*;
+use crate::components::*;
+use super::head_to_head::HeadToHead;
+use super::matchmaking::{fix_seat_ratings, open_profile, open_seated_duel};
+use super::table_tiers::{require_tier_gate, TableTierConfig};
+use super::{gate_holding, CreateDuelParams, GameError};
+
+/// CreateDuelWithOpponent - Both players sign one transaction that creates the
+/// duel and seats them, so neither is left waiting with an entry fee posted
+#[derive(Accounts)]
+pub struct CreateDuelWithOpponent<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub opponent: Signer<'info>,
+
+    /// CHECK: World PDA
+    #[account(mut)]
+    pub world: AccountInfo<'info>,
+
+    /// CHECK: Entity for the duel
+    #[account(mut)]
+    pub entity: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + std::mem::size_of::<DuelComponent>(),
+        seeds = [b"duel", entity.key().as_ref()],
+        bump
+    )]
+    pub duel: Account<'info, ComponentData<DuelComponent>>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + std::mem::size_of::<BettingComponent>() + MAX_BLIND_LEVELS * std::mem::size_of::<BlindLevel>(),
+        seeds = [b"betting", entity.key().as_ref()],
+        bump
+    )]
+    pub betting: Account<'info, ComponentData<BettingComponent>>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + std::mem::size_of::<PlayerComponent>(),
+        seeds = [b"player", creator.key().as_ref(), entity.key().as_ref()],
+        bump
+    )]
+    pub creator_player: Account<'info, ComponentData<PlayerComponent>>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + std::mem::size_of::<PlayerComponent>(),
+        seeds = [b"player", opponent.key().as_ref(), entity.key().as_ref()],
+        bump
+    )]
+    pub opponent_player: Account<'info, ComponentData<PlayerComponent>>,
+
+    #[account(
+        mut,
+        seeds = [b"psych", creator.key().as_ref()],
+        bump = creator_psych.load()?.bump
+    )]
+    pub creator_psych: Account<'info, ComponentData<PsychProfileComponent>>,
+
+    #[account(
+        mut,
+        seeds = [b"psych", opponent.key().as_ref()],
+        bump = opponent_psych.load()?.bump
+    )]
+    pub opponent_psych: Account<'info, ComponentData<PsychProfileComponent>>,
+
+    #[account(
+        seeds = [b"compliance_config"],
+        bump
+    )]
+    pub compliance_config: Account<'info, ComponentData<ComplianceConfig>>,
+
+    #[account(
+        seeds = [b"compliance_credential", opponent.key().as_ref()],
+        bump
+    )]
+    pub opponent_credential: Option<Account<'info, ComponentData<ComplianceCredential>>>,
+
+    #[account(
+        seeds = [b"feature_flags"],
+        seeds::program = shared::GAME_PROGRAM_ID,
+        bump = feature_flags.bump
+    )]
+    pub feature_flags: Account<'info, shared::FeatureFlags>,
+
+    #[account(
+        seeds = [b"event_schedule"],
+        seeds::program = shared::GAME_PROGRAM_ID,
+        bump = event_schedule.bump
+    )]
+    pub event_schedule: Option<Account<'info, shared::EventSchedule>>,
+
+    /// Holdings for gated tables
+    #[account(token::authority = creator)]
+    pub creator_gate_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(token::authority = opponent)]
+    pub opponent_gate_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Mint and minimum each gated tier requires; needed for gated tables
+    #[account(
+        seeds = [b"table_tiers"],
+        bump
+    )]
+    pub table_tiers: Option<Account<'info, ComponentData<TableTierConfig>>>,
+
+    /// Repeat-pairing counter shared by both players
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = shared::PairingWindow::LEN,
+        seeds = [
+            shared::PAIRING_WINDOW_SEED,
+            shared::PairingWindow::ordered(creator.key(), opponent.key()).0.as_ref(),
+            shared::PairingWindow::ordered(creator.key(), opponent.key()).1.as_ref()
+        ],
+        bump
+    )]
+    pub pairing_window: Account<'info, shared::PairingWindow>,
+
+    /// The pair's lifetime record, counted at settlement
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + std::mem::size_of::<HeadToHead>(),
+        seeds = [
+            b"head_to_head",
+            shared::PairingWindow::ordered(creator.key(), opponent.key()).0.as_ref(),
+            shared::PairingWindow::ordered(creator.key(), opponent.key()).1.as_ref()
+        ],
+        bump
+    )]
+    pub head_to_head: Account<'info, ComponentData<HeadToHead>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateDuelWithOpponent<'info> {
+    pub fn process(
+        &mut self,
+        params: CreateDuelParams,
+        allowlist_proof: Vec<[u8; 32]>,
+        bumps: &CreateDuelWithOpponentBumps,
+    ) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let players = [self.creator.key(), self.opponent.key()];
+        require!(players[0] != players[1], GameError::DuelAlreadyFull);
+        self.pairing_window.record(players[0], players[1], bumps.pairing_window, current_time)?;
+        self.head_to_head.load_mut()?.open(players[0], players[1]);
+
+        // The opponent takes the joining seat, so the same entry checks apply
+        let credential = match &self.opponent_credential {
+            Some(account) => Some(account.load()?.clone()),
+            None => None,
+        };
+        self.compliance_config.load()?.verify(&players[1], &allowlist_proof, credential.as_ref(), current_time)?;
+        require_tier_gate(&self.table_tiers, &params.table_gate)?;
+        for holding in [&self.creator_gate_token_account, &self.opponent_gate_token_account] {
+            require!(params.table_gate.is_satisfied_by(gate_holding(holding)), GameError::TableGateNotMet);
+        }
+
+        let reputations = [
+            open_profile(&self.creator_psych)?,
+            open_profile(&self.opponent_psych)?,
+        ];
+        require!(reputations[1] >= params.min_reputation, GameError::ReputationTooLow);
+
+        let modifiers = shared::event_modifiers(self.event_schedule.as_deref(), current_time);
+        let mut duel = self.duel.load_init()?;
+        let mut betting = self.betting.load_init()?;
+        let mut creator_player = self.creator_player.load_init()?;
+        let mut opponent_player = self.opponent_player.load_init()?;
+        open_seated_duel(
+            &mut duel,
+            &mut betting,
+            [&mut creator_player, &mut opponent_player],
+            players,
+            params,
+            modifiers,
+            current_time,
+        )?;
+        duel.bump = bumps.duel;
+        betting.bump = bumps.betting;
+        creator_player.bump = bumps.creator_player;
+        opponent_player.bump = bumps.opponent_player;
+        let profiles = [self.creator_psych.load()?, self.opponent_psych.load()?];
+        fix_seat_ratings(&mut duel, [&mut creator_player, &mut opponent_player], [&*profiles[0], &*profiles[1]]);
+
+        emit!(crate::DuelStartedEvent {
+            duel_id: duel.duel_id,
+            player_one: players[0],
+            player_two: players[1],
+            player_one_cosmetics: Default::default(),
+            player_two_cosmetics: Default::default(),
+            started_at: current_time,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,246 @@
+use anchor_lang::prelude::*;
+use bolt_lang::*;
+use crate::components::*;
+use super::lobby::LobbyRegistry;
+use super::spectator::SpectatorPool;
+use super::admin_config::AdminConfigError;
+
+/// Suggested TTL: waiting duels become collectable a day after creation
+pub const DEFAULT_ABANDONED_DUEL_TTL: i64 = 24 * 60 * 60;
+
+/// Shortest TTL governance may set, so live tables are never swept
+pub const MIN_ABANDONED_DUEL_TTL: i64 = 60 * 60;
+
+/// Suggested share of the reclaimed rent paid to the cranker (10%)
+pub const DEFAULT_GC_BOUNTY_BPS: u16 = 1_000;
+
+/// Largest bounty share governance may set (50%)
+pub const MAX_GC_BOUNTY_BPS: u16 = 5_000;
+
+/// InitializeDuelGc - Configure how abandoned duels are collected; super admin only
+#[derive(Accounts)]
+pub struct InitializeDuelGc<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [shared::ADMIN_CONFIG_SEED],
+        bump = admin_config.bump,
+        constraint = admin_config.super_admin == authority.key() @ AdminConfigError::NotSuperAdmin
+    )]
+    pub admin_config: Account<'info, shared::AdminConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<DuelGcConfig>(),
+        seeds = [b"duel_gc_config"],
+        bump
+    )]
+    pub config: Account<'info, ComponentData<DuelGcConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// UpdateDuelGc - Adjust the TTL or cranker bounty
+#[derive(Accounts)]
+pub struct UpdateDuelGc<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"duel_gc_config"],
+        bump
+    )]
+    pub config: Account<'info, ComponentData<DuelGcConfig>>,
+}
+
+/// GcAbandonedDuel - Close a duel nobody joined within the TTL, returning its
+/// rent to the creator less the cranker's bounty; anyone may crank it
+#[derive(Accounts)]
+pub struct GcAbandonedDuel<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    /// CHECK: Entity for the duel
+    pub entity: AccountInfo<'info>,
+
+    /// CHECK: Must be the duel's creator; receives the reclaimed rent
+    #[account(
+        mut,
+        address = duel.load()?.player_one @ DuelGcError::WrongCreator,
+        constraint = shared::is_wallet(&creator) @ shared::GameError::InvalidAccountOwner
+    )]
+    pub creator: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        close = creator,
+        seeds = [b"duel", entity.key().as_ref()],
+        bump = duel.load()?.bump
+    )]
+    pub duel: Account<'info, ComponentData<DuelComponent>>,
+
+    #[account(
+        mut,
+        close = creator,
+        seeds = [b"betting", entity.key().as_ref()],
+        bump = betting.load()?.bump
+    )]
+    pub betting: Account<'info, ComponentData<BettingComponent>>,
+
+    #[account(
+        mut,
+        close = creator,
+        seeds = [b"player", creator.key().as_ref(), entity.key().as_ref()],
+        bump = creator_player.load()?.bump
+    )]
+    pub creator_player: Account<'info, ComponentData<PlayerComponent>>,
+
+    /// CHECK: The duel's spectator pool PDA; voided here if one was opened
+    #[account(
+        mut,
+        seeds = [b"spectator_pool", entity.key().as_ref()],
+        bump
+    )]
+    pub spectator_pool: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"duel_gc_config"],
+        bump
+    )]
+    pub config: Account<'info, ComponentData<DuelGcConfig>>,
+
+    #[account(
+        mut,
+        seeds = [b"lobby"],
+        bump
+    )]
+    pub lobby: Account<'info, ComponentData<LobbyRegistry>>,
+}
+
+/// DuelGcConfig - Governance-set collection terms for abandoned duels
+#[component]
+#[derive(Default)]
+pub struct DuelGcConfig {
+    pub authority: Pubkey,
+    pub ttl_seconds: i64,
+    pub bounty_bps: u16,
+    pub updated_at: i64,
+}
+
+impl DuelGcConfig {
+    fn apply(&mut self, ttl_seconds: i64, bounty_bps: u16) -> Result<()> {
+        require!(
+            ttl_seconds >= MIN_ABANDONED_DUEL_TTL && bounty_bps <= MAX_GC_BOUNTY_BPS,
+            DuelGcError::InvalidConfig
+        );
+        self.ttl_seconds = ttl_seconds;
+        self.bounty_bps = bounty_bps;
+        self.updated_at = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
+
+    /// Whether a duel created at `start_time` has waited out the TTL
+    pub fn is_abandoned(&self, start_time: i64, now: i64) -> bool {
+        now >= start_time.saturating_add(self.ttl_seconds)
+    }
+
+    /// Cranker's cut of the reclaimed rent
+    pub fn bounty(&self, reclaimed: u64) -> u64 {
+        (reclaimed as u128 * self.bounty_bps as u128 / 10_000) as u64
+    }
+}
+
+impl<'info> InitializeDuelGc<'info> {
+    pub fn process(&mut self, ttl_seconds: i64, bounty_bps: u16) -> Result<()> {
+        let mut config = self.config.load_init()?;
+        config.authority = self.authority.key();
+        config.apply(ttl_seconds, bounty_bps)
+    }
+}
+
+impl<'info> UpdateDuelGc<'info> {
+    pub fn process(&mut self, ttl_seconds: i64, bounty_bps: u16) -> Result<()> {
+        let mut config = self.config.load_mut()?;
+        require_keys_eq!(config.authority, self.authority.key(), DuelGcError::Unauthorized);
+        config.apply(ttl_seconds, bounty_bps)
+    }
+}
+
+impl<'info> GcAbandonedDuel<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let config = self.config.load()?;
+
+        let (duel_id, refunded_entry_fee) = {
+            let duel = self.duel.load()?;
+            require!(duel.game_state == GameState::WaitingForPlayers, DuelGcError::NotAbandoned);
+            require!(config.is_abandoned(duel.start_time, now), DuelGcError::NotAbandoned);
+            // The posted entry fee is the only thing in the pot before anyone joins
+            (duel.duel_id, self.betting.load()?.total_pot)
+        };
+
+        // Settlement needs the duel, so void any side market now and let
+        // backers reclaim their stakes from the pool on their own
+        if !self.spectator_pool.data_is_empty() {
+            let pool: Account<ComponentData<SpectatorPool>> =
+                Account::try_from(&self.spectator_pool.to_account_info())?;
+            {
+                let mut pool = pool.load_mut()?;
+                if !pool.settled {
+                    pool.voided = true;
+                    pool.settled = true;
+                }
+            }
+            pool.exit(&crate::ID)?;
+        }
+
+        self.lobby.load_mut()?.delist(&self.entity.key());
+
+        // The bounty comes out of the reclaimed rent; the close constraints
+        // sweep the remainder of all three accounts to the creator
+        let reclaimed = self.duel.to_account_info().lamports()
+            + self.betting.to_account_info().lamports()
+            + self.creator_player.to_account_info().lamports();
+        let bounty = config.bounty(reclaimed);
+        drop(config);
+        let duel_info = self.duel.to_account_info();
+        let bounty = bounty.min(duel_info.lamports());
+        **duel_info.try_borrow_mut_lamports()? -= bounty;
+        **self.cranker.to_account_info().try_borrow_mut_lamports()? += bounty;
+
+        emit!(AbandonedDuelCollectedEvent {
+            duel_id,
+            creator: self.creator.key(),
+            cranker: self.cranker.key(),
+            refunded_entry_fee,
+            rent_reclaimed: reclaimed - bounty,
+            bounty,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct AbandonedDuelCollectedEvent {
+    pub duel_id: u64,
+    pub creator: Pubkey,
+    pub cranker: Pubkey,
+    pub refunded_entry_fee: u64,
+    pub rent_reclaimed: u64,
+    pub bounty: u64,
+}
+
+#[error_code]
+pub enum DuelGcError {
+    #[msg("Duel is not waiting for players or its TTL has not passed")]
+    NotAbandoned,
+    #[msg("Creator account does not match the duel")]
+    WrongCreator,
+    #[msg("GC TTL or bounty is out of range")]
+    InvalidConfig,
+    #[msg("Only the GC authority can change collection terms")]
+    Unauthorized,
+}
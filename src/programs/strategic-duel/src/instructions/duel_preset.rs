@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use bolt_lang::*;
+use crate::components::*;
+use super::{CreateDuel, CreateDuelParams};
+
+/// SaveDuelPreset - Store or overwrite one of a host's saved table settings
+#[derive(Accounts)]
+#[instruction(preset_id: u8)]
+pub struct SaveDuelPreset<'info> {
+    #[account(mut)]
+    pub host: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = host,
+        space = 8 + std::mem::size_of::<DuelPreset>() + MAX_BLIND_LEVELS * std::mem::size_of::<BlindLevel>(),
+        seeds = [b"duel_preset", host.key().as_ref(), &[preset_id]],
+        bump
+    )]
+    pub preset: Account<'info, ComponentData<DuelPreset>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// CreateDuelFromPreset - Open a duel with the settings of a saved preset
+#[derive(Accounts)]
+#[instruction(preset_id: u8)]
+pub struct CreateDuelFromPreset<'info> {
+    pub create: CreateDuel<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"duel_preset", create.creator.key().as_ref(), &[preset_id]],
+        bump,
+        constraint = preset.load()?.host == create.creator.key() @ PresetError::NotPresetHost
+    )]
+    pub preset: Account<'info, ComponentData<DuelPreset>>,
+}
+
+/// DuelPreset - A host's saved CreateDuelParams, reusable for new tables
+#[component]
+#[derive(Default)]
+pub struct DuelPreset {
+    pub host: Pubkey,
+    pub preset_id: u8,
+    pub params: CreateDuelParams,
+    pub duels_created: u32,
+    pub updated_at: i64,
+    pub last_used_at: i64,
+}
+
+impl<'info> SaveDuelPreset<'info> {
+    pub fn process(&mut self, preset_id: u8, params: CreateDuelParams) -> Result<()> {
+        let mut preset = self.preset.load_mut()?;
+        let host = self.host.key();
+        if preset.host == Pubkey::default() {
+            preset.host = host;
+            preset.preset_id = preset_id;
+        }
+        require_keys_eq!(preset.host, host, PresetError::NotPresetHost);
+
+        preset.params = params;
+        preset.updated_at = Clock::get()?.unix_timestamp;
+
+        emit!(DuelPresetSavedEvent {
+            host,
+            preset_id,
+            max_rounds: preset.params.max_rounds,
+            min_bet: preset.params.min_bet,
+            max_bet: preset.params.max_bet,
+            entry_fee: preset.params.entry_fee,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> CreateDuelFromPreset<'info> {
+    pub fn process(&mut self, preset_id: u8, bumps: &CreateDuelFromPresetBumps) -> Result<()> {
+        let params = self.preset.load()?.params.clone();
+        self.create.process(params, &bumps.create)?;
+
+        let mut preset = self.preset.load_mut()?;
+        preset.duels_created = preset.duels_created.saturating_add(1);
+        preset.last_used_at = Clock::get()?.unix_timestamp;
+
+        emit!(DuelCreatedFromPresetEvent {
+            host: preset.host,
+            preset_id,
+            duel_id: self.create.duel.load()?.duel_id,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct DuelPresetSavedEvent {
+    pub host: Pubkey,
+    pub preset_id: u8,
+    pub max_rounds: u8,
+    pub min_bet: u64,
+    pub max_bet: u64,
+    pub entry_fee: u64,
+}
+
+#[event]
+pub struct DuelCreatedFromPresetEvent {
+    pub host: Pubkey,
+    pub preset_id: u8,
+    pub duel_id: u64,
+}
+
+#[error_code]
+pub enum PresetError {
+    #[msg("Only the host who saved a preset can use or change it")]
+    NotPresetHost,
+}
@@ -0,0 +1,194 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use bolt_lang::*;
+use crate::components::*;
+
+/// Seconds committed players have to reveal once resolution is requested
+pub const ENTROPY_REVEAL_WINDOW: i64 = 120;
+
+/// CommitEntropy - A seated player commits to secret entropy before resolution
+#[derive(Accounts)]
+pub struct CommitEntropy<'info> {
+    pub player_signer: Signer<'info>,
+
+    /// CHECK: Entity for the duel
+    pub entity: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"duel", entity.key().as_ref()],
+        bump = duel.load()?.bump
+    )]
+    pub duel: Account<'info, ComponentData<DuelComponent>>,
+}
+
+/// RevealEntropy - Open a commitment once the duel is waiting on VRF
+/// resolution, before the reveal deadline
+#[derive(Accounts)]
+pub struct RevealEntropy<'info> {
+    pub player_signer: Signer<'info>,
+
+    /// CHECK: Entity for the duel
+    pub entity: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"duel", entity.key().as_ref()],
+        bump = duel.load()?.bump
+    )]
+    pub duel: Account<'info, ComponentData<DuelComponent>>,
+}
+
+impl<'info> CommitEntropy<'info> {
+    pub fn process(&mut self, commitment: [u8; 32]) -> Result<()> {
+        let mut duel = self.duel.load_mut()?;
+
+        // Commitments close when resolution starts so reveals can't be chosen late
+        require!(
+            matches!(
+                duel.game_state,
+                GameState::WaitingForPlayers | GameState::InProgress | GameState::AwaitingAction
+            ),
+            EntropyError::CommitWindowClosed
+        );
+        let seat = seat_of(&duel, &self.player_signer.key())?;
+        require!(duel.entropy_commitments[seat] == [0u8; 32], EntropyError::AlreadyCommitted);
+        require!(commitment != [0u8; 32], EntropyError::InvalidCommitment);
+
+        duel.entropy_commitments[seat] = commitment;
+
+        emit!(EntropyCommittedEvent {
+            duel_id: duel.duel_id,
+            player: self.player_signer.key(),
+            commitment,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> RevealEntropy<'info> {
+    pub fn process(&mut self, entropy: [u8; 32]) -> Result<()> {
+        let mut duel = self.duel.load_mut()?;
+
+        require!(
+            duel.game_state == GameState::ResolutionPending
+                && Clock::get()?.unix_timestamp <= duel.entropy_reveal_deadline,
+            EntropyError::RevealWindowClosed
+        );
+        let player = self.player_signer.key();
+        let seat = seat_of(&duel, &player)?;
+        require!(duel.entropy_commitments[seat] != [0u8; 32], EntropyError::NothingCommitted);
+        require!(!duel.entropy_revealed[seat], EntropyError::AlreadyRevealed);
+        require!(
+            entropy_commitment(&entropy, &player) == duel.entropy_commitments[seat],
+            EntropyError::InvalidCommitment
+        );
+
+        duel.entropy_reveals[seat] = entropy;
+        duel.entropy_revealed[seat] = true;
+
+        emit!(EntropyRevealedEvent {
+            duel_id: duel.duel_id,
+            player,
+            entropy,
+        });
+
+        Ok(())
+    }
+}
+
+/// Commitment clients submit: the hash of the secret and the committing player,
+/// so one player cannot copy the other's commitment
+pub fn entropy_commitment(entropy: &[u8; 32], player: &Pubkey) -> [u8; 32] {
+    hashv(&[entropy, player.as_ref()]).to_bytes()
+}
+
+/// Final randomness from the VRF output and every revealed player secret.
+/// Committed but unrevealed seats contribute nothing.
+pub fn mix_entropy(vrf_result: u64, duel: &DuelComponent) -> u64 {
+    if !duel.entropy_revealed.iter().any(|revealed| *revealed) {
+        return vrf_result;
+    }
+    let zero = [0u8; 32];
+    let reveal = |seat: usize| if duel.entropy_revealed[seat] { &duel.entropy_reveals[seat] } else { &zero };
+    let hash = hashv(&[&vrf_result.to_le_bytes(), reveal(0), reveal(1)]).to_bytes();
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&hash[0..8]);
+    u64::from_le_bytes(bytes)
+}
+
+/// Where the committed entropy stands for resolution
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RevealStatus {
+    /// Every commitment is open, or both seats let the deadline pass
+    Ready,
+    /// A commitment is still unopened inside the reveal window
+    Outstanding,
+    /// Only one seat failed to reveal in time; the other seat wins
+    Forfeit { winner_seat: usize },
+}
+
+/// A seat that withholds its reveal after seeing the VRF-bound state forfeits,
+/// so withholding can never be used to veto an unfavourable outcome
+pub fn reveal_status(duel: &DuelComponent, now: i64) -> RevealStatus {
+    let unrevealed = |seat: usize| duel.entropy_commitments[seat] != [0u8; 32] && !duel.entropy_revealed[seat];
+    match (unrevealed(0), unrevealed(1)) {
+        (false, false) => RevealStatus::Ready,
+        _ if now <= duel.entropy_reveal_deadline => RevealStatus::Outstanding,
+        (true, false) => RevealStatus::Forfeit { winner_seat: 1 },
+        (false, true) => RevealStatus::Forfeit { winner_seat: 0 },
+        (true, true) => RevealStatus::Ready,
+    }
+}
+
+fn seat_of(duel: &DuelComponent, player: &Pubkey) -> Result<usize> {
+    if *player == duel.player_one {
+        Ok(0)
+    } else if *player == duel.player_two && duel.player_two != Pubkey::default() {
+        Ok(1)
+    } else {
+        err!(EntropyError::NotSeated)
+    }
+}
+
+#[event]
+pub struct EntropyCommittedEvent {
+    pub duel_id: u64,
+    pub player: Pubkey,
+    pub commitment: [u8; 32],
+}
+
+#[event]
+pub struct EntropyRevealedEvent {
+    pub duel_id: u64,
+    pub player: Pubkey,
+    pub entropy: [u8; 32],
+}
+
+#[event]
+pub struct EntropyForfeitedEvent {
+    pub duel_id: u64,
+    pub forfeited_by: Pubkey,
+    pub winner: Pubkey,
+}
+
+#[error_code]
+pub enum EntropyError {
+    #[msg("Signer is not seated in this duel")]
+    NotSeated,
+    #[msg("Entropy commitments are closed")]
+    CommitWindowClosed,
+    #[msg("Entropy can only be revealed while resolution is pending")]
+    RevealWindowClosed,
+    #[msg("Entropy already committed")]
+    AlreadyCommitted,
+    #[msg("No entropy commitment to reveal")]
+    NothingCommitted,
+    #[msg("Entropy already revealed")]
+    AlreadyRevealed,
+    #[msg("Entropy does not match the commitment")]
+    InvalidCommitment,
+    #[msg("A committed player can still reveal entropy")]
+    RevealsOutstanding,
+}
@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{ed25519_program, hash::hashv, sysvar::instructions as ix_sysvar};
 use bolt_lang::*;
 use crate::components::*;
+use crate::instructions::operator_bond::{BondError, OperatorBond};
 
 /// Ephemeral Rollup State Delegation for MagicBlock integration
 #[derive(Accounts)]
@@ -8,12 +10,15 @@ pub struct EphemeralRollupDelegation<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
 
-    /// CHECK: MagicBlock validator authority
-    pub validator: AccountInfo<'info>,
+    /// MagicBlock validator authority; co-signs delegation and every transition it posts
+    pub validator: Signer<'info>,
 
-    /// CHECK: World PDA
-    #[account(mut)]
-    pub world: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"world"],
+        bump
+    )]
+    pub world: Account<'info, ComponentData<crate::BoltWorldComponent>>,
 
     /// CHECK: Entity for the duel
     #[account(mut)]
@@ -22,7 +27,7 @@ pub struct EphemeralRollupDelegation<'info> {
     #[account(
         mut,
         seeds = [b"duel", entity.key().as_ref()],
-        bump
+        bump = duel.load()?.bump
     )]
     pub duel: Account<'info, ComponentData<DuelComponent>>,
 
@@ -54,9 +59,139 @@ pub struct EphemeralRollupDelegation<'info> {
     )]
     pub session_token: Account<'info, ComponentData<SessionTokenComponent>>,
 
+    /// Bond of the validator operating the rollup
+    #[account(
+        mut,
+        seeds = [b"operator_bond", validator.key().as_ref()],
+        bump
+    )]
+    pub operator_bond: Account<'info, ComponentData<OperatorBond>>,
+
     pub system_program: Program<'info, System>,
 }
 
+/// RenewDelegation - Extend an active rollup session with the player's consent
+#[derive(Accounts)]
+pub struct RenewDelegation<'info> {
+    /// Usually the rollup operator; cannot extend without the player's signature
+    pub submitter: Signer<'info>,
+
+    /// CHECK: Player whose consent is verified through the ed25519 instruction
+    pub player: AccountInfo<'info>,
+
+    /// CHECK: Entity for the duel
+    pub entity: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"duel", entity.key().as_ref()],
+        bump = duel.load()?.bump
+    )]
+    pub duel: Account<'info, ComponentData<DuelComponent>>,
+
+    #[account(
+        mut,
+        seeds = [b"ephemeral_rollup", entity.key().as_ref()],
+        bump
+    )]
+    pub ephemeral_rollup: Account<'info, ComponentData<EphemeralRollupComponent>>,
+
+    #[account(
+        mut,
+        seeds = [b"session_token", entity.key().as_ref(), player.key().as_ref()],
+        bump
+    )]
+    pub session_token: Account<'info, ComponentData<SessionTokenComponent>>,
+
+    /// CHECK: Instructions sysvar, read to find the ed25519 consent signature
+    #[account(address = ix_sysvar::ID)]
+    pub instructions: AccountInfo<'info>,
+}
+
+/// EmergencyExitRollup - Pull a duel back from its rollup. Only the player
+/// signs, so an unresponsive validator cannot block the exit.
+#[derive(Accounts)]
+pub struct EmergencyExitRollup<'info> {
+    pub authority: Signer<'info>,
+
+    /// CHECK: Entity for the duel
+    pub entity: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"duel", entity.key().as_ref()],
+        bump = duel.load()?.bump
+    )]
+    pub duel: Account<'info, ComponentData<DuelComponent>>,
+
+    #[account(
+        mut,
+        seeds = [b"ephemeral_rollup", entity.key().as_ref()],
+        bump
+    )]
+    pub ephemeral_rollup: Account<'info, ComponentData<EphemeralRollupComponent>>,
+
+    #[account(
+        mut,
+        seeds = [b"session_token", entity.key().as_ref(), authority.key().as_ref()],
+        bump
+    )]
+    pub session_token: Account<'info, ComponentData<SessionTokenComponent>>,
+}
+
+/// DisputeStateTransition - A duel player challenges a pending transition
+/// inside its window. A proven fraud blocks finalization and lets
+/// `slash_operator_bond` slash the operator for `FraudProven`.
+#[derive(Accounts)]
+pub struct DisputeStateTransition<'info> {
+    pub challenger: Signer<'info>,
+
+    /// CHECK: Entity for the duel
+    pub entity: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"duel", entity.key().as_ref()],
+        bump = duel.load()?.bump
+    )]
+    pub duel: Account<'info, ComponentData<DuelComponent>>,
+
+    #[account(
+        mut,
+        seeds = [b"ephemeral_rollup", entity.key().as_ref()],
+        bump
+    )]
+    pub ephemeral_rollup: Account<'info, ComponentData<EphemeralRollupComponent>>,
+
+    #[account(
+        mut,
+        seeds = [b"state_transition", entity.key().as_ref()],
+        bump
+    )]
+    pub state_transition: Account<'info, ComponentData<StateTransitionComponent>>,
+}
+
+/// Accounts per duel in `FinalizeRollupBatch`'s remaining_accounts:
+/// entity, duel, ephemeral_rollup, state_transition
+pub const BATCH_ACCOUNTS_PER_DUEL: usize = 4;
+
+/// FinalizeRollupBatch - Finalize every listed duel delegated to one validator.
+/// Duels are passed in `remaining_accounts`, `BATCH_ACCOUNTS_PER_DUEL` at a time.
+#[derive(Accounts)]
+pub struct FinalizeRollupBatch<'info> {
+    /// Validator operating the rollup
+    pub validator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"operator_bond", validator.key().as_ref()],
+        bump
+    )]
+    pub operator_bond: Account<'info, ComponentData<OperatorBond>>,
+
+    /// CHECK: Instructions sysvar, read to find the validator's signature over the batch root
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
 /// Ephemeral Rollup Component for managing rollup state
 #[component]
 #[derive(Default)]
@@ -182,6 +317,11 @@ impl<'info> EphemeralRollupDelegation<'info> {
             GameError::InvalidGameState
         );
 
+        // Only bonded operators can take custody of a duel
+        let mut bond = self.operator_bond.load_mut()?;
+        require!(bond.can_accept_rollup(), BondError::InsufficientBond);
+        bond.active_rollups += 1;
+
         // Initialize rollup
         rollup.duel_id = duel.duel_id;
         rollup.rollup_id = self.generate_rollup_id(&duel, current_time);
@@ -189,6 +329,7 @@ impl<'info> EphemeralRollupDelegation<'info> {
         rollup.expiration_timestamp = current_time + rollup_duration;
         rollup.validator_pubkey = self.validator.key();
         rollup.rollup_status = RollupStatus::Initializing;
+        duel.latency_authority = self.validator.key();
         rollup.delegation_proof = delegation_proof;
         rollup.is_active = true;
         rollup.emergency_exit_enabled = true;
@@ -245,8 +386,9 @@ impl<'info> EphemeralRollupDelegation<'info> {
         let rollup = self.ephemeral_rollup.load()?;
         let mut transition = self.state_transition.load_mut()?;
 
-        // Verify rollup is active
+        // Verify rollup is active and the transition comes from its operator
         require!(rollup.is_active, GameError::RollupNotActive);
+        require_keys_eq!(rollup.validator_pubkey, self.validator.key(), RollupError::WrongValidator);
         require!(rollup.rollup_status == RollupStatus::Active, GameError::InvalidRollupStatus);
         require!(current_time < rollup.expiration_timestamp, GameError::RollupExpired);
 
@@ -288,8 +430,9 @@ impl<'info> EphemeralRollupDelegation<'info> {
         let mut duel = self.duel.load_mut()?;
         let state_transition = self.state_transition.load()?;
 
-        // Verify rollup can be finalized
+        // Verify rollup can be finalized by its own operator
         require!(rollup.is_active, GameError::RollupNotActive);
+        require_keys_eq!(rollup.validator_pubkey, self.validator.key(), RollupError::WrongValidator);
         require!(rollup.can_finalize, GameError::CannotFinalizeRollup);
         require!(!state_transition.is_disputed, GameError::TransitionDisputed);
         require!(current_time > state_transition.challenge_window_end, GameError::ChallengeWindowActive);
@@ -304,6 +447,9 @@ impl<'info> EphemeralRollupDelegation<'info> {
         };
         rollup.state_checkpoints.push(final_checkpoint);
 
+        let mut bond = self.operator_bond.load_mut()?;
+        bond.active_rollups = bond.active_rollups.saturating_sub(1);
+
         // Finalize rollup
         rollup.rollup_status = RollupStatus::Finalized;
         rollup.is_active = false;
@@ -312,6 +458,10 @@ impl<'info> EphemeralRollupDelegation<'info> {
         // Update duel state
         duel.rollup_delegated = false;
         duel.rollup_finalized = true;
+        require!(
+            duel.game_state.can_step_to(state_transition.to_state),
+            RollupError::InvalidStateTransition
+        );
         duel.game_state = state_transition.to_state;
 
         emit!(RollupFinalizedEvent {
@@ -325,44 +475,6 @@ impl<'info> EphemeralRollupDelegation<'info> {
         Ok(())
     }
 
-    pub fn emergency_exit(&mut self) -> Result<()> {
-        let clock = Clock::get()?;
-        let current_time = clock.unix_timestamp;
-
-        let mut rollup = self.ephemeral_rollup.load_mut()?;
-        let mut duel = self.duel.load_mut()?;
-        let mut session_token = self.session_token.load_mut()?;
-
-        // Verify emergency exit is allowed
-        require!(rollup.emergency_exit_enabled, GameError::EmergencyExitDisabled);
-        require!(session_token.permissions.emergency_exit_allowed, GameError::EmergencyExitNotPermitted);
-
-        // Perform emergency state recovery
-        self.recover_state_from_last_checkpoint(&mut duel, &rollup)?;
-
-        // Deactivate rollup
-        rollup.rollup_status = RollupStatus::EmergencyExit;
-        rollup.is_active = false;
-        rollup.emergency_exit_enabled = false;
-
-        // Deactivate session
-        session_token.is_active = false;
-        session_token.delegated_to_rollup = false;
-
-        // Reset duel rollup state
-        duel.rollup_delegated = false;
-        duel.rollup_id = None;
-
-        emit!(EmergencyExitEvent {
-            duel_id: duel.duel_id,
-            rollup_id: rollup.rollup_id,
-            exit_timestamp: current_time,
-            recovered_state: duel.game_state,
-        });
-
-        Ok(())
-    }
-
     // Helper functions
     fn generate_rollup_id(&self, duel: &DuelComponent, timestamp: i64) -> [u8; 32] {
         let mut id_data = Vec::new();
@@ -423,23 +535,11 @@ impl<'info> EphemeralRollupDelegation<'info> {
     }
 
     fn validate_state_transition(&self, transition: &StateTransitionComponent) -> Result<()> {
-        // Validate transition is logical
-        match (transition.from_state, transition.to_state) {
-            (GameState::InProgress, GameState::AwaitingAction) => Ok(()),
-            (GameState::AwaitingAction, GameState::InProgress) => Ok(()),
-            (GameState::InProgress, GameState::ResolutionPending) => Ok(()),
-            (GameState::ResolutionPending, GameState::Completed) => Ok(()),
-            _ => Err(GameError::InvalidStateTransition.into()),
-        }
-    }
-
-    fn recover_state_from_last_checkpoint(&self, duel: &mut DuelComponent, rollup: &EphemeralRollupComponent) -> Result<()> {
-        if let Some(last_checkpoint) = rollup.state_checkpoints.last() {
-            // Recover to last known good state
-            // In a real implementation, this would deserialize the state from the checkpoint
-            duel.game_state = GameState::InProgress; // Safe fallback state
-            duel.last_action_time = last_checkpoint.timestamp;
-        }
+        // Validate transition against the shared duel transition table
+        require!(
+            transition.from_state.can_transition_to(transition.to_state),
+            RollupError::InvalidStateTransition
+        );
         Ok(())
     }
 
@@ -498,6 +598,269 @@ impl<'info> EphemeralRollupDelegation<'info> {
     }
 }
 
+fn recover_state_from_last_checkpoint(duel: &mut DuelComponent, rollup: &EphemeralRollupComponent) -> Result<()> {
+    if let Some(last_checkpoint) = rollup.state_checkpoints.last() {
+        // Recover to last known good state
+        // In a real implementation, this would deserialize the state from the checkpoint
+        require!(duel.apply_transition(DuelTrigger::Recover), RollupError::InvalidStateTransition); // Safe fallback state
+        duel.last_action_time = last_checkpoint.timestamp;
+    }
+    Ok(())
+}
+
+/// Whether a posted transition contradicts the duel's committed state: it starts
+/// somewhere else, or lands more than one legal step away
+pub fn is_fraudulent_transition(committed: GameState, from_state: GameState, to_state: GameState) -> bool {
+    from_state != committed || !committed.can_step_to(to_state)
+}
+
+/// Message a player signs to consent to a renewal: rollup id || new expiry (LE)
+pub fn renewal_message(rollup_id: &[u8; 32], new_expiry: i64) -> [u8; 40] {
+    let mut message = [0u8; 40];
+    message[..32].copy_from_slice(rollup_id);
+    message[32..].copy_from_slice(&new_expiry.to_le_bytes());
+    message
+}
+
+/// Check that the instruction right before this one is an ed25519 verification of
+/// `message` signed by `signer`
+fn verify_ed25519_signature(instructions: &AccountInfo, signer: &Pubkey, message: &[u8]) -> Result<()> {
+    let current_index = ix_sysvar::load_current_index_checked(instructions)?;
+    require!(current_index > 0, RollupError::MissingConsentSignature);
+    let ix = ix_sysvar::load_instruction_at_checked((current_index - 1) as usize, instructions)?;
+    require_keys_eq!(ix.program_id, ed25519_program::ID, RollupError::MissingConsentSignature);
+    require!(ed25519_matches(&ix.data, signer, message), RollupError::InvalidConsentSignature);
+    Ok(())
+}
+
+/// Whether ed25519 program instruction data verifies exactly one `message` signed
+/// by `signer`, with all offsets pointing into that same instruction
+pub(crate) fn ed25519_matches(data: &[u8], signer: &Pubkey, message: &[u8]) -> bool {
+    shared::ed25519_signature(data, signer, message).is_some()
+}
+
+impl<'info> RenewDelegation<'info> {
+    pub fn process(&mut self, new_expiry: i64) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let duel = self.duel.load()?;
+        let mut rollup = self.ephemeral_rollup.load_mut()?;
+        let mut session_token = self.session_token.load_mut()?;
+
+        require!(rollup.is_active, RollupError::RollupNotActive);
+        require!(rollup.rollup_status == RollupStatus::Active, RollupError::InvalidRollupStatus);
+        // A lapsed delegation must go through a full re-delegation
+        require!(current_time < rollup.expiration_timestamp, RollupError::RollupExpired);
+        require!(new_expiry > rollup.expiration_timestamp, RollupError::InvalidRenewal);
+        require!(
+            session_token.is_active && session_token.player == self.player.key(),
+            RollupError::InvalidRenewal
+        );
+        require!(
+            self.player.key() == duel.player_one || self.player.key() == duel.player_two,
+            RollupError::InvalidRenewal
+        );
+
+        // Binding the signature to the rollup id and the exact new expiry keeps the
+        // operator from reusing it on another session or for a longer extension
+        verify_ed25519_signature(
+            &self.instructions,
+            &self.player.key(),
+            &renewal_message(&rollup.rollup_id, new_expiry),
+        )?;
+
+        let previous_expiry = rollup.expiration_timestamp;
+        rollup.expiration_timestamp = new_expiry;
+        session_token.expires_at = new_expiry;
+
+        emit!(DelegationRenewedEvent {
+            duel_id: rollup.duel_id,
+            rollup_id: rollup.rollup_id,
+            player: self.player.key(),
+            previous_expiry,
+            new_expiry,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> EmergencyExitRollup<'info> {
+        pub fn process(&mut self) -> Result<()> {
+            let clock = Clock::get()?;
+            let current_time = clock.unix_timestamp;
+
+            let mut rollup = self.ephemeral_rollup.load_mut()?;
+            let mut duel = self.duel.load_mut()?;
+            let mut session_token = self.session_token.load_mut()?;
+
+            // Verify emergency exit is allowed
+            require!(rollup.emergency_exit_enabled, GameError::EmergencyExitDisabled);
+            require!(session_token.permissions.emergency_exit_allowed, GameError::EmergencyExitNotPermitted);
+
+            // Perform emergency state recovery
+            recover_state_from_last_checkpoint(&mut duel, &rollup)?;
+
+            // Deactivate rollup
+            rollup.rollup_status = RollupStatus::EmergencyExit;
+            rollup.is_active = false;
+            rollup.emergency_exit_enabled = false;
+
+            // Deactivate session
+            session_token.is_active = false;
+            session_token.delegated_to_rollup = false;
+
+            // Reset duel rollup state
+            duel.rollup_delegated = false;
+            duel.rollup_id = None;
+
+            emit!(EmergencyExitEvent {
+                duel_id: duel.duel_id,
+                rollup_id: rollup.rollup_id,
+                exit_timestamp: current_time,
+                recovered_state: duel.game_state,
+            });
+
+            Ok(())
+        }
+}
+
+impl<'info> DisputeStateTransition<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let duel = self.duel.load()?;
+        let mut rollup = self.ephemeral_rollup.load_mut()?;
+        let mut transition = self.state_transition.load_mut()?;
+
+        require!(duel.seat_of(&self.challenger.key()).is_some(), RollupError::NotDuelPlayer);
+        require!(rollup.is_active, RollupError::RollupNotActive);
+        require!(!transition.is_disputed, RollupError::TransitionDisputed);
+        require!(current_time <= transition.challenge_window_end, RollupError::ChallengeWindowClosed);
+        require!(
+            is_fraudulent_transition(duel.game_state, transition.from_state, transition.to_state),
+            RollupError::TransitionNotFraudulent
+        );
+
+        transition.is_disputed = true;
+        rollup.can_finalize = false;
+
+        emit!(TransitionDisputedEvent {
+            duel_id: duel.duel_id,
+            rollup_id: rollup.rollup_id,
+            transition_id: transition.transition_id,
+            challenger: self.challenger.key(),
+            committed_state: duel.game_state,
+            from_state: transition.from_state,
+            to_state: transition.to_state,
+        });
+
+        Ok(())
+    }
+}
+
+/// Root committing to every duel in a batch, in the order they were finalized
+pub fn batch_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    let mut root = [0u8; 32];
+    for leaf in leaves {
+        root = hashv(&[&root, leaf]).to_bytes();
+    }
+    root
+}
+
+impl<'info> FinalizeRollupBatch<'info> {
+    /// `batch_signature` is the validator's ed25519 signature over the batch root
+    /// of (rollup_id, final state root) for every duel, verified by the preceding
+    /// ed25519 instruction, so one attestation finalizes them all
+    pub fn process(&mut self, remaining_accounts: &[AccountInfo<'info>], batch_signature: [u8; 64]) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        require!(
+            !remaining_accounts.is_empty() && remaining_accounts.len() % BATCH_ACCOUNTS_PER_DUEL == 0,
+            RollupError::InvalidBatch
+        );
+
+        let mut leaves = Vec::with_capacity(remaining_accounts.len() / BATCH_ACCOUNTS_PER_DUEL);
+        let mut duels = Vec::with_capacity(leaves.capacity());
+        let mut entities: Vec<Pubkey> = Vec::with_capacity(leaves.capacity());
+
+        for group in remaining_accounts.chunks(BATCH_ACCOUNTS_PER_DUEL) {
+            let entity = group[0].key();
+            require!(!entities.contains(&entity), RollupError::InvalidBatch);
+            entities.push(entity);
+            for (info, seed) in group[1..].iter().zip([&b"duel"[..], b"ephemeral_rollup", b"state_transition"]) {
+                let (expected, _) = Pubkey::find_program_address(&[seed, entity.as_ref()], &crate::ID);
+                require_keys_eq!(info.key(), expected, RollupError::InvalidBatch);
+            }
+
+            let duel: Account<ComponentData<DuelComponent>> = Account::try_from(&group[1])?;
+            let rollup: Account<ComponentData<EphemeralRollupComponent>> = Account::try_from(&group[2])?;
+            let transition: Account<ComponentData<StateTransitionComponent>> = Account::try_from(&group[3])?;
+
+            let state_root = {
+                let rollup_data = rollup.load()?;
+                let transition_data = transition.load()?;
+                require_keys_eq!(rollup_data.validator_pubkey, self.validator.key(), RollupError::InvalidBatch);
+                require!(rollup_data.is_active, RollupError::RollupNotActive);
+                require!(rollup_data.can_finalize, RollupError::CannotFinalizeRollup);
+                require!(!transition_data.is_disputed, RollupError::TransitionDisputed);
+                require!(current_time > transition_data.challenge_window_end, RollupError::ChallengeWindowActive);
+
+                let state_root = hashv(&[
+                    &rollup_data.duel_id.to_le_bytes(),
+                    &[transition_data.to_state as u8],
+                    &rollup_data.transaction_count.to_le_bytes(),
+                    &rollup_data.gas_used.to_le_bytes(),
+                ]).to_bytes();
+                leaves.push(hashv(&[&rollup_data.rollup_id, &state_root]).to_bytes());
+                state_root
+            };
+            duels.push((duel, rollup, transition, state_root));
+        }
+
+        let root = batch_root(&leaves);
+        let signature = shared::preceding_ed25519_signature(&self.instructions, &self.validator.key(), &root)?;
+        require!(signature == batch_signature, RollupError::InvalidBatch);
+
+        for (duel, rollup, transition, state_root) in duels {
+            let to_state = transition.load()?.to_state;
+            {
+                let mut rollup_data = rollup.load_mut()?;
+                let checkpoint_id = rollup_data.state_checkpoints.len() as u64;
+                let transaction_count = rollup_data.transaction_count;
+                rollup_data.state_checkpoints.push(StateCheckpoint {
+                    checkpoint_id,
+                    state_root,
+                    timestamp: current_time,
+                    transaction_count,
+                    merkle_root: root,
+                });
+                rollup_data.rollup_status = RollupStatus::Finalized;
+                rollup_data.is_active = false;
+                rollup_data.can_finalize = false;
+
+                let mut duel_data = duel.load_mut()?;
+                duel_data.rollup_delegated = false;
+                duel_data.rollup_finalized = true;
+                require!(duel_data.game_state.can_step_to(to_state), RollupError::InvalidStateTransition);
+                duel_data.game_state = to_state;
+            }
+            duel.exit(&crate::ID)?;
+            rollup.exit(&crate::ID)?;
+        }
+
+        let mut bond = self.operator_bond.load_mut()?;
+        bond.active_rollups = bond.active_rollups.saturating_sub(leaves.len() as u32);
+
+        emit!(RollupBatchFinalizedEvent {
+            validator: self.validator.key(),
+            duel_count: leaves.len() as u32,
+            batch_root: root,
+            batch_signature,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+}
+
 // Events
 #[event]
 pub struct RollupDelegatedEvent {
@@ -535,6 +898,35 @@ pub struct EmergencyExitEvent {
     pub recovered_state: GameState,
 }
 
+#[event]
+pub struct TransitionDisputedEvent {
+    pub duel_id: u64,
+    pub rollup_id: [u8; 32],
+    pub transition_id: u64,
+    pub challenger: Pubkey,
+    pub committed_state: GameState,
+    pub from_state: GameState,
+    pub to_state: GameState,
+}
+
+#[event]
+pub struct RollupBatchFinalizedEvent {
+    pub validator: Pubkey,
+    pub duel_count: u32,
+    pub batch_root: [u8; 32],
+    pub batch_signature: [u8; 64],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DelegationRenewedEvent {
+    pub duel_id: u64,
+    pub rollup_id: [u8; 32],
+    pub player: Pubkey,
+    pub previous_expiry: i64,
+    pub new_expiry: i64,
+}
+
 // Additional error codes
 #[error_code]
 pub enum RollupError {
@@ -556,4 +948,20 @@ pub enum RollupError {
     EmergencyExitNotPermitted,
     #[msg("Invalid state transition")]
     InvalidStateTransition,
+    #[msg("Renewal requires a preceding ed25519 consent signature")]
+    MissingConsentSignature,
+    #[msg("Consent signature does not match the player, rollup or expiry")]
+    InvalidConsentSignature,
+    #[msg("Invalid delegation renewal")]
+    InvalidRenewal,
+    #[msg("Batch accounts or shared proof do not match")]
+    InvalidBatch,
+    #[msg("Signer is not the validator operating this rollup")]
+    WrongValidator,
+    #[msg("Only a duel player can dispute a transition")]
+    NotDuelPlayer,
+    #[msg("Challenge window has closed")]
+    ChallengeWindowClosed,
+    #[msg("Transition follows from the committed duel state")]
+    TransitionNotFraudulent,
 }
\ No newline at end of file
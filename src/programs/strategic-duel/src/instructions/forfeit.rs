@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+use bolt_lang::*;
+use crate::components::*;
+use super::replay_log::{record_replay_entry, ReplayChunk, MAX_REPLAY_ENTRIES_PER_CHUNK};
+
+/// Most of a player's unspent blinds an early forfeit may return (50%)
+pub const MAX_EARLY_FORFEIT_REFUND_BPS: u16 = 5_000;
+
+/// ForfeitDuel - Concede mid-match; the opponent wins outright
+#[derive(Accounts)]
+pub struct ForfeitDuel<'info> {
+    #[account(mut)]
+    pub player_signer: Signer<'info>,
+
+    /// CHECK: Entity for the duel
+    pub entity: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"duel", entity.key().as_ref()],
+        bump = duel.load()?.bump
+    )]
+    pub duel: Account<'info, ComponentData<DuelComponent>>,
+
+    #[account(
+        mut,
+        seeds = [b"player", player_signer.key().as_ref(), entity.key().as_ref()],
+        bump = player.load()?.bump
+    )]
+    pub player: Account<'info, ComponentData<PlayerComponent>>,
+
+    #[account(
+        mut,
+        seeds = [b"psych", player_signer.key().as_ref()],
+        bump = player_psych.load()?.bump
+    )]
+    pub player_psych: Option<Account<'info, ComponentData<PsychProfileComponent>>>,
+
+    #[account(
+        init_if_needed,
+        payer = player_signer,
+        space = 8 + std::mem::size_of::<ReplayChunk>() + MAX_REPLAY_ENTRIES_PER_CHUNK * std::mem::size_of::<ReplayEntry>(),
+        seeds = [b"replay", entity.key().as_ref(), &duel.load()?.replay_chunk_count.to_le_bytes()],
+        bump
+    )]
+    pub replay_chunk: Account<'info, ComponentData<ReplayChunk>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Refund owed to a player conceding now. Only a player who has not acted
+/// yet qualifies, so everything they have committed is forced blinds and antes.
+pub fn early_forfeit_refund(duel: &DuelComponent, player: &PlayerComponent) -> u64 {
+    if player.actions_taken > 0 {
+        return 0;
+    }
+    (player.total_bet as u128 * duel.early_forfeit_refund_bps as u128 / 10_000) as u64
+}
+
+impl<'info> ForfeitDuel<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let mut duel = self.duel.load_mut()?;
+        let mut player = self.player.load_mut()?;
+
+        require!(
+            duel.game_state == GameState::InProgress || duel.game_state == GameState::AwaitingAction,
+            ForfeitError::InvalidGameState
+        );
+        require!(!duel.migration_locked, ForfeitError::InvalidGameState);
+        require!(player.is_active, ForfeitError::PlayerInactive);
+        let seat = duel.seat_of(&player.player_id).ok_or(ForfeitError::PlayerInactive)?;
+        let opponent = if seat == 0 { duel.player_two } else { duel.player_one };
+
+        let mut replay_chunk = self.replay_chunk.load_mut()?;
+        record_replay_entry(&mut duel, &mut replay_chunk, ReplayEntry {
+            seat,
+            action_type: ActionType::Forfeit,
+            bet_amount: 0,
+            round_number: duel.current_round,
+            timestamp: current_time,
+        })?;
+
+        let refund = early_forfeit_refund(&duel, &player);
+        player.is_active = false;
+        require!(duel.apply_transition(DuelTrigger::Forfeit), ForfeitError::InvalidGameState);
+        duel.winner = Some(opponent);
+        duel.forfeited_by = Some(player.player_id);
+        duel.forfeit_refund = refund;
+        duel.last_action_time = current_time;
+
+        // Concessions count against the player's reputation
+        if !duel.is_practice {
+            let psych_account = self.player_psych.as_ref().ok_or(ForfeitError::MissingPsychProfile)?;
+            let mut psych = psych_account.load_mut()?;
+            psych.forfeits += 1;
+            psych.refresh_reputation();
+        }
+
+        emit!(DuelForfeitedEvent {
+            duel_id: duel.duel_id,
+            forfeited_by: player.player_id,
+            winner: opponent,
+            round: duel.current_round,
+            blind_refund: refund,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct DuelForfeitedEvent {
+    pub duel_id: u64,
+    pub forfeited_by: Pubkey,
+    pub winner: Pubkey,
+    pub round: u8,
+    pub blind_refund: u64,
+}
+
+#[error_code]
+pub enum ForfeitError {
+    #[msg("Duel cannot be forfeited in its current state")]
+    InvalidGameState,
+    #[msg("Player is not active in this duel")]
+    PlayerInactive,
+    #[msg("Player's profile is required to record the forfeit")]
+    MissingPsychProfile,
+}
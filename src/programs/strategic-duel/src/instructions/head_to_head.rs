@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use bolt_lang::*;
+
+/// HeadToHead - Lifetime record between two players, one account per
+/// unordered pair. Keyed by the ordered pair (see `ordered_pair`) so both
+/// players and the lobby derive the same address.
+#[component]
+#[derive(Default)]
+pub struct HeadToHead {
+    pub first: Pubkey,
+    pub second: Pubkey,
+    pub wins: [u32; 2], // Indexed first, second
+    pub draws: u32,
+    pub duels: u32,
+    pub total_pot: u64,
+    pub largest_pot: u64,
+    pub last_duel_id: u64,
+    pub last_played_at: i64,
+}
+
+impl HeadToHead {
+    /// Name the pair on a freshly opened record; keys are stored in `ordered` order
+    pub fn open(&mut self, a: Pubkey, b: Pubkey) {
+        if self.first == Pubkey::default() {
+            let (first, second) = shared::PairingWindow::ordered(a, b);
+            self.first = first;
+            self.second = second;
+        }
+    }
+
+    /// Count a settled duel; `winner` is None for a draw
+    pub fn record(&mut self, winner: Option<Pubkey>, pot: u64, duel_id: u64, now: i64) {
+        match winner {
+            Some(winner) if winner == self.first => self.wins[0] += 1,
+            Some(_) => self.wins[1] += 1,
+            None => self.draws += 1,
+        }
+        self.duels += 1;
+        self.total_pot = self.total_pot.saturating_add(pot);
+        self.largest_pot = self.largest_pot.max(pot);
+        self.last_duel_id = duel_id;
+        self.last_played_at = now;
+    }
+
+    /// (wins, losses, draws) from `player`'s side
+    pub fn record_for(&self, player: &Pubkey) -> (u32, u32, u32) {
+        if *player == self.first {
+            (self.wins[0], self.wins[1], self.draws)
+        } else {
+            (self.wins[1], self.wins[0], self.draws)
+        }
+    }
+}
+
+#[event]
+pub struct HeadToHeadUpdatedEvent {
+    pub first: Pubkey,
+    pub second: Pubkey,
+    pub wins: [u32; 2],
+    pub draws: u32,
+    pub duels: u32,
+}
@@ -0,0 +1,337 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use bolt_lang::*;
+use crate::components::*;
+use super::admin_config::AdminConfigError;
+
+/// Maximum premium the vault may charge (5%)
+pub const MAX_INSURANCE_PREMIUM_BPS: u16 = 500;
+/// How long a resolution may wait for the VRF oracle before it counts as stale
+pub const ORACLE_STALENESS_WINDOW: i64 = 30 * 60; // 30 minutes
+
+/// InitializeInsuranceVault - Create the shared insurance pool; super admin only
+#[derive(Accounts)]
+pub struct InitializeInsuranceVault<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [shared::ADMIN_CONFIG_SEED],
+        bump = admin_config.bump,
+        constraint = admin_config.super_admin == authority.key() @ AdminConfigError::NotSuperAdmin
+    )]
+    pub admin_config: Account<'info, shared::AdminConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<InsuranceVault>(),
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: Account<'info, ComponentData<InsuranceVault>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// PurchaseInsurance - Opt in to coverage for a duel stake at join time
+#[derive(Accounts)]
+pub struct PurchaseInsurance<'info> {
+    #[account(mut)]
+    pub player_signer: Signer<'info>,
+
+    /// CHECK: Entity for the duel
+    pub entity: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"duel", entity.key().as_ref()],
+        bump = duel.load()?.bump
+    )]
+    pub duel: Account<'info, ComponentData<DuelComponent>>,
+
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: Account<'info, ComponentData<InsuranceVault>>,
+
+    #[account(
+        init,
+        payer = player_signer,
+        space = 8 + std::mem::size_of::<InsurancePolicy>(),
+        seeds = [b"insurance_policy", player_signer.key().as_ref(), entity.key().as_ref()],
+        bump
+    )]
+    pub policy: Account<'info, ComponentData<InsurancePolicy>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// ClaimInsurance - Refund an insured stake after a protocol failure
+#[derive(Accounts)]
+pub struct ClaimInsurance<'info> {
+    #[account(mut)]
+    pub player_signer: Signer<'info>,
+
+    /// CHECK: Entity for the duel
+    pub entity: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"duel", entity.key().as_ref()],
+        bump = duel.load()?.bump
+    )]
+    pub duel: Account<'info, ComponentData<DuelComponent>>,
+
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: Account<'info, ComponentData<InsuranceVault>>,
+
+    #[account(
+        mut,
+        seeds = [b"insurance_policy", player_signer.key().as_ref(), entity.key().as_ref()],
+        bump
+    )]
+    pub policy: Account<'info, ComponentData<InsurancePolicy>>,
+
+    /// Present only if the duel was ever delegated to an ephemeral rollup
+    #[account(
+        seeds = [b"ephemeral_rollup", entity.key().as_ref()],
+        bump
+    )]
+    pub ephemeral_rollup: Option<Account<'info, ComponentData<EphemeralRollupComponent>>>,
+}
+
+/// InsuranceVault - Pool funded by premiums, paying out on technical forfeits
+#[component]
+#[derive(Default)]
+pub struct InsuranceVault {
+    pub authority: Pubkey,
+    pub premium_bps: u16,
+    pub max_coverage: u64,
+    pub total_premiums: u64,
+    pub total_claims_paid: u64,
+    pub policies_written: u64,
+    pub claims_count: u64,
+}
+
+/// InsurancePolicy - A player's coverage for a single duel
+#[component]
+#[derive(Default)]
+pub struct InsurancePolicy {
+    pub duel_id: u64,
+    pub player: Pubkey,
+    pub covered_amount: u64,
+    pub premium_paid: u64,
+    pub purchased_at: i64,
+    pub claimed: bool,
+    pub failure_reason: FailureReason,
+}
+
+impl InsuranceVault {
+    pub fn premium_for(&self, covered_amount: u64) -> u64 {
+        ((covered_amount as u128 * self.premium_bps as u128 + 9_999) / 10_000) as u64
+    }
+
+    /// Most a seat may insure: its escrowed stake, up to the pool limit.
+    /// Chip-only duels put no lamports at risk and cannot be insured.
+    pub fn coverage_limit(&self, escrowed_stake: u64) -> u64 {
+        self.max_coverage.min(escrowed_stake)
+    }
+}
+
+impl<'info> InitializeInsuranceVault<'info> {
+    pub fn process(&mut self, premium_bps: u16, max_coverage: u64) -> Result<()> {
+        require!(premium_bps <= MAX_INSURANCE_PREMIUM_BPS, InsuranceError::InvalidPremium);
+
+        let mut vault = self.insurance_vault.load_init()?;
+        vault.authority = self.authority.key();
+        vault.premium_bps = premium_bps;
+        vault.max_coverage = max_coverage;
+
+        Ok(())
+    }
+}
+
+impl<'info> PurchaseInsurance<'info> {
+    pub fn process(&mut self, covered_amount: u64) -> Result<()> {
+        let clock = Clock::get()?;
+        let duel = self.duel.load()?;
+
+        // Coverage can only be bought while joining, before any round is played
+        require!(duel.current_round == 0, InsuranceError::PurchaseWindowClosed);
+        require!(
+            duel.game_state == GameState::WaitingForPlayers || duel.game_state == GameState::InProgress,
+            InsuranceError::PurchaseWindowClosed
+        );
+        require!(
+            duel.seat_of(&self.player_signer.key()).is_some(),
+            InsuranceError::NotDuelPlayer
+        );
+        require!(!duel.is_practice, InsuranceError::NotDuelPlayer);
+
+        let premium = {
+            let vault = self.insurance_vault.load()?;
+            require!(
+                covered_amount > 0 && covered_amount <= vault.coverage_limit(duel.escrowed_stake),
+                InsuranceError::CoverageExceeded
+            );
+            vault.premium_for(covered_amount)
+        };
+
+        system_program::transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: self.player_signer.to_account_info(),
+                    to: self.insurance_vault.to_account_info(),
+                },
+            ),
+            premium,
+        )?;
+
+        let mut vault = self.insurance_vault.load_mut()?;
+        vault.total_premiums += premium;
+        vault.policies_written += 1;
+
+        let mut policy = self.policy.load_init()?;
+        policy.duel_id = duel.duel_id;
+        policy.player = self.player_signer.key();
+        policy.covered_amount = covered_amount;
+        policy.premium_paid = premium;
+        policy.purchased_at = clock.unix_timestamp;
+
+        emit!(InsurancePurchasedEvent {
+            duel_id: duel.duel_id,
+            player: policy.player,
+            covered_amount,
+            premium,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> ClaimInsurance<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let clock = Clock::get()?;
+        let current_time = clock.unix_timestamp;
+
+        let mut duel = self.duel.load_mut()?;
+        let mut policy = self.policy.load_mut()?;
+
+        require!(!policy.claimed, InsuranceError::AlreadyClaimed);
+        require!(policy.duel_id == duel.duel_id, InsuranceError::NotDuelPlayer);
+        require!(duel.game_state != GameState::Completed, InsuranceError::NoProtocolFailure);
+
+        let reason = self.detect_failure(&duel, current_time)?;
+        require!(reason != FailureReason::None, InsuranceError::NoProtocolFailure);
+
+        // Coverage never exceeds the stake, so the claim pays out what escrow
+        // would refund and the pool takes that part of the stake in return
+        let seat = duel.seat_of(&self.player_signer.key()).ok_or(InsuranceError::NotDuelPlayer)? as usize;
+        let payout = policy.covered_amount.min(duel.refundable_stake(seat));
+        require!(payout > 0, InsuranceError::StakeAlreadyRefunded);
+        {
+            let vault_info = self.insurance_vault.to_account_info();
+            let rent_floor = Rent::get()?.minimum_balance(vault_info.data_len());
+            require!(
+                vault_info.lamports().saturating_sub(rent_floor) >= payout,
+                InsuranceError::InsufficientPoolFunds
+            );
+
+            // The vault and duel are owned by this program, so lamports can be moved directly
+            **vault_info.try_borrow_mut_lamports()? -= payout;
+            **self.player_signer.to_account_info().try_borrow_mut_lamports()? += payout;
+            duel.claim_stake(seat, payout);
+            **self.duel.to_account_info().try_borrow_mut_lamports()? -= payout;
+            **vault_info.try_borrow_mut_lamports()? += payout;
+        }
+
+        let mut vault = self.insurance_vault.load_mut()?;
+        vault.total_claims_paid += payout;
+        vault.claims_count += 1;
+
+        policy.claimed = true;
+        policy.failure_reason = reason;
+
+        // A protocol failure voids the duel; the reason stays on the duel for
+        // every other policy written against it
+        if duel.protocol_failure == FailureReason::None {
+            require!(duel.apply_transition(DuelTrigger::Void), InsuranceError::NoProtocolFailure);
+            duel.protocol_failure = reason;
+        }
+
+        emit!(InsuranceClaimedEvent {
+            duel_id: duel.duel_id,
+            player: policy.player,
+            payout,
+            reason,
+        });
+
+        Ok(())
+    }
+
+    /// Check the on-chain evidence for a covered failure
+    fn detect_failure(&self, duel: &DuelComponent, current_time: i64) -> Result<FailureReason> {
+        if duel.protocol_failure != FailureReason::None {
+            return Ok(duel.protocol_failure);
+        }
+
+        if let Some(rollup_account) = &self.ephemeral_rollup {
+            let rollup = rollup_account.load()?;
+            if rollup.duel_id == duel.duel_id && rollup.rollup_status == RollupStatus::EmergencyExit {
+                return Ok(FailureReason::RollupEmergencyExit);
+            }
+        }
+
+        if duel.game_state == GameState::ResolutionPending
+            && !duel.vrf_verified
+            && current_time > duel.last_action_time + ORACLE_STALENESS_WINDOW
+        {
+            return Ok(FailureReason::OracleStale);
+        }
+
+        Ok(FailureReason::None)
+    }
+}
+
+#[event]
+pub struct InsurancePurchasedEvent {
+    pub duel_id: u64,
+    pub player: Pubkey,
+    pub covered_amount: u64,
+    pub premium: u64,
+}
+
+#[event]
+pub struct InsuranceClaimedEvent {
+    pub duel_id: u64,
+    pub player: Pubkey,
+    pub payout: u64,
+    pub reason: FailureReason,
+}
+
+#[error_code]
+pub enum InsuranceError {
+    #[msg("Premium exceeds the allowed maximum")]
+    InvalidPremium,
+    #[msg("Insurance can only be purchased at join")]
+    PurchaseWindowClosed,
+    #[msg("Signer is not a player in this duel")]
+    NotDuelPlayer,
+    #[msg("Requested coverage exceeds the escrowed stake or the pool limit")]
+    CoverageExceeded,
+    #[msg("Policy already claimed")]
+    AlreadyClaimed,
+    #[msg("No verifiable protocol failure for this duel")]
+    NoProtocolFailure,
+    #[msg("Insurance pool has insufficient funds")]
+    InsufficientPoolFunds,
+    #[msg("The insured stake has already been refunded")]
+    StakeAlreadyRefunded,
+}
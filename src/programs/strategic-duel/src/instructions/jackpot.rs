@@ -0,0 +1,158 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use bolt_lang::*;
+use super::admin_config::AdminConfigError;
+
+/// Largest share of each pot that may feed the jackpot
+pub const MAX_JACKPOT_CONTRIBUTION_BPS: u16 = 100; // 1%
+/// Most generous odds a jackpot may be configured with (1 in N)
+pub const MIN_JACKPOT_ODDS: u32 = 100;
+
+/// InitializeJackpot - Create the progressive jackpot vault; super admin only
+#[derive(Accounts)]
+pub struct InitializeJackpot<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [shared::ADMIN_CONFIG_SEED],
+        bump = admin_config.bump,
+        constraint = admin_config.super_admin == authority.key() @ AdminConfigError::NotSuperAdmin
+    )]
+    pub admin_config: Account<'info, shared::AdminConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<JackpotVault>(),
+        seeds = [b"jackpot_vault"],
+        bump
+    )]
+    pub jackpot_vault: Account<'info, ComponentData<JackpotVault>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// UpdateJackpot - Adjust the contribution, odds or payout split
+#[derive(Accounts)]
+pub struct UpdateJackpot<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"jackpot_vault"],
+        bump
+    )]
+    pub jackpot_vault: Account<'info, ComponentData<JackpotVault>>,
+}
+
+/// JackpotVault - Progressive pool fed by a micro-rake on every settled pot.
+/// Each settlement rolls against `hit_odds`; a hit pays the whole balance to
+/// the duel's two players.
+#[component]
+#[derive(Default)]
+pub struct JackpotVault {
+    pub authority: Pubkey,
+    pub contribution_bps: u16,
+    pub hit_odds: u32, // 1 in hit_odds per settled duel
+    pub winner_share_bps: u16, // Remainder goes to the losing seat
+    pub balance: u64,
+    pub total_contributed: u64,
+    pub total_paid: u64,
+    pub hits: u32,
+    pub last_hit_duel: u64,
+}
+
+impl JackpotVault {
+    pub fn is_valid_config(contribution_bps: u16, hit_odds: u32, winner_share_bps: u16) -> bool {
+        contribution_bps <= MAX_JACKPOT_CONTRIBUTION_BPS && hit_odds >= MIN_JACKPOT_ODDS && winner_share_bps <= 10_000
+    }
+
+    pub fn contribution_for(&self, pot: u64) -> u64 {
+        (pot as u128 * self.contribution_bps as u128 / 10_000) as u64
+    }
+
+    /// Roll for a hit against the duel's `jackpot_roll`, which is drawn from the
+    /// resolved randomness and so is unknown until the VRF answers. A duel that
+    /// never reached VRF resolution (a forfeit) has no roll and never hits.
+    pub fn is_hit(&self, duel_id: u64, jackpot_roll: &[u8; 32]) -> bool {
+        if self.hit_odds == 0 || *jackpot_roll == [0u8; 32] {
+            return false;
+        }
+        let roll = hashv(&[b"jackpot", &duel_id.to_le_bytes(), jackpot_roll]).to_bytes();
+        u64::from_le_bytes(roll[..8].try_into().unwrap()) % self.hit_odds as u64 == 0
+    }
+
+    /// Jackpot shares in seat order: winner then loser, or an even split on a draw
+    pub fn payout_split(&self, amount: u64, is_draw: bool) -> [u64; 2] {
+        let first = if is_draw {
+            amount - amount / 2
+        } else {
+            (amount as u128 * self.winner_share_bps as u128 / 10_000) as u64
+        };
+        [first, amount - first]
+    }
+
+    fn apply(&mut self, contribution_bps: u16, hit_odds: u32, winner_share_bps: u16) -> Result<()> {
+        require!(
+            Self::is_valid_config(contribution_bps, hit_odds, winner_share_bps),
+            JackpotError::InvalidConfig
+        );
+        self.contribution_bps = contribution_bps;
+        self.hit_odds = hit_odds;
+        self.winner_share_bps = winner_share_bps;
+
+        emit!(JackpotConfiguredEvent {
+            contribution_bps,
+            hit_odds,
+            winner_share_bps,
+        });
+
+        Ok(())
+    }
+}
+
+/// Jackpot roll recorded at resolution from the duel's final randomness
+pub fn jackpot_roll(randomness: u64, vrf_seed: &[u8; 32]) -> [u8; 32] {
+    hashv(&[b"jackpot_roll", &randomness.to_le_bytes(), vrf_seed]).to_bytes()
+}
+
+impl<'info> InitializeJackpot<'info> {
+    pub fn process(&mut self, contribution_bps: u16, hit_odds: u32, winner_share_bps: u16) -> Result<()> {
+        let mut vault = self.jackpot_vault.load_init()?;
+        vault.authority = self.authority.key();
+        vault.apply(contribution_bps, hit_odds, winner_share_bps)
+    }
+}
+
+impl<'info> UpdateJackpot<'info> {
+    pub fn process(&mut self, contribution_bps: u16, hit_odds: u32, winner_share_bps: u16) -> Result<()> {
+        let mut vault = self.jackpot_vault.load_mut()?;
+        require_keys_eq!(vault.authority, self.authority.key(), JackpotError::Unauthorized);
+        vault.apply(contribution_bps, hit_odds, winner_share_bps)
+    }
+}
+
+#[event]
+pub struct JackpotConfiguredEvent {
+    pub contribution_bps: u16,
+    pub hit_odds: u32,
+    pub winner_share_bps: u16,
+}
+
+#[event]
+pub struct JackpotHitEvent {
+    pub duel_id: u64,
+    pub players: [Pubkey; 2], // Seat order as paid: winner, loser
+    pub shares: [u64; 2],
+    pub amount: u64,
+    pub hit_odds: u32,
+}
+
+#[error_code]
+pub enum JackpotError {
+    #[msg("Jackpot contribution, odds or split out of range")]
+    InvalidConfig,
+    #[msg("Only the jackpot authority can update it")]
+    Unauthorized,
+}
@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+use bolt_lang::*;
+use crate::components::*;
+use super::admin_config::AdminConfigError;
+
+/// Most open tables the lobby indexes at once
+pub const MAX_LOBBY_ENTRIES: usize = 64;
+
+/// Listings drop out of the lobby after this many slots (~1 hour)
+pub const LOBBY_ENTRY_TTL_SLOTS: u64 = 9_000;
+
+/// Upper entry-fee bound of each stake bracket, in lamports; fees above the
+/// last bound fall in the top bracket
+pub const STAKE_BRACKET_BOUNDS: [u64; 4] = [
+    0,              // Free
+    10_000_000,     // Micro, up to 0.01 SOL
+    100_000_000,    // Low, up to 0.1 SOL
+    1_000_000_000,  // Mid, up to 1 SOL
+];
+
+pub fn stake_bracket(entry_fee: u64) -> u8 {
+    STAKE_BRACKET_BOUNDS
+        .iter()
+        .position(|bound| entry_fee <= *bound)
+        .unwrap_or(STAKE_BRACKET_BOUNDS.len()) as u8
+}
+
+/// InitializeLobby - Create the open-table index; super admin only
+#[derive(Accounts)]
+pub struct InitializeLobby<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [shared::ADMIN_CONFIG_SEED],
+        bump = admin_config.bump,
+        constraint = admin_config.super_admin == authority.key() @ AdminConfigError::NotSuperAdmin
+    )]
+    pub admin_config: Account<'info, shared::AdminConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<LobbyRegistry>() + MAX_LOBBY_ENTRIES * std::mem::size_of::<LobbyEntry>(),
+        seeds = [b"lobby"],
+        bump
+    )]
+    pub lobby: Account<'info, ComponentData<LobbyRegistry>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct LobbyEntry {
+    pub entity: Pubkey,
+    pub creator: Pubkey,
+    pub entry_fee: u64,
+    pub stake_bracket: u8,
+    pub variant: OutcomeEvaluator,
+    pub listed_slot: u64,
+    pub expires_slot: u64,
+}
+
+/// LobbyRegistry - Joinable duels, so clients can list tables from one account
+/// instead of scanning program accounts. Maintained by create, join and cancel.
+#[component]
+#[derive(Default)]
+pub struct LobbyRegistry {
+    pub entries: Vec<LobbyEntry>,
+}
+
+impl LobbyRegistry {
+    /// Drop listings whose expiry slot has passed
+    pub fn prune(&mut self, slot: u64) -> usize {
+        let before = self.entries.len();
+        self.entries.retain(|entry| entry.expires_slot > slot);
+        before - self.entries.len()
+    }
+
+    pub fn list(&mut self, entry: LobbyEntry) -> Result<()> {
+        self.prune(entry.listed_slot);
+        require!(self.entries.len() < MAX_LOBBY_ENTRIES, LobbyError::LobbyFull);
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    pub fn delist(&mut self, entity: &Pubkey) -> bool {
+        let before = self.entries.len();
+        self.entries.retain(|entry| entry.entity != *entity);
+        before != self.entries.len()
+    }
+
+    /// Live listings, optionally narrowed to one stake bracket and variant
+    pub fn open_tables(
+        &self,
+        bracket: Option<u8>,
+        variant: Option<OutcomeEvaluator>,
+        slot: u64,
+    ) -> impl Iterator<Item = &LobbyEntry> {
+        self.entries.iter().filter(move |entry| {
+            entry.expires_slot > slot
+                && bracket.map_or(true, |b| entry.stake_bracket == b)
+                && variant.map_or(true, |v| entry.variant == v)
+        })
+    }
+}
+
+impl<'info> InitializeLobby<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let mut lobby = self.lobby.load_init()?;
+        lobby.entries = Vec::new();
+        Ok(())
+    }
+}
+
+#[error_code]
+pub enum LobbyError {
+    #[msg("Lobby is full; wait for listings to be joined or expire")]
+    LobbyFull,
+}
@@ -0,0 +1,581 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use bolt_lang::*;
+use super::admin_config::AdminConfigError;
+
+/// Largest share of paid rake that may be handed back before tier multipliers
+pub const MAX_RAKEBACK_BPS: u16 = 5_000;
+/// Largest staking tier multiplier (3x)
+pub const MAX_TIER_MULTIPLIER_BPS: u16 = 30_000;
+/// Most that rake-back times the top tier multiplier may return: all of the rake
+pub const MAX_EFFECTIVE_RAKEBACK_BPS: u64 = 10_000;
+/// Staking tiers above the unstaked base tier
+pub const LOYALTY_STAKING_TIERS: usize = 3;
+
+/// InitializeLoyaltyProgram - Configure rake-back epochs and staking tiers; super admin only
+#[derive(Accounts)]
+pub struct InitializeLoyaltyProgram<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [shared::ADMIN_CONFIG_SEED],
+        bump = admin_config.bump,
+        constraint = admin_config.super_admin == authority.key() @ AdminConfigError::NotSuperAdmin
+    )]
+    pub admin_config: Account<'info, shared::AdminConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<LoyaltyProgram>(),
+        seeds = [b"loyalty_program"],
+        bump
+    )]
+    pub loyalty_program: Account<'info, ComponentData<LoyaltyProgram>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// FundLoyaltyProgram - Treasury tops up the rake-back pool
+#[derive(Accounts)]
+pub struct FundLoyaltyProgram<'info> {
+    #[account(mut)]
+    pub treasury: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"loyalty_program"],
+        bump
+    )]
+    pub loyalty_program: Account<'info, ComponentData<LoyaltyProgram>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// ClaimRakeback - Pay out rake-back on rake paid in closed epochs
+#[derive(Accounts)]
+pub struct ClaimRakeback<'info> {
+    /// The player, or their identity's cold wallet once one is linked
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"loyalty_program"],
+        bump
+    )]
+    pub loyalty_program: Account<'info, ComponentData<LoyaltyProgram>>,
+
+    #[account(
+        mut,
+        seeds = [b"loyalty", loyalty_account.load()?.player.as_ref()],
+        bump
+    )]
+    pub loyalty_account: Account<'info, ComponentData<LoyaltyAccount>>,
+
+    /// The player's locked stake; without one the base tier applies
+    #[account(
+        mut,
+        seeds = [b"loyalty_stake", loyalty_account.load()?.player.as_ref()],
+        bump = loyalty_stake.load()?.bump
+    )]
+    pub loyalty_stake: Option<Account<'info, ComponentData<LoyaltyStake>>>,
+
+    /// CHECK: The player's game-program identity, empty if none
+    #[account(
+        seeds = [b"identity", loyalty_account.load()?.player.as_ref()],
+        seeds::program = shared::GAME_PROGRAM_ID,
+        bump,
+        constraint = shared::PlayerIdentity::may_withdraw(&player_identity, &loyalty_account.load()?.player, &player.key())
+            @ shared::GameError::ColdWalletRequired
+    )]
+    pub player_identity: UncheckedAccount<'info>,
+}
+
+/// StakeLoyalty - Lock staking receipts in the player's vault to earn a tier
+#[derive(Accounts)]
+pub struct StakeLoyalty<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [b"loyalty_program"],
+        bump
+    )]
+    pub loyalty_program: Account<'info, ComponentData<LoyaltyProgram>>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + std::mem::size_of::<LoyaltyStake>(),
+        seeds = [b"loyalty_stake", shared::resolve_player(player_identity.as_deref(), shared::PlayerIdentity::exists(&signer_identity), &player.key(), Clock::get()?.unix_timestamp)?.as_ref()],
+        bump
+    )]
+    pub loyalty_stake: Account<'info, ComponentData<LoyaltyStake>>,
+
+    #[account(address = loyalty_program.load()?.staking_mint @ LoyaltyError::WrongStakingMint)]
+    pub staking_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = staking_mint,
+        token::authority = player
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        token::mint = staking_mint,
+        token::authority = loyalty_stake,
+        seeds = [b"loyalty_stake_vault", shared::resolve_player(player_identity.as_deref(), shared::PlayerIdentity::exists(&signer_identity), &player.key(), Clock::get()?.unix_timestamp)?.as_ref()],
+        bump
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    /// Linked wallets stake for the identity's player
+    #[account(
+        seeds = [b"identity", player_identity.player_key.as_ref()],
+        seeds::program = shared::GAME_PROGRAM_ID,
+        bump = player_identity.bump
+    )]
+    pub player_identity: Option<Account<'info, shared::PlayerIdentity>>,
+
+    /// CHECK: The signer's own identity PDA, empty unless it is an identity's player key
+    #[account(
+        seeds = [b"identity", player.key().as_ref()],
+        seeds::program = shared::GAME_PROGRAM_ID,
+        bump
+    )]
+    pub signer_identity: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// UnstakeLoyalty - Withdraw locked staking receipts
+#[derive(Accounts)]
+pub struct UnstakeLoyalty<'info> {
+    /// The player, or their identity's cold wallet once one is linked
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [b"loyalty_program"],
+        bump
+    )]
+    pub loyalty_program: Account<'info, ComponentData<LoyaltyProgram>>,
+
+    #[account(
+        mut,
+        seeds = [b"loyalty_stake", loyalty_stake.load()?.player.as_ref()],
+        bump = loyalty_stake.load()?.bump
+    )]
+    pub loyalty_stake: Account<'info, ComponentData<LoyaltyStake>>,
+
+    /// CHECK: The player's game-program identity, empty if none
+    #[account(
+        seeds = [b"identity", loyalty_stake.load()?.player.as_ref()],
+        seeds::program = shared::GAME_PROGRAM_ID,
+        bump,
+        constraint = shared::PlayerIdentity::may_withdraw(&player_identity, &loyalty_stake.load()?.player, &player.key())
+            @ shared::GameError::ColdWalletRequired
+    )]
+    pub player_identity: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::authority = player
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"loyalty_stake_vault", loyalty_stake.load()?.player.as_ref()],
+        bump
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// LoyaltyProgram - Rake-back pool, funded from lamport rake at settlement
+/// (and optional treasury top-ups) and paid per epoch
+#[component]
+#[derive(Default)]
+pub struct LoyaltyProgram {
+    pub authority: Pubkey,
+    pub treasury: Pubkey,
+    pub staking_mint: Pubkey,
+    pub epoch_start: i64,
+    pub epoch_duration: i64,
+    pub rakeback_bps: u16,
+    // Minimum staking receipt balance for tiers 1..=3; ascending
+    pub tier_thresholds: [u64; LOYALTY_STAKING_TIERS],
+    // Multiplier for the base tier followed by each staking tier
+    pub tier_multipliers_bps: [u16; LOYALTY_STAKING_TIERS + 1],
+    pub total_funded: u64,
+    pub total_claimed: u64,
+}
+
+/// LoyaltyAccount - A player's rake paid, accrued by settlement
+#[component]
+#[derive(Default)]
+pub struct LoyaltyAccount {
+    pub player: Pubkey,
+    pub lifetime_rake_paid: u64,
+    pub epoch: u32,
+    pub epoch_rake_paid: u64, // Open epoch, not yet claimable
+    pub claimable_rake: u64,  // Closed epochs awaiting a claim
+    pub total_rakeback_claimed: u64,
+    pub last_claimed_at: i64,
+}
+
+/// LoyaltyStake - Staking receipts locked in the player's vault. Tiers come
+/// from the time-weighted average held over the last closed epoch, so a
+/// balance borrowed for one transaction earns nothing.
+#[component]
+#[derive(Default)]
+pub struct LoyaltyStake {
+    pub player: Pubkey,
+    pub amount: u64,
+    pub epoch: u32,
+    pub epoch_stake_seconds: u128, // Amount × seconds held in the open epoch
+    pub closed_epoch_average: u64,
+    pub last_update: i64,
+    pub bump: u8,
+}
+
+impl LoyaltyProgram {
+    pub fn is_valid_config(
+        epoch_duration: i64,
+        rakeback_bps: u16,
+        tier_thresholds: &[u64; LOYALTY_STAKING_TIERS],
+        tier_multipliers_bps: &[u16; LOYALTY_STAKING_TIERS + 1],
+    ) -> bool {
+        epoch_duration > 0
+            && rakeback_bps <= MAX_RAKEBACK_BPS
+            && tier_thresholds[0] > 0
+            && tier_thresholds.windows(2).all(|pair| pair[0] < pair[1])
+            && tier_multipliers_bps.iter().all(|&m| m <= MAX_TIER_MULTIPLIER_BPS)
+            && tier_multipliers_bps.windows(2).all(|pair| pair[0] <= pair[1])
+            // Multipliers ascend, so the last one bounds every payout
+            && rakeback_bps as u64 * tier_multipliers_bps[LOYALTY_STAKING_TIERS] as u64
+                <= MAX_EFFECTIVE_RAKEBACK_BPS * 10_000
+    }
+
+    pub fn current_epoch(&self, now: i64) -> u32 {
+        if self.epoch_duration <= 0 || now <= self.epoch_start {
+            return 0;
+        }
+        ((now - self.epoch_start) / self.epoch_duration).min(u32::MAX as i64) as u32
+    }
+
+    /// Start of `epoch` in unix time
+    pub fn epoch_start_of(&self, epoch: u32) -> i64 {
+        self.epoch_start + epoch as i64 * self.epoch_duration
+    }
+
+    /// Staking tier for a time-weighted average stake
+    pub fn tier_for(&self, average_stake: u64) -> u8 {
+        self.tier_thresholds.iter().filter(|&&threshold| average_stake >= threshold).count() as u8
+    }
+
+    pub fn rakeback_for(&self, rake_paid: u64, tier: u8) -> u64 {
+        let multiplier = self.tier_multipliers_bps[tier as usize] as u128;
+        (rake_paid as u128 * self.rakeback_bps as u128 * multiplier / 100_000_000) as u64
+    }
+
+    /// Most rake-back `rake` can ever earn, at the top tier; settlement moves
+    /// this much of each lamport rake into the pool so every claim is covered
+    pub fn max_rakeback_for(&self, rake: u64) -> u64 {
+        self.rakeback_for(rake, LOYALTY_STAKING_TIERS as u8)
+    }
+}
+
+/// Split a rake between the seats in proportion to what each put into the pot,
+/// evenly when neither contributed. The first seat takes any odd lamport.
+pub fn split_rake(rake: u64, contributions: [u64; 2]) -> [u64; 2] {
+    let total = contributions[0] as u128 + contributions[1] as u128;
+    let second = match total {
+        0 => rake / 2,
+        total => (rake as u128 * contributions[1] as u128 / total) as u64,
+    };
+    [rake - second, second]
+}
+
+impl LoyaltyStake {
+    /// Accrue the held amount up to `now`, closing out any epochs that ended
+    pub fn accrue(&mut self, program: &LoyaltyProgram, now: i64) {
+        let epoch = program.current_epoch(now);
+        if epoch > self.epoch {
+            let epoch_end = program.epoch_start_of(self.epoch + 1);
+            let held = (epoch_end - self.last_update).max(0) as u128;
+            let total = self.epoch_stake_seconds + self.amount as u128 * held;
+            // Epochs after the open one passed untouched, so the amount was held throughout
+            self.closed_epoch_average = if epoch == self.epoch + 1 {
+                (total / program.epoch_duration as u128) as u64
+            } else {
+                self.amount
+            };
+            self.epoch = epoch;
+            self.epoch_stake_seconds = 0;
+            self.last_update = program.epoch_start_of(epoch);
+        }
+        let held = (now - self.last_update).max(0) as u128;
+        self.epoch_stake_seconds += self.amount as u128 * held;
+        self.last_update = now;
+    }
+}
+
+impl LoyaltyAccount {
+    /// Close the open epoch into the claimable balance once `epoch` has moved past it
+    pub fn roll_to(&mut self, epoch: u32) {
+        if epoch > self.epoch {
+            self.claimable_rake += self.epoch_rake_paid;
+            self.epoch_rake_paid = 0;
+            self.epoch = epoch;
+        }
+    }
+
+    pub fn record_rake(&mut self, rake: u64, epoch: u32) {
+        self.roll_to(epoch);
+        self.epoch_rake_paid += rake;
+        self.lifetime_rake_paid += rake;
+    }
+}
+
+impl<'info> InitializeLoyaltyProgram<'info> {
+    pub fn process(
+        &mut self,
+        treasury: Pubkey,
+        staking_mint: Pubkey,
+        epoch_duration: i64,
+        rakeback_bps: u16,
+        tier_thresholds: [u64; LOYALTY_STAKING_TIERS],
+        tier_multipliers_bps: [u16; LOYALTY_STAKING_TIERS + 1],
+    ) -> Result<()> {
+        require!(
+            LoyaltyProgram::is_valid_config(epoch_duration, rakeback_bps, &tier_thresholds, &tier_multipliers_bps),
+            LoyaltyError::InvalidConfig
+        );
+
+        let mut program = self.loyalty_program.load_init()?;
+        program.authority = self.authority.key();
+        program.treasury = treasury;
+        program.staking_mint = staking_mint;
+        program.epoch_start = Clock::get()?.unix_timestamp;
+        program.epoch_duration = epoch_duration;
+        program.rakeback_bps = rakeback_bps;
+        program.tier_thresholds = tier_thresholds;
+        program.tier_multipliers_bps = tier_multipliers_bps;
+
+        Ok(())
+    }
+}
+
+impl<'info> FundLoyaltyProgram<'info> {
+    pub fn process(&mut self, amount: u64) -> Result<()> {
+        require!(amount > 0, LoyaltyError::InvalidAmount);
+        require_keys_eq!(
+            self.loyalty_program.load()?.treasury,
+            self.treasury.key(),
+            LoyaltyError::Unauthorized
+        );
+
+        system_program::transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: self.treasury.to_account_info(),
+                    to: self.loyalty_program.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let mut program = self.loyalty_program.load_mut()?;
+        program.total_funded += amount;
+
+        emit!(LoyaltyFundedEvent {
+            treasury: self.treasury.key(),
+            amount,
+            total_funded: program.total_funded,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> StakeLoyalty<'info> {
+    pub fn process(&mut self, amount: u64, bump: u8) -> Result<()> {
+        require!(amount > 0, LoyaltyError::InvalidAmount);
+        let now = Clock::get()?.unix_timestamp;
+
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: self.player_token_account.to_account_info(),
+                    to: self.stake_vault.to_account_info(),
+                    authority: self.player.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let program = self.loyalty_program.load()?;
+        let mut stake = self.loyalty_stake.load_mut()?;
+        if stake.player == Pubkey::default() {
+            stake.player = shared::resolve_player(self.player_identity.as_deref(), shared::PlayerIdentity::exists(&self.signer_identity), &self.player.key(), now)?;
+            stake.bump = bump;
+            stake.epoch = program.current_epoch(now);
+            stake.last_update = now;
+        }
+        stake.accrue(&program, now);
+        stake.amount += amount;
+
+        emit!(LoyaltyStakeChangedEvent {
+            player: stake.player,
+            amount: stake.amount,
+            closed_epoch_average: stake.closed_epoch_average,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> UnstakeLoyalty<'info> {
+    pub fn process(&mut self, amount: u64) -> Result<()> {
+        require!(amount > 0, LoyaltyError::InvalidAmount);
+        let now = Clock::get()?.unix_timestamp;
+        let player = self.loyalty_stake.load()?.player;
+
+        let bump = {
+            let program = self.loyalty_program.load()?;
+            let mut stake = self.loyalty_stake.load_mut()?;
+            require!(stake.amount >= amount, LoyaltyError::InsufficientStake);
+            stake.accrue(&program, now);
+            stake.amount -= amount;
+
+            emit!(LoyaltyStakeChangedEvent {
+                player,
+                amount: stake.amount,
+                closed_epoch_average: stake.closed_epoch_average,
+            });
+            stake.bump
+        };
+
+        let seeds = &[b"loyalty_stake".as_ref(), player.as_ref(), &[bump]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: self.stake_vault.to_account_info(),
+                    to: self.player_token_account.to_account_info(),
+                    authority: self.loyalty_stake.to_account_info(),
+                },
+                &[&seeds[..]],
+            ),
+            amount,
+        )
+    }
+}
+
+impl<'info> ClaimRakeback<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        let (epoch, tier, rakeback) = {
+            let program = self.loyalty_program.load()?;
+            let mut account = self.loyalty_account.load_mut()?;
+            let epoch = program.current_epoch(now);
+            account.roll_to(epoch);
+            let average_stake = match &self.loyalty_stake {
+                Some(stake_account) => {
+                    let mut stake = stake_account.load_mut()?;
+                    stake.accrue(&program, now);
+                    stake.closed_epoch_average
+                }
+                None => 0,
+            };
+            let tier = program.tier_for(average_stake);
+            (epoch, tier, program.rakeback_for(account.claimable_rake, tier))
+        };
+        require!(rakeback > 0, LoyaltyError::NothingToClaim);
+
+        {
+            let pool_info = self.loyalty_program.to_account_info();
+            let rent_floor = Rent::get()?.minimum_balance(pool_info.data_len());
+            require!(
+                pool_info.lamports().saturating_sub(rent_floor) >= rakeback,
+                LoyaltyError::InsufficientPoolFunds
+            );
+
+            // The pool is owned by this program, so lamports can be moved directly
+            **pool_info.try_borrow_mut_lamports()? -= rakeback;
+            **self.player.to_account_info().try_borrow_mut_lamports()? += rakeback;
+        }
+
+        let mut program = self.loyalty_program.load_mut()?;
+        program.total_claimed += rakeback;
+
+        let mut account = self.loyalty_account.load_mut()?;
+        let rake_claimed = account.claimable_rake;
+        account.claimable_rake = 0;
+        account.total_rakeback_claimed += rakeback;
+        account.last_claimed_at = now;
+
+        emit!(RakebackClaimedEvent {
+            player: account.player,
+            epoch,
+            tier,
+            rake_claimed,
+            rakeback,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct LoyaltyFundedEvent {
+    pub treasury: Pubkey,
+    pub amount: u64,
+    pub total_funded: u64,
+}
+
+#[event]
+pub struct LoyaltyStakeChangedEvent {
+    pub player: Pubkey,
+    pub amount: u64,
+    pub closed_epoch_average: u64,
+}
+
+#[event]
+pub struct RakebackClaimedEvent {
+    pub player: Pubkey,
+    pub epoch: u32,
+    pub tier: u8,
+    pub rake_claimed: u64,
+    pub rakeback: u64,
+}
+
+#[error_code]
+pub enum LoyaltyError {
+    #[msg("Invalid loyalty program configuration")]
+    InvalidConfig,
+    #[msg("Only the configured treasury can fund the loyalty pool")]
+    Unauthorized,
+    #[msg("Funding amount must be positive")]
+    InvalidAmount,
+    #[msg("No rake-back is claimable yet")]
+    NothingToClaim,
+    #[msg("Loyalty pool cannot cover this claim")]
+    InsufficientPoolFunds,
+    #[msg("Staking mint does not match the loyalty program")]
+    WrongStakingMint,
+    #[msg("Unstake amount exceeds the locked stake")]
+    InsufficientStake,
+}
@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+use bolt_lang::*;
+use crate::components::*;
+use super::head_to_head::{HeadToHead, HeadToHeadUpdatedEvent};
+use super::GameError;
+
+/// Match summaries kept per player; the oldest is overwritten first
+pub const RECENT_MATCHES_CAPACITY: usize = 32;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum MatchResult {
+    Win,
+    Loss,
+    Draw,
+}
+
+impl Default for MatchResult {
+    fn default() -> Self {
+        MatchResult::Loss
+    }
+}
+
+/// One settled duel from the owning player's side
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct MatchSummary {
+    pub duel_id: u64,
+    pub opponent: Pubkey,
+    pub result: MatchResult,
+    pub stake: u64,
+    pub payout: u64,
+    pub rating_delta: i32, // ELO track of the duel's queue; 0 for Glicko-2 and practice duels
+    pub settled_at: i64,
+}
+
+/// RecentMatches - Ring buffer of a player's last settled duels, written at
+/// settlement so clients can show history without an indexer
+#[component]
+#[derive(Default)]
+pub struct RecentMatches {
+    pub player: Pubkey,
+    pub matches: [MatchSummary; RECENT_MATCHES_CAPACITY],
+    pub next_slot: u8,
+    pub total_recorded: u32,
+}
+
+impl RecentMatches {
+    pub fn record(&mut self, summary: MatchSummary) {
+        self.matches[self.next_slot as usize] = summary;
+        self.next_slot = ((self.next_slot as usize + 1) % RECENT_MATCHES_CAPACITY) as u8;
+        self.total_recorded += 1;
+    }
+
+    /// Recorded summaries, newest first
+    pub fn newest_first(&self) -> impl Iterator<Item = &MatchSummary> {
+        let filled = (self.total_recorded as usize).min(RECENT_MATCHES_CAPACITY);
+        let next = self.next_slot as usize;
+        (1..=filled).map(move |back| &self.matches[(next + RECENT_MATCHES_CAPACITY - back) % RECENT_MATCHES_CAPACITY])
+    }
+}
+
+/// OpenMatchHistory - Open a player's recent match buffer. Players get one
+/// with their psych profile; anyone may open a missing one so a profile from
+/// before history existed cannot hold up settlement.
+#[derive(Accounts)]
+#[instruction(player: Pubkey)]
+pub struct OpenMatchHistory<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<RecentMatches>(),
+        seeds = [b"recent_matches", player.as_ref()],
+        bump
+    )]
+    pub recent_matches: Account<'info, ComponentData<RecentMatches>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> OpenMatchHistory<'info> {
+    pub fn process(&mut self, player: Pubkey) -> Result<()> {
+        self.recent_matches.load_init()?.player = player;
+        Ok(())
+    }
+}
+
+/// Each seat's summary of a finished duel, seat order as given
+pub fn seat_summaries(
+    duel_id: u64,
+    players: [Pubkey; 2],
+    stakes: [u64; 2],
+    results: [MatchResult; 2],
+    payouts: [u64; 2],
+    rating_deltas: [i32; 2],
+    now: i64,
+) -> [MatchSummary; 2] {
+    [0, 1].map(|seat| MatchSummary {
+        duel_id,
+        opponent: players[1 - seat],
+        result: results[seat],
+        stake: stakes[seat],
+        payout: payouts[seat],
+        rating_delta: rating_deltas[seat],
+        settled_at: now,
+    })
+}
+
+/// Push a finished duel onto both players' recent match buffers and count it
+/// in the pair's head-to-head record. Every path that ends a duel with a
+/// result calls this; practice duels are not recorded.
+pub fn record_history(
+    head_to_head: &Option<Account<ComponentData<HeadToHead>>>,
+    recent_matches: [&Option<Account<ComponentData<RecentMatches>>>; 2],
+    winner: Option<Pubkey>,
+    summaries: [MatchSummary; 2],
+    pot: u64,
+) -> Result<()> {
+    let head_to_head_account = head_to_head.as_ref().ok_or(GameError::MissingMatchHistory)?;
+    let mut head_to_head = head_to_head_account.load_mut()?;
+    head_to_head.record(winner, pot, summaries[0].duel_id, summaries[0].settled_at);
+    emit!(HeadToHeadUpdatedEvent {
+        first: head_to_head.first,
+        second: head_to_head.second,
+        wins: head_to_head.wins,
+        draws: head_to_head.draws,
+        duels: head_to_head.duels,
+    });
+
+    for seat in 0..2 {
+        let account = recent_matches[seat].as_ref().ok_or(GameError::MissingMatchHistory)?;
+        let mut history = account.load_mut()?;
+        // Seat one's opponent is seat zero, so this is the owning player
+        history.player = summaries[1 - seat].opponent;
+        history.record(summaries[seat]);
+    }
+    Ok(())
+}
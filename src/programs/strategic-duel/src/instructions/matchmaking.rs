@@ -0,0 +1,396 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{ed25519_program, hash::hash, sysvar::instructions as ix_sysvar};
+use bolt_lang::*;
+use crate::components::*;
+use super::ephemeral_rollup::ed25519_matches;
+use super::head_to_head::HeadToHead;
+use super::{generate_vrf_seed, CreateDuelParams, GameError};
+
+/// Domain prefix of a signed matchmaking intent
+pub const INTENT_DOMAIN: &[u8] = b"strategic-duel/intent/v2";
+
+/// Off-chain matchmaking intent, signed by the player with their wallet key
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct MatchmakingIntent {
+    pub player: Pubkey,
+    pub stake: u64,
+    // Accepted opponent reputation band, inclusive
+    pub min_rating: u16,
+    pub max_rating: u16,
+    pub expiry: i64,
+    pub nonce: u64,
+}
+
+impl MatchmakingIntent {
+    /// Exact bytes the player signs for a duel opened with `params`. Binding
+    /// the program id keeps an intent from being replayed against another
+    /// deployment, and the params digest covers every table setting, so a
+    /// matchmaker cannot seat the player at a table they did not agree to.
+    pub fn message(&self, params: &CreateDuelParams) -> Result<Vec<u8>> {
+        let params_hash = hash(&params.try_to_vec()?).to_bytes();
+        let mut message = Vec::with_capacity(INTENT_DOMAIN.len() + 32 + 32 + 8 + 2 + 2 + 8 + 8 + 32);
+        message.extend_from_slice(INTENT_DOMAIN);
+        message.extend_from_slice(crate::ID.as_ref());
+        message.extend_from_slice(self.player.as_ref());
+        message.extend_from_slice(&self.stake.to_le_bytes());
+        message.extend_from_slice(&self.min_rating.to_le_bytes());
+        message.extend_from_slice(&self.max_rating.to_le_bytes());
+        message.extend_from_slice(&self.expiry.to_le_bytes());
+        message.extend_from_slice(&self.nonce.to_le_bytes());
+        message.extend_from_slice(&params_hash);
+        Ok(message)
+    }
+
+    pub fn accepts(&self, opponent_rating: u16) -> bool {
+        opponent_rating >= self.min_rating && opponent_rating <= self.max_rating
+    }
+}
+
+/// Highest intent nonce consumed per player; intents must use a larger one
+#[component]
+#[derive(Default)]
+pub struct IntentNonce {
+    pub player: Pubkey,
+    pub last_nonce: u64,
+}
+
+/// CreateMatchedDuel - Seat two players from their signed intents in one transaction.
+/// The two ed25519 verifications must immediately precede this instruction, in seat order.
+#[derive(Accounts)]
+pub struct CreateMatchedDuel<'info> {
+    #[account(mut)]
+    pub matchmaker: Signer<'info>,
+
+    /// CHECK: World PDA
+    #[account(mut)]
+    pub world: AccountInfo<'info>,
+
+    /// CHECK: Entity for the duel
+    #[account(mut)]
+    pub entity: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = matchmaker,
+        space = 8 + std::mem::size_of::<DuelComponent>(),
+        seeds = [b"duel", entity.key().as_ref()],
+        bump
+    )]
+    pub duel: Account<'info, ComponentData<DuelComponent>>,
+
+    #[account(
+        init,
+        payer = matchmaker,
+        space = 8 + std::mem::size_of::<BettingComponent>() + MAX_BLIND_LEVELS * std::mem::size_of::<BlindLevel>(),
+        seeds = [b"betting", entity.key().as_ref()],
+        bump
+    )]
+    pub betting: Account<'info, ComponentData<BettingComponent>>,
+
+    /// CHECK: Seat one, authenticated by their signed intent
+    pub player_one: UncheckedAccount<'info>,
+
+    /// CHECK: Seat two, authenticated by their signed intent
+    pub player_two: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = matchmaker,
+        space = 8 + std::mem::size_of::<PlayerComponent>(),
+        seeds = [b"player", player_one.key().as_ref(), entity.key().as_ref()],
+        bump
+    )]
+    pub player_one_component: Account<'info, ComponentData<PlayerComponent>>,
+
+    #[account(
+        init,
+        payer = matchmaker,
+        space = 8 + std::mem::size_of::<PlayerComponent>(),
+        seeds = [b"player", player_two.key().as_ref(), entity.key().as_ref()],
+        bump
+    )]
+    pub player_two_component: Account<'info, ComponentData<PlayerComponent>>,
+
+    #[account(
+        mut,
+        seeds = [b"psych", player_one.key().as_ref()],
+        bump = player_one_psych.load()?.bump
+    )]
+    pub player_one_psych: Account<'info, ComponentData<PsychProfileComponent>>,
+
+    #[account(
+        mut,
+        seeds = [b"psych", player_two.key().as_ref()],
+        bump = player_two_psych.load()?.bump
+    )]
+    pub player_two_psych: Account<'info, ComponentData<PsychProfileComponent>>,
+
+    #[account(
+        init_if_needed,
+        payer = matchmaker,
+        space = 8 + std::mem::size_of::<IntentNonce>(),
+        seeds = [b"intent_nonce", player_one.key().as_ref()],
+        bump
+    )]
+    pub player_one_nonce: Account<'info, ComponentData<IntentNonce>>,
+
+    #[account(
+        init_if_needed,
+        payer = matchmaker,
+        space = 8 + std::mem::size_of::<IntentNonce>(),
+        seeds = [b"intent_nonce", player_two.key().as_ref()],
+        bump
+    )]
+    pub player_two_nonce: Account<'info, ComponentData<IntentNonce>>,
+
+    #[account(
+        seeds = [b"feature_flags"],
+        seeds::program = shared::GAME_PROGRAM_ID,
+        bump = feature_flags.bump
+    )]
+    pub feature_flags: Account<'info, shared::FeatureFlags>,
+
+    #[account(
+        seeds = [b"event_schedule"],
+        seeds::program = shared::GAME_PROGRAM_ID,
+        bump = event_schedule.bump
+    )]
+    pub event_schedule: Option<Account<'info, shared::EventSchedule>>,
+
+    /// CHECK: Instructions sysvar, read to find both intent signatures
+    #[account(address = ix_sysvar::ID)]
+    pub instructions: AccountInfo<'info>,
+
+    /// Repeat-pairing counter shared by both players
+    #[account(
+        init_if_needed,
+        payer = matchmaker,
+        space = shared::PairingWindow::LEN,
+        seeds = [
+            shared::PAIRING_WINDOW_SEED,
+            shared::PairingWindow::ordered(player_one.key(), player_two.key()).0.as_ref(),
+            shared::PairingWindow::ordered(player_one.key(), player_two.key()).1.as_ref()
+        ],
+        bump
+    )]
+    pub pairing_window: Account<'info, shared::PairingWindow>,
+
+    /// The pair's lifetime record, counted at settlement
+    #[account(
+        init_if_needed,
+        payer = matchmaker,
+        space = 8 + std::mem::size_of::<HeadToHead>(),
+        seeds = [
+            b"head_to_head",
+            shared::PairingWindow::ordered(player_one.key(), player_two.key()).0.as_ref(),
+            shared::PairingWindow::ordered(player_one.key(), player_two.key()).1.as_ref()
+        ],
+        bump
+    )]
+    pub head_to_head: Account<'info, ComponentData<HeadToHead>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Count this duel on a player's profile; returns their reputation going in
+pub(crate) fn open_profile(profile: &Account<ComponentData<PsychProfileComponent>>) -> Result<u16> {
+    let mut psych = profile.load_mut()?;
+    let reputation = psych.reputation;
+    psych.duels_started += 1;
+    psych.refresh_reputation();
+    Ok(reputation)
+}
+
+/// Initialize a duel with both seats filled and both entry fees in the pot,
+/// skipping the WaitingForPlayers window entirely
+pub(crate) fn open_seated_duel(
+    duel: &mut DuelComponent,
+    betting: &mut BettingComponent,
+    seats: [&mut PlayerComponent; 2],
+    players: [Pubkey; 2],
+    params: CreateDuelParams,
+    modifiers: shared::EventModifiers,
+    current_time: i64,
+) -> Result<()> {
+    let duel_id = current_time as u64;
+
+    duel.duel_id = duel_id;
+    duel.player_one = players[0];
+    duel.player_two = players[1];
+    duel.current_round = 0;
+    duel.max_rounds = params.max_rounds;
+    duel.game_state = GameState::WaitingForPlayers;
+    duel.start_time = current_time;
+    duel.last_action_time = current_time;
+    duel.timeout_duration = params.timeout_duration;
+    duel.reconnect_grace_period = params.reconnect_grace_period;
+    duel.outcome_evaluator = params.outcome_evaluator;
+    duel.randomness_provider = params.randomness_provider;
+    duel.handicap = params.handicap;
+    duel.early_forfeit_refund_bps = params.early_forfeit_refund_bps;
+    duel.queue_type = params.queue_type;
+    duel.is_private = params.is_private;
+    duel.min_reputation = params.min_reputation;
+    duel.table_gate = params.table_gate;
+    duel.vrf_seed = generate_vrf_seed(duel_id);
+    require!(duel.apply_transition(DuelTrigger::Join), GameError::InvalidGameState);
+
+    betting.duel_id = duel_id;
+    betting.min_bet = params.min_bet;
+    betting.max_bet = modifiers.apply_max_bet(params.max_bet);
+    betting.total_pot = params.entry_fee.checked_mul(2).ok_or(GameError::InvalidRaise)?;
+    betting.blind_levels = params.blind_levels;
+    betting.blind_escalation = params.blind_escalation;
+    betting.betting_structure = params.betting_structure;
+
+    let positions = [PlayerPosition::Small, PlayerPosition::Big];
+    for (seat, player) in seats.into_iter().enumerate() {
+        player.player_id = players[seat];
+        player.duel_id = duel_id;
+        player.chip_count = 10000; // Starting chips
+        player.is_active = true;
+        player.position = positions[seat];
+        player.last_seen = current_time;
+        duel.replay_state.chip_counts[seat] = player.chip_count;
+        duel.replay_state.is_active[seat] = true;
+    }
+    duel.replay_state.total_pot = betting.total_pot;
+
+    Ok(())
+}
+
+/// Store both players' current ratings in the duel's queue on their seats and
+/// fix the handicap terms
+pub(crate) fn fix_seat_ratings(
+    duel: &mut DuelComponent,
+    seats: [&mut PlayerComponent; 2],
+    profiles: [&PsychProfileComponent; 2],
+) {
+    let tracks = [profiles[0].track(duel.queue_type), profiles[1].track(duel.queue_type)];
+    let ratings = [tracks[0].rating(), tracks[1].rating()];
+    for (seat, player) in seats.into_iter().enumerate() {
+        player.skill_rating = ratings[seat];
+        player.rating_deviation = tracks[seat].deviation();
+    }
+    duel.fix_handicap(ratings);
+}
+
+impl<'info> CreateMatchedDuel<'info> {
+    pub fn process(
+        &mut self,
+        params: CreateDuelParams,
+        intents: [MatchmakingIntent; 2],
+        bumps: &CreateMatchedDuelBumps,
+    ) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let players = [self.player_one.key(), self.player_two.key()];
+
+        require!(players[0] != players[1], MatchmakingError::SamePlayer);
+        self.pairing_window.record(players[0], players[1], bumps.pairing_window, current_time)?;
+        self.head_to_head.load_mut()?.open(players[0], players[1]);
+        // No gate token accounts are passed, so only open tables can be matched
+        require!(!params.table_gate.is_gated(), MatchmakingError::GatedTableUnsupported);
+
+        let current_index = ix_sysvar::load_current_index_checked(&self.instructions)? as usize;
+        require!(current_index >= 2, MatchmakingError::MissingIntentSignature);
+        for (seat, intent) in intents.iter().enumerate() {
+            shared::require_feature(&self.feature_flags, shared::FEATURE_CREATE_DUEL, &intent.player)?;
+            require_keys_eq!(intent.player, players[seat], MatchmakingError::InvalidIntentSignature);
+            require!(intent.stake == params.entry_fee, MatchmakingError::StakeMismatch);
+            require!(current_time < intent.expiry, MatchmakingError::IntentExpired);
+
+            let ix = ix_sysvar::load_instruction_at_checked(current_index - 2 + seat, &self.instructions)?;
+            require_keys_eq!(ix.program_id, ed25519_program::ID, MatchmakingError::MissingIntentSignature);
+            require!(
+                ed25519_matches(&ix.data, &intent.player, &intent.message(&params)?),
+                MatchmakingError::InvalidIntentSignature
+            );
+        }
+
+        for (account, intent) in [(&self.player_one_nonce, &intents[0]), (&self.player_two_nonce, &intents[1])] {
+            let mut nonce = account.load_mut()?;
+            require!(intent.nonce > nonce.last_nonce, MatchmakingError::IntentNonceUsed);
+            nonce.player = intent.player;
+            nonce.last_nonce = intent.nonce;
+        }
+
+        let reputations = [
+            open_profile(&self.player_one_psych)?,
+            open_profile(&self.player_two_psych)?,
+        ];
+        for seat in 0..2 {
+            require!(reputations[seat] >= params.min_reputation, GameError::ReputationTooLow);
+            require!(intents[seat].accepts(reputations[1 - seat]), MatchmakingError::RatingOutOfBand);
+        }
+
+        let modifiers = shared::event_modifiers(self.event_schedule.as_deref(), current_time);
+        let mut duel = self.duel.load_init()?;
+        let mut betting = self.betting.load_init()?;
+        let mut player_one = self.player_one_component.load_init()?;
+        let mut player_two = self.player_two_component.load_init()?;
+        open_seated_duel(
+            &mut duel,
+            &mut betting,
+            [&mut player_one, &mut player_two],
+            players,
+            params,
+            modifiers,
+            current_time,
+        )?;
+        duel.bump = bumps.duel;
+        betting.bump = bumps.betting;
+        player_one.bump = bumps.player_one_component;
+        player_two.bump = bumps.player_two_component;
+        let profiles = [self.player_one_psych.load()?, self.player_two_psych.load()?];
+        fix_seat_ratings(&mut duel, [&mut player_one, &mut player_two], [&*profiles[0], &*profiles[1]]);
+
+        emit!(MatchedDuelCreatedEvent {
+            duel_id: duel.duel_id,
+            matchmaker: self.matchmaker.key(),
+            player_one: players[0],
+            player_two: players[1],
+            stake: intents[0].stake,
+            nonces: [intents[0].nonce, intents[1].nonce],
+        });
+        emit!(crate::DuelStartedEvent {
+            duel_id: duel.duel_id,
+            player_one: players[0],
+            player_two: players[1],
+            player_one_cosmetics: Default::default(),
+            player_two_cosmetics: Default::default(),
+            started_at: current_time,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct MatchedDuelCreatedEvent {
+    pub duel_id: u64,
+    pub matchmaker: Pubkey,
+    pub player_one: Pubkey,
+    pub player_two: Pubkey,
+    pub stake: u64,
+    pub nonces: [u64; 2],
+}
+
+#[error_code]
+pub enum MatchmakingError {
+    #[msg("Each seat needs a preceding ed25519 intent signature")]
+    MissingIntentSignature,
+    #[msg("Intent signature does not match the seated player")]
+    InvalidIntentSignature,
+    #[msg("Matchmaking intent has expired")]
+    IntentExpired,
+    #[msg("Intent nonce already used")]
+    IntentNonceUsed,
+    #[msg("Intent stake does not match the duel's entry fee")]
+    StakeMismatch,
+    #[msg("Opponent is outside the intent's rating band")]
+    RatingOutOfBand,
+    #[msg("A player cannot be matched against themselves")]
+    SamePlayer,
+    #[msg("Gated tables cannot be created from intents")]
+    GatedTableUnsupported,
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use super::admin_config::AdminConfigError;
+use super::GameError;
+
+/// Metrics ids recorded by this program; dashboards map them back to names
+pub const METRIC_SETTLEMENT: u8 = 0;
+
+/// Settlement branches
+pub const METRIC_BRANCH_DECISIVE: u8 = 0;
+pub const METRIC_BRANCH_DRAW: u8 = 1;
+
+/// InitializeMetricsBuffer - Create one shard of the compute-unit sample rings the
+/// keeper reads; super admin only
+#[derive(Accounts)]
+#[instruction(shard: u8)]
+pub struct InitializeMetricsBuffer<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [shared::ADMIN_CONFIG_SEED],
+        bump = admin_config.bump,
+        constraint = admin_config.super_admin == authority.key() @ AdminConfigError::NotSuperAdmin
+    )]
+    pub admin_config: Account<'info, shared::AdminConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = shared::MetricsBuffer::LEN,
+        seeds = [shared::METRICS_BUFFER_SEED, &[shard]],
+        bump
+    )]
+    pub metrics_buffer: Account<'info, shared::MetricsBuffer>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeMetricsBuffer<'info> {
+    pub fn process(&mut self, shard: u8, bump: u8) -> Result<()> {
+        require!(shard < shared::METRICS_SHARDS, GameError::InvalidGameState);
+        self.metrics_buffer.authority = self.authority.key();
+        self.metrics_buffer.shard = shard;
+        self.metrics_buffer.bump = bump;
+        Ok(())
+    }
+}
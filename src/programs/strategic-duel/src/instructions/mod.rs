@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
 use bolt_lang::*;
 use crate::components::*;
 
@@ -12,6 +13,44 @@ pub mod vrf_attestation;
 pub mod rollup_settlement;
 pub mod ephemeral_rollup;
 pub mod gas_optimization;
+pub mod replay_log;
+pub mod reconnect;
+pub mod practice;
+pub mod insurance;
+pub mod transfer_limit;
+pub mod compliance;
+pub mod state_migration;
+pub mod bankroll;
+pub mod operator_bond;
+pub mod system_registry;
+pub mod entropy;
+pub mod close_action;
+pub mod matchmaking;
+pub mod direct_duel;
+pub mod lobby;
+pub mod vrf_request;
+pub mod randomness;
+pub mod spectator;
+pub mod delayed_feed;
+pub mod pause;
+pub mod forfeit;
+pub mod rake;
+pub mod table_tiers;
+pub mod loyalty;
+pub mod jackpot;
+pub mod daily_contest;
+pub mod rating_decay;
+pub mod queue_ratings;
+pub mod match_history;
+pub mod head_to_head;
+pub mod duel_preset;
+pub mod scheduled_duel;
+pub mod observer_access;
+pub mod metrics;
+pub mod program_info;
+pub mod rate_limit;
+pub mod duel_gc;
+pub mod admin_config;
 
 pub use create_duel::*;
 pub use join_duel::*;
@@ -23,6 +62,44 @@ pub use vrf_attestation::*;
 pub use rollup_settlement::*;
 pub use ephemeral_rollup::*;
 pub use gas_optimization::*;
+pub use replay_log::*;
+pub use reconnect::*;
+pub use practice::*;
+pub use insurance::*;
+pub use transfer_limit::*;
+pub use compliance::*;
+pub use state_migration::*;
+pub use bankroll::*;
+pub use operator_bond::*;
+pub use system_registry::*;
+pub use entropy::*;
+pub use close_action::*;
+pub use matchmaking::*;
+pub use direct_duel::*;
+pub use lobby::*;
+pub use vrf_request::*;
+pub use randomness::*;
+pub use spectator::*;
+pub use delayed_feed::*;
+pub use pause::*;
+pub use forfeit::*;
+pub use rake::*;
+pub use table_tiers::*;
+pub use loyalty::*;
+pub use jackpot::*;
+pub use daily_contest::*;
+pub use rating_decay::*;
+pub use queue_ratings::*;
+pub use match_history::*;
+pub use head_to_head::*;
+pub use duel_preset::*;
+pub use scheduled_duel::*;
+pub use observer_access::*;
+pub use metrics::*;
+pub use program_info::*;
+pub use rate_limit::*;
+pub use duel_gc::*;
+pub use admin_config::*;
 
 /// CreateDuel - Initialize a new duel game
 #[derive(Accounts)]
@@ -50,7 +127,7 @@ pub struct CreateDuel<'info> {
     #[account(
         init,
         payer = creator,
-        space = 8 + std::mem::size_of::<BettingComponent>(),
+        space = 8 + std::mem::size_of::<BettingComponent>() + MAX_BLIND_LEVELS * std::mem::size_of::<BlindLevel>(),
         seeds = [b"betting", entity.key().as_ref()],
         bump
     )]
@@ -66,13 +143,88 @@ pub struct CreateDuel<'info> {
     pub creator_player: Account<'info, ComponentData<PlayerComponent>>,
 
     #[account(
-        init,
+        mut,
+        seeds = [b"psych", creator.key().as_ref()],
+        bump = creator_psych.load()?.bump
+    )]
+    pub creator_psych: Account<'info, ComponentData<PsychProfileComponent>>,
+
+    #[account(
+        seeds = [b"feature_flags"],
+        seeds::program = shared::GAME_PROGRAM_ID,
+        bump = feature_flags.bump
+    )]
+    pub feature_flags: Account<'info, shared::FeatureFlags>,
+
+    #[account(
+        seeds = [b"event_schedule"],
+        seeds::program = shared::GAME_PROGRAM_ID,
+        bump = event_schedule.bump
+    )]
+    pub event_schedule: Option<Account<'info, shared::EventSchedule>>,
+
+    /// Creator's holding for gated tables
+    #[account(token::authority = creator)]
+    pub gate_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Mint and minimum each gated tier requires; needed for gated tables
+    #[account(
+        seeds = [b"table_tiers"],
+        bump
+    )]
+    pub table_tiers: Option<Account<'info, ComponentData<TableTierConfig>>>,
+
+    /// Open-table index; listed on create, removed on join or cancel
+    #[account(
+        mut,
+        seeds = [b"lobby"],
+        bump
+    )]
+    pub lobby: Account<'info, ComponentData<LobbyRegistry>>,
+
+    #[account(
+        init_if_needed,
         payer = creator,
+        space = shared::RateLimit::LEN,
+        seeds = [b"rate_limit", creator.key().as_ref(), &[shared::RATE_LIMIT_CREATE_DUEL]],
+        bump
+    )]
+    pub rate_limit: Account<'info, shared::RateLimit>,
+
+    /// CHECK: Governance rate limits; required once initialized, defaults until then
+    #[account(
+        seeds = [shared::RATE_LIMIT_CONFIG_SEED],
+        bump
+    )]
+    pub rate_limit_config: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// InitializePsychProfile - Open a player's cross-duel profile, once, before their first duel
+#[derive(Accounts)]
+pub struct InitializePsychProfile<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        init,
+        payer = player,
         space = 8 + std::mem::size_of::<PsychProfileComponent>(),
-        seeds = [b"psych", creator.key().as_ref()],
+        seeds = [b"psych", player.key().as_ref()],
         bump
     )]
-    pub creator_psych: Account<'info, ComponentData<PsychProfileComponent>>,
+    pub psych_profile: Account<'info, ComponentData<PsychProfileComponent>>,
+
+    /// The player's match history, paid for by them rather than by whoever settles
+    #[account(
+        init,
+        payer = player,
+        space = 8 + std::mem::size_of::<RecentMatches>(),
+        seeds = [b"recent_matches", player.key().as_ref()],
+        bump
+    )]
+    pub recent_matches: Account<'info, ComponentData<RecentMatches>>,
 
     pub system_program: Program<'info, System>,
 }
@@ -94,7 +246,7 @@ pub struct JoinDuel<'info> {
     #[account(
         mut,
         seeds = [b"duel", entity.key().as_ref()],
-        bump
+        bump = duel.load()?.bump
     )]
     pub duel: Account<'info, ComponentData<DuelComponent>>,
 
@@ -107,14 +259,79 @@ pub struct JoinDuel<'info> {
     )]
     pub player_component: Account<'info, ComponentData<PlayerComponent>>,
 
+    #[account(
+        mut,
+        seeds = [b"psych", player.key().as_ref()],
+        bump = player_psych.load()?.bump
+    )]
+    pub player_psych: Account<'info, ComponentData<PsychProfileComponent>>,
+
+    #[account(
+        seeds = [b"compliance_config"],
+        bump
+    )]
+    pub compliance_config: Account<'info, ComponentData<ComplianceConfig>>,
+
+    #[account(
+        seeds = [b"compliance_credential", player.key().as_ref()],
+        bump
+    )]
+    pub compliance_credential: Option<Account<'info, ComponentData<ComplianceCredential>>>,
+
+    // Cosmetic loadouts (NFT program) echoed in DuelStartedEvent; cosmetics never affect play
+    #[account(
+        seeds = [b"cosmetic_loadout", duel.load()?.player_one.as_ref()],
+        seeds::program = shared::NFT_PROGRAM_ID,
+        bump = creator_loadout.bump
+    )]
+    pub creator_loadout: Option<Account<'info, shared::CosmeticLoadout>>,
+
+    #[account(
+        seeds = [b"cosmetic_loadout", player.key().as_ref()],
+        seeds::program = shared::NFT_PROGRAM_ID,
+        bump = player_loadout.bump
+    )]
+    pub player_loadout: Option<Account<'info, shared::CosmeticLoadout>>,
+
+    /// Joiner's holding for gated tables
+    #[account(token::authority = player)]
+    pub gate_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Open-table index; listed on create, removed on join or cancel
+    #[account(
+        mut,
+        seeds = [b"lobby"],
+        bump
+    )]
+    pub lobby: Account<'info, ComponentData<LobbyRegistry>>,
+
+    /// Repeat-pairing counter shared by both players
     #[account(
         init_if_needed,
         payer = player,
-        space = 8 + std::mem::size_of::<PsychProfileComponent>(),
-        seeds = [b"psych", player.key().as_ref()],
+        space = shared::PairingWindow::LEN,
+        seeds = [
+            shared::PAIRING_WINDOW_SEED,
+            shared::PairingWindow::ordered(duel.load()?.player_one, player.key()).0.as_ref(),
+            shared::PairingWindow::ordered(duel.load()?.player_one, player.key()).1.as_ref()
+        ],
         bump
     )]
-    pub player_psych: Account<'info, ComponentData<PsychProfileComponent>>,
+    pub pairing_window: Account<'info, shared::PairingWindow>,
+
+    /// The pair's lifetime record, counted at settlement
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + std::mem::size_of::<HeadToHead>(),
+        seeds = [
+            b"head_to_head",
+            shared::PairingWindow::ordered(duel.load()?.player_one, player.key()).0.as_ref(),
+            shared::PairingWindow::ordered(duel.load()?.player_one, player.key()).1.as_ref()
+        ],
+        bump
+    )]
+    pub head_to_head: Account<'info, ComponentData<HeadToHead>>,
 
     pub system_program: Program<'info, System>,
 }
@@ -125,9 +342,12 @@ pub struct ActionProcessing<'info> {
     #[account(mut)]
     pub player_signer: Signer<'info>,
 
-    /// CHECK: World PDA
-    #[account(mut)]
-    pub world: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"world"],
+        bump
+    )]
+    pub world: Account<'info, ComponentData<crate::BoltWorldComponent>>,
 
     /// CHECK: Entity for the action
     #[account(mut)]
@@ -136,14 +356,14 @@ pub struct ActionProcessing<'info> {
     #[account(
         mut,
         seeds = [b"duel", entity.key().as_ref()],
-        bump
+        bump = duel.load()?.bump
     )]
     pub duel: Account<'info, ComponentData<DuelComponent>>,
 
     #[account(
         mut,
         seeds = [b"player", player.key().as_ref(), entity.key().as_ref()],
-        bump
+        bump = player.load()?.bump
     )]
     pub player: Account<'info, ComponentData<PlayerComponent>>,
 
@@ -159,17 +379,29 @@ pub struct ActionProcessing<'info> {
     #[account(
         mut,
         seeds = [b"betting", entity.key().as_ref()],
-        bump
+        bump = betting.load()?.bump
     )]
     pub betting: Account<'info, ComponentData<BettingComponent>>,
 
     #[account(
         mut,
         seeds = [b"psych", player.key().as_ref()],
-        bump
+        bump = psych_profile.load()?.bump
     )]
     pub psych_profile: Account<'info, ComponentData<PsychProfileComponent>>,
 
+    #[account(
+        init_if_needed,
+        payer = player_signer,
+        space = 8 + std::mem::size_of::<ReplayChunk>() + MAX_REPLAY_ENTRIES_PER_CHUNK * std::mem::size_of::<ReplayEntry>(),
+        seeds = [b"replay", entity.key().as_ref(), &duel.load()?.replay_chunk_count.to_le_bytes()],
+        bump
+    )]
+    pub replay_chunk: Account<'info, ComponentData<ReplayChunk>>,
+
+    /// ER sequencer co-signing a latency attestation, if one is supplied
+    pub latency_signer: Option<Signer<'info>>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -177,6 +409,7 @@ pub struct ActionProcessing<'info> {
 #[derive(Accounts)]
 pub struct RoundProgression<'info> {
     /// CHECK: Authority to advance rounds (could be any player or automated)
+    #[account(mut)]
     pub authority: Signer<'info>,
 
     /// CHECK: World PDA
@@ -190,16 +423,41 @@ pub struct RoundProgression<'info> {
     #[account(
         mut,
         seeds = [b"duel", entity.key().as_ref()],
-        bump
+        bump = duel.load()?.bump
     )]
     pub duel: Account<'info, ComponentData<DuelComponent>>,
 
     #[account(
         mut,
         seeds = [b"betting", entity.key().as_ref()],
-        bump
+        bump = betting.load()?.bump
     )]
     pub betting: Account<'info, ComponentData<BettingComponent>>,
+
+    #[account(
+        mut,
+        seeds = [b"player", duel.load()?.player_one.as_ref(), entity.key().as_ref()],
+        bump = player_one.load()?.bump
+    )]
+    pub player_one: Account<'info, ComponentData<PlayerComponent>>,
+
+    #[account(
+        mut,
+        seeds = [b"player", duel.load()?.player_two.as_ref(), entity.key().as_ref()],
+        bump = player_two.load()?.bump
+    )]
+    pub player_two: Account<'info, ComponentData<PlayerComponent>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + std::mem::size_of::<ReplayChunk>() + MAX_REPLAY_ENTRIES_PER_CHUNK * std::mem::size_of::<ReplayEntry>(),
+        seeds = [b"replay", entity.key().as_ref(), &duel.load()?.replay_chunk_count.to_le_bytes()],
+        bump
+    )]
+    pub replay_chunk: Account<'info, ComponentData<ReplayChunk>>,
+
+    pub system_program: Program<'info, System>,
 }
 
 /// VrfResolution - Resolve game with VRF
@@ -208,9 +466,12 @@ pub struct VrfResolution<'info> {
     /// CHECK: VRF authority
     pub vrf_authority: Signer<'info>,
 
-    /// CHECK: World PDA
-    #[account(mut)]
-    pub world: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"world"],
+        bump
+    )]
+    pub world: Account<'info, ComponentData<crate::BoltWorldComponent>>,
 
     /// CHECK: Entity for the duel
     #[account(mut)]
@@ -219,16 +480,35 @@ pub struct VrfResolution<'info> {
     #[account(
         mut,
         seeds = [b"duel", entity.key().as_ref()],
-        bump
+        bump = duel.load()?.bump
     )]
     pub duel: Account<'info, ComponentData<DuelComponent>>,
 
     #[account(
         mut,
         seeds = [b"betting", entity.key().as_ref()],
-        bump
+        bump = betting.load()?.bump
     )]
     pub betting: Account<'info, ComponentData<BettingComponent>>,
+
+    /// This duel's resolution request; required unless the duel resolves by
+    /// commit-reveal, which has no provider to answer it
+    #[account(
+        seeds = [b"vrf_request", entity.key().as_ref()],
+        bump
+    )]
+    pub vrf_request: Option<Account<'info, ComponentData<VrfRequest>>>,
+
+    /// Oracle registry; the signer must be the duel provider's oracle
+    #[account(
+        seeds = [b"randomness_registry"],
+        bump
+    )]
+    pub randomness_registry: Account<'info, ComponentData<RandomnessRegistry>>,
+
+    /// CHECK: Switchboard randomness account; required for Switchboard duels,
+    /// owner and reveal are checked in switchboard_randomness
+    pub switchboard_randomness: Option<UncheckedAccount<'info>>,
 }
 
 /// PsychologicalAnalysis - Analyze player behavior
@@ -248,19 +528,19 @@ pub struct PsychologicalAnalysis<'info> {
     #[account(
         mut,
         seeds = [b"psych", entity.key().as_ref()],
-        bump
+        bump = psych_profile.load()?.bump
     )]
     pub psych_profile: Account<'info, ComponentData<PsychProfileComponent>>,
 
     #[account(
         seeds = [b"player", entity.key().as_ref()],
-        bump
+        bump = player.load()?.bump
     )]
     pub player: Account<'info, ComponentData<PlayerComponent>>,
 
     #[account(
         seeds = [b"betting", entity.key().as_ref()],
-        bump
+        bump = betting.load()?.bump
     )]
     pub betting: Account<'info, ComponentData<BettingComponent>>,
 }
@@ -269,6 +549,7 @@ pub struct PsychologicalAnalysis<'info> {
 #[derive(Accounts)]
 pub struct Settlement<'info> {
     /// CHECK: Settlement authority
+    #[account(mut)]
     pub authority: Signer<'info>,
 
     /// CHECK: World PDA
@@ -282,41 +563,252 @@ pub struct Settlement<'info> {
     #[account(
         mut,
         seeds = [b"duel", entity.key().as_ref()],
-        bump
+        bump = duel.load()?.bump
     )]
     pub duel: Account<'info, ComponentData<DuelComponent>>,
 
     #[account(
         mut,
         seeds = [b"betting", entity.key().as_ref()],
-        bump
+        bump = betting.load()?.bump
     )]
     pub betting: Account<'info, ComponentData<BettingComponent>>,
 
     #[account(
         mut,
-        seeds = [b"player", duel.load()?.winner.unwrap().as_ref(), entity.key().as_ref()],
-        bump
+        seeds = [b"player", get_winner_key(&duel.load()?).as_ref(), entity.key().as_ref()],
+        bump = winner_player.load()?.bump
     )]
     pub winner_player: Account<'info, ComponentData<PlayerComponent>>,
 
     #[account(
         mut,
         seeds = [b"player", get_loser_key(&duel.load()?).as_ref(), entity.key().as_ref()],
-        bump
+        bump = loser_player.load()?.bump
     )]
     pub loser_player: Account<'info, ComponentData<PlayerComponent>>,
 
-    /// CHECK: Treasury account for rake collection
-    #[account(mut)]
+    /// CHECK: Wallet of the seat settled as the winner; paid its share of any escrowed stake
+    #[account(mut, address = get_winner_key(&duel.load()?) @ GameError::WrongPayoutWallet)]
+    pub winner_wallet: AccountInfo<'info>,
+
+    /// CHECK: Wallet of the other seat
+    #[account(mut, address = get_loser_key(&duel.load()?) @ GameError::WrongPayoutWallet)]
+    pub loser_wallet: AccountInfo<'info>,
+
+    /// CHECK: Treasury account for rake collection; fixed by the rake schedule
+    #[account(mut, address = rake_schedule.load()?.treasury @ RakeError::WrongTreasury)]
     pub treasury: AccountInfo<'info>,
 
+    /// Chip-dumping cap; until governance configures it, pair flow is tracked uncapped
+    #[account(
+        seeds = [b"transfer_limit_config"],
+        bump
+    )]
+    pub transfer_limit_config: Option<Account<'info, ComponentData<TransferLimitConfig>>>,
+
+    /// Governance choice of rating system for the duel's queue
+    #[account(
+        seeds = [b"queue_ratings"],
+        bump
+    )]
+    pub queue_ratings: Account<'info, ComponentData<QueueRatingConfig>>,
+
+    /// Governance rake schedule
+    #[account(
+        seeds = [b"rake_schedule"],
+        bump
+    )]
+    pub rake_schedule: Account<'info, ComponentData<RakeSchedule>>,
+
+    /// Progressive jackpot fed by a micro-rake on every staked duel
+    #[account(
+        mut,
+        seeds = [b"jackpot_vault"],
+        bump
+    )]
+    pub jackpot_vault: Account<'info, ComponentData<JackpotVault>>,
+
+    /// Daily high-hand contest; every real-stake pot is checked against the record
+    #[account(
+        mut,
+        seeds = [b"daily_contest"],
+        bump
+    )]
+    pub daily_contest: Account<'info, ComponentData<DailyContest>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + std::mem::size_of::<TransferLimit>(),
+        seeds = [b"transfer_limit", ordered_pair(&duel.load()?).0.as_ref(), ordered_pair(&duel.load()?).1.as_ref()],
+        bump
+    )]
+    pub transfer_limit: Account<'info, ComponentData<TransferLimit>>,
+
+    /// Cross-duel profiles carrying reputation; required unless the duel is a practice duel
+    #[account(
+        mut,
+        seeds = [b"psych", get_winner_key(&duel.load()?).as_ref()],
+        bump = winner_psych.load()?.bump
+    )]
+    pub winner_psych: Option<Account<'info, ComponentData<PsychProfileComponent>>>,
+
+    #[account(
+        mut,
+        seeds = [b"psych", get_loser_key(&duel.load()?).as_ref()],
+        bump = loser_psych.load()?.bump
+    )]
+    pub loser_psych: Option<Account<'info, ComponentData<PsychProfileComponent>>>,
+
+    /// CHECK: Winner's bankroll PDA; when one exists the escrowed winnings are
+    /// credited to it and large wins start the withdrawal cooldown
+    #[account(
+        mut,
+        seeds = [b"bankroll", get_winner_key(&duel.load()?).as_ref()],
+        bump
+    )]
+    pub winner_bankroll: UncheckedAccount<'info>,
+
+    /// Rake paid accrues to each player's loyalty account
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + std::mem::size_of::<LoyaltyAccount>(),
+        seeds = [b"loyalty", get_winner_key(&duel.load()?).as_ref()],
+        bump
+    )]
+    pub winner_loyalty: Account<'info, ComponentData<LoyaltyAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + std::mem::size_of::<LoyaltyAccount>(),
+        seeds = [b"loyalty", get_loser_key(&duel.load()?).as_ref()],
+        bump
+    )]
+    pub loser_loyalty: Account<'info, ComponentData<LoyaltyAccount>>,
+
+    /// Each player's match history ring buffer, opened with their profile;
+    /// practice duels are not recorded
+    #[account(
+        mut,
+        seeds = [b"recent_matches", get_winner_key(&duel.load()?).as_ref()],
+        bump
+    )]
+    pub winner_recent_matches: Option<Account<'info, ComponentData<RecentMatches>>>,
+
+    #[account(
+        mut,
+        seeds = [b"recent_matches", get_loser_key(&duel.load()?).as_ref()],
+        bump
+    )]
+    pub loser_recent_matches: Option<Account<'info, ComponentData<RecentMatches>>>,
+
+    /// Opened when the pair was seated
+    #[account(
+        mut,
+        seeds = [b"head_to_head", ordered_pair(&duel.load()?).0.as_ref(), ordered_pair(&duel.load()?).1.as_ref()],
+        bump
+    )]
+    pub head_to_head: Option<Account<'info, ComponentData<HeadToHead>>>,
+
+    /// Sets the rake-back epoch and receives the rake-back share of lamport rake
+    #[account(
+        mut,
+        seeds = [b"loyalty_program"],
+        bump
+    )]
+    pub loyalty_program: Account<'info, ComponentData<LoyaltyProgram>>,
+
+    /// Compute-unit samples for dashboards, in the shard this duel maps to
+    #[account(
+        mut,
+        seeds = [shared::METRICS_BUFFER_SEED, &[shared::metrics_shard(&duel.key())]],
+        bump = metrics_buffer.bump
+    )]
+    pub metrics_buffer: Account<'info, shared::MetricsBuffer>,
+
+    /// Guild rake share; with a seat's guild and membership below, the guild's
+    /// slice of that seat's lamport rake is deposited into the guild treasury
+    #[account(
+        seeds = [b"guild_config"],
+        bump = guild_config.bump,
+        seeds::program = sol_duel_guild::ID
+    )]
+    pub guild_config: Option<Account<'info, sol_duel_guild::GuildConfig>>,
+
+    #[account(mut)]
+    pub winner_guild: Option<Account<'info, sol_duel_guild::Guild>>,
+
+    #[account(
+        mut,
+        seeds = [b"guild_member", get_winner_key(&duel.load()?).as_ref()],
+        bump = winner_guild_member.bump,
+        seeds::program = sol_duel_guild::ID
+    )]
+    pub winner_guild_member: Option<Account<'info, sol_duel_guild::GuildMember>>,
+
+    #[account(mut)]
+    pub loser_guild: Option<Account<'info, sol_duel_guild::Guild>>,
+
+    #[account(
+        mut,
+        seeds = [b"guild_member", get_loser_key(&duel.load()?).as_ref()],
+        bump = loser_guild_member.bump,
+        seeds::program = sol_duel_guild::ID
+    )]
+    pub loser_guild_member: Option<Account<'info, sol_duel_guild::GuildMember>>,
+
+    /// CHECK: System-owned PDA the guild program takes rake deposits from; it
+    /// only holds the guild slices for the length of the deposit
+    #[account(
+        mut,
+        seeds = [shared::GUILD_RAKE_AUTHORITY_SEED],
+        bump
+    )]
+    pub guild_rake_authority: UncheckedAccount<'info>,
+
+    pub guild_program: Option<Program<'info, sol_duel_guild::program::SolDuelGuild>>,
+
+    /// Standard result record read by escrows, quests and tournaments
+    #[account(
+        init,
+        payer = authority,
+        space = shared::WinnerAttestation::LEN,
+        seeds = [shared::WINNER_ATTESTATION_SEED, duel.key().as_ref()],
+        bump
+    )]
+    pub winner_attestation: Account<'info, shared::WinnerAttestation>,
+
+    /// Holds the CPI guard configuration for settlement
+    #[account(
+        seeds = [shared::ADMIN_CONFIG_SEED],
+        bump = admin_config.bump
+    )]
+    pub admin_config: Account<'info, shared::AdminConfig>,
+
+    /// CHECK: Instructions sysvar, read by the CPI guard
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
+// Seat settled as the winner; a draw settles player one in that seat
+fn get_winner_key(duel: &DuelComponent) -> Pubkey {
+    match duel.winner {
+        Some(winner) => winner,
+        None if duel.is_draw => duel.player_one,
+        None => Pubkey::default(),
+    }
+}
+
 // Helper function to get loser key
 fn get_loser_key(duel: &DuelComponent) -> Pubkey {
-    if let Some(winner) = duel.winner {
+    if duel.is_draw {
+        duel.player_two
+    } else if let Some(winner) = duel.winner {
         if winner == duel.player_one {
             duel.player_two
         } else {
@@ -328,18 +820,64 @@ fn get_loser_key(duel: &DuelComponent) -> Pubkey {
 }
 
 /// Instruction parameters
-#[derive(AnchorSerialize, AnchorDeserialize)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
 pub struct CreateDuelParams {
     pub max_rounds: u8,
     pub min_bet: u64,
     pub max_bet: u64,
     pub timeout_duration: i64,
     pub entry_fee: u64,
+    pub reconnect_grace_period: i64,
+    pub outcome_evaluator: OutcomeEvaluator,
+    pub randomness_provider: RandomnessProvider,
+    pub handicap: HandicapMode,
+    pub blind_levels: Vec<BlindLevel>,
+    pub blind_escalation: BlindEscalation,
+    pub betting_structure: BettingStructure,
+    pub min_reputation: u16,
+    pub table_gate: TableGate,
+    pub early_forfeit_refund_bps: u16,
+    pub queue_type: QueueType,
+    pub is_private: bool,
+}
+
+impl CreateDuelParams {
+    /// Parameter bounds shared by every instruction that opens a duel
+    pub fn validate(&self) -> Result<()> {
+        require!(self.max_rounds > 0 && self.max_rounds <= 10, GameError::InvalidGameState);
+        require!(self.min_bet > 0 && self.min_bet <= self.max_bet, GameError::InvalidRaise);
+        require!(self.timeout_duration >= 30 && self.timeout_duration <= 300, GameError::ActionTimeout);
+        require!(
+            self.reconnect_grace_period >= 0 && self.reconnect_grace_period <= MAX_RECONNECT_GRACE_PERIOD,
+            GameError::ActionTimeout
+        );
+        require!(self.table_gate.is_valid(), GameError::InvalidTableGate);
+        require!(
+            self.early_forfeit_refund_bps <= MAX_EARLY_FORFEIT_REFUND_BPS,
+            GameError::InvalidForfeitRefund
+        );
+        // Tournament ratings only move through game-program tournament matches
+        require!(self.queue_type != QueueType::Tournament, GameError::TournamentQueueReserved);
+        require!(self.blind_levels.len() <= MAX_BLIND_LEVELS, GameError::InvalidBlindSchedule);
+        for level in self.blind_levels.iter() {
+            require!(
+                level.small_blind <= level.big_blind && level.big_blind <= self.max_bet,
+                GameError::InvalidBlindSchedule
+            );
+        }
+        match self.blind_escalation {
+            BlindEscalation::EveryRounds(rounds) => require!(rounds > 0, GameError::InvalidBlindSchedule),
+            BlindEscalation::EverySeconds(seconds) => require!(seconds > 0, GameError::InvalidBlindSchedule),
+            BlindEscalation::None => {},
+        }
+        Ok(())
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct JoinDuelParams {
     pub entry_fee: u64,
+    pub allowlist_proof: Vec<[u8; 32]>,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
@@ -363,15 +901,25 @@ pub struct VrfParams {
 
 /// Instruction implementations
 impl<'info> CreateDuel<'info> {
-    pub fn process(&mut self, params: CreateDuelParams) -> Result<()> {
+    pub fn process(&mut self, params: CreateDuelParams, bumps: &CreateDuelBumps) -> Result<()> {
         let clock = Clock::get()?;
         let current_time = clock.unix_timestamp;
 
+        // Throttle table spam before anything is listed in the lobby
+        let window = shared::RateLimitConfig::window_for(&self.rate_limit_config, shared::RATE_LIMIT_CREATE_DUEL)?;
+        if self.rate_limit.player == Pubkey::default() {
+            self.rate_limit.player = self.creator.key();
+            self.rate_limit.action = shared::RATE_LIMIT_CREATE_DUEL;
+            self.rate_limit.window_start = current_time;
+        }
+        self.rate_limit.consume(window, current_time)?;
+
         // Generate unique duel ID
         let duel_id = clock.unix_timestamp as u64;
 
         // Initialize duel component
         let mut duel = self.duel.load_init()?;
+        duel.bump = bumps.duel;
         duel.duel_id = duel_id;
         duel.player_one = self.creator.key();
         duel.player_two = Pubkey::default(); // Will be set when second player joins
@@ -381,17 +929,38 @@ impl<'info> CreateDuel<'info> {
         duel.start_time = current_time;
         duel.last_action_time = current_time;
         duel.timeout_duration = params.timeout_duration;
+        duel.reconnect_grace_period = params.reconnect_grace_period;
+        duel.outcome_evaluator = params.outcome_evaluator;
+        duel.randomness_provider = params.randomness_provider;
+        duel.handicap = params.handicap;
+        duel.early_forfeit_refund_bps = params.early_forfeit_refund_bps;
+        duel.queue_type = params.queue_type;
+        duel.is_private = params.is_private;
+        duel.min_reputation = params.min_reputation;
+        require_tier_gate(&self.table_tiers, &params.table_gate)?;
+        require!(
+            params.table_gate.is_satisfied_by(gate_holding(&self.gate_token_account)),
+            GameError::TableGateNotMet
+        );
+        duel.table_gate = params.table_gate;
         duel.vrf_seed = generate_vrf_seed(duel_id);
 
         // Initialize betting component
         let mut betting = self.betting.load_init()?;
+        betting.bump = bumps.betting;
         betting.duel_id = duel_id;
         betting.min_bet = params.min_bet;
-        betting.max_bet = params.max_bet;
+        // High-roller events raise the table limit for duels created during them
+        let modifiers = shared::event_modifiers(self.event_schedule.as_deref(), current_time);
+        betting.max_bet = modifiers.apply_max_bet(params.max_bet);
         betting.total_pot = params.entry_fee;
+        betting.blind_levels = params.blind_levels;
+        betting.blind_escalation = params.blind_escalation;
+        betting.betting_structure = params.betting_structure;
 
         // Initialize creator's player component
         let mut player = self.creator_player.load_init()?;
+        player.bump = bumps.creator_player;
         player.player_id = self.creator.key();
         player.duel_id = duel_id;
         player.chip_count = 10000; // Starting chips
@@ -399,31 +968,92 @@ impl<'info> CreateDuel<'info> {
         player.position = PlayerPosition::Small;
         player.last_seen = current_time;
 
-        // Initialize psychological profile
-        let mut psych = self.creator_psych.load_init()?;
-        psych.player = self.creator.key();
+        let mut psych = self.creator_psych.load_mut()?;
+        psych.duels_started += 1;
+        psych.refresh_reputation();
+        player.skill_rating = psych.track(duel.queue_type).rating();
+        player.rating_deviation = psych.track(duel.queue_type).deviation();
+        duel.join_ratings[0] = player.skill_rating;
+
+        // Seed the replay log with the opening state
+        duel.replay_state = ReplayState {
+            chip_counts: [player.chip_count, 0],
+            is_active: [true, false],
+            total_pot: betting.total_pot,
+            ..ReplayState::default()
+        };
+
+        // Private tables are shared by invitation, never listed
+        if !duel.is_private {
+            let slot = clock.slot;
+            self.lobby.load_mut()?.list(LobbyEntry {
+                entity: self.entity.key(),
+                creator: self.creator.key(),
+                entry_fee: params.entry_fee,
+                stake_bracket: stake_bracket(params.entry_fee),
+                variant: duel.outcome_evaluator,
+                listed_slot: slot,
+                expires_slot: slot + LOBBY_ENTRY_TTL_SLOTS,
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'info> InitializePsychProfile<'info> {
+    pub fn process(&mut self, bumps: &InitializePsychProfileBumps) -> Result<()> {
+        let mut psych = self.psych_profile.load_init()?;
+        psych.player = self.player.key();
+        psych.bump = bumps.psych_profile;
         psych.avg_decision_time = 5000; // 5 seconds default
         psych.consistency_rating = 500; // Neutral starting rating
-
+        psych.refresh_reputation();
+        self.recent_matches.load_init()?.player = self.player.key();
         Ok(())
     }
 }
 
 impl<'info> JoinDuel<'info> {
-    pub fn process(&mut self, params: JoinDuelParams) -> Result<()> {
+    pub fn process(&mut self, params: JoinDuelParams, bumps: &JoinDuelBumps) -> Result<()> {
         let clock = Clock::get()?;
         let current_time = clock.unix_timestamp;
 
+        // Regulated deployments gate entry on an allowlist proof or issuer credential
+        let credential = match &self.compliance_credential {
+            Some(account) => Some(account.load()?.clone()),
+            None => None,
+        };
+        self.compliance_config.load()?.verify(
+            &self.player.key(),
+            &params.allowlist_proof,
+            credential.as_ref(),
+            current_time,
+        )?;
+
         // Load and update duel
         let mut duel = self.duel.load_mut()?;
         require!(duel.game_state == GameState::WaitingForPlayers, GameError::InvalidGameState);
         require!(duel.player_two == Pubkey::default(), GameError::DuelAlreadyFull);
+        self.pairing_window.record(duel.player_one, self.player.key(), bumps.pairing_window, current_time)?;
+        self.head_to_head.load_mut()?.open(duel.player_one, self.player.key());
+
+        let mut psych = self.player_psych.load_mut()?;
+        require!(psych.reputation >= duel.min_reputation, GameError::ReputationTooLow);
+        require!(
+            duel.table_gate.is_satisfied_by(gate_holding(&self.gate_token_account)),
+            GameError::TableGateNotMet
+        );
+        psych.duels_started += 1;
+        psych.refresh_reputation();
 
         duel.player_two = self.player.key();
-        duel.game_state = GameState::InProgress;
+        require!(duel.apply_transition(DuelTrigger::Join), GameError::InvalidGameState);
+        self.lobby.load_mut()?.delist(&self.entity.key());
 
         // Initialize joining player's component
         let mut player = self.player_component.load_init()?;
+        player.bump = bumps.player_component;
         player.player_id = self.player.key();
         player.duel_id = duel.duel_id;
         player.chip_count = 10000; // Starting chips
@@ -431,19 +1061,36 @@ impl<'info> JoinDuel<'info> {
         player.position = PlayerPosition::Big;
         player.last_seen = current_time;
 
-        // Initialize or load psychological profile
-        let mut psych = self.player_psych.load_init()?;
-        if psych.player == Pubkey::default() {
-            psych.player = self.player.key();
-            psych.avg_decision_time = 5000;
-            psych.consistency_rating = 500;
-        }
+        // Ratings are fixed here so handicap terms cannot drift before settlement
+        player.skill_rating = psych.track(duel.queue_type).rating();
+        player.rating_deviation = psych.track(duel.queue_type).deviation();
+        let ratings = [duel.join_ratings[0], player.skill_rating];
+        duel.fix_handicap(ratings);
+
+        duel.replay_state.chip_counts[1] = player.chip_count;
+        duel.replay_state.is_active[1] = true;
+
+        let cosmetics = |loadout: &Option<Account<'info, shared::CosmeticLoadout>>| {
+            loadout.as_ref().map(|l| l.equipped).unwrap_or_default()
+        };
+        emit!(crate::DuelStartedEvent {
+            duel_id: duel.duel_id,
+            player_one: duel.player_one,
+            player_two: duel.player_two,
+            player_one_cosmetics: cosmetics(&self.creator_loadout),
+            player_two_cosmetics: cosmetics(&self.player_loadout),
+            started_at: current_time,
+        });
 
         Ok(())
     }
 }
 
 /// Helper functions
+fn gate_holding(account: &Option<Account<TokenAccount>>) -> Option<(Pubkey, u64)> {
+    account.as_ref().map(|account| (account.mint, account.amount))
+}
+
 fn generate_vrf_seed(duel_id: u64) -> [u8; 32] {
     let mut seed = [0u8; 32];
     let clock = Clock::get().unwrap();
@@ -487,4 +1134,30 @@ pub enum GameError {
     NoWinnerDetermined,
     #[msg("Game already settled")]
     AlreadySettled,
+    #[msg("Latency attestation is not signed by the duel's sequencer")]
+    InvalidLatencyAttestation,
+    #[msg("Invalid blind schedule")]
+    InvalidBlindSchedule,
+    #[msg("Duel is frozen for state migration")]
+    MigrationLocked,
+    #[msg("Player reputation is below the duel's minimum")]
+    ReputationTooLow,
+    #[msg("Both players' profiles are required to settle")]
+    MissingPsychProfile,
+    #[msg("Both players' match history and the pair's head-to-head record are required")]
+    MissingMatchHistory,
+    #[msg("Invalid table tier requirement")]
+    InvalidTableGate,
+    #[msg("Player does not meet the table's holding requirement")]
+    TableGateNotMet,
+    #[msg("Settlement already in progress")]
+    ReentrancyDetected,
+    #[msg("Duel is paused")]
+    DuelPaused,
+    #[msg("Early forfeit refund exceeds the maximum")]
+    InvalidForfeitRefund,
+    #[msg("Wallet does not match the seat being paid")]
+    WrongPayoutWallet,
+    #[msg("The tournament queue is reserved for tournament matches")]
+    TournamentQueueReserved,
 }
\ No newline at end of file
@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+use bolt_lang::*;
+use crate::components::*;
+
+/// Most observers a private duel may grant at once
+pub const MAX_OBSERVERS: usize = 8;
+
+/// SetDuelObservers - A seated player replaces the observer list of a private duel
+#[derive(Accounts)]
+pub struct SetDuelObservers<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// CHECK: Entity for the duel
+    pub entity: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"duel", entity.key().as_ref()],
+        bump = duel.load()?.bump
+    )]
+    pub duel: Account<'info, ComponentData<DuelComponent>>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + std::mem::size_of::<SpectatorState>() + MAX_OBSERVERS * std::mem::size_of::<ObserverGrant>(),
+        seeds = [b"spectator_state", entity.key().as_ref()],
+        bump
+    )]
+    pub spectator_state: Account<'info, ComponentData<SpectatorState>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// One observer granted by a seated player, e.g. that player's coach
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct ObserverGrant {
+    pub observer: Pubkey,
+    pub granted_by: u8, // Seat index
+}
+
+/// SpectatorState - Who may watch a private duel through the lobby and delayed
+/// feeds. Public duels need no entry; anyone may spectate them. This is a
+/// listing control only: the duel's accounts and events stay readable on chain.
+#[component]
+#[derive(Default)]
+pub struct SpectatorState {
+    pub duel_id: u64,
+    pub observers: Vec<ObserverGrant>,
+    pub updated_at: i64,
+}
+
+impl SpectatorState {
+    pub fn can_observe(&self, key: &Pubkey) -> bool {
+        self.observers.iter().any(|grant| grant.observer == *key)
+    }
+
+    /// Swap in `seat`'s observers, leaving the other seat's grants untouched
+    pub fn set_seat_observers(&mut self, seat: u8, observers: &[Pubkey]) -> Result<()> {
+        self.observers.retain(|grant| grant.granted_by != seat);
+        for observer in observers {
+            if !self.can_observe(observer) {
+                self.observers.push(ObserverGrant { observer: *observer, granted_by: seat });
+            }
+        }
+        require!(self.observers.len() <= MAX_OBSERVERS, ObserverError::TooManyObservers);
+        Ok(())
+    }
+}
+
+/// Whether `viewer` may watch the duel through the lobby or a delayed feed.
+/// Private duels need a SpectatorState listing the viewer; seated players can
+/// always see their own table. Nothing stops a direct read of the accounts.
+pub fn may_spectate(duel: &DuelComponent, state: Option<&SpectatorState>, viewer: &Pubkey) -> bool {
+    !duel.is_private
+        || duel.seat_of(viewer).is_some()
+        || state.map_or(false, |state| state.duel_id == duel.duel_id && state.can_observe(viewer))
+}
+
+impl<'info> SetDuelObservers<'info> {
+    pub fn process(&mut self, observers: Vec<Pubkey>) -> Result<()> {
+        let duel = self.duel.load()?;
+        require!(duel.is_private, ObserverError::DuelNotPrivate);
+        require!(
+            !matches!(duel.game_state, GameState::Completed | GameState::Cancelled),
+            ObserverError::DuelFinished
+        );
+        let player = self.player.key();
+        let seat = duel.seat_of(&player).ok_or(ObserverError::NotSeated)?;
+
+        let mut state = self.spectator_state.load_mut()?;
+        state.duel_id = duel.duel_id;
+        state.set_seat_observers(seat, &observers)?;
+        state.updated_at = Clock::get()?.unix_timestamp;
+
+        emit!(DuelObserversUpdatedEvent {
+            duel_id: duel.duel_id,
+            granted_by: player,
+            observers,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct DuelObserversUpdatedEvent {
+    pub duel_id: u64,
+    pub granted_by: Pubkey,
+    pub observers: Vec<Pubkey>,
+}
+
+#[error_code]
+pub enum ObserverError {
+    #[msg("Observer lists only apply to private duels")]
+    DuelNotPrivate,
+    #[msg("Only a seated player can grant observers")]
+    NotSeated,
+    #[msg("Duel is already over")]
+    DuelFinished,
+    #[msg("Too many observers granted for this duel")]
+    TooManyObservers,
+    #[msg("Private duel; viewer has not been granted access")]
+    NotAnObserver,
+}
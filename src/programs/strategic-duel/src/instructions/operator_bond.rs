@@ -0,0 +1,274 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use bolt_lang::*;
+use crate::components::*;
+use crate::instructions::ephemeral_rollup::*;
+
+/// Minimum bond an operator must hold for `delegate_to_rollup` to accept its rollup
+pub const MIN_OPERATOR_BOND: u64 = 1_000_000_000; // 1 SOL
+
+/// PostOperatorBond - Create or top up a rollup operator's bond
+#[derive(Accounts)]
+pub struct PostOperatorBond<'info> {
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = operator,
+        space = 8 + std::mem::size_of::<OperatorBond>(),
+        seeds = [b"operator_bond", operator.key().as_ref()],
+        bump
+    )]
+    pub operator_bond: Account<'info, ComponentData<OperatorBond>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// WithdrawOperatorBond - Reclaim bond once no rollups are outstanding
+#[derive(Accounts)]
+pub struct WithdrawOperatorBond<'info> {
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"operator_bond", operator.key().as_ref()],
+        bump
+    )]
+    pub operator_bond: Account<'info, ComponentData<OperatorBond>>,
+}
+
+/// SlashOperatorBond - Permissionless crank slashing a faulty operator
+#[derive(Accounts)]
+pub struct SlashOperatorBond<'info> {
+    pub cranker: Signer<'info>,
+
+    /// CHECK: Entity for the duel
+    pub entity: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"duel", entity.key().as_ref()],
+        bump = duel.load()?.bump
+    )]
+    pub duel: Account<'info, ComponentData<DuelComponent>>,
+
+    #[account(
+        mut,
+        seeds = [b"ephemeral_rollup", entity.key().as_ref()],
+        bump
+    )]
+    pub ephemeral_rollup: Account<'info, ComponentData<EphemeralRollupComponent>>,
+
+    #[account(
+        seeds = [b"state_transition", entity.key().as_ref()],
+        bump
+    )]
+    pub state_transition: Account<'info, ComponentData<StateTransitionComponent>>,
+
+    #[account(
+        mut,
+        seeds = [b"operator_bond", ephemeral_rollup.load()?.validator_pubkey.as_ref()],
+        bump
+    )]
+    pub operator_bond: Account<'info, ComponentData<OperatorBond>>,
+
+    #[account(
+        seeds = [b"player", duel.load()?.player_one.as_ref(), entity.key().as_ref()],
+        bump = player_one.load()?.bump
+    )]
+    pub player_one: Account<'info, ComponentData<PlayerComponent>>,
+
+    #[account(
+        seeds = [b"player", duel.load()?.player_two.as_ref(), entity.key().as_ref()],
+        bump = player_two.load()?.bump
+    )]
+    pub player_two: Account<'info, ComponentData<PlayerComponent>>,
+
+    /// CHECK: Receives player one's share; must be the duel's player one
+    #[account(
+        mut,
+        address = duel.load()?.player_one @ BondError::InvalidPayee,
+        constraint = shared::is_wallet(&player_one_wallet) @ shared::GameError::InvalidAccountOwner
+    )]
+    pub player_one_wallet: AccountInfo<'info>,
+
+    /// CHECK: Receives player two's share; must be the duel's player two
+    #[account(
+        mut,
+        address = duel.load()?.player_two @ BondError::InvalidPayee,
+        constraint = shared::is_wallet(&player_two_wallet) @ shared::GameError::InvalidAccountOwner
+    )]
+    pub player_two_wallet: AccountInfo<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum SlashReason {
+    FraudProven,
+    FailedToFinalize,
+}
+
+/// OperatorBond - Lamports a rollup operator puts at stake for honest finalization
+#[component]
+#[derive(Default)]
+pub struct OperatorBond {
+    pub operator: Pubkey,
+    pub amount: u64,
+    pub active_rollups: u32,
+    pub slash_count: u32,
+    pub slashed_total: u64,
+    pub last_slashed_at: i64,
+}
+
+impl OperatorBond {
+    pub fn can_accept_rollup(&self) -> bool {
+        self.amount >= MIN_OPERATOR_BOND
+    }
+
+    /// Amount taken per fault: the minimum bond, or whatever is left
+    pub fn slash_amount(&self) -> u64 {
+        self.amount.min(MIN_OPERATOR_BOND)
+    }
+}
+
+/// Split `amount` between the players in proportion to what they put into the pot
+pub fn pro_rata_split(amount: u64, stake_one: u64, stake_two: u64) -> (u64, u64) {
+    let total = stake_one as u128 + stake_two as u128;
+    let share_one = if total == 0 {
+        amount / 2
+    } else {
+        (amount as u128 * stake_one as u128 / total) as u64
+    };
+    (share_one, amount - share_one)
+}
+
+impl<'info> PostOperatorBond<'info> {
+    pub fn process(&mut self, amount: u64) -> Result<()> {
+        system_program::transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: self.operator.to_account_info(),
+                    to: self.operator_bond.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let mut bond = self.operator_bond.load_mut()?;
+        bond.operator = self.operator.key();
+        bond.amount += amount;
+
+        emit!(OperatorBondPostedEvent {
+            operator: bond.operator,
+            amount,
+            total_bond: bond.amount,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> WithdrawOperatorBond<'info> {
+    pub fn process(&mut self, amount: u64) -> Result<()> {
+        {
+            let mut bond = self.operator_bond.load_mut()?;
+            require!(bond.active_rollups == 0, BondError::RollupsOutstanding);
+            require!(amount <= bond.amount, BondError::InsufficientBond);
+            bond.amount -= amount;
+        }
+
+        // The bond is owned by this program, so lamports can be moved directly
+        **self.operator_bond.to_account_info().try_borrow_mut_lamports()? -= amount;
+        **self.operator.to_account_info().try_borrow_mut_lamports()? += amount;
+
+        Ok(())
+    }
+}
+
+impl<'info> SlashOperatorBond<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let (share_one, share_two, reason, duel_id, rollup_id) = {
+            let mut rollup = self.ephemeral_rollup.load_mut()?;
+            let transition = self.state_transition.load()?;
+            let mut bond = self.operator_bond.load_mut()?;
+
+            require!(
+                rollup.rollup_status != RollupStatus::Finalized && rollup.rollup_status != RollupStatus::Disputed,
+                BondError::NotSlashable
+            );
+            let reason = if transition.is_disputed {
+                SlashReason::FraudProven
+            } else if current_time > rollup.expiration_timestamp {
+                SlashReason::FailedToFinalize
+            } else {
+                return Err(BondError::NotSlashable.into());
+            };
+
+            let slashed = bond.slash_amount();
+            bond.amount -= slashed;
+            bond.active_rollups = bond.active_rollups.saturating_sub(1);
+            bond.slash_count += 1;
+            bond.slashed_total += slashed;
+            bond.last_slashed_at = current_time;
+
+            rollup.rollup_status = RollupStatus::Disputed;
+            rollup.is_active = false;
+
+            let (share_one, share_two) = pro_rata_split(
+                slashed,
+                self.player_one.load()?.total_bet,
+                self.player_two.load()?.total_bet,
+            );
+            (share_one, share_two, reason, rollup.duel_id, rollup.rollup_id)
+        };
+
+        let bond_info = self.operator_bond.to_account_info();
+        **bond_info.try_borrow_mut_lamports()? -= share_one + share_two;
+        **self.player_one_wallet.try_borrow_mut_lamports()? += share_one;
+        **self.player_two_wallet.try_borrow_mut_lamports()? += share_two;
+
+        emit!(OperatorSlashedEvent {
+            duel_id,
+            rollup_id,
+            operator: self.operator_bond.load()?.operator,
+            reason,
+            amount: share_one + share_two,
+            player_one_share: share_one,
+            player_two_share: share_two,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct OperatorBondPostedEvent {
+    pub operator: Pubkey,
+    pub amount: u64,
+    pub total_bond: u64,
+}
+
+#[event]
+pub struct OperatorSlashedEvent {
+    pub duel_id: u64,
+    pub rollup_id: [u8; 32],
+    pub operator: Pubkey,
+    pub reason: SlashReason,
+    pub amount: u64,
+    pub player_one_share: u64,
+    pub player_two_share: u64,
+}
+
+#[error_code]
+pub enum BondError {
+    #[msg("Operator bond is below the required minimum")]
+    InsufficientBond,
+    #[msg("Operator still has rollups outstanding")]
+    RollupsOutstanding,
+    #[msg("Rollup has no proven fault to slash")]
+    NotSlashable,
+    #[msg("Payee does not match the duel's player")]
+    InvalidPayee,
+}
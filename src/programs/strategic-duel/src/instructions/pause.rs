@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+use bolt_lang::*;
+use crate::components::*;
+
+/// Longest break the players may agree on
+pub const MAX_PAUSE_DURATION: i64 = 600;
+
+/// ProposePause - Ask the opponent to freeze the action timer for a break
+#[derive(Accounts)]
+pub struct ProposePause<'info> {
+    pub player_signer: Signer<'info>,
+
+    /// CHECK: Entity for the duel
+    pub entity: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"duel", entity.key().as_ref()],
+        bump = duel.load()?.bump
+    )]
+    pub duel: Account<'info, ComponentData<DuelComponent>>,
+}
+
+/// AcceptPause - The opponent agrees and the pause starts
+#[derive(Accounts)]
+pub struct AcceptPause<'info> {
+    pub player_signer: Signer<'info>,
+
+    /// CHECK: Entity for the duel
+    pub entity: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"duel", entity.key().as_ref()],
+        bump = duel.load()?.bump
+    )]
+    pub duel: Account<'info, ComponentData<DuelComponent>>,
+}
+
+fn require_pausable(duel: &DuelComponent, current_time: i64) -> Result<()> {
+    require!(
+        duel.game_state == GameState::InProgress || duel.game_state == GameState::AwaitingAction,
+        PauseError::InvalidGameState
+    );
+    require!(!duel.pause_used, PauseError::PauseAlreadyUsed);
+    require!(!duel.is_timeout_exceeded(current_time), PauseError::TimerExpired);
+    Ok(())
+}
+
+impl<'info> ProposePause<'info> {
+    pub fn process(&mut self, duration: i64) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let mut duel = self.duel.load_mut()?;
+
+        let seat = duel.seat_of(&self.player_signer.key()).ok_or(PauseError::NotAPlayer)?;
+        require_pausable(&duel, current_time)?;
+        require!(duration > 0 && duration <= MAX_PAUSE_DURATION, PauseError::InvalidDuration);
+
+        // A fresh proposal from either seat replaces any standing one
+        duel.pause_proposer = Some(seat);
+        duel.pause_proposed_duration = duration;
+
+        emit!(PauseProposedEvent {
+            duel_id: duel.duel_id,
+            proposer: self.player_signer.key(),
+            duration,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> AcceptPause<'info> {
+    pub fn process(&mut self, expected_duration: i64) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let mut duel = self.duel.load_mut()?;
+
+        let seat = duel.seat_of(&self.player_signer.key()).ok_or(PauseError::NotAPlayer)?;
+        require_pausable(&duel, current_time)?;
+        let proposer = duel.pause_proposer.ok_or(PauseError::NoProposal)?;
+        require!(proposer != seat, PauseError::CannotAcceptOwnProposal);
+        // A proposal replaced after the accept was signed must not be accepted in its place
+        require!(duel.pause_proposed_duration == expected_duration, PauseError::ProposalChanged);
+
+        // Freeze the timer by pushing the deadline out; play resumes on its
+        // own once `paused_until` passes
+        let duration = duel.pause_proposed_duration;
+        duel.grace_extension += duration;
+        duel.paused_until = current_time + duration;
+        duel.pause_used = true;
+        duel.pause_proposer = None;
+        duel.pause_proposed_duration = 0;
+
+        emit!(PauseStartedEvent {
+            duel_id: duel.duel_id,
+            duration,
+            resumes_at: duel.paused_until,
+            new_deadline: duel.last_action_time + duel.timeout_duration + duel.grace_extension,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct PauseProposedEvent {
+    pub duel_id: u64,
+    pub proposer: Pubkey,
+    pub duration: i64,
+}
+
+#[event]
+pub struct PauseStartedEvent {
+    pub duel_id: u64,
+    pub duration: i64,
+    pub resumes_at: i64,
+    pub new_deadline: i64,
+}
+
+#[error_code]
+pub enum PauseError {
+    #[msg("A pause is not possible in the current game state")]
+    InvalidGameState,
+    #[msg("Only the duel's players can pause it")]
+    NotAPlayer,
+    #[msg("This duel has already used its pause")]
+    PauseAlreadyUsed,
+    #[msg("Action timer already expired")]
+    TimerExpired,
+    #[msg("Pause duration is out of range")]
+    InvalidDuration,
+    #[msg("No pause has been proposed")]
+    NoProposal,
+    #[msg("The opponent must accept the pause")]
+    CannotAcceptOwnProposal,
+    #[msg("The pause proposal changed since it was accepted")]
+    ProposalChanged,
+}
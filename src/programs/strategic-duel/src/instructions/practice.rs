@@ -0,0 +1,348 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use bolt_lang::*;
+use crate::components::*;
+
+/// CreatePracticeDuel - Start a stake-free duel against a scripted bot opponent
+#[derive(Accounts)]
+pub struct CreatePracticeDuel<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// CHECK: World PDA
+    #[account(mut)]
+    pub world: AccountInfo<'info>,
+
+    /// CHECK: Entity for the duel
+    #[account(mut)]
+    pub entity: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + std::mem::size_of::<DuelComponent>(),
+        seeds = [b"duel", entity.key().as_ref()],
+        bump
+    )]
+    pub duel: Account<'info, ComponentData<DuelComponent>>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + std::mem::size_of::<BettingComponent>() + MAX_BLIND_LEVELS * std::mem::size_of::<BlindLevel>(),
+        seeds = [b"betting", entity.key().as_ref()],
+        bump
+    )]
+    pub betting: Account<'info, ComponentData<BettingComponent>>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + std::mem::size_of::<PlayerComponent>(),
+        seeds = [b"player", creator.key().as_ref(), entity.key().as_ref()],
+        bump
+    )]
+    pub creator_player: Account<'info, ComponentData<PlayerComponent>>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + std::mem::size_of::<BotConfig>(),
+        seeds = [b"bot_config", entity.key().as_ref()],
+        bump
+    )]
+    pub bot_config: Account<'info, ComponentData<BotConfig>>,
+
+    /// The bot plays as the bot config PDA
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + std::mem::size_of::<PlayerComponent>(),
+        seeds = [b"player", bot_config.key().as_ref(), entity.key().as_ref()],
+        bump
+    )]
+    pub bot_player: Account<'info, ComponentData<PlayerComponent>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// AdvanceBot - Permissionless crank that plays the bot's move
+#[derive(Accounts)]
+pub struct AdvanceBot<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    /// CHECK: Entity for the duel
+    pub entity: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"duel", entity.key().as_ref()],
+        bump = duel.load()?.bump
+    )]
+    pub duel: Account<'info, ComponentData<DuelComponent>>,
+
+    #[account(
+        mut,
+        seeds = [b"betting", entity.key().as_ref()],
+        bump = betting.load()?.bump
+    )]
+    pub betting: Account<'info, ComponentData<BettingComponent>>,
+
+    #[account(
+        mut,
+        seeds = [b"bot_config", entity.key().as_ref()],
+        bump
+    )]
+    pub bot_config: Account<'info, ComponentData<BotConfig>>,
+
+    #[account(
+        mut,
+        seeds = [b"player", bot_config.key().as_ref(), entity.key().as_ref()],
+        bump = bot_player.load()?.bump
+    )]
+    pub bot_player: Account<'info, ComponentData<PlayerComponent>>,
+
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = 8 + std::mem::size_of::<ReplayChunk>() + MAX_REPLAY_ENTRIES_PER_CHUNK * std::mem::size_of::<ReplayEntry>(),
+        seeds = [b"replay", entity.key().as_ref(), &duel.load()?.replay_chunk_count.to_le_bytes()],
+        bump
+    )]
+    pub replay_chunk: Account<'info, ComponentData<ReplayChunk>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// BotConfig - Deterministic policy parameters for a practice opponent
+#[component]
+#[derive(Default)]
+pub struct BotConfig {
+    pub duel_id: u64,
+    pub creator: Pubkey,
+    pub aggression: u16,     // 0-1000, chance to raise with a strong hand
+    pub call_threshold: u16, // 0-1000, minimum strength to continue facing a bet
+    pub actions_taken: u16,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct PracticeDuelParams {
+    pub max_rounds: u8,
+    pub timeout_duration: i64,
+    pub aggression: u16,
+    pub call_threshold: u16,
+}
+
+impl BotConfig {
+    /// Pseudo hand strength in 0..1000, fixed by the duel seed so runs are reproducible
+    pub fn hand_strength(&self, vrf_seed: &[u8; 32], round: u8) -> u16 {
+        let hash = hashv(&[vrf_seed, &[round], &self.actions_taken.to_le_bytes()]).to_bytes();
+        (u16::from_le_bytes([hash[0], hash[1]]) % 1000) as u16
+    }
+
+    /// Choose the bot's action given the amount it must call
+    pub fn decide(&self, strength: u16, to_call: u64, can_raise: bool) -> ActionType {
+        let wants_raise = can_raise && strength >= 1000u16.saturating_sub(self.aggression);
+        if to_call == 0 {
+            if wants_raise { ActionType::Raise } else { ActionType::Check }
+        } else if strength >= self.call_threshold {
+            if wants_raise { ActionType::Raise } else { ActionType::Call }
+        } else {
+            ActionType::Fold
+        }
+    }
+}
+
+impl<'info> CreatePracticeDuel<'info> {
+    pub fn process(&mut self, params: PracticeDuelParams, bumps: &CreatePracticeDuelBumps) -> Result<()> {
+        let clock = Clock::get()?;
+        let current_time = clock.unix_timestamp;
+        let duel_id = clock.unix_timestamp as u64;
+        let bot_key = self.bot_config.key();
+
+        let mut bot = self.bot_config.load_init()?;
+        bot.duel_id = duel_id;
+        bot.creator = self.creator.key();
+        bot.aggression = params.aggression;
+        bot.call_threshold = params.call_threshold;
+
+        // Practice duels carry no stakes: no entry fee and no rake
+        let mut betting = self.betting.load_init()?;
+        betting.bump = bumps.betting;
+        betting.duel_id = duel_id;
+        betting.min_bet = crate::MIN_BET;
+        betting.max_bet = crate::MAX_BET;
+
+        let mut player = self.creator_player.load_init()?;
+        player.bump = bumps.creator_player;
+        player.player_id = self.creator.key();
+        player.duel_id = duel_id;
+        player.chip_count = crate::STARTING_CHIPS;
+        player.is_active = true;
+        player.position = PlayerPosition::Small;
+        player.last_seen = current_time;
+
+        let mut bot_player = self.bot_player.load_init()?;
+        bot_player.bump = bumps.bot_player;
+        bot_player.player_id = bot_key;
+        bot_player.duel_id = duel_id;
+        bot_player.chip_count = crate::STARTING_CHIPS;
+        bot_player.is_active = true;
+        bot_player.position = PlayerPosition::Big;
+        bot_player.last_seen = current_time;
+
+        let mut duel = self.duel.load_init()?;
+        duel.bump = bumps.duel;
+        duel.duel_id = duel_id;
+        duel.player_one = self.creator.key();
+        duel.player_two = bot_key;
+        duel.max_rounds = params.max_rounds;
+        // The bot takes the second seat immediately
+        require!(duel.apply_transition(DuelTrigger::Join), PracticeError::NotPracticeDuel);
+        duel.start_time = current_time;
+        duel.last_action_time = current_time;
+        duel.timeout_duration = params.timeout_duration;
+        duel.is_practice = true;
+        // Seat the bot as having acted last, so the human opens and the bot only ever answers
+        duel.last_actor = bot_key;
+        duel.vrf_seed = hashv(&[&duel_id.to_le_bytes(), self.entity.key().as_ref()]).to_bytes();
+        duel.replay_state = ReplayState {
+            chip_counts: [player.chip_count, bot_player.chip_count],
+            is_active: [true, true],
+            ..ReplayState::default()
+        };
+
+        emit!(PracticeDuelCreatedEvent {
+            duel_id,
+            player: self.creator.key(),
+            bot: bot_key,
+            aggression: params.aggression,
+            call_threshold: params.call_threshold,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> AdvanceBot<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let clock = Clock::get()?;
+        let current_time = clock.unix_timestamp;
+        let bot_key = self.bot_config.key();
+
+        let mut duel = self.duel.load_mut()?;
+        let mut betting = self.betting.load_mut()?;
+        let mut bot_player = self.bot_player.load_mut()?;
+        let mut bot = self.bot_config.load_mut()?;
+
+        require!(duel.is_practice, PracticeError::NotPracticeDuel);
+        require!(!duel.migration_locked, crate::GameError::MigrationLocked);
+        require!(duel.game_state == GameState::AwaitingAction, PracticeError::NotBotTurn);
+        require!(bot_player.is_active, PracticeError::NotBotTurn);
+        // The bot answers the human; it never acts twice in a row
+        require!(duel.last_actor != bot_key, PracticeError::NotBotTurn);
+
+        let to_call = betting.current_bet.saturating_sub(bot_player.total_bet);
+        let raise_amount = betting.min_raise();
+        let can_raise = betting.can_raise(bot_player.chip_count, raise_amount)
+            && betting.is_legal_raise(raise_amount, to_call)
+            && bot_player.can_bet(to_call + raise_amount);
+        let strength = bot.hand_strength(&duel.vrf_seed, duel.current_round);
+        let mut action_type = bot.decide(strength, to_call, can_raise);
+
+        // Short-stacked bots call with whatever they have left
+        if action_type == ActionType::Call && !bot_player.can_bet(to_call) {
+            action_type = ActionType::AllIn;
+        }
+
+        let bet_amount = match action_type {
+            ActionType::Call => {
+                bot_player.chip_count -= to_call;
+                bot_player.total_bet += to_call;
+                betting.add_to_pot(to_call);
+                0
+            },
+            ActionType::Raise => {
+                let total_required = betting.current_bet + raise_amount;
+                let additional_bet = total_required.saturating_sub(bot_player.total_bet);
+                bot_player.chip_count -= additional_bet;
+                bot_player.total_bet = total_required;
+                betting.current_bet = total_required;
+                betting.register_raise(raise_amount);
+                betting.add_to_pot(additional_bet);
+                raise_amount
+            },
+            ActionType::AllIn => {
+                let all_in_amount = bot_player.chip_count;
+                bot_player.chip_count = 0;
+                bot_player.total_bet += all_in_amount;
+                betting.register_all_in(bot_player.total_bet);
+                betting.add_to_pot(all_in_amount);
+                0
+            },
+            ActionType::Fold => {
+                bot_player.is_active = false;
+                require!(duel.apply_transition(DuelTrigger::Fold), PracticeError::NotBotTurn);
+                duel.request_resolution(current_time);
+                0
+            },
+            _ => 0,
+        };
+
+        let mut replay_chunk = self.replay_chunk.load_mut()?;
+        record_replay_entry(&mut duel, &mut replay_chunk, ReplayEntry {
+            seat: 1,
+            action_type,
+            bet_amount,
+            round_number: duel.current_round,
+            timestamp: current_time,
+        })?;
+
+        bot.actions_taken += 1;
+        bot_player.actions_taken += 1;
+        bot_player.last_seen = current_time;
+        duel.last_action_time = current_time;
+        duel.last_actor = bot_key;
+
+        emit!(BotActionEvent {
+            duel_id: duel.duel_id,
+            bot: bot_key,
+            action_type,
+            amount: bet_amount,
+            hand_strength: strength,
+            pot_total: betting.total_pot,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct PracticeDuelCreatedEvent {
+    pub duel_id: u64,
+    pub player: Pubkey,
+    pub bot: Pubkey,
+    pub aggression: u16,
+    pub call_threshold: u16,
+}
+
+#[event]
+pub struct BotActionEvent {
+    pub duel_id: u64,
+    pub bot: Pubkey,
+    pub action_type: ActionType,
+    pub amount: u64,
+    pub hand_strength: u16,
+    pub pot_total: u64,
+}
+
+#[error_code]
+pub enum PracticeError {
+    #[msg("Duel is not a practice duel")]
+    NotPracticeDuel,
+    #[msg("It is not the bot's turn")]
+    NotBotTurn,
+    #[msg("Invalid bot parameters")]
+    InvalidBotParams,
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+/// UpdateProgramInfo - Upgrade authority records the deployed build after an upgrade
+#[derive(Accounts)]
+pub struct UpdateProgramInfo<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::StrategicDuel>,
+
+    #[account(constraint = program_data.upgrade_authority_address == Some(authority.key()) @ ProgramInfoError::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = shared::ProgramInfo::LEN,
+        seeds = [b"program_info"],
+        bump
+    )]
+    pub program_info: Account<'info, shared::ProgramInfo>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> UpdateProgramInfo<'info> {
+    pub fn process(&mut self, version: [u16; 3], git_commit: [u8; 20], idl_hash: [u8; 32], bump: u8) -> Result<()> {
+        let clock = Clock::get()?;
+        let deployed_slot = self.program_data.slot;
+        let info = &mut self.program_info;
+        // A stale upgrade must not overwrite the record of a newer one
+        require!(deployed_slot >= info.deployed_slot, ProgramInfoError::StaleDeployment);
+
+        info.program_id = crate::ID;
+        info.version = version;
+        info.git_commit = git_commit;
+        info.idl_hash = idl_hash;
+        info.deployed_slot = deployed_slot;
+        info.updated_by = self.authority.key();
+        info.updated_at = clock.unix_timestamp;
+        info.bump = bump;
+
+        emit!(ProgramInfoUpdatedEvent {
+            version,
+            git_commit,
+            idl_hash,
+            deployed_slot,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct ProgramInfoUpdatedEvent {
+    pub version: [u16; 3],
+    pub git_commit: [u8; 20],
+    pub idl_hash: [u8; 32],
+    pub deployed_slot: u64,
+}
+
+#[error_code]
+pub enum ProgramInfoError {
+    #[msg("Only the program's upgrade authority can update its info")]
+    Unauthorized,
+    #[msg("Program info already describes a newer deployment")]
+    StaleDeployment,
+}
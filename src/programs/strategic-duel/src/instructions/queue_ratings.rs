@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+use bolt_lang::*;
+use crate::components::*;
+use super::admin_config::AdminConfigError;
+
+/// InitializeQueueRatings - Choose the rating system each queue is rated under; super admin only
+#[derive(Accounts)]
+pub struct InitializeQueueRatings<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [shared::ADMIN_CONFIG_SEED],
+        bump = admin_config.bump,
+        constraint = admin_config.super_admin == authority.key() @ AdminConfigError::NotSuperAdmin
+    )]
+    pub admin_config: Account<'info, shared::AdminConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<QueueRatingConfig>(),
+        seeds = [b"queue_ratings"],
+        bump
+    )]
+    pub queue_ratings: Account<'info, ComponentData<QueueRatingConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// UpdateQueueRatings - Move a queue to another rating system
+#[derive(Accounts)]
+pub struct UpdateQueueRatings<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"queue_ratings"],
+        bump
+    )]
+    pub queue_ratings: Account<'info, ComponentData<QueueRatingConfig>>,
+}
+
+/// QueueRatingConfig - Governance-set rating system per queue, so every duel
+/// in a queue is rated the same way rather than as its creator picked
+#[component]
+#[derive(Default)]
+pub struct QueueRatingConfig {
+    pub authority: Pubkey,
+    // Indexed by QueueType
+    pub systems: [RatingSystem; shared::QUEUE_TYPES],
+    pub updated_at: i64,
+}
+
+impl QueueRatingConfig {
+    pub fn system_for(&self, queue: QueueType) -> RatingSystem {
+        self.systems[queue.index()]
+    }
+
+    fn apply(&mut self, systems: [RatingSystem; shared::QUEUE_TYPES]) -> Result<()> {
+        self.systems = systems;
+        self.updated_at = Clock::get()?.unix_timestamp;
+
+        emit!(QueueRatingsUpdatedEvent {
+            authority: self.authority,
+            systems,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> InitializeQueueRatings<'info> {
+    pub fn process(&mut self, systems: [RatingSystem; shared::QUEUE_TYPES]) -> Result<()> {
+        let mut config = self.queue_ratings.load_init()?;
+        config.authority = self.authority.key();
+        config.apply(systems)
+    }
+}
+
+impl<'info> UpdateQueueRatings<'info> {
+    pub fn process(&mut self, systems: [RatingSystem; shared::QUEUE_TYPES]) -> Result<()> {
+        let mut config = self.queue_ratings.load_mut()?;
+        require_keys_eq!(config.authority, self.authority.key(), QueueRatingError::Unauthorized);
+        config.apply(systems)
+    }
+}
+
+#[event]
+pub struct QueueRatingsUpdatedEvent {
+    pub authority: Pubkey,
+    pub systems: [RatingSystem; shared::QUEUE_TYPES],
+}
+
+#[error_code]
+pub enum QueueRatingError {
+    #[msg("Only the queue ratings authority can update it")]
+    Unauthorized,
+}
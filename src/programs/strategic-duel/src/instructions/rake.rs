@@ -0,0 +1,151 @@
+use anchor_lang::prelude::*;
+use bolt_lang::*;
+use super::admin_config::AdminConfigError;
+
+/// Most pot-size tiers a rake schedule may define
+pub const MAX_RAKE_TIERS: usize = 8;
+
+/// Highest rake any tier may charge
+pub const MAX_RAKE_BPS: u16 = 1_000; // 10%
+
+/// InitializeRakeSchedule - Configure the settlement rake schedule; super admin only
+#[derive(Accounts)]
+pub struct InitializeRakeSchedule<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [shared::ADMIN_CONFIG_SEED],
+        bump = admin_config.bump,
+        constraint = admin_config.super_admin == authority.key() @ AdminConfigError::NotSuperAdmin
+    )]
+    pub admin_config: Account<'info, shared::AdminConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<RakeSchedule>() + MAX_RAKE_TIERS * std::mem::size_of::<RakeTier>(),
+        seeds = [b"rake_schedule"],
+        bump
+    )]
+    pub rake_schedule: Account<'info, ComponentData<RakeSchedule>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// UpdateRakeSchedule - Replace the tiers, cap, no-flop-no-drop rule or treasury
+#[derive(Accounts)]
+pub struct UpdateRakeSchedule<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"rake_schedule"],
+        bump
+    )]
+    pub rake_schedule: Account<'info, ComponentData<RakeSchedule>>,
+}
+
+/// One rake tier: pots of at least `min_pot` are raked at `rake_bps`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct RakeTier {
+    pub min_pot: u64,
+    pub rake_bps: u16,
+}
+
+/// RakeSchedule - Governance-set rake applied on both settlement paths
+#[component]
+#[derive(Default)]
+pub struct RakeSchedule {
+    pub authority: Pubkey,
+    // Only account either settlement path pays rake into
+    pub treasury: Pubkey,
+    pub tiers: Vec<RakeTier>, // Ascending by min_pot
+    pub max_rake_per_pot: u64, // 0 = uncapped
+    // No rake on duels that end in the first round
+    pub no_flop_no_drop: bool,
+    pub updated_at: i64,
+}
+
+impl RakeSchedule {
+    /// Tiers must be non-empty, strictly ascending by pot size and within the bps ceiling
+    pub fn validate_tiers(tiers: &[RakeTier]) -> bool {
+        !tiers.is_empty()
+            && tiers.len() <= MAX_RAKE_TIERS
+            && tiers.iter().all(|tier| tier.rake_bps <= MAX_RAKE_BPS)
+            && tiers.windows(2).all(|pair| pair[0].min_pot < pair[1].min_pot)
+    }
+
+    /// Rake on `pot` for a duel that ended in `final_round` (0-based)
+    pub fn rake_for(&self, pot: u64, final_round: u8) -> u64 {
+        if self.no_flop_no_drop && final_round == 0 {
+            return 0;
+        }
+        let bps = self.tiers
+            .iter()
+            .rev()
+            .find(|tier| pot >= tier.min_pot)
+            .map_or(0, |tier| tier.rake_bps);
+        let rake = (pot as u128 * bps as u128 / 10_000) as u64;
+        if self.max_rake_per_pot > 0 {
+            rake.min(self.max_rake_per_pot)
+        } else {
+            rake
+        }
+    }
+
+    fn apply(&mut self, treasury: Pubkey, tiers: Vec<RakeTier>, max_rake_per_pot: u64, no_flop_no_drop: bool) -> Result<()> {
+        require!(Self::validate_tiers(&tiers), RakeError::InvalidTiers);
+        require!(treasury != Pubkey::default(), RakeError::WrongTreasury);
+        self.treasury = treasury;
+        self.tiers = tiers;
+        self.max_rake_per_pot = max_rake_per_pot;
+        self.no_flop_no_drop = no_flop_no_drop;
+        self.updated_at = Clock::get()?.unix_timestamp;
+
+        emit!(RakeScheduleUpdatedEvent {
+            authority: self.authority,
+            treasury,
+            tiers: self.tiers.clone(),
+            max_rake_per_pot,
+            no_flop_no_drop,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> InitializeRakeSchedule<'info> {
+    pub fn process(&mut self, treasury: Pubkey, tiers: Vec<RakeTier>, max_rake_per_pot: u64, no_flop_no_drop: bool) -> Result<()> {
+        let mut schedule = self.rake_schedule.load_init()?;
+        schedule.authority = self.authority.key();
+        schedule.apply(treasury, tiers, max_rake_per_pot, no_flop_no_drop)
+    }
+}
+
+impl<'info> UpdateRakeSchedule<'info> {
+    pub fn process(&mut self, treasury: Pubkey, tiers: Vec<RakeTier>, max_rake_per_pot: u64, no_flop_no_drop: bool) -> Result<()> {
+        let mut schedule = self.rake_schedule.load_mut()?;
+        require_keys_eq!(schedule.authority, self.authority.key(), RakeError::Unauthorized);
+        schedule.apply(treasury, tiers, max_rake_per_pot, no_flop_no_drop)
+    }
+}
+
+#[event]
+pub struct RakeScheduleUpdatedEvent {
+    pub authority: Pubkey,
+    pub treasury: Pubkey,
+    pub tiers: Vec<RakeTier>,
+    pub max_rake_per_pot: u64,
+    pub no_flop_no_drop: bool,
+}
+
+#[error_code]
+pub enum RakeError {
+    #[msg("Rake tiers must be ascending by pot size and within the rake ceiling")]
+    InvalidTiers,
+    #[msg("Only the schedule authority can update the rake")]
+    Unauthorized,
+    #[msg("Treasury does not match the rake schedule")]
+    WrongTreasury,
+}
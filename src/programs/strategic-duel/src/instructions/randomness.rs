@@ -0,0 +1,158 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use bolt_lang::*;
+use crate::components::*;
+use super::admin_config::AdminConfigError;
+
+pub const RANDOMNESS_PROVIDER_COUNT: usize = 3;
+
+/// Switchboard On-Demand program; owns every randomness account it reveals
+pub const SWITCHBOARD_ON_DEMAND_ID: Pubkey = pubkey!("SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv");
+
+// RandomnessAccountData layout after the 8-byte discriminator
+const SWITCHBOARD_AUTHORITY_OFFSET: usize = 8;
+const SWITCHBOARD_REVEAL_SLOT_OFFSET: usize = 144;
+const SWITCHBOARD_VALUE_OFFSET: usize = 152;
+const SWITCHBOARD_ACCOUNT_MIN_LEN: usize = SWITCHBOARD_VALUE_OFFSET + 32;
+
+/// ConfigureRandomnessProvider - Set the oracle for a provider and enable or
+/// disable it; super admin only
+#[derive(Accounts)]
+pub struct ConfigureRandomnessProvider<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [shared::ADMIN_CONFIG_SEED],
+        bump = admin_config.bump,
+        constraint = admin_config.super_admin == authority.key() @ AdminConfigError::NotSuperAdmin
+    )]
+    pub admin_config: Account<'info, shared::AdminConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + std::mem::size_of::<RandomnessRegistry>(),
+        seeds = [b"randomness_registry"],
+        bump
+    )]
+    pub randomness_registry: Account<'info, ComponentData<RandomnessRegistry>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct ProviderEntry {
+    pub oracle: Pubkey, // Signer whose proofs are accepted, and for Switchboard the randomness account authority; unused for CommitReveal
+    pub enabled: bool,
+}
+
+/// RandomnessRegistry - Oracles accepted per randomness provider, indexed by
+/// `RandomnessProvider`
+#[component]
+#[derive(Default)]
+pub struct RandomnessRegistry {
+    pub authority: Pubkey,
+    pub providers: [ProviderEntry; RANDOMNESS_PROVIDER_COUNT],
+    pub updated_at: i64,
+}
+
+impl RandomnessRegistry {
+    pub fn entry(&self, provider: RandomnessProvider) -> &ProviderEntry {
+        &self.providers[provider as usize]
+    }
+
+    /// Reject a proof from a disabled provider or from anyone but its oracle
+    pub fn check_oracle(&self, provider: RandomnessProvider, signer: &Pubkey) -> Result<()> {
+        let entry = self.entry(provider);
+        require!(entry.enabled, RandomnessError::ProviderDisabled);
+        require!(
+            provider == RandomnessProvider::CommitReveal || entry.oracle == *signer,
+            RandomnessError::OracleMismatch
+        );
+        Ok(())
+    }
+}
+
+impl<'info> ConfigureRandomnessProvider<'info> {
+    pub fn process(&mut self, provider: RandomnessProvider, oracle: Pubkey, enabled: bool) -> Result<()> {
+        let mut registry = self.randomness_registry.load_mut()?;
+        registry.authority = self.authority.key();
+        registry.providers[provider as usize] = ProviderEntry { oracle, enabled };
+        registry.updated_at = Clock::get()?.unix_timestamp;
+
+        emit!(RandomnessProviderConfiguredEvent { provider, oracle, enabled });
+        Ok(())
+    }
+}
+
+/// Read the value from a Switchboard randomness account. The account must
+/// belong to the configured oracle and have been revealed in this slot, so a
+/// value revealed earlier cannot be picked after the fact.
+pub fn switchboard_reveal(data: &[u8], oracle: &Pubkey, current_slot: u64) -> Result<[u8; 32]> {
+    require!(data.len() >= SWITCHBOARD_ACCOUNT_MIN_LEN, RandomnessError::InvalidSwitchboardAccount);
+
+    let authority = &data[SWITCHBOARD_AUTHORITY_OFFSET..SWITCHBOARD_AUTHORITY_OFFSET + 32];
+    require!(authority == oracle.as_ref(), RandomnessError::OracleMismatch);
+
+    let mut reveal_slot = [0u8; 8];
+    reveal_slot.copy_from_slice(&data[SWITCHBOARD_REVEAL_SLOT_OFFSET..SWITCHBOARD_VALUE_OFFSET]);
+    require!(u64::from_le_bytes(reveal_slot) == current_slot, RandomnessError::SwitchboardNotRevealed);
+
+    let mut value = [0u8; 32];
+    value.copy_from_slice(&data[SWITCHBOARD_VALUE_OFFSET..SWITCHBOARD_ACCOUNT_MIN_LEN]);
+    Ok(value)
+}
+
+/// Switchboard randomness for a duel. The revealed value must be carried in
+/// the first half of the proof so the transcript records it.
+pub fn switchboard_randomness(
+    seed: &[u8; 32],
+    proof: &[u8; 64],
+    randomness_account: &AccountInfo,
+    oracle: &Pubkey,
+) -> Result<u64> {
+    require!(
+        randomness_account.owner == &SWITCHBOARD_ON_DEMAND_ID,
+        RandomnessError::InvalidSwitchboardAccount
+    );
+    let value = switchboard_reveal(&randomness_account.try_borrow_data()?, oracle, Clock::get()?.slot)?;
+    require!(proof[..32] == value, RandomnessError::SwitchboardValueMismatch);
+    Ok(first_u64(&hashv(&[seed, &value]).to_bytes()))
+}
+
+/// Oracle-free randomness from both players' revealed entropy. Each seat
+/// committed before seeing the other's reveal, so neither controls the result.
+pub fn commit_reveal_randomness(duel: &DuelComponent) -> Result<u64> {
+    require!(duel.entropy_revealed.iter().all(|revealed| *revealed), RandomnessError::RevealIncomplete);
+    Ok(first_u64(&hashv(&[&duel.vrf_seed, &duel.entropy_reveals[0], &duel.entropy_reveals[1]]).to_bytes()))
+}
+
+fn first_u64(hash: &[u8; 32]) -> u64 {
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&hash[0..8]);
+    u64::from_le_bytes(bytes)
+}
+
+#[event]
+pub struct RandomnessProviderConfiguredEvent {
+    pub provider: RandomnessProvider,
+    pub oracle: Pubkey,
+    pub enabled: bool,
+}
+
+#[error_code]
+pub enum RandomnessError {
+    #[msg("Randomness provider is disabled")]
+    ProviderDisabled,
+    #[msg("Signer is not the oracle for this duel's randomness provider")]
+    OracleMismatch,
+    #[msg("Both players must reveal entropy before commit-reveal resolution")]
+    RevealIncomplete,
+    #[msg("Switchboard duels need a randomness account owned by Switchboard On-Demand")]
+    InvalidSwitchboardAccount,
+    #[msg("Switchboard randomness was not revealed in this slot")]
+    SwitchboardNotRevealed,
+    #[msg("Proof does not carry the revealed Switchboard value")]
+    SwitchboardValueMismatch,
+}
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use shared::{RateLimitConfig, RateWindow, RATE_LIMITED_ACTIONS, RATE_LIMIT_CONFIG_SEED};
+use super::admin_config::AdminConfigError;
+
+/// InitializeRateLimitConfig - Take governance of the per-player rate limits
+/// for every program in the deployment; super admin only
+#[derive(Accounts)]
+pub struct InitializeRateLimitConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [shared::ADMIN_CONFIG_SEED],
+        bump = admin_config.bump,
+        constraint = admin_config.super_admin == authority.key() @ AdminConfigError::NotSuperAdmin
+    )]
+    pub admin_config: Account<'info, shared::AdminConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = RateLimitConfig::LEN,
+        seeds = [RATE_LIMIT_CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, RateLimitConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// SetRateLimit - Change the window for one rate-limited entrypoint
+#[derive(Accounts)]
+pub struct SetRateLimit<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [RATE_LIMIT_CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, RateLimitConfig>,
+}
+
+impl<'info> InitializeRateLimitConfig<'info> {
+    pub fn process(&mut self, bump: u8) -> Result<()> {
+        self.config.init(self.authority.key(), bump, Clock::get()?.unix_timestamp);
+        Ok(())
+    }
+}
+
+impl<'info> SetRateLimit<'info> {
+    pub fn process(&mut self, action: u8, window: RateWindow) -> Result<()> {
+        let config = &mut self.config;
+        require_keys_eq!(config.authority, self.authority.key(), RateLimitError::Unauthorized);
+        require!((action as usize) < RATE_LIMITED_ACTIONS, RateLimitError::UnknownAction);
+        config.windows[action as usize] = window;
+        config.updated_at = Clock::get()?.unix_timestamp;
+
+        emit!(RateLimitUpdatedEvent {
+            action,
+            window_secs: window.window_secs,
+            max_calls: window.max_calls,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct RateLimitUpdatedEvent {
+    pub action: u8,
+    pub window_secs: u32,
+    pub max_calls: u16,
+}
+
+#[error_code]
+pub enum RateLimitError {
+    #[msg("Only the rate limit authority can change limits")]
+    Unauthorized,
+    #[msg("No rate limit exists for this action")]
+    UnknownAction,
+}
@@ -0,0 +1,210 @@
+use anchor_lang::prelude::*;
+use bolt_lang::*;
+use crate::components::*;
+use super::admin_config::AdminConfigError;
+
+pub const SECONDS_PER_WEEK: i64 = 7 * 24 * 60 * 60;
+/// Most rating points a single week of inactivity may cost
+pub const MAX_DECAY_PER_WEEK: u32 = 50;
+
+/// InitializeRatingDecay - Configure inactivity decay for the ranked queue; super admin only
+#[derive(Accounts)]
+pub struct InitializeRatingDecay<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [shared::ADMIN_CONFIG_SEED],
+        bump = admin_config.bump,
+        constraint = admin_config.super_admin == authority.key() @ AdminConfigError::NotSuperAdmin
+    )]
+    pub admin_config: Account<'info, shared::AdminConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<RatingDecayConfig>(),
+        seeds = [b"rating_decay_config"],
+        bump
+    )]
+    pub config: Account<'info, ComponentData<RatingDecayConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// UpdateRatingDecay - Adjust the decay terms or toggle the off-season
+#[derive(Accounts)]
+pub struct UpdateRatingDecay<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"rating_decay_config"],
+        bump
+    )]
+    pub config: Account<'info, ComponentData<RatingDecayConfig>>,
+}
+
+/// DecayRating - Apply accrued inactivity decay to one player's ranked
+/// rating; any keeper may crank it
+#[derive(Accounts)]
+pub struct DecayRating<'info> {
+    pub keeper: Signer<'info>,
+
+    #[account(
+        seeds = [b"rating_decay_config"],
+        bump
+    )]
+    pub config: Account<'info, ComponentData<RatingDecayConfig>>,
+
+    /// CHECK: Player whose profile decays
+    pub player: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"psych", player.key().as_ref()],
+        bump = psych_profile.load()?.bump
+    )]
+    pub psych_profile: Account<'info, ComponentData<PsychProfileComponent>>,
+}
+
+/// RatingDecayConfig - Ranked ratings above `threshold` lose `decay_per_week`
+/// for every full week without a rated game, under both rating systems.
+/// Nothing decays in the off-season, and weeks spent in it never count.
+#[component]
+#[derive(Default)]
+pub struct RatingDecayConfig {
+    pub authority: Pubkey,
+    pub threshold: u32,
+    pub decay_per_week: u32,
+    pub off_season: bool,
+    pub season_resumed_at: i64,
+}
+
+impl RatingDecayConfig {
+    /// Start of the inactivity not yet decayed
+    fn inactive_since(&self, track: &RatingTrack) -> i64 {
+        track.last_rated_at.max(track.last_decayed_at).max(self.season_resumed_at)
+    }
+
+    /// Full inactive weeks not yet decayed
+    pub fn inactive_weeks(&self, track: &RatingTrack, now: i64) -> i64 {
+        if self.off_season || track.rated_games == 0 {
+            return 0;
+        }
+        (now - self.inactive_since(track)).max(0) / SECONDS_PER_WEEK
+    }
+
+    /// Full inactive weeks not yet decayed, and the rating after decaying them
+    pub fn pending_decay(&self, track: &RatingTrack, now: i64) -> (i64, u32) {
+        let rating = track.rating();
+        if rating <= self.threshold {
+            return (0, rating);
+        }
+        let weeks = self.inactive_weeks(track, now);
+        let decay = (weeks as u64 * self.decay_per_week as u64).min(u32::MAX as u64) as u32;
+        (weeks, rating.saturating_sub(decay).max(self.threshold))
+    }
+
+    /// The Glicko-2 rating after `weeks` of decay, by the same points and down to the same threshold
+    pub fn decayed_glicko(&self, glicko: shared::Glicko2Rating, weeks: i64) -> shared::Glicko2Rating {
+        let floor = self.threshold as i64 * shared::GLICKO_FX;
+        if glicko.deviation == 0 || glicko.rating <= floor {
+            return glicko;
+        }
+        let decay = weeks.saturating_mul(self.decay_per_week as i64).saturating_mul(shared::GLICKO_FX);
+        shared::Glicko2Rating { rating: glicko.rating.saturating_sub(decay).max(floor), ..glicko }
+    }
+
+    fn apply(&mut self, threshold: u32, decay_per_week: u32, off_season: bool, now: i64) -> Result<()> {
+        require!(
+            decay_per_week > 0 && decay_per_week <= MAX_DECAY_PER_WEEK && threshold >= INITIAL_SKILL_RATING,
+            RatingDecayError::InvalidConfig
+        );
+        // Leaving the off-season restarts every player's inactivity clock
+        if self.off_season && !off_season {
+            self.season_resumed_at = now;
+        }
+        self.threshold = threshold;
+        self.decay_per_week = decay_per_week;
+        self.off_season = off_season;
+
+        Ok(())
+    }
+}
+
+impl<'info> InitializeRatingDecay<'info> {
+    pub fn process(&mut self, threshold: u32, decay_per_week: u32, off_season: bool) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let mut config = self.config.load_init()?;
+        config.authority = self.authority.key();
+        config.season_resumed_at = now;
+        config.apply(threshold, decay_per_week, off_season, now)
+    }
+}
+
+impl<'info> UpdateRatingDecay<'info> {
+    pub fn process(&mut self, threshold: u32, decay_per_week: u32, off_season: bool) -> Result<()> {
+        let mut config = self.config.load_mut()?;
+        require_keys_eq!(config.authority, self.authority.key(), RatingDecayError::Unauthorized);
+        config.apply(threshold, decay_per_week, off_season, Clock::get()?.unix_timestamp)
+    }
+}
+
+impl<'info> DecayRating<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let config = self.config.load()?;
+        require!(!config.off_season, RatingDecayError::OffSeason);
+
+        let mut psych = self.psych_profile.load_mut()?;
+        let track = psych.track_mut(QueueType::Ranked);
+        let weeks = config.inactive_weeks(track, now);
+        let (_, decayed) = config.pending_decay(track, now);
+        let glicko = config.decayed_glicko(track.glicko, weeks);
+        let previous = track.rating();
+        let previous_glicko = track.glicko;
+        require!(
+            weeks > 0 && (decayed < previous || glicko != previous_glicko),
+            RatingDecayError::NothingToDecay
+        );
+
+        track.skill_rating = decayed;
+        track.glicko = glicko;
+        // Partial weeks carry over to the next crank
+        track.last_decayed_at = config.inactive_since(track) + weeks * SECONDS_PER_WEEK;
+
+        emit!(RatingDecayedEvent {
+            player: psych.player,
+            previous_rating: previous,
+            new_rating: decayed,
+            previous_glicko_rating: previous_glicko.display_rating(),
+            new_glicko_rating: glicko.display_rating(),
+            inactive_weeks: weeks as u32,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct RatingDecayedEvent {
+    pub player: Pubkey,
+    pub previous_rating: u32,
+    pub new_rating: u32,
+    pub previous_glicko_rating: u32,
+    pub new_glicko_rating: u32,
+    pub inactive_weeks: u32,
+}
+
+#[error_code]
+pub enum RatingDecayError {
+    #[msg("Decay must be positive, capped, and start at or above the initial rating")]
+    InvalidConfig,
+    #[msg("Only the decay authority can update it")]
+    Unauthorized,
+    #[msg("Rating decay is paused for the off-season")]
+    OffSeason,
+    #[msg("This rating has no decay due")]
+    NothingToDecay,
+}
@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use bolt_lang::*;
+use crate::components::*;
+
+/// Longest grace period a duel may configure for reconnects
+pub const MAX_RECONNECT_GRACE_PERIOD: i64 = 120;
+
+/// RequestReconnect - Pause the action timer once per match after a transient disconnect
+#[derive(Accounts)]
+pub struct RequestReconnect<'info> {
+    pub player_signer: Signer<'info>,
+
+    /// CHECK: Entity for the duel
+    pub entity: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"duel", entity.key().as_ref()],
+        bump = duel.load()?.bump
+    )]
+    pub duel: Account<'info, ComponentData<DuelComponent>>,
+
+    #[account(
+        mut,
+        seeds = [b"player", player_signer.key().as_ref(), entity.key().as_ref()],
+        bump = player.load()?.bump
+    )]
+    pub player: Account<'info, ComponentData<PlayerComponent>>,
+}
+
+impl<'info> RequestReconnect<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let clock = Clock::get()?;
+        let current_time = clock.unix_timestamp;
+
+        let mut duel = self.duel.load_mut()?;
+        let mut player = self.player.load_mut()?;
+
+        require!(
+            duel.game_state == GameState::InProgress || duel.game_state == GameState::AwaitingAction,
+            ReconnectError::InvalidGameState
+        );
+        require!(player.is_active, ReconnectError::PlayerInactive);
+        // Only the player the timer is running against may pause it
+        require!(duel.is_on_clock(&player), ReconnectError::NotOnClock);
+        require!(!player.reconnect_used, ReconnectError::ReconnectAlreadyUsed);
+        require!(duel.reconnect_grace_period > 0, ReconnectError::ReconnectDisabled);
+        // A timer that already ran out cannot be rescued
+        require!(!duel.is_timeout_exceeded(current_time), ReconnectError::TimerExpired);
+
+        // Pause the action timer by extending the current deadline
+        duel.grace_extension += duel.reconnect_grace_period;
+
+        // Issue a nonce the client must echo in its next action to prove it re-synced
+        let sync_nonce = generate_sync_nonce(&duel, &player.player_id, clock.slot);
+        player.reconnect_used = true;
+        player.pending_sync_nonce = sync_nonce;
+        player.last_seen = current_time;
+
+        emit!(ReconnectGrantedEvent {
+            duel_id: duel.duel_id,
+            player: player.player_id,
+            grace_period: duel.reconnect_grace_period,
+            new_deadline: duel.last_action_time + duel.timeout_duration + duel.grace_extension,
+            sync_nonce,
+        });
+
+        Ok(())
+    }
+}
+
+/// Consume a pending sync nonce, if one was issued. Called before processing an action.
+pub fn consume_sync_nonce(player: &mut PlayerComponent, echoed_nonce: Option<u64>) -> Result<()> {
+    if player.pending_sync_nonce == 0 {
+        return Ok(());
+    }
+    require!(
+        echoed_nonce == Some(player.pending_sync_nonce),
+        ReconnectError::SyncNonceMismatch
+    );
+    player.pending_sync_nonce = 0;
+    Ok(())
+}
+
+fn generate_sync_nonce(duel: &DuelComponent, player: &Pubkey, slot: u64) -> u64 {
+    let hash = hashv(&[
+        &duel.duel_id.to_le_bytes(),
+        player.as_ref(),
+        &slot.to_le_bytes(),
+        &duel.replay_head_hash,
+    ]);
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&hash.to_bytes()[0..8]);
+    // Zero is reserved for "no pending nonce"
+    u64::from_le_bytes(bytes).max(1)
+}
+
+#[event]
+pub struct ReconnectGrantedEvent {
+    pub duel_id: u64,
+    pub player: Pubkey,
+    pub grace_period: i64,
+    pub new_deadline: i64,
+    pub sync_nonce: u64,
+}
+
+#[error_code]
+pub enum ReconnectError {
+    #[msg("Reconnect is not possible in the current game state")]
+    InvalidGameState,
+    #[msg("Player is not active")]
+    PlayerInactive,
+    #[msg("Reconnect grace already used this match")]
+    ReconnectAlreadyUsed,
+    #[msg("Reconnect grace is disabled for this duel")]
+    ReconnectDisabled,
+    #[msg("Action timer already expired")]
+    TimerExpired,
+    #[msg("State sync nonce mismatch")]
+    SyncNonceMismatch,
+    #[msg("Only the player on the clock can request a reconnect")]
+    NotOnClock,
+}
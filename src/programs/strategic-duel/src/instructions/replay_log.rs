@@ -0,0 +1,284 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use bolt_lang::*;
+use crate::components::*;
+
+/// Maximum number of actions stored in a single replay chunk
+pub const MAX_REPLAY_ENTRIES_PER_CHUNK: usize = 32;
+
+/// VerifyReplay - Re-execute logged actions and compare the result against known state.
+/// Replay chunks for the requested range are passed in `remaining_accounts` in order,
+/// optionally followed by the chunk right after the range.
+#[derive(Accounts)]
+pub struct VerifyReplay<'info> {
+    /// CHECK: Anyone may verify a replay
+    pub verifier: Signer<'info>,
+
+    /// CHECK: Entity for the duel
+    pub entity: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"duel", entity.key().as_ref()],
+        bump = duel.load()?.bump
+    )]
+    pub duel: Account<'info, ComponentData<DuelComponent>>,
+
+    #[account(
+        seeds = [b"betting", entity.key().as_ref()],
+        bump = betting.load()?.bump
+    )]
+    pub betting: Account<'info, ComponentData<BettingComponent>>,
+
+    #[account(
+        seeds = [b"player", duel.load()?.player_one.as_ref(), entity.key().as_ref()],
+        bump = player_one.load()?.bump
+    )]
+    pub player_one: Account<'info, ComponentData<PlayerComponent>>,
+
+    #[account(
+        seeds = [b"player", duel.load()?.player_two.as_ref(), entity.key().as_ref()],
+        bump = player_two.load()?.bump
+    )]
+    pub player_two: Account<'info, ComponentData<PlayerComponent>>,
+}
+
+/// ReplayChunk - Append-only segment of a duel's action log.
+/// Each chunk records the state it started from so any contiguous range can be replayed.
+#[component]
+#[derive(Default)]
+pub struct ReplayChunk {
+    pub duel_id: u64,
+    pub chunk_index: u16,
+    pub start_state: ReplayState,
+    pub entries: Vec<ReplayEntry>,
+    pub prev_chunk_hash: [u8; 32],
+    pub chunk_hash: [u8; 32],
+    pub is_sealed: bool,
+}
+
+impl ReplayChunk {
+    pub fn is_full(&self) -> bool {
+        self.entries.len() >= MAX_REPLAY_ENTRIES_PER_CHUNK
+    }
+
+    /// Initialize a chunk that has no entries yet from the duel's replay head
+    pub fn open_if_empty(&mut self, duel: &DuelComponent) {
+        if !self.entries.is_empty() {
+            return;
+        }
+        self.duel_id = duel.duel_id;
+        self.chunk_index = duel.replay_chunk_count;
+        self.start_state = duel.replay_state;
+        self.prev_chunk_hash = duel.replay_head_hash;
+        self.chunk_hash = [0u8; 32];
+        self.is_sealed = false;
+    }
+
+    /// Append an entry, sealing the chunk once it reaches capacity.
+    /// Returns true if this append sealed the chunk.
+    pub fn append(&mut self, entry: ReplayEntry) -> Result<bool> {
+        require!(!self.is_sealed, ReplayError::ChunkSealed);
+        require!(!self.is_full(), ReplayError::ChunkFull);
+
+        self.entries.push(entry);
+        self.chunk_hash = self.compute_hash();
+
+        if self.is_full() {
+            self.is_sealed = true;
+        }
+        Ok(self.is_sealed)
+    }
+
+    /// Hash chained over the previous chunk, the start state and every entry
+    pub fn compute_hash(&self) -> [u8; 32] {
+        let mut data = Vec::with_capacity(76 + self.entries.len() * 19);
+        data.extend_from_slice(&self.prev_chunk_hash);
+        data.extend_from_slice(&self.duel_id.to_le_bytes());
+        data.extend_from_slice(&self.chunk_index.to_le_bytes());
+        data.extend_from_slice(&self.start_state.hash());
+        for entry in self.entries.iter() {
+            data.push(entry.seat);
+            data.push(entry.action_type as u8);
+            data.extend_from_slice(&entry.bet_amount.to_le_bytes());
+            data.push(entry.round_number);
+            data.extend_from_slice(&entry.timestamp.to_le_bytes());
+        }
+        hashv(&[&data]).to_bytes()
+    }
+}
+
+/// Record an action in the duel's current replay chunk and advance the replay head.
+/// Called from every instruction that changes duel state through a player action.
+pub fn record_replay_entry(
+    duel: &mut DuelComponent,
+    chunk: &mut ReplayChunk,
+    entry: ReplayEntry,
+) -> Result<()> {
+    chunk.open_if_empty(duel);
+    require!(chunk.chunk_index == duel.replay_chunk_count, ReplayError::MissingChunk);
+    require!(duel.replay_state.apply(&entry), ReplayError::ReplayDiverged);
+
+    duel.action_transcript = extend_transcript(&duel.action_transcript, duel.duel_id, &entry);
+    let sealed = chunk.append(entry)?;
+    duel.replay_head_hash = chunk.chunk_hash;
+    if sealed {
+        duel.replay_chunk_count += 1;
+    }
+    Ok(())
+}
+
+/// Fold one action into the running transcript. Unlike the replay head it
+/// spans chunk boundaries with a single value, so attestations can commit to
+/// the full action history before the duel resolves.
+pub fn extend_transcript(running: &[u8; 32], duel_id: u64, entry: &ReplayEntry) -> [u8; 32] {
+    hashv(&[
+        running,
+        &duel_id.to_le_bytes(),
+        &[entry.seat, entry.action_type as u8, entry.round_number],
+        &entry.bet_amount.to_le_bytes(),
+        &entry.timestamp.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+/// Hash binding a resolved duel to its full record: the replay head (which chains
+/// every logged action in order), the VRF inputs and output, revealed player
+/// entropy, final balances and the winner. Third parties can recompute it from
+/// the replay chunks and the resolution event.
+pub fn compute_transcript_hash(
+    duel: &DuelComponent,
+    vrf_proof: &[u8; 64],
+    randomness: u64,
+    winner: &Pubkey,
+) -> [u8; 32] {
+    hashv(&[
+        &duel.duel_id.to_le_bytes(),
+        &duel.replay_head_hash,
+        &duel.replay_chunk_count.to_le_bytes(),
+        &duel.action_transcript,
+        &duel.vrf_seed,
+        vrf_proof,
+        &randomness.to_le_bytes(),
+        &duel.entropy_reveals[0],
+        &duel.entropy_reveals[1],
+        &duel.replay_state.hash(),
+        winner.as_ref(),
+    ])
+    .to_bytes()
+}
+
+impl<'info> VerifyReplay<'info> {
+    pub fn process(
+        &self,
+        remaining_accounts: &[AccountInfo<'info>],
+        start_chunk: u16,
+        end_chunk: u16,
+    ) -> Result<()> {
+        require!(start_chunk <= end_chunk, ReplayError::InvalidChunkRange);
+        let chunk_count = (end_chunk - start_chunk) as usize + 1;
+        require!(remaining_accounts.len() >= chunk_count, ReplayError::MissingChunk);
+
+        let duel = self.duel.load()?;
+
+        let mut state: Option<ReplayState> = None;
+        let mut prev_hash: Option<[u8; 32]> = None;
+
+        for (offset, chunk_info) in remaining_accounts.iter().take(chunk_count).enumerate() {
+            let chunk = self.load_chunk(chunk_info, start_chunk + offset as u16, duel.duel_id)?;
+            require!(chunk.compute_hash() == chunk.chunk_hash, ReplayError::ChunkHashMismatch);
+
+            if let Some(prev) = prev_hash {
+                require!(chunk.prev_chunk_hash == prev, ReplayError::ChunkHashMismatch);
+            }
+
+            // Each chunk must start exactly where the previous one left off
+            let mut current = match state {
+                Some(replayed) => {
+                    require!(replayed == chunk.start_state, ReplayError::ReplayDiverged);
+                    replayed
+                },
+                None => chunk.start_state,
+            };
+            for entry in chunk.entries.iter() {
+                require!(current.apply(entry), ReplayError::ReplayDiverged);
+            }
+
+            state = Some(current);
+            prev_hash = Some(chunk.chunk_hash);
+        }
+
+        let mut replayed = state.ok_or(ReplayError::MissingChunk)?;
+
+        // Compare against the following chunk's start state, or the live components
+        // when the range ends at the replay head
+        let expected = match remaining_accounts.get(chunk_count) {
+            Some(next_info) => {
+                let next = self.load_chunk(next_info, end_chunk + 1, duel.duel_id)?;
+                require!(next.prev_chunk_hash == prev_hash.unwrap_or_default(), ReplayError::ChunkHashMismatch);
+                next.start_state
+            },
+            None => {
+                require!(end_chunk >= duel.replay_chunk_count.saturating_sub(1), ReplayError::MissingChunk);
+                let player_one = self.player_one.load()?;
+                let player_two = self.player_two.load()?;
+                let betting = self.betting.load()?;
+                replayed.advance_to_round(duel.current_round);
+                ReplayState::capture(&duel, &player_one, &player_two, &betting)
+            },
+        };
+
+        let final_state_hash = replayed.hash();
+        require!(final_state_hash == expected.hash(), ReplayError::StateHashMismatch);
+
+        emit!(ReplayVerifiedEvent {
+            duel_id: duel.duel_id,
+            start_chunk,
+            end_chunk,
+            final_state_hash,
+            last_chunk_hash: prev_hash.unwrap_or_default(),
+        });
+
+        Ok(())
+    }
+
+    fn load_chunk(&self, chunk_info: &AccountInfo<'info>, chunk_index: u16, duel_id: u64) -> Result<ReplayChunk> {
+        let (expected_key, _) = Pubkey::find_program_address(
+            &[b"replay", self.entity.key().as_ref(), &chunk_index.to_le_bytes()],
+            &crate::ID,
+        );
+        require_keys_eq!(chunk_info.key(), expected_key, ReplayError::MissingChunk);
+
+        let chunk_account: Account<ComponentData<ReplayChunk>> = Account::try_from(chunk_info)?;
+        let chunk = chunk_account.load()?.clone();
+        require!(chunk.duel_id == duel_id, ReplayError::MissingChunk);
+        require!(chunk.chunk_index == chunk_index, ReplayError::MissingChunk);
+        Ok(chunk)
+    }
+}
+
+#[event]
+pub struct ReplayVerifiedEvent {
+    pub duel_id: u64,
+    pub start_chunk: u16,
+    pub end_chunk: u16,
+    pub final_state_hash: [u8; 32],
+    pub last_chunk_hash: [u8; 32],
+}
+
+#[error_code]
+pub enum ReplayError {
+    #[msg("Invalid replay chunk range")]
+    InvalidChunkRange,
+    #[msg("Replay chunk missing or out of order")]
+    MissingChunk,
+    #[msg("Replay chunk is sealed")]
+    ChunkSealed,
+    #[msg("Replay chunk is full")]
+    ChunkFull,
+    #[msg("Replay chunk hash mismatch")]
+    ChunkHashMismatch,
+    #[msg("Replayed action diverged from logged state")]
+    ReplayDiverged,
+    #[msg("Replayed state hash does not match final state")]
+    StateHashMismatch,
+}
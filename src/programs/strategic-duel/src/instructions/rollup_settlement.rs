@@ -1,6 +1,10 @@
 use anchor_lang::prelude::*;
 use bolt_lang::*;
 use crate::components::*;
+use super::head_to_head::HeadToHead;
+use super::match_history::{record_history, seat_summaries, MatchResult, RecentMatches};
+use super::rake::{RakeError, RakeSchedule};
+use super::transfer_limit::ordered_pair;
 
 /// Rollup Settlement with L1 mapping for MagicBlock Ephemeral Rollups
 #[derive(Accounts)]
@@ -8,12 +12,16 @@ pub struct RollupSettlement<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
 
-    /// CHECK: L1 settlement authority (MagicBlock validator)
-    pub l1_authority: AccountInfo<'info>,
+    /// L1 settlement authority: the validator the duel was delegated to
+    #[account(address = duel.load()?.latency_authority @ SettlementError::NotRollupValidator)]
+    pub l1_authority: Signer<'info>,
 
-    /// CHECK: World PDA
-    #[account(mut)]
-    pub world: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"world"],
+        bump
+    )]
+    pub world: Account<'info, ComponentData<crate::BoltWorldComponent>>,
 
     /// CHECK: Entity for the duel
     #[account(mut)]
@@ -22,7 +30,7 @@ pub struct RollupSettlement<'info> {
     #[account(
         mut,
         seeds = [b"duel", entity.key().as_ref()],
-        bump,
+        bump = duel.load()?.bump,
         constraint = duel.load()?.vrf_verified @ GameError::VrfNotVerified
     )]
     pub duel: Account<'info, ComponentData<DuelComponent>>,
@@ -47,17 +55,25 @@ pub struct RollupSettlement<'info> {
     #[account(
         mut,
         seeds = [b"betting", entity.key().as_ref()],
-        bump
+        bump = betting.load()?.bump
     )]
     pub betting: Account<'info, ComponentData<BettingComponent>>,
 
     #[account(
         mut,
-        seeds = [b"player", duel.load()?.winner.unwrap().as_ref(), entity.key().as_ref()],
-        bump
+        seeds = [b"player", super::get_winner_key(&duel.load()?).as_ref(), entity.key().as_ref()],
+        bump = winner_player.load()?.bump
     )]
     pub winner_player: Account<'info, ComponentData<PlayerComponent>>,
 
+    /// Credited any handicap or early-forfeit refund
+    #[account(
+        mut,
+        seeds = [b"player", super::get_loser_key(&duel.load()?).as_ref(), entity.key().as_ref()],
+        bump = loser_player.load()?.bump
+    )]
+    pub loser_player: Account<'info, ComponentData<PlayerComponent>>,
+
     /// CHECK: L1 commitment account for state mapping
     #[account(
         init_if_needed,
@@ -68,10 +84,39 @@ pub struct RollupSettlement<'info> {
     )]
     pub l1_commitment: Account<'info, ComponentData<L1CommitmentComponent>>,
 
-    /// CHECK: Treasury for fee collection
-    #[account(mut)]
+    /// CHECK: Treasury for fee collection; fixed by the rake schedule
+    #[account(mut, address = rake_schedule.load()?.treasury @ RakeError::WrongTreasury)]
     pub treasury: AccountInfo<'info>,
 
+    /// Governance rake schedule, the same one L1 settlement applies
+    #[account(
+        seeds = [b"rake_schedule"],
+        bump
+    )]
+    pub rake_schedule: Account<'info, ComponentData<RakeSchedule>>,
+
+    /// Each player's match history, recorded as on L1; practice duels are not recorded
+    #[account(
+        mut,
+        seeds = [b"recent_matches", super::get_winner_key(&duel.load()?).as_ref()],
+        bump
+    )]
+    pub winner_recent_matches: Option<Account<'info, ComponentData<RecentMatches>>>,
+
+    #[account(
+        mut,
+        seeds = [b"recent_matches", super::get_loser_key(&duel.load()?).as_ref()],
+        bump
+    )]
+    pub loser_recent_matches: Option<Account<'info, ComponentData<RecentMatches>>>,
+
+    #[account(
+        mut,
+        seeds = [b"head_to_head", ordered_pair(&duel.load()?).0.as_ref(), ordered_pair(&duel.load()?).1.as_ref()],
+        bump
+    )]
+    pub head_to_head: Option<Account<'info, ComponentData<HeadToHead>>>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -157,8 +202,23 @@ impl<'info> RollupSettlement<'info> {
 
         // Verify game is ready for settlement
         require!(duel.ready_for_settlement, GameError::NotReadyForSettlement);
+        // Drawn duels have no winner to prove and settle through the split-pot path
         require!(duel.winner.is_some(), GameError::NoWinnerDetermined);
         require!(!betting.is_settled, GameError::AlreadySettled);
+        // Escrowed lamports are paid out by the L1 Settlement system only
+        require!(duel.escrowed_stake == 0, SettlementError::EscrowedDuel);
+        require!(
+            duel.transcript_validated && vrf_attestation.transcript_hash == duel.action_transcript,
+            crate::instructions::VrfError::InvalidTranscriptHash
+        );
+        require!(betting.lock(), GameError::ReentrancyDetected);
+
+        // Rake by the governance schedule, as the L1 path does; practice duels are stake-free
+        betting.rake_amount = if duel.is_practice {
+            0
+        } else {
+            self.rake_schedule.load()?.rake_for(betting.total_pot, duel.current_round)
+        };
 
         // Initialize settlement
         settlement.duel_id = duel.duel_id;
@@ -203,7 +263,29 @@ impl<'info> RollupSettlement<'info> {
         );
 
         // Start optimistic settlement process
-        self.initiate_optimistic_settlement(&mut settlement, &mut betting, &mut winner_player)?;
+        let mut loser_player = self.loser_player.load_mut()?;
+        let payouts = self.initiate_optimistic_settlement(&duel, &mut settlement, &mut betting, &mut winner_player, &mut loser_player)?;
+        betting.unlock();
+
+        // The rollup path does not move ratings, so it records no rating change
+        if !duel.is_practice {
+            let summaries = seat_summaries(
+                duel.duel_id,
+                [winner_player.player_id, loser_player.player_id],
+                [winner_player.total_bet, loser_player.total_bet],
+                [MatchResult::Win, MatchResult::Loss],
+                payouts,
+                [0, 0],
+                current_time,
+            );
+            record_history(
+                &self.head_to_head,
+                [&self.winner_recent_matches, &self.loser_recent_matches],
+                duel.winner,
+                summaries,
+                betting.total_pot,
+            )?;
+        }
 
         emit!(RollupSettlementInitiatedEvent {
             duel_id: duel.duel_id,
@@ -386,13 +468,21 @@ impl<'info> RollupSettlement<'info> {
 
     fn initiate_optimistic_settlement(
         &self,
+        duel: &DuelComponent,
         settlement: &mut RollupSettlementComponent,
         betting: &mut BettingComponent,
         winner: &mut PlayerComponent,
-    ) -> Result<()> {
+        loser: &mut PlayerComponent,
+    ) -> Result<[u64; 2]> {
         // Calculate final payout after fees
         let settlement_fee = settlement.settlement_fee;
-        let net_payout = betting.total_pot.saturating_sub(betting.rake_amount).saturating_sub(settlement_fee);
+        let net_pot = betting.total_pot.saturating_sub(betting.rake_amount).saturating_sub(settlement_fee);
+        let winner_seat = duel.seat_of(&winner.player_id).ok_or(GameError::InvalidGameState)?;
+        let (net_payout, loser_refund) = duel.settlement_split(winner_seat, net_pot);
+        
+        // The handicap and early-forfeit share goes back to the loser, as on L1
+        loser.chip_count = loser.chip_count.checked_add(loser_refund)
+            .ok_or(GameError::ArithmeticOverflow)?;
         
         // Update winner's balance (optimistically)
         winner.total_winnings = winner.total_winnings.checked_add(net_payout)
@@ -406,7 +496,7 @@ impl<'info> RollupSettlement<'info> {
         // Update settlement status
         settlement.settlement_status = SettlementStatus::OptimisticConfirmed;
         
-        Ok(())
+        Ok([net_payout, loser_refund])
     }
 
     fn hash_bytes(&self, input: &[u8]) -> [u8; 32] {
@@ -468,4 +558,8 @@ pub enum SettlementError {
     InconsistentPotCalculation,
     #[msg("Insufficient rent exemption")]
     InsufficientRentExemption,
+    #[msg("Duels holding escrowed stakes settle through Settlement")]
+    EscrowedDuel,
+    #[msg("Signer is not the validator the duel was delegated to")]
+    NotRollupValidator,
 }
\ No newline at end of file
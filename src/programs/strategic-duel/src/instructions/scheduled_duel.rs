@@ -0,0 +1,635 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::system_program;
+use bolt_lang::*;
+use crate::components::*;
+use super::head_to_head::HeadToHead;
+use super::match_history::{record_history, seat_summaries, MatchResult, RecentMatches};
+use super::matchmaking::{fix_seat_ratings, open_profile, open_seated_duel};
+use super::{CreateDuelParams, GameError};
+
+/// Shortest notice a scheduled duel may be booked with
+pub const MIN_SCHEDULE_LEAD_TIME: i64 = 5 * 60;
+
+/// Longest check-in window either side of the start time
+pub const MAX_CHECK_IN_GRACE: i64 = 15 * 60;
+
+/// ScheduleDuel - Book a future duel against a named opponent and escrow the host's stake
+#[derive(Accounts)]
+#[instruction(schedule_id: u64)]
+pub struct ScheduleDuel<'info> {
+    #[account(mut)]
+    pub host: Signer<'info>,
+
+    #[account(
+        init,
+        payer = host,
+        space = 8 + std::mem::size_of::<ScheduledDuel>(),
+        seeds = [b"scheduled_duel", host.key().as_ref(), &schedule_id.to_le_bytes()],
+        bump
+    )]
+    pub scheduled_duel: Account<'info, ComponentData<ScheduledDuel>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// AcceptScheduledDuel - The invited opponent escrows a matching stake. The
+/// pairing is counted here, when both sides have committed to it.
+#[derive(Accounts)]
+pub struct AcceptScheduledDuel<'info> {
+    #[account(mut)]
+    pub opponent: Signer<'info>,
+
+    #[account(mut)]
+    pub scheduled_duel: Account<'info, ComponentData<ScheduledDuel>>,
+
+    /// Repeat-pairing counter shared by both players
+    #[account(
+        init_if_needed,
+        payer = opponent,
+        space = shared::PairingWindow::LEN,
+        seeds = [
+            shared::PAIRING_WINDOW_SEED,
+            shared::PairingWindow::ordered(scheduled_duel.load()?.players[0], opponent.key()).0.as_ref(),
+            shared::PairingWindow::ordered(scheduled_duel.load()?.players[0], opponent.key()).1.as_ref()
+        ],
+        bump
+    )]
+    pub pairing_window: Account<'info, shared::PairingWindow>,
+
+    /// The pair's lifetime record, counted at settlement
+    #[account(
+        init_if_needed,
+        payer = opponent,
+        space = 8 + std::mem::size_of::<HeadToHead>(),
+        seeds = [
+            b"head_to_head",
+            shared::PairingWindow::ordered(scheduled_duel.load()?.players[0], opponent.key()).0.as_ref(),
+            shared::PairingWindow::ordered(scheduled_duel.load()?.players[0], opponent.key()).1.as_ref()
+        ],
+        bump
+    )]
+    pub head_to_head: Account<'info, ComponentData<HeadToHead>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// CheckInScheduledDuel - Confirm presence inside the check-in window
+#[derive(Accounts)]
+pub struct CheckInScheduledDuel<'info> {
+    pub player: Signer<'info>,
+
+    #[account(mut)]
+    pub scheduled_duel: Account<'info, ComponentData<ScheduledDuel>>,
+}
+
+/// StartScheduledDuel - Permissionless once both players have checked in: open
+/// the booked duel with both escrowed stakes and close the booking to the host
+#[derive(Accounts)]
+pub struct StartScheduledDuel<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    #[account(mut, close = host_wallet)]
+    pub scheduled_duel: Account<'info, ComponentData<ScheduledDuel>>,
+
+    /// CHECK: Must be the host recorded on the booking; receives the booking's rent
+    #[account(
+        mut,
+        address = scheduled_duel.load()?.players[0] @ ScheduleError::InvalidOpponent,
+        constraint = shared::is_wallet(&host_wallet) @ shared::GameError::InvalidAccountOwner
+    )]
+    pub host_wallet: AccountInfo<'info>,
+
+    /// CHECK: Must be the opponent recorded on the booking
+    #[account(address = scheduled_duel.load()?.players[1] @ ScheduleError::InvalidOpponent)]
+    pub opponent_wallet: AccountInfo<'info>,
+
+    /// CHECK: World PDA
+    #[account(mut)]
+    pub world: AccountInfo<'info>,
+
+    /// CHECK: Entity for the duel
+    #[account(mut)]
+    pub entity: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = cranker,
+        space = 8 + std::mem::size_of::<DuelComponent>(),
+        seeds = [b"duel", entity.key().as_ref()],
+        bump
+    )]
+    pub duel: Account<'info, ComponentData<DuelComponent>>,
+
+    #[account(
+        init,
+        payer = cranker,
+        space = 8 + std::mem::size_of::<BettingComponent>() + MAX_BLIND_LEVELS * std::mem::size_of::<BlindLevel>(),
+        seeds = [b"betting", entity.key().as_ref()],
+        bump
+    )]
+    pub betting: Account<'info, ComponentData<BettingComponent>>,
+
+    #[account(
+        init,
+        payer = cranker,
+        space = 8 + std::mem::size_of::<PlayerComponent>(),
+        seeds = [b"player", host_wallet.key().as_ref(), entity.key().as_ref()],
+        bump
+    )]
+    pub host_player: Account<'info, ComponentData<PlayerComponent>>,
+
+    #[account(
+        init,
+        payer = cranker,
+        space = 8 + std::mem::size_of::<PlayerComponent>(),
+        seeds = [b"player", opponent_wallet.key().as_ref(), entity.key().as_ref()],
+        bump
+    )]
+    pub opponent_player: Account<'info, ComponentData<PlayerComponent>>,
+
+    #[account(
+        mut,
+        seeds = [b"psych", host_wallet.key().as_ref()],
+        bump = host_psych.load()?.bump
+    )]
+    pub host_psych: Account<'info, ComponentData<PsychProfileComponent>>,
+
+    #[account(
+        mut,
+        seeds = [b"psych", opponent_wallet.key().as_ref()],
+        bump = opponent_psych.load()?.bump
+    )]
+    pub opponent_psych: Account<'info, ComponentData<PsychProfileComponent>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// CrankScheduledDuel - Permissionless: charge a no-show or release an
+/// unaccepted booking once the check-in window has closed, closing the booking
+/// to the host
+#[derive(Accounts)]
+pub struct CrankScheduledDuel<'info> {
+    #[account(mut, close = host_wallet)]
+    pub scheduled_duel: Account<'info, ComponentData<ScheduledDuel>>,
+
+    /// CHECK: Must be the host recorded on the booking
+    #[account(
+        mut,
+        address = scheduled_duel.load()?.players[0] @ ScheduleError::InvalidOpponent,
+        constraint = shared::is_wallet(&host_wallet) @ shared::GameError::InvalidAccountOwner
+    )]
+    pub host_wallet: AccountInfo<'info>,
+
+    /// CHECK: Must be the opponent recorded on the booking
+    #[account(
+        mut,
+        address = scheduled_duel.load()?.players[1] @ ScheduleError::InvalidOpponent,
+        constraint = shared::is_wallet(&opponent_wallet) @ shared::GameError::InvalidAccountOwner
+    )]
+    pub opponent_wallet: AccountInfo<'info>,
+
+    /// A no-show forfeit is recorded in both players' history like a finished duel
+    #[account(
+        mut,
+        seeds = [b"recent_matches", scheduled_duel.load()?.players[0].as_ref()],
+        bump
+    )]
+    pub host_recent_matches: Option<Account<'info, ComponentData<RecentMatches>>>,
+
+    #[account(
+        mut,
+        seeds = [b"recent_matches", scheduled_duel.load()?.players[1].as_ref()],
+        bump
+    )]
+    pub opponent_recent_matches: Option<Account<'info, ComponentData<RecentMatches>>>,
+
+    /// Opened when the booking was accepted
+    #[account(
+        mut,
+        seeds = [
+            b"head_to_head",
+            shared::PairingWindow::ordered(scheduled_duel.load()?.players[0], scheduled_duel.load()?.players[1]).0.as_ref(),
+            shared::PairingWindow::ordered(scheduled_duel.load()?.players[0], scheduled_duel.load()?.players[1]).1.as_ref()
+        ],
+        bump
+    )]
+    pub head_to_head: Option<Account<'info, ComponentData<HeadToHead>>>,
+}
+
+/// RefundDuelEscrow - Permissionless: return both escrowed stakes from a
+/// cancelled duel
+#[derive(Accounts)]
+pub struct RefundDuelEscrow<'info> {
+    /// CHECK: Entity for the duel
+    pub entity: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"duel", entity.key().as_ref()],
+        bump = duel.load()?.bump
+    )]
+    pub duel: Account<'info, ComponentData<DuelComponent>>,
+
+    /// CHECK: Must be seat one's wallet
+    #[account(mut, address = duel.load()?.player_one @ ScheduleError::InvalidOpponent)]
+    pub player_one_wallet: AccountInfo<'info>,
+
+    /// CHECK: Must be seat two's wallet
+    #[account(mut, address = duel.load()?.player_two @ ScheduleError::InvalidOpponent)]
+    pub player_two_wallet: AccountInfo<'info>,
+}
+
+/// Lifecycle of a scheduled duel
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum ScheduleStatus {
+    Proposed,   // Host escrowed, waiting on the opponent
+    Confirmed,  // Both stakes escrowed
+    Started,    // Both checked in; stakes escrowed on the opened duel
+    Forfeited,  // One side missed check-in and paid the forfeit fee
+    Cancelled,  // Never confirmed, or neither side showed
+}
+
+impl Default for ScheduleStatus {
+    fn default() -> Self {
+        ScheduleStatus::Proposed
+    }
+}
+
+/// ScheduledDuel - Escrowed booking for a duel at a fixed future time
+#[component]
+#[derive(Default)]
+pub struct ScheduledDuel {
+    pub schedule_id: u64,
+    pub players: [Pubkey; 2], // Host, opponent
+    pub stake: u64,
+    pub forfeit_fee: u64, // Paid by a no-show to the side that checked in
+    pub start_time: i64,
+    pub check_in_grace: i64,
+    pub escrowed: [bool; 2],
+    pub checked_in: [bool; 2],
+    pub status: ScheduleStatus,
+    pub created_at: i64,
+    // Hash of the CreateDuelParams the duel opens with; the opponent accepts these terms
+    pub params_hash: [u8; 32],
+}
+
+/// Commitment to the duel terms a booking opens with
+pub fn schedule_params_hash(params: &CreateDuelParams) -> Result<[u8; 32]> {
+    Ok(hashv(&[b"scheduled_duel", &params.try_to_vec()?]).to_bytes())
+}
+
+impl ScheduledDuel {
+    pub fn seat_of(&self, player: &Pubkey) -> Option<usize> {
+        self.players.iter().position(|seated| seated == player)
+    }
+
+    /// Check-in opens `check_in_grace` before the start and closes the same span after it
+    pub fn check_in_open(&self, now: i64) -> bool {
+        now >= self.start_time - self.check_in_grace && now <= self.start_time + self.check_in_grace
+    }
+
+    pub fn check_in_closed(&self, now: i64) -> bool {
+        now > self.start_time + self.check_in_grace
+    }
+
+    /// Lamports owed back to each seat when the booking resolves; a started
+    /// duel keeps both stakes in escrow, so nothing is owed back
+    pub fn resolution(&self) -> (ScheduleStatus, [u64; 2]) {
+        let escrowed = self.escrowed.map(|escrowed| if escrowed { self.stake } else { 0 });
+        if self.status == ScheduleStatus::Proposed {
+            return (ScheduleStatus::Cancelled, escrowed);
+        }
+        match self.checked_in {
+            [true, true] => (ScheduleStatus::Started, [0, 0]),
+            [false, false] => (ScheduleStatus::Cancelled, escrowed),
+            [present_first, _] => {
+                let present = if present_first { 0 } else { 1 };
+                let mut payouts = escrowed;
+                payouts[present] += self.forfeit_fee;
+                payouts[1 - present] -= self.forfeit_fee;
+                (ScheduleStatus::Forfeited, payouts)
+            }
+        }
+    }
+}
+
+impl<'info> ScheduleDuel<'info> {
+    pub fn process(
+        &mut self,
+        schedule_id: u64,
+        opponent: Pubkey,
+        start_time: i64,
+        stake: u64,
+        forfeit_fee: u64,
+        check_in_grace: i64,
+        params: CreateDuelParams,
+    ) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let host = self.host.key();
+        params.validate()?;
+        // The stakes become the entry fees, and no gate accounts are passed at start
+        require!(params.entry_fee == stake, ScheduleError::InvalidStake);
+        require!(!params.table_gate.is_gated(), GameError::InvalidTableGate);
+        require!(opponent != host && opponent != Pubkey::default(), ScheduleError::InvalidOpponent);
+        require!(start_time >= current_time + MIN_SCHEDULE_LEAD_TIME, ScheduleError::StartTooSoon);
+        require!(
+            check_in_grace > 0 && check_in_grace <= MAX_CHECK_IN_GRACE,
+            ScheduleError::InvalidCheckInGrace
+        );
+        require!(stake > 0 && forfeit_fee <= stake, ScheduleError::InvalidStake);
+
+        system_program::transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: self.host.to_account_info(),
+                    to: self.scheduled_duel.to_account_info(),
+                },
+            ),
+            stake,
+        )?;
+
+        let mut scheduled = self.scheduled_duel.load_init()?;
+        scheduled.schedule_id = schedule_id;
+        scheduled.players = [host, opponent];
+        scheduled.stake = stake;
+        scheduled.forfeit_fee = forfeit_fee;
+        scheduled.start_time = start_time;
+        scheduled.check_in_grace = check_in_grace;
+        scheduled.escrowed = [true, false];
+        scheduled.status = ScheduleStatus::Proposed;
+        scheduled.created_at = current_time;
+        scheduled.params_hash = schedule_params_hash(&params)?;
+
+        emit!(DuelScheduledEvent {
+            schedule_id,
+            host,
+            opponent,
+            start_time,
+            stake,
+            forfeit_fee,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> AcceptScheduledDuel<'info> {
+    pub fn process(&mut self, params_hash: [u8; 32], bumps: &AcceptScheduledDuelBumps) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let (host, stake) = {
+            let scheduled = self.scheduled_duel.load()?;
+            require!(scheduled.status == ScheduleStatus::Proposed, ScheduleError::InvalidStatus);
+            require_keys_eq!(scheduled.players[1], self.opponent.key(), ScheduleError::InvalidOpponent);
+            require!(scheduled.params_hash == params_hash, ScheduleError::TermsMismatch);
+            // Accepting after check-in opens would leave no time to show up
+            require!(
+                current_time < scheduled.start_time - scheduled.check_in_grace,
+                ScheduleError::StartTooSoon
+            );
+            (scheduled.players[0], scheduled.stake)
+        };
+        self.pairing_window.record(host, self.opponent.key(), bumps.pairing_window, current_time)?;
+        self.head_to_head.load_mut()?.open(host, self.opponent.key());
+
+        system_program::transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: self.opponent.to_account_info(),
+                    to: self.scheduled_duel.to_account_info(),
+                },
+            ),
+            stake,
+        )?;
+
+        let mut scheduled = self.scheduled_duel.load_mut()?;
+        scheduled.escrowed[1] = true;
+        scheduled.status = ScheduleStatus::Confirmed;
+
+        Ok(())
+    }
+}
+
+impl<'info> CheckInScheduledDuel<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let mut scheduled = self.scheduled_duel.load_mut()?;
+        require!(scheduled.status == ScheduleStatus::Confirmed, ScheduleError::InvalidStatus);
+        require!(scheduled.check_in_open(current_time), ScheduleError::CheckInClosed);
+        let seat = scheduled.seat_of(&self.player.key()).ok_or(ScheduleError::InvalidOpponent)?;
+        scheduled.checked_in[seat] = true;
+
+        emit!(ScheduledDuelCheckInEvent {
+            schedule_id: scheduled.schedule_id,
+            player: self.player.key(),
+            checked_in_at: current_time,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> StartScheduledDuel<'info> {
+    pub fn process(&mut self, params: CreateDuelParams, bumps: &StartScheduledDuelBumps) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let (schedule_id, players, stake) = {
+            let scheduled = self.scheduled_duel.load()?;
+            require!(scheduled.status == ScheduleStatus::Confirmed, ScheduleError::InvalidStatus);
+            require!(scheduled.resolution().0 == ScheduleStatus::Started, ScheduleError::NotBothCheckedIn);
+            require!(scheduled.params_hash == schedule_params_hash(&params)?, ScheduleError::TermsMismatch);
+            (scheduled.schedule_id, scheduled.players, scheduled.stake)
+        };
+
+        let reputations = [
+            open_profile(&self.host_psych)?,
+            open_profile(&self.opponent_psych)?,
+        ];
+        require!(
+            reputations.iter().all(|&reputation| reputation >= params.min_reputation),
+            GameError::ReputationTooLow
+        );
+
+        let duel_id = {
+            let mut duel = self.duel.load_init()?;
+            let mut betting = self.betting.load_init()?;
+            let mut host = self.host_player.load_init()?;
+            let mut opponent = self.opponent_player.load_init()?;
+            open_seated_duel(
+                &mut duel,
+                &mut betting,
+                [&mut host, &mut opponent],
+                players,
+                params,
+                shared::EventModifiers::default(),
+                current_time,
+            )?;
+            duel.bump = bumps.duel;
+            betting.bump = bumps.betting;
+            host.bump = bumps.host_player;
+            opponent.bump = bumps.opponent_player;
+            let profiles = [self.host_psych.load()?, self.opponent_psych.load()?];
+            fix_seat_ratings(&mut duel, [&mut host, &mut opponent], [&*profiles[0], &*profiles[1]]);
+            duel.escrowed_stake = stake;
+            duel.duel_id
+        };
+
+        // Both stakes move from the booking into the duel, which pays them out at settlement
+        let escrow = stake.checked_mul(2).ok_or(ScheduleError::InvalidStake)?;
+        **self.scheduled_duel.to_account_info().try_borrow_mut_lamports()? -= escrow;
+        **self.duel.to_account_info().try_borrow_mut_lamports()? += escrow;
+        self.scheduled_duel.load_mut()?.status = ScheduleStatus::Started;
+
+        emit!(ScheduledDuelResolvedEvent {
+            schedule_id,
+            players,
+            status: ScheduleStatus::Started,
+            payouts: [0, 0],
+        });
+        emit!(crate::DuelStartedEvent {
+            duel_id,
+            player_one: players[0],
+            player_two: players[1],
+            player_one_cosmetics: Default::default(),
+            player_two_cosmetics: Default::default(),
+            started_at: current_time,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> CrankScheduledDuel<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let (schedule_id, players, status, payouts, stake, checked_in) = {
+            let scheduled = self.scheduled_duel.load()?;
+            require!(
+                scheduled.status == ScheduleStatus::Proposed || scheduled.status == ScheduleStatus::Confirmed,
+                ScheduleError::InvalidStatus
+            );
+            require!(scheduled.check_in_closed(current_time), ScheduleError::CheckInStillOpen);
+            let (status, payouts) = scheduled.resolution();
+            // Both sides showed up, so the duel is started rather than refunded
+            require!(status != ScheduleStatus::Started, ScheduleError::ReadyToStart);
+            (scheduled.schedule_id, scheduled.players, status, payouts, scheduled.stake, scheduled.checked_in)
+        };
+
+        // The side that showed wins the forfeit; the booking id stands in for the duel id
+        if status == ScheduleStatus::Forfeited {
+            let present = if checked_in[0] { 0 } else { 1 };
+            let results = [0, 1].map(|seat| if seat == present { MatchResult::Win } else { MatchResult::Loss });
+            record_history(
+                &self.head_to_head,
+                [&self.host_recent_matches, &self.opponent_recent_matches],
+                Some(players[present]),
+                seat_summaries(schedule_id, players, [stake; 2], results, payouts, [0, 0], current_time),
+                stake * 2,
+            )?;
+        }
+
+        // The booking account is owned by this program, so lamports can be moved directly
+        let escrow_info = self.scheduled_duel.to_account_info();
+        **escrow_info.try_borrow_mut_lamports()? -= payouts[0] + payouts[1];
+        **self.host_wallet.try_borrow_mut_lamports()? += payouts[0];
+        **self.opponent_wallet.try_borrow_mut_lamports()? += payouts[1];
+
+        self.scheduled_duel.load_mut()?.status = status;
+
+        emit!(ScheduledDuelResolvedEvent {
+            schedule_id,
+            players,
+            status,
+            payouts,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> RefundDuelEscrow<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let (duel_id, refunds) = {
+            let mut duel = self.duel.load_mut()?;
+            require!(duel.game_state == GameState::Cancelled, ScheduleError::InvalidStatus);
+            require!(duel.escrowed_stake > 0, ScheduleError::NothingEscrowed);
+            // Stake an insurance claim already paid out stays with the pool
+            let refunds = [duel.refundable_stake(0), duel.refundable_stake(1)];
+            duel.escrowed_stake = 0;
+            (duel.duel_id, refunds)
+        };
+
+        // The duel is owned by this program, so lamports can be moved directly
+        **self.duel.to_account_info().try_borrow_mut_lamports()? -= refunds[0] + refunds[1];
+        **self.player_one_wallet.try_borrow_mut_lamports()? += refunds[0];
+        **self.player_two_wallet.try_borrow_mut_lamports()? += refunds[1];
+
+        emit!(DuelEscrowRefundedEvent {
+            duel_id,
+            players: [self.player_one_wallet.key(), self.player_two_wallet.key()],
+            refunds,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct DuelScheduledEvent {
+    pub schedule_id: u64,
+    pub host: Pubkey,
+    pub opponent: Pubkey,
+    pub start_time: i64,
+    pub stake: u64,
+    pub forfeit_fee: u64,
+}
+
+#[event]
+pub struct ScheduledDuelCheckInEvent {
+    pub schedule_id: u64,
+    pub player: Pubkey,
+    pub checked_in_at: i64,
+}
+
+#[event]
+pub struct ScheduledDuelResolvedEvent {
+    pub schedule_id: u64,
+    pub players: [Pubkey; 2],
+    pub status: ScheduleStatus,
+    pub payouts: [u64; 2],
+}
+
+#[event]
+pub struct DuelEscrowRefundedEvent {
+    pub duel_id: u64,
+    pub players: [Pubkey; 2],
+    pub refunds: [u64; 2],
+}
+
+#[error_code]
+pub enum ScheduleError {
+    #[msg("Opponent is not part of this scheduled duel")]
+    InvalidOpponent,
+    #[msg("Start time is too close to book or accept")]
+    StartTooSoon,
+    #[msg("Check-in grace window is out of range")]
+    InvalidCheckInGrace,
+    #[msg("Stake must be positive and cover the forfeit fee")]
+    InvalidStake,
+    #[msg("Scheduled duel is not in the required state")]
+    InvalidStatus,
+    #[msg("Check-in window is not open")]
+    CheckInClosed,
+    #[msg("Check-in window has not closed yet")]
+    CheckInStillOpen,
+    #[msg("Duel terms do not match the booking")]
+    TermsMismatch,
+    #[msg("Both players must check in before the duel starts")]
+    NotBothCheckedIn,
+    #[msg("Both players checked in; start the duel instead")]
+    ReadyToStart,
+    #[msg("Duel holds no escrowed stake")]
+    NothingEscrowed,
+}
@@ -0,0 +1,429 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use bolt_lang::*;
+use crate::components::*;
+use super::compliance::{ComplianceConfig, ComplianceCredential};
+
+/// Highest rake the operator may take from a spectator pool (10%)
+pub const MAX_SPECTATOR_RAKE_BPS: u16 = 1_000;
+
+/// Bets close once this round begins; rounds are zero-indexed, so the market
+/// stays open for the first round only
+pub const SPECTATOR_LOCK_ROUND: u8 = 1;
+
+/// OpenSpectatorPool - Operator opens a parimutuel side market on a duel
+#[derive(Accounts)]
+pub struct OpenSpectatorPool<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Entity for the duel
+    pub entity: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"duel", entity.key().as_ref()],
+        bump = duel.load()?.bump
+    )]
+    pub duel: Account<'info, ComponentData<DuelComponent>>,
+
+    #[account(
+        seeds = [b"compliance_config"],
+        bump
+    )]
+    pub compliance_config: Account<'info, ComponentData<ComplianceConfig>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<SpectatorPool>(),
+        seeds = [b"spectator_pool", entity.key().as_ref()],
+        bump
+    )]
+    pub spectator_pool: Account<'info, ComponentData<SpectatorPool>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// PlaceSpectatorBet - Back one seat of a duel from outside the table
+#[derive(Accounts)]
+pub struct PlaceSpectatorBet<'info> {
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+
+    /// CHECK: Entity for the duel
+    pub entity: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"duel", entity.key().as_ref()],
+        bump = duel.load()?.bump
+    )]
+    pub duel: Account<'info, ComponentData<DuelComponent>>,
+
+    #[account(
+        seeds = [b"compliance_config"],
+        bump
+    )]
+    pub compliance_config: Account<'info, ComponentData<ComplianceConfig>>,
+
+    #[account(
+        seeds = [b"compliance_credential", bettor.key().as_ref()],
+        bump
+    )]
+    pub bettor_credential: Option<Account<'info, ComponentData<ComplianceCredential>>>,
+
+    #[account(
+        mut,
+        seeds = [b"spectator_pool", entity.key().as_ref()],
+        bump
+    )]
+    pub spectator_pool: Account<'info, ComponentData<SpectatorPool>>,
+
+    #[account(
+        init_if_needed,
+        payer = bettor,
+        space = 8 + std::mem::size_of::<SpectatorBet>(),
+        seeds = [b"spectator_bet", bettor.key().as_ref(), entity.key().as_ref()],
+        bump
+    )]
+    pub spectator_bet: Account<'info, ComponentData<SpectatorBet>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// SettleSpectatorPool - Fix the winning side once the duel is over and pay
+/// the rake; anyone may call it
+#[derive(Accounts)]
+pub struct SettleSpectatorPool<'info> {
+    pub caller: Signer<'info>,
+
+    /// CHECK: Entity for the duel
+    pub entity: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"duel", entity.key().as_ref()],
+        bump = duel.load()?.bump
+    )]
+    pub duel: Account<'info, ComponentData<DuelComponent>>,
+
+    #[account(
+        mut,
+        seeds = [b"spectator_pool", entity.key().as_ref()],
+        bump
+    )]
+    pub spectator_pool: Account<'info, ComponentData<SpectatorPool>>,
+
+    /// CHECK: Must match the pool's rake recipient
+    #[account(mut, constraint = shared::is_wallet(&rake_recipient) @ shared::GameError::InvalidAccountOwner)]
+    pub rake_recipient: AccountInfo<'info>,
+}
+
+/// ClaimSpectatorPayout - Collect winnings, or the stake back if the pool was voided
+#[derive(Accounts)]
+pub struct ClaimSpectatorPayout<'info> {
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+
+    /// CHECK: Entity for the duel
+    pub entity: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"spectator_pool", entity.key().as_ref()],
+        bump
+    )]
+    pub spectator_pool: Account<'info, ComponentData<SpectatorPool>>,
+
+    #[account(
+        mut,
+        seeds = [b"spectator_bet", bettor.key().as_ref(), entity.key().as_ref()],
+        bump
+    )]
+    pub spectator_bet: Account<'info, ComponentData<SpectatorBet>>,
+}
+
+/// SpectatorPool - Parimutuel side market on one duel. The pool account is its
+/// own vault, kept apart from the players' pot.
+#[component]
+#[derive(Default)]
+pub struct SpectatorPool {
+    pub duel_id: u64,
+    pub rake_recipient: Pubkey,
+    pub rake_bps: u16,
+    pub min_bet: u64,
+    pub max_bet_per_backer: u64,
+    pub max_pool: u64,
+    pub totals: [u64; 2], // Indexed by seat
+    pub backers: u32,
+    pub settled: bool,
+    pub voided: bool,
+    pub winning_seat: u8,
+    pub rake_paid: u64,
+    pub total_paid_out: u64,
+}
+
+/// SpectatorBet - One bettor's stake in a duel's spectator pool
+#[component]
+#[derive(Default)]
+pub struct SpectatorBet {
+    pub bettor: Pubkey,
+    pub seat: u8,
+    pub amount: u64,
+    pub claimed: bool,
+}
+
+impl SpectatorPool {
+    pub fn total(&self) -> u64 {
+        self.totals[0] + self.totals[1]
+    }
+
+    pub fn rake(&self) -> u64 {
+        (self.total() as u128 * self.rake_bps as u128 / 10_000) as u64
+    }
+
+    /// What a bet is owed once the pool has settled: its stake back if the
+    /// pool was voided, otherwise its share of the pool net of rake
+    pub fn payout_for(&self, bet: &SpectatorBet) -> u64 {
+        if self.voided {
+            return bet.amount;
+        }
+        let winning_total = self.totals[self.winning_seat as usize];
+        if bet.seat != self.winning_seat || winning_total == 0 {
+            return 0;
+        }
+        let net_pool = self.total() - self.rake();
+        (bet.amount as u128 * net_pool as u128 / winning_total as u128) as u64
+    }
+}
+
+impl<'info> OpenSpectatorPool<'info> {
+    pub fn process(&mut self, rake_bps: u16, min_bet: u64, max_bet_per_backer: u64, max_pool: u64) -> Result<()> {
+        let config = self.compliance_config.load()?;
+        require_keys_eq!(config.authority, self.authority.key(), SpectatorError::Unauthorized);
+        require!(!config.spectator_betting_paused, SpectatorError::BettingPaused);
+        require!(rake_bps <= MAX_SPECTATOR_RAKE_BPS, SpectatorError::InvalidRake);
+        require!(
+            min_bet > 0 && min_bet <= max_bet_per_backer && max_bet_per_backer <= max_pool,
+            SpectatorError::InvalidLimits
+        );
+
+        let duel = self.duel.load()?;
+        require!(!duel.is_practice && !duel.is_private, SpectatorError::MarketClosed);
+        require!(duel.current_round < SPECTATOR_LOCK_ROUND, SpectatorError::MarketClosed);
+
+        let mut pool = self.spectator_pool.load_init()?;
+        pool.duel_id = duel.duel_id;
+        pool.rake_recipient = self.authority.key();
+        pool.rake_bps = rake_bps;
+        pool.min_bet = min_bet;
+        pool.max_bet_per_backer = max_bet_per_backer;
+        pool.max_pool = max_pool;
+
+        emit!(SpectatorPoolOpenedEvent {
+            duel_id: duel.duel_id,
+            rake_bps,
+            min_bet,
+            max_bet_per_backer,
+            max_pool,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> PlaceSpectatorBet<'info> {
+    pub fn process(&mut self, seat: u8, amount: u64, allowlist_proof: Vec<[u8; 32]>) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let bettor = self.bettor.key();
+
+        {
+            let config = self.compliance_config.load()?;
+            require!(!config.spectator_betting_paused, SpectatorError::BettingPaused);
+            let credential = match &self.bettor_credential {
+                Some(account) => Some(account.load()?.clone()),
+                None => None,
+            };
+            config.verify(&bettor, &allowlist_proof, credential.as_ref(), current_time)?;
+        }
+
+        let duel = self.duel.load()?;
+        require!(
+            duel.game_state != GameState::Completed && duel.game_state != GameState::Cancelled,
+            SpectatorError::MarketClosed
+        );
+        require!(duel.current_round < SPECTATOR_LOCK_ROUND, SpectatorError::MarketClosed);
+        // Players cannot trade on their own table
+        require!(duel.seat_of(&bettor).is_none(), SpectatorError::PlayerCannotBet);
+        require!(seat < 2, SpectatorError::InvalidSeat);
+
+        let mut pool = self.spectator_pool.load_mut()?;
+        let mut bet = self.spectator_bet.load_mut()?;
+        let is_new_backer = bet.bettor == Pubkey::default();
+        if is_new_backer {
+            bet.bettor = bettor;
+            bet.seat = seat;
+        }
+        require!(bet.seat == seat, SpectatorError::InvalidSeat);
+        require!(amount >= pool.min_bet, SpectatorError::BetOutOfLimits);
+        require!(bet.amount + amount <= pool.max_bet_per_backer, SpectatorError::BetOutOfLimits);
+        require!(pool.total() + amount <= pool.max_pool, SpectatorError::PoolFull);
+
+        system_program::transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: self.bettor.to_account_info(),
+                    to: self.spectator_pool.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        bet.amount += amount;
+        pool.totals[seat as usize] += amount;
+        if is_new_backer {
+            pool.backers += 1;
+        }
+
+        emit!(SpectatorBetPlacedEvent {
+            duel_id: duel.duel_id,
+            bettor,
+            seat,
+            amount,
+            totals: pool.totals,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> SettleSpectatorPool<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let duel = self.duel.load()?;
+        let mut pool = self.spectator_pool.load_mut()?;
+        require!(!pool.settled, SpectatorError::AlreadySettled);
+        require_keys_eq!(pool.rake_recipient, self.rake_recipient.key(), SpectatorError::Unauthorized);
+
+        match duel.game_state {
+            GameState::Completed => {
+                let winner = duel.winner.ok_or(SpectatorError::MarketOpen)?;
+                let seat = duel.seat_of(&winner).ok_or(SpectatorError::InvalidSeat)?;
+                pool.winning_seat = seat;
+                // Nobody backed the winner, so there is no one to pay: refund everyone
+                pool.voided = pool.totals[seat as usize] == 0;
+            },
+            GameState::Cancelled => pool.voided = true,
+            _ => return err!(SpectatorError::MarketOpen),
+        }
+
+        let rake = if pool.voided { 0 } else { pool.rake() };
+        if rake > 0 {
+            // The pool is owned by this program, so lamports can be moved directly
+            **self.spectator_pool.to_account_info().try_borrow_mut_lamports()? -= rake;
+            **self.rake_recipient.try_borrow_mut_lamports()? += rake;
+        }
+        pool.rake_paid = rake;
+        pool.settled = true;
+
+        emit!(SpectatorPoolSettledEvent {
+            duel_id: pool.duel_id,
+            winning_seat: pool.winning_seat,
+            voided: pool.voided,
+            totals: pool.totals,
+            rake,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> ClaimSpectatorPayout<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let mut pool = self.spectator_pool.load_mut()?;
+        let mut bet = self.spectator_bet.load_mut()?;
+        require!(pool.settled, SpectatorError::MarketOpen);
+        require!(!bet.claimed, SpectatorError::AlreadyClaimed);
+
+        let payout = pool.payout_for(&bet);
+        require!(payout > 0, SpectatorError::NothingToClaim);
+
+        **self.spectator_pool.to_account_info().try_borrow_mut_lamports()? -= payout;
+        **self.bettor.to_account_info().try_borrow_mut_lamports()? += payout;
+
+        bet.claimed = true;
+        pool.total_paid_out += payout;
+
+        emit!(SpectatorPayoutClaimedEvent {
+            duel_id: pool.duel_id,
+            bettor: bet.bettor,
+            seat: bet.seat,
+            payout,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct SpectatorPoolOpenedEvent {
+    pub duel_id: u64,
+    pub rake_bps: u16,
+    pub min_bet: u64,
+    pub max_bet_per_backer: u64,
+    pub max_pool: u64,
+}
+
+#[event]
+pub struct SpectatorBetPlacedEvent {
+    pub duel_id: u64,
+    pub bettor: Pubkey,
+    pub seat: u8,
+    pub amount: u64,
+    pub totals: [u64; 2],
+}
+
+#[event]
+pub struct SpectatorPoolSettledEvent {
+    pub duel_id: u64,
+    pub winning_seat: u8,
+    pub voided: bool,
+    pub totals: [u64; 2],
+    pub rake: u64,
+}
+
+#[event]
+pub struct SpectatorPayoutClaimedEvent {
+    pub duel_id: u64,
+    pub bettor: Pubkey,
+    pub seat: u8,
+    pub payout: u64,
+}
+
+#[error_code]
+pub enum SpectatorError {
+    #[msg("Only the compliance authority can open spectator pools")]
+    Unauthorized,
+    #[msg("Spectator betting is paused")]
+    BettingPaused,
+    #[msg("Spectator rake exceeds the maximum")]
+    InvalidRake,
+    #[msg("Spectator pool limits are inconsistent")]
+    InvalidLimits,
+    #[msg("Spectator market is closed for this duel")]
+    MarketClosed,
+    #[msg("Duel players cannot bet in their own spectator pool")]
+    PlayerCannotBet,
+    #[msg("Invalid seat for a spectator bet")]
+    InvalidSeat,
+    #[msg("Bet is outside the pool's limits")]
+    BetOutOfLimits,
+    #[msg("Spectator pool has reached its maximum size")]
+    PoolFull,
+    #[msg("Duel has not finished yet")]
+    MarketOpen,
+    #[msg("Spectator pool already settled")]
+    AlreadySettled,
+    #[msg("Spectator payout already claimed")]
+    AlreadyClaimed,
+    #[msg("Nothing to claim for this bet")]
+    NothingToClaim,
+}
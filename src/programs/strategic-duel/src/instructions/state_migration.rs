@@ -0,0 +1,466 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use bolt_lang::*;
+use crate::components::*;
+
+/// ExportStateHash - Freeze a live duel and commit to its serialized state
+#[derive(Accounts)]
+pub struct ExportStateHash<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::StrategicDuel>,
+
+    #[account(constraint = program_data.upgrade_authority_address == Some(authority.key()) @ MigrationError::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+
+    /// CHECK: Entity for the duel
+    pub entity: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"duel", entity.key().as_ref()],
+        bump = duel.load()?.bump
+    )]
+    pub duel: Account<'info, ComponentData<DuelComponent>>,
+
+    #[account(
+        seeds = [b"betting", entity.key().as_ref()],
+        bump = betting.load()?.bump
+    )]
+    pub betting: Account<'info, ComponentData<BettingComponent>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + std::mem::size_of::<StateExport>(),
+        seeds = [b"state_export", entity.key().as_ref()],
+        bump
+    )]
+    pub state_export: Account<'info, ComponentData<StateExport>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// ImportState - Recreate an exported duel under a new entity, possibly in a new program
+#[derive(Accounts)]
+#[instruction(source_program: Pubkey, source_entity: Pubkey)]
+pub struct ImportState<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::StrategicDuel>,
+
+    #[account(constraint = program_data.upgrade_authority_address == Some(authority.key()) @ MigrationError::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+
+    /// CHECK: Export record in the source program, validated against its PDA and owner
+    pub source_export: AccountInfo<'info>,
+
+    /// CHECK: Entity the duel is migrated to
+    pub entity: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<DuelComponent>(),
+        seeds = [b"duel", entity.key().as_ref()],
+        bump
+    )]
+    pub duel: Account<'info, ComponentData<DuelComponent>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<BettingComponent>() + MAX_BLIND_LEVELS * std::mem::size_of::<BlindLevel>(),
+        seeds = [b"betting", entity.key().as_ref()],
+        bump
+    )]
+    pub betting: Account<'info, ComponentData<BettingComponent>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// BackfillComponentBumps - Store canonical bumps on components created before they were recorded
+#[derive(Accounts)]
+pub struct BackfillComponentBumps<'info> {
+    pub cranker: Signer<'info>,
+
+    /// CHECK: Entity for the duel
+    pub entity: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"duel", entity.key().as_ref()],
+        bump
+    )]
+    pub duel: Account<'info, ComponentData<DuelComponent>>,
+
+    #[account(
+        mut,
+        seeds = [b"betting", entity.key().as_ref()],
+        bump
+    )]
+    pub betting: Account<'info, ComponentData<BettingComponent>>,
+
+    #[account(
+        mut,
+        seeds = [b"player", duel.load()?.player_one.as_ref(), entity.key().as_ref()],
+        bump
+    )]
+    pub player_one: Account<'info, ComponentData<PlayerComponent>>,
+
+    /// Absent while the duel is still waiting for its second seat
+    #[account(
+        mut,
+        seeds = [b"player", duel.load()?.player_two.as_ref(), entity.key().as_ref()],
+        bump
+    )]
+    pub player_two: Option<Account<'info, ComponentData<PlayerComponent>>>,
+
+    #[account(
+        mut,
+        seeds = [b"psych", duel.load()?.player_one.as_ref()],
+        bump
+    )]
+    pub player_one_psych: Option<Account<'info, ComponentData<PsychProfileComponent>>>,
+
+    #[account(
+        mut,
+        seeds = [b"psych", duel.load()?.player_two.as_ref()],
+        bump
+    )]
+    pub player_two_psych: Option<Account<'info, ComponentData<PsychProfileComponent>>>,
+}
+
+/// MigratePsychProfile - Move a profile written before per-queue ratings into the ranked track
+#[derive(Accounts)]
+#[instruction(player: Pubkey)]
+pub struct MigratePsychProfile<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Still in the legacy layout, so it is decoded by hand
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"psych", player.as_ref()],
+        bump
+    )]
+    pub psych_profile: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// PsychProfileComponent as written before ratings were split per queue
+#[derive(AnchorDeserialize)]
+pub struct LegacyPsychProfile {
+    pub player: Pubkey,
+    pub avg_decision_time: u32,
+    pub decision_variance: u32,
+    pub bluff_frequency: u16,
+    pub fold_frequency: u16,
+    pub aggression_score: u16,
+    pub consistency_rating: u16,
+    pub pressure_response: u16,
+    pub late_game_behavior: u16,
+    pub sample_size: u32,
+    pub last_updated: i64,
+    pub duels_started: u32,
+    pub duels_completed: u32,
+    pub timeouts: u32,
+    pub disputes: u32,
+    pub collusion_flags: u32,
+    pub reputation: u16,
+    pub skill_rating: u32,
+    pub forfeits: u32,
+    pub rating_deviation: u16,
+    pub rated_games: u32,
+    pub glicko: shared::Glicko2Rating,
+}
+
+impl LegacyPsychProfile {
+    /// Every pre-split rating was a ranked rating; the other queues start fresh
+    pub fn into_current(self, bump: u8) -> PsychProfileComponent {
+        let mut tracks = [RatingTrack::default(); shared::QUEUE_TYPES];
+        tracks[QueueType::Ranked.index()] = RatingTrack {
+            skill_rating: self.skill_rating,
+            rating_deviation: self.rating_deviation,
+            rated_games: self.rated_games,
+            glicko: self.glicko,
+            ..RatingTrack::default()
+        };
+        PsychProfileComponent {
+            player: self.player,
+            avg_decision_time: self.avg_decision_time,
+            decision_variance: self.decision_variance,
+            bluff_frequency: self.bluff_frequency,
+            fold_frequency: self.fold_frequency,
+            aggression_score: self.aggression_score,
+            consistency_rating: self.consistency_rating,
+            pressure_response: self.pressure_response,
+            late_game_behavior: self.late_game_behavior,
+            sample_size: self.sample_size,
+            last_updated: self.last_updated,
+            duels_started: self.duels_started,
+            duels_completed: self.duels_completed,
+            timeouts: self.timeouts,
+            disputes: self.disputes,
+            collusion_flags: self.collusion_flags,
+            reputation: self.reputation,
+            forfeits: self.forfeits,
+            tracks,
+            bump,
+        }
+    }
+}
+
+/// StateExport - Commitment to a frozen duel's state, read back during import
+#[component]
+#[derive(Default)]
+pub struct StateExport {
+    pub entity: Pubkey,
+    pub duel_id: u64,
+    pub state_hash: [u8; 32],
+    pub duel_len: u32,
+    pub betting_len: u32,
+    pub exported_at: i64,
+    pub slot: u64,
+}
+
+/// Domain-separated hash over the serialized components
+pub fn state_hash(duel_bytes: &[u8], betting_bytes: &[u8]) -> [u8; 32] {
+    hashv(&[b"strategic_duel_state_v1", duel_bytes, betting_bytes]).to_bytes()
+}
+
+impl<'info> ExportStateHash<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let clock = Clock::get()?;
+        let mut duel = self.duel.load_mut()?;
+        let betting = self.betting.load()?;
+
+        require!(!duel.migration_locked, MigrationError::AlreadyExported);
+
+        // Lock first so the committed bytes match what import will receive
+        duel.migration_locked = true;
+        let duel_bytes = duel.try_to_vec()?;
+        let betting_bytes = betting.try_to_vec()?;
+        let hash = state_hash(&duel_bytes, &betting_bytes);
+
+        let mut export = self.state_export.load_mut()?;
+        export.entity = self.entity.key();
+        export.duel_id = duel.duel_id;
+        export.state_hash = hash;
+        export.duel_len = duel_bytes.len() as u32;
+        export.betting_len = betting_bytes.len() as u32;
+        export.exported_at = clock.unix_timestamp;
+        export.slot = clock.slot;
+
+        emit!(StateExportedEvent {
+            duel_id: duel.duel_id,
+            entity: self.entity.key(),
+            state_hash: hash,
+            slot: clock.slot,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> ImportState<'info> {
+    /// Import the exported bytes after checking them against the source export record.
+    /// A layout-breaking upgrade must decode the previous layout here before import.
+    pub fn process(
+        &mut self,
+        source_program: Pubkey,
+        source_entity: Pubkey,
+        duel_bytes: Vec<u8>,
+        betting_bytes: Vec<u8>,
+        bumps: &ImportStateBumps,
+    ) -> Result<()> {
+        let (expected_export, _) = Pubkey::find_program_address(
+            &[b"state_export", source_entity.as_ref()],
+            &source_program,
+        );
+        require_keys_eq!(self.source_export.key(), expected_export, MigrationError::InvalidExport);
+        require_keys_eq!(*self.source_export.owner, source_program, MigrationError::InvalidExport);
+
+        let export = {
+            let data = self.source_export.try_borrow_data()?;
+            require!(data.len() > 8, MigrationError::InvalidExport);
+            StateExport::deserialize(&mut &data[8..])?
+        };
+        require_keys_eq!(export.entity, source_entity, MigrationError::InvalidExport);
+        require!(
+            state_hash(&duel_bytes, &betting_bytes) == export.state_hash,
+            MigrationError::StateHashMismatch
+        );
+
+        let mut imported_duel = DuelComponent::deserialize(&mut &duel_bytes[..])?;
+        let mut imported_betting = BettingComponent::deserialize(&mut &betting_bytes[..])?;
+        require!(imported_duel.duel_id == export.duel_id, MigrationError::StateHashMismatch);
+
+        // The migrated duel resumes play from the current time
+        imported_duel.migration_locked = false;
+        imported_duel.last_action_time = Clock::get()?.unix_timestamp;
+        imported_duel.grace_extension = 0;
+        // Bumps belong to the new entity's addresses, not the source's
+        imported_duel.bump = bumps.duel;
+        imported_betting.bump = bumps.betting;
+
+        *self.duel.load_init()? = imported_duel;
+        *self.betting.load_init()? = imported_betting;
+
+        emit!(StateImportedEvent {
+            duel_id: export.duel_id,
+            source_program,
+            source_entity,
+            entity: self.entity.key(),
+            state_hash: export.state_hash,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> BackfillComponentBumps<'info> {
+    /// Permissionless: the canonical bump is fixed by the address, so writing it
+    /// cannot change anything but a stale zero. Older accounts were sized from
+    /// `size_of`, which leaves room for the extra byte.
+    pub fn process(&mut self, bumps: &BackfillComponentBumpsBumps) -> Result<()> {
+        let mut backfilled = 0u8;
+
+        let mut duel = self.duel.load_mut()?;
+        if duel.bump != bumps.duel {
+            duel.bump = bumps.duel;
+            backfilled += 1;
+        }
+        let mut betting = self.betting.load_mut()?;
+        if betting.bump != bumps.betting {
+            betting.bump = bumps.betting;
+            backfilled += 1;
+        }
+        let mut player_one = self.player_one.load_mut()?;
+        if player_one.bump != bumps.player_one {
+            player_one.bump = bumps.player_one;
+            backfilled += 1;
+        }
+        if let Some(account) = &self.player_two {
+            let mut player_two = account.load_mut()?;
+            if player_two.bump != bumps.player_two {
+                player_two.bump = bumps.player_two;
+                backfilled += 1;
+            }
+        }
+        for (account, bump) in [
+            (&self.player_one_psych, bumps.player_one_psych),
+            (&self.player_two_psych, bumps.player_two_psych),
+        ] {
+            if let Some(account) = account {
+                let mut psych = account.load_mut()?;
+                if psych.bump != bump {
+                    psych.bump = bump;
+                    backfilled += 1;
+                }
+            }
+        }
+
+        emit!(ComponentBumpsBackfilledEvent {
+            duel_id: duel.duel_id,
+            entity: self.entity.key(),
+            backfilled,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> MigratePsychProfile<'info> {
+    /// Permissionless: the payer only covers the extra rent, and the stored
+    /// values are carried over unchanged. Profiles already in the current
+    /// layout are rejected.
+    pub fn process(&mut self, player: Pubkey, bumps: &MigratePsychProfileBumps) -> Result<()> {
+        let space = 8 + std::mem::size_of::<PsychProfileComponent>();
+        let info = self.psych_profile.to_account_info();
+        require!(info.data_len() < space, MigrationError::AlreadyMigrated);
+
+        let legacy = {
+            let data = info.try_borrow_data()?;
+            require!(data.len() > 8, MigrationError::AlreadyMigrated);
+            LegacyPsychProfile::deserialize(&mut &data[8..])?
+        };
+
+        let required = Rent::get()?.minimum_balance(space);
+        let top_up = required.saturating_sub(info.lamports());
+        if top_up > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: self.payer.to_account_info(),
+                        to: info.clone(),
+                    },
+                ),
+                top_up,
+            )?;
+        }
+        info.realloc(space, true)?;
+
+        // The discriminator stays; everything after it is rewritten
+        let profile = legacy.into_current(bumps.psych_profile);
+        profile.serialize(&mut &mut info.try_borrow_mut_data()?[8..])?;
+
+        emit!(PsychProfileMigratedEvent {
+            player,
+            ranked_rating: profile.track(QueueType::Ranked).rating(),
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct StateExportedEvent {
+    pub duel_id: u64,
+    pub entity: Pubkey,
+    pub state_hash: [u8; 32],
+    pub slot: u64,
+}
+
+#[event]
+pub struct StateImportedEvent {
+    pub duel_id: u64,
+    pub source_program: Pubkey,
+    pub source_entity: Pubkey,
+    pub entity: Pubkey,
+    pub state_hash: [u8; 32],
+}
+
+#[event]
+pub struct ComponentBumpsBackfilledEvent {
+    pub duel_id: u64,
+    pub entity: Pubkey,
+    pub backfilled: u8,
+}
+
+#[event]
+pub struct PsychProfileMigratedEvent {
+    pub player: Pubkey,
+    pub ranked_rating: u32,
+}
+
+#[error_code]
+pub enum MigrationError {
+    #[msg("Only the program upgrade authority can migrate state")]
+    Unauthorized,
+    #[msg("Duel state already exported")]
+    AlreadyExported,
+    #[msg("Export record does not match the source program or entity")]
+    InvalidExport,
+    #[msg("Imported state does not match the exported hash")]
+    StateHashMismatch,
+    #[msg("Account is already in the current layout")]
+    AlreadyMigrated,
+}
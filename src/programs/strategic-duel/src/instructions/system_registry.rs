@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use bolt_lang::*;
+use crate::BoltWorldComponent;
+
+/// SetSystemActive - World authority enables or disables a registered system
+#[derive(Accounts)]
+pub struct SetSystemActive<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"world"],
+        bump,
+        constraint = world.load()?.authority == authority.key() @ SystemRegistryError::Unauthorized
+    )]
+    pub world: Account<'info, ComponentData<BoltWorldComponent>>,
+}
+
+impl<'info> SetSystemActive<'info> {
+    pub fn process(&mut self, system_id: u32, active: bool) -> Result<()> {
+        let mut world = self.world.load_mut()?;
+        world.system_registry.set_active(system_id, active)?;
+        world.last_updated = Clock::get()?.unix_timestamp;
+
+        emit!(SystemActivationChangedEvent {
+            system_id,
+            active,
+            changed_by: self.authority.key(),
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct SystemActivationChangedEvent {
+    pub system_id: u32,
+    pub active: bool,
+    pub changed_by: Pubkey,
+}
+
+#[error_code]
+pub enum SystemRegistryError {
+    #[msg("Only the world authority can change the system registry")]
+    Unauthorized,
+    #[msg("System is not registered in the world")]
+    SystemNotRegistered,
+    #[msg("System is disabled")]
+    SystemDisabled,
+    #[msg("A component the system depends on is missing or disabled")]
+    MissingComponentDependency,
+    #[msg("World is not active")]
+    WorldInactive,
+}
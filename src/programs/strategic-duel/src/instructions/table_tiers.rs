@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+use bolt_lang::*;
+use crate::components::*;
+use super::admin_config::AdminConfigError;
+use super::GameError;
+
+/// InitializeTableTiers - Fix the mint each gated table tier is entered with; super admin only
+#[derive(Accounts)]
+pub struct InitializeTableTiers<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [shared::ADMIN_CONFIG_SEED],
+        bump = admin_config.bump,
+        constraint = admin_config.super_admin == authority.key() @ AdminConfigError::NotSuperAdmin
+    )]
+    pub admin_config: Account<'info, shared::AdminConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<TableTierConfig>(),
+        seeds = [b"table_tiers"],
+        bump
+    )]
+    pub table_tiers: Account<'info, ComponentData<TableTierConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// UpdateTableTiers - Replace the gated tiers' requirements
+#[derive(Accounts)]
+pub struct UpdateTableTiers<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"table_tiers"],
+        bump
+    )]
+    pub table_tiers: Account<'info, ComponentData<TableTierConfig>>,
+}
+
+/// TableTierConfig - Governance-set requirement behind each gated tier, so a
+/// creator cannot label a table VIP and gate it on a token of their choosing
+#[component]
+#[derive(Default)]
+pub struct TableTierConfig {
+    pub authority: Pubkey,
+    // Indexed by tier; Standard's entry is unused
+    pub gates: [TableGate; 3],
+    pub updated_at: i64,
+}
+
+impl TableTierConfig {
+    /// A gated table must use its tier's mint and ask for at least the tier's minimum
+    pub fn allows(&self, gate: &TableGate) -> bool {
+        if !gate.is_gated() {
+            return true;
+        }
+        let required = &self.gates[gate.tier as usize];
+        required.gate_mint != Pubkey::default()
+            && gate.gate_mint == required.gate_mint
+            && gate.min_balance >= required.min_balance
+    }
+
+    fn apply(&mut self, gates: [TableGate; 3]) -> Result<()> {
+        for (index, gate) in gates.iter().enumerate() {
+            require!(gate.tier as usize == index && gate.is_valid(), TableTierError::InvalidTiers);
+        }
+        self.gates = gates;
+        self.updated_at = Clock::get()?.unix_timestamp;
+
+        emit!(TableTiersUpdatedEvent {
+            authority: self.authority,
+            gates,
+        });
+
+        Ok(())
+    }
+}
+
+/// Reject a gated table whose mint or minimum does not match its tier
+pub fn require_tier_gate(
+    table_tiers: &Option<Account<ComponentData<TableTierConfig>>>,
+    gate: &TableGate,
+) -> Result<()> {
+    if !gate.is_gated() {
+        return Ok(());
+    }
+    let config = table_tiers.as_ref().ok_or(TableTierError::TiersNotConfigured)?;
+    require!(config.load()?.allows(gate), GameError::InvalidTableGate);
+    Ok(())
+}
+
+impl<'info> InitializeTableTiers<'info> {
+    pub fn process(&mut self, gates: [TableGate; 3]) -> Result<()> {
+        let mut config = self.table_tiers.load_init()?;
+        config.authority = self.authority.key();
+        config.apply(gates)
+    }
+}
+
+impl<'info> UpdateTableTiers<'info> {
+    pub fn process(&mut self, gates: [TableGate; 3]) -> Result<()> {
+        let mut config = self.table_tiers.load_mut()?;
+        require_keys_eq!(config.authority, self.authority.key(), TableTierError::Unauthorized);
+        config.apply(gates)
+    }
+}
+
+#[event]
+pub struct TableTiersUpdatedEvent {
+    pub authority: Pubkey,
+    pub gates: [TableGate; 3],
+}
+
+#[error_code]
+pub enum TableTierError {
+    #[msg("Each tier needs its own valid gate, listed in tier order")]
+    InvalidTiers,
+    #[msg("Gated tables need the table tier config")]
+    TiersNotConfigured,
+    #[msg("Only the tier config authority can update it")]
+    Unauthorized,
+}
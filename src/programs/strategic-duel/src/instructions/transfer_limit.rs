@@ -0,0 +1,153 @@
+use anchor_lang::prelude::*;
+use bolt_lang::*;
+use crate::components::*;
+use super::admin_config::AdminConfigError;
+
+/// Default window over which net transfers between two players are tracked
+pub const DEFAULT_TRANSFER_WINDOW: i64 = 24 * 60 * 60; // 1 day
+
+/// InitializeTransferLimits - Configure the chip-dumping guard; super admin only
+#[derive(Accounts)]
+pub struct InitializeTransferLimits<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [shared::ADMIN_CONFIG_SEED],
+        bump = admin_config.bump,
+        constraint = admin_config.super_admin == authority.key() @ AdminConfigError::NotSuperAdmin
+    )]
+    pub admin_config: Account<'info, shared::AdminConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<TransferLimitConfig>(),
+        seeds = [b"transfer_limit_config"],
+        bump
+    )]
+    pub config: Account<'info, ComponentData<TransferLimitConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// UpdateTransferLimits - Adjust the cap or window
+#[derive(Accounts)]
+pub struct UpdateTransferLimits<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"transfer_limit_config"],
+        bump
+    )]
+    pub config: Account<'info, ComponentData<TransferLimitConfig>>,
+}
+
+/// TransferLimitConfig - Global cap on net lamports moving between two players
+#[component]
+#[derive(Default)]
+pub struct TransferLimitConfig {
+    pub authority: Pubkey,
+    pub max_net_transfer: u64,
+    pub window_seconds: i64,
+    pub flagged_pairs: u64,
+}
+
+/// TransferLimit - Net flow between a pair of players within the current window.
+/// Keyed by the ordered pair so both directions net against each other.
+#[component]
+#[derive(Default)]
+pub struct TransferLimit {
+    pub first: Pubkey,
+    pub second: Pubkey,
+    pub window_start: i64,
+    pub net_flow: i64, // positive: first -> second
+    pub duels_in_window: u32,
+    pub flagged: bool,
+    pub flag_count: u32,
+    pub last_flagged_at: i64,
+}
+
+impl TransferLimit {
+    /// Record `amount` moving from `from` to its opponent. Returns true if the
+    /// pair's net flow exceeded the cap, which flags it for review without blocking.
+    pub fn record(&mut self, config: &TransferLimitConfig, from: &Pubkey, amount: u64, now: i64) -> bool {
+        let window = if config.window_seconds > 0 { config.window_seconds } else { DEFAULT_TRANSFER_WINDOW };
+        if now >= self.window_start + window {
+            self.window_start = now;
+            self.net_flow = 0;
+            self.duels_in_window = 0;
+        }
+
+        let amount = amount.min(i64::MAX as u64) as i64;
+        self.net_flow = if *from == self.first {
+            self.net_flow.saturating_add(amount)
+        } else {
+            self.net_flow.saturating_sub(amount)
+        };
+        self.duels_in_window += 1;
+
+        let exceeded = config.max_net_transfer > 0 && self.net_flow.unsigned_abs() > config.max_net_transfer;
+        if exceeded {
+            self.flagged = true;
+            self.flag_count += 1;
+            self.last_flagged_at = now;
+        }
+        exceeded
+    }
+}
+
+/// The duel's players in canonical order, used to derive the pair's limit PDA
+pub fn ordered_pair(duel: &DuelComponent) -> (Pubkey, Pubkey) {
+    if duel.player_one <= duel.player_two {
+        (duel.player_one, duel.player_two)
+    } else {
+        (duel.player_two, duel.player_one)
+    }
+}
+
+impl<'info> InitializeTransferLimits<'info> {
+    pub fn process(&mut self, max_net_transfer: u64, window_seconds: i64) -> Result<()> {
+        require!(window_seconds > 0, TransferLimitError::InvalidWindow);
+
+        let mut config = self.config.load_init()?;
+        config.authority = self.authority.key();
+        config.max_net_transfer = max_net_transfer;
+        config.window_seconds = window_seconds;
+
+        Ok(())
+    }
+}
+
+impl<'info> UpdateTransferLimits<'info> {
+    pub fn process(&mut self, max_net_transfer: u64, window_seconds: i64) -> Result<()> {
+        require!(window_seconds > 0, TransferLimitError::InvalidWindow);
+
+        let mut config = self.config.load_mut()?;
+        require_keys_eq!(config.authority, self.authority.key(), TransferLimitError::Unauthorized);
+        config.max_net_transfer = max_net_transfer;
+        config.window_seconds = window_seconds;
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct ChipDumpingFlaggedEvent {
+    pub duel_id: u64,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+    pub net_flow: i64,
+    pub duels_in_window: u32,
+    pub window_start: i64,
+}
+
+#[error_code]
+pub enum TransferLimitError {
+    #[msg("Transfer window must be positive")]
+    InvalidWindow,
+    #[msg("Only the config authority can update transfer limits")]
+    Unauthorized,
+}
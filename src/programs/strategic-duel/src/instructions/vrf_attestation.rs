@@ -8,8 +8,8 @@ pub struct VrfAttestation<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
 
-    /// CHECK: TEE attestation authority
-    pub tee_authority: AccountInfo<'info>,
+    /// TEE attestation authority; co-signs every attestation it vouches for
+    pub tee_authority: Signer<'info>,
 
     /// CHECK: World PDA
     #[account(mut)]
@@ -22,7 +22,7 @@ pub struct VrfAttestation<'info> {
     #[account(
         mut,
         seeds = [b"duel", entity.key().as_ref()],
-        bump
+        bump = duel.load()?.bump
     )]
     pub duel: Account<'info, ComponentData<DuelComponent>>,
 
@@ -38,7 +38,7 @@ pub struct VrfAttestation<'info> {
     #[account(
         mut,
         seeds = [b"betting", entity.key().as_ref()],
-        bump
+        bump = betting.load()?.bump
     )]
     pub betting: Account<'info, ComponentData<BettingComponent>>,
 
@@ -98,6 +98,15 @@ impl<'info> VrfAttestation<'info> {
             GameError::InvalidGameState
         );
         require!(duel.resolution_pending, GameError::NoResolutionPending);
+        require!(
+            super::reveal_status(&duel, current_time) != super::RevealStatus::Outstanding,
+            super::EntropyError::RevealsOutstanding
+        );
+        if duel.outcome_evaluator == OutcomeEvaluator::Weighted {
+            require!(weights_hash == duel.weights_hash, VrfError::InvalidWeightsHash);
+        }
+        // The attested transcript must be the one accumulated action by action
+        require!(transcript_hash == duel.action_transcript, VrfError::InvalidTranscriptHash);
 
         // Initialize or update attestation
         attestation.duel_id = duel.duel_id;
@@ -126,6 +135,7 @@ impl<'info> VrfAttestation<'info> {
 
         // Update duel state
         duel.vrf_verified = true;
+        duel.transcript_validated = true;
         duel.ready_for_settlement = true;
 
         emit!(VrfAttestationEvent {
@@ -0,0 +1,291 @@
+use anchor_lang::prelude::*;
+use bolt_lang::*;
+use crate::components::*;
+use super::GameError;
+use super::admin_config::AdminConfigError;
+
+/// How long a provider has to answer a resolution request before it can be expired
+pub const VRF_REQUEST_TIMEOUT: i64 = 10 * 60; // 10 minutes
+
+/// InitializeVrfRequestRegistry - Set the providers resolution requests are
+/// assigned to; super admin only
+#[derive(Accounts)]
+pub struct InitializeVrfRequestRegistry<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [shared::ADMIN_CONFIG_SEED],
+        bump = admin_config.bump,
+        constraint = admin_config.super_admin == authority.key() @ AdminConfigError::NotSuperAdmin
+    )]
+    pub admin_config: Account<'info, shared::AdminConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<VrfRequestRegistry>(),
+        seeds = [b"vrf_requests"],
+        bump
+    )]
+    pub vrf_requests: Account<'info, ComponentData<VrfRequestRegistry>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// RequestVrf - Open the duel's resolution request so a silent provider can be
+/// expired. Each duel has its own request PDA, paid for by the requester.
+#[derive(Accounts)]
+pub struct RequestVrf<'info> {
+    #[account(mut)]
+    pub requester: Signer<'info>,
+
+    /// CHECK: Entity for the duel
+    pub entity: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"duel", entity.key().as_ref()],
+        bump = duel.load()?.bump
+    )]
+    pub duel: Account<'info, ComponentData<DuelComponent>>,
+
+    #[account(
+        seeds = [b"vrf_requests"],
+        bump
+    )]
+    pub vrf_requests: Account<'info, ComponentData<VrfRequestRegistry>>,
+
+    #[account(
+        init,
+        payer = requester,
+        space = 8 + std::mem::size_of::<VrfRequest>(),
+        seeds = [b"vrf_request", entity.key().as_ref()],
+        bump
+    )]
+    pub vrf_request: Account<'info, ComponentData<VrfRequest>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// ExpireVrfRequest - Hand a stale request to the fallback provider, or void
+/// the duel and refund both stakes once no provider is left
+#[derive(Accounts)]
+pub struct ExpireVrfRequest<'info> {
+    pub caller: Signer<'info>,
+
+    /// CHECK: Entity for the duel
+    pub entity: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"duel", entity.key().as_ref()],
+        bump = duel.load()?.bump
+    )]
+    pub duel: Account<'info, ComponentData<DuelComponent>>,
+
+    #[account(
+        mut,
+        seeds = [b"betting", entity.key().as_ref()],
+        bump = betting.load()?.bump
+    )]
+    pub betting: Account<'info, ComponentData<BettingComponent>>,
+
+    #[account(
+        mut,
+        seeds = [b"player", duel.load()?.player_one.as_ref(), entity.key().as_ref()],
+        bump = player_one.load()?.bump
+    )]
+    pub player_one: Account<'info, ComponentData<PlayerComponent>>,
+
+    #[account(
+        mut,
+        seeds = [b"player", duel.load()?.player_two.as_ref(), entity.key().as_ref()],
+        bump = player_two.load()?.bump
+    )]
+    pub player_two: Account<'info, ComponentData<PlayerComponent>>,
+
+    #[account(
+        mut,
+        seeds = [b"vrf_requests"],
+        bump
+    )]
+    pub vrf_requests: Account<'info, ComponentData<VrfRequestRegistry>>,
+
+    #[account(
+        mut,
+        seeds = [b"vrf_request", entity.key().as_ref()],
+        bump
+    )]
+    pub vrf_request: Account<'info, ComponentData<VrfRequest>>,
+
+    /// CHECK: Paid for the request; its rent returns here once the request closes
+    #[account(mut, address = vrf_request.load()?.requester @ VrfRequestError::WrongRequester)]
+    pub requester: AccountInfo<'info>,
+}
+
+/// VrfRequest - A duel's outstanding resolution request
+#[component]
+#[derive(Default)]
+pub struct VrfRequest {
+    pub entity: Pubkey,
+    pub duel_id: u64,
+    pub requester: Pubkey,
+    pub provider: Pubkey,
+    pub requested_at: i64,
+    pub attempts: u8,
+}
+
+impl VrfRequest {
+    pub fn is_expired(&self, now: i64) -> bool {
+        now >= self.requested_at + VRF_REQUEST_TIMEOUT
+    }
+}
+
+/// VrfRequestRegistry - Providers requests are assigned to, plus lifetime counters
+#[component]
+#[derive(Default)]
+pub struct VrfRequestRegistry {
+    pub authority: Pubkey,
+    pub primary_provider: Pubkey,
+    pub fallback_provider: Option<Pubkey>,
+    pub total_reassigned: u64,
+    pub total_expired: u64,
+}
+
+impl<'info> InitializeVrfRequestRegistry<'info> {
+    pub fn process(&mut self, primary_provider: Pubkey, fallback_provider: Option<Pubkey>) -> Result<()> {
+        require!(fallback_provider != Some(primary_provider), VrfRequestError::InvalidFallback);
+
+        let mut registry = self.vrf_requests.load_init()?;
+        registry.authority = self.authority.key();
+        registry.primary_provider = primary_provider;
+        registry.fallback_provider = fallback_provider;
+        Ok(())
+    }
+}
+
+impl<'info> RequestVrf<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let duel = self.duel.load()?;
+        require!(
+            duel.game_state == GameState::ResolutionPending && duel.resolution_pending,
+            GameError::NoResolutionPending
+        );
+
+        let provider = self.vrf_requests.load()?.primary_provider;
+        let mut request = self.vrf_request.load_init()?;
+        request.entity = self.entity.key();
+        request.duel_id = duel.duel_id;
+        request.requester = self.requester.key();
+        request.provider = provider;
+        request.requested_at = current_time;
+        request.attempts = 1;
+
+        emit!(VrfRequestedEvent {
+            duel_id: duel.duel_id,
+            provider,
+            requested_at: current_time,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> ExpireVrfRequest<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+
+        // Resolved since it was opened; just return the request's rent
+        if self.duel.load()?.game_state != GameState::ResolutionPending {
+            return self.vrf_request.close(self.requester.to_account_info());
+        }
+
+        let mut registry = self.vrf_requests.load_mut()?;
+        let mut duel = self.duel.load_mut()?;
+        let request = self.vrf_request.load()?.clone();
+        require!(request.is_expired(current_time), VrfRequestError::RequestNotExpired);
+
+        if let Some(fallback) = registry.fallback_provider.filter(|fallback| *fallback != request.provider) {
+            let mut reassigned = self.vrf_request.load_mut()?;
+            reassigned.provider = fallback;
+            reassigned.requested_at = current_time;
+            reassigned.attempts += 1;
+            registry.total_reassigned += 1;
+
+            emit!(VrfRequestReassignedEvent {
+                duel_id: duel.duel_id,
+                from_provider: request.provider,
+                to_provider: fallback,
+                attempts: request.attempts + 1,
+                requested_at: current_time,
+            });
+            return Ok(());
+        }
+
+        // No provider left to ask: void the duel and hand every stake back
+        let mut betting = self.betting.load_mut()?;
+        let mut player_one = self.player_one.load_mut()?;
+        let mut player_two = self.player_two.load_mut()?;
+        require!(duel.apply_transition(DuelTrigger::Void), GameError::InvalidGameState);
+        duel.resolution_pending = false;
+
+        let refunds = [player_one.total_bet, player_two.total_bet];
+        for player in [&mut player_one, &mut player_two] {
+            player.chip_count += player.total_bet;
+            player.total_bet = 0;
+        }
+        betting.total_pot = 0;
+        betting.is_settled = true;
+        registry.total_expired += 1;
+
+        emit!(VrfRequestExpiredEvent {
+            duel_id: duel.duel_id,
+            provider: request.provider,
+            attempts: request.attempts,
+            refunds,
+            expired_at: current_time,
+        });
+
+        self.vrf_request.close(self.requester.to_account_info())
+    }
+}
+
+#[event]
+pub struct VrfRequestedEvent {
+    pub duel_id: u64,
+    pub provider: Pubkey,
+    pub requested_at: i64,
+}
+
+#[event]
+pub struct VrfRequestReassignedEvent {
+    pub duel_id: u64,
+    pub from_provider: Pubkey,
+    pub to_provider: Pubkey,
+    pub attempts: u8,
+    pub requested_at: i64,
+}
+
+#[event]
+pub struct VrfRequestExpiredEvent {
+    pub duel_id: u64,
+    pub provider: Pubkey,
+    pub attempts: u8,
+    pub refunds: [u64; 2], // Indexed by seat
+    pub expired_at: i64,
+}
+
+#[error_code]
+pub enum VrfRequestError {
+    #[msg("No outstanding VRF request for this duel")]
+    RequestNotFound,
+    #[msg("VRF request has not timed out yet")]
+    RequestNotExpired,
+    #[msg("Fallback provider must differ from the primary")]
+    InvalidFallback,
+    #[msg("VRF proof must come from the provider the request is assigned to")]
+    ProviderMismatch,
+    #[msg("Rent must return to the account that opened the request")]
+    WrongRequester,
+}
@@ -22,12 +22,26 @@ pub mod strategic_duel {
     ) -> Result<()> {
         msg!("Creating new Strategic Duel with ID: {}", Clock::get()?.unix_timestamp);
         
-        // Validate parameters
-        require!(params.max_rounds > 0 && params.max_rounds <= 10, GameError::InvalidGameState);
-        require!(params.min_bet > 0 && params.min_bet <= params.max_bet, GameError::InvalidRaise);
-        require!(params.timeout_duration >= 30 && params.timeout_duration <= 300, GameError::ActionTimeout);
+        params.validate()?;
+        shared::require_feature(
+            &ctx.accounts.feature_flags,
+            shared::FEATURE_CREATE_DUEL,
+            &ctx.accounts.creator.key(),
+        )?;
         
-        ctx.accounts.process(params)
+        ctx.accounts.process(params, &ctx.bumps)
+    }
+
+    /// Open the caller's psych profile; required before their first duel
+    pub fn initialize_psych_profile(ctx: Context<InitializePsychProfile>) -> Result<()> {
+        msg!("Opening psych profile for {}", ctx.accounts.player.key());
+        ctx.accounts.process(&ctx.bumps)
+    }
+
+    /// Open a player's match history buffer if their profile predates it (permissionless)
+    pub fn open_match_history(ctx: Context<OpenMatchHistory>, player: Pubkey) -> Result<()> {
+        msg!("Opening match history for {}", player);
+        ctx.accounts.process(player)
     }
 
     /// Join an existing duel as the second player
@@ -36,7 +50,96 @@ pub mod strategic_duel {
         params: JoinDuelParams,
     ) -> Result<()> {
         msg!("Player joining duel: {}", ctx.accounts.player.key());
-        ctx.accounts.process(params)
+        ctx.accounts.process(params, &ctx.bumps)
+    }
+
+    /// Create the open-table lobby index
+    pub fn initialize_lobby(ctx: Context<InitializeLobby>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    /// Set the VRF providers resolution requests are assigned to (super admin only)
+    pub fn initialize_vrf_request_registry(
+        ctx: Context<InitializeVrfRequestRegistry>,
+        primary_provider: Pubkey,
+        fallback_provider: Option<Pubkey>,
+    ) -> Result<()> {
+        ctx.accounts.process(primary_provider, fallback_provider)
+    }
+
+    /// Open a duel's VRF resolution request; the requester pays its rent
+    pub fn request_vrf(ctx: Context<RequestVrf>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    /// Reassign a timed-out VRF request to the fallback provider, or void the
+    /// duel and refund both players
+    pub fn expire_vrf_request(ctx: Context<ExpireVrfRequest>) -> Result<()> {
+        msg!("Expiring VRF request for entity: {}", ctx.accounts.entity.key());
+        ctx.accounts.process()
+    }
+
+    /// Set the oracle for a randomness provider and enable or disable it (super admin only)
+    pub fn configure_randomness_provider(
+        ctx: Context<ConfigureRandomnessProvider>,
+        provider: RandomnessProvider,
+        oracle: Pubkey,
+        enabled: bool,
+    ) -> Result<()> {
+        msg!("Configuring randomness provider {:?}", provider);
+        ctx.accounts.process(provider, oracle, enabled)
+    }
+
+    /// Create a duel from two players' signed matchmaking intents, seating and
+    /// funding both sides in one transaction
+    pub fn create_matched_duel(
+        ctx: Context<CreateMatchedDuel>,
+        params: CreateDuelParams,
+        intents: [MatchmakingIntent; 2],
+    ) -> Result<()> {
+        msg!("Creating matched duel: {} vs {}", intents[0].player, intents[1].player);
+        params.validate()?;
+        ctx.accounts.process(params, intents, &ctx.bumps)
+    }
+
+    /// Create a duel with both players signing, so the duel starts seated and
+    /// neither side is left waiting with an entry fee posted
+    pub fn create_duel_with_opponent(
+        ctx: Context<CreateDuelWithOpponent>,
+        params: CreateDuelParams,
+        allowlist_proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        msg!("Creating duel: {} vs {}", ctx.accounts.creator.key(), ctx.accounts.opponent.key());
+        params.validate()?;
+        shared::require_feature(
+            &ctx.accounts.feature_flags,
+            shared::FEATURE_CREATE_DUEL,
+            &ctx.accounts.creator.key(),
+        )?;
+        ctx.accounts.process(params, allowlist_proof, &ctx.bumps)
+    }
+
+    /// Create a stake-free practice duel against a scripted bot
+    pub fn create_practice_duel(
+        ctx: Context<CreatePracticeDuel>,
+        params: PracticeDuelParams,
+    ) -> Result<()> {
+        msg!("Creating practice duel for: {}", ctx.accounts.creator.key());
+
+        require!(params.max_rounds > 0 && params.max_rounds <= 10, GameError::InvalidGameState);
+        require!(params.timeout_duration >= 30 && params.timeout_duration <= 300, GameError::ActionTimeout);
+        require!(
+            params.aggression <= 1000 && params.call_threshold <= 1000,
+            PracticeError::InvalidBotParams
+        );
+
+        ctx.accounts.process(params, &ctx.bumps)
+    }
+
+    /// Permissionless crank that plays the practice bot's move
+    pub fn advance_bot(ctx: Context<AdvanceBot>) -> Result<()> {
+        msg!("Advancing practice bot");
+        ctx.accounts.process()
     }
 
     /// Process a player action (CHECK, RAISE, CALL, FOLD)
@@ -44,6 +147,8 @@ pub mod strategic_duel {
         ctx: Context<ActionProcessing>,
         action_type: ActionType,
         bet_amount: u64,
+        sync_nonce: Option<u64>,
+        latency: Option<LatencyAttestation>,
     ) -> Result<()> {
         msg!("Processing action: {:?} with amount: {}", action_type, bet_amount);
         
@@ -61,7 +166,48 @@ pub mod strategic_duel {
             _ => return Err(GameError::InvalidActionType.into()),
         }
 
-        action_processing::execute(ctx, action_type, bet_amount)
+        ctx.accounts.world.load()?.require_system(SYSTEM_ACTION_PROCESSING)?;
+
+        action_processing::execute(ctx, action_type, bet_amount, sync_nonce, latency)
+    }
+
+    /// Concede a duel in progress; the opponent wins and settles as usual
+    pub fn forfeit_duel(ctx: Context<ForfeitDuel>) -> Result<()> {
+        msg!("Duel forfeited by: {}", ctx.accounts.player_signer.key());
+        ctx.accounts.process()
+    }
+
+    /// Propose a mutually agreed break that freezes the action timer
+    pub fn propose_pause(ctx: Context<ProposePause>, duration: i64) -> Result<()> {
+        msg!("Pause of {}s proposed by: {}", duration, ctx.accounts.player_signer.key());
+        ctx.accounts.process(duration)
+    }
+
+    /// Accept the opponent's pause proposal for the duration it was seen with;
+    /// play resumes automatically when it ends
+    pub fn accept_pause(ctx: Context<AcceptPause>, expected_duration: i64) -> Result<()> {
+        ctx.accounts.process(expected_duration)
+    }
+
+    /// Close a processed action record, refunding rent to the player
+    pub fn close_action(ctx: Context<CloseAction>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    /// Pause the action timer once per match and issue a state-sync nonce
+    pub fn request_reconnect(ctx: Context<RequestReconnect>) -> Result<()> {
+        msg!("Reconnect requested by: {}", ctx.accounts.player_signer.key());
+        ctx.accounts.process()
+    }
+
+    /// Commit to secret entropy that will be mixed with the VRF output
+    pub fn commit_entropy(ctx: Context<CommitEntropy>, commitment: [u8; 32]) -> Result<()> {
+        ctx.accounts.process(commitment)
+    }
+
+    /// Reveal committed entropy once the duel awaits VRF resolution, before the deadline
+    pub fn reveal_entropy(ctx: Context<RevealEntropy>, entropy: [u8; 32]) -> Result<()> {
+        ctx.accounts.process(entropy)
     }
 
     /// Advance to the next round
@@ -76,6 +222,7 @@ pub mod strategic_duel {
         vrf_proof: [u8; 64],
     ) -> Result<()> {
         msg!("Resolving game with VRF");
+        ctx.accounts.world.load()?.require_system(SYSTEM_VRF_RESOLUTION)?;
         vrf_resolution::execute(ctx, vrf_proof)
     }
 
@@ -101,6 +248,7 @@ pub mod strategic_duel {
         validator_signatures: Vec<[u8; 64]>,
     ) -> Result<()> {
         msg!("Processing rollup settlement");
+        ctx.accounts.world.load()?.require_system(SYSTEM_ROLLUP_SETTLEMENT)?;
         ctx.accounts.process(rollup_block_height, l1_block_height, winner_proof, validator_signatures)
     }
 
@@ -111,9 +259,34 @@ pub mod strategic_duel {
         delegation_proof: [u8; 256],
     ) -> Result<()> {
         msg!("Delegating state to ephemeral rollup");
+        ctx.accounts.world.load()?.require_system(SYSTEM_EPHEMERAL_ROLLUP)?;
         ctx.accounts.delegate_to_rollup(rollup_duration, delegation_proof)
     }
 
+    /// Extend a rollup session; needs the player's ed25519 signature over (rollup_id, new_expiry)
+    pub fn renew_delegation(ctx: Context<RenewDelegation>, new_expiry: i64) -> Result<()> {
+        msg!("Renewing rollup delegation until {}", new_expiry);
+        ctx.accounts.process(new_expiry)
+    }
+
+    /// Post or top up a rollup operator's bond
+    pub fn post_operator_bond(ctx: Context<PostOperatorBond>, amount: u64) -> Result<()> {
+        msg!("Posting operator bond of {} lamports", amount);
+        ctx.accounts.process(amount)
+    }
+
+    /// Withdraw an operator's bond once it has no outstanding rollups
+    pub fn withdraw_operator_bond(ctx: Context<WithdrawOperatorBond>, amount: u64) -> Result<()> {
+        msg!("Withdrawing {} lamports of operator bond", amount);
+        ctx.accounts.process(amount)
+    }
+
+    /// Slash an operator for a disputed transition or a rollup left unfinalized past expiry
+    pub fn slash_operator_bond(ctx: Context<SlashOperatorBond>) -> Result<()> {
+        msg!("Slashing rollup operator bond");
+        ctx.accounts.process()
+    }
+
     /// Create state transition in rollup
     pub fn create_state_transition(
         ctx: Context<EphemeralRollupDelegation>,
@@ -132,10 +305,507 @@ pub mod strategic_duel {
         ctx.accounts.finalize_rollup()
     }
 
-    /// Emergency exit from rollup
-    pub fn emergency_exit_rollup(ctx: Context<EphemeralRollupDelegation>) -> Result<()> {
+    /// Finalize many duels delegated to the same validator under one signed batch root
+    pub fn finalize_rollup_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, FinalizeRollupBatch<'info>>,
+        batch_signature: [u8; 64],
+    ) -> Result<()> {
+        msg!("Finalizing rollup batch of {} accounts", ctx.remaining_accounts.len());
+        ctx.accounts.process(ctx.remaining_accounts, batch_signature)
+    }
+
+    /// Emergency exit from rollup; signed by the player alone
+    pub fn emergency_exit_rollup(ctx: Context<EmergencyExitRollup>) -> Result<()> {
         msg!("Emergency exit from rollup");
-        ctx.accounts.emergency_exit()
+        ctx.accounts.process()
+    }
+
+    /// Dispute a pending rollup transition that contradicts the duel's committed state
+    pub fn dispute_state_transition(ctx: Context<DisputeStateTransition>) -> Result<()> {
+        msg!("Disputing rollup transition for: {}", ctx.accounts.entity.key());
+        ctx.accounts.process()
+    }
+
+    /// Re-execute the logged actions in `start_chunk..=end_chunk` and confirm the resulting state hash
+    pub fn verify_replay<'info>(
+        ctx: Context<'_, '_, '_, 'info, VerifyReplay<'info>>,
+        start_chunk: u16,
+        end_chunk: u16,
+    ) -> Result<()> {
+        msg!("Verifying replay chunks {} to {}", start_chunk, end_chunk);
+        ctx.accounts.process(ctx.remaining_accounts, start_chunk, end_chunk)
+    }
+
+    /// Create the shared wager insurance pool (super admin only)
+    pub fn initialize_insurance_vault(
+        ctx: Context<InitializeInsuranceVault>,
+        premium_bps: u16,
+        max_coverage: u64,
+    ) -> Result<()> {
+        msg!("Initializing insurance vault with premium {} bps", premium_bps);
+        ctx.accounts.process(premium_bps, max_coverage)
+    }
+
+    /// Insure a duel stake against technical forfeits
+    pub fn purchase_insurance(ctx: Context<PurchaseInsurance>, covered_amount: u64) -> Result<()> {
+        msg!("Purchasing insurance for {} lamports", covered_amount);
+        ctx.accounts.process(covered_amount)
+    }
+
+    /// Claim a refund after a verifiable protocol failure
+    pub fn claim_insurance(ctx: Context<ClaimInsurance>) -> Result<()> {
+        msg!("Processing insurance claim for: {}", ctx.accounts.player_signer.key());
+        ctx.accounts.process()
+    }
+
+    /// Open a parimutuel spectator pool on a duel
+    pub fn open_spectator_pool(
+        ctx: Context<OpenSpectatorPool>,
+        rake_bps: u16,
+        min_bet: u64,
+        max_bet_per_backer: u64,
+        max_pool: u64,
+    ) -> Result<()> {
+        msg!("Opening spectator pool with rake {} bps", rake_bps);
+        ctx.accounts.process(rake_bps, min_bet, max_bet_per_backer, max_pool)
+    }
+
+    /// Back one seat in a duel's spectator pool before the second round
+    pub fn place_spectator_bet(
+        ctx: Context<PlaceSpectatorBet>,
+        seat: u8,
+        amount: u64,
+        allowlist_proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        msg!("Spectator bet of {} lamports on seat {}", amount, seat);
+        ctx.accounts.process(seat, amount, allowlist_proof)
+    }
+
+    /// Fix the winning side of a finished duel's spectator pool and pay the rake
+    pub fn settle_spectator_pool(ctx: Context<SettleSpectatorPool>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    /// Collect a spectator payout or refund
+    pub fn claim_spectator_payout(ctx: Context<ClaimSpectatorPayout>) -> Result<()> {
+        msg!("Claiming spectator payout for: {}", ctx.accounts.bettor.key());
+        ctx.accounts.process()
+    }
+
+    /// Fix the broadcast delay every delayed feed runs at (super admin only)
+    pub fn initialize_feed_delay(ctx: Context<InitializeFeedDelay>, delay_seconds: i64) -> Result<()> {
+        msg!("Initializing feed delay: {}s", delay_seconds);
+        ctx.accounts.process(delay_seconds)
+    }
+
+    /// Change the delay for feeds opened from now on
+    pub fn update_feed_delay(ctx: Context<UpdateFeedDelay>, delay_seconds: i64) -> Result<()> {
+        msg!("Updating feed delay: {}s", delay_seconds);
+        ctx.accounts.process(delay_seconds)
+    }
+
+    /// Open a broadcast-safe delayed mirror of a duel at the configured delay
+    pub fn open_delayed_feed(ctx: Context<OpenDelayedFeed>) -> Result<()> {
+        msg!("Opening delayed feed for duel: {}", ctx.accounts.duel.load()?.duel_id);
+        ctx.accounts.process()
+    }
+
+    /// Release logged actions, and finally the result, once they have aged past the feed delay
+    pub fn mirror_delayed_events(ctx: Context<MirrorDelayedEvents>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    /// Create a winnings vault with a withdrawal policy
+    pub fn initialize_bankroll(ctx: Context<InitializeBankroll>, policy: WithdrawalPolicy) -> Result<()> {
+        msg!("Initializing bankroll for: {}", ctx.accounts.owner.key());
+        ctx.accounts.process(policy)
+    }
+
+    /// Deposit lamports into a bankroll
+    pub fn deposit_bankroll(ctx: Context<DepositBankroll>, amount: u64) -> Result<()> {
+        msg!("Depositing {} lamports to bankroll", amount);
+        ctx.accounts.process(amount)
+    }
+
+    /// Withdraw from a bankroll subject to limits, cooldowns and co-signing
+    pub fn withdraw_bankroll(ctx: Context<WithdrawBankroll>, amount: u64) -> Result<()> {
+        msg!("Withdrawing {} lamports from bankroll", amount);
+        ctx.accounts.process(amount)
+    }
+
+    /// Replace a bankroll's withdrawal policy
+    pub fn set_withdrawal_policy(ctx: Context<SetWithdrawalPolicy>, policy: WithdrawalPolicy) -> Result<()> {
+        msg!("Updating withdrawal policy for: {}", ctx.accounts.owner.key());
+        ctx.accounts.process(policy)
+    }
+
+    /// Freeze a duel and publish the hash of its state ahead of a migration
+    pub fn export_state_hash(ctx: Context<ExportStateHash>) -> Result<()> {
+        msg!("Exporting state for entity: {}", ctx.accounts.entity.key());
+        ctx.accounts.process()
+    }
+
+    /// Recreate an exported duel after an upgrade or redeploy
+    pub fn import_state(
+        ctx: Context<ImportState>,
+        source_program: Pubkey,
+        source_entity: Pubkey,
+        duel_bytes: Vec<u8>,
+        betting_bytes: Vec<u8>,
+    ) -> Result<()> {
+        msg!("Importing state from {} into {}", source_entity, ctx.accounts.entity.key());
+        ctx.accounts.process(source_program, source_entity, duel_bytes, betting_bytes, &ctx.bumps)
+    }
+
+    /// Configure the KYC/allowlist gate for regulated deployments (super admin only)
+    pub fn configure_compliance(
+        ctx: Context<ConfigureCompliance>,
+        mode: ComplianceMode,
+        allowlist_root: [u8; 32],
+        attestation_issuer: Pubkey,
+        spectator_betting_paused: bool,
+    ) -> Result<()> {
+        msg!("Configuring compliance mode: {:?}", mode);
+        ctx.accounts.process(mode, allowlist_root, attestation_issuer, spectator_betting_paused)
+    }
+
+    /// Issue, refresh or revoke a player's compliance credential
+    pub fn issue_compliance_credential(
+        ctx: Context<IssueComplianceCredential>,
+        player: Pubkey,
+        expires_at: i64,
+        revoked: bool,
+    ) -> Result<()> {
+        msg!("Issuing compliance credential for: {}", player);
+        ctx.accounts.process(player, expires_at, revoked)
+    }
+
+    /// Configure the per-pair chip-dumping guard (super admin only)
+    pub fn initialize_transfer_limits(
+        ctx: Context<InitializeTransferLimits>,
+        max_net_transfer: u64,
+        window_seconds: i64,
+    ) -> Result<()> {
+        msg!("Initializing transfer limits: {} per {}s", max_net_transfer, window_seconds);
+        ctx.accounts.process(max_net_transfer, window_seconds)
+    }
+
+    /// Adjust the chip-dumping cap or window
+    pub fn update_transfer_limits(
+        ctx: Context<UpdateTransferLimits>,
+        max_net_transfer: u64,
+        window_seconds: i64,
+    ) -> Result<()> {
+        msg!("Updating transfer limits: {} per {}s", max_net_transfer, window_seconds);
+        ctx.accounts.process(max_net_transfer, window_seconds)
+    }
+
+    /// Fix the mint and minimum holding behind each gated table tier (super admin only)
+    pub fn initialize_table_tiers(ctx: Context<InitializeTableTiers>, gates: [TableGate; 3]) -> Result<()> {
+        msg!("Initializing table tier gates");
+        ctx.accounts.process(gates)
+    }
+
+    /// Replace the gated table tiers' requirements
+    pub fn update_table_tiers(ctx: Context<UpdateTableTiers>, gates: [TableGate; 3]) -> Result<()> {
+        msg!("Updating table tier gates");
+        ctx.accounts.process(gates)
+    }
+
+    /// Configure the tiered settlement rake schedule (super admin only)
+    pub fn initialize_rake_schedule(
+        ctx: Context<InitializeRakeSchedule>,
+        treasury: Pubkey,
+        tiers: Vec<RakeTier>,
+        max_rake_per_pot: u64,
+        no_flop_no_drop: bool,
+    ) -> Result<()> {
+        msg!("Initializing rake schedule with {} tiers", tiers.len());
+        ctx.accounts.process(treasury, tiers, max_rake_per_pot, no_flop_no_drop)
+    }
+
+    /// Replace the rake tiers, cap, no-flop-no-drop rule or treasury
+    pub fn update_rake_schedule(
+        ctx: Context<UpdateRakeSchedule>,
+        treasury: Pubkey,
+        tiers: Vec<RakeTier>,
+        max_rake_per_pot: u64,
+        no_flop_no_drop: bool,
+    ) -> Result<()> {
+        msg!("Updating rake schedule: {} tiers, cap {}", tiers.len(), max_rake_per_pot);
+        ctx.accounts.process(treasury, tiers, max_rake_per_pot, no_flop_no_drop)
+    }
+
+    /// Configure rake-back epochs and staking tier multipliers (super admin only)
+    pub fn initialize_loyalty_program(
+        ctx: Context<InitializeLoyaltyProgram>,
+        treasury: Pubkey,
+        staking_mint: Pubkey,
+        epoch_duration: i64,
+        rakeback_bps: u16,
+        tier_thresholds: [u64; LOYALTY_STAKING_TIERS],
+        tier_multipliers_bps: [u16; LOYALTY_STAKING_TIERS + 1],
+    ) -> Result<()> {
+        msg!("Initializing loyalty program: {} bps rake-back per {}s epoch", rakeback_bps, epoch_duration);
+        ctx.accounts.process(treasury, staking_mint, epoch_duration, rakeback_bps, tier_thresholds, tier_multipliers_bps)
+    }
+
+    /// Move treasury funds into the rake-back pool
+    pub fn fund_loyalty_program(ctx: Context<FundLoyaltyProgram>, amount: u64) -> Result<()> {
+        msg!("Funding loyalty pool with {} lamports", amount);
+        ctx.accounts.process(amount)
+    }
+
+    /// Claim rake-back on rake paid in closed epochs
+    pub fn claim_rakeback(ctx: Context<ClaimRakeback>) -> Result<()> {
+        msg!("Claiming rake-back for: {}", ctx.accounts.player.key());
+        ctx.accounts.process()
+    }
+
+    /// Lock staking receipts toward a rake-back tier
+    pub fn stake_loyalty(ctx: Context<StakeLoyalty>, amount: u64) -> Result<()> {
+        let bump = ctx.bumps.loyalty_stake;
+        ctx.accounts.process(amount, bump)
+    }
+
+    /// Withdraw locked staking receipts
+    pub fn unstake_loyalty(ctx: Context<UnstakeLoyalty>, amount: u64) -> Result<()> {
+        ctx.accounts.process(amount)
+    }
+
+    /// Create the progressive jackpot with its micro-rake, odds and split (super admin only)
+    pub fn initialize_jackpot(
+        ctx: Context<InitializeJackpot>,
+        contribution_bps: u16,
+        hit_odds: u32,
+        winner_share_bps: u16,
+    ) -> Result<()> {
+        msg!("Initializing jackpot: {} bps per pot, 1 in {} odds", contribution_bps, hit_odds);
+        ctx.accounts.process(contribution_bps, hit_odds, winner_share_bps)
+    }
+
+    /// Adjust the jackpot contribution, odds or payout split
+    pub fn update_jackpot(
+        ctx: Context<UpdateJackpot>,
+        contribution_bps: u16,
+        hit_odds: u32,
+        winner_share_bps: u16,
+    ) -> Result<()> {
+        msg!("Updating jackpot: {} bps per pot, 1 in {} odds", contribution_bps, hit_odds);
+        ctx.accounts.process(contribution_bps, hit_odds, winner_share_bps)
+    }
+
+    /// Create the daily high-hand contest (super admin only)
+    pub fn initialize_daily_contest(
+        ctx: Context<InitializeDailyContest>,
+        treasury: Pubkey,
+        prize_amount: u64,
+    ) -> Result<()> {
+        msg!("Initializing daily contest with prize {}", prize_amount);
+        ctx.accounts.process(treasury, prize_amount)
+    }
+
+    /// Move treasury funds into the daily contest prize pool
+    pub fn fund_daily_contest(ctx: Context<FundDailyContest>, amount: u64) -> Result<()> {
+        msg!("Funding daily contest with {} lamports", amount);
+        ctx.accounts.process(amount)
+    }
+
+    /// Pay the finished day's record holder and start the next day
+    pub fn finalize_contest_day(ctx: Context<FinalizeContestDay>) -> Result<()> {
+        msg!("Finalizing contest day");
+        ctx.accounts.process()
+    }
+
+    /// Configure inactivity decay for ranked ratings (super admin only)
+    pub fn initialize_rating_decay(
+        ctx: Context<InitializeRatingDecay>,
+        threshold: u32,
+        decay_per_week: u32,
+        off_season: bool,
+    ) -> Result<()> {
+        msg!("Initializing rating decay: {} per week above {}", decay_per_week, threshold);
+        ctx.accounts.process(threshold, decay_per_week, off_season)
+    }
+
+    /// Adjust decay terms or toggle the off-season pause
+    pub fn update_rating_decay(
+        ctx: Context<UpdateRatingDecay>,
+        threshold: u32,
+        decay_per_week: u32,
+        off_season: bool,
+    ) -> Result<()> {
+        msg!("Updating rating decay: {} per week above {}, off-season {}", decay_per_week, threshold, off_season);
+        ctx.accounts.process(threshold, decay_per_week, off_season)
+    }
+
+    /// Apply accrued inactivity decay to a player's ranked rating
+    pub fn decay_rating(ctx: Context<DecayRating>) -> Result<()> {
+        msg!("Decaying rating for: {}", ctx.accounts.player.key());
+        ctx.accounts.process()
+    }
+
+    /// Choose the rating system each queue is rated under (super admin only)
+    pub fn initialize_queue_ratings(
+        ctx: Context<InitializeQueueRatings>,
+        systems: [RatingSystem; shared::QUEUE_TYPES],
+    ) -> Result<()> {
+        msg!("Initializing queue rating systems: {:?}", systems);
+        ctx.accounts.process(systems)
+    }
+
+    /// Move queues to another rating system; applies from their next settlement
+    pub fn update_queue_ratings(
+        ctx: Context<UpdateQueueRatings>,
+        systems: [RatingSystem; shared::QUEUE_TYPES],
+    ) -> Result<()> {
+        msg!("Updating queue rating systems: {:?}", systems);
+        ctx.accounts.process(systems)
+    }
+
+    /// Save or overwrite a host's preferred table settings under `preset_id`
+    pub fn save_duel_preset(
+        ctx: Context<SaveDuelPreset>,
+        preset_id: u8,
+        params: CreateDuelParams,
+    ) -> Result<()> {
+        msg!("Saving duel preset {} for host: {}", preset_id, ctx.accounts.host.key());
+        params.validate()?;
+        ctx.accounts.process(preset_id, params)
+    }
+
+    /// Open a duel using a saved preset's settings
+    pub fn create_duel_from_preset(
+        ctx: Context<CreateDuelFromPreset>,
+        preset_id: u8,
+    ) -> Result<()> {
+        msg!("Creating duel from preset {} for host: {}", preset_id, ctx.accounts.create.creator.key());
+        // Bounds may have tightened since the preset was saved
+        ctx.accounts.preset.load()?.params.validate()?;
+        shared::require_feature(
+            &ctx.accounts.create.feature_flags,
+            shared::FEATURE_CREATE_DUEL,
+            &ctx.accounts.create.creator.key(),
+        )?;
+        ctx.accounts.process(preset_id, &ctx.bumps)
+    }
+
+    /// Book a duel against `opponent` at `start_time`, escrowing the host's stake
+    pub fn schedule_duel(
+        ctx: Context<ScheduleDuel>,
+        schedule_id: u64,
+        opponent: Pubkey,
+        start_time: i64,
+        stake: u64,
+        forfeit_fee: u64,
+        check_in_grace: i64,
+        params: CreateDuelParams,
+    ) -> Result<()> {
+        msg!("Scheduling duel {} for {}", schedule_id, start_time);
+        ctx.accounts.process(schedule_id, opponent, start_time, stake, forfeit_fee, check_in_grace, params)
+    }
+
+    /// Accept a scheduled duel's terms by escrowing the matching stake
+    pub fn accept_scheduled_duel(ctx: Context<AcceptScheduledDuel>, params_hash: [u8; 32]) -> Result<()> {
+        ctx.accounts.process(params_hash, &ctx.bumps)
+    }
+
+    /// Check in for a scheduled duel around its start time
+    pub fn check_in_scheduled_duel(ctx: Context<CheckInScheduledDuel>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    /// Open a scheduled duel once both players have checked in (permissionless)
+    pub fn start_scheduled_duel(ctx: Context<StartScheduledDuel>, params: CreateDuelParams) -> Result<()> {
+        msg!("Starting scheduled duel for entity: {}", ctx.accounts.entity.key());
+        ctx.accounts.process(params, &ctx.bumps)
+    }
+
+    /// Settle a scheduled duel's no-show forfeit or release it (permissionless)
+    pub fn crank_scheduled_duel(ctx: Context<CrankScheduledDuel>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    /// Return both escrowed stakes from a cancelled duel (permissionless)
+    pub fn refund_duel_escrow(ctx: Context<RefundDuelEscrow>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    /// Replace the caller's observers on a private duel (coaches, streamers)
+    pub fn set_duel_observers(
+        ctx: Context<SetDuelObservers>,
+        observers: Vec<Pubkey>,
+    ) -> Result<()> {
+        msg!("Setting {} observers for entity: {}", observers.len(), ctx.accounts.entity.key());
+        ctx.accounts.process(observers)
+    }
+
+    /// Create one shard of the metrics buffers settlement records compute-unit samples into
+    pub fn initialize_metrics_buffer(ctx: Context<InitializeMetricsBuffer>, shard: u8) -> Result<()> {
+        let bump = ctx.bumps.metrics_buffer;
+        ctx.accounts.process(shard, bump)
+    }
+
+    /// Self-report version, commit and IDL hash after an upgrade
+    pub fn update_program_info(
+        ctx: Context<UpdateProgramInfo>,
+        version: [u16; 3],
+        git_commit: [u8; 20],
+        idl_hash: [u8; 32],
+    ) -> Result<()> {
+        msg!("Program info: v{}.{}.{}", version[0], version[1], version[2]);
+        let bump = ctx.bumps.program_info;
+        ctx.accounts.process(version, git_commit, idl_hash, bump)
+    }
+
+    /// Take governance of per-player rate limits, starting from the defaults (super admin only)
+    pub fn initialize_rate_limit_config(ctx: Context<InitializeRateLimitConfig>) -> Result<()> {
+        let bump = ctx.bumps.config;
+        ctx.accounts.process(bump)
+    }
+
+    /// Change the sliding window for one rate-limited entrypoint
+    pub fn set_rate_limit(
+        ctx: Context<SetRateLimit>,
+        action: u8,
+        window: shared::RateWindow,
+    ) -> Result<()> {
+        msg!("Rate limit {}: {} calls per {}s", action, window.max_calls, window.window_secs);
+        ctx.accounts.process(action, window)
+    }
+
+    /// Configure the TTL and cranker bounty for collecting abandoned duels (super admin only)
+    pub fn initialize_duel_gc(ctx: Context<InitializeDuelGc>, ttl_seconds: i64, bounty_bps: u16) -> Result<()> {
+        ctx.accounts.process(ttl_seconds, bounty_bps)
+    }
+
+    /// Adjust the abandoned-duel TTL or cranker bounty
+    pub fn update_duel_gc(ctx: Context<UpdateDuelGc>, ttl_seconds: i64, bounty_bps: u16) -> Result<()> {
+        ctx.accounts.process(ttl_seconds, bounty_bps)
+    }
+
+    /// Close a duel left waiting past its TTL and pay the cranker (permissionless)
+    pub fn gc_abandoned_duel(ctx: Context<GcAbandonedDuel>) -> Result<()> {
+        msg!("Collecting abandoned duel for entity: {}", ctx.accounts.entity.key());
+        ctx.accounts.process()
+    }
+
+    /// Create the program's AdminConfig (upgrade authority only)
+    pub fn initialize_admin_config(ctx: Context<InitializeAdminConfig>, super_admin: Pubkey) -> Result<()> {
+        let bump = ctx.bumps.admin_config;
+        ctx.accounts.process(super_admin, bump)
+    }
+
+    /// Store canonical bumps on a duel's components created before bumps were recorded
+    pub fn backfill_component_bumps(ctx: Context<BackfillComponentBumps>) -> Result<()> {
+        msg!("Backfilling component bumps for entity: {}", ctx.accounts.entity.key());
+        ctx.accounts.process(&ctx.bumps)
+    }
+
+    /// Move a psych profile written before per-queue ratings into the current layout
+    pub fn migrate_psych_profile(ctx: Context<MigratePsychProfile>, player: Pubkey) -> Result<()> {
+        msg!("Migrating psych profile for player: {}", player);
+        ctx.accounts.process(player, &ctx.bumps)
     }
 
     /// Update psychological analysis for a player
@@ -150,14 +820,19 @@ pub mod strategic_duel {
         settlement::execute(ctx)
     }
 
+    /// Enable or disable a registered ECS system - world authority only
+    pub fn set_system_active(ctx: Context<SetSystemActive>, system_id: u32, active: bool) -> Result<()> {
+        msg!("Setting system {} active: {}", system_id, active);
+        ctx.accounts.process(system_id, active)
+    }
+
     /// Emergency functions for game management
     
     /// Cancel a duel (only if still waiting for players)
     pub fn cancel_duel(ctx: Context<CancelDuel>) -> Result<()> {
         let mut duel = ctx.accounts.duel.load_mut()?;
-        require!(duel.game_state == GameState::WaitingForPlayers, GameError::InvalidGameState);
-        
-        duel.game_state = GameState::Cancelled;
+        require!(duel.apply_transition(DuelTrigger::Cancel), GameError::InvalidGameState);
+        ctx.accounts.lobby.load_mut()?.delist(&ctx.accounts.entity.key());
         
         emit!(DuelCancelledEvent {
             duel_id: duel.duel_id,
@@ -179,9 +854,27 @@ pub mod strategic_duel {
         require!(player.is_active, GameError::PlayerInactive);
         
         // Timeout defaults to FOLD
+        let seat = duel.seat_of(&player.player_id).ok_or(GameError::PlayerInactive)?;
+        let mut replay_chunk = ctx.accounts.replay_chunk.load_mut()?;
+        record_replay_entry(&mut duel, &mut replay_chunk, ReplayEntry {
+            seat,
+            action_type: ActionType::Timeout,
+            bet_amount: 0,
+            round_number: duel.current_round,
+            timestamp: current_time,
+        })?;
+
         player.is_active = false;
-        duel.game_state = GameState::ResolutionPending;
+        require!(duel.apply_transition(DuelTrigger::Timeout), GameError::InvalidGameState);
         duel.last_action_time = current_time;
+
+        // Timeouts count against the player's reputation
+        if !duel.is_practice {
+            let psych_account = ctx.accounts.player_psych.as_ref().ok_or(GameError::MissingPsychProfile)?;
+            let mut psych = psych_account.load_mut()?;
+            psych.timeouts += 1;
+            psych.refresh_reputation();
+        }
         
         emit!(TimeoutEvent {
             duel_id: duel.duel_id,
@@ -259,30 +952,39 @@ pub struct CancelDuel<'info> {
     #[account(
         mut,
         seeds = [b"duel", entity.key().as_ref()],
-        bump
+        bump = duel.load()?.bump
     )]
     pub duel: Account<'info, ComponentData<DuelComponent>>,
 
     /// CHECK: Entity reference
     pub entity: AccountInfo<'info>,
+
+    /// Open-table index; listed on create, removed on join or cancel
+    #[account(
+        mut,
+        seeds = [b"lobby"],
+        bump
+    )]
+    pub lobby: Account<'info, ComponentData<LobbyRegistry>>,
 }
 
 #[derive(Accounts)]
 pub struct HandleTimeout<'info> {
     /// CHECK: Can be any signer to handle timeout
+    #[account(mut)]
     pub authority: Signer<'info>,
 
     #[account(
         mut,
         seeds = [b"duel", entity.key().as_ref()],
-        bump
+        bump = duel.load()?.bump
     )]
     pub duel: Account<'info, ComponentData<DuelComponent>>,
 
     #[account(
         mut,
         seeds = [b"player", player_key.as_ref(), entity.key().as_ref()],
-        bump
+        bump = player.load()?.bump
     )]
     pub player: Account<'info, ComponentData<PlayerComponent>>,
 
@@ -291,31 +993,49 @@ pub struct HandleTimeout<'info> {
     
     /// CHECK: Player key for seeds
     pub player_key: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"psych", player_key.key().as_ref()],
+        bump = player_psych.load()?.bump
+    )]
+    pub player_psych: Option<Account<'info, ComponentData<PsychProfileComponent>>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + std::mem::size_of::<ReplayChunk>() + MAX_REPLAY_ENTRIES_PER_CHUNK * std::mem::size_of::<ReplayEntry>(),
+        seeds = [b"replay", entity.key().as_ref(), &duel.load()?.replay_chunk_count.to_le_bytes()],
+        bump
+    )]
+    pub replay_chunk: Account<'info, ComponentData<ReplayChunk>>,
+
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
 pub struct GetGameStats<'info> {
     #[account(
         seeds = [b"duel", entity.key().as_ref()],
-        bump
+        bump = duel.load()?.bump
     )]
     pub duel: Account<'info, ComponentData<DuelComponent>>,
 
     #[account(
         seeds = [b"betting", entity.key().as_ref()],
-        bump
+        bump = betting.load()?.bump
     )]
     pub betting: Account<'info, ComponentData<BettingComponent>>,
 
     #[account(
         seeds = [b"player", duel.load()?.player_one.as_ref(), entity.key().as_ref()],
-        bump
+        bump = player_one.load()?.bump
     )]
     pub player_one: Account<'info, ComponentData<PlayerComponent>>,
 
     #[account(
         seeds = [b"player", duel.load()?.player_two.as_ref(), entity.key().as_ref()],
-        bump
+        bump = player_two.load()?.bump
     )]
     pub player_two: Account<'info, ComponentData<PlayerComponent>>,
 
@@ -423,7 +1143,55 @@ impl ComponentRegistry {
     }
 }
 
+/// System IDs in the world's SystemRegistry
+pub const SYSTEM_ACTION_PROCESSING: u32 = 1;
+pub const SYSTEM_VRF_RESOLUTION: u32 = 2;
+pub const SYSTEM_ROLLUP_SETTLEMENT: u32 = 3;
+pub const SYSTEM_EPHEMERAL_ROLLUP: u32 = 4;
+
+impl BoltWorldComponent {
+    /// Gate a system-execution instruction on the registry, so a faulty system
+    /// can be switched off on-chain without a redeploy
+    pub fn require_system(&self, system_id: u32) -> Result<()> {
+        require!(self.is_active, SystemRegistryError::WorldInactive);
+        self.system_registry.require_runnable(system_id, &self.component_registry)
+    }
+}
+
+impl ComponentRegistry {
+    pub fn is_component_active(&self, component_id: u32) -> bool {
+        self.registered_components
+            .iter()
+            .any(|component| component.component_id == component_id && component.is_active)
+    }
+}
+
 impl SystemRegistry {
+    pub fn get(&self, system_id: u32) -> Option<&SystemInfo> {
+        self.registered_systems.iter().find(|system| system.system_id == system_id)
+    }
+
+    pub fn set_active(&mut self, system_id: u32, active: bool) -> Result<()> {
+        let system = self.registered_systems
+            .iter_mut()
+            .find(|system| system.system_id == system_id)
+            .ok_or(SystemRegistryError::SystemNotRegistered)?;
+        system.is_active = active;
+        Ok(())
+    }
+
+    /// The system must be registered and active, and every component it
+    /// declares as a dependency must be registered and active too
+    pub fn require_runnable(&self, system_id: u32, components: &ComponentRegistry) -> Result<()> {
+        let system = self.get(system_id).ok_or(SystemRegistryError::SystemNotRegistered)?;
+        require!(system.is_active, SystemRegistryError::SystemDisabled);
+        require!(
+            system.dependencies.iter().all(|id| components.is_component_active(*id)),
+            SystemRegistryError::MissingComponentDependency
+        );
+        Ok(())
+    }
+
     pub fn new() -> Self {
         Self {
             registered_systems: vec![
@@ -457,6 +1225,18 @@ impl SystemRegistry {
     }
 }
 
+/// Emitted when the second player joins. Cosmetics are mints indexed by
+/// `shared::CosmeticType`; clients should confirm the player still holds each one.
+#[event]
+pub struct DuelStartedEvent {
+    pub duel_id: u64,
+    pub player_one: Pubkey,
+    pub player_two: Pubkey,
+    pub player_one_cosmetics: [Option<Pubkey>; shared::COSMETIC_SLOT_COUNT],
+    pub player_two_cosmetics: [Option<Pubkey>; shared::COSMETIC_SLOT_COUNT],
+    pub started_at: i64,
+}
+
 #[event]
 pub struct DuelCancelledEvent {
     pub duel_id: u64,
@@ -601,6 +1381,837 @@ mod tests {
         assert!(!utils::is_valid_bet_size(200, 50, 250, 150)); // Above chips
     }
 
+    #[test]
+    fn test_decision_time_normalization() {
+        let mut profile = PsychProfileComponent::default();
+
+        // Latency is subtracted and capped at the attested maximum
+        assert_eq!(profile.normalize_decision_time(8_000, 1_500, 60_000), 6_500);
+        assert_eq!(profile.normalize_decision_time(8_000, 20_000, 60_000), 3_000);
+        // Raw times never exceed the action timeout
+        assert_eq!(profile.normalize_decision_time(90_000, 0, 60_000), 60_000);
+
+        // With enough samples, outliers clamp to three standard deviations
+        profile.avg_decision_time = 4_000;
+        profile.decision_variance = 250_000; // std dev 500ms
+        profile.sample_size = MIN_SAMPLES_FOR_OUTLIER_CLAMP;
+        assert_eq!(profile.normalize_decision_time(30_000, 0, 60_000), 5_500);
+        assert_eq!(profile.normalize_decision_time(1_000, 0, 60_000), 2_500);
+    }
+
+    #[test]
+    fn test_only_the_player_on_the_clock_can_pause_it() {
+        let mut duel = DuelComponent::default();
+        duel.player_one = Pubkey::new_unique();
+        duel.player_two = Pubkey::new_unique();
+        let mut small = PlayerComponent::default();
+        small.player_id = duel.player_one;
+        small.position = PlayerPosition::Small;
+        let mut big = PlayerComponent::default();
+        big.player_id = duel.player_two;
+        big.position = PlayerPosition::Big;
+
+        // Before anyone acts, the small blind is first to act
+        assert!(duel.is_on_clock(&small));
+        assert!(!duel.is_on_clock(&big));
+
+        duel.last_actor = duel.player_one;
+        assert!(!duel.is_on_clock(&small));
+        assert!(duel.is_on_clock(&big));
+    }
+
+    #[test]
+    fn test_player_entropy_mixing() {
+        let mut duel = DuelComponent::default();
+        let vrf_result = 42u64;
+
+        // No reveals leaves the VRF output untouched
+        assert_eq!(mix_entropy(vrf_result, &duel), vrf_result);
+
+        duel.entropy_reveals[0] = [7u8; 32];
+        duel.entropy_revealed[0] = true;
+        let one_reveal = mix_entropy(vrf_result, &duel);
+        assert_ne!(one_reveal, vrf_result);
+
+        // Each player's secret changes the outcome
+        duel.entropy_reveals[1] = [9u8; 32];
+        duel.entropy_revealed[1] = true;
+        assert_ne!(mix_entropy(vrf_result, &duel), one_reveal);
+
+        let player = Pubkey::new_unique();
+        assert_ne!(entropy_commitment(&[7u8; 32], &player), entropy_commitment(&[7u8; 32], &Pubkey::new_unique()));
+
+        // Randomness waits for open commitments until the deadline, then the silent seat forfeits
+        duel.entropy_revealed[1] = false;
+        duel.entropy_commitments = [[1u8; 32], [2u8; 32]];
+        duel.entropy_reveal_deadline = 100;
+        assert_eq!(reveal_status(&duel, 100), RevealStatus::Outstanding);
+        assert_eq!(reveal_status(&duel, 101), RevealStatus::Forfeit { winner_seat: 0 });
+        duel.entropy_revealed[0] = false;
+        assert_eq!(reveal_status(&duel, 101), RevealStatus::Ready);
+    }
+
+    #[test]
+    fn test_transcript_hash_binds_outcome() {
+        let mut duel = DuelComponent {
+            duel_id: 7,
+            replay_head_hash: [3u8; 32],
+            ..DuelComponent::default()
+        };
+        let proof = [1u8; 64];
+        let winner = Pubkey::new_unique();
+        let transcript = compute_transcript_hash(&duel, &proof, 99, &winner);
+
+        assert_eq!(transcript, compute_transcript_hash(&duel, &proof, 99, &winner));
+        assert_ne!(transcript, compute_transcript_hash(&duel, &proof, 99, &Pubkey::new_unique()));
+        assert_ne!(transcript, compute_transcript_hash(&duel, &proof, 100, &winner));
+
+        // Any change to the action log or final balances changes the transcript
+        duel.replay_head_hash = [4u8; 32];
+        assert_ne!(transcript, compute_transcript_hash(&duel, &proof, 99, &winner));
+        duel.replay_head_hash = [3u8; 32];
+        duel.replay_state.chip_counts[0] = 1;
+        assert_ne!(transcript, compute_transcript_hash(&duel, &proof, 99, &winner));
+    }
+
+    #[test]
+    fn test_action_transcript_chains_in_order() {
+        let call = ReplayEntry { seat: 0, action_type: ActionType::Call, bet_amount: 50, round_number: 1, timestamp: 10 };
+        let raise = ReplayEntry { seat: 1, action_type: ActionType::Raise, bet_amount: 100, round_number: 1, timestamp: 11 };
+        let start = [0u8; 32];
+
+        let forward = extend_transcript(&extend_transcript(&start, 7, &call), 7, &raise);
+        let reversed = extend_transcript(&extend_transcript(&start, 7, &raise), 7, &call);
+        assert_ne!(forward, reversed);
+        assert_ne!(forward, extend_transcript(&extend_transcript(&start, 8, &call), 8, &raise));
+        assert_eq!(forward, extend_transcript(&extend_transcript(&start, 7, &call), 7, &raise));
+    }
+
+    #[test]
+    fn test_outcome_evaluators_are_deterministic() {
+        let duel = DuelComponent {
+            player_one: Pubkey::new_unique(),
+            player_two: Pubkey::new_unique(),
+            max_rounds: 5,
+            ..DuelComponent::default()
+        };
+
+        for evaluator in [
+            OutcomeEvaluator::HighCard,
+            OutcomeEvaluator::BestOfRounds,
+            OutcomeEvaluator::DamageRace,
+            OutcomeEvaluator::Weighted,
+        ] {
+            let mut seats = [0u32; 2];
+            for randomness in 0..200u64 {
+                let seat = evaluator.evaluate(randomness, &duel);
+                assert_eq!(seat, evaluator.evaluate(randomness, &duel));
+                seats[seat as usize] += 1;
+            }
+            // Both seats must be reachable under every rule set
+            assert!(seats[0] > 0 && seats[1] > 0);
+        }
+    }
+
+    #[test]
+    fn test_performance_weights() {
+        let mut duel = DuelComponent::default();
+        duel.replay_state.is_active = [true, true];
+        assert_eq!(performance_weights(&duel), [5_000, 5_000]);
+
+        // Committing more chips and winning rounds both shift the odds
+        duel.replay_state.total_bets = [300, 100];
+        duel.round_wins = [2, 0];
+        let weights = performance_weights(&duel);
+        assert_eq!(weights, [1_000 + 3_000 + 4_000, 2_000]);
+        assert_eq!(weights[0] + weights[1], 10_000);
+
+        // A folded seat cannot win
+        duel.replay_state.is_active = [false, true];
+        assert_eq!(performance_weights(&duel), [0, 10_000]);
+        duel.commit_weights();
+        for randomness in 0..50u64 {
+            assert_eq!(OutcomeEvaluator::Weighted.evaluate(randomness, &duel), 1);
+        }
+
+        // Entering resolution commits the weights; later changes no longer match
+        duel.game_state = GameState::InProgress;
+        assert!(duel.apply_transition(DuelTrigger::FinalRound));
+        assert_eq!(duel.weights_hash, weights_commitment(&duel, duel.weights_bps));
+        duel.round_wins = [0, 3];
+        duel.replay_state.is_active = [true, true];
+        assert_ne!(duel.weights_hash, weights_commitment(&duel, performance_weights(&duel)));
+        // Resolution samples the committed weights, not the changed state
+        for randomness in 0..50u64 {
+            assert_eq!(OutcomeEvaluator::Weighted.evaluate(randomness, &duel), 1);
+        }
+    }
+
+    #[test]
+    fn test_close_round_counts_only_won_rounds() {
+        let mut duel = DuelComponent::default();
+        duel.replay_state.is_active = [true, true];
+
+        // Outbetting an opponent who stays in does not win the round
+        duel.replay_state.total_bets = [500, 100];
+        duel.close_round();
+        assert_eq!(duel.round_wins, [0, 0]);
+
+        duel.replay_state.is_active = [true, false];
+        duel.close_round();
+        assert_eq!(duel.round_wins, [1, 0]);
+    }
+
+    #[test]
+    fn test_blind_schedule_escalation() {
+        let mut betting = BettingComponent {
+            blind_levels: vec![
+                BlindLevel { small_blind: 50, big_blind: 100, ante: 0 },
+                BlindLevel { small_blind: 100, big_blind: 200, ante: 25 },
+            ],
+            blind_escalation: BlindEscalation::EveryRounds(2),
+            ..BettingComponent::default()
+        };
+
+        assert_eq!(betting.level_for(1, 0), 0);
+        assert_eq!(betting.level_for(2, 0), 0);
+        assert_eq!(betting.level_for(3, 0), 1);
+        // Capped at the last configured level
+        assert_eq!(betting.level_for(9, 0), 1);
+
+        betting.blind_level = 1;
+        assert_eq!(betting.forced_bet(PlayerPosition::Small), 125);
+        assert_eq!(betting.forced_bet(PlayerPosition::Big), 225);
+
+        betting.blind_escalation = BlindEscalation::EverySeconds(600);
+        assert_eq!(betting.level_for(1, 599), 0);
+        assert_eq!(betting.level_for(1, 600), 1);
+    }
+
+    #[test]
+    fn test_betting_structure_raises() {
+        let mut betting = BettingComponent {
+            min_bet: 100,
+            total_pot: 300,
+            current_bet: 200,
+            ..BettingComponent::default()
+        };
+
+        // No-limit: raises must be at least the last raise
+        betting.register_raise(200);
+        assert!(!betting.is_legal_raise(150, 200));
+        assert!(betting.is_legal_raise(5_000, 200));
+
+        // Pot-limit: capped at the pot after calling
+        betting.betting_structure = BettingStructure::PotLimit;
+        assert!(betting.is_legal_raise(500, 200));
+        assert!(!betting.is_legal_raise(501, 200));
+
+        // Fixed-limit: exact size and capped raises per round
+        betting.betting_structure = BettingStructure::FixedLimit;
+        betting.reset_round_raises();
+        assert!(betting.is_legal_raise(100, 0));
+        assert!(!betting.is_legal_raise(200, 0));
+        betting.raises_this_round = FIXED_LIMIT_MAX_RAISES;
+        assert!(!betting.is_legal_raise(100, 0));
+
+        // A short all-in lifts the bet without reopening raising
+        betting.betting_structure = BettingStructure::NoLimit;
+        betting.reset_round_raises();
+        betting.register_raise(200);
+        betting.register_all_in(250);
+        assert_eq!(betting.current_bet, 250);
+        assert_eq!(betting.last_raise_amount, 200);
+        assert_eq!(betting.raises_this_round, 1);
+    }
+
+    #[test]
+    fn test_transfer_limit_flags_net_flow() {
+        let config = TransferLimitConfig {
+            max_net_transfer: 1_000,
+            window_seconds: DEFAULT_TRANSFER_WINDOW,
+            ..TransferLimitConfig::default()
+        };
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut limit = TransferLimit { first: a, second: b, ..TransferLimit::default() };
+
+        assert!(!limit.record(&config, &a, 800, 100));
+        // Flow back the other way nets out
+        assert!(!limit.record(&config, &b, 500, 200));
+        assert!(!limit.record(&config, &a, 700, 300));
+        assert!(limit.record(&config, &a, 100, 400));
+        assert!(limit.flagged);
+
+        // A new window starts from zero
+        assert!(!limit.record(&config, &a, 900, 400 + DEFAULT_TRANSFER_WINDOW));
+        assert_eq!(limit.net_flow, 900);
+    }
+
+    #[test]
+    fn test_reputation_score() {
+        let mut psych = PsychProfileComponent::default();
+        assert_eq!(psych.refresh_reputation(), DEFAULT_REPUTATION);
+
+        psych.duels_started = 10;
+        psych.duels_completed = 9;
+        psych.timeouts = 1;
+        assert_eq!(psych.refresh_reputation(), 800);
+
+        psych.collusion_flags = 1;
+        assert_eq!(psych.refresh_reputation(), 650);
+
+        psych.disputes = 20;
+        assert_eq!(psych.refresh_reputation(), 0);
+    }
+
+    #[test]
+    fn test_bankroll_withdrawal_policy() {
+        let cosigner = Pubkey::new_unique();
+        let mut bankroll = Bankroll {
+            policy: WithdrawalPolicy {
+                daily_limit: 1_000,
+                large_win_threshold: 5_000,
+                large_win_cooldown: 3_600,
+                cosigner,
+                cosign_threshold: 500,
+            },
+            ..Bankroll::default()
+        };
+
+        assert!(bankroll.authorize_withdrawal(400, false, 0).is_ok());
+        // Above the co-sign threshold needs the co-signer
+        assert!(bankroll.authorize_withdrawal(600, false, 10).is_err());
+        assert!(bankroll.authorize_withdrawal(600, true, 10).is_ok());
+        assert!(bankroll.authorize_withdrawal(100, true, 20).is_err());
+
+        // A large win locks withdrawals; the daily window resets afterwards
+        assert!(bankroll.record_win(5_000, 100));
+        assert!(bankroll.authorize_withdrawal(100, false, 200).is_err());
+        assert!(bankroll.authorize_withdrawal(100, false, WITHDRAWAL_WINDOW).is_ok());
+
+        // Raising the limit waits out the timelock; tightening applies at once
+        let looser = WithdrawalPolicy { daily_limit: 0, ..bankroll.policy };
+        assert!(bankroll.set_policy(looser, 1_000));
+        assert_eq!(bankroll.policy.daily_limit, 1_000);
+        bankroll.activate_pending_policy(1_000 + POLICY_TIMELOCK);
+        assert_eq!(bankroll.policy.daily_limit, 0);
+
+        let tighter = WithdrawalPolicy { daily_limit: 200, ..bankroll.policy };
+        assert!(!bankroll.set_policy(tighter, 2_000 + POLICY_TIMELOCK));
+        assert_eq!(bankroll.policy.daily_limit, 200);
+        assert!(bankroll.pending_policy.is_none());
+    }
+
+    #[test]
+    fn test_operator_slash_split() {
+        assert_eq!(pro_rata_split(1_000, 300, 100), (750, 250));
+        assert_eq!(pro_rata_split(1_001, 0, 0), (500, 501));
+
+        let bond = OperatorBond { amount: MIN_OPERATOR_BOND / 2, ..OperatorBond::default() };
+        assert!(!bond.can_accept_rollup());
+        assert_eq!(bond.slash_amount(), MIN_OPERATOR_BOND / 2);
+    }
+
+    #[test]
+    fn test_fraudulent_transition() {
+        // A transition must start from the committed state and stay reachable from it
+        assert!(!is_fraudulent_transition(GameState::InProgress, GameState::InProgress, GameState::AwaitingAction));
+        assert!(is_fraudulent_transition(GameState::InProgress, GameState::AwaitingAction, GameState::InProgress));
+        assert!(is_fraudulent_transition(GameState::Completed, GameState::Completed, GameState::InProgress));
+    }
+
+    #[test]
+    fn test_elo_handicap_split() {
+        assert_eq!(elo_payout_shares([1500, 1500]), [10_000, 10_000]);
+
+        // A 400-point favorite (expected score ~0.91) takes 55% of the pot on a win
+        let mut duel = DuelComponent { handicap: HandicapMode::EloOdds, ..DuelComponent::default() };
+        duel.fix_handicap([1600, 1200]);
+        assert_eq!(duel.payout_share_bps, [5_500, 10_000]);
+        assert_eq!(duel.handicap_split(0, 2_000), (1_100, 900));
+        assert_eq!(duel.handicap_split(1, 2_000), (2_000, 0));
+
+        // Duels without fixed terms settle even
+        assert_eq!(DuelComponent::default().handicap_split(0, 2_000), (2_000, 0));
+    }
+
+    #[test]
+    fn test_early_forfeit_refund() {
+        let mut duel = DuelComponent { early_forfeit_refund_bps: 5_000, ..DuelComponent::default() };
+        let mut player = PlayerComponent { total_bet: 300, ..PlayerComponent::default() };
+        assert_eq!(early_forfeit_refund(&duel, &player), 150);
+
+        // The refund comes out of the winner's take
+        duel.forfeit_refund = 150;
+        assert_eq!(duel.settlement_split(0, 2_000), (1_850, 150));
+
+        // Once the player has acted, nothing is refunded
+        player.actions_taken = 1;
+        assert_eq!(early_forfeit_refund(&duel, &player), 0);
+    }
+
+    #[test]
+    fn test_draw_split() {
+        // Even pots split evenly; the odd chip goes to the small-blind seat
+        assert_eq!(DuelComponent::draw_split(1_000, PlayerPosition::Small), [500, 500]);
+        assert_eq!(DuelComponent::draw_split(1_001, PlayerPosition::Small), [501, 500]);
+        assert_eq!(DuelComponent::draw_split(1_001, PlayerPosition::Big), [500, 501]);
+    }
+
+    #[test]
+    fn test_draw_resolution_reaches_settlement() {
+        let mut duel = DuelComponent { game_state: GameState::ResolutionPending, resolution_pending: true, ..DuelComponent::default() };
+        let betting = BettingComponent { total_pot: 1_001, ..BettingComponent::default() };
+
+        // An exact tie completes the duel with no winner and leaves the pot to settlement
+        assert!(duel.record_resolution(None));
+        assert_eq!(duel.game_state, GameState::Completed);
+        assert!(duel.is_draw && duel.winner.is_none() && !duel.resolution_pending);
+        assert!(!betting.is_settled);
+        assert_eq!(DuelComponent::draw_split(betting.total_pot, PlayerPosition::Small), [501, 500]);
+
+        // A completed duel cannot be resolved again
+        assert!(!duel.record_resolution(Some(Pubkey::new_unique())));
+        assert!(duel.is_draw);
+    }
+
+    #[test]
+    fn test_table_tier_gate_binding() {
+        let vip_mint = Pubkey::new_unique();
+        let gate = |tier, gate_mint, min_balance| TableGate { tier, gate_mint, min_balance };
+        let config = TableTierConfig {
+            gates: [
+                TableGate::default(),
+                gate(TableTier::HighStakes, Pubkey::new_unique(), 100),
+                gate(TableTier::Vip, vip_mint, 1),
+            ],
+            ..TableTierConfig::default()
+        };
+        assert!(config.allows(&TableGate::default()));
+        assert!(config.allows(&gate(TableTier::Vip, vip_mint, 5)));
+        // A VIP label cannot be paired with another mint or a lower minimum
+        assert!(!config.allows(&gate(TableTier::Vip, Pubkey::new_unique(), 1)));
+        assert!(!config.allows(&gate(TableTier::HighStakes, vip_mint, 100)));
+    }
+
+    #[test]
+    fn test_rake_schedule_tiers() {
+        let tier = |min_pot, rake_bps| RakeTier { min_pot, rake_bps };
+        let mut schedule = RakeSchedule {
+            tiers: vec![tier(0, 0), tier(1_000, 500), tier(10_000, 250)],
+            max_rake_per_pot: 400,
+            no_flop_no_drop: true,
+            ..RakeSchedule::default()
+        };
+        assert!(RakeSchedule::validate_tiers(&schedule.tiers));
+        assert!(!RakeSchedule::validate_tiers(&[tier(1_000, 500), tier(1_000, 250)]));
+
+        assert_eq!(schedule.rake_for(500, 2), 0);
+        assert_eq!(schedule.rake_for(2_000, 2), 100);
+        // Large pots drop to the lower tier and are capped per pot
+        assert_eq!(schedule.rake_for(12_000, 2), 300);
+        assert_eq!(schedule.rake_for(40_000, 2), 400);
+        // No flop, no drop
+        assert_eq!(schedule.rake_for(2_000, 0), 0);
+        schedule.no_flop_no_drop = false;
+        assert_eq!(schedule.rake_for(2_000, 0), 100);
+    }
+
+    #[test]
+    fn test_rakeback_accrual() {
+        let staking_mint = Pubkey::new_unique();
+        let program = LoyaltyProgram {
+            staking_mint,
+            epoch_duration: 100,
+            rakeback_bps: 2_000,
+            tier_thresholds: [10, 100, 1_000],
+            tier_multipliers_bps: [10_000, 12_500, 15_000, 20_000],
+            ..LoyaltyProgram::default()
+        };
+        assert_eq!(program.tier_for(0), 0);
+        assert_eq!(program.tier_for(150), 2);
+        assert!(LoyaltyProgram::is_valid_config(100, 2_000, &program.tier_thresholds, &program.tier_multipliers_bps));
+        // 50% rake-back at 3x would pay out 150% of the rake
+        assert!(!LoyaltyProgram::is_valid_config(100, MAX_RAKEBACK_BPS, &program.tier_thresholds, &[10_000, 15_000, 20_000, 30_000]));
+        assert_eq!(program.max_rakeback_for(1_000), 400);
+        assert_eq!(split_rake(101, [300, 100]), [76, 25]);
+        assert_eq!(split_rake(101, [0, 0]), [51, 50]);
+
+        // Only the time-weighted stake over a closed epoch counts
+        let mut stake = LoyaltyStake { amount: 0, last_update: 0, ..LoyaltyStake::default() };
+        stake.accrue(&program, 90);
+        stake.amount = 1_000; // Borrowed for the last 10s: averages 100, not 1,000
+        stake.accrue(&program, 100);
+        stake.amount = 0;
+        stake.accrue(&program, 150);
+        assert_eq!(program.tier_for(stake.closed_epoch_average), 2);
+        stake.accrue(&program, 250);
+        assert_eq!(program.tier_for(stake.closed_epoch_average), 0);
+
+        // Rake in the open epoch only becomes claimable once the epoch closes
+        let mut account = LoyaltyAccount::default();
+        account.record_rake(400, program.current_epoch(50));
+        account.record_rake(100, program.current_epoch(99));
+        assert_eq!(account.claimable_rake, 0);
+        account.roll_to(program.current_epoch(150));
+        assert_eq!((account.claimable_rake, account.lifetime_rake_paid), (500, 500));
+
+        assert_eq!(program.rakeback_for(account.claimable_rake, 0), 100);
+        assert_eq!(program.rakeback_for(account.claimable_rake, 3), 200);
+    }
+
+    #[test]
+    fn test_jackpot_odds_and_split() {
+        let vault = JackpotVault { contribution_bps: 50, hit_odds: 100, winner_share_bps: 7_000, ..JackpotVault::default() };
+        assert!(JackpotVault::is_valid_config(50, 100, 7_000));
+        assert!(!JackpotVault::is_valid_config(500, 100, 7_000));
+        assert!(!JackpotVault::is_valid_config(50, 10, 7_000));
+        assert_eq!(vault.contribution_for(20_000), 100);
+        assert_eq!(vault.payout_split(1_001, false), [700, 301]);
+        assert_eq!(vault.payout_split(1_001, true), [501, 500]);
+
+        // The roll is a pure function of the resolved randomness, and hits about 1 in `hit_odds`
+        let hits = (0..10_000u64).filter(|&id| vault.is_hit(id, &[7u8; 32])).count();
+        assert_eq!(vault.is_hit(3, &[7u8; 32]), vault.is_hit(3, &[7u8; 32]));
+        assert!((50..=150).contains(&hits));
+
+        // A forfeit settles without VRF output, so its zeroed roll can never hit
+        assert!((0..10_000u64).all(|id| !vault.is_hit(id, &[0u8; 32])));
+    }
+
+    #[test]
+    fn test_daily_contest_records() {
+        let day = 86_400;
+        let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut contest = DailyContest { day: 1, ..DailyContest::default() };
+
+        assert!(contest.record(day + 10, 5_000, alice, 1));
+        assert!(!contest.record(day + 20, 4_000, bob, 2));
+        assert!(contest.record(day + 30, 6_000, bob, 3));
+        assert_eq!((contest.record_holder, contest.record_pot), (Some(bob), 6_000));
+
+        // After midnight the standing record waits for the finalize crank
+        assert!(!contest.record(2 * day + 5, 9_000, alice, 4));
+        assert_eq!(contest.day, 1);
+    }
+
+    #[test]
+    fn test_k_factor_and_placement() {
+        assert_eq!(k_factor(INITIAL_RATING_DEVIATION), MAX_K_FACTOR);
+        assert_eq!(k_factor(MIN_RATING_DEVIATION), MIN_K_FACTOR);
+        assert_eq!(k_factor(0), MIN_K_FACTOR);
+
+        let mut psych = PsychProfileComponent::default();
+        assert_eq!(psych.track(QueueType::Ranked).deviation(), INITIAL_RATING_DEVIATION);
+        for game in 1..=PLACEMENT_MATCHES {
+            assert_eq!(psych.track(QueueType::Ranked).public_rating(), None);
+            psych.track_mut(QueueType::Ranked).record_rated_game(1_200 + game, game as i64);
+        }
+        // Placement done: the rating is public and the deviation has tightened
+        assert_eq!(psych.track(QueueType::Ranked).public_rating(), Some(1_210));
+        assert_eq!(psych.track(QueueType::Ranked).deviation(), MIN_RATING_DEVIATION);
+        // Other queues are untouched
+        assert_eq!(psych.track(QueueType::Casual).rating(), INITIAL_SKILL_RATING);
+    }
+
+    #[test]
+    fn test_rating_decay() {
+        let week = SECONDS_PER_WEEK;
+        let mut config = RatingDecayConfig { threshold: 1_800, decay_per_week: 15, ..RatingDecayConfig::default() };
+        let track = RatingTrack { skill_rating: 1_850, rated_games: 40, last_rated_at: 0, ..RatingTrack::default() };
+
+        assert_eq!(config.pending_decay(&track, week - 1), (0, 1_850));
+        assert_eq!(config.pending_decay(&track, 3 * week), (3, 1_805));
+        // Never below the threshold, and never for ratings already under it
+        assert_eq!(config.pending_decay(&track, 10 * week), (10, 1_800));
+        let below = RatingTrack { skill_rating: 1_500, ..track };
+        assert_eq!(config.pending_decay(&below, 10 * week), (0, 1_500));
+
+        // Off-season weeks never count
+        config.off_season = true;
+        assert_eq!(config.pending_decay(&track, 3 * week), (0, 1_850));
+        config.off_season = false;
+        config.season_resumed_at = 8 * week;
+        assert_eq!(config.pending_decay(&track, 9 * week), (1, 1_835));
+
+        // Glicko-2 ratings decay by the same points, down to the same threshold
+        let glicko = shared::Glicko2Rating { rating: 1_850 * shared::GLICKO_FX, ..shared::Glicko2Rating::INITIAL };
+        assert_eq!(config.decayed_glicko(glicko, 3).rating, 1_805 * shared::GLICKO_FX);
+        assert_eq!(config.decayed_glicko(glicko, 10).rating, 1_800 * shared::GLICKO_FX);
+        assert_eq!(config.decayed_glicko(shared::Glicko2Rating::default(), 10), shared::Glicko2Rating::default());
+
+        // Settlement applies its delta on top of any decay since the duel opened
+        let mut decayed = RatingTrack { skill_rating: 1_805, ..track };
+        decayed.record_rated_delta(16, 10 * week);
+        assert_eq!(decayed.rating(), 1_821);
+    }
+
+    #[test]
+    fn test_recent_matches_ring_buffer() {
+        let mut history = RecentMatches::default();
+        assert_eq!(history.newest_first().count(), 0);
+
+        for duel_id in 1..=(RECENT_MATCHES_CAPACITY as u64 + 3) {
+            history.record(MatchSummary { duel_id, ..MatchSummary::default() });
+        }
+        // The three oldest were overwritten; newest comes first
+        let ids: Vec<u64> = history.newest_first().map(|summary| summary.duel_id).collect();
+        assert_eq!(ids.len(), RECENT_MATCHES_CAPACITY);
+        assert_eq!(ids[0], RECENT_MATCHES_CAPACITY as u64 + 3);
+        assert_eq!(*ids.last().unwrap(), 4);
+    }
+
+    #[test]
+    fn test_head_to_head_record() {
+        let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut record = HeadToHead { first: alice, second: bob, ..HeadToHead::default() };
+        record.record(Some(alice), 1_000, 1, 10);
+        record.record(Some(bob), 4_000, 2, 20);
+        record.record(Some(alice), 500, 3, 30);
+        record.record(None, 800, 4, 40);
+
+        assert_eq!(record.record_for(&alice), (2, 1, 1));
+        assert_eq!(record.record_for(&bob), (1, 2, 1));
+        assert_eq!((record.duels, record.total_pot, record.largest_pot), (4, 6_300, 4_000));
+    }
+
+    #[test]
+    fn test_head_to_head_opens_in_pair_order() {
+        let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut from_alice = HeadToHead::default();
+        let mut from_bob = HeadToHead::default();
+        from_alice.open(alice, bob);
+        from_bob.open(bob, alice);
+        assert_eq!((from_alice.first, from_alice.second), (from_bob.first, from_bob.second));
+
+        // Reopening an existing record leaves it alone
+        let stranger = Pubkey::new_unique();
+        from_alice.open(stranger, alice);
+        assert_eq!((from_alice.first, from_alice.second), (from_bob.first, from_bob.second));
+
+        let summaries = seat_summaries(7, [alice, bob], [100, 60], [MatchResult::Win, MatchResult::Loss], [150, 10], [12, -12], 50);
+        assert_eq!((summaries[0].opponent, summaries[1].opponent), (bob, alice));
+        assert_eq!((summaries[0].payout, summaries[1].rating_delta), (150, -12));
+    }
+
+    #[test]
+    fn test_scheduled_duel_resolution() {
+        let mut scheduled = ScheduledDuel {
+            stake: 1_000,
+            forfeit_fee: 200,
+            start_time: 10_000,
+            check_in_grace: 300,
+            escrowed: [true, false],
+            ..ScheduledDuel::default()
+        };
+        assert!(!scheduled.check_in_open(9_699));
+        assert!(scheduled.check_in_open(9_700) && scheduled.check_in_open(10_300));
+        assert!(scheduled.check_in_closed(10_301));
+
+        // Never accepted: the host gets their stake back
+        assert_eq!(scheduled.resolution(), (ScheduleStatus::Cancelled, [1_000, 0]));
+
+        scheduled.status = ScheduleStatus::Confirmed;
+        scheduled.escrowed = [true, true];
+        scheduled.checked_in = [false, true];
+        assert_eq!(scheduled.resolution(), (ScheduleStatus::Forfeited, [800, 1_200]));
+
+        // Both present: the stakes stay escrowed for the duel
+        scheduled.checked_in = [true, true];
+        assert_eq!(scheduled.resolution(), (ScheduleStatus::Started, [0, 0]));
+    }
+
+    #[test]
+    fn test_scheduled_duel_start_and_escrow_payout() {
+        let (host, opponent) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let params = CreateDuelParams {
+            max_rounds: 3,
+            min_bet: 10,
+            max_bet: 1_000,
+            timeout_duration: 60,
+            entry_fee: 1_000,
+            ..CreateDuelParams::default()
+        };
+        let scheduled = ScheduledDuel {
+            players: [host, opponent],
+            stake: 1_000,
+            escrowed: [true, true],
+            checked_in: [true, true],
+            status: ScheduleStatus::Confirmed,
+            params_hash: schedule_params_hash(&params).unwrap(),
+            ..ScheduledDuel::default()
+        };
+        assert_eq!(scheduled.resolution().0, ScheduleStatus::Started);
+        // Any change to the booked terms is rejected at start
+        let other_terms = CreateDuelParams { max_bet: 5_000, ..params.clone() };
+        assert_ne!(scheduled.params_hash, schedule_params_hash(&other_terms).unwrap());
+
+        let mut duel = DuelComponent::default();
+        let mut betting = BettingComponent::default();
+        let (mut first, mut second) = (PlayerComponent::default(), PlayerComponent::default());
+        instructions::matchmaking::open_seated_duel(
+            &mut duel,
+            &mut betting,
+            [&mut first, &mut second],
+            scheduled.players,
+            params,
+            shared::EventModifiers::default(),
+            1_000,
+        )
+        .unwrap();
+        duel.escrowed_stake = scheduled.stake;
+        assert_eq!((duel.player_one, duel.player_two), (host, opponent));
+        assert_eq!(duel.game_state, GameState::InProgress);
+        assert_eq!(betting.total_pot, 2_000);
+
+        // Winner takes the pot less a 5% rake; lamports follow the chips
+        assert_eq!(duel.escrow_split(2_000, 100, [1_900, 0]), (100, [1_900, 0]));
+        // A handicap refund to the loser is mirrored in lamports
+        assert_eq!(duel.escrow_split(4_000, 200, [2_850, 950]), (100, [1_425, 475]));
+        // Chip-only duels move no lamports
+        duel.escrowed_stake = 0;
+        assert_eq!(duel.escrow_split(2_000, 100, [1_900, 0]), (0, [0, 0]));
+    }
+
+    #[test]
+    fn test_insurance_claim_then_escrow_refund() {
+        let mut duel = DuelComponent { escrowed_stake: 1_000, ..DuelComponent::default() };
+
+        // Seat one claims part of its stake; the pool takes that part from escrow
+        assert_eq!(duel.claim_stake(0, 600), 600);
+        // Nothing beyond the stake can ever be taken from the seat
+        assert_eq!(duel.claim_stake(0, 600), 400);
+        assert_eq!(duel.claim_stake(0, 600), 0);
+
+        // The refund returns only what no claim paid out
+        assert_eq!([duel.refundable_stake(0), duel.refundable_stake(1)], [0, 1_000]);
+
+        // Once refunded, nothing is left to claim against
+        duel.escrowed_stake = 0;
+        assert_eq!(duel.claim_stake(1, 500), 0);
+    }
+
+    #[test]
+    fn test_private_duel_observers() {
+        let (alice, bob, coach, stranger) =
+            (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let duel = DuelComponent { duel_id: 7, player_one: alice, player_two: bob, is_private: true, ..DuelComponent::default() };
+        let mut state = SpectatorState { duel_id: 7, ..SpectatorState::default() };
+
+        state.set_seat_observers(0, &[coach]).unwrap();
+        assert!(may_spectate(&duel, Some(&state), &coach));
+        assert!(may_spectate(&duel, None, &bob));
+        assert!(!may_spectate(&duel, Some(&state), &stranger));
+
+        // Bob's grants never displace Alice's
+        state.set_seat_observers(1, &[stranger]).unwrap();
+        state.set_seat_observers(1, &[]).unwrap();
+        assert!(state.can_observe(&coach) && !state.can_observe(&stranger));
+
+        let public = DuelComponent { is_private: false, ..duel };
+        assert!(may_spectate(&public, None, &stranger));
+    }
+
+    #[test]
+    fn test_rate_limit_sliding_window() {
+        let window = shared::RateWindow { window_secs: 60, max_calls: 5 };
+        let mut limit = shared::RateLimit { window_start: 1_000, ..shared::RateLimit::default() };
+        for second in 0..5 {
+            assert!(limit.consume(window, 1_000 + second).is_ok());
+        }
+        assert!(limit.consume(window, 1_010).is_err());
+
+        // Halfway into the next window, half of the previous five still count
+        for _ in 0..3 {
+            assert!(limit.consume(window, 1_090).is_ok());
+        }
+        assert!(limit.consume(window, 1_090).is_err());
+
+        // Idle for two windows clears the history
+        assert!(limit.consume(window, 1_300).is_ok());
+        assert_eq!((limit.previous_count, limit.current_count), (0, 1));
+    }
+
+    #[test]
+    fn test_duel_gc_terms() {
+        let config = DuelGcConfig {
+            ttl_seconds: DEFAULT_ABANDONED_DUEL_TTL,
+            bounty_bps: DEFAULT_GC_BOUNTY_BPS,
+            ..Default::default()
+        };
+
+        assert!(!config.is_abandoned(1_000, 1_000 + DEFAULT_ABANDONED_DUEL_TTL - 1));
+        assert!(config.is_abandoned(1_000, 1_000 + DEFAULT_ABANDONED_DUEL_TTL));
+        assert!(!config.is_abandoned(i64::MAX - 10, i64::MAX));
+
+        assert_eq!(config.bounty(5_000_000), 500_000);
+        assert_eq!(config.bounty(0), 0);
+        assert_eq!(DuelGcConfig { bounty_bps: MAX_GC_BOUNTY_BPS, ..config }.bounty(u64::MAX), u64::MAX / 2);
+    }
+
+    #[test]
+    fn test_switchboard_reveal() {
+        let oracle = Pubkey::new_unique();
+        let mut data = vec![0u8; 376];
+        data[8..40].copy_from_slice(oracle.as_ref());
+        data[144..152].copy_from_slice(&500u64.to_le_bytes());
+        data[152..184].copy_from_slice(&[9u8; 32]);
+
+        assert_eq!(switchboard_reveal(&data, &oracle, 500).unwrap(), [9u8; 32]);
+        // Stale reveal, foreign authority, truncated account
+        assert!(switchboard_reveal(&data, &oracle, 501).is_err());
+        assert!(switchboard_reveal(&data, &Pubkey::new_unique(), 500).is_err());
+        assert!(switchboard_reveal(&data[..100], &oracle, 500).is_err());
+    }
+
+    #[test]
+    fn test_insurance_coverage_limit() {
+        let vault = InsuranceVault { premium_bps: 200, max_coverage: 5_000, ..Default::default() };
+
+        // Coverage never exceeds the seat's escrow or the pool limit
+        assert_eq!(vault.coverage_limit(0), 0);
+        assert_eq!(vault.coverage_limit(3_000), 3_000);
+        assert_eq!(vault.coverage_limit(9_000), 5_000);
+        assert_eq!(vault.premium_for(5_000), 100);
+    }
+
+    #[test]
+    fn test_spectator_pool_payouts() {
+        let mut pool = SpectatorPool { rake_bps: 500, totals: [3_000, 1_000], ..SpectatorPool::default() };
+        let backer = |seat, amount| SpectatorBet { seat, amount, ..SpectatorBet::default() };
+        assert_eq!(pool.rake(), 200);
+
+        // Seat 1 wins: its backers split the 3_800 left after rake pro rata
+        pool.winning_seat = 1;
+        assert_eq!(pool.payout_for(&backer(1, 250)), 950);
+        assert_eq!(pool.payout_for(&backer(0, 3_000)), 0);
+
+        // A voided pool hands every stake back
+        pool.voided = true;
+        assert_eq!(pool.payout_for(&backer(0, 3_000)), 3_000);
+    }
+
+    #[test]
+    fn test_system_registry_gating() {
+        let components = ComponentRegistry::new();
+        let mut systems = SystemRegistry::new();
+        assert!(systems.require_runnable(SYSTEM_VRF_RESOLUTION, &components).is_ok());
+
+        // A disabled system is refused until re-enabled
+        systems.set_active(SYSTEM_VRF_RESOLUTION, false).unwrap();
+        assert!(systems.require_runnable(SYSTEM_VRF_RESOLUTION, &components).is_err());
+        assert!(systems.require_runnable(SYSTEM_ACTION_PROCESSING, &components).is_ok());
+        systems.set_active(SYSTEM_VRF_RESOLUTION, true).unwrap();
+        assert!(systems.require_runnable(SYSTEM_VRF_RESOLUTION, &components).is_ok());
+
+        // Unknown systems and disabled dependencies are rejected
+        assert!(systems.set_active(99, false).is_err());
+        let mut without_vrf = components.clone();
+        without_vrf.registered_components[3].is_active = false;
+        assert!(systems.require_runnable(SYSTEM_VRF_RESOLUTION, &without_vrf).is_err());
+        assert!(systems.require_runnable(SYSTEM_ACTION_PROCESSING, &without_vrf).is_ok());
+    }
+
     #[test]
     fn test_elo_calculation() {
         let (winner_change, loser_change) = utils::calculate_elo_change(1200, 1200, 32);
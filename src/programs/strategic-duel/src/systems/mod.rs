@@ -7,17 +7,25 @@ pub mod round_progression;
 pub mod vrf_resolution;
 pub mod psychological_analysis;
 pub mod settlement;
+pub mod outcome;
 
 pub use action_processing::*;
 pub use round_progression::*;
 pub use vrf_resolution::*;
 pub use psychological_analysis::*;
 pub use settlement::*;
+pub use outcome::*;
 
 /// ActionProcessingSystem - Handles CHECK, RAISE, CALL, FOLD actions
 #[system]
 pub mod action_processing {
-    pub fn execute(ctx: Context<ActionProcessing>, action_type: ActionType, bet_amount: u64) -> Result<()> {
+    pub fn execute(
+        ctx: Context<ActionProcessing>,
+        action_type: ActionType,
+        bet_amount: u64,
+        sync_nonce: Option<u64>,
+        latency: Option<LatencyAttestation>,
+    ) -> Result<()> {
         let clock = Clock::get()?;
         let current_time = clock.unix_timestamp;
 
@@ -30,11 +38,31 @@ pub mod action_processing {
 
         // Validate game state
         require!(duel.game_state == GameState::AwaitingAction, GameError::InvalidGameState);
+        require!(!duel.migration_locked, GameError::MigrationLocked);
         require!(player.is_active, GameError::PlayerInactive);
         require!(!duel.is_timeout_exceeded(current_time), GameError::ActionTimeout);
+        require!(!duel.is_paused(current_time), GameError::DuelPaused);
 
-        // Record action timing for psychological analysis
-        let decision_time = (current_time - duel.last_action_time) as u32;
+        // A reconnected client must prove it re-synced before acting
+        consume_sync_nonce(&mut player, sync_nonce)?;
+
+        // Record action timing for psychological analysis, excluding any reconnect grace
+        // and the network latency attested by the ER sequencer
+        let latency_ms = match latency {
+            Some(attestation) => {
+                let signer = ctx.accounts.latency_signer.as_ref().ok_or(GameError::InvalidLatencyAttestation)?;
+                require!(
+                    signer.key() == attestation.sequencer && attestation.sequencer == duel.latency_authority,
+                    GameError::InvalidLatencyAttestation
+                );
+                require!(attestation.slot <= clock.slot, GameError::InvalidLatencyAttestation);
+                attestation.latency_ms
+            },
+            None => 0,
+        };
+        let raw_ms = (current_time - duel.last_action_time - duel.grace_extension).max(0) as u64 * 1000;
+        let timeout_ms = duel.timeout_duration.max(0) as u64 * 1000;
+        let decision_time = psych_profile.normalize_decision_time(raw_ms, latency_ms, timeout_ms);
         psych_profile.update_decision_time(decision_time);
 
         // Process action based on type
@@ -55,13 +83,16 @@ pub mod action_processing {
                 let total_required = betting.current_bet + bet_amount;
                 let additional_bet = total_required.saturating_sub(player.total_bet);
                 
+                let to_call = betting.current_bet.saturating_sub(player.total_bet);
+                
                 require!(betting.can_raise(player.chip_count, bet_amount), GameError::InvalidRaise);
+                require!(betting.is_legal_raise(bet_amount, to_call), GameError::InvalidRaise);
                 require!(player.can_bet(additional_bet), GameError::InsufficientChips);
 
                 player.chip_count -= additional_bet;
                 player.total_bet = total_required;
                 betting.current_bet = total_required;
-                betting.last_raise_amount = bet_amount;
+                betting.register_raise(bet_amount);
                 betting.add_to_pot(additional_bet);
 
                 // Update psychological profile for aggression
@@ -73,7 +104,7 @@ pub mod action_processing {
                 
                 // Check if only one player remains
                 if should_end_round(&duel) {
-                    duel.game_state = GameState::ResolutionPending;
+                    require!(duel.apply_transition(DuelTrigger::Fold), GameError::InvalidGameState);
                 }
             },
             ActionType::AllIn => {
@@ -82,6 +113,7 @@ pub mod action_processing {
 
                 player.chip_count = 0;
                 player.total_bet += all_in_amount;
+                betting.register_all_in(player.total_bet);
                 betting.add_to_pot(all_in_amount);
 
                 // Create side pot if necessary
@@ -101,13 +133,27 @@ pub mod action_processing {
         action.is_processed = true;
         action.processing_time = Some(current_time);
 
+        // Append to the replay log
+        let seat = duel.seat_of(&player.player_id).ok_or(GameError::PlayerInactive)?;
+        let mut replay_chunk = ctx.accounts.replay_chunk.load_mut()?;
+        record_replay_entry(&mut duel, &mut replay_chunk, ReplayEntry {
+            seat,
+            action_type,
+            bet_amount,
+            round_number: duel.current_round,
+            timestamp: current_time,
+        })?;
+
         // Update game state
         player.actions_taken += 1;
         duel.last_action_time = current_time;
+        duel.last_actor = player.player_id;
+        duel.grace_extension = 0;
 
         // Transition to next game state
-        if all_players_acted(&duel) {
-            duel.game_state = GameState::InProgress;
+        // A fold may already have moved the duel to resolution
+        if duel.game_state == GameState::AwaitingAction && all_players_acted(&duel) {
+            require!(duel.apply_transition(DuelTrigger::RoundComplete), GameError::InvalidGameState);
         }
 
         emit!(ActionProcessedEvent {
@@ -153,21 +199,25 @@ pub mod round_progression {
         let mut betting = ctx.accounts.betting.load_mut()?;
 
         require!(duel.game_state == GameState::InProgress, GameError::InvalidGameState);
+        require!(!duel.migration_locked, GameError::MigrationLocked);
 
         // Check if round should advance
         if should_advance_round(&duel, current_time) {
+            duel.close_round();
             duel.current_round += 1;
             betting.betting_round += 1;
             betting.current_bet = 0;
+            betting.reset_round_raises();
             
             // Reset player betting amounts for new round
             reset_round_betting(&mut duel);
 
             if duel.current_round >= duel.max_rounds {
-                duel.game_state = GameState::ResolutionPending;
-                duel.resolution_pending = true;
+                require!(duel.apply_transition(DuelTrigger::FinalRound), GameError::InvalidGameState);
+                duel.request_resolution(current_time);
             } else {
-                duel.game_state = GameState::AwaitingAction;
+                require!(duel.apply_transition(DuelTrigger::OpenRound), GameError::InvalidGameState);
+                post_blinds(&ctx, &mut duel, &mut betting, current_time)?;
             }
 
             emit!(RoundAdvancedEvent {
@@ -180,6 +230,60 @@ pub mod round_progression {
         Ok(())
     }
 
+    /// Post the small/big blind and ante for both seats at round start
+    fn post_blinds(
+        ctx: &Context<RoundProgression>,
+        duel: &mut DuelComponent,
+        betting: &mut BettingComponent,
+        current_time: i64,
+    ) -> Result<()> {
+        if betting.blind_levels.is_empty() {
+            return Ok(());
+        }
+
+        let level = betting.level_for(duel.current_round, current_time - duel.start_time);
+        if level != betting.blind_level {
+            betting.blind_level = level;
+            emit!(BlindLevelRaisedEvent {
+                duel_id: duel.duel_id,
+                level,
+                small_blind: betting.blind_levels[level as usize].small_blind,
+                big_blind: betting.blind_levels[level as usize].big_blind,
+                ante: betting.blind_levels[level as usize].ante,
+            });
+        }
+
+        let mut player_one = ctx.accounts.player_one.load_mut()?;
+        let mut player_two = ctx.accounts.player_two.load_mut()?;
+        let mut replay_chunk = ctx.accounts.replay_chunk.load_mut()?;
+
+        for (seat, player) in [&mut *player_one, &mut *player_two].into_iter().enumerate() {
+            if !player.is_active {
+                continue;
+            }
+            // Short stacks post what they have left
+            let posted = betting.forced_bet(player.position).min(player.chip_count);
+            if posted == 0 {
+                continue;
+            }
+
+            player.chip_count -= posted;
+            player.total_bet += posted;
+            betting.current_bet = betting.current_bet.max(player.total_bet);
+            betting.add_to_pot(posted);
+
+            record_replay_entry(duel, &mut replay_chunk, ReplayEntry {
+                seat: seat as u8,
+                action_type: ActionType::PostBlind,
+                bet_amount: posted,
+                round_number: duel.current_round,
+                timestamp: current_time,
+            })?;
+        }
+
+        Ok(())
+    }
+
     fn should_advance_round(duel: &DuelComponent, current_time: i64) -> bool {
         // Logic to determine if round should advance
         duel.current_round < duel.max_rounds
@@ -195,27 +299,95 @@ pub mod round_progression {
 pub mod vrf_resolution {
     pub fn execute(ctx: Context<VrfResolution>, vrf_proof: [u8; 64]) -> Result<()> {
         let mut duel = ctx.accounts.duel.load_mut()?;
-        let mut betting = ctx.accounts.betting.load_mut()?;
+        let betting = ctx.accounts.betting.load()?;
 
         require!(duel.game_state == GameState::ResolutionPending, GameError::InvalidGameState);
         require!(duel.resolution_pending, GameError::NoResolutionPending);
 
-        // Verify VRF proof
-        let vrf_result = verify_vrf_proof(&duel.vrf_seed, &vrf_proof)?;
-        
-        // Determine winner based on VRF result and game logic
-        let winner = determine_winner(vrf_result, &duel)?;
+        // Oracle-backed duels resolve only through their request, answered by
+        // the provider it is assigned to; expire_vrf_request reclaims its rent
+        if duel.randomness_provider != RandomnessProvider::CommitReveal {
+            let request = ctx.accounts.vrf_request
+                .as_ref()
+                .ok_or(crate::instructions::VrfRequestError::RequestNotFound)?;
+            require!(
+                request.load()?.provider == ctx.accounts.vrf_authority.key(),
+                crate::instructions::VrfRequestError::ProviderMismatch
+            );
+        }
+
+        let oracle = ctx.accounts.vrf_authority.key();
+        ctx.accounts.randomness_registry.load()?.check_oracle(duel.randomness_provider, &oracle)?;
+
+        // Randomness is only accepted once every commitment is opened or its
+        // reveal window has closed
+        let reveal_status = crate::instructions::reveal_status(&duel, Clock::get()?.unix_timestamp);
+        require!(
+            reveal_status != crate::instructions::RevealStatus::Outstanding,
+            crate::instructions::EntropyError::RevealsOutstanding
+        );
+
+        // Verify the duel's provider proof, then mix in revealed player entropy
+        // so neither the oracle nor the rollup alone controls the outcome
+        let randomness = match duel.randomness_provider {
+            RandomnessProvider::MagicBlockVrf => {
+                let vrf_result = verify_vrf_proof(&duel.vrf_seed, &vrf_proof)?;
+                crate::instructions::mix_entropy(vrf_result, &duel)
+            }
+            RandomnessProvider::Switchboard => {
+                let randomness_account = ctx.accounts.switchboard_randomness
+                    .as_ref()
+                    .ok_or(crate::instructions::RandomnessError::InvalidSwitchboardAccount)?;
+                let vrf_result = crate::instructions::switchboard_randomness(
+                    &duel.vrf_seed,
+                    &vrf_proof,
+                    randomness_account,
+                    &oracle,
+                )?;
+                crate::instructions::mix_entropy(vrf_result, &duel)
+            }
+            RandomnessProvider::CommitReveal => crate::instructions::commit_reveal_randomness(&duel)?,
+        };
         
-        duel.winner = Some(winner);
-        duel.game_state = GameState::Completed;
-        duel.resolution_pending = false;
-        betting.is_settled = true;
+        // Weighted odds must be the ones committed before the randomness existed
+        if duel.outcome_evaluator == OutcomeEvaluator::Weighted {
+            require!(
+                duel.weights_hash == weights_commitment(&duel, performance_weights(&duel)),
+                crate::instructions::VrfError::InvalidWeightsHash
+            );
+            duel.weights_validated = true;
+        }
+
+        // Determine winner based on VRF result and game logic; an exact tie
+        // leaves no winner and settles as a split pot
+        let winner = match reveal_status {
+            crate::instructions::RevealStatus::Forfeit { winner_seat } => {
+                let winner = if winner_seat == 0 { duel.player_one } else { duel.player_two };
+                emit!(crate::instructions::EntropyForfeitedEvent {
+                    duel_id: duel.duel_id,
+                    forfeited_by: if winner_seat == 0 { duel.player_two } else { duel.player_one },
+                    winner,
+                });
+                Some(winner)
+            }
+            _ => determine_winner(randomness, &duel),
+        };
+        // Settlement pays the result out, so the betting stays unsettled here
+        require!(duel.record_resolution(winner), GameError::InvalidGameState);
+        let winner = winner.unwrap_or_default();
+        duel.transcript_hash = crate::instructions::compute_transcript_hash(&duel, &vrf_proof, randomness, &winner);
+        duel.jackpot_roll = crate::instructions::jackpot_roll(randomness, &duel.vrf_seed);
 
         emit!(GameResolvedEvent {
             duel_id: duel.duel_id,
             winner,
             pot_size: betting.total_pot,
-            randomness: vrf_result,
+            randomness,
+            vrf_seed: duel.vrf_seed,
+            vrf_proof,
+            replay_head_hash: duel.replay_head_hash,
+            final_chip_counts: duel.replay_state.chip_counts,
+            transcript_hash: duel.transcript_hash,
         });
 
         Ok(())
@@ -229,13 +401,12 @@ pub mod vrf_resolution {
         Ok(hasher.finish())
     }
 
-    fn determine_winner(randomness: u64, duel: &DuelComponent) -> Result<Pubkey> {
-        // Winner determination logic based on randomness and game state
-        if randomness % 2 == 0 {
-            Ok(duel.player_one)
-        } else {
-            Ok(duel.player_two)
-        }
+    fn determine_winner(randomness: u64, duel: &DuelComponent) -> Option<Pubkey> {
+        // Winner determination is delegated to the duel's configured rule set
+        duel.outcome_evaluator.evaluate_outcome(randomness, duel).map(|seat| match seat {
+            0 => duel.player_one,
+            _ => duel.player_two,
+        })
     }
 }
 
@@ -305,58 +476,562 @@ pub mod psychological_analysis {
 #[system]
 pub mod settlement {
     pub fn execute(ctx: Context<Settlement>) -> Result<()> {
+        let meter = shared::CuMeter::start();
+        // Optionally reject settlement wrapped by unknown programs
+        ctx.accounts.admin_config.check_cpi_origin(&ctx.accounts.instructions, &crate::ID)?;
+
         let mut duel = ctx.accounts.duel.load_mut()?;
         let mut betting = ctx.accounts.betting.load_mut()?;
         let mut winner_player = ctx.accounts.winner_player.load_mut()?;
         let mut loser_player = ctx.accounts.loser_player.load_mut()?;
 
         require!(duel.game_state == GameState::Completed, GameError::InvalidGameState);
-        require!(duel.winner.is_some(), GameError::NoWinnerDetermined);
+        require!(duel.winner.is_some() || duel.is_draw, GameError::NoWinnerDetermined);
         require!(!betting.is_settled, GameError::AlreadySettled);
+        require!(betting.lock(), GameError::ReentrancyDetected);
+        let ratings_before = [winner_player.skill_rating, loser_player.skill_rating];
+        // The queue decides how the result is rated; recorded on the duel for indexers
+        duel.rating_system = ctx.accounts.queue_ratings.load()?.system_for(duel.queue_type);
+
+        // Calculate rake - practice duels are stake-free
+        let rake = if duel.is_practice {
+            0
+        } else {
+            ctx.accounts.rake_schedule.load()?.rake_for(betting.total_pot, duel.current_round)
+        };
+        // Micro-rake into the progressive jackpot, on top of the house rake
+        let jackpot_cut = if duel.is_practice {
+            0
+        } else {
+            ctx.accounts.jackpot_vault.load()?.contribution_for(betting.total_pot - rake)
+        };
+        let distributable = betting.total_pot - rake - jackpot_cut;
+
+        // Exact ties split the pot; the context seats player one as "winner"
+        if duel.is_draw {
+            let shares = DuelComponent::draw_split(distributable, winner_player.position);
+            for (player, share) in [(&mut *winner_player, shares[0]), (&mut *loser_player, shares[1])] {
+                player.chip_count += share;
+                player.games_played += 1;
+            }
+            // Events leave ratings out until placement is done (the accounts still hold them)
+            let mut public_ratings = [None; 2];
+            if !duel.is_practice {
+                match duel.rating_system {
+                    RatingSystem::Elo => update_draw_ratings(&mut winner_player, &mut loser_player),
+                    RatingSystem::Glicko2 => update_glicko_ratings(&ctx, &duel, shared::GLICKO_DRAW)?,
+                }
+                let first_psych = ctx.accounts.winner_psych.as_ref().ok_or(GameError::MissingPsychProfile)?;
+                let second_psych = ctx.accounts.loser_psych.as_ref().ok_or(GameError::MissingPsychProfile)?;
+                for (seat, (profile, rating)) in [(first_psych, winner_player.skill_rating), (second_psych, loser_player.skill_rating)].into_iter().enumerate() {
+                    let mut psych = profile.load_mut()?;
+                    let track = psych.track_mut(duel.queue_type);
+                    track.record_rated_delta(rating as i64 - ratings_before[seat] as i64, Clock::get()?.unix_timestamp);
+                    public_ratings[seat] = track.public_rating();
+                    psych.duels_completed += 1;
+                    let reputation = psych.refresh_reputation();
+                    emit!(ReputationUpdatedEvent {
+                        player: psych.player,
+                        duel_id: duel.duel_id,
+                        reputation,
+                        duels_completed: psych.duels_completed,
+                        collusion_flags: psych.collusion_flags,
+                    });
+                }
+            }
+
+            betting.is_settled = true;
+            betting.rake_amount = rake;
+            betting.unlock();
+
+            // Each seat carries the share of the rake matching its share of the pot
+            let settled_at = Clock::get()?.unix_timestamp;
+            let rake_shares = DuelComponent::draw_split(rake, winner_player.position);
+            let bets = [winner_player.total_bet, loser_player.total_bet];
+            let escrow_rake = pay_escrow(&ctx, &mut duel, betting.total_pot, rake, shares, bets)?;
+            accrue_loyalty(&ctx, &winner_player, &loser_player, escrow_rake, settled_at)?;
+            record_settled_history(&ctx, &duel, [&*winner_player, &*loser_player], ratings_before, [MatchResult::Draw; 2], shares, betting.total_pot, settled_at)?;
+            settle_jackpot(&ctx, &duel, jackpot_cut, &mut winner_player, &mut loser_player)?;
+            for (player, counterparty, payout, rake) in [
+                (&winner_player, loser_player.player_id, shares[0], rake_shares[0]),
+                (&loser_player, winner_player.player_id, shares[1], rake_shares[1]),
+            ] {
+                emit!(SettlementReceiptEvent {
+                    version: SETTLEMENT_RECEIPT_VERSION,
+                    duel_id: duel.duel_id,
+                    player: player.player_id,
+                    counterparty,
+                    stake_in: player.total_bet,
+                    payout,
+                    rake,
+                    net_pnl: net_pnl(player.total_bet, payout),
+                    is_practice: duel.is_practice,
+                    started_at: duel.start_time,
+                    settled_at,
+                });
+            }
+
+            ctx.accounts.winner_attestation.set_inner(shared::WinnerAttestation::new(
+                crate::ID,
+                ctx.accounts.duel.key(),
+                duel.duel_id,
+                None,
+                ctx.accounts.authority.key(),
+                settled_at,
+                ctx.bumps.winner_attestation,
+            ));
+
+            emit!(DrawSettledEvent {
+                duel_id: duel.duel_id,
+                players: [winner_player.player_id, loser_player.player_id],
+                shares,
+                rake,
+                new_ratings: public_ratings,
+            });
+
+            shared::record_metric(
+                &mut ctx.accounts.metrics_buffer,
+                &meter,
+                crate::instructions::METRIC_SETTLEMENT,
+                crate::instructions::METRIC_BRANCH_DRAW,
+                2,
+                Clock::get()?.slot,
+            );
+            return Ok(());
+        }
 
         let winner = duel.winner.unwrap();
-        
-        // Calculate rake
-        let rake = betting.calculate_rake(250); // 2.5% rake
-        let payout = betting.total_pot - rake;
+
+        let winner_seat = duel.seat_of(&winner).ok_or(GameError::NoWinnerDetermined)?;
+        // Handicapped duels hand part of a favorite's winnings back to the underdog,
+        // and early forfeits part of the conceding player's blinds
+        let (payout, loser_refund) = duel.settlement_split(winner_seat, distributable);
 
         // Distribute winnings
         if winner == winner_player.player_id {
             winner_player.chip_count += payout;
             winner_player.games_won += 1;
             winner_player.total_winnings += payout;
+            loser_player.chip_count += loser_refund;
         }
 
         // Update both players' game counts
         winner_player.games_played += 1;
         loser_player.games_played += 1;
 
-        // Update skill ratings using ELO-like system
-        update_skill_ratings(&mut winner_player, &mut loser_player, true);
+        // Update skill ratings using ELO-like system; bots don't affect ratings
+        if !duel.is_practice {
+            match duel.rating_system {
+                RatingSystem::Elo => update_skill_ratings(&mut winner_player, &mut loser_player, true),
+                RatingSystem::Glicko2 => update_glicko_ratings(&ctx, &duel, shared::GLICKO_WIN)?,
+            }
+        }
+
+        // Track net value moving between this pair; excess is flagged for review, not blocked
+        let mut collusion_flagged = false;
+        if !duel.is_practice {
+            let default_config = TransferLimitConfig::default();
+            let loaded_config = ctx.accounts.transfer_limit_config.as_ref().map(|account| account.load()).transpose()?;
+            let config: &TransferLimitConfig = match &loaded_config {
+                Some(config) => config,
+                None => &default_config,
+            };
+            let mut limit = ctx.accounts.transfer_limit.load_mut()?;
+            if limit.first == Pubkey::default() {
+                let (first, second) = ordered_pair(&duel);
+                limit.first = first;
+                limit.second = second;
+            }
+
+            let current_time = Clock::get()?.unix_timestamp;
+            // Value is the escrowed lamports the winner takes beyond their own stake
+            let (_, escrow_shares) = duel.escrow_split(betting.total_pot, rake, [payout, loser_refund]);
+            let transferred = escrow_shares[0].saturating_sub(duel.escrowed_stake);
+            if limit.record(config, &loser_player.player_id, transferred, current_time) {
+                collusion_flagged = true;
+                emit!(ChipDumpingFlaggedEvent {
+                    duel_id: duel.duel_id,
+                    from: loser_player.player_id,
+                    to: winner,
+                    amount: transferred,
+                    net_flow: limit.net_flow,
+                    duels_in_window: limit.duels_in_window,
+                    window_start: limit.window_start,
+                });
+            }
+        }
+
+        // Completing the duel and any collusion flag feed both players' reputation;
+        // events leave ratings out until placement is done
+        let mut winner_public_rating = None;
+        if !duel.is_practice {
+            let winner_psych = ctx.accounts.winner_psych.as_ref().ok_or(GameError::MissingPsychProfile)?;
+            let loser_psych = ctx.accounts.loser_psych.as_ref().ok_or(GameError::MissingPsychProfile)?;
+            for (profile, delta, won) in [
+                (winner_psych, winner_player.skill_rating as i64 - ratings_before[0] as i64, true),
+                (loser_psych, loser_player.skill_rating as i64 - ratings_before[1] as i64, false),
+            ] {
+                let mut psych = profile.load_mut()?;
+                let track = psych.track_mut(duel.queue_type);
+                track.record_rated_delta(delta, Clock::get()?.unix_timestamp);
+                track.wins += won as u32;
+                psych.duels_completed += 1;
+                if collusion_flagged {
+                    psych.collusion_flags += 1;
+                }
+                let reputation = psych.refresh_reputation();
+                emit!(ReputationUpdatedEvent {
+                    player: psych.player,
+                    duel_id: duel.duel_id,
+                    reputation,
+                    duels_completed: psych.duels_completed,
+                    collusion_flags: psych.collusion_flags,
+                });
+            }
+            winner_public_rating = winner_psych.load()?.track(duel.queue_type).public_rating();
+        }
 
         // Mark as settled
         betting.is_settled = true;
         betting.rake_amount = rake;
+        betting.unlock();
+
+        // One receipt per seat; the rake comes out of the winner's payout
+        let settled_at = Clock::get()?.unix_timestamp;
+        let real_stake = duel.escrowed_stake > 0;
+        let bets = [winner_player.total_bet, loser_player.total_bet];
+        let escrow_rake = pay_escrow(&ctx, &mut duel, betting.total_pot, rake, [payout, loser_refund], bets)?;
+        accrue_loyalty(&ctx, &winner_player, &loser_player, escrow_rake, settled_at)?;
+        record_settled_history(
+            &ctx,
+            &duel,
+            [&*winner_player, &*loser_player],
+            ratings_before,
+            [MatchResult::Win, MatchResult::Loss],
+            [payout, loser_refund],
+            betting.total_pot,
+            settled_at,
+        )?;
+        settle_jackpot(&ctx, &duel, jackpot_cut, &mut winner_player, &mut loser_player)?;
+
+        // Largest pot won today, for the daily high-hand prize; chip-only pots
+        // cost nothing to inflate, so only escrowed duels count
+        if real_stake && !duel.is_practice {
+            let mut contest = ctx.accounts.daily_contest.load_mut()?;
+            if contest.record(settled_at, betting.total_pot, winner, duel.duel_id) {
+                emit!(DailyRecordSetEvent {
+                    day: contest.day,
+                    holder: winner,
+                    duel_id: duel.duel_id,
+                    pot: betting.total_pot,
+                });
+            }
+        }
+        for (player, counterparty, payout, rake) in [
+            (&winner_player, loser_player.player_id, payout, rake),
+            (&loser_player, winner_player.player_id, loser_refund, 0),
+        ] {
+            emit!(SettlementReceiptEvent {
+                version: SETTLEMENT_RECEIPT_VERSION,
+                duel_id: duel.duel_id,
+                player: player.player_id,
+                counterparty,
+                stake_in: player.total_bet,
+                payout,
+                rake,
+                net_pnl: net_pnl(player.total_bet, payout),
+                is_practice: duel.is_practice,
+                started_at: duel.start_time,
+                settled_at,
+            });
+        }
+
+        ctx.accounts.winner_attestation.set_inner(shared::WinnerAttestation::new(
+            crate::ID,
+            ctx.accounts.duel.key(),
+            duel.duel_id,
+            Some(winner),
+            ctx.accounts.authority.key(),
+            settled_at,
+            ctx.bumps.winner_attestation,
+        ));
 
         emit!(GameSettledEvent {
             duel_id: duel.duel_id,
             winner,
             payout,
             rake,
-            winner_new_rating: winner_player.skill_rating,
+            winner_new_rating: winner_public_rating,
+            transcript_hash: duel.transcript_hash,
         });
 
+        shared::record_metric(
+            &mut ctx.accounts.metrics_buffer,
+            &meter,
+            crate::instructions::METRIC_SETTLEMENT,
+            crate::instructions::METRIC_BRANCH_DECISIVE,
+            2,
+            Clock::get()?.slot,
+        );
+        Ok(())
+    }
+
+    /// Pay out any lamports escrowed on the duel in line with the chip
+    /// settlement: the rake's share to the treasury less what the loyalty pool
+    /// and the seats' guilds take of it, the rest to the seats in winner, loser
+    /// order. Returns the lamport rake.
+    fn pay_escrow(
+        ctx: &Context<Settlement>,
+        duel: &mut DuelComponent,
+        total_pot: u64,
+        rake: u64,
+        payouts: [u64; 2],
+        bets: [u64; 2],
+    ) -> Result<u64> {
+        let (escrow_rake, shares) = duel.escrow_split(total_pot, rake, payouts);
+        if duel.escrowed_stake == 0 {
+            return Ok(0);
+        }
+        duel.escrowed_stake = 0;
+
+        // Fund the rake-back this rake can earn, so claims are only ever paid from real rake
+        let pool_share = {
+            let mut program = ctx.accounts.loyalty_program.load_mut()?;
+            let pool_share = program.max_rakeback_for(escrow_rake);
+            program.total_funded += pool_share;
+            pool_share
+        };
+        let guild_rake = pay_guild_rake(ctx, escrow_rake - pool_share, bets)?;
+
+        // The duel is owned by this program, so lamports can be moved directly
+        let duel_info = ctx.accounts.duel.to_account_info();
+        **duel_info.try_borrow_mut_lamports()? -= escrow_rake - guild_rake + shares[0] + shares[1];
+        **ctx.accounts.loyalty_program.to_account_info().try_borrow_mut_lamports()? += pool_share;
+        **ctx.accounts.treasury.try_borrow_mut_lamports()? += escrow_rake - pool_share - guild_rake;
+        **ctx.accounts.loser_wallet.try_borrow_mut_lamports()? += shares[1];
+
+        // A winner who keeps a bankroll is paid into it, so its withdrawal policy covers the winnings
+        let bankroll_info = ctx.accounts.winner_bankroll.to_account_info();
+        if bankroll_info.data_is_empty() {
+            **ctx.accounts.winner_wallet.try_borrow_mut_lamports()? += shares[0];
+        } else {
+            **bankroll_info.try_borrow_mut_lamports()? += shares[0];
+            let bankroll_account: Account<ComponentData<Bankroll>> = Account::try_from(&bankroll_info)?;
+            {
+                let mut bankroll = bankroll_account.load_mut()?;
+                bankroll.total_deposited += shares[0];
+                // Large wins lock withdrawals for the policy cooldown; the
+                // threshold is in lamports, so it is checked against the share
+                if !duel.is_draw && bankroll.record_win(shares[0], Clock::get()?.unix_timestamp) {
+                    emit!(LargeWinCooldownEvent {
+                        owner: bankroll.owner,
+                        duel_id: duel.duel_id,
+                        amount: shares[0],
+                        cooldown_until: bankroll.cooldown_until,
+                    });
+                }
+            }
+            bankroll_account.exit(&crate::ID)?;
+        }
+
+        emit!(EscrowSettledEvent {
+            duel_id: duel.duel_id,
+            players: [ctx.accounts.winner_wallet.key(), ctx.accounts.loser_wallet.key()],
+            payouts: shares,
+            rake: escrow_rake,
+        });
+
+        Ok(escrow_rake)
+    }
+
+    /// Route the guild's slice of each seat's share of `rake` into that seat's
+    /// guild treasury. The slice is moved from the duel to the guild rake
+    /// authority, which signs it into the treasury through the guild program.
+    /// Seats outside a guild, or settlements without the guild accounts, route
+    /// nothing. Returns the lamports routed.
+    fn pay_guild_rake(ctx: &Context<Settlement>, rake: u64, bets: [u64; 2]) -> Result<u64> {
+        let (config, program) = match (&ctx.accounts.guild_config, &ctx.accounts.guild_program) {
+            (Some(config), Some(program)) => (config, program),
+            _ => return Ok(0),
+        };
+        let seat_rakes = crate::instructions::split_rake(rake, bets);
+        let authority = ctx.accounts.guild_rake_authority.to_account_info();
+        let mut routed = 0;
+        for (guild, member, seat_rake) in [
+            (&ctx.accounts.winner_guild, &ctx.accounts.winner_guild_member, seat_rakes[0]),
+            (&ctx.accounts.loser_guild, &ctx.accounts.loser_guild_member, seat_rakes[1]),
+        ] {
+            let (guild, member) = match (guild, member) {
+                (Some(guild), Some(member)) => (guild, member),
+                _ => continue,
+            };
+            let share = config.rake_share(seat_rake);
+            if share == 0 {
+                continue;
+            }
+
+            **ctx.accounts.duel.to_account_info().try_borrow_mut_lamports()? -= share;
+            **authority.try_borrow_mut_lamports()? += share;
+            sol_duel_guild::cpi::deposit_rake(
+                CpiContext::new_with_signer(
+                    program.to_account_info(),
+                    sol_duel_guild::cpi::accounts::DepositRake {
+                        guild_config: config.to_account_info(),
+                        guild: guild.to_account_info(),
+                        guild_member: member.to_account_info(),
+                        rake_authority: authority.clone(),
+                        system_program: ctx.accounts.system_program.to_account_info(),
+                    },
+                    &[&[shared::GUILD_RAKE_AUTHORITY_SEED, &[ctx.bumps.guild_rake_authority]]],
+                ),
+                seat_rake,
+            )?;
+            routed += share;
+        }
+        Ok(routed)
+    }
+
+    /// Credit the lamport rake to both seats' loyalty accounts for the current
+    /// epoch, split by what each put into the pot. Chip-only rake earns nothing.
+    fn accrue_loyalty(
+        ctx: &Context<Settlement>,
+        first: &PlayerComponent,
+        second: &PlayerComponent,
+        escrow_rake: u64,
+        now: i64,
+    ) -> Result<()> {
+        let epoch = ctx.accounts.loyalty_program.load()?.current_epoch(now);
+        let rake_shares = crate::instructions::split_rake(escrow_rake, [first.total_bet, second.total_bet]);
+        for (account, player, rake) in [
+            (&ctx.accounts.winner_loyalty, first, rake_shares[0]),
+            (&ctx.accounts.loser_loyalty, second, rake_shares[1]),
+        ] {
+            let mut loyalty = account.load_mut()?;
+            loyalty.player = player.player_id;
+            if rake > 0 {
+                loyalty.record_rake(rake, epoch);
+            }
+        }
+        Ok(())
+    }
+
+    /// Record the settled duel in both players' history, seat order as settled
+    fn record_settled_history(
+        ctx: &Context<Settlement>,
+        duel: &DuelComponent,
+        players: [&PlayerComponent; 2],
+        ratings_before: [u32; 2],
+        results: [MatchResult; 2],
+        payouts: [u64; 2],
+        pot: u64,
+        now: i64,
+    ) -> Result<()> {
+        if duel.is_practice {
+            return Ok(());
+        }
+        let summaries = seat_summaries(
+            duel.duel_id,
+            [players[0].player_id, players[1].player_id],
+            [players[0].total_bet, players[1].total_bet],
+            results,
+            payouts,
+            [0, 1].map(|seat| players[seat].skill_rating as i32 - ratings_before[seat] as i32),
+            now,
+        );
+        record_history(
+            &ctx.accounts.head_to_head,
+            [&ctx.accounts.winner_recent_matches, &ctx.accounts.loser_recent_matches],
+            duel.winner,
+            summaries,
+            pot,
+        )
+    }
+
+    /// Add this pot's contribution to the jackpot, then roll for a hit. The
+    /// first player is the winner's seat, or player one on a draw.
+    fn settle_jackpot(
+        ctx: &Context<Settlement>,
+        duel: &DuelComponent,
+        contribution: u64,
+        first: &mut PlayerComponent,
+        second: &mut PlayerComponent,
+    ) -> Result<()> {
+        if duel.is_practice {
+            return Ok(());
+        }
+        let mut vault = ctx.accounts.jackpot_vault.load_mut()?;
+        vault.balance += contribution;
+        vault.total_contributed += contribution;
+
+        if vault.balance == 0 || !vault.is_hit(duel.duel_id, &duel.jackpot_roll) {
+            return Ok(());
+        }
+
+        let amount = vault.balance;
+        let shares = vault.payout_split(amount, duel.is_draw);
+        first.chip_count += shares[0];
+        second.chip_count += shares[1];
+        vault.balance = 0;
+        vault.total_paid += amount;
+        vault.hits += 1;
+        vault.last_hit_duel = duel.duel_id;
+
+        emit!(JackpotHitEvent {
+            duel_id: duel.duel_id,
+            players: [first.player_id, second.player_id],
+            shares,
+            amount,
+            hit_odds: vault.hit_odds,
+        });
+
+        Ok(())
+    }
+
+    fn net_pnl(stake_in: u64, payout: u64) -> i64 {
+        (payout as i128 - stake_in as i128).clamp(i64::MIN as i128, i64::MAX as i128) as i64
+    }
+
+    /// ELO for a draw: each side scores half a point against its expectation,
+    /// so the lower-rated player gains what the higher-rated one loses
+    fn update_draw_ratings(first: &mut PlayerComponent, second: &mut PlayerComponent) {
+        let expected_first = 1.0 / (1.0 + 10.0_f64.powf((second.skill_rating as f64 - first.skill_rating as f64) / 400.0));
+        let delta = 0.5 - expected_first;
+        first.skill_rating = (first.skill_rating as f64 + k_factor(first.rating_deviation) * delta).round().max(0.0) as u32;
+        second.skill_rating = (second.skill_rating as f64 - k_factor(second.rating_deviation) * delta).round().max(0.0) as u32;
+    }
+
+    /// Glicko-2 update for both seats from their pre-game ratings, aged for the
+    /// time since each last played. `first_score` is the winner seat's result
+    /// (player one on a draw); the ELO track is untouched.
+    fn update_glicko_ratings(ctx: &Context<Settlement>, duel: &DuelComponent, first_score: i64) -> Result<()> {
+        let first_psych = ctx.accounts.winner_psych.as_ref().ok_or(GameError::MissingPsychProfile)?;
+        let second_psych = ctx.accounts.loser_psych.as_ref().ok_or(GameError::MissingPsychProfile)?;
+        let mut first = first_psych.load_mut()?;
+        let mut second = second_psych.load_mut()?;
+
+        // The caller moves last_rated_at to now once the result is recorded
+        let now = Clock::get()?.unix_timestamp;
+        let queue = duel.queue_type;
+        let (first_before, second_before) = (first.track(queue).glicko_at(now), second.track(queue).glicko_at(now));
+        first.track_mut(queue).glicko = shared::glicko2_update(first_before, second_before, first_score);
+        second.track_mut(queue).glicko = shared::glicko2_update(second_before, first_before, shared::GLICKO_WIN - first_score);
+
+        emit!(GlickoRatingsUpdatedEvent {
+            duel_id: duel.duel_id,
+            players: [first.player, second.player],
+            ratings: [first.track(queue).glicko, second.track(queue).glicko],
+        });
         Ok(())
     }
 
     fn update_skill_ratings(winner: &mut PlayerComponent, loser: &mut PlayerComponent, winner_won: bool) {
-        let k_factor = 32; // ELO K-factor
+        // Each side moves by its own K-factor, so placement games barely shift a veteran
         let expected_winner = 1.0 / (1.0 + 10.0_f64.powf((loser.skill_rating as f64 - winner.skill_rating as f64) / 400.0));
         let expected_loser = 1.0 - expected_winner;
 
         if winner_won {
-            winner.skill_rating = (winner.skill_rating as f64 + k_factor as f64 * (1.0 - expected_winner)) as u32;
-            loser.skill_rating = (loser.skill_rating as f64 + k_factor as f64 * (0.0 - expected_loser)) as u32;
+            winner.skill_rating = (winner.skill_rating as f64 + k_factor(winner.rating_deviation) * (1.0 - expected_winner)) as u32;
+            loser.skill_rating = (loser.skill_rating as f64 + k_factor(loser.rating_deviation) * (0.0 - expected_loser)).max(0.0) as u32;
         }
     }
 }
@@ -371,6 +1046,31 @@ pub struct ActionProcessedEvent {
     pub pot_total: u64,
 }
 
+#[event]
+pub struct DrawSettledEvent {
+    pub duel_id: u64,
+    pub players: [Pubkey; 2], // Seat order
+    pub shares: [u64; 2],
+    pub rake: u64,
+    pub new_ratings: [Option<u32>; 2], // None while in placement
+}
+
+#[event]
+pub struct GlickoRatingsUpdatedEvent {
+    pub duel_id: u64,
+    pub players: [Pubkey; 2], // Winner seat first; player one on a draw
+    pub ratings: [shared::Glicko2Rating; 2],
+}
+
+#[event]
+pub struct ReputationUpdatedEvent {
+    pub player: Pubkey,
+    pub duel_id: u64,
+    pub reputation: u16,
+    pub duels_completed: u32,
+    pub collusion_flags: u32,
+}
+
 #[event]
 pub struct RoundAdvancedEvent {
     pub duel_id: u64,
@@ -378,12 +1078,26 @@ pub struct RoundAdvancedEvent {
     pub pot_size: u64,
 }
 
+#[event]
+pub struct BlindLevelRaisedEvent {
+    pub duel_id: u64,
+    pub level: u8,
+    pub small_blind: u64,
+    pub big_blind: u64,
+    pub ante: u64,
+}
+
 #[event]
 pub struct GameResolvedEvent {
     pub duel_id: u64,
     pub winner: Pubkey,
     pub pot_size: u64,
     pub randomness: u64,
+    pub vrf_seed: [u8; 32],
+    pub vrf_proof: [u8; 64],
+    pub replay_head_hash: [u8; 32],
+    pub final_chip_counts: [u64; 2],
+    pub transcript_hash: [u8; 32],
 }
 
 #[event]
@@ -400,7 +1114,37 @@ pub struct GameSettledEvent {
     pub winner: Pubkey,
     pub payout: u64,
     pub rake: u64,
-    pub winner_new_rating: u32,
+    pub winner_new_rating: Option<u32>, // None while in placement
+    pub transcript_hash: [u8; 32],
+}
+
+/// Bumped whenever a field is added to or reordered in `SettlementReceiptEvent`
+pub const SETTLEMENT_RECEIPT_VERSION: u8 = 1;
+
+/// Escrowed lamports paid out at settlement, in winner, loser order
+#[event]
+pub struct EscrowSettledEvent {
+    pub duel_id: u64,
+    pub players: [Pubkey; 2],
+    pub payouts: [u64; 2],
+    pub rake: u64,
+}
+
+/// Per-player settlement record in a fixed layout, so indexers and tax exports
+/// can build P&L without reconstructing it from pot and action history
+#[event]
+pub struct SettlementReceiptEvent {
+    pub version: u8,
+    pub duel_id: u64,
+    pub player: Pubkey,
+    pub counterparty: Pubkey,
+    pub stake_in: u64,
+    pub payout: u64,
+    pub rake: u64,
+    pub net_pnl: i64,
+    pub is_practice: bool,
+    pub started_at: i64,
+    pub settled_at: i64,
 }
 
 /// Game errors
@@ -428,4 +1172,6 @@ pub enum GameError {
     NoWinnerDetermined,
     #[msg("Game already settled")]
     AlreadySettled,
+    #[msg("Settlement already in progress")]
+    ReentrancyDetected,
 }
\ No newline at end of file
@@ -0,0 +1,190 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use crate::components::*;
+
+/// Hit points each player starts with in a damage race
+pub const DAMAGE_RACE_HIT_POINTS: u16 = 100;
+/// Exchanges played before a damage race falls back to remaining hit points
+pub const DAMAGE_RACE_MAX_EXCHANGES: u8 = 20;
+
+const DECK_SIZE: u64 = 52;
+
+/// Weighted resolution splits 10,000 bps: a base share every seat keeps, then
+/// shares divided by chips committed and by rounds won
+pub const WEIGHT_BASE_BPS: u64 = 2_000;
+pub const WEIGHT_COMMITMENT_BPS: u64 = 4_000;
+pub const WEIGHT_ROUNDS_BPS: u64 = 4_000;
+const WEIGHT_TOTAL_BPS: u64 = WEIGHT_BASE_BPS + WEIGHT_COMMITMENT_BPS + WEIGHT_ROUNDS_BPS;
+
+impl OutcomeEvaluator {
+    /// Resolve the winning seat (0 = player one, 1 = player two) from the verified
+    /// randomness. Every variant is a pure function of its inputs so any observer can
+    /// recompute the result from the resolution event.
+    pub fn evaluate(&self, randomness: u64, duel: &DuelComponent) -> u8 {
+        match self {
+            OutcomeEvaluator::HighCard => high_card_winner(randomness, 0),
+            OutcomeEvaluator::BestOfRounds => best_of_rounds_winner(randomness, duel.max_rounds),
+            OutcomeEvaluator::DamageRace => damage_race_winner(randomness, &duel.replay_state.total_bets),
+            OutcomeEvaluator::Weighted => weighted_winner(randomness, duel),
+        }
+    }
+
+    /// Like `evaluate`, but reports an exact tie as None instead of playing a
+    /// decider: a drawn series, or a damage race that ends level
+    pub fn evaluate_outcome(&self, randomness: u64, duel: &DuelComponent) -> Option<u8> {
+        match self {
+            OutcomeEvaluator::BestOfRounds => best_of_rounds_outcome(randomness, duel.max_rounds),
+            OutcomeEvaluator::DamageRace => damage_race_outcome(randomness, &duel.replay_state.total_bets),
+            // Distinct cards and a weighted draw always separate the seats
+            OutcomeEvaluator::HighCard | OutcomeEvaluator::Weighted => Some(self.evaluate(randomness, duel)),
+        }
+    }
+
+    pub fn winner(&self, randomness: u64, duel: &DuelComponent) -> Pubkey {
+        match self.evaluate(randomness, duel) {
+            0 => duel.player_one,
+            _ => duel.player_two,
+        }
+    }
+}
+
+impl DuelComponent {
+    /// Award the closing round to the seat still in it when the opponent
+    /// folded or timed out; outbetting the opponent alone wins nothing, since
+    /// chips committed are already weighed on their own
+    pub fn close_round(&mut self) {
+        let active = self.replay_state.is_active;
+        if active[0] != active[1] {
+            self.round_wins[if active[0] { 0 } else { 1 }] += 1;
+        }
+        self.round_start_bets = self.replay_state.total_bets;
+    }
+
+    /// Freeze the weighted-resolution odds before the VRF is requested
+    pub fn commit_weights(&mut self) {
+        self.weights_bps = performance_weights(self);
+        self.weights_hash = weights_commitment(self, self.weights_bps);
+    }
+}
+
+/// Win probability per seat in basis points. A seat that folded or timed out
+/// against an active opponent has no chance; otherwise commitment and round
+/// wins move the odds away from even, never past 90/10.
+pub fn performance_weights(duel: &DuelComponent) -> [u16; 2] {
+    let active = duel.replay_state.is_active;
+    if active[0] != active[1] {
+        return if active[0] { [WEIGHT_TOTAL_BPS as u16, 0] } else { [0, WEIGHT_TOTAL_BPS as u16] };
+    }
+
+    let share = |values: [u64; 2], budget: u64| {
+        let total = values[0] as u128 + values[1] as u128;
+        if total == 0 {
+            budget / 2
+        } else {
+            (budget as u128 * values[0] as u128 / total) as u64
+        }
+    };
+    let first = WEIGHT_BASE_BPS / 2
+        + share(duel.replay_state.total_bets, WEIGHT_COMMITMENT_BPS)
+        + share([duel.round_wins[0] as u64, duel.round_wins[1] as u64], WEIGHT_ROUNDS_BPS);
+    [first as u16, (WEIGHT_TOTAL_BPS - first) as u16]
+}
+
+pub fn weights_commitment(duel: &DuelComponent, weights: [u16; 2]) -> [u8; 32] {
+    hashv(&[
+        b"weights",
+        &duel.duel_id.to_le_bytes(),
+        &duel.vrf_seed,
+        &weights[0].to_le_bytes(),
+        &weights[1].to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+/// Sample the performance weights committed on entering resolution with the
+/// verified randomness; play after the commitment cannot move them
+fn weighted_winner(randomness: u64, duel: &DuelComponent) -> u8 {
+    let weights = duel.weights_bps;
+    if draw(randomness, b"weighted", 0, 0, 0) % WEIGHT_TOTAL_BPS < weights[0] as u64 { 0 } else { 1 }
+}
+
+/// Draw a value in `0..modulus` for a seat from the randomness and a domain tag
+fn draw(randomness: u64, tag: &[u8], round: u8, seat: u8, nonce: u8) -> u64 {
+    let hash = hashv(&[&randomness.to_le_bytes(), tag, &[round, seat, nonce]]).to_bytes();
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&hash[0..8]);
+    u64::from_le_bytes(bytes)
+}
+
+/// Deal one card to each seat; the higher rank wins and suit breaks rank ties
+fn high_card_winner(randomness: u64, round: u8) -> u8 {
+    let first = draw(randomness, b"high_card", round, 0, 0) % DECK_SIZE;
+    let mut second = draw(randomness, b"high_card", round, 1, 0) % DECK_SIZE;
+    let mut nonce = 1;
+    // Both seats can't hold the same card
+    while second == first {
+        second = draw(randomness, b"high_card", round, 1, nonce) % DECK_SIZE;
+        nonce += 1;
+    }
+
+    let (first_rank, first_suit) = (first / 4, first % 4);
+    let (second_rank, second_suit) = (second / 4, second % 4);
+    if (first_rank, first_suit) > (second_rank, second_suit) { 0 } else { 1 }
+}
+
+/// Play one high-card hand per configured round; a drawn series goes to a decider hand
+fn best_of_rounds_winner(randomness: u64, rounds: u8) -> u8 {
+    best_of_rounds_outcome(randomness, rounds).unwrap_or_else(|| high_card_winner(randomness, rounds.max(1)))
+}
+
+/// Series result, or None when both seats won the same number of hands
+fn best_of_rounds_outcome(randomness: u64, rounds: u8) -> Option<u8> {
+    let rounds = rounds.max(1);
+    let mut wins = [0u8; 2];
+    for round in 0..rounds {
+        wins[high_card_winner(randomness, round) as usize] += 1;
+    }
+
+    match wins[0].cmp(&wins[1]) {
+        std::cmp::Ordering::Greater => Some(0),
+        std::cmp::Ordering::Less => Some(1),
+        std::cmp::Ordering::Equal => None,
+    }
+}
+
+/// Players trade hits until one runs out of hit points. Chips committed to the pot
+/// add up to ten bonus damage per hit, so betting pressure matters to the outcome.
+fn damage_race_winner(randomness: u64, total_bets: &[u64; 2]) -> u8 {
+    damage_race_outcome(randomness, total_bets)
+        .unwrap_or_else(|| high_card_winner(randomness, DAMAGE_RACE_MAX_EXCHANGES))
+}
+
+/// Damage race result, or None when both seats finish on the same hit points
+fn damage_race_outcome(randomness: u64, total_bets: &[u64; 2]) -> Option<u8> {
+    let committed = total_bets[0].saturating_add(total_bets[1]).max(1);
+    let bonus = [
+        (total_bets[0].saturating_mul(10) / committed) as u16,
+        (total_bets[1].saturating_mul(10) / committed) as u16,
+    ];
+    let mut hit_points = [DAMAGE_RACE_HIT_POINTS; 2];
+
+    for exchange in 0..DAMAGE_RACE_MAX_EXCHANGES {
+        // Alternate who strikes first so neither seat has a structural edge
+        let order = if exchange % 2 == 0 { [0u8, 1u8] } else { [1u8, 0u8] };
+        for attacker in order {
+            let defender = 1 - attacker as usize;
+            let damage = 10 + (draw(randomness, b"damage_race", exchange, attacker, 0) % 21) as u16
+                + bonus[attacker as usize];
+            hit_points[defender] = hit_points[defender].saturating_sub(damage);
+            if hit_points[defender] == 0 {
+                return Some(attacker);
+            }
+        }
+    }
+
+    match hit_points[0].cmp(&hit_points[1]) {
+        std::cmp::Ordering::Greater => Some(0),
+        std::cmp::Ordering::Less => Some(1),
+        std::cmp::Ordering::Equal => None,
+    }
+}
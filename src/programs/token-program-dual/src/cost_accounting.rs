@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use shared::{GameError, WinnerAttestation};
+
+use crate::ErTokenOperation;
+
+/// Flat ER fee charged per settled operation, in lamports
+pub const ER_FEE_PER_OPERATION: u64 = 5_000;
+
+/// Share of ER fees paid to the rollup operator, in basis points
+pub const ER_OPERATOR_SHARE_BPS: u64 = 2_000;
+
+/// Per-session cost ledger, opened with the ER allocation and closed out at settlement
+#[account]
+pub struct ErSessionCosts {
+    pub owner: Pubkey,
+    pub allocation_amount: u64,
+    pub max_cost_cap: Option<u64>,
+    pub operation_count: u32,
+    pub total_gas: u64,
+    pub fee_debited: u64,
+    pub operator_revenue: u64,
+    // Staked tokens backing this session beyond the allocation
+    pub collateral_pledged: u64,
+    // game-program match the pledge backs; its winner attestation settles the pledge
+    pub match_account: Pubkey,
+    pub opened_at: i64,
+    pub settled_at: Option<i64>,
+    pub bump: u8,
+}
+
+impl ErSessionCosts {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // owner
+        8 + // allocation_amount
+        1 + 8 + // max_cost_cap
+        4 + // operation_count
+        8 + // total_gas
+        8 + // fee_debited
+        8 + // operator_revenue
+        8 + // collateral_pledged
+        32 + // match_account
+        8 + // opened_at
+        1 + 8 + // settled_at
+        1; // bump
+
+    /// Fee for settling `operation_count` operations. Reported gas is not an
+    /// input: it comes from the caller, so it is tallied for reporting only.
+    pub fn fee_for(operation_count: usize) -> Result<u64> {
+        ER_FEE_PER_OPERATION
+            .checked_mul(operation_count as u64)
+            .ok_or(GameError::ArithmeticOverflow.into())
+    }
+
+    /// Aggregate the session's operations and return the fee to charge for
+    /// them, in lamports. Fails if the session total would exceed the cap.
+    pub fn record_operations(&mut self, operations: &[ErTokenOperation]) -> Result<u64> {
+        if self.settled_at.is_some() {
+            return Err(GameError::InvalidGameState.into());
+        }
+
+        for operation in operations {
+            self.total_gas = self.total_gas
+                .checked_add(operation.gas_used)
+                .ok_or(GameError::ArithmeticOverflow)?;
+        }
+        let fee = Self::fee_for(operations.len())?;
+        self.fee_debited = self.fee_debited
+            .checked_add(fee)
+            .ok_or(GameError::ArithmeticOverflow)?;
+        self.operation_count = self.operation_count.saturating_add(operations.len() as u32);
+
+        if self.fee_debited > self.max_cost_cap.unwrap_or(u64::MAX) {
+            return Err(GameError::CostCapExceeded.into());
+        }
+
+        self.operator_revenue = self.fee_debited * ER_OPERATOR_SHARE_BPS / 10_000;
+        Ok(fee)
+    }
+
+    /// Collateral forfeited to the attested result: the whole pledge when
+    /// someone else won the backed match, nothing on a win or a draw
+    pub fn collateral_loss(&self, attestation: &WinnerAttestation) -> u64 {
+        match attestation.winner {
+            Some(winner) if winner != self.owner => self.collateral_pledged,
+            _ => 0,
+        }
+    }
+}
+
+#[event]
+pub struct ErCostSummary {
+    pub owner: Pubkey,
+    pub er_session_id: String,
+    pub operation_count: u32,
+    pub total_gas: u64,
+    pub fee_debited: u64,
+    pub operator_revenue: u64,
+    pub max_cost_cap: Option<u64>,
+    pub timestamp: i64,
+}
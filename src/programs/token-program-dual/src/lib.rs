@@ -1,12 +1,21 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
 use anchor_spl::{
     token::{self, Token, TokenAccount, Mint, MintTo, Transfer, Burn},
     associated_token::AssociatedToken,
 };
 
+pub mod cost_accounting;
+pub mod pool_split;
+pub mod reward_accrual;
+pub mod stake_collateral;
 pub mod instructions;
 pub mod state;
 
+pub use cost_accounting::*;
+pub use pool_split::*;
+pub use reward_accrual::*;
+pub use stake_collateral::*;
 pub use instructions::*;
 pub use state::*;
 
@@ -16,7 +25,7 @@ use shared::magicblock::{
     router::*,
     state_management::*,
 };
-use shared::GameError;
+use shared::{GameError, FeatureFlags, FEATURE_STAKE, FEATURE_BRIDGE, ReasonCode, require_feature};
 
 declare_id!("TOKDxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx");
 
@@ -53,13 +62,46 @@ pub mod sol_duel_token_dual {
         instructions::mint_tokens_mainnet::handler(ctx, amount)
     }
 
-    /// Create temporary token allocation for ER gameplay
+    /// Create temporary token allocation for ER gameplay; the session's fees are
+    /// charged in lamports at settlement and capped at `max_cost_cap` when set.
+    /// Collateral backs the game-program match `match_account` and is settled by its result.
     pub fn allocate_tokens_er(
         ctx: Context<AllocateTokensEr>,
         amount: u64,
         er_session_id: String,
         expiry_timestamp: i64,
+        max_cost_cap: Option<u64>,
+        collateral_amount: u64,
+        match_account: Pubkey,
     ) -> Result<()> {
+        let timestamp = Clock::get()?.unix_timestamp;
+        let costs = &mut ctx.accounts.er_session_costs;
+        costs.owner = ctx.accounts.user.key();
+        costs.allocation_amount = amount;
+        costs.max_cost_cap = max_cost_cap;
+        costs.collateral_pledged = collateral_amount;
+        costs.match_account = match_account;
+        costs.opened_at = timestamp;
+        costs.bump = ctx.bumps.er_session_costs;
+
+        if collateral_amount > 0 {
+            if match_account == Pubkey::default() {
+                return Err(GameError::InvalidMatchConfig.into());
+            }
+            let collateral = ctx.accounts.stake_collateral
+                .as_mut()
+                .ok_or(GameError::ErCollateralNotAllowed)?;
+            collateral.lock(collateral_amount)?;
+
+            emit!(StakeCollateralLocked {
+                owner: collateral.owner,
+                er_session_id: er_session_id.clone(),
+                amount: collateral_amount,
+                collateral_locked: collateral.collateral_locked,
+                timestamp,
+            });
+        }
+
         instructions::allocate_tokens_er::handler(ctx, amount, er_session_id, expiry_timestamp)
     }
 
@@ -70,6 +112,20 @@ pub mod sol_duel_token_dual {
         duration: i64,
         allow_er_usage: bool,
     ) -> Result<()> {
+        require_feature(&ctx.accounts.feature_flags, FEATURE_STAKE, &ctx.accounts.staker.key())?;
+
+        let collateral = &mut ctx.accounts.stake_collateral;
+        collateral.owner = ctx.accounts.staker.key();
+        collateral.staked_amount = amount;
+        collateral.allow_er_usage = allow_er_usage;
+        collateral.collateral_locked = 0;
+        collateral.losses_deducted = 0;
+        collateral.bump = ctx.bumps.stake_collateral;
+
+        let staker = ctx.accounts.staker.key();
+        let bump = ctx.bumps.stake_accrual;
+        ctx.accounts.stake_accrual.open(staker, amount, duration, Clock::get()?.unix_timestamp, bump);
+
         instructions::stake_tokens_dual::handler(ctx, amount, duration, allow_er_usage)
     }
 
@@ -78,26 +134,183 @@ pub mod sol_duel_token_dual {
         ctx: Context<ClaimRewardsDual>,
         include_er_rewards: bool,
     ) -> Result<()> {
+        // Mainnet staking rewards accrue here; the handler only covers ER rewards
+        let timestamp = Clock::get()?.unix_timestamp;
+        let accrual = &mut ctx.accounts.stake_accrual;
+        let amount = accrual.claim(timestamp)?;
+
+        if amount > 0 {
+            let authority_seeds: &[&[u8]] = &[b"mint_authority_dual", &[ctx.bumps.mint_authority]];
+            token::mint_to(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    MintTo {
+                        mint: ctx.accounts.mint.to_account_info(),
+                        to: ctx.accounts.claimer_token_account.to_account_info(),
+                        authority: ctx.accounts.mint_authority.to_account_info(),
+                    },
+                    &[authority_seeds],
+                ),
+                amount,
+            )?;
+
+            emit!(StakeRewardsClaimed {
+                owner: accrual.owner,
+                amount,
+                apr_bps: accrual.apr_bps,
+                rewards_claimed: accrual.rewards_claimed,
+                timestamp,
+            });
+        }
+
+        if !include_er_rewards {
+            return Ok(());
+        }
         instructions::claim_rewards_dual::handler(ctx, include_er_rewards)
     }
 
+    /// Withdraw part or all of a dual stake once its lock has ended
+    pub fn unstake_tokens_dual(ctx: Context<UnstakeTokensDual>, amount: u64) -> Result<()> {
+        let timestamp = Clock::get()?.unix_timestamp;
+        let accrual = &mut ctx.accounts.stake_accrual;
+        require!(timestamp >= accrual.unlock_time, GameError::StakingPeriodNotComplete);
+
+        ctx.accounts.stake_collateral.withdraw(amount)?;
+        accrual.reduce(amount, timestamp)?;
+
+        let seeds = &[b"stake_vault_authority_dual".as_ref(), &[ctx.bumps.stake_vault_authority]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.stake_vault.to_account_info(),
+                    to: ctx.accounts.staker_token_account.to_account_info(),
+                    authority: ctx.accounts.stake_vault_authority.to_account_info(),
+                },
+                &[&seeds[..]],
+            ),
+            amount,
+        )?;
+
+        emit!(StakeWithdrawnDual {
+            owner: accrual.owner,
+            amount,
+            remaining_stake: ctx.accounts.stake_collateral.staked_amount,
+            timestamp,
+        });
+
+        Ok(())
+    }
+
     /// Settle ER token operations back to mainnet
     pub fn settle_er_operations(
         ctx: Context<SettleErOperations>,
         er_session_id: String,
         operations: Vec<ErTokenOperation>,
     ) -> Result<()> {
+        let costs = &mut ctx.accounts.er_session_costs;
+        let timestamp = Clock::get()?.unix_timestamp;
+        let fee = costs.record_operations(&operations)?;
+        costs.settled_at = Some(timestamp);
+
+        // The session owner pays the fee into the vault, which holds the
+        // operator's share until it is paid out
+        if fee > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.authority.to_account_info(),
+                        to: ctx.accounts.token_vault_dual.to_account_info(),
+                    },
+                ),
+                fee,
+            )?;
+        }
+
+        // Pledged collateral is settled by the backed match's attested result,
+        // never by the caller's operation list
+        if costs.collateral_pledged > 0 {
+            let attestation = ctx.accounts.winner_attestation
+                .as_ref()
+                .ok_or(GameError::InvalidMatchConfig)?;
+            let loss = costs.collateral_loss(attestation);
+            let collateral = ctx.accounts.stake_collateral
+                .as_mut()
+                .ok_or(GameError::ErCollateralNotAllowed)?;
+            let deducted = collateral.settle(costs.collateral_pledged, loss)?;
+
+            if deducted > 0 {
+                // The slashed tokens stop earning from here on
+                ctx.accounts.stake_accrual
+                    .as_mut()
+                    .ok_or(GameError::ErCollateralNotAllowed)?
+                    .reduce(deducted, timestamp)?;
+
+                let winner = attestation.winner.ok_or(GameError::InvalidMatchConfig)?;
+                let winner_token_account = ctx.accounts.winner_token_account
+                    .as_ref()
+                    .ok_or(GameError::InvalidAccountOwner)?;
+                if winner_token_account.owner != winner || winner_token_account.mint != ctx.accounts.mint.key() {
+                    return Err(GameError::InvalidAccountOwner.into());
+                }
+
+                let seeds = &[b"stake_vault_authority_dual".as_ref(), &[ctx.bumps.stake_vault_authority]];
+                token::transfer(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        Transfer {
+                            from: ctx.accounts.stake_vault.to_account_info(),
+                            to: winner_token_account.to_account_info(),
+                            authority: ctx.accounts.stake_vault_authority.to_account_info(),
+                        },
+                        &[&seeds[..]],
+                    ),
+                    deducted,
+                )?;
+
+                emit!(StakeLossDeducted {
+                    owner: collateral.owner,
+                    er_session_id: er_session_id.clone(),
+                    match_account: costs.match_account,
+                    winner,
+                    deducted,
+                    remaining_stake: collateral.staked_amount,
+                    timestamp,
+                });
+            }
+        }
+
+        emit!(ErCostSummary {
+            owner: costs.owner,
+            er_session_id: er_session_id.clone(),
+            operation_count: costs.operation_count,
+            total_gas: costs.total_gas,
+            fee_debited: costs.fee_debited,
+            operator_revenue: costs.operator_revenue,
+            max_cost_cap: costs.max_cost_cap,
+            timestamp,
+        });
+
         instructions::settle_er_operations::handler(ctx, er_session_id, operations)
     }
 
+    /// Close a settled ER session's cost ledger and settlement record,
+    /// refunding rent to the session owner
+    pub fn close_er_session(ctx: Context<CloseErSession>, er_session_id: String) -> Result<()> {
+        msg!("Closed ER session {} for {}", er_session_id, ctx.accounts.owner.key());
+        Ok(())
+    }
+
     /// Emergency freeze for cross-chain operations
     pub fn emergency_freeze_dual(
         ctx: Context<EmergencyFreezeDual>,
         freeze_mainnet: bool,
         freeze_er: bool,
-        reason: String,
+        reason: ReasonCode,
+        memo_hash: Option<[u8; 32]>,
     ) -> Result<()> {
-        instructions::emergency_freeze_dual::handler(ctx, freeze_mainnet, freeze_er, reason)
+        instructions::emergency_freeze_dual::handler(ctx, freeze_mainnet, freeze_er, reason, memo_hash)
     }
 
     /// Bridge tokens between mainnet and ER
@@ -107,6 +320,7 @@ pub mod sol_duel_token_dual {
         source_environment: ExecutionEnvironment,
         destination_environment: ExecutionEnvironment,
     ) -> Result<()> {
+        require_feature(&ctx.accounts.feature_flags, FEATURE_BRIDGE, &ctx.accounts.user.key())?;
         instructions::bridge_tokens::handler(ctx, amount, source_environment, destination_environment)
     }
 
@@ -118,17 +332,88 @@ pub mod sol_duel_token_dual {
         er_percentage: u8,
         distribution_type: RewardDistributionType,
     ) -> Result<()> {
+        PoolSplit::validate_new(pool_size, mainnet_percentage, er_percentage, ctx.accounts.authority_token_account.amount)?;
+
+        let split = &mut ctx.accounts.pool_split;
+        split.reward_pool = ctx.accounts.reward_pool_dual.key();
+        split.pool_size = pool_size;
+        split.mainnet_percentage = mainnet_percentage;
+        split.er_percentage = er_percentage;
+        split.mainnet_distributed = 0;
+        split.er_distributed = 0;
+        split.updated_at = Clock::get()?.unix_timestamp;
+        split.bump = ctx.bumps.pool_split;
+
         instructions::create_reward_pool_dual::handler(ctx, pool_size, mainnet_percentage, er_percentage, distribution_type)
     }
 
+    /// Adjust the mainnet/ER split of an existing pool; amounts already paid
+    /// on either side stay reserved
+    pub fn rebalance_pool_split(
+        ctx: Context<RebalancePoolSplit>,
+        mainnet_percentage: u8,
+        er_percentage: u8,
+    ) -> Result<()> {
+        let split = &mut ctx.accounts.pool_split;
+        split.rebalance(mainnet_percentage, er_percentage, Clock::get()?.unix_timestamp)?;
+
+        emit!(PoolSplitRebalanced {
+            reward_pool: split.reward_pool,
+            mainnet_percentage,
+            er_percentage,
+            mainnet_distributed: split.mainnet_distributed,
+            er_distributed: split.er_distributed,
+            timestamp: split.updated_at,
+        });
+
+        Ok(())
+    }
+
     /// Distribute rewards with environment routing
     pub fn distribute_rewards_dual(
         ctx: Context<DistributeRewardsDual>,
         recipients: Vec<RewardRecipient>,
         force_environment: Option<ExecutionEnvironment>,
     ) -> Result<()> {
+        ctx.accounts.pool_split.record_distribution(&recipients, force_environment)?;
         instructions::distribute_rewards_dual::handler(ctx, recipients, force_environment)
     }
+
+    /// Self-report version, commit and IDL hash after an upgrade - upgrade authority only
+    pub fn update_program_info(
+        ctx: Context<UpdateProgramInfo>,
+        version: [u16; 3],
+        git_commit: [u8; 20],
+        idl_hash: [u8; 32],
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let deployed_slot = ctx.accounts.program_data.slot;
+        let info = &mut ctx.accounts.program_info;
+        
+        // A stale upgrade must not overwrite the record of a newer one
+        if deployed_slot < info.deployed_slot {
+            return Err(GameError::StaleProgramInfo.into());
+        }
+        
+        info.program_id = crate::ID;
+        info.version = version;
+        info.git_commit = git_commit;
+        info.idl_hash = idl_hash;
+        info.deployed_slot = deployed_slot;
+        info.updated_by = ctx.accounts.authority.key();
+        info.updated_at = clock.unix_timestamp;
+        info.bump = ctx.bumps.program_info;
+        
+        emit!(ProgramInfoUpdated {
+            version,
+            git_commit,
+            idl_hash,
+            deployed_slot,
+            timestamp: clock.unix_timestamp,
+        });
+        
+        Ok(())
+    }
 }
 
 /// Dual-mode token operation types
@@ -283,7 +568,7 @@ pub struct MintTokensMainnet<'info> {
     )]
     pub recipient_token_account: Account<'info, TokenAccount>,
     
-    /// CHECK: This is the recipient of the tokens
+    /// CHECK: Only the authority of the recipient token account; any owner may hold tokens
     pub recipient: UncheckedAccount<'info>,
     
     #[account(mut)]
@@ -322,6 +607,22 @@ pub struct AllocateTokensEr<'info> {
     )]
     pub er_allocation: Account<'info, ErTokenAllocation>,
 
+    #[account(
+        init,
+        payer = user,
+        space = ErSessionCosts::LEN,
+        seeds = [b"er_costs", user.key().as_ref(), er_session_id.as_bytes()],
+        bump
+    )]
+    pub er_session_costs: Account<'info, ErSessionCosts>,
+
+    #[account(
+        mut,
+        seeds = [b"stake_collateral", user.key().as_ref()],
+        bump = stake_collateral.bump
+    )]
+    pub stake_collateral: Option<Account<'info, StakeCollateral>>,
+
     #[account(
         mut,
         associated_token::mint = mint,
@@ -356,6 +657,24 @@ pub struct StakeTokensDual<'info> {
         bump
     )]
     pub stake_account_dual: Account<'info, StakeAccountDual>,
+
+    #[account(
+        init,
+        payer = staker,
+        space = StakeCollateral::LEN,
+        seeds = [b"stake_collateral", staker.key().as_ref()],
+        bump
+    )]
+    pub stake_collateral: Account<'info, StakeCollateral>,
+
+    #[account(
+        init,
+        payer = staker,
+        space = StakeAccrual::LEN,
+        seeds = [b"stake_accrual", staker.key().as_ref()],
+        bump
+    )]
+    pub stake_accrual: Account<'info, StakeAccrual>,
     
     #[account(
         init,
@@ -377,6 +696,15 @@ pub struct StakeTokensDual<'info> {
         bump = dual_mode_config.bump
     )]
     pub dual_mode_config: Account<'info, DualModeConfig>,
+
+    #[account(
+        seeds = [b"feature_flags"],
+        seeds::program = shared::GAME_PROGRAM_ID,
+        bump = feature_flags.bump,
+        constraint = shared::is_owned_account(&feature_flags.to_account_info(), &shared::GAME_PROGRAM_ID, FeatureFlags::LEN)
+            @ GameError::InvalidAccountOwner
+    )]
+    pub feature_flags: Account<'info, FeatureFlags>,
     
     #[account(mut)]
     pub staker: Signer<'info>,
@@ -400,11 +728,28 @@ pub struct ClaimRewardsDual<'info> {
     
     #[account(
         mut,
-        seeds = [b"stake_dual", claimer.key().as_ref()],
+        seeds = [b"stake_dual", stake_accrual.owner.as_ref()],
         bump
     )]
     pub stake_account_dual: Account<'info, StakeAccountDual>,
 
+    #[account(
+        mut,
+        seeds = [b"stake_accrual", stake_accrual.owner.as_ref()],
+        bump = stake_accrual.bump
+    )]
+    pub stake_accrual: Account<'info, StakeAccrual>,
+
+    /// CHECK: The owner's game-program identity, empty if none; once linked only its cold wallet may claim
+    #[account(
+        seeds = [b"identity", stake_accrual.owner.as_ref()],
+        seeds::program = shared::GAME_PROGRAM_ID,
+        bump,
+        constraint = shared::PlayerIdentity::may_withdraw(&player_identity, &stake_accrual.owner, &claimer.key())
+            @ GameError::ColdWalletRequired
+    )]
+    pub player_identity: UncheckedAccount<'info>,
+
     #[account(
         mut,
         seeds = [b"token_vault_dual"],
@@ -426,6 +771,62 @@ pub struct ClaimRewardsDual<'info> {
 }
 
 #[derive(Accounts)]
+pub struct UnstakeTokensDual<'info> {
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = staker
+    )]
+    pub staker_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"stake_collateral", stake_accrual.owner.as_ref()],
+        bump = stake_collateral.bump
+    )]
+    pub stake_collateral: Account<'info, StakeCollateral>,
+
+    #[account(
+        mut,
+        seeds = [b"stake_accrual", stake_accrual.owner.as_ref()],
+        bump = stake_accrual.bump
+    )]
+    pub stake_accrual: Account<'info, StakeAccrual>,
+
+    /// CHECK: The owner's game-program identity, empty if none; once linked only its cold wallet may unstake
+    #[account(
+        seeds = [b"identity", stake_accrual.owner.as_ref()],
+        seeds::program = shared::GAME_PROGRAM_ID,
+        bump,
+        constraint = shared::PlayerIdentity::may_withdraw(&player_identity, &stake_accrual.owner, &staker.key())
+            @ GameError::ColdWalletRequired
+    )]
+    pub player_identity: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = stake_vault_authority
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: This is the stake vault authority PDA
+    #[account(
+        seeds = [b"stake_vault_authority_dual"],
+        bump
+    )]
+    pub stake_vault_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub staker: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(er_session_id: String)]
 pub struct SettleErOperations<'info> {
     #[account(mut)]
     pub mint: Account<'info, Mint>,
@@ -445,6 +846,60 @@ pub struct SettleErOperations<'info> {
     )]
     pub er_allocation: Account<'info, ErTokenAllocation>,
 
+    #[account(
+        mut,
+        seeds = [b"er_costs", authority.key().as_ref(), er_session_id.as_bytes()],
+        bump = er_session_costs.bump
+    )]
+    pub er_session_costs: Account<'info, ErSessionCosts>,
+
+    #[account(
+        mut,
+        seeds = [b"stake_collateral", authority.key().as_ref()],
+        bump = stake_collateral.bump
+    )]
+    pub stake_collateral: Option<Account<'info, StakeCollateral>>,
+
+    /// Reward checkpoint of the same stake; required when a loss is deducted
+    #[account(
+        mut,
+        seeds = [b"stake_accrual", authority.key().as_ref()],
+        bump = stake_accrual.bump
+    )]
+    pub stake_accrual: Option<Account<'info, StakeAccrual>>,
+
+    /// Result of the match the session's collateral backs; required when collateral was pledged
+    #[account(
+        seeds = [shared::WINNER_ATTESTATION_SEED, er_session_costs.match_account.as_ref()],
+        seeds::program = shared::GAME_PROGRAM_ID,
+        bump = winner_attestation.bump,
+        constraint = shared::is_owned_account(
+            &winner_attestation.to_account_info(),
+            &shared::GAME_PROGRAM_ID,
+            shared::WinnerAttestation::LEN,
+        ) @ GameError::InvalidAccountOwner,
+        constraint = winner_attestation.is_consistent() @ GameError::InvalidMatchConfig
+    )]
+    pub winner_attestation: Option<Account<'info, shared::WinnerAttestation>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = stake_vault_authority
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: This is the stake vault authority PDA
+    #[account(
+        seeds = [b"stake_vault_authority_dual"],
+        bump
+    )]
+    pub stake_vault_authority: UncheckedAccount<'info>,
+
+    /// Match winner's token account, credited with forfeited collateral
+    #[account(mut)]
+    pub winner_token_account: Option<Account<'info, TokenAccount>>,
+
     #[account(
         seeds = [b"dual_mode_config"],
         bump = dual_mode_config.bump
@@ -463,9 +918,35 @@ pub struct SettleErOperations<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
 
+    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+#[instruction(er_session_id: String)]
+pub struct CloseErSession<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"er_costs", owner.key().as_ref(), er_session_id.as_bytes()],
+        bump = er_session_costs.bump,
+        has_one = owner,
+        constraint = er_session_costs.settled_at.is_some() @ GameError::InvalidGameState
+    )]
+    pub er_session_costs: Account<'info, ErSessionCosts>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"er_settlement", er_session_id.as_bytes()],
+        bump
+    )]
+    pub er_settlement: Account<'info, ErSettlement>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
 #[derive(Accounts)]
 pub struct BridgeTokens<'info> {
     #[account(mut)]
@@ -500,6 +981,15 @@ pub struct BridgeTokens<'info> {
     )]
     pub dual_mode_config: Account<'info, DualModeConfig>,
 
+    #[account(
+        seeds = [b"feature_flags"],
+        seeds::program = shared::GAME_PROGRAM_ID,
+        bump = feature_flags.bump,
+        constraint = shared::is_owned_account(&feature_flags.to_account_info(), &shared::GAME_PROGRAM_ID, FeatureFlags::LEN)
+            @ GameError::InvalidAccountOwner
+    )]
+    pub feature_flags: Account<'info, FeatureFlags>,
+
     #[account(mut)]
     pub user: Signer<'info>,
 
@@ -542,6 +1032,15 @@ pub struct CreateRewardPoolDual<'info> {
         bump
     )]
     pub reward_pool_dual: Account<'info, RewardPoolDual>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = PoolSplit::LEN,
+        seeds = [b"pool_split", reward_pool_dual.key().as_ref()],
+        bump
+    )]
+    pub pool_split: Account<'info, PoolSplit>,
     
     #[account(
         init,
@@ -590,6 +1089,13 @@ pub struct DistributeRewardsDual<'info> {
         bump = reward_pool_dual.bump
     )]
     pub reward_pool_dual: Account<'info, RewardPoolDual>,
+
+    #[account(
+        mut,
+        seeds = [b"pool_split", reward_pool_dual.key().as_ref()],
+        bump = pool_split.bump
+    )]
+    pub pool_split: Account<'info, PoolSplit>,
     
     #[account(
         mut,
@@ -620,4 +1126,156 @@ pub struct DistributeRewardsDual<'info> {
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
-}
\ No newline at end of file
+}
+
+#[derive(Accounts)]
+pub struct RebalancePoolSplit<'info> {
+    #[account(
+        seeds = [b"reward_pool_dual", reward_pool_dual.authority.as_ref(), &reward_pool_dual.created_at.to_le_bytes()],
+        bump = reward_pool_dual.bump
+    )]
+    pub reward_pool_dual: Account<'info, RewardPoolDual>,
+
+    #[account(
+        mut,
+        seeds = [b"pool_split", reward_pool_dual.key().as_ref()],
+        bump = pool_split.bump
+    )]
+    pub pool_split: Account<'info, PoolSplit>,
+
+    #[account(constraint = authority.key() == reward_pool_dual.authority)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateProgramInfo<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = shared::ProgramInfo::LEN,
+        seeds = [b"program_info"],
+        bump
+    )]
+    pub program_info: Account<'info, shared::ProgramInfo>,
+    
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::SolDuelTokenDual>,
+    
+    #[account(constraint = program_data.upgrade_authority_address == Some(authority.key()) @ GameError::InvalidUpgradeAuthority)]
+    pub program_data: Account<'info, ProgramData>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct ProgramInfoUpdated {
+    pub version: [u16; 3],
+    pub git_commit: [u8; 20],
+    pub idl_hash: [u8; 32],
+    pub deployed_slot: u64,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: i64 = 24 * 3600;
+    const YEAR: i64 = 365 * DAY;
+    // Multiple of 1971 so every tier's accumulator lands on a whole value
+    const START: i64 = 1_700_050_572;
+
+    fn accrual(amount: u64, duration: i64) -> StakeAccrual {
+        let mut accrual = StakeAccrual {
+            owner: Pubkey::default(),
+            amount: 0,
+            apr_bps: 0,
+            reward_per_token_paid: 0,
+            rewards_stored: 0,
+            rewards_claimed: 0,
+            last_update: 0,
+            bump: 0,
+        };
+        accrual.open(Pubkey::default(), amount, duration, START, 255);
+        accrual
+    }
+
+    #[test]
+    fn test_apr_tiers() {
+        assert_eq!(apr_for_duration(0), 500);
+        assert_eq!(apr_for_duration(30 * DAY - 1), 500);
+        assert_eq!(apr_for_duration(30 * DAY), 1_000);
+        assert_eq!(apr_for_duration(90 * DAY), 1_500);
+        assert_eq!(apr_for_duration(180 * DAY), 2_500);
+        assert_eq!(apr_for_duration(10 * YEAR), 2_500);
+        // Negative durations fall back to the flexible tier
+        assert_eq!(apr_for_duration(-1), 500);
+    }
+
+    #[test]
+    fn test_full_year_accrual() {
+        let mut flexible = accrual(1_000_000_000, 0);
+        assert_eq!(flexible.pending(START + YEAR), 50_000_000);
+
+        let long = accrual(1_000_000_000, 180 * DAY);
+        assert_eq!(long.pending(START + YEAR), 250_000_000);
+
+        assert_eq!(flexible.claim(START + YEAR).unwrap(), 50_000_000);
+        assert_eq!(flexible.pending(START + YEAR), 0);
+        assert_eq!(flexible.rewards_claimed, 50_000_000);
+    }
+
+    #[test]
+    fn test_no_accrual_without_elapsed_time() {
+        let mut stake = accrual(1_000_000_000, 0);
+        assert_eq!(stake.pending(START), 0);
+        assert_eq!(stake.claim(START).unwrap(), 0);
+        // Clock going backwards neither accrues nor panics
+        assert_eq!(stake.pending(START - 100), 0);
+        stake.checkpoint(START - 100);
+        assert_eq!(stake.last_update, START);
+    }
+
+    #[test]
+    fn test_checkpoint_frequency_does_not_drift() {
+        let mut per_second = accrual(123_456_789, 90 * DAY);
+        let once = accrual(123_456_789, 90 * DAY);
+
+        for t in 1..=DAY {
+            per_second.checkpoint(START + t);
+        }
+        assert_eq!(per_second.rewards_stored, once.earned_scaled(START + DAY));
+        assert_eq!(per_second.pending(START + DAY), once.pending(START + DAY));
+    }
+
+    #[test]
+    fn test_frequent_claims_carry_dust() {
+        // A stake too small to earn a whole token per claim still gets paid
+        // once the carried remainder adds up
+        let mut frequent = accrual(1_000, 0);
+        let mut claimed = 0;
+        for t in (DAY..=YEAR).step_by(DAY as usize) {
+            claimed += frequent.claim(START + t).unwrap();
+        }
+        let single = accrual(1_000, 0);
+        assert_eq!(claimed, single.pending(START + YEAR));
+        assert_eq!(claimed, 50);
+    }
+
+    #[test]
+    fn test_accrual_is_per_second() {
+        // 5% APR on this amount is just over one token per second
+        let stake = accrual(SECONDS_PER_YEAR as u64 * 20 + 1_000, 0);
+        assert_eq!(stake.pending(START + 1), 1);
+        assert_eq!(stake.pending(START + 59), 59);
+    }
+
+    #[test]
+    fn test_large_stake_does_not_overflow() {
+        let stake = accrual(u64::MAX / 4, 180 * DAY);
+        assert_eq!(stake.pending(START + YEAR), u64::MAX / 4 / 4);
+    }
+}
@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use shared::GameError;
+use shared::magicblock::router::ExecutionEnvironment;
+
+use crate::RewardRecipient;
+
+/// Mainnet/ER split of a dual reward pool, kept next to the pool so splits can
+/// be rebalanced without losing track of what each side already paid out
+#[account]
+pub struct PoolSplit {
+    pub reward_pool: Pubkey,
+    pub pool_size: u64,
+    pub mainnet_percentage: u8,
+    pub er_percentage: u8,
+    pub mainnet_distributed: u64,
+    pub er_distributed: u64,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl PoolSplit {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // reward_pool
+        8 + // pool_size
+        1 + // mainnet_percentage
+        1 + // er_percentage
+        8 + // mainnet_distributed
+        8 + // er_distributed
+        8 + // updated_at
+        1; // bump
+
+    pub fn validate_split(mainnet_percentage: u8, er_percentage: u8) -> Result<()> {
+        if mainnet_percentage as u16 + er_percentage as u16 != 100 {
+            return Err(GameError::InvalidRewardDistribution.into());
+        }
+        Ok(())
+    }
+
+    /// A new pool must be non-empty, split exactly 100% and fully funded by the transfer
+    pub fn validate_new(pool_size: u64, mainnet_percentage: u8, er_percentage: u8, funding_balance: u64) -> Result<()> {
+        Self::validate_split(mainnet_percentage, er_percentage)?;
+        if pool_size == 0 {
+            return Err(GameError::InvalidRewardDistribution.into());
+        }
+        if pool_size > funding_balance {
+            return Err(GameError::InsufficientFunds.into());
+        }
+        Ok(())
+    }
+
+    /// ER share rounds down; mainnet takes the remainder so nothing is stranded
+    pub fn er_allocation(&self) -> u64 {
+        (self.pool_size as u128 * self.er_percentage as u128 / 100) as u64
+    }
+
+    pub fn mainnet_allocation(&self) -> u64 {
+        self.pool_size - self.er_allocation()
+    }
+
+    pub fn remaining(&self, environment: ExecutionEnvironment) -> u64 {
+        match environment {
+            ExecutionEnvironment::EphemeralRollup => self.er_allocation().saturating_sub(self.er_distributed),
+            // Cross-environment payouts settle on mainnet
+            ExecutionEnvironment::Mainnet | ExecutionEnvironment::Both => {
+                self.mainnet_allocation().saturating_sub(self.mainnet_distributed)
+            }
+        }
+    }
+
+    /// Charge a batch of payouts against each side's allocation
+    pub fn record_distribution(
+        &mut self,
+        recipients: &[RewardRecipient],
+        force_environment: Option<ExecutionEnvironment>,
+    ) -> Result<()> {
+        let (mut mainnet, mut er) = (0u64, 0u64);
+        for recipient in recipients {
+            let bucket = match force_environment.unwrap_or(recipient.preferred_environment) {
+                ExecutionEnvironment::EphemeralRollup => &mut er,
+                ExecutionEnvironment::Mainnet | ExecutionEnvironment::Both => &mut mainnet,
+            };
+            *bucket = bucket.checked_add(recipient.amount).ok_or(GameError::ArithmeticOverflow)?;
+        }
+
+        if mainnet > self.remaining(ExecutionEnvironment::Mainnet) || er > self.remaining(ExecutionEnvironment::EphemeralRollup) {
+            return Err(GameError::RewardPoolEmpty.into());
+        }
+        self.mainnet_distributed += mainnet;
+        self.er_distributed += er;
+        Ok(())
+    }
+
+    /// Move the split on a live pool. Neither side may drop below what it has
+    /// already distributed.
+    pub fn rebalance(&mut self, mainnet_percentage: u8, er_percentage: u8, now: i64) -> Result<()> {
+        Self::validate_split(mainnet_percentage, er_percentage)?;
+
+        let previous = (self.mainnet_percentage, self.er_percentage);
+        self.mainnet_percentage = mainnet_percentage;
+        self.er_percentage = er_percentage;
+        if self.mainnet_allocation() < self.mainnet_distributed || self.er_allocation() < self.er_distributed {
+            (self.mainnet_percentage, self.er_percentage) = previous;
+            return Err(GameError::InvalidRewardDistribution.into());
+        }
+
+        self.updated_at = now;
+        Ok(())
+    }
+}
+
+#[event]
+pub struct PoolSplitRebalanced {
+    pub reward_pool: Pubkey,
+    pub mainnet_percentage: u8,
+    pub er_percentage: u8,
+    pub mainnet_distributed: u64,
+    pub er_distributed: u64,
+    pub timestamp: i64,
+}
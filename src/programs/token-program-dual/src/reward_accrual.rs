@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+use shared::GameError;
+
+pub const SECONDS_PER_YEAR: u128 = 365 * 24 * 3600;
+
+/// Fixed-point scale for the reward-per-token accumulator
+pub const REWARD_PRECISION: u128 = 1_000_000_000_000_000_000;
+
+/// Fixed APR tiers as (minimum lock duration in seconds, APR in basis points),
+/// ordered by duration
+pub const APR_TIERS: [(i64, u16); 4] = [
+    (0, 500),
+    (30 * 24 * 3600, 1_000),
+    (90 * 24 * 3600, 1_500),
+    (180 * 24 * 3600, 2_500),
+];
+
+pub fn apr_for_duration(duration: i64) -> u16 {
+    APR_TIERS
+        .iter()
+        .rev()
+        .find(|(min_duration, _)| duration >= *min_duration)
+        .map(|(_, apr_bps)| *apr_bps)
+        .unwrap_or(APR_TIERS[0].1)
+}
+
+/// Reward owed per staked token since the unix epoch at a fixed APR, scaled by
+/// `REWARD_PRECISION`. With a fixed rate each tier's accumulator is a closed
+/// form of time, so there is no pool-wide state to keep in sync.
+pub fn reward_per_token(apr_bps: u16, timestamp: i64) -> u128 {
+    apr_bps as u128 * REWARD_PRECISION * timestamp.max(0) as u128 / (10_000 * SECONDS_PER_YEAR)
+}
+
+/// Per-stake accrual checkpoint, opened with the dual stake account.
+/// Earnings are kept scaled and only floored at payout, so checkpointing more
+/// often never loses dust.
+#[account]
+pub struct StakeAccrual {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub apr_bps: u16,
+    pub reward_per_token_paid: u128,
+    // Earned but unpaid rewards, scaled by REWARD_PRECISION
+    pub rewards_stored: u128,
+    pub rewards_claimed: u64,
+    pub last_update: i64,
+    // End of the lock the APR tier was chosen for
+    pub unlock_time: i64,
+    pub bump: u8,
+}
+
+impl StakeAccrual {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // owner
+        8 + // amount
+        2 + // apr_bps
+        16 + // reward_per_token_paid
+        16 + // rewards_stored
+        8 + // rewards_claimed
+        8 + // last_update
+        8 + // unlock_time
+        1; // bump
+
+    pub fn open(&mut self, owner: Pubkey, amount: u64, duration: i64, now: i64, bump: u8) {
+        self.owner = owner;
+        self.amount = amount;
+        self.apr_bps = apr_for_duration(duration);
+        self.reward_per_token_paid = reward_per_token(self.apr_bps, now);
+        self.rewards_stored = 0;
+        self.rewards_claimed = 0;
+        self.last_update = now;
+        self.unlock_time = now.saturating_add(duration.max(0));
+        self.bump = bump;
+    }
+
+    pub fn earned_scaled(&self, now: i64) -> u128 {
+        let delta = reward_per_token(self.apr_bps, now).saturating_sub(self.reward_per_token_paid);
+        self.rewards_stored + self.amount as u128 * delta
+    }
+
+    /// Whole tokens claimable at `now`
+    pub fn pending(&self, now: i64) -> u64 {
+        (self.earned_scaled(now) / REWARD_PRECISION).min(u64::MAX as u128) as u64
+    }
+
+    pub fn checkpoint(&mut self, now: i64) {
+        if now <= self.last_update {
+            return;
+        }
+        self.rewards_stored = self.earned_scaled(now);
+        self.reward_per_token_paid = reward_per_token(self.apr_bps, now);
+        self.last_update = now;
+    }
+
+    /// Shrink the accruing stake after an unstake or slash. Rewards earned on
+    /// the old amount are checkpointed first, so they are kept.
+    pub fn reduce(&mut self, amount: u64, now: i64) -> Result<()> {
+        self.checkpoint(now);
+        self.amount = self.amount
+            .checked_sub(amount)
+            .ok_or(GameError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Pay out whole tokens, carrying the sub-token remainder forward
+    pub fn claim(&mut self, now: i64) -> Result<u64> {
+        self.checkpoint(now);
+        let payout = self.pending(now);
+        self.rewards_stored -= payout as u128 * REWARD_PRECISION;
+        self.rewards_claimed = self.rewards_claimed
+            .checked_add(payout)
+            .ok_or(GameError::ArithmeticOverflow)?;
+        Ok(payout)
+    }
+}
+
+#[event]
+pub struct StakeRewardsClaimed {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub apr_bps: u16,
+    pub rewards_claimed: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakeWithdrawnDual {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub remaining_stake: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use shared::GameError;
+
+/// Largest share of a stake that may back ER sessions at once, in basis points
+pub const MAX_ER_COLLATERAL_BPS: u64 = 5_000;
+
+/// Collateral ledger for a dual stake. Opened alongside the stake account and
+/// consumed by ER allocations when the staker opted into ER usage.
+#[account]
+pub struct StakeCollateral {
+    pub owner: Pubkey,
+    pub staked_amount: u64,
+    pub allow_er_usage: bool,
+    // Collateral currently pledged to open ER sessions
+    pub collateral_locked: u64,
+    pub losses_deducted: u64,
+    pub bump: u8,
+}
+
+impl StakeCollateral {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // owner
+        8 + // staked_amount
+        1 + // allow_er_usage
+        8 + // collateral_locked
+        8 + // losses_deducted
+        1; // bump
+
+    pub fn available_collateral(&self) -> u64 {
+        let cap = (self.staked_amount as u128 * MAX_ER_COLLATERAL_BPS as u128 / 10_000) as u64;
+        cap.saturating_sub(self.collateral_locked)
+    }
+
+    /// Pledge part of the stake to an ER session
+    pub fn lock(&mut self, amount: u64) -> Result<()> {
+        if !self.allow_er_usage {
+            return Err(GameError::ErCollateralNotAllowed.into());
+        }
+        if amount > self.available_collateral() {
+            return Err(GameError::ErCollateralExceeded.into());
+        }
+        self.collateral_locked += amount;
+        Ok(())
+    }
+
+    /// Release a session's pledge, deducting its loss from the stake. The
+    /// deduction never exceeds what the session pledged; returns the amount taken.
+    pub fn settle(&mut self, pledged: u64, loss: u64) -> Result<u64> {
+        self.collateral_locked = self.collateral_locked
+            .checked_sub(pledged)
+            .ok_or(GameError::ArithmeticOverflow)?;
+
+        let deducted = loss.min(pledged).min(self.staked_amount);
+        self.staked_amount -= deducted;
+        self.losses_deducted = self.losses_deducted
+            .checked_add(deducted)
+            .ok_or(GameError::ArithmeticOverflow)?;
+        Ok(deducted)
+    }
+
+    /// Take part of the stake back out. What stays must still cover the
+    /// collateral pledged to open sessions.
+    pub fn withdraw(&mut self, amount: u64) -> Result<()> {
+        let remaining = self.staked_amount
+            .checked_sub(amount)
+            .ok_or(GameError::InsufficientFunds)?;
+        let cap = (remaining as u128 * MAX_ER_COLLATERAL_BPS as u128 / 10_000) as u64;
+        if cap < self.collateral_locked {
+            return Err(GameError::ErCollateralExceeded.into());
+        }
+        self.staked_amount = remaining;
+        Ok(())
+    }
+}
+
+#[event]
+pub struct StakeCollateralLocked {
+    pub owner: Pubkey,
+    pub er_session_id: String,
+    pub amount: u64,
+    pub collateral_locked: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakeLossDeducted {
+    pub owner: Pubkey,
+    pub er_session_id: String,
+    pub match_account: Pubkey,
+    // Match winner the deducted collateral was transferred to
+    pub winner: Pubkey,
+    pub deducted: u64,
+    pub remaining_stake: u64,
+    pub timestamp: i64,
+}
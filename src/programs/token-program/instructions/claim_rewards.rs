@@ -27,6 +27,11 @@ pub fn handler(ctx: Context<crate::ClaimRewards>) -> Result<()> {
         return Err(GameError::RewardPoolEmpty.into());
     }
     
+    if let Err(err) = ctx.accounts.vault_registry.assert_mint_authority(&ctx.accounts.mint.key(), &ctx.accounts.mint_authority.key()) {
+        stake_account.reentrancy_guard = ReentrancyState::NotEntered;
+        return Err(err);
+    }
+    
     // Mint reward tokens to staker
     let mint_authority_bump = ctx.bumps.mint_authority;
     let signer_seeds = &[
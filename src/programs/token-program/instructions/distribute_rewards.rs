@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, TokenAccount, Transfer};
+use crate::shared::{GameError, ReentrancyState};
+
+/// Pay out from the pool vault. Recipient token accounts are passed as
+/// remaining accounts in the same order as `recipients`.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, crate::DistributeRewards<'info>>,
+    recipients: Vec<Pubkey>,
+    amounts: Vec<u64>,
+) -> Result<()> {
+    // SECURITY: Optionally reject distribution wrapped by unknown programs
+    ctx.accounts.admin_config.check_cpi_origin(&ctx.accounts.instructions, &crate::ID)?;
+
+    let clock = Clock::get()?;
+    let reward_pool = &mut ctx.accounts.reward_pool;
+
+    // SECURITY: Reentrancy Guard - flushed to account data so a nested call
+    // through the token transfers below is rejected by the context constraint
+    reward_pool.reentrancy_guard = ReentrancyState::Entered;
+    reward_pool.exit(&crate::ID)?;
+
+    if !reward_pool.is_active || reward_pool.is_expired(clock.unix_timestamp) {
+        return Err(GameError::InvalidGameState.into());
+    }
+    if recipients.is_empty()
+        || recipients.len() != amounts.len()
+        || recipients.len() > reward_pool.max_recipients as usize
+        || ctx.remaining_accounts.len() != recipients.len()
+    {
+        return Err(GameError::InvalidRewardDistribution.into());
+    }
+
+    let total = amounts.iter().try_fold(0u64, |acc, amount| acc.checked_add(*amount))
+        .ok_or(GameError::ArithmeticOverflow)?;
+    if total > reward_pool.remaining_amount() {
+        return Err(GameError::RewardPoolEmpty.into());
+    }
+
+    // SECURITY: Record the distribution before the transfers (Checks-Effects-Interactions)
+    reward_pool.distributed_amount = reward_pool.distributed_amount
+        .checked_add(total)
+        .ok_or(GameError::ArithmeticOverflow)?;
+    if reward_pool.remaining_amount() == 0 {
+        reward_pool.is_active = false;
+    }
+
+    let reward_pool_key = reward_pool.key();
+    ctx.accounts.vault_registry.assert_pool_vault(&reward_pool_key, &ctx.accounts.pool_vault, &ctx.accounts.pool_vault_authority.key())?;
+    let vault_authority_bump = ctx.bumps.pool_vault_authority;
+    let signer_seeds = &[
+        b"pool_vault_authority".as_ref(),
+        reward_pool_key.as_ref(),
+        &[vault_authority_bump],
+    ];
+
+    for ((recipient, amount), recipient_info) in recipients.iter().zip(amounts.iter()).zip(ctx.remaining_accounts.iter()) {
+        let recipient_account = Account::<TokenAccount>::try_from(recipient_info)?;
+        if recipient_account.owner != *recipient || recipient_account.mint != ctx.accounts.mint.key() {
+            return Err(GameError::TokenAccountMismatch.into());
+        }
+        if *amount == 0 {
+            continue;
+        }
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.pool_vault.to_account_info(),
+                    to: recipient_info.clone(),
+                    authority: ctx.accounts.pool_vault_authority.to_account_info(),
+                },
+                &[signer_seeds],
+            ),
+            *amount,
+        )?;
+
+        emit!(RewardDistributed {
+            reward_pool: reward_pool_key,
+            recipient: *recipient,
+            amount: *amount,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    let reward_pool = &mut ctx.accounts.reward_pool;
+    reward_pool.reentrancy_guard = ReentrancyState::NotEntered;
+
+    msg!(
+        "Distributed {} tokens to {} recipients. Remaining: {}",
+        total,
+        recipients.len(),
+        reward_pool.remaining_amount()
+    );
+
+    Ok(())
+}
+
+#[event]
+pub struct RewardDistributed {
+    pub reward_pool: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
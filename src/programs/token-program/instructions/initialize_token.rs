@@ -19,6 +19,18 @@ pub fn handler(
     token_vault.created_at = clock.unix_timestamp;
     token_vault.bump = ctx.bumps.token_vault;
     
+    // Record canonical vault addresses for later CPIs to check against
+    let vault_registry = &mut ctx.accounts.vault_registry;
+    vault_registry.mint = ctx.accounts.mint.key();
+    vault_registry.token_vault = token_vault.key();
+    vault_registry.mint_authority = ctx.accounts.mint_authority.key();
+    vault_registry.stake_vault_authority = ctx.accounts.stake_vault_authority.key();
+    vault_registry.registered_at = clock.unix_timestamp;
+    vault_registry.bump = ctx.bumps.vault_registry;
+    
+    // Read by the CPI guard on reward distribution
+    ctx.accounts.admin_config.init(ctx.accounts.authority.key(), ctx.bumps.admin_config);
+    
     // Mint initial supply if specified
     if initial_supply > 0 {
         let mint_authority_bump = ctx.bumps.mint_authority;
@@ -15,6 +15,8 @@ pub fn handler(ctx: Context<crate::MintTokens>, amount: u64) -> Result<()> {
         return Err(GameError::InvalidCombatParams.into());
     }
     
+    ctx.accounts.vault_registry.assert_mint_authority(&ctx.accounts.mint.key(), &ctx.accounts.mint_authority.key())?;
+    
     // Mint tokens using PDA authority
     let mint_authority_bump = ctx.bumps.mint_authority;
     let signer_seeds = &[
@@ -7,6 +7,7 @@ pub mod unstake_tokens;
 pub mod claim_rewards;
 pub mod create_reward_pool;
 pub mod distribute_rewards;
+pub mod program_info;
 
 pub use initialize_token::*;
 pub use mint_tokens::*;
@@ -16,4 +17,5 @@ pub use stake_tokens::*;
 pub use unstake_tokens::*;
 pub use claim_rewards::*;
 pub use create_reward_pool::*;
-pub use distribute_rewards::*;
\ No newline at end of file
+pub use distribute_rewards::*;
+pub use program_info::*;
\ No newline at end of file
@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Transfer};
 use crate::state::StakeAccount;
-use crate::shared::GameError;
+use crate::shared::{GameError, PlayerIdentity, resolve_player};
 
 pub fn handler(
     ctx: Context<crate::StakeTokens>,
@@ -19,6 +19,10 @@ pub fn handler(
     }
     
     let clock = Clock::get()?;
+    // Linked wallets stake for the identity's player, who withdraws with the cold wallet
+    let player_key = resolve_player(ctx.accounts.player_identity.as_deref(), PlayerIdentity::exists(&ctx.accounts.signer_identity), &ctx.accounts.staker.key(), clock.unix_timestamp)?;
+    
+    ctx.accounts.vault_registry.assert_stake_vault(&ctx.accounts.stake_vault, &ctx.accounts.stake_vault_authority.key())?;
     
     // Transfer tokens to stake vault
     let transfer_ctx = CpiContext::new(
@@ -33,7 +37,7 @@ pub fn handler(
     
     // Initialize stake account
     let stake_account = &mut ctx.accounts.stake_account;
-    stake_account.staker = ctx.accounts.staker.key();
+    stake_account.staker = player_key;
     stake_account.amount = amount;
     stake_account.staked_at = clock.unix_timestamp;
     stake_account.duration = duration;
@@ -43,7 +47,7 @@ pub fn handler(
     stake_account.bump = ctx.bumps.stake_account;
     
     emit!(TokensStaked {
-        staker: ctx.accounts.staker.key(),
+        staker: player_key,
         amount,
         duration,
         unlock_time: clock.unix_timestamp + duration,
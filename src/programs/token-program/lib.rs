@@ -11,7 +11,7 @@ pub use instructions::*;
 pub use state::*;
 
 // Import shared modules
-use crate::shared::{GameError};
+use crate::shared::{GameError, ReentrancyState, AdminConfig, ADMIN_CONFIG_SEED, PlayerIdentity, GAME_PROGRAM_ID, resolve_player};
 
 declare_id!("TOKExxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx");
 
@@ -81,13 +81,23 @@ pub mod sol_duel_token {
     }
 
     /// Distribute rewards from pool
-    pub fn distribute_rewards(
-        ctx: Context<DistributeRewards>,
+    pub fn distribute_rewards<'info>(
+        ctx: Context<'_, '_, '_, 'info, DistributeRewards<'info>>,
         recipients: Vec<Pubkey>,
         amounts: Vec<u64>,
     ) -> Result<()> {
         instructions::distribute_rewards::handler(ctx, recipients, amounts)
     }
+
+    /// Self-report version, commit and IDL hash after an upgrade - upgrade authority only
+    pub fn update_program_info(
+        ctx: Context<UpdateProgramInfo>,
+        version: [u16; 3],
+        git_commit: [u8; 20],
+        idl_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::program_info::handler(ctx, version, git_commit, idl_hash)
+    }
 }
 
 #[derive(Accounts)]
@@ -118,6 +128,31 @@ pub struct InitializeToken<'info> {
     )]
     pub token_vault: Account<'info, TokenVault>,
     
+    #[account(
+        init,
+        payer = authority,
+        space = VaultRegistry::LEN,
+        seeds = [b"vault_registry"],
+        bump
+    )]
+    pub vault_registry: Account<'info, VaultRegistry>,
+    
+    /// CHECK: This is the stake vault authority PDA, recorded in the registry
+    #[account(
+        seeds = [b"stake_vault_authority"],
+        bump
+    )]
+    pub stake_vault_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = AdminConfig::LEN,
+        seeds = [ADMIN_CONFIG_SEED],
+        bump
+    )]
+    pub admin_config: Account<'info, AdminConfig>,
+    
     #[account(mut)]
     pub authority: Signer<'info>,
     
@@ -144,6 +179,12 @@ pub struct MintTokens<'info> {
     )]
     pub token_vault: Account<'info, TokenVault>,
     
+    #[account(
+        seeds = [b"vault_registry"],
+        bump = vault_registry.bump
+    )]
+    pub vault_registry: Account<'info, VaultRegistry>,
+    
     #[account(
         init_if_needed,
         payer = authority,
@@ -235,7 +276,7 @@ pub struct StakeTokens<'info> {
         init,
         payer = staker,
         space = StakeAccount::LEN,
-        seeds = [b"stake", staker.key().as_ref()],
+        seeds = [b"stake", resolve_player(player_identity.as_deref(), PlayerIdentity::exists(&signer_identity), &staker.key(), Clock::get()?.unix_timestamp)?.as_ref()],
         bump
     )]
     pub stake_account: Account<'info, StakeAccount>,
@@ -255,9 +296,31 @@ pub struct StakeTokens<'info> {
     )]
     pub stake_vault_authority: UncheckedAccount<'info>,
     
+    #[account(
+        seeds = [b"vault_registry"],
+        bump = vault_registry.bump
+    )]
+    pub vault_registry: Account<'info, VaultRegistry>,
+    
     #[account(mut)]
     pub staker: Signer<'info>,
     
+    /// Linked wallets stake for the identity's player
+    #[account(
+        seeds = [b"identity", player_identity.player_key.as_ref()],
+        seeds::program = GAME_PROGRAM_ID,
+        bump = player_identity.bump
+    )]
+    pub player_identity: Option<Account<'info, PlayerIdentity>>,
+    
+    /// CHECK: The signer's own identity PDA, empty unless it is an identity's player key
+    #[account(
+        seeds = [b"identity", staker.key().as_ref()],
+        seeds::program = GAME_PROGRAM_ID,
+        bump
+    )]
+    pub signer_identity: UncheckedAccount<'info>,
+    
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
@@ -277,16 +340,27 @@ pub struct UnstakeTokens<'info> {
     
     #[account(
         mut,
-        seeds = [b"stake", staker.key().as_ref()],
+        seeds = [b"stake", stake_account.staker.as_ref()],
         bump,
         close = staker
     )]
     pub stake_account: Account<'info, StakeAccount>,
     
+    /// CHECK: Staker's identity, if any; once it exists only its cold wallet may withdraw
+    #[account(
+        seeds = [b"identity", stake_account.staker.as_ref()],
+        seeds::program = GAME_PROGRAM_ID,
+        bump,
+        constraint = PlayerIdentity::may_withdraw(&player_identity, &stake_account.staker, &staker.key())
+            @ GameError::ColdWalletRequired
+    )]
+    pub player_identity: UncheckedAccount<'info>,
+    
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = stake_vault_authority
+        associated_token::authority = stake_vault_authority,
+        constraint = stake_vault.owner == vault_registry.stake_vault_authority @ GameError::VaultMismatch
     )]
     pub stake_vault: Account<'info, TokenAccount>,
     
@@ -297,6 +371,12 @@ pub struct UnstakeTokens<'info> {
     )]
     pub stake_vault_authority: UncheckedAccount<'info>,
     
+    #[account(
+        seeds = [b"vault_registry"],
+        bump = vault_registry.bump
+    )]
+    pub vault_registry: Account<'info, VaultRegistry>,
+    
     #[account(mut)]
     pub staker: Signer<'info>,
     
@@ -317,11 +397,21 @@ pub struct ClaimRewards<'info> {
     
     #[account(
         mut,
-        seeds = [b"stake", staker.key().as_ref()],
+        seeds = [b"stake", stake_account.staker.as_ref()],
         bump
     )]
     pub stake_account: Account<'info, StakeAccount>,
     
+    /// CHECK: Staker's identity, if any; once it exists only its cold wallet may claim
+    #[account(
+        seeds = [b"identity", stake_account.staker.as_ref()],
+        seeds::program = GAME_PROGRAM_ID,
+        bump,
+        constraint = PlayerIdentity::may_withdraw(&player_identity, &stake_account.staker, &staker.key())
+            @ GameError::ColdWalletRequired
+    )]
+    pub player_identity: UncheckedAccount<'info>,
+    
     /// CHECK: This is the mint authority PDA
     #[account(
         seeds = [b"mint_authority"],
@@ -329,6 +419,12 @@ pub struct ClaimRewards<'info> {
     )]
     pub mint_authority: UncheckedAccount<'info>,
     
+    #[account(
+        seeds = [b"vault_registry"],
+        bump = vault_registry.bump
+    )]
+    pub vault_registry: Account<'info, VaultRegistry>,
+    
     #[account(mut)]
     pub staker: Signer<'info>,
     
@@ -387,7 +483,8 @@ pub struct DistributeRewards<'info> {
     #[account(
         mut,
         seeds = [b"reward_pool", reward_pool.authority.as_ref(), &reward_pool.created_at.to_le_bytes()],
-        bump = reward_pool.bump
+        bump = reward_pool.bump,
+        constraint = reward_pool.reentrancy_guard == ReentrancyState::NotEntered @ GameError::ReentrancyDetected
     )]
     pub reward_pool: Account<'info, RewardPool>,
     
@@ -411,7 +508,46 @@ pub struct DistributeRewards<'info> {
     )]
     pub authority: Signer<'info>,
     
+    #[account(
+        seeds = [ADMIN_CONFIG_SEED],
+        bump = admin_config.bump
+    )]
+    pub admin_config: Account<'info, AdminConfig>,
+    
+    #[account(
+        seeds = [b"vault_registry"],
+        bump = vault_registry.bump
+    )]
+    pub vault_registry: Account<'info, VaultRegistry>,
+    
+    /// CHECK: Instructions sysvar, read by the CPI guard
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+    
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
-}
\ No newline at end of file
+}
+
+#[derive(Accounts)]
+pub struct UpdateProgramInfo<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = crate::shared::ProgramInfo::LEN,
+        seeds = [b"program_info"],
+        bump
+    )]
+    pub program_info: Account<'info, crate::shared::ProgramInfo>,
+    
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::SolDuelToken>,
+    
+    #[account(constraint = program_data.upgrade_authority_address == Some(authority.key()) @ GameError::InvalidUpgradeAuthority)]
+    pub program_data: Account<'info, ProgramData>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
-use crate::shared::ReentrancyState;
+use anchor_spl::token::TokenAccount;
+use crate::shared::{GameError, ReentrancyState};
 
 #[account]
 pub struct TokenVault {
@@ -23,6 +24,61 @@ impl TokenVault {
         1; // bump
 }
 
+/// Canonical vault and authority addresses, recorded once at initialization.
+/// Handlers check PDA accounts against it before moving tokens, so a lookalike
+/// account that happens to satisfy a context's seeds cannot stand in for a vault.
+#[account]
+pub struct VaultRegistry {
+    pub mint: Pubkey,
+    pub token_vault: Pubkey,
+    pub mint_authority: Pubkey,
+    pub stake_vault_authority: Pubkey,
+    pub registered_at: i64,
+    pub bump: u8,
+}
+
+impl VaultRegistry {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // mint
+        32 + // token_vault
+        32 + // mint_authority
+        32 + // stake_vault_authority
+        8 + // registered_at
+        1; // bump
+
+    pub fn assert_mint_authority(&self, mint: &Pubkey, mint_authority: &Pubkey) -> Result<()> {
+        if *mint != self.mint || *mint_authority != self.mint_authority {
+            return Err(GameError::VaultMismatch.into());
+        }
+        Ok(())
+    }
+
+    pub fn assert_stake_vault(&self, stake_vault: &TokenAccount, stake_vault_authority: &Pubkey) -> Result<()> {
+        if *stake_vault_authority != self.stake_vault_authority
+            || stake_vault.owner != self.stake_vault_authority
+            || stake_vault.mint != self.mint
+        {
+            return Err(GameError::VaultMismatch.into());
+        }
+        Ok(())
+    }
+
+    /// Pool vault authorities are per pool, so they are re-derived rather than stored
+    pub fn assert_pool_vault(&self, reward_pool: &Pubkey, pool_vault: &TokenAccount, pool_vault_authority: &Pubkey) -> Result<()> {
+        let (expected, _) = Pubkey::find_program_address(
+            &[b"pool_vault_authority", reward_pool.as_ref()],
+            &crate::ID,
+        );
+        if *pool_vault_authority != expected
+            || pool_vault.owner != expected
+            || pool_vault.mint != self.mint
+        {
+            return Err(GameError::VaultMismatch.into());
+        }
+        Ok(())
+    }
+}
+
 #[account]
 pub struct StakeAccount {
     pub staker: Pubkey,
@@ -102,6 +158,8 @@ pub struct RewardPool {
     pub created_at: i64,
     pub expires_at: Option<i64>,
     pub is_active: bool,
+    // SECURITY: Held while a distribution moves funds out of the pool vault
+    pub reentrancy_guard: ReentrancyState,
     pub bump: u8,
 }
 
@@ -116,6 +174,7 @@ impl RewardPool {
         8 + // created_at
         1 + 8 + // expires_at (Option<i64>)
         1 + // is_active
+        1 + // reentrancy_guard
         1; // bump
 
     pub fn remaining_amount(&self) -> u64 {
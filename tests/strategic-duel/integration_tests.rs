@@ -42,6 +42,18 @@ async fn test_full_game_flow_with_magicblock() {
         max_bet: 10000,
         timeout_duration: 300,
         entry_fee: 1000,
+        reconnect_grace_period: 30,
+        outcome_evaluator: OutcomeEvaluator::HighCard,
+        randomness_provider: RandomnessProvider::MagicBlockVrf,
+        handicap: HandicapMode::Even,
+        blind_levels: vec![],
+        blind_escalation: BlindEscalation::None,
+        betting_structure: BettingStructure::NoLimit,
+        min_reputation: 0,
+        table_gate: TableGate::default(),
+        early_forfeit_refund_bps: 0,
+        queue_type: QueueType::Casual,
+        is_private: false,
     };
     
     create_duel(&mut context, &creator, &entity, duel_params).await.unwrap();
@@ -50,6 +62,7 @@ async fn test_full_game_flow_with_magicblock() {
     // Step 2: Join duel
     let join_params = JoinDuelParams {
         entry_fee: 1000,
+        allowlist_proof: vec![],
     };
     
     join_duel(&mut context, &joiner, &entity, join_params).await.unwrap();
@@ -295,6 +308,18 @@ async fn test_performance_benchmarks() {
             max_bet: 1000,
             timeout_duration: 300,
             entry_fee: 100,
+            reconnect_grace_period: 30,
+            outcome_evaluator: OutcomeEvaluator::HighCard,
+            randomness_provider: RandomnessProvider::MagicBlockVrf,
+            handicap: HandicapMode::Even,
+            blind_levels: vec![],
+            blind_escalation: BlindEscalation::None,
+            betting_structure: BettingStructure::NoLimit,
+            min_reputation: 0,
+            table_gate: TableGate::default(),
+            early_forfeit_refund_bps: 0,
+            queue_type: QueueType::Casual,
+            is_private: false,
         };
         create_duel(&mut context, &user, &entity, params).await.unwrap();
     }